@@ -7,25 +7,121 @@
 #include <stdint.h>
 #include <stdlib.h>
 
+/**
+ * A language cron values can be described in.
+ */
+typedef enum CronLanguage {
+  CronLanguage_English,
+  CronLanguage_French,
+  CronLanguage_German,
+  CronLanguage_Japanese,
+  CronLanguage_Spanish,
+} CronLanguage;
+
+/**
+ * The kind of error that caused a cron expression to fail to parse.
+ */
+typedef enum CronErrorCode {
+  /**
+   * The expression parsed successfully
+   */
+  CronErrorCode_Ok,
+  /**
+   * The expression string is null
+   */
+  CronErrorCode_NullInput,
+  /**
+   * The expression string is not valid UTF-8
+   */
+  CronErrorCode_InvalidUtf8,
+  /**
+   * A field contains an unexpected character or is otherwise malformed
+   */
+  CronErrorCode_InvalidField,
+  /**
+   * A value in a field is out of range
+   */
+  CronErrorCode_ValueOutOfRange,
+  /**
+   * The expression ended before all fields were read
+   */
+  CronErrorCode_MissingField,
+  /**
+   * The expression has input remaining after the last field
+   */
+  CronErrorCode_TrailingInput,
+  /**
+   * The expression starts with an `@` but isn't a known shortcut
+   */
+  CronErrorCode_UnknownShortcut,
+  /**
+   * The expression is invalid for another reason
+   */
+  CronErrorCode_Invalid,
+  /**
+   * The expression matches the same times as an earlier expression, reported by
+   * `saffron_cron_validate_many`
+   */
+  CronErrorCode_Duplicate,
+} CronErrorCode;
+
 /**
  * A cron value managed by Rust.
  *
- * Created with a UTF-8 string using `saffron_cron_parse`. Freed using `saffron_cron_free`.
+ * Created with a UTF-8 string using `saffron_cron_parse`. Copied using `saffron_cron_clone`.
+ * Freed using `saffron_cron_free`.
+ *
+ * A cron value is never modified after it's created, so it can be used from multiple threads at
+ * once as long as it isn't freed while another thread is using it.
  */
 typedef struct Cron Cron;
 
 /**
  * A future times iterator managed by Rust.
  *
- * Created with an existing cron value using `saffron_cron_iter_from` or `saffron_cron_iter_after`.
- * Freed using `saffron_cron_iter_free`.
+ * Created with an existing cron value using `saffron_cron_iter_from`, `saffron_cron_iter_after`,
+ * `saffron_cron_iter_between`, or their millisecond variants. Copied using
+ * `saffron_cron_iter_clone`. Freed using `saffron_cron_iter_free`.
+ *
+ * An iterator is modified each time it's advanced, so it must not be used from multiple threads
+ * at once. It can be moved to another thread, or cloned to give each thread its own iterator.
  */
 typedef struct CronTimesIter CronTimesIter;
 
+/**
+ * Details about why a cron expression failed to parse, filled by `saffron_cron_parse_ex`.
+ */
+typedef struct CronError {
+  /**
+   * The kind of error
+   */
+  enum CronErrorCode code;
+  /**
+   * The index of the field that failed to parse, from 0 for the minutes field to 5 for the
+   * weeks field, or -1 if the error isn't specific to a field
+   */
+  int32_t field;
+  /**
+   * The byte offset in the expression where the error occurred
+   */
+  size_t position;
+  /**
+   * A null terminated message describing the kind of error. The message is static and must
+   * not be freed.
+   */
+  const char *message;
+} CronError;
+
 #ifdef __cplusplus
 extern "C" {
 #endif // __cplusplus
 
+/**
+ * Returns a static null terminated message describing the error code. The message must not be
+ * freed.
+ */
+const char *saffron_error_message(enum CronErrorCode code);
+
 /**
  * Parses a UTF-8 string `s` with length `l` (without a null terminator) into a Cron value.
  * Returns null if:
@@ -35,19 +131,80 @@ extern "C" {
  * * `s` is not valid UTF-8,
  *
  * * `s` is not a valid cron expression,
+ *
+ * Use `saffron_cron_parse_ex` to find out why an expression failed to parse.
  */
 const struct Cron *saffron_cron_parse(const char *s, size_t l);
 
+/**
+ * Parses a UTF-8 string `s` with length `l` (without a null terminator) into a Cron value like
+ * `saffron_cron_parse`. If `err` isn't null, it's filled with the reason the expression failed to
+ * parse, or a `CronErrorCode_Ok` code if it parsed successfully.
+ */
+const struct Cron *saffron_cron_parse_ex(const char *s, size_t l, struct CronError *err);
+
+/**
+ * Parses `n` UTF-8 strings into cron values like `saffron_cron_parse`, where `s[i]` is a string
+ * with length `l[i]` (without a null terminator). The cron value of each string is written to
+ * `out[i]`, or null if it failed to parse. If `errs` isn't null, the reason each string failed to
+ * parse is written to `errs[i]`. Returns a bool indicating if every string parsed successfully.
+ *
+ * Every cron value written to `out` must be freed with `saffron_cron_free`.
+ */
+bool saffron_cron_parse_many(const char *const *s,
+                             const size_t *l,
+                             size_t n,
+                             const struct Cron **out,
+                             struct CronError *errs);
+
+/**
+ * Validates `n` UTF-8 strings, where `s[i]` is a string with length `l[i]` (without a null
+ * terminator). This checks that every string is a valid cron expression and that no two
+ * expressions match the same times. Returns a bool indicating if the strings are valid.
+ *
+ * If they aren't, the index of the first invalid string is written to `index` and the reason to
+ * `err`. If the string matches the same times as an earlier string, the error has a
+ * `CronErrorCode_Duplicate` code and the index of the earlier string is written to `other`. Any of
+ * `index`, `err`, and `other` can be null.
+ */
+bool saffron_cron_validate_many(const char *const *s,
+                                const size_t *l,
+                                size_t n,
+                                size_t *index,
+                                struct CronError *err,
+                                size_t *other);
+
 /**
  * Frees a previously created cron value.
  */
 void saffron_cron_free(const struct Cron *c);
 
+/**
+ * Returns a copy of the cron value, which must be freed separately using `saffron_cron_free`.
+ */
+const struct Cron *saffron_cron_clone(const struct Cron *c);
+
 /**
  * Returns a bool indicating if the cron value contains any matching times.
  */
 bool saffron_cron_any(const struct Cron *c);
 
+/**
+ * Returns the length in bytes of the UTF-8 description of the cron value in the given language,
+ * not including a null terminator.
+ */
+size_t saffron_cron_describe_len(const struct Cron *c, enum CronLanguage lang);
+
+/**
+ * Writes the UTF-8 description of the cron value in the given language into `buf`, followed by a
+ * null terminator. Returns a bool indicating if the description was written, which requires `len`
+ * to be at least `saffron_cron_describe_len` + 1 bytes. Nothing is written if `buf` is too small.
+ */
+bool saffron_cron_describe(const struct Cron *c,
+                           enum CronLanguage lang,
+                           char *buf,
+                           size_t len);
+
 /**
  * Returns a bool indicating if the cron value contains the given time in UTC non-leap seconds
  * since January 1st, 1970, 00:00:00.
@@ -56,6 +213,14 @@ bool saffron_cron_any(const struct Cron *c);
  */
 bool saffron_cron_contains(const struct Cron *c, int64_t s);
 
+/**
+ * Returns a bool indicating if the cron value contains the given time in UTC non-leap milliseconds
+ * since January 1st, 1970, 00:00:00.
+ *
+ * The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+ */
+bool saffron_cron_contains_ms(const struct Cron *c, int64_t ms);
+
 /**
  * Gets the next matching time in the cron value starting from the given time in UTC non-leap
  * seconds `s`. Returns a bool indicating if a next time exists, inserting the new timestamp into `s`.
@@ -65,6 +230,15 @@ bool saffron_cron_contains(const struct Cron *c, int64_t s);
 bool saffron_cron_next_from(const struct Cron *c,
                             int64_t *s);
 
+/**
+ * Gets the next matching time in the cron value starting from the given time in UTC non-leap
+ * milliseconds `ms`. Returns a bool indicating if a next time exists, inserting the new timestamp
+ * into `ms`.
+ *
+ * The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+ */
+bool saffron_cron_next_from_ms(const struct Cron *c, int64_t *ms);
+
 /**
  * Gets the next matching time in the cron value after the given time in UTC non-leap seconds `s`.
  * Returns a bool indicating if a next time exists, inserting the new timestamp into `s`.
@@ -73,6 +247,25 @@ bool saffron_cron_next_from(const struct Cron *c,
  */
 bool saffron_cron_next_after(const struct Cron *c, int64_t *s);
 
+/**
+ * Gets the next matching time in the cron value after the given time in UTC non-leap
+ * milliseconds `ms`. Returns a bool indicating if a next time exists, inserting the new timestamp
+ * into `ms`.
+ *
+ * The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+ */
+bool saffron_cron_next_after_ms(const struct Cron *c, int64_t *ms);
+
+/**
+ * Gets the earliest next matching time of `n` cron values starting from the given time in UTC
+ * non-leap seconds `s`. Null cron values are skipped, so the output of `saffron_cron_parse_many`
+ * can be passed as is. Returns a bool indicating if any cron value has a next time, inserting the
+ * earliest one into `s`.
+ *
+ * The valid range for `s` is -8334632851200 <= `s` <= 8210298412799.
+ */
+bool saffron_cron_next_of_many(const struct Cron *const *c, size_t n, int64_t *s);
+
 /**
  * Returns an iterator of future times starting from the specified timestamp `s` in UTC non-leap
  * seconds, or null if `s` is out of range of valid values.
@@ -81,6 +274,14 @@ bool saffron_cron_next_after(const struct Cron *c, int64_t *s);
  */
 struct CronTimesIter *saffron_cron_iter_from(const struct Cron *c, int64_t s);
 
+/**
+ * Returns an iterator of future times starting from the specified timestamp `ms` in UTC non-leap
+ * milliseconds, or null if `ms` is out of range of valid values.
+ *
+ * The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+ */
+struct CronTimesIter *saffron_cron_iter_from_ms(const struct Cron *c, int64_t ms);
+
 /**
  * Returns an iterator of future times starting after the specified timestamp `s` in UTC non-leap
  * seconds, or null if `s` is out of range of valid values.
@@ -89,12 +290,50 @@ struct CronTimesIter *saffron_cron_iter_from(const struct Cron *c, int64_t s);
  */
 struct CronTimesIter *saffron_cron_iter_after(const struct Cron *c, int64_t s);
 
+/**
+ * Returns an iterator of future times starting after the specified timestamp `ms` in UTC non-leap
+ * milliseconds, or null if `ms` is out of range of valid values.
+ *
+ * The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+ */
+struct CronTimesIter *saffron_cron_iter_after_ms(const struct Cron *c, int64_t ms);
+
+/**
+ * Returns an iterator of the times from the timestamp `start` up to but not including the
+ * timestamp `end` in UTC non-leap seconds, or null if either is out of range of valid values.
+ *
+ * The valid range for `start` and `end` is -8334632851200 <= `t` <= 8210298412799.
+ */
+struct CronTimesIter *saffron_cron_iter_between(const struct Cron *c, int64_t start, int64_t end);
+
+/**
+ * Returns an iterator of the times from the timestamp `start` up to but not including the
+ * timestamp `end` in UTC non-leap milliseconds, or null if either is out of range of valid values.
+ *
+ * The valid range for `start` and `end` is -8334632851200000 <= `t` <= 8210298412799999.
+ */
+struct CronTimesIter *saffron_cron_iter_between_ms(const struct Cron *c,
+                                                   int64_t start,
+                                                   int64_t end);
+
 /**
  * Gets the next timestamp in an cron times iterator, writing it to `s`. Returns a bool indicating
  * if a next time was written to `s`.
  */
 bool saffron_cron_iter_next(struct CronTimesIter *c, int64_t *s);
 
+/**
+ * Gets the next timestamp in an cron times iterator in UTC non-leap milliseconds, writing it to
+ * `ms`. Returns a bool indicating if a next time was written to `ms`.
+ */
+bool saffron_cron_iter_next_ms(struct CronTimesIter *c, int64_t *ms);
+
+/**
+ * Returns a copy of the cron times iterator at its current position, which must be freed
+ * separately using `saffron_cron_iter_free`.
+ */
+struct CronTimesIter *saffron_cron_iter_clone(const struct CronTimesIter *c);
+
 /**
  * Frees a previously created cron times iterator value.
  */
@@ -2,19 +2,48 @@
 
 use chrono::prelude::*;
 use libc::{c_char, size_t};
+use saffron::parse::{English, FieldKind, French, German, Japanese, Spanish};
 use std::ptr;
 
 /// A cron value managed by Rust.
 ///
-/// Created with a UTF-8 string using `saffron_cron_parse`. Freed using `saffron_cron_free`.
+/// Created with a UTF-8 string using `saffron_cron_parse`. Copied using `saffron_cron_clone`.
+/// Freed using `saffron_cron_free`.
+///
+/// A cron value is never modified after it's created, so it can be used from multiple threads at
+/// once as long as it isn't freed while another thread is using it.
 pub struct Cron(saffron::Cron);
 
 /// A future times iterator managed by Rust.
 ///
-/// Created with an existing cron value using `saffron_cron_iter_from` or `saffron_cron_iter_after`.
-/// Freed using `saffron_cron_iter_free`.
+/// Created with an existing cron value using `saffron_cron_iter_from`, `saffron_cron_iter_after`,
+/// `saffron_cron_iter_between`, or their millisecond variants. Copied using
+/// `saffron_cron_iter_clone`. Freed using `saffron_cron_iter_free`.
+///
+/// An iterator is modified each time it's advanced, so it must not be used from multiple threads
+/// at once. It can be moved to another thread, or cloned to give each thread its own iterator.
 pub struct CronTimesIter(saffron::CronTimesIter);
 
+// Checks the thread safety guarantees documented above
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    fn assert_send<T: Send>() {}
+
+    assert_send_sync::<Cron>();
+    assert_send::<CronTimesIter>();
+};
+
+/// A language cron values can be described in.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum CronLanguage {
+    English,
+    French,
+    German,
+    Japanese,
+    Spanish,
+}
+
 fn box_it<T>(val: T) -> *mut T {
     Box::into_raw(val.into())
 }
@@ -23,6 +52,102 @@ unsafe fn rebox_it<T>(ptr: *mut T) -> Box<T> {
     Box::from_raw(ptr)
 }
 
+/// The kind of error that caused a cron expression to fail to parse.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CronErrorCode {
+    /// The expression parsed successfully
+    Ok,
+    /// The expression string is null
+    NullInput,
+    /// The expression string is not valid UTF-8
+    InvalidUtf8,
+    /// A field contains an unexpected character or is otherwise malformed
+    InvalidField,
+    /// A value in a field is out of range
+    ValueOutOfRange,
+    /// The expression ended before all fields were read
+    MissingField,
+    /// The expression has input remaining after the last field
+    TrailingInput,
+    /// The expression starts with an `@` but isn't a known shortcut
+    UnknownShortcut,
+    /// The expression is invalid for another reason
+    Invalid,
+    /// The expression matches the same times as an earlier expression, reported by
+    /// `saffron_cron_validate_many`
+    Duplicate,
+}
+
+/// Details about why a cron expression failed to parse, filled by `saffron_cron_parse_ex`.
+#[repr(C)]
+pub struct CronError {
+    /// The kind of error
+    pub code: CronErrorCode,
+    /// The index of the field that failed to parse, from 0 for the minutes field to 5 for the
+    /// weeks field, or -1 if the error isn't specific to a field
+    pub field: i32,
+    /// The byte offset in the expression where the error occurred
+    pub position: size_t,
+    /// A null terminated message describing the kind of error. The message is static and must
+    /// not be freed.
+    pub message: *const c_char,
+}
+
+impl CronError {
+    fn new(code: CronErrorCode) -> Self {
+        CronError {
+            code,
+            field: -1,
+            position: 0,
+            message: saffron_error_message(code),
+        }
+    }
+
+    fn from_parse_error(err: &saffron::parse::CronParseError) -> Self {
+        use saffron::parse::CronParseError::*;
+
+        let code = match err {
+            InvalidField { .. } => CronErrorCode::InvalidField,
+            ValueOutOfRange { .. } => CronErrorCode::ValueOutOfRange,
+            MissingField { .. } => CronErrorCode::MissingField,
+            TrailingInput { .. } => CronErrorCode::TrailingInput,
+            UnknownShortcut => CronErrorCode::UnknownShortcut,
+            _ => CronErrorCode::Invalid,
+        };
+        let field = err
+            .field()
+            .and_then(|field| FieldKind::ALL.iter().position(|&kind| kind == field))
+            .map_or(-1, |i| i as i32);
+        CronError {
+            field,
+            position: err.position(),
+            ..CronError::new(code)
+        }
+    }
+}
+
+/// Returns a static null terminated message describing the error code. The message must not be
+/// freed.
+#[no_mangle]
+pub extern "C" fn saffron_error_message(code: CronErrorCode) -> *const c_char {
+    let message: &'static [u8] = match code {
+        CronErrorCode::Ok => b"No error\0",
+        CronErrorCode::NullInput => b"The expression is null\0",
+        CronErrorCode::InvalidUtf8 => b"The expression is not valid UTF-8\0",
+        CronErrorCode::InvalidField => b"A field contains an unexpected character\0",
+        CronErrorCode::ValueOutOfRange => b"A value in a field is out of range\0",
+        CronErrorCode::MissingField => b"The expression is missing a field\0",
+        CronErrorCode::TrailingInput => b"The expression has unexpected input after its fields\0",
+        CronErrorCode::UnknownShortcut => b"Unknown shortcut expression\0",
+        CronErrorCode::Invalid => b"Invalid cron expression\0",
+        CronErrorCode::Duplicate => {
+            b"The expression matches the same times as another expression\0"
+        }
+    };
+    message.as_ptr() as *const c_char
+}
+
 /// Parses a UTF-8 string `s` with length `l` (without a null terminator) into a Cron value.
 /// Returns null if:
 ///
@@ -31,22 +156,115 @@ unsafe fn rebox_it<T>(ptr: *mut T) -> Box<T> {
 /// * `s` is not valid UTF-8,
 ///
 /// * `s` is not a valid cron expression,
+///
+/// Use `saffron_cron_parse_ex` to find out why an expression failed to parse.
 #[no_mangle]
 pub unsafe extern "C" fn saffron_cron_parse(s: *const c_char, l: size_t) -> *const Cron {
+    saffron_cron_parse_ex(s, l, ptr::null_mut())
+}
+
+/// Parses a UTF-8 string `s` with length `l` (without a null terminator) into a Cron value like
+/// `saffron_cron_parse`. If `err` isn't null, it's filled with the reason the expression failed to
+/// parse, or a `CronErrorCode_Ok` code if it parsed successfully.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_parse_ex(
+    s: *const c_char,
+    l: size_t,
+    err: *mut CronError,
+) -> *const Cron {
+    let (cron, error) = match parse(s, l) {
+        Ok(cron) => (box_it(Cron(cron)) as _, CronError::new(CronErrorCode::Ok)),
+        Err(error) => (ptr::null(), error),
+    };
+    if !err.is_null() {
+        *err = error;
+    }
+    cron
+}
+
+unsafe fn parse(s: *const c_char, l: size_t) -> Result<saffron::Cron, CronError> {
     if s.is_null() {
-        return ptr::null();
+        return Err(CronError::new(CronErrorCode::NullInput));
     }
 
     let slice = std::slice::from_raw_parts(s as *const u8, l);
-    let string = match std::str::from_utf8(slice) {
-        Ok(s) => s,
-        Err(_) => return ptr::null(),
-    };
+    let string =
+        std::str::from_utf8(slice).map_err(|_| CronError::new(CronErrorCode::InvalidUtf8))?;
+    string
+        .parse()
+        .map_err(|err| CronError::from_parse_error(&err))
+}
+
+/// Parses `n` UTF-8 strings into cron values like `saffron_cron_parse`, where `s[i]` is a string
+/// with length `l[i]` (without a null terminator). The cron value of each string is written to
+/// `out[i]`, or null if it failed to parse. If `errs` isn't null, the reason each string failed to
+/// parse is written to `errs[i]`. Returns a bool indicating if every string parsed successfully.
+///
+/// Every cron value written to `out` must be freed with `saffron_cron_free`.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_parse_many(
+    s: *const *const c_char,
+    l: *const size_t,
+    n: size_t,
+    out: *mut *const Cron,
+    errs: *mut CronError,
+) -> bool {
+    let mut all = true;
+    for i in 0..n {
+        let err = if errs.is_null() {
+            ptr::null_mut()
+        } else {
+            errs.add(i)
+        };
+        let cron = saffron_cron_parse_ex(*s.add(i), *l.add(i), err);
+        all &= !cron.is_null();
+        *out.add(i) = cron;
+    }
+    all
+}
+
+/// Validates `n` UTF-8 strings, where `s[i]` is a string with length `l[i]` (without a null
+/// terminator). This checks that every string is a valid cron expression and that no two
+/// expressions match the same times. Returns a bool indicating if the strings are valid.
+///
+/// If they aren't, the index of the first invalid string is written to `index` and the reason to
+/// `err`. If the string matches the same times as an earlier string, the error has a
+/// `CronErrorCode_Duplicate` code and the index of the earlier string is written to `other`. Any of
+/// `index`, `err`, and `other` can be null.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_validate_many(
+    s: *const *const c_char,
+    l: *const size_t,
+    n: size_t,
+    index: *mut size_t,
+    err: *mut CronError,
+    other: *mut size_t,
+) -> bool {
+    let mut parsed: Vec<saffron::Cron> = Vec::with_capacity(n);
+    for i in 0..n {
+        let (error, earlier) = match parse(*s.add(i), *l.add(i)) {
+            Ok(cron) => match parsed.iter().position(|old| old.equivalent_to(&cron)) {
+                Some(earlier) => (CronError::new(CronErrorCode::Duplicate), earlier),
+                None => {
+                    parsed.push(cron);
+                    continue;
+                }
+            },
+            Err(error) => (error, i),
+        };
 
-    match string.parse() {
-        Ok(cron) => box_it(Cron(cron)) as _,
-        Err(_) => ptr::null(),
+        if !index.is_null() {
+            *index = i;
+        }
+        if !err.is_null() {
+            *err = error;
+        }
+        if !other.is_null() && earlier != i {
+            *other = earlier;
+        }
+        return false;
     }
+    true
 }
 
 /// Frees a previously created cron value.
@@ -55,12 +273,56 @@ pub unsafe extern "C" fn saffron_cron_free(c: *const Cron) {
     drop(rebox_it(c as *mut Cron))
 }
 
+/// Returns a copy of the cron value, which must be freed separately using `saffron_cron_free`.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_clone(c: *const Cron) -> *const Cron {
+    box_it(Cron((*c).0.clone()))
+}
+
 /// Returns a bool indicating if the cron value contains any matching times.
 #[no_mangle]
 pub unsafe extern "C" fn saffron_cron_any(c: *const Cron) -> bool {
     (*c).0.any()
 }
 
+/// Describes the cron value in the given language
+fn describe(cron: &saffron::Cron, lang: CronLanguage) -> String {
+    match lang {
+        CronLanguage::English => cron.describe(English::default()).to_string(),
+        CronLanguage::French => cron.describe(French::default()).to_string(),
+        CronLanguage::German => cron.describe(German::default()).to_string(),
+        CronLanguage::Japanese => cron.describe(Japanese::default()).to_string(),
+        CronLanguage::Spanish => cron.describe(Spanish::default()).to_string(),
+    }
+}
+
+/// Returns the length in bytes of the UTF-8 description of the cron value in the given language,
+/// not including a null terminator.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_describe_len(c: *const Cron, lang: CronLanguage) -> size_t {
+    describe(&(*c).0, lang).len()
+}
+
+/// Writes the UTF-8 description of the cron value in the given language into `buf`, followed by a
+/// null terminator. Returns a bool indicating if the description was written, which requires `len`
+/// to be at least `saffron_cron_describe_len` + 1 bytes. Nothing is written if `buf` is too small.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_describe(
+    c: *const Cron,
+    lang: CronLanguage,
+    buf: *mut c_char,
+    len: size_t,
+) -> bool {
+    let description = describe(&(*c).0, lang);
+    if buf.is_null() || description.len() >= len {
+        return false;
+    }
+
+    ptr::copy_nonoverlapping(description.as_ptr(), buf as *mut u8, description.len());
+    *buf.add(description.len()) = 0;
+    true
+}
+
 /// Returns a bool indicating if the cron value contains the given time in UTC non-leap seconds
 /// since January 1st, 1970, 00:00:00.
 ///
@@ -75,6 +337,20 @@ pub unsafe extern "C" fn saffron_cron_contains(c: *const Cron, s: i64) -> bool {
     }
 }
 
+/// Returns a bool indicating if the cron value contains the given time in UTC non-leap milliseconds
+/// since January 1st, 1970, 00:00:00.
+///
+/// The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_contains_ms(c: *const Cron, ms: i64) -> bool {
+    let cron = &*c;
+    if let Some(time) = Utc.timestamp_millis_opt(ms).single() {
+        cron.0.contains(time)
+    } else {
+        false
+    }
+}
+
 /// Gets the next matching time in the cron value starting from the given time in UTC non-leap
 /// seconds `s`. Returns a bool indicating if a next time exists, inserting the new timestamp into `s`.
 ///
@@ -94,6 +370,26 @@ pub unsafe extern "C" fn saffron_cron_next_from(c: *const Cron, s: *mut i64) ->
     }
 }
 
+/// Gets the next matching time in the cron value starting from the given time in UTC non-leap
+/// milliseconds `ms`. Returns a bool indicating if a next time exists, inserting the new timestamp
+/// into `ms`.
+///
+/// The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_next_from_ms(c: *const Cron, ms: *mut i64) -> bool {
+    let cron = &*c;
+    if let Some(time) = Utc
+        .timestamp_millis_opt(*ms)
+        .single()
+        .and_then(|time| cron.0.next_from(time))
+    {
+        *ms = time.timestamp_millis();
+        true
+    } else {
+        false
+    }
+}
+
 /// Gets the next matching time in the cron value after the given time in UTC non-leap seconds `s`.
 /// Returns a bool indicating if a next time exists, inserting the new timestamp into `s`.
 ///
@@ -113,6 +409,56 @@ pub unsafe extern "C" fn saffron_cron_next_after(c: *const Cron, s: *mut i64) ->
     }
 }
 
+/// Gets the next matching time in the cron value after the given time in UTC non-leap
+/// milliseconds `ms`. Returns a bool indicating if a next time exists, inserting the new timestamp
+/// into `ms`.
+///
+/// The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_next_after_ms(c: *const Cron, ms: *mut i64) -> bool {
+    let cron = &*c;
+    if let Some(time) = Utc
+        .timestamp_millis_opt(*ms)
+        .single()
+        .and_then(|time| cron.0.next_after(time))
+    {
+        *ms = time.timestamp_millis();
+        true
+    } else {
+        false
+    }
+}
+
+/// Gets the earliest next matching time of `n` cron values starting from the given time in UTC
+/// non-leap seconds `s`. Null cron values are skipped, so the output of `saffron_cron_parse_many`
+/// can be passed as is. Returns a bool indicating if any cron value has a next time, inserting the
+/// earliest one into `s`.
+///
+/// The valid range for `s` is -8334632851200 <= `s` <= 8210298412799.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_next_of_many(
+    c: *const *const Cron,
+    n: size_t,
+    s: *mut i64,
+) -> bool {
+    let start = match Utc.timestamp_opt(*s, 0).single() {
+        Some(start) => start,
+        None => return false,
+    };
+
+    let next = (0..n)
+        .map(|i| *c.add(i))
+        .filter(|cron| !cron.is_null())
+        .filter_map(|cron| (*cron).0.next_from(start))
+        .min();
+    if let Some(time) = next {
+        *s = time.timestamp();
+        true
+    } else {
+        false
+    }
+}
+
 /// Returns an iterator of future times starting from the specified timestamp `s` in UTC non-leap
 /// seconds, or null if `s` is out of range of valid values.
 ///
@@ -127,6 +473,20 @@ pub unsafe extern "C" fn saffron_cron_iter_from(c: *const Cron, s: i64) -> *mut
     }
 }
 
+/// Returns an iterator of future times starting from the specified timestamp `ms` in UTC non-leap
+/// milliseconds, or null if `ms` is out of range of valid values.
+///
+/// The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_iter_from_ms(c: *const Cron, ms: i64) -> *mut CronTimesIter {
+    let cron = &*c;
+    if let Some(time) = Utc.timestamp_millis_opt(ms).single() {
+        box_it(CronTimesIter(cron.0.clone().iter_from(time)))
+    } else {
+        ptr::null_mut()
+    }
+}
+
 /// Returns an iterator of future times starting after the specified timestamp `s` in UTC non-leap
 /// seconds, or null if `s` is out of range of valid values.
 ///
@@ -141,6 +501,60 @@ pub unsafe extern "C" fn saffron_cron_iter_after(c: *const Cron, s: i64) -> *mut
     }
 }
 
+/// Returns an iterator of future times starting after the specified timestamp `ms` in UTC non-leap
+/// milliseconds, or null if `ms` is out of range of valid values.
+///
+/// The valid range for `ms` is -8334632851200000 <= `ms` <= 8210298412799999.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_iter_after_ms(c: *const Cron, ms: i64) -> *mut CronTimesIter {
+    let cron = &*c;
+    if let Some(time) = Utc.timestamp_millis_opt(ms).single() {
+        box_it(CronTimesIter(cron.0.clone().iter_after(time)))
+    } else {
+        ptr::null_mut()
+    }
+}
+
+/// Returns an iterator of the times from the timestamp `start` up to but not including the
+/// timestamp `end` in UTC non-leap seconds, or null if either is out of range of valid values.
+///
+/// The valid range for `start` and `end` is -8334632851200 <= `t` <= 8210298412799.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_iter_between(
+    c: *const Cron,
+    start: i64,
+    end: i64,
+) -> *mut CronTimesIter {
+    let cron = &*c;
+    match (
+        Utc.timestamp_opt(start, 0).single(),
+        Utc.timestamp_opt(end, 0).single(),
+    ) {
+        (Some(start), Some(end)) => box_it(CronTimesIter(cron.0.clone().iter(start..end))),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Returns an iterator of the times from the timestamp `start` up to but not including the
+/// timestamp `end` in UTC non-leap milliseconds, or null if either is out of range of valid values.
+///
+/// The valid range for `start` and `end` is -8334632851200000 <= `t` <= 8210298412799999.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_iter_between_ms(
+    c: *const Cron,
+    start: i64,
+    end: i64,
+) -> *mut CronTimesIter {
+    let cron = &*c;
+    match (
+        Utc.timestamp_millis_opt(start).single(),
+        Utc.timestamp_millis_opt(end).single(),
+    ) {
+        (Some(start), Some(end)) => box_it(CronTimesIter(cron.0.clone().iter(start..end))),
+        _ => ptr::null_mut(),
+    }
+}
+
 /// Gets the next timestamp in an cron times iterator, writing it to `s`. Returns a bool indicating
 /// if a next time was written to `s`.
 #[no_mangle]
@@ -154,8 +568,368 @@ pub unsafe extern "C" fn saffron_cron_iter_next(c: *mut CronTimesIter, s: *mut i
     }
 }
 
+/// Gets the next timestamp in an cron times iterator in UTC non-leap milliseconds, writing it to
+/// `ms`. Returns a bool indicating if a next time was written to `ms`.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_iter_next_ms(c: *mut CronTimesIter, ms: *mut i64) -> bool {
+    match (*c).0.next() {
+        Some(time) => {
+            *ms = time.timestamp_millis();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns a copy of the cron times iterator at its current position, which must be freed
+/// separately using `saffron_cron_iter_free`.
+#[no_mangle]
+pub unsafe extern "C" fn saffron_cron_iter_clone(c: *const CronTimesIter) -> *mut CronTimesIter {
+    box_it(CronTimesIter((*c).0.clone()))
+}
+
 /// Frees a previously created cron times iterator value.
 #[no_mangle]
 pub unsafe extern "C" fn saffron_cron_iter_free(c: *mut CronTimesIter) {
     drop(rebox_it(c))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn parse_str(s: &str) -> *const Cron {
+        unsafe { saffron_cron_parse(s.as_ptr() as *const c_char, s.len()) }
+    }
+
+    fn parse_ex_str(s: &[u8]) -> (*const Cron, CronError) {
+        let mut err = CronError::new(CronErrorCode::Invalid);
+        let cron = unsafe { saffron_cron_parse_ex(s.as_ptr() as *const c_char, s.len(), &mut err) };
+        (cron, err)
+    }
+
+    fn message(code: CronErrorCode) -> &'static str {
+        unsafe { CStr::from_ptr(saffron_error_message(code)) }
+            .to_str()
+            .unwrap()
+    }
+
+    fn describe_str(cron: *const Cron, lang: CronLanguage) -> String {
+        unsafe {
+            let len = saffron_cron_describe_len(cron, lang);
+            let mut buf = vec![0 as c_char; len + 1];
+            assert!(saffron_cron_describe(
+                cron,
+                lang,
+                buf.as_mut_ptr(),
+                buf.len()
+            ));
+            CStr::from_ptr(buf.as_ptr()).to_str().unwrap().to_string()
+        }
+    }
+
+    /// Returns the times left in an iterator in seconds, freeing it
+    fn drain(iter: *mut CronTimesIter) -> Vec<i64> {
+        assert!(!iter.is_null());
+        let mut times = Vec::new();
+        let mut s = 0;
+        unsafe {
+            while saffron_cron_iter_next(iter, &mut s) {
+                times.push(s);
+            }
+            saffron_cron_iter_free(iter);
+        }
+        times
+    }
+
+    // 2020-12-01T00:00:00Z
+    const START: i64 = 1_606_780_800;
+    const DAY: i64 = 24 * 60 * 60;
+
+    #[test]
+    fn describes_in_every_language() {
+        let cron = parse_str("30 18 * * MON");
+        assert_eq!(
+            describe_str(cron, CronLanguage::English),
+            "At 6:30 PM on Monday"
+        );
+        assert_eq!(
+            describe_str(cron, CronLanguage::German),
+            "Um 18:30 am Montag"
+        );
+        for lang in [
+            CronLanguage::French,
+            CronLanguage::Japanese,
+            CronLanguage::Spanish,
+        ] {
+            assert!(!describe_str(cron, lang).is_empty());
+        }
+        unsafe { saffron_cron_free(cron) };
+    }
+
+    #[test]
+    fn describe_needs_room_for_the_terminator() {
+        let cron = parse_str("* * * * *");
+        unsafe {
+            let len = saffron_cron_describe_len(cron, CronLanguage::English);
+            let mut buf = vec![1 as c_char; len + 1];
+            assert!(!saffron_cron_describe(
+                cron,
+                CronLanguage::English,
+                buf.as_mut_ptr(),
+                len
+            ));
+            assert!(buf.iter().all(|&c| c == 1));
+            assert!(!saffron_cron_describe(
+                cron,
+                CronLanguage::English,
+                ptr::null_mut(),
+                len + 1
+            ));
+            saffron_cron_free(cron);
+        }
+    }
+
+    #[test]
+    fn parse_ex_reports_why_parsing_failed() {
+        let (cron, err) = parse_ex_str(b"0 0 * * MON");
+        assert!(!cron.is_null());
+        assert!(err.code == CronErrorCode::Ok);
+        unsafe { saffron_cron_free(cron) };
+
+        let (cron, err) = parse_ex_str(b"0 0 * *");
+        assert!(cron.is_null());
+        assert!(err.code == CronErrorCode::MissingField);
+        assert_eq!(
+            err.message,
+            saffron_error_message(CronErrorCode::MissingField)
+        );
+
+        let (cron, err) = parse_ex_str(b"0 25 * * *");
+        assert!(cron.is_null());
+        assert!(err.code == CronErrorCode::ValueOutOfRange);
+        assert_eq!(err.field, 1);
+
+        let (cron, err) = parse_ex_str(b"0 0 * * \xFF");
+        assert!(cron.is_null());
+        assert!(err.code == CronErrorCode::InvalidUtf8);
+
+        let mut err = CronError::new(CronErrorCode::Ok);
+        let cron = unsafe { saffron_cron_parse_ex(ptr::null(), 0, &mut err) };
+        assert!(cron.is_null());
+        assert!(err.code == CronErrorCode::NullInput);
+
+        let s = "invalid";
+        let cron =
+            unsafe { saffron_cron_parse_ex(s.as_ptr() as *const c_char, s.len(), ptr::null_mut()) };
+        assert!(cron.is_null());
+    }
+
+    #[test]
+    fn every_error_code_has_a_message() {
+        for code in [
+            CronErrorCode::Ok,
+            CronErrorCode::NullInput,
+            CronErrorCode::InvalidUtf8,
+            CronErrorCode::InvalidField,
+            CronErrorCode::ValueOutOfRange,
+            CronErrorCode::MissingField,
+            CronErrorCode::TrailingInput,
+            CronErrorCode::UnknownShortcut,
+            CronErrorCode::Invalid,
+            CronErrorCode::Duplicate,
+        ] {
+            assert!(!message(code).is_empty());
+        }
+    }
+
+    #[test]
+    fn parses_many() {
+        let exprs = ["0 0 * * *", "invalid", "*/5 * * * *"];
+        let s: Vec<*const c_char> = exprs.iter().map(|s| s.as_ptr() as _).collect();
+        let l: Vec<size_t> = exprs.iter().map(|s| s.len()).collect();
+        let mut out = [ptr::null(); 3];
+        let mut errs: Vec<CronError> = (0..3).map(|_| CronError::new(CronErrorCode::Ok)).collect();
+        unsafe {
+            assert!(!saffron_cron_parse_many(
+                s.as_ptr(),
+                l.as_ptr(),
+                3,
+                out.as_mut_ptr(),
+                errs.as_mut_ptr()
+            ));
+            assert!(!out[0].is_null() && out[1].is_null() && !out[2].is_null());
+            assert!(errs[0].code == CronErrorCode::Ok);
+            assert!(errs[1].code == CronErrorCode::InvalidField);
+
+            // with null errors and a null string
+            let s = [s[0], ptr::null()];
+            let mut out2 = [ptr::null(); 2];
+            assert!(!saffron_cron_parse_many(
+                s.as_ptr(),
+                l.as_ptr(),
+                2,
+                out2.as_mut_ptr(),
+                ptr::null_mut()
+            ));
+            assert!(!out2[0].is_null() && out2[1].is_null());
+
+            let mut next = START;
+            assert!(saffron_cron_next_of_many(out.as_ptr(), 3, &mut next));
+            assert_eq!(next, START);
+            let mut next = START + 60;
+            assert!(saffron_cron_next_of_many(out.as_ptr(), 3, &mut next));
+            assert_eq!(next, START + 5 * 60);
+            let nulls = [ptr::null(); 2];
+            assert!(!saffron_cron_next_of_many(nulls.as_ptr(), 2, &mut next));
+            assert!(!saffron_cron_next_of_many(out.as_ptr(), 0, &mut next));
+            let mut out_of_range = i64::MAX;
+            assert!(!saffron_cron_next_of_many(
+                out.as_ptr(),
+                3,
+                &mut out_of_range
+            ));
+
+            for cron in out.iter().chain(&out2).filter(|cron| !cron.is_null()) {
+                saffron_cron_free(*cron);
+            }
+        }
+    }
+
+    /// Validates the expressions, returning the index, error code, and duplicated index
+    fn validate(exprs: &[&str]) -> Option<(size_t, CronErrorCode, Option<size_t>)> {
+        let s: Vec<*const c_char> = exprs.iter().map(|s| s.as_ptr() as _).collect();
+        let l: Vec<size_t> = exprs.iter().map(|s| s.len()).collect();
+        let mut index = 0;
+        let mut err = CronError::new(CronErrorCode::Ok);
+        let mut other = size_t::MAX;
+        let valid = unsafe {
+            saffron_cron_validate_many(
+                s.as_ptr(),
+                l.as_ptr(),
+                exprs.len(),
+                &mut index,
+                &mut err,
+                &mut other,
+            )
+        };
+        if valid {
+            None
+        } else {
+            Some((index, err.code, Some(other).filter(|&o| o != size_t::MAX)))
+        }
+    }
+
+    #[test]
+    fn validates_many() {
+        assert!(validate(&[]).is_none());
+        assert!(validate(&["0 0 * * *", "*/5 * * * *"]).is_none());
+        assert!(
+            validate(&["0 0 * * *", "invalid"]) == Some((1, CronErrorCode::InvalidField, None))
+        );
+        assert!(
+            validate(&["0 0 * * MON", "0 0 * * 2", "invalid"])
+                == Some((1, CronErrorCode::Duplicate, Some(0)))
+        );
+
+        // with null outputs and a null string
+        let s = [ptr::null()];
+        let l = [0];
+        assert!(!unsafe {
+            saffron_cron_validate_many(
+                s.as_ptr(),
+                l.as_ptr(),
+                1,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        });
+    }
+
+    #[test]
+    fn clones_cron_values() {
+        let cron = parse_str("0 0 * * *");
+        unsafe {
+            let clone = saffron_cron_clone(cron);
+            saffron_cron_free(cron);
+            assert!(saffron_cron_any(clone));
+            assert!(saffron_cron_contains(clone, START));
+            saffron_cron_free(clone);
+        }
+    }
+
+    #[test]
+    fn handles_milliseconds() {
+        let cron = parse_str("0 0 * * *");
+        let start_ms = START * 1000;
+        unsafe {
+            assert!(saffron_cron_contains_ms(cron, start_ms + 999));
+            assert!(!saffron_cron_contains_ms(cron, start_ms + 60_000));
+            assert!(!saffron_cron_contains_ms(cron, i64::MAX));
+
+            let mut ms = start_ms;
+            assert!(saffron_cron_next_from_ms(cron, &mut ms));
+            assert_eq!(ms, start_ms);
+            assert!(saffron_cron_next_after_ms(cron, &mut ms));
+            assert_eq!(ms, start_ms + DAY * 1000);
+            let mut ms = i64::MAX;
+            assert!(!saffron_cron_next_from_ms(cron, &mut ms));
+            assert!(!saffron_cron_next_after_ms(cron, &mut ms));
+            assert_eq!(ms, i64::MAX);
+
+            let iter = saffron_cron_iter_from_ms(cron, start_ms);
+            let mut ms = 0;
+            assert!(saffron_cron_iter_next_ms(iter, &mut ms));
+            assert_eq!(ms, start_ms);
+            saffron_cron_iter_free(iter);
+            let iter = saffron_cron_iter_after_ms(cron, start_ms);
+            assert!(saffron_cron_iter_next_ms(iter, &mut ms));
+            assert_eq!(ms, start_ms + DAY * 1000);
+            saffron_cron_iter_free(iter);
+            assert!(saffron_cron_iter_from_ms(cron, i64::MAX).is_null());
+            assert!(saffron_cron_iter_after_ms(cron, i64::MAX).is_null());
+
+            saffron_cron_free(cron);
+        }
+    }
+
+    #[test]
+    fn iterates_between_times() {
+        let cron = parse_str("0 0 * * *");
+        unsafe {
+            assert_eq!(
+                drain(saffron_cron_iter_between(cron, START, START + 2 * DAY)),
+                [START, START + DAY]
+            );
+            assert_eq!(drain(saffron_cron_iter_between(cron, START, START)), []);
+            assert!(saffron_cron_iter_between(cron, START, i64::MAX).is_null());
+            assert!(saffron_cron_iter_between(cron, i64::MIN, START).is_null());
+
+            let iter = saffron_cron_iter_between_ms(cron, START * 1000, (START + 2 * DAY) * 1000);
+            assert_eq!(drain(iter), [START, START + DAY]);
+            assert!(saffron_cron_iter_between_ms(cron, START * 1000, i64::MAX).is_null());
+
+            saffron_cron_free(cron);
+        }
+    }
+
+    #[test]
+    fn clones_iterators_at_their_position() {
+        let cron = parse_str("0 0 * * *");
+        unsafe {
+            let iter = saffron_cron_iter_from(cron, START);
+            let mut s = 0;
+            assert!(saffron_cron_iter_next(iter, &mut s));
+            let clone = saffron_cron_iter_clone(iter);
+            assert!(saffron_cron_iter_next(iter, &mut s));
+            assert_eq!(s, START + DAY);
+            assert!(saffron_cron_iter_next(clone, &mut s));
+            assert_eq!(s, START + DAY);
+            saffron_cron_iter_free(iter);
+            saffron_cron_iter_free(clone);
+            saffron_cron_free(cron);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Differential tests comparing the times saffron matches against the `cron` and `croner` crates.
+//!
+//! Each case is generated from a seed: a random expression using only the syntax every crate
+//! reads the same way (values, ranges, steps, and lists, with at most one of the day fields
+//! restricted) and a random start time. The next times after the start are compared, and any
+//! difference is reported with the seed so the case can be run again on its own.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// The times compared after the start of each case
+const TIMES: usize = 5;
+
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A xorshift generator, so a seed always generates the same case
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a value in `min..=max`
+    fn range(&mut self, min: u32, max: u32) -> u32 {
+        min + (self.next() % u64::from(max - min + 1)) as u32
+    }
+
+    /// Returns true one in `n` times
+    fn one_in(&mut self, n: u32) -> bool {
+        self.range(1, n) == 1
+    }
+}
+
+/// Generates a field with values in `min..=max`, written with `name` for each value. Returns
+/// `None` for a `*`. Only numbered fields have steps over a range or ranges of one value, since
+/// the crates don't all read those the same way for names.
+fn field(
+    rng: &mut Rng,
+    min: u32,
+    max: u32,
+    name: Option<&dyn Fn(u32) -> String>,
+) -> Option<String> {
+    let number = |value: u32| value.to_string();
+    let named = name.is_some();
+    let name = name.unwrap_or(&number);
+    if rng.one_in(3) {
+        return None;
+    }
+    let terms = (0..rng.range(1, 3))
+        .map(|_| match rng.range(0, 3) {
+            0 | 1 => name(rng.range(min, max)),
+            // croner reads a range of one name, like `SUN-SUN`, as every day
+            2 if named => {
+                let start = rng.range(min, max - 1);
+                format!("{}-{}", name(start), name(rng.range(start + 1, max)))
+            }
+            2 => {
+                let start = rng.range(min, max);
+                format!("{}-{}", name(start), name(rng.range(start, max)))
+            }
+            _ => {
+                let step = rng.range(2, (max - min).max(2));
+                if rng.one_in(2) || named {
+                    format!("*/{}", step)
+                } else {
+                    let start = rng.range(min, max);
+                    let end = rng.range(start, max);
+                    format!("{}-{}/{}", name(start), name(end), step)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    Some(terms.join(","))
+}
+
+/// A generated expression in the five standard fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    /// The seed the case was generated from
+    pub seed: u64,
+    /// The expression, in the syntax read by saffron and croner
+    pub expr: String,
+    /// The time to find the next times after
+    pub start: DateTime<Utc>,
+}
+
+impl Case {
+    /// Generates the case for a seed
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let day_name = |value: u32| DAY_NAMES[value as usize].to_string();
+
+        let minutes = field(&mut rng, 0, 59, None);
+        let hours = field(&mut rng, 0, 23, None);
+        let months = field(&mut rng, 1, 12, None);
+        // the crates combine the day fields differently when both are restricted
+        let (doms, dows) = if rng.one_in(2) {
+            (field(&mut rng, 1, 31, None), None)
+        } else {
+            (None, field(&mut rng, 0, 6, Some(&day_name)))
+        };
+        let star = |field: Option<String>| field.unwrap_or_else(|| "*".to_string());
+        let expr = format!(
+            "{} {} {} {} {}",
+            star(minutes),
+            star(hours),
+            star(doms),
+            star(months),
+            star(dows)
+        );
+
+        // any minute from 1970 to 2100
+        let minutes = u64::from(rng.range(0, 130 * 365 * 24 * 60));
+        let start = Utc.timestamp_opt(0, 0).unwrap() + Duration::minutes(minutes as i64);
+        Case { seed, expr, start }
+    }
+
+    /// Returns the next times after the start matched by saffron
+    pub fn saffron(&self) -> Result<Vec<DateTime<Utc>>, String> {
+        let cron: saffron::Cron = self.expr.parse().map_err(|err| format!("{}", err))?;
+        Ok(cron.iter_after(self.start).take(TIMES).collect())
+    }
+
+    /// Returns the next times after the start matched by the `cron` crate, which reads a
+    /// seconds field first
+    pub fn cron(&self) -> Result<Vec<DateTime<Utc>>, String> {
+        let schedule = cron::Schedule::from_str(&format!("0 {}", self.expr))
+            .map_err(|err| format!("{:?}", err))?;
+        Ok(schedule.after(&self.start).take(TIMES).collect())
+    }
+
+    /// Returns the next times after the start matched by the `croner` crate
+    pub fn croner(&self) -> Result<Vec<DateTime<Utc>>, String> {
+        let cron = croner::Cron::new(&self.expr)
+            .parse()
+            .map_err(|err| format!("{:?}", err))?;
+        let mut times = Vec::with_capacity(TIMES);
+        let mut time = self.start;
+        while times.len() < TIMES {
+            match cron.find_next_occurrence(&time, false) {
+                Ok(next) => {
+                    times.push(next);
+                    time = next;
+                }
+                Err(_) => break,
+            }
+        }
+        Ok(times)
+    }
+
+    /// Compares the times matched by saffron against the other crates, returning each
+    /// difference
+    pub fn check(&self) -> Vec<Divergence> {
+        let saffron = self.saffron();
+        [("cron", self.cron()), ("croner", self.croner())]
+            .iter()
+            .filter(|(_, other)| *other != saffron)
+            .map(|(name, other)| Divergence {
+                case: self.clone(),
+                other: name,
+                saffron: saffron.clone(),
+                times: other.clone(),
+            })
+            .collect()
+    }
+}
+
+/// A case where saffron and another crate match different times
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// The case the crates differ in
+    pub case: Case,
+    /// The name of the other crate
+    pub other: &'static str,
+    /// The times matched by saffron, or the error parsing the expression
+    pub saffron: Result<Vec<DateTime<Utc>>, String>,
+    /// The times matched by the other crate, or the error parsing the expression
+    pub times: Result<Vec<DateTime<Utc>>, String>,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "seed {}: `{}` after {} differs from {}",
+            self.case.seed, self.case.expr, self.case.start, self.other
+        )?;
+        writeln!(f, "  saffron: {:?}", self.saffron)?;
+        write!(f, "  {}: {:?}", self.other, self.times)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cases_are_reproducible() {
+        assert_eq!(Case::new(42), Case::new(42));
+        assert_ne!(Case::new(42), Case::new(43));
+    }
+
+    #[test]
+    fn agrees_with_other_crates() {
+        let divergences = (1..=2000)
+            .flat_map(|seed| Case::new(seed).check())
+            .collect::<Vec<_>>();
+        for divergence in &divergences {
+            println!("{}", divergence);
+        }
+        assert!(divergences.is_empty(), "{} divergences", divergences.len());
+    }
+}
@@ -0,0 +1,38 @@
+//! Runs the differential tests for a range of seeds, printing every divergence.
+
+use saffron_difftest::Case;
+use std::process;
+
+const USAGE: &str = "\
+Usage: saffron-difftest [count] [first seed]
+
+Checks `count` cases (default 10000) starting from the given seed (default 1). A divergence
+can be checked again on its own with a count of 1 and its seed.";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let number = |i: usize, default: u64| match args.get(i) {
+        Some(arg) => arg.parse::<u64>().map_err(|_| arg.clone()),
+        None => Ok(default),
+    };
+    let (count, first) = match (number(0, 10_000), number(1, 1)) {
+        (Ok(count), Ok(first)) if args.len() <= 2 => (count, first),
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let mut divergences = 0;
+    for seed in first..first.saturating_add(count) {
+        for divergence in Case::new(seed).check() {
+            println!("{}", divergence);
+            divergences += 1;
+        }
+    }
+
+    println!("{} cases, {} divergences", count, divergences);
+    if divergences > 0 {
+        process::exit(1);
+    }
+}
@@ -1,5 +1,6 @@
 /**
- * An iterator over all matching dates for a cron value starting at or after a specific date.
+ * An iterator over all matching dates for a cron value starting at or after a specific date, and
+ * optionally ending before another date.
  */
 export class CronTimesIter {
     /**
@@ -14,6 +15,19 @@ export class CronTimesIter {
         value: Date | undefined,
         done: boolean
     };
+    /**
+     * Takes up to the next `n` matching times in the cron times iterator in one call.
+     * @param {number} n The max number of times to take
+     * @returns {Date[]} The next matching times, which has less than `n` times if the iterator ends
+     */
+    collect(n: number): Date[];
+    /**
+     * Takes every matching time in the cron times iterator before the given date in one call. The
+     * iterator continues from the first time at or after the date.
+     * @param {Date} date The date to stop at
+     * @returns {Date[]} The matching times before the date
+     */
+    takeUntil(date: Date): Date[];
     /**
      * Returns this instance.
      * @returns {CronTimesIter}
@@ -21,6 +35,54 @@ export class CronTimesIter {
     [Symbol.iterator](): CronTimesIter;
 }
 
+/**
+ * Options for the descriptions returned by `Cron.parseAndDescribe` and `Cron.describeSegments`.
+ */
+export interface DescribeOptions {
+    /**
+     * The locale to describe the expression in, like "en-US" or "de"
+     */
+    locale?: string;
+    /**
+     * Whether descriptions use a 12 hour clock. Defaults to true for English and false for
+     * the other languages.
+     */
+    hour12?: boolean;
+}
+
+/**
+ * A piece of a description returned by `Cron.describeSegments`.
+ */
+export interface DescriptionSegment {
+    /**
+     * The part of the expression the text describes, or "text" for the words joining the other
+     * segments
+     */
+    kind: "text" | "time" | "dayOfMonth" | "dayOfWeek" | "month" | "week";
+    /**
+     * The text of the segment, including any spaces before it
+     */
+    text: string;
+}
+
+/**
+ * The result of validating one expression with `Cron.validate`.
+ */
+export type ValidationResult =
+    | { ok: true }
+    | {
+        ok: false,
+        /**
+         * Why the expression is invalid
+         */
+        error: string,
+        /**
+         * The index of an earlier expression matching the same times, if the expression is a
+         * duplicate
+         */
+        duplicateOf?: number
+    };
+
 /**
  * A parsed cron value. This can be used to check if a time matches the cron value or get an iterator
  * of all future times.
@@ -35,12 +97,38 @@ export default class Cron {
     constructor(s: string);
     /**
      * Parses a cron expression into a cron value and string description.
+     *
+     * The description is written in the language of the `locale` option, which is one of "en",
+     * "de", "es", "fr", or "ja" and may include a region like "en-US". Other locales are described
+     * in English. The `hour12` option picks between a 12 hour and 24 hour clock for every
+     * language, and defaults to `true` for English and `false` for the other languages.
      * 
      * @param {string} s The string value to parse
+     * @param {DescribeOptions} [options] The options of the description
      * @returns {[Cron, string]} A cron value and a string description
-     * @throws If the string is not a valid cron expression
+     * @throws If the string is not a valid cron expression or an option has the wrong type
      */
-    static parseAndDescribe(s: string): [Cron, string];
+    static parseAndDescribe(s: string, options?: DescribeOptions): [Cron, string];
+    /**
+     * Describes a cron expression as an array of segments, each holding the text describing one
+     * part of the expression, so the parts can be styled or linked separately. Joining the text of
+     * every segment gives the description returned by `parseAndDescribe`.
+     *
+     * @param {string} s The string value to describe
+     * @param {DescribeOptions} [options] The options of the description
+     * @returns {DescriptionSegment[]} The segments of the description
+     * @throws If the string is not a valid cron expression or an option has the wrong type
+     */
+    static describeSegments(s: string, options?: DescribeOptions): DescriptionSegment[];
+    /**
+     * Validates every expression in an array, checking that each one parses and that no two
+     * expressions match the same times. Unlike the validation of the Cloudflare API, this doesn't
+     * stop at the first invalid expression, so every invalid expression can be shown at once.
+     *
+     * @param {string[]} crons The expressions to validate
+     * @returns {ValidationResult[]} The result of each expression
+     */
+    static validate(crons: string[]): ValidationResult[];
     /**
      * Frees the underlying wasm memory associated with this object.
      */
@@ -96,4 +184,11 @@ export default class Cron {
      * @returns {CronTimesIter} An iterator of all times starting after the specified date
      */
     iterAfter(date: Date): CronTimesIter;
+    /**
+     * Returns an iterator of all times starting at the start date and ending before the end date.
+     * @param {Date} start The date to start the iterator from
+     * @param {Date} end The date to end the iterator before
+     * @returns {CronTimesIter} An iterator of all times from the start date until the end date
+     */
+    iterBetween(start: Date, end: Date): CronTimesIter;
 }
\ No newline at end of file
@@ -2,7 +2,8 @@ import * as _ from "./saffron_bg.wasm"; // unused because the wasm/js story suck
 import { WasmCron, WasmCronTimesIter } from "./saffron_bg.js";
 
 /**
- * An iterator over all matching dates for a cron value starting at or after a specific date.
+ * An iterator over all matching dates for a cron value starting at or after a specific date, and
+ * optionally ending before another date.
  */
 export class CronTimesIter {
   /** @private */
@@ -35,6 +36,25 @@ export class CronTimesIter {
     };
   }
 
+  /**
+   * Takes up to the next `n` matching times in the cron times iterator in one call.
+   * @param {number} n The max number of times to take
+   * @returns {Date[]} The next matching times, which has less than `n` times if the iterator ends
+   */
+  collect(n) {
+    return this.iter.collect(n);
+  }
+
+  /**
+   * Takes every matching time in the cron times iterator before the given date in one call. The
+   * iterator continues from the first time at or after the date.
+   * @param {Date} date The date to stop at
+   * @returns {Date[]} The matching times before the date
+   */
+  takeUntil(date) {
+    return this.iter.takeUntil(date);
+  }
+
   /**
    * Returns this instance.
    * @returns {CronTimesIter}
@@ -61,13 +81,19 @@ export default class Cron {
 
   /**
    * Parses a cron expression into a cron value and string description.
+   *
+   * The description is written in the language of the `locale` option, which is one of "en",
+   * "de", "es", "fr", or "ja" and may include a region like "en-US". Other locales are described
+   * in English. The `hour12` option picks between a 12 hour and 24 hour clock for every
+   * language, and defaults to `true` for English and `false` for the other languages.
    * 
    * @param {string} s The string value to parse
+   * @param {{ locale?: string, hour12?: boolean }} [options] The options of the description
    * @returns {[Cron, string]} A cron value and a string description
-   * @throws If the string is not a valid cron expression
+   * @throws If the string is not a valid cron expression or an option has the wrong type
    */
-  static parseAndDescribe(s) {
-    let [cron, description] = WasmCron.parseAndDescribe(s);
+  static parseAndDescribe(s, options) {
+    let [cron, description] = WasmCron.parseAndDescribe(s, options);
 
     const obj = Object.create(Cron.prototype);
     obj.value = cron;
@@ -75,6 +101,37 @@ export default class Cron {
     return [obj, description];
   }
 
+  /**
+   * Describes a cron expression as an array of segments, each holding the text describing one
+   * part of the expression, so the parts can be styled or linked separately. Joining the text of
+   * every segment gives the description returned by `parseAndDescribe`.
+   *
+   * The `kind` of a segment is one of "time", "dayOfMonth", "dayOfWeek", "month", "week", or
+   * "text" for the words joining the other segments.
+   *
+   * @param {string} s The string value to describe
+   * @param {{ locale?: string, hour12?: boolean }} [options] The options of the description
+   * @returns {{ kind: string, text: string }[]} The segments of the description
+   * @throws If the string is not a valid cron expression or an option has the wrong type
+   */
+  static describeSegments(s, options) {
+    return WasmCron.describeSegments(s, options);
+  }
+
+  /**
+   * Validates every expression in an array, checking that each one parses and that no two
+   * expressions match the same times. Unlike the validation of the Cloudflare API, this doesn't
+   * stop at the first invalid expression, so every invalid expression can be shown at once.
+   *
+   * @param {string[]} crons The expressions to validate
+   * @returns {({ ok: true } | { ok: false, error: string, duplicateOf?: number })[]} The result
+   * of each expression, where `duplicateOf` is the index of an earlier expression matching the same
+   * times
+   */
+  static validate(crons) {
+    return WasmCron.validateMany(crons);
+  }
+
   /**
    * Frees the underlying wasm memory associated with this object.
    */
@@ -155,4 +212,15 @@ export default class Cron {
     const iter = WasmCronTimesIter.startAfter(this.value, date);
     return CronTimesIter.__wrap(iter);
   }
+
+  /**
+   * Returns an iterator of all times starting at the start date and ending before the end date.
+   * @param {Date} start The date to start the iterator from
+   * @param {Date} end The date to end the iterator before
+   * @returns {CronTimesIter} An iterator of all times from the start date until the end date
+   */
+  iterBetween(start, end) {
+    const iter = WasmCronTimesIter.between(this.value, start, end);
+    return CronTimesIter.__wrap(iter);
+  }
 }
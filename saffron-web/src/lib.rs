@@ -1,6 +1,9 @@
 use chrono::prelude::*;
-use js_sys::{Array as JsArray, Date as JsDate, JsString};
-use saffron::parse::{CronExpr, English};
+use js_sys::{Array as JsArray, Date as JsDate, JsString, Object, Reflect};
+use saffron::parse::{
+    CronExpr, DescriptionSegment, English, French, German, HourFormat, Japanese, Language,
+    SegmentKind, Spanish,
+};
 use saffron::{Cron, CronTimesIter};
 use wasm_bindgen::prelude::*;
 
@@ -9,6 +12,131 @@ fn chrono_to_js_date(date: DateTime<Utc>) -> JsDate {
     JsDate::new(&js_millis)
 }
 
+/// The options of a description, read from an object like `{ locale: "de", hour12: false }`
+struct DescribeOptions {
+    /// The primary language subtag of the locale, like "en" for "en-US"
+    locale: String,
+    /// Whether to use a 12 hour clock, or the locale's usual clock if unset
+    hour12: Option<bool>,
+}
+
+impl DescribeOptions {
+    fn from_js(options: &JsValue) -> Result<Self, JsValue> {
+        let mut parsed = DescribeOptions {
+            locale: "en".to_string(),
+            hour12: None,
+        };
+        if options.is_undefined() || options.is_null() {
+            return Ok(parsed);
+        }
+
+        let locale = Reflect::get(options, &"locale".into())?;
+        if let Some(locale) = locale.as_string() {
+            let language = locale.split(['-', '_']).next().unwrap_or("");
+            parsed.locale = language.to_ascii_lowercase();
+        } else if !locale.is_undefined() {
+            return Err(JsString::from("The locale option must be a string").into());
+        }
+
+        let hour12 = Reflect::get(options, &"hour12".into())?;
+        if let Some(hour12) = hour12.as_bool() {
+            parsed.hour12 = Some(hour12);
+        } else if !hour12.is_undefined() {
+            return Err(JsString::from("The hour12 option must be a boolean").into());
+        }
+        Ok(parsed)
+    }
+
+    /// Returns the language of the locale, falling back to English for locales without a
+    /// translation. English uses a 12 hour clock unless `hour12` is false, and the other
+    /// languages use a 24 hour clock unless it's true.
+    fn language(&self) -> Box<dyn Language> {
+        let hour = |default| match self.hour12 {
+            Some(true) => HourFormat::Hour12,
+            Some(false) => HourFormat::Hour24,
+            None => default,
+        };
+        match self.locale.as_str() {
+            "de" => {
+                let mut german = German::default();
+                german.hour = hour(HourFormat::Hour24);
+                Box::new(german)
+            }
+            "es" => {
+                let mut spanish = Spanish::default();
+                spanish.hour = hour(HourFormat::Hour24);
+                Box::new(spanish)
+            }
+            "fr" => {
+                let mut french = French::default();
+                french.hour = hour(HourFormat::Hour24);
+                Box::new(french)
+            }
+            "ja" => {
+                let mut japanese = Japanese::default();
+                japanese.hour = hour(HourFormat::Hour24);
+                Box::new(japanese)
+            }
+            _ => {
+                let mut english = English::default();
+                english.hour = hour(HourFormat::Hour12);
+                Box::new(english)
+            }
+        }
+    }
+
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        self.language().segments(expr)
+    }
+
+    fn describe(&self, expr: &CronExpr) -> String {
+        self.segments(expr)
+            .into_iter()
+            .map(|segment| segment.text)
+            .collect()
+    }
+}
+
+/// The name of a segment kind in the JS API
+fn segment_kind_name(kind: SegmentKind) -> &'static str {
+    match kind {
+        SegmentKind::Time => "time",
+        SegmentKind::DaysOfMonth => "dayOfMonth",
+        SegmentKind::DaysOfWeek => "dayOfWeek",
+        SegmentKind::Months => "month",
+        SegmentKind::Weeks => "week",
+        _ => "text",
+    }
+}
+
+/// Checks one expression for `validateMany`, keeping it if it's valid so later expressions can be
+/// checked for duplicates of it. Errors include the index of the duplicated expression, if any.
+fn validate_one(
+    value: JsValue,
+    i: u32,
+    parsed: &mut Vec<(u32, Cron, String)>,
+) -> Result<(), (String, Option<u32>)> {
+    let string = value
+        .as_string()
+        .ok_or_else(|| (format!("Element '{}' is not a string", i), None))?;
+    let cron = string
+        .parse::<Cron>()
+        .map_err(|err| (err.to_string(), None))?;
+
+    if let Some((j, _, old_str)) = parsed.iter().find(|(_, old, _)| old.equivalent_to(&cron)) {
+        return Err((
+            format!(
+                "Expression '{}' already exists in the form of '{}'",
+                string, old_str
+            ),
+            Some(*j),
+        ));
+    }
+
+    parsed.push((i, cron, string));
+    Ok(())
+}
+
 /// @private
 #[wasm_bindgen]
 #[derive(Clone, Debug)]
@@ -26,10 +154,11 @@ impl WasmCron {
     }
 
     #[wasm_bindgen(js_name = parseAndDescribe)]
-    pub fn parse_and_describe(s: &str) -> Result<JsArray, JsValue> {
+    pub fn parse_and_describe(s: &str, options: JsValue) -> Result<JsArray, JsValue> {
+        let options = DescribeOptions::from_js(&options)?;
         s.parse()
             .map(move |expr: CronExpr| {
-                let description = expr.describe(English::default()).to_string();
+                let description = options.describe(&expr);
                 let cron = Self {
                     inner: Cron::new(expr),
                 };
@@ -42,6 +171,51 @@ impl WasmCron {
             .map_err(|e| JsString::from(e.to_string()).into())
     }
 
+    /// Describes an expression as an array of `{ kind, text }` segments, where joining the text of
+    /// every segment gives the description returned by `parseAndDescribe`.
+    #[wasm_bindgen(js_name = describeSegments)]
+    pub fn describe_segments(s: &str, options: JsValue) -> Result<JsArray, JsValue> {
+        let options = DescribeOptions::from_js(&options)?;
+        let expr = s
+            .parse::<CronExpr>()
+            .map_err(|e| JsValue::from(JsString::from(e.to_string())))?;
+
+        let segments = JsArray::new();
+        for segment in options.segments(&expr) {
+            let object = Object::new();
+            Reflect::set(
+                &object,
+                &"kind".into(),
+                &segment_kind_name(segment.kind).into(),
+            )?;
+            Reflect::set(&object, &"text".into(), &segment.text.into())?;
+            segments.push(&object);
+        }
+        Ok(segments)
+    }
+
+    /// Validates every expression in the array, returning a result object for each of them.
+    /// Unlike the API's validation, this doesn't stop at the first invalid expression.
+    #[wasm_bindgen(js_name = validateMany)]
+    pub fn validate_many(crons: JsArray) -> Result<JsArray, JsValue> {
+        let results = JsArray::new();
+        let mut parsed: Vec<(u32, Cron, String)> = Vec::new();
+        for i in 0..crons.length() {
+            let result = Object::new();
+            let error = validate_one(crons.get(i), i, &mut parsed).err();
+
+            Reflect::set(&result, &"ok".into(), &error.is_none().into())?;
+            if let Some((error, duplicate_of)) = error {
+                Reflect::set(&result, &"error".into(), &error.into())?;
+                if let Some(j) = duplicate_of {
+                    Reflect::set(&result, &"duplicateOf".into(), &j.into())?;
+                }
+            }
+            results.push(&result);
+        }
+        Ok(results)
+    }
+
     pub fn any(&self) -> bool {
         self.inner.any()
     }
@@ -70,26 +244,69 @@ impl WasmCron {
 #[wasm_bindgen]
 pub struct WasmCronTimesIter {
     inner: CronTimesIter,
+    /// A time taken from the inner iterator by `takeUntil` that wasn't returned yet
+    peeked: Option<DateTime<Utc>>,
 }
 
 #[wasm_bindgen]
 impl WasmCronTimesIter {
-    #[wasm_bindgen(js_name = startFrom)]
-    pub fn start_from(cron: &WasmCron, date: JsDate) -> Self {
+    fn new(inner: CronTimesIter) -> Self {
         Self {
-            inner: cron.inner.clone().iter_from(date.into()),
+            inner,
+            peeked: None,
         }
     }
 
+    #[wasm_bindgen(js_name = startFrom)]
+    pub fn start_from(cron: &WasmCron, date: JsDate) -> Self {
+        Self::new(cron.inner.clone().iter_from(date.into()))
+    }
+
     #[wasm_bindgen(js_name = startAfter)]
     pub fn start_after(cron: &WasmCron, date: JsDate) -> Self {
-        Self {
-            inner: cron.inner.clone().iter_after(date.into()),
-        }
+        Self::new(cron.inner.clone().iter_after(date.into()))
+    }
+
+    pub fn between(cron: &WasmCron, start: JsDate, end: JsDate) -> Self {
+        let start: DateTime<Utc> = start.into();
+        let end: DateTime<Utc> = end.into();
+        Self::new(cron.inner.clone().iter(start..end))
     }
 
     #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<JsDate> {
-        self.inner.next().map(chrono_to_js_date)
+        self.next_time().map(chrono_to_js_date)
+    }
+
+    /// Takes the next `n` times at most
+    pub fn collect(&mut self, n: u32) -> JsArray {
+        let array = JsArray::new();
+        for _ in 0..n {
+            if let Some(time) = self.next_time() {
+                array.push(&chrono_to_js_date(time));
+            } else {
+                break;
+            }
+        }
+        array
+    }
+
+    /// Takes every time before the given date
+    #[wasm_bindgen(js_name = takeUntil)]
+    pub fn take_until(&mut self, date: JsDate) -> JsArray {
+        let end: DateTime<Utc> = date.into();
+        let array = JsArray::new();
+        while let Some(time) = self.next_time() {
+            if time >= end {
+                self.peeked = Some(time);
+                break;
+            }
+            array.push(&chrono_to_js_date(time));
+        }
+        array
+    }
+
+    fn next_time(&mut self) -> Option<DateTime<Utc>> {
+        self.peeked.take().or_else(|| self.inner.next())
     }
 }
@@ -20,6 +20,48 @@ it("parses and describes * * * * *", () => {
   }
 })
 
+it("describes in the given locale and clock", () => {
+  let descriptions = [
+    [{ hour12: false }, "At 18:30 on Monday"],
+    [{ locale: "en-US" }, "At 6:30 PM on Monday"],
+    [{ locale: "de" }, "Um 18:30 am Montag"],
+    [{ locale: "FR-ca" }, "À 18:30 le lundi"],
+    [{ locale: "xx" }, "At 6:30 PM on Monday"],
+    [{ locale: "es", hour12: true }, "A las 6:30 p. m. el lunes"],
+    [{ locale: "ja", hour12: true }, "毎週月曜日の午後6:30"],
+  ];
+  for (const [options, expected] of descriptions) {
+    let [cron, description] = Cron.parseAndDescribe("30 18 * * MON", options);
+    cron.free();
+    expect(description).toBe(expected);
+  }
+
+  expect(() => Cron.parseAndDescribe("* * * * *", { hour12: "no" })).toThrow();
+})
+
+it("describes each part of an expression", () => {
+  expect(Cron.describeSegments("0 9 1 * MON")).toStrictEqual([
+    { kind: "time", text: "At 9:00 AM" },
+    { kind: "dayOfMonth", text: " on the 1st" },
+    { kind: "text", text: " and" },
+    { kind: "dayOfWeek", text: " on Monday" },
+    { kind: "month", text: " of every month" },
+  ]);
+  expect(Cron.describeSegments("@daily")).toStrictEqual([
+    { kind: "text", text: "Every day at midnight" },
+  ]);
+  expect(() => Cron.describeSegments("invalid")).toThrow();
+})
+
+it("validates every expression", () => {
+  let results = Cron.validate(["0 0 * * *", "invalid", "0 * * * *", "0 0 * * ?", 5]);
+  expect(results.map(result => result.ok)).toStrictEqual([true, false, true, false, false]);
+  expect(results[1].duplicateOf).toBeUndefined();
+  expect(results[3].duplicateOf).toBe(0);
+  expect(results[3].error).toBe("Expression '0 0 * * ?' already exists in the form of '0 0 * * *'");
+  expect(results[4].error).toBe("Element '4' is not a string");
+})
+
 it("throws on invalid cron", () => {
   expect(() => new Cron("invalid")).toThrow();
 })
@@ -109,3 +151,47 @@ it("iterates from the next 5 minutes", () => {
     new Date("2020-12-01T00:04:00Z"),
   ])
 })
+
+it("iterates between two dates", () => {
+  let cron = new Cron("0 */6 * * *");
+  let iter = cron.iterBetween(startDate, new Date("2020-12-02T00:00:00Z"));
+  let arr;
+  try {
+    arr = [...iter];
+  } finally {
+    iter.free();
+    cron.free();
+  }
+
+  expect(arr).toStrictEqual([
+    new Date("2020-12-01T00:00:00Z"),
+    new Date("2020-12-01T06:00:00Z"),
+    new Date("2020-12-01T12:00:00Z"),
+    new Date("2020-12-01T18:00:00Z"),
+  ])
+})
+
+it("collects the next 3 times, then every time until a date", () => {
+  let cron = new Cron("*/10 * * * *");
+  let iter = cron.iterFrom(startDate);
+  let first, second, next;
+  try {
+    first = iter.collect(3);
+    second = iter.takeUntil(new Date("2020-12-01T00:50:00Z"));
+    next = iter.next().value;
+  } finally {
+    iter.free();
+    cron.free();
+  }
+
+  expect(first).toStrictEqual([
+    new Date("2020-12-01T00:00:00Z"),
+    new Date("2020-12-01T00:10:00Z"),
+    new Date("2020-12-01T00:20:00Z"),
+  ])
+  expect(second).toStrictEqual([
+    new Date("2020-12-01T00:30:00Z"),
+    new Date("2020-12-01T00:40:00Z"),
+  ])
+  expect(next).toStrictEqual(new Date("2020-12-01T00:50:00Z"))
+})
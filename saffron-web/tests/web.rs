@@ -0,0 +1,202 @@
+//! Tests for the exports behind the JS wrapper in `pkg`, run with `wasm-pack test --node` or
+//! `cargo test` with `wasm-bindgen-test-runner` as the runner. The wrapper itself is tested with
+//! jest in `saffron.test.js`.
+
+use js_sys::{Array as JsArray, Date as JsDate, Object, Reflect};
+use saffron_web::{WasmCron, WasmCronTimesIter};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn date(text: &str) -> JsDate {
+    JsDate::new(&text.into())
+}
+
+/// Returns the dates in an array as ISO strings
+fn iso_strings(dates: JsArray) -> Vec<String> {
+    dates
+        .iter()
+        .map(|date| String::from(JsDate::from(date).to_iso_string()))
+        .collect()
+}
+
+/// Builds an options object from pairs of keys and values
+fn options(pairs: &[(&str, JsValue)]) -> JsValue {
+    let object = Object::new();
+    for (key, value) in pairs {
+        Reflect::set(&object, &(*key).into(), value).unwrap();
+    }
+    object.into()
+}
+
+fn describe(cron: &str, options: JsValue) -> Result<String, String> {
+    WasmCron::parse_and_describe(cron, options)
+        .map(|array| array.get(1).as_string().unwrap())
+        .map_err(|err| err.as_string().unwrap())
+}
+
+#[wasm_bindgen_test]
+fn describes_in_english_by_default() {
+    assert_eq!(
+        describe("30 18 * * MON", JsValue::UNDEFINED),
+        Ok("At 6:30 PM on Monday".to_string())
+    );
+    assert_eq!(
+        describe("30 18 * * MON", JsValue::NULL),
+        Ok("At 6:30 PM on Monday".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn describes_in_the_given_locale_and_clock() {
+    let cases = [
+        (options(&[("hour12", false.into())]), "At 18:30 on Monday"),
+        (
+            options(&[("locale", "en-US".into())]),
+            "At 6:30 PM on Monday",
+        ),
+        (options(&[("locale", "de".into())]), "Um 18:30 am Montag"),
+        (options(&[("locale", "FR_ca".into())]), "À 18:30 le lundi"),
+        (options(&[("locale", "xx".into())]), "At 6:30 PM on Monday"),
+        (
+            options(&[("locale", "es".into()), ("hour12", true.into())]),
+            "A las 6:30 p. m. el lunes",
+        ),
+    ];
+    for (options, expected) in cases {
+        assert_eq!(describe("30 18 * * MON", options), Ok(expected.to_string()));
+    }
+}
+
+#[wasm_bindgen_test]
+fn rejects_invalid_options() {
+    assert!(describe("* * * * *", options(&[("locale", 1.into())])).is_err());
+    assert!(describe("* * * * *", options(&[("hour12", "yes".into())])).is_err());
+}
+
+#[wasm_bindgen_test]
+fn iterates_between_two_dates() {
+    let cron = WasmCron::new("0 0 * * *").unwrap();
+    let mut iter = WasmCronTimesIter::between(
+        &cron,
+        date("2020-12-01T00:00:00Z"),
+        date("2020-12-03T00:00:00Z"),
+    );
+    assert_eq!(
+        iso_strings(iter.collect(5)),
+        ["2020-12-01T00:00:00.000Z", "2020-12-02T00:00:00.000Z"]
+    );
+    assert!(iter.next().is_none());
+}
+
+#[wasm_bindgen_test]
+fn collects_at_most_n_times() {
+    let cron = WasmCron::new("0 0 * * *").unwrap();
+    let mut iter = WasmCronTimesIter::start_from(&cron, date("2020-12-01T00:00:00Z"));
+    assert_eq!(iso_strings(iter.collect(0)), Vec::<String>::new());
+    assert_eq!(
+        iso_strings(iter.collect(2)),
+        ["2020-12-01T00:00:00.000Z", "2020-12-02T00:00:00.000Z"]
+    );
+    assert_eq!(
+        iter.next().map(|date| String::from(date.to_iso_string())),
+        Some("2020-12-03T00:00:00.000Z".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn takes_until_a_date_without_skipping_the_next_time() {
+    let cron = WasmCron::new("0 0 * * *").unwrap();
+    let mut iter = WasmCronTimesIter::start_after(&cron, date("2020-12-01T00:00:00Z"));
+    assert_eq!(
+        iso_strings(iter.take_until(date("2020-12-03T00:00:00Z"))),
+        ["2020-12-02T00:00:00.000Z"]
+    );
+    assert_eq!(
+        iso_strings(iter.take_until(date("2020-12-03T00:00:00Z"))),
+        Vec::<String>::new()
+    );
+    assert_eq!(iso_strings(iter.collect(1)), ["2020-12-03T00:00:00.000Z"]);
+}
+
+/// Returns a field of a JS object
+fn get(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &key.into()).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn validates_every_expression() {
+    let crons: JsArray = [
+        JsValue::from("0 0 * * MON"),
+        JsValue::from("0 0 * *"),
+        JsValue::from(1),
+        JsValue::from("0 0 * * 2"),
+        JsValue::from("*/5 * * * *"),
+    ]
+    .iter()
+    .collect();
+    let results: Vec<JsValue> = WasmCron::validate_many(crons).unwrap().iter().collect();
+    assert_eq!(results.len(), 5);
+
+    let ok: Vec<bool> = results
+        .iter()
+        .map(|result| get(result, "ok").as_bool().unwrap())
+        .collect();
+    assert_eq!(ok, [true, false, false, false, true]);
+    assert!(get(&results[0], "error").is_undefined());
+    assert!(get(&results[1], "error").as_string().is_some());
+    assert!(get(&results[1], "duplicateOf").is_undefined());
+    assert_eq!(
+        get(&results[2], "error").as_string(),
+        Some("Element '2' is not a string".to_string())
+    );
+    assert_eq!(
+        get(&results[3], "error").as_string(),
+        Some("Expression '0 0 * * 2' already exists in the form of '0 0 * * MON'".to_string())
+    );
+    assert_eq!(get(&results[3], "duplicateOf").as_f64(), Some(0.0));
+}
+
+/// Returns the `(kind, text)` pairs of the segments of a description
+fn segments(cron: &str, options: JsValue) -> Vec<(String, String)> {
+    WasmCron::describe_segments(cron, options)
+        .unwrap()
+        .iter()
+        .map(|segment| {
+            (
+                get(&segment, "kind").as_string().unwrap(),
+                get(&segment, "text").as_string().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn describes_segments() {
+    let expected = [
+        ("time", "At 9:00 AM"),
+        ("dayOfMonth", " on the 1st"),
+        ("text", " and"),
+        ("dayOfWeek", " on Monday"),
+        ("month", " of every month"),
+    ];
+    let monday = segments("0 9 1 * MON", JsValue::UNDEFINED);
+    assert_eq!(
+        monday,
+        expected.map(|(kind, text)| (kind.to_string(), text.to_string()))
+    );
+
+    let text: String = monday.into_iter().map(|(_, text)| text).collect();
+    assert_eq!(Ok(text), describe("0 9 1 * MON", JsValue::UNDEFINED));
+    assert_eq!(
+        segments("@daily", JsValue::UNDEFINED),
+        [("text".to_string(), "Every day at midnight".to_string())]
+    );
+    assert!(WasmCron::describe_segments("invalid", JsValue::UNDEFINED).is_err());
+}
+
+#[wasm_bindgen_test]
+fn describes_segments_in_the_given_locale() {
+    let segments = segments("0 18 * * *", options(&[("locale", "de".into())]));
+    assert_eq!(segments[0].0, "time");
+    assert!(segments[0].1.contains("18:00"));
+}
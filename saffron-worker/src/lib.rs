@@ -4,15 +4,14 @@ use saffron::parse::{CronExpr, English};
 use saffron::Cron;
 use wasm_bindgen::prelude::*;
 
-use std::collections::HashMap;
-
 #[wasm_bindgen]
 extern "C" {
-    static env: String;
+    #[wasm_bindgen(thread_local_v2, js_name = env)]
+    static ENV: String;
 }
 
 fn set_panic_hook() {
-    if *env == "dev" {
+    if ENV.with(|env| env == "dev") {
         console_error_panic_hook::set_once();
     }
 }
@@ -63,16 +62,41 @@ impl DescriptionResult {
     }
 }
 
+/// The number of future executions estimated if no count is given
+const DEFAULT_FUTURE_EXECUTIONS: u32 = 5;
+
+/// The most future executions estimated in one description, which keeps responses small for
+/// expressions matching often in a long horizon
+const MAX_FUTURE_EXECUTIONS: u32 = 1000;
+
 /// Describes a given cron string. Used for live cron previews on the dash if wasm isn't available.
+///
+/// The description estimates up to `count` future executions (5 by default, at most 1000) at or
+/// after `start`, which defaults to now. If `end` is given, only executions before it are
+/// estimated.
 #[wasm_bindgen]
-pub fn describe(cron: &str) -> DescriptionResult {
+pub fn describe(
+    cron: &str,
+    count: Option<u32>,
+    start: Option<JsDate>,
+    end: Option<JsDate>,
+) -> DescriptionResult {
     set_panic_hook();
 
     match cron.parse::<CronExpr>() {
         Ok(expr) => {
             let description = expr.describe(English::default()).to_string();
             let compiled = Cron::new(expr);
-            let est_future_executions = compiled.iter_from(Utc::now()).take(5).collect();
+            let count = count
+                .unwrap_or(DEFAULT_FUTURE_EXECUTIONS)
+                .min(MAX_FUTURE_EXECUTIONS);
+            let start = start.map_or_else(Utc::now, DateTime::from);
+            let end = end.map(DateTime::<Utc>::from);
+            let est_future_executions = compiled
+                .iter_from(start)
+                .take_while(|time| end.is_none_or(|end| *time < end))
+                .take(count as usize)
+                .collect();
 
             DescriptionResult {
                 description: Some(Description {
@@ -112,37 +136,46 @@ pub fn validate(crons: JsArray) -> ValidationResult {
     set_panic_hook();
 
     let len = crons.length();
-    let mut map = HashMap::with_capacity(len as usize);
+    let mut strings: Vec<String> = Vec::with_capacity(len as usize);
     for i in 0..len {
-        let string = match crons.get(i).as_string() {
-            Some(string) => string,
+        match crons.get(i).as_string() {
+            Some(string) => strings.push(string),
             None => {
                 return ValidationResult {
                     errors: Some(vec![format!("Element '{}' is not a string", i)]),
                 }
             }
-        };
+        }
+    }
 
-        let cron: Cron = match string.parse() {
-            Ok(cron) => cron,
-            Err(err) => {
-                return ValidationResult {
-                    errors: Some(vec![format!(
-                        "Failed to parse expression at index '{}': {}",
-                        i, err
-                    )]),
-                }
-            }
+    // Every expression before the first that fails to parse compiles, so a duplicate among
+    // them comes before the parse error
+    let (parsed, error) = match saffron::parse_many(strings.iter().map(String::as_str)) {
+        Ok(parsed) => (parsed, None),
+        Err(mut errors) => {
+            let (i, err) = errors.remove(0);
+            let parsed =
+                saffron::parse_many(strings[..i].iter().map(String::as_str)).unwrap_or_default();
+            (parsed, Some((i, err)))
+        }
+    };
+
+    if let Some(&(i, first)) = saffron::find_duplicates(&parsed).first() {
+        return ValidationResult {
+            errors: Some(vec![format!(
+                "Expression '{}' already exists in the form of '{}'",
+                strings[i], strings[first]
+            )]),
         };
+    }
 
-        if let Some(old_str) = map.insert(cron, string.clone()) {
-            return ValidationResult {
-                errors: Some(vec![format!(
-                    "Expression '{}' already exists in the form of '{}'",
-                    string, old_str
-                )]),
-            };
-        }
+    if let Some((i, err)) = error {
+        return ValidationResult {
+            errors: Some(vec![format!(
+                "Failed to parse expression at index '{}': {}",
+                i, err
+            )]),
+        };
     }
 
     ValidationResult { errors: None }
@@ -0,0 +1,126 @@
+//! Tests for the worker's exports, run with `wasm-pack test --node` or `cargo test` with
+//! `wasm-bindgen-test-runner` as the runner.
+
+use js_sys::{Array as JsArray, Date as JsDate, Reflect};
+use saffron_worker::{describe, validate, Description};
+use wasm_bindgen::convert::TryFromJsValue;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Defines the `env` global the worker reads from its Wrangler environment
+fn set_env() {
+    Reflect::set(&js_sys::global(), &"env".into(), &"test".into()).unwrap();
+}
+
+fn date(text: &str) -> JsDate {
+    JsDate::new(&text.into())
+}
+
+/// Returns the estimated executions of a description of `cron` as ISO strings
+fn executions(
+    cron: &str,
+    count: Option<u32>,
+    start: Option<JsDate>,
+    end: Option<JsDate>,
+) -> Vec<String> {
+    set_env();
+    let result = describe(cron, count, start, end);
+    assert!(result.errors().is_none());
+    let description = Description::try_from_js_value(result.description()).unwrap();
+    description
+        .est_future_executions()
+        .iter()
+        .map(|date| String::from(JsDate::from(date).to_iso_string()))
+        .collect()
+}
+
+/// Returns the errors validating `crons` reports
+fn errors(crons: &[&str]) -> Option<Vec<String>> {
+    set_env();
+    let crons: JsArray = crons.iter().map(|cron| JsValue::from(*cron)).collect();
+    validate(crons).errors().map(|errors| {
+        errors
+            .iter()
+            .map(|error| error.as_string().unwrap())
+            .collect()
+    })
+}
+
+#[wasm_bindgen_test]
+fn describe_estimates_five_executions_by_default() {
+    let start = Some(date("2020-10-18T00:00:00Z"));
+    assert_eq!(executions("0 0 * * MON", None, start, None).len(), 5);
+}
+
+#[wasm_bindgen_test]
+fn describe_estimates_count_executions_from_start() {
+    let start = Some(date("2020-10-18T00:00:00Z"));
+    assert_eq!(
+        executions("0 0 * * MON", Some(3), start, None),
+        [
+            "2020-10-19T00:00:00.000Z",
+            "2020-10-26T00:00:00.000Z",
+            "2020-11-02T00:00:00.000Z",
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn describe_estimates_executions_before_end() {
+    let start = Some(date("2020-10-18T00:00:00Z"));
+    let end = Some(date("2020-11-02T00:00:00Z"));
+    assert_eq!(
+        executions("0 0 * * MON", Some(10), start, end),
+        ["2020-10-19T00:00:00.000Z", "2020-10-26T00:00:00.000Z"]
+    );
+}
+
+#[wasm_bindgen_test]
+fn describe_caps_the_execution_count() {
+    let start = Some(date("2020-10-18T00:00:00Z"));
+    assert_eq!(executions("* * * * *", Some(5000), start, None).len(), 1000);
+}
+
+#[wasm_bindgen_test]
+fn describe_reports_parse_errors() {
+    set_env();
+    let result = describe("0 0 * *", None, None, None);
+    assert!(result.description().is_undefined() || result.description().is_null());
+    assert_eq!(result.errors().unwrap().length(), 1);
+}
+
+#[wasm_bindgen_test]
+fn validate_accepts_distinct_expressions() {
+    assert_eq!(errors(&[]), None);
+    assert_eq!(errors(&["0 0 * * MON", "*/5 * * * *"]), None);
+}
+
+#[wasm_bindgen_test]
+fn validate_reports_a_parse_error() {
+    let errors = errors(&["0 0 * * MON", "0 0 * *"]).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with("Failed to parse expression at index '1'"));
+}
+
+#[wasm_bindgen_test]
+fn validate_reports_a_duplicate() {
+    assert_eq!(
+        errors(&["0 0 * * MON", "0 0 * * 2"]),
+        Some(vec![
+            "Expression '0 0 * * 2' already exists in the form of '0 0 * * MON'".to_string()
+        ])
+    );
+}
+
+#[wasm_bindgen_test]
+fn validate_reports_the_first_error() {
+    assert_eq!(
+        errors(&["0 0 * * MON", "0 0 * * 2", "0 0 * *"]),
+        Some(vec![
+            "Expression '0 0 * * 2' already exists in the form of '0 0 * * MON'".to_string()
+        ])
+    );
+    let errors = errors(&["0 0 * *", "0 0 * * MON", "0 0 * * 2"]).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with("Failed to parse expression at index '0'"));
+}
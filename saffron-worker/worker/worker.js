@@ -20,6 +20,15 @@ function jsonResponseHeaders() {
   }
 }
 
+// Reads an optional date in a request body, returning null if the date is invalid
+function optionalDate(value) {
+  if (value == null) {
+    return undefined;
+  }
+  let date = new Date(value);
+  return isNaN(date.getTime()) ? null : date;
+}
+
 function apiResponse(result, success, errors) {
   let json = JSON.stringify({
     result,
@@ -97,7 +106,16 @@ async function handleRequest(request) {
       if (cron == null) {
         return status(400, "Bad Request");
       }
-      let result = describe(cron);
+      let count = body.count;
+      if (count != null && !(Number.isInteger(count) && count >= 0)) {
+        return status(400, "Bad Request");
+      }
+      let start = optionalDate(body.start);
+      let end = optionalDate(body.end);
+      if (start === null || end === null) {
+        return status(400, "Bad Request");
+      }
+      let result = describe(cron, count == null ? undefined : count, start, end);
       let success = result.errors == null;
       return apiResponse(success ? {
         est_future_times: result.description.est_future_executions,
@@ -1,8 +1,14 @@
+use chrono::prelude::*;
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
 fn cron_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("Cron.from_str");
-    let inputs = ["* * * * *", "1 12 3 6 *", "12-35 1-23 2-5 1-11 *"];
+    let inputs = [
+        "* * * * *",
+        "1 12 3 6 *",
+        "12-35 1-23 2-5 1-11 *",
+        "0,15,30,45 8-18 1,15 JAN-JUN *",
+    ];
     for input in inputs.iter() {
         group.bench_with_input(BenchmarkId::from_parameter(input), input, |b, input| {
             b.iter(|| input.parse::<saffron::Cron>().unwrap())
@@ -11,5 +17,19 @@ fn cron_benchmark(c: &mut Criterion) {
     group.finish()
 }
 
-criterion_group!(benches, cron_benchmark);
+fn sparse_iter_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Cron.iter_from_ref");
+    let start = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    let inputs = ["0 0 29 2 *", "0 0 31 12 *", "0 0 29 2 MON"];
+    for input in inputs.iter() {
+        let cron = input.parse::<saffron::Cron>().unwrap();
+        // 25 leap days span a century
+        group.bench_with_input(BenchmarkId::from_parameter(input), &cron, |b, cron| {
+            b.iter(|| cron.iter_from_ref(start).take(25).last())
+        });
+    }
+    group.finish()
+}
+
+criterion_group!(benches, cron_benchmark, sparse_iter_benchmark);
 criterion_main!(benches);
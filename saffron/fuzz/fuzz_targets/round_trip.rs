@@ -0,0 +1,24 @@
+#![no_main]
+use chrono::{Duration, TimeZone, Utc};
+use libfuzzer_sys::fuzz_target;
+use saffron::parse::{CronExpr, CronString};
+use saffron::Cron;
+
+fuzz_target!(|input: (CronString, i64)| {
+    let (string, timestamp) = input;
+    let expr: CronExpr = string.as_str().parse().expect("Valid cron string");
+    assert_eq!(&expr, string.expr());
+    assert_eq!(expr.to_string().parse::<CronExpr>(), Ok(expr.clone()));
+
+    let cron = Cron::new(expr);
+    let start = match Utc.timestamp_opt(timestamp % 4_102_444_800, 0).single() {
+        Some(start) => start,
+        None => return,
+    };
+    if let Some(next) = cron.next_from(start) {
+        assert!(cron.contains(next));
+    }
+    if let Err(inconsistency) = cron.verify_consistency(start..start + Duration::days(2)) {
+        panic!("{}: {}", cron, inconsistency);
+    }
+});
@@ -0,0 +1,584 @@
+//! Support for generating cron values and expressions with the `arbitrary` crate, so they can be
+//! fuzzed and property tested.
+
+use crate::parse::{
+    BusinessDay, CronExpr, DayOfMonth, DayOfMonthExpr, DayOfMonthOffset, DayOfMonthSpecial,
+    DayOfWeek, DayOfWeekExpr, DayOfWeekSpecial, Exclusions, Expr, ExprValue, Exprs, Hour, Last,
+    Minute, Month, NthDay, OrsExpr, Shortcut, Step, ValueOutOfRangeError,
+};
+use crate::Cron;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+
+/// Picks a value between the min and max of an expression value
+fn value<E>(u: &mut Unstructured) -> Result<E>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+{
+    let value = u.int_in_range(E::MIN..=E::MAX)?;
+    Ok(E::try_from(value).expect("Values from MIN to MAX are in range"))
+}
+
+macro_rules! arbitrary_values {
+    ($($ty:ty),*) => {
+        $(
+            impl<'a> Arbitrary<'a> for $ty {
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    value(u)
+                }
+            }
+        )*
+    };
+}
+
+arbitrary_values!(
+    Minute,
+    Hour,
+    DayOfMonth,
+    DayOfMonthOffset,
+    BusinessDay,
+    Month,
+    NthDay,
+    DayOfWeek
+);
+
+impl<'a, E: ExprValue> Arbitrary<'a> for Step<E> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        value(u)
+    }
+}
+
+impl<'a, E> Arbitrary<'a> for OrsExpr<E>
+where
+    E: Arbitrary<'a> + ExprValue,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2u8)? {
+            0 => OrsExpr::One(E::arbitrary(u)?),
+            1 => OrsExpr::Range(E::arbitrary(u)?, E::arbitrary(u)?),
+            _ => OrsExpr::Step {
+                start: E::arbitrary(u)?,
+                end: E::arbitrary(u)?,
+                step: Step::arbitrary(u)?,
+            },
+        })
+    }
+}
+
+impl<'a, E> Arbitrary<'a> for Exprs<E>
+where
+    E: Arbitrary<'a> + ExprValue,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut exprs = Exprs::new(OrsExpr::arbitrary(u)?);
+        for _ in 0..u.int_in_range(0..=3u8)? {
+            exprs.tail.push(OrsExpr::arbitrary(u)?);
+        }
+        Ok(exprs)
+    }
+}
+
+impl<'a, E> Arbitrary<'a> for Expr<E>
+where
+    E: Arbitrary<'a> + ExprValue,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 4)? {
+            Ok(Expr::All)
+        } else {
+            Exprs::arbitrary(u).map(Expr::Many)
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Last {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4u8)? {
+            0 => Last::Day,
+            1 => Last::Weekday,
+            2 => Last::Offset(DayOfMonthOffset::arbitrary(u)?),
+            3 => Last::OffsetWeekday(DayOfMonthOffset::arbitrary(u)?),
+            _ => Last::WeekdayOffset(DayOfMonthOffset::arbitrary(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for DayOfMonthSpecial {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2u8)? {
+            0 => DayOfMonthSpecial::Last(Last::arbitrary(u)?),
+            1 => DayOfMonthSpecial::ClosestWeekday(DayOfMonth::arbitrary(u)?),
+            _ => DayOfMonthSpecial::BusinessDay(BusinessDay::arbitrary(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for DayOfMonthExpr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=9u8)? {
+            0 => DayOfMonthExpr::All,
+            1 => DayOfMonthExpr::Ignored,
+            2 => DayOfMonthExpr::Last(Last::arbitrary(u)?),
+            3 => DayOfMonthExpr::ClosestWeekday(DayOfMonth::arbitrary(u)?),
+            4 => DayOfMonthExpr::BusinessDay(BusinessDay::arbitrary(u)?),
+            5 => DayOfMonthExpr::Specials(specials(u)?),
+            // a W after one day on its own is a single closest weekday expression
+            6 => match Exprs::arbitrary(u)? {
+                Exprs {
+                    first: OrsExpr::One(day),
+                    tail,
+                } if tail.is_empty() => DayOfMonthExpr::ClosestWeekday(day),
+                exprs => DayOfMonthExpr::ClosestWeekdays(exprs),
+            },
+            _ => DayOfMonthExpr::Many(Exprs::arbitrary(u)?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for DayOfWeekSpecial {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? {
+            DayOfWeekSpecial::Last(DayOfWeek::arbitrary(u)?)
+        } else {
+            DayOfWeekSpecial::Nth(DayOfWeek::arbitrary(u)?, NthDay::arbitrary(u)?)
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for DayOfWeekExpr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=8u8)? {
+            0 => DayOfWeekExpr::All,
+            1 => DayOfWeekExpr::Ignored,
+            2 => DayOfWeekExpr::Last(DayOfWeek::arbitrary(u)?),
+            3 => DayOfWeekExpr::Nth(DayOfWeek::arbitrary(u)?, NthDay::arbitrary(u)?),
+            4 => DayOfWeekExpr::Specials(specials(u)?),
+            _ => DayOfWeekExpr::Many(Exprs::arbitrary(u)?),
+        })
+    }
+}
+
+/// Picks a list of two to four special day expressions
+fn specials<'a, S: Arbitrary<'a>>(u: &mut Unstructured<'a>) -> Result<Vec<S>> {
+    (0..u.int_in_range(2..=4u8)?)
+        .map(|_| S::arbitrary(u))
+        .collect()
+}
+
+impl<'a> Arbitrary<'a> for Shortcut {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&Shortcut::ALL).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for CronExpr {
+    /// Generates a valid expression, one that's equal to its display string parsed.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.ratio(1, 16)? {
+            return Shortcut::arbitrary(u).map(Shortcut::expr);
+        }
+
+        let mut expr = CronExpr {
+            minutes: Expr::arbitrary(u)?,
+            hours: Expr::arbitrary(u)?,
+            doms: DayOfMonthExpr::arbitrary(u)?,
+            months: Expr::arbitrary(u)?,
+            dows: DayOfWeekExpr::arbitrary(u)?,
+            // only the extended dialect has a weeks field
+            weeks: Expr::All,
+            shortcut: None,
+            exclusions: Exclusions::default(),
+            days_and: false,
+        };
+        // only one of the day fields can be ignored
+        if expr.doms == DayOfMonthExpr::Ignored && expr.dows == DayOfWeekExpr::Ignored {
+            expr.dows = DayOfWeekExpr::All;
+        }
+        Ok(expr)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Cron {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        CronExpr::arbitrary(u).map(Cron::new)
+    }
+}
+
+/// A value that can be written in a cron string, by number or by name
+trait Token: ExprValue + Display + Copy + PartialEq {
+    fn name(self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl Token for Minute {}
+impl Token for Hour {}
+impl Token for DayOfMonth {}
+impl Token for DayOfMonthOffset {}
+impl Token for NthDay {}
+impl Token for BusinessDay {}
+
+impl Token for Month {
+    fn name(self) -> Option<&'static str> {
+        const NAMES: [&str; 12] = [
+            "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+        ];
+        Some(NAMES[usize::from(u8::from(self))])
+    }
+}
+
+impl Token for DayOfWeek {
+    fn name(self) -> Option<&'static str> {
+        const NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+        Some(NAMES[usize::from(u8::from(self))])
+    }
+}
+
+/// Writes cron strings in a random mix of the syntax the parser accepts
+struct Writer<'u, 'a> {
+    u: &'u mut Unstructured<'a>,
+    string: String,
+}
+
+impl Writer<'_, '_> {
+    fn str(&mut self, s: &str) {
+        self.string.push_str(s);
+    }
+
+    fn name(&mut self, name: &str) -> Result<()> {
+        if self.u.arbitrary()? {
+            self.str(&name.to_ascii_lowercase());
+        } else {
+            self.str(name);
+        }
+        Ok(())
+    }
+
+    fn value<E: Token>(&mut self, value: E) -> Result<()> {
+        match value.name() {
+            Some(name) if self.u.arbitrary()? => self.name(name),
+            _ => {
+                self.str(&value.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    fn ors_expr<E: Token>(&mut self, expr: &OrsExpr<E>) -> Result<()> {
+        match *expr {
+            OrsExpr::One(value) => self.value(value),
+            OrsExpr::Range(start, end) => {
+                self.value(start)?;
+                self.str("-");
+                self.value(end)
+            }
+            OrsExpr::Step { start, end, step } => {
+                let full = start == E::min() && end == E::max();
+                if full && self.u.arbitrary()? {
+                    self.str("*");
+                } else {
+                    self.value(start)?;
+                    if end != E::max() || self.u.arbitrary()? {
+                        self.str("-");
+                        self.value(end)?;
+                    }
+                }
+                self.str("/");
+                self.str(&step.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    fn exprs<E: Token>(&mut self, exprs: &Exprs<E>) -> Result<()> {
+        for (i, expr) in exprs.iter().enumerate() {
+            if i != 0 {
+                self.str(",");
+            }
+            self.ors_expr(expr)?;
+        }
+        Ok(())
+    }
+
+    fn expr<E: Token>(&mut self, expr: &Expr<E>) -> Result<()> {
+        match expr {
+            Expr::All => {
+                self.str("*");
+                Ok(())
+            }
+            Expr::Many(exprs) => self.exprs(exprs),
+        }
+    }
+
+    fn doms(&mut self, expr: &DayOfMonthExpr) -> Result<()> {
+        match expr {
+            DayOfMonthExpr::All => self.str("*"),
+            DayOfMonthExpr::Ignored => self.str("?"),
+            DayOfMonthExpr::Last(last) => {
+                self.dom_special(&DayOfMonthSpecial::Last(last.clone()))?
+            }
+            &DayOfMonthExpr::ClosestWeekday(day) => {
+                self.dom_special(&DayOfMonthSpecial::ClosestWeekday(day))?
+            }
+            &DayOfMonthExpr::BusinessDay(day) => {
+                self.dom_special(&DayOfMonthSpecial::BusinessDay(day))?
+            }
+            DayOfMonthExpr::Specials(specials) => {
+                for (i, special) in specials.iter().enumerate() {
+                    if i != 0 {
+                        self.str(",");
+                    }
+                    self.dom_special(special)?;
+                }
+            }
+            DayOfMonthExpr::ClosestWeekdays(exprs) => {
+                self.exprs(exprs)?;
+                self.str("W");
+            }
+            DayOfMonthExpr::Many(exprs) => self.exprs(exprs)?,
+        }
+        Ok(())
+    }
+
+    fn dom_special(&mut self, special: &DayOfMonthSpecial) -> Result<()> {
+        match special {
+            DayOfMonthSpecial::Last(Last::Day) => self.str("L"),
+            DayOfMonthSpecial::Last(Last::Weekday) => self.str("LW"),
+            DayOfMonthSpecial::Last(Last::Offset(offset)) => {
+                self.str("L-");
+                self.value(*offset)?;
+            }
+            DayOfMonthSpecial::Last(Last::OffsetWeekday(offset)) => {
+                self.str("L-");
+                self.value(*offset)?;
+                self.str("W");
+            }
+            DayOfMonthSpecial::Last(Last::WeekdayOffset(offset)) => {
+                self.str("LW-");
+                self.value(*offset)?;
+            }
+            DayOfMonthSpecial::ClosestWeekday(day) => {
+                self.value(*day)?;
+                self.str("W");
+            }
+            DayOfMonthSpecial::BusinessDay(day) => {
+                self.value(*day)?;
+                self.str("B");
+            }
+        }
+        Ok(())
+    }
+
+    fn dows(&mut self, expr: &DayOfWeekExpr) -> Result<()> {
+        match expr {
+            DayOfWeekExpr::All => self.str("*"),
+            DayOfWeekExpr::Ignored => self.str("?"),
+            &DayOfWeekExpr::Last(day) => self.dow_special(&DayOfWeekSpecial::Last(day))?,
+            &DayOfWeekExpr::Nth(day, nth) => self.dow_special(&DayOfWeekSpecial::Nth(day, nth))?,
+            DayOfWeekExpr::Specials(specials) => {
+                for (i, special) in specials.iter().enumerate() {
+                    if i != 0 {
+                        self.str(",");
+                    }
+                    self.dow_special(special)?;
+                }
+            }
+            DayOfWeekExpr::Many(exprs) => self.exprs(exprs)?,
+        }
+        Ok(())
+    }
+
+    fn dow_special(&mut self, special: &DayOfWeekSpecial) -> Result<()> {
+        match *special {
+            DayOfWeekSpecial::Last(day) => {
+                self.value(day)?;
+                self.str("L");
+            }
+            DayOfWeekSpecial::Nth(day, nth) => {
+                self.value(day)?;
+                self.str("#");
+                self.value(nth)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn separator(&mut self) -> Result<()> {
+        for _ in 0..self.u.int_in_range(1..=3u8)? {
+            self.str(" ");
+        }
+        Ok(())
+    }
+}
+
+/// A valid cron string and the expression it parses into. Unlike the display string of an
+/// expression, the cron string is written in a random mix of the syntax the parser accepts, like
+/// names, `*` steps, and lowercase shortcuts.
+///
+/// # Example
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use saffron::parse::{CronExpr, CronString};
+///
+/// let mut u = Unstructured::new(&[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9]);
+/// let string = CronString::arbitrary(&mut u).unwrap();
+/// assert_eq!(string.as_str().parse::<CronExpr>().as_ref(), Ok(string.expr()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronString {
+    string: String,
+    expr: CronExpr,
+}
+
+impl CronString {
+    /// Returns the cron string
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.string
+    }
+
+    /// Returns the expression the cron string parses into
+    #[inline]
+    pub fn expr(&self) -> &CronExpr {
+        &self.expr
+    }
+}
+
+impl Display for CronString {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.string)
+    }
+}
+
+impl<'a> Arbitrary<'a> for CronString {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let expr = CronExpr::arbitrary(u)?;
+        let mut writer = Writer {
+            u,
+            string: String::new(),
+        };
+        if let Some(shortcut) = expr.shortcut {
+            writer.str("@");
+            writer.name(&shortcut.name().to_ascii_uppercase())?;
+        } else {
+            writer.expr(&expr.minutes)?;
+            writer.separator()?;
+            writer.expr(&expr.hours)?;
+            writer.separator()?;
+            writer.doms(&expr.doms)?;
+            writer.separator()?;
+            writer.expr(&expr.months)?;
+            writer.separator()?;
+            writer.dows(&expr.dows)?;
+        }
+
+        Ok(CronString {
+            string: writer.string,
+            expr,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    use crate::{CronOptions, DayPolicy};
+    use chrono::{TimeZone, Utc};
+
+    /// Runs a check over unstructured data made from a few hundred pseudo-random seeds
+    fn check(mut f: impl FnMut(&mut Unstructured) -> Result<()>) {
+        for seed in 1..=500u64 {
+            // xorshift, to get the same data every run
+            let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            let data: Vec<u8> = (0..256)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect();
+            f(&mut Unstructured::new(&data)).unwrap();
+        }
+    }
+
+    #[test]
+    fn strings_parse_into_their_expr() {
+        check(|u| {
+            let string = CronString::arbitrary(u)?;
+            assert_eq!(
+                string.as_str().parse::<CronExpr>().as_ref(),
+                Ok(string.expr()),
+                "{}",
+                string
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn exprs_round_trip() {
+        check(|u| {
+            let expr = CronExpr::arbitrary(u)?;
+            let string = expr.to_string();
+            assert_eq!(string.parse::<CronExpr>(), Ok(expr), "{}", string);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn any_is_exact() {
+        check(|u| {
+            let expr = CronExpr::arbitrary(u)?;
+            for &day_policy in &[DayPolicy::Or, DayPolicy::And] {
+                let mut options = CronOptions::new();
+                options.day_policy = day_policy;
+                let cron = Cron::with_options(expr.clone(), options).unwrap();
+                assert_eq!(cron.any(), cron.any_in_cycle(), "{}", cron);
+            }
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        check(|u| {
+            let cron = Cron::arbitrary(u)?;
+            assert_eq!(
+                Cron::from_bytes(&cron.to_bytes()),
+                Ok(cron.clone()),
+                "{}",
+                cron
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn next_times_are_contained() {
+        check(|u| {
+            let cron = Cron::arbitrary(u)?;
+            // whole minutes before 2100, since times are matched by the minute
+            let start = Utc
+                .timestamp_opt(u.int_in_range(0..=68_374_080)? * 60, 0)
+                .unwrap();
+            if let Some(next) = cron.next_from(start) {
+                assert!(next >= start);
+                assert!(cron.contains(next), "{} from {}", cron, start);
+            }
+            Ok(())
+        })
+    }
+}
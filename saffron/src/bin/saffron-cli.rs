@@ -0,0 +1,149 @@
+//! A command line tool for describing, validating, and listing the times of cron expressions.
+//!
+//! Each command reads one expression from its arguments, or one expression per line from stdin
+//! if none is given.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use saffron::parse::{CronExpr, English};
+use saffron::Cron;
+use std::io::{self, BufRead};
+use std::process;
+
+const USAGE: &str = "\
+Usage: saffron-cli <command> [options] [expression]
+
+Commands:
+  describe                 Prints a description of the expression
+  next [-n N]              Prints the next N times the expression matches (default 1)
+  validate                 Checks that the expression is valid and matches some time
+  between <start> <end>    Prints every time the expression matches from start until end
+
+Times are written in RFC 3339, like 2021-01-01T00:00:00Z. If no expression is given, one
+expression is read from each line of stdin.";
+
+/// A command and its options
+enum Command {
+    Describe,
+    Next(usize),
+    Validate,
+    Between(DateTime<Utc>, DateTime<Utc>),
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (command, expr) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(msg) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let exprs = match expr {
+        Some(expr) => vec![expr],
+        None => io::stdin()
+            .lock()
+            .lines()
+            .map(|line| line.unwrap_or_else(|err| fail(&err.to_string())))
+            .filter(|line| !line.trim().is_empty())
+            .collect(),
+    };
+
+    let mut failed = false;
+    for expr in &exprs {
+        if let Err(err) = run(&command, expr.trim()) {
+            if exprs.len() > 1 {
+                eprintln!("{}: {}", expr.trim(), err);
+            } else {
+                eprintln!("{}", err);
+            }
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Reads the command and the expression, if any, from the arguments
+fn parse_args(args: &[String]) -> Result<(Command, Option<String>), String> {
+    let (name, mut rest) = match args.split_first() {
+        Some((name, rest)) => (name.as_str(), rest),
+        None => return Err("Missing command".to_string()),
+    };
+
+    let command = match name {
+        "describe" => Command::Describe,
+        "validate" => Command::Validate,
+        "next" => match rest.first().map(String::as_str) {
+            Some("-n") => {
+                let count = rest
+                    .get(1)
+                    .and_then(|count| count.parse().ok())
+                    .ok_or_else(|| "Expected a number of times after -n".to_string())?;
+                rest = &rest[2..];
+                Command::Next(count)
+            }
+            _ => Command::Next(1),
+        },
+        "between" => {
+            if rest.len() < 2 {
+                return Err("Expected a start and end time".to_string());
+            }
+            let start = parse_time(&rest[0])?;
+            let end = parse_time(&rest[1])?;
+            rest = &rest[2..];
+            Command::Between(start, end)
+        }
+        "-h" | "--help" | "help" => {
+            println!("{}", USAGE);
+            process::exit(0);
+        }
+        _ => return Err(format!("Unknown command '{}'", name)),
+    };
+
+    match rest {
+        [] => Ok((command, None)),
+        [expr] => Ok((command, Some(expr.clone()))),
+        _ => Err("Expected one expression, quote it to pass it as one argument".to_string()),
+    }
+}
+
+fn parse_time(s: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|err| format!("Invalid time '{}': {}", s, err))
+}
+
+/// Runs the command for one expression, returning the error message if it fails
+fn run(command: &Command, expr: &str) -> Result<(), String> {
+    let expr = expr.parse::<CronExpr>().map_err(|err| err.to_string())?;
+    match *command {
+        Command::Describe => println!("{}", expr.describe(English::default())),
+        Command::Validate => {
+            if !Cron::new(expr).any() {
+                return Err("Expression never matches any time".to_string());
+            }
+        }
+        Command::Next(count) => {
+            for time in Cron::new(expr).iter_from(Utc::now()).take(count) {
+                println!("{}", format_time(time));
+            }
+        }
+        Command::Between(start, end) => {
+            for time in Cron::new(expr).iter(start..end) {
+                println!("{}", format_time(time));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}", msg);
+    process::exit(1);
+}
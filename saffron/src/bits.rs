@@ -0,0 +1,189 @@
+//! Bit masks of the values matched by expressions, shared by every field so they're filled the
+//! same way no matter how wide the field is.
+
+use crate::parse::{ExprValue, OrsExpr};
+
+/// The values set in a field with `BITS` values, numbered from zero so value `n` is bit `n`.
+/// Every compiled field fills its mask through this, so a new field only needs its width.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub(crate) struct BitField<const BITS: usize>(u64);
+
+impl<const BITS: usize> BitField<BITS> {
+    /// Creates a field with the values of the given mask set
+    #[inline]
+    pub(crate) fn from_bits(bits: u64) -> Self {
+        debug_assert!(BITS <= 64 && (BITS == 64 || bits >> BITS == 0));
+        Self(bits)
+    }
+
+    /// Returns the mask of the values set
+    #[inline]
+    pub(crate) fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Sets one value.
+    #[inline]
+    pub(crate) fn set(&mut self, value: u8) {
+        self.set_step(value, value, 1);
+    }
+
+    /// Sets the values from the start to the end (inclusive). A range ending before it starts
+    /// wraps around past the last value, like `FRI-SUN` or `50-10`.
+    #[inline]
+    pub(crate) fn set_range_wrapping(&mut self, start: u8, end: u8) {
+        self.set_step(start, end, 1);
+    }
+
+    /// Sets every `step`th value from the start to the end (inclusive), wrapping around past
+    /// the last value like [`set_range_wrapping`].
+    ///
+    /// [`set_range_wrapping`]: #method.set_range_wrapping
+    #[inline]
+    pub(crate) fn set_step(&mut self, start: u8, end: u8, step: u8) {
+        self.0 |= fill(start, end, step, BITS as u8);
+    }
+
+    /// Sets the values matched by an expression of a field with the same number of values.
+    #[inline]
+    pub(crate) fn set_expr<E>(&mut self, expr: &OrsExpr<E>)
+    where
+        E: Copy + ExprValue,
+        u8: From<E>,
+    {
+        debug_assert_eq!(usize::from(E::MAX - E::MIN + 1), BITS);
+        match *expr {
+            OrsExpr::One(value) => self.set(value.into()),
+            OrsExpr::Range(start, end) => self.set_range_wrapping(start.into(), end.into()),
+            OrsExpr::Step { start, end, step } => {
+                self.set_step(start.into(), end.into(), step.into())
+            }
+        }
+    }
+}
+
+/// Returns a mask of the values from the start to the end (inclusive) of a field with the given
+/// number of values, taking every `step`th value. Values are zero based, so value `n` is bit `n`
+/// of the mask. A range ending before it starts wraps around past the last value, like `FRI-SUN`
+/// or `50-10`.
+pub(crate) fn fill(start: u8, end: u8, step: u8, count: u8) -> u64 {
+    debug_assert!(start < count && end < count && count <= 64 && step != 0);
+
+    if step == 1 {
+        return if start <= end {
+            range(start, end)
+        } else {
+            range(start, count - 1) | range(0, end)
+        };
+    }
+
+    let end = if end < start { end + count } else { end };
+    let mut mask = 0;
+    for value in (start..=end).step_by(step as usize) {
+        mask |= 1 << (value % count);
+    }
+    mask
+}
+
+/// Returns a mask with the bits from the start to the end (inclusive) set. Clears the bits above
+/// the end by shifting them out the top, then the bits below the start.
+#[inline]
+fn range(start: u8, end: u8) -> u64 {
+    (u64::MAX >> (63 - end)) & (u64::MAX << start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sets every bit one by one, to check the masks against
+    fn naive(start: u8, end: u8, step: u8, count: u8) -> u64 {
+        let mut mask = 0;
+        let mut value = start;
+        let mut steps = 0;
+        loop {
+            if steps % step == 0 {
+                mask |= 1 << value;
+            }
+            if value == end {
+                return mask;
+            }
+            value = (value + 1) % count;
+            steps += 1;
+        }
+    }
+
+    #[test]
+    fn fills_every_range_and_step() {
+        for &count in &[7, 12, 24, 31, 53, 60, 64] {
+            for start in 0..count {
+                for end in 0..count {
+                    for step in 1..=count {
+                        assert_eq!(
+                            fill(start, end, step, count),
+                            naive(start, end, step, count),
+                            "{}-{}/{} of {}",
+                            start,
+                            end,
+                            step,
+                            count
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut field = BitField::<60>::default();
+        field.set(0);
+        field.set(59);
+        field.set(0);
+        assert_eq!(field.bits(), 1 | 1 << 59);
+    }
+
+    #[test]
+    fn set_range_wrapping() {
+        // FRI-SUN
+        let mut field = BitField::<7>::default();
+        field.set_range_wrapping(5, 0);
+        assert_eq!(field.bits(), 0b110_0001);
+
+        let mut field = BitField::<64>::default();
+        field.set_range_wrapping(0, 63);
+        assert_eq!(field.bits(), u64::MAX);
+
+        let mut field = BitField::<24>::from_bits(0b1000);
+        field.set_range_wrapping(0, 1);
+        assert_eq!(field.bits(), 0b1011);
+    }
+
+    #[test]
+    fn set_step() {
+        // NOV-FEB/2
+        let mut field = BitField::<12>::default();
+        field.set_step(10, 1, 2);
+        assert_eq!(field.bits(), 0b0100_0000_0001);
+
+        let mut field = BitField::<60>::default();
+        field.set_step(0, 59, 15);
+        assert_eq!(field.bits(), 1 | 1 << 15 | 1 << 30 | 1 << 45);
+    }
+
+    #[test]
+    fn set_expr() {
+        use crate::parse::{Hour, Step};
+        use core::convert::TryFrom;
+
+        let hour = |hour| Hour::try_from(hour).unwrap();
+        let mut field = BitField::<24>::default();
+        field.set_expr(&OrsExpr::Range(hour(22), hour(1)));
+        field.set_expr(&OrsExpr::Step {
+            start: hour(6),
+            end: hour(12),
+            step: Step::try_from(3).unwrap(),
+        });
+        assert_eq!(field.bits(), 0b1100_0000_0001_0010_0100_0011);
+    }
+}
@@ -0,0 +1,515 @@
+//! A compact, versioned binary encoding of compiled cron values, for storing them without
+//! keeping and reparsing their expressions.
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    Cron, DayPolicy, DaysOfMonth, DaysOfMonthKind, DaysOfWeek, DaysOfWeekKind, DomSpecials,
+    DowSpecials, Hours, Minutes, Months, Weeks,
+};
+use core::fmt::{self, Display, Formatter};
+
+/// The version of the encoding written by [`Cron::to_bytes`]. It's the first byte of every
+/// encoded value and changes whenever the layout does.
+const VERSION: u8 = 1;
+
+/// The length of the part of the encoding every value has.
+///
+/// | Bytes  | Contents                                                                  |
+/// |--------|---------------------------------------------------------------------------|
+/// | 0      | The version                                                               |
+/// | 1      | The day of month kind (bits 0-3), day of week kind (4-6), and a weeks flag (7) |
+/// | 2-9    | The minutes mask                                                          |
+/// | 10-12  | The hours mask                                                            |
+/// | 13-14  | The months mask (bits 0-11) and the day policy (12-13)                    |
+/// | 15-18  | The day of month value                                                    |
+/// | 19     | The day of week value                                                     |
+///
+/// It's followed by the weeks mask in 7 bytes if the weeks flag is set, then the day of month
+/// specials in 20 bytes and the day of week specials in 8 bytes if either field is a list of
+/// special expressions. All integers are little endian.
+const HEADER_LEN: usize = 20;
+const WEEKS_LEN: usize = 7;
+const DOM_SPECIALS_LEN: usize = 20;
+const DOW_SPECIALS_LEN: usize = 8;
+
+const WEEKS_FLAG: u8 = 0x80;
+const POLICY_SHIFT: u32 = 12;
+
+/// An error returned when bytes can't be decoded into a cron value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CronDecodeError {
+    /// The bytes were encoded with a version of the encoding this version of the crate doesn't
+    /// know. The unknown version is included.
+    UnsupportedVersion(u8),
+    /// The bytes end early or have bytes left over after the value.
+    InvalidLength,
+    /// The bytes aren't a value [`Cron::to_bytes`] could have written.
+    InvalidValue,
+}
+
+impl Display for CronDecodeError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CronDecodeError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported encoding version {}", version)
+            }
+            CronDecodeError::InvalidLength => f.write_str("Invalid length for an encoded value"),
+            CronDecodeError::InvalidValue => f.write_str("Invalid encoded value"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CronDecodeError {}
+
+impl Cron {
+    /// Encodes the cron value into a compact binary form, which [`Cron::from_bytes`] decodes
+    /// back into an equal value without parsing an expression. Most values encode into 20
+    /// bytes, with 7 more for values restricting the weeks of the year and up to 28 more for
+    /// lists of special day expressions, like `1W,15W`.
+    ///
+    /// 20 bytes is the smallest whole number of bytes the fixed fields fit in: the minutes,
+    /// hours and months masks take 60, 24 and 12 bits, the day of month value 32 bits, the day
+    /// of week value 8 bits and the day policy 2 bits, which with the version and kind bytes
+    /// comes to 154 bits. Packing it into 16 bytes would mean dropping information.
+    ///
+    /// The first byte is a version. Bytes written by this version of the crate can always be
+    /// decoded by later versions, but not the other way around.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let cron: Cron = "*/15 9-17 * * MON-FRI".parse().expect("Couldn't parse expression!");
+    /// let bytes = cron.to_bytes();
+    /// assert_eq!(bytes.len(), 20);
+    /// assert_eq!(Cron::from_bytes(&bytes), Ok(cron));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let weeks = self.weeks.0 != Weeks::ALL;
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.push(VERSION);
+        let mut tags = dom_tag(self.dom.kind()) | (dow_tag(self.dow.kind()) << 4);
+        if weeks {
+            tags |= WEEKS_FLAG;
+        }
+        bytes.push(tags);
+        bytes.extend_from_slice(&self.minutes.0.to_le_bytes());
+        bytes.extend_from_slice(&self.hours.0.to_le_bytes()[..3]);
+        let policy = match self.day_policy {
+            DayPolicy::Or => 0,
+            DayPolicy::And => 1,
+            DayPolicy::QuartzStrict => 2,
+        };
+        bytes.extend_from_slice(&(self.months.0 | (policy << POLICY_SHIFT)).to_le_bytes());
+        bytes.extend_from_slice(&self.dom.1.to_le_bytes());
+        bytes.push(self.dow.1);
+
+        if weeks {
+            bytes.extend_from_slice(&self.weeks.0.to_le_bytes()[..WEEKS_LEN]);
+        }
+        if let DaysOfMonthKind::Specials(specials) = self.dom.kind() {
+            for slot in &specials.0 {
+                bytes.extend_from_slice(&slot.to_le_bytes());
+            }
+        }
+        if let DaysOfWeekKind::Specials(specials) = self.dow.kind() {
+            bytes.extend_from_slice(&specials.0.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a cron value from bytes written by [`Cron::to_bytes`]. Returns an error if the
+    /// bytes were written by a newer version of the encoding, or if they aren't a value
+    /// `to_bytes` could have written, so a decoded value always behaves like a parsed one.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, CronDecodeError};
+    ///
+    /// let cron: Cron = "0 0 1W,15W * ?".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(Cron::from_bytes(&cron.to_bytes()), Ok(cron));
+    ///
+    /// assert_eq!(Cron::from_bytes(&[255]), Err(CronDecodeError::UnsupportedVersion(255)));
+    /// assert_eq!(Cron::from_bytes(&[]), Err(CronDecodeError::InvalidLength));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Cron, CronDecodeError> {
+        match bytes.first() {
+            Some(&VERSION) => {}
+            Some(&version) => return Err(CronDecodeError::UnsupportedVersion(version)),
+            None => return Err(CronDecodeError::InvalidLength),
+        }
+        let mut reader = Reader(bytes);
+        let header = reader.take(HEADER_LEN)?;
+        let tags = header[1];
+        let minutes = read_u64(&header[2..10]);
+        let hours = read_u64(&header[10..13]) as u32;
+        let months = read_u64(&header[13..15]) as u16;
+        let dom_value = read_u64(&header[15..19]) as u32;
+        let dow_value = header[19];
+
+        let weeks = if tags & WEEKS_FLAG != 0 {
+            let weeks = read_u64(reader.take(WEEKS_LEN)?);
+            // an unrestricted field is never written
+            if weeks == Weeks::ALL {
+                return Err(CronDecodeError::InvalidValue);
+            }
+            weeks
+        } else {
+            Weeks::ALL
+        };
+        let dom_kind = match tags & 0x0F {
+            0 => DaysOfMonthKind::Pattern,
+            1 => DaysOfMonthKind::Star,
+            2 => DaysOfMonthKind::Ignored,
+            3 => DaysOfMonthKind::Last,
+            4 => DaysOfMonthKind::Weekday,
+            5 => DaysOfMonthKind::Weekdays,
+            6 => DaysOfMonthKind::LastWeekday,
+            7 => DaysOfMonthKind::BusinessDay,
+            8 => DaysOfMonthKind::LastWeekdayOffset,
+            9 => {
+                let mut specials = DomSpecials::default();
+                for (slot, bytes) in specials
+                    .0
+                    .iter_mut()
+                    .zip(reader.take(DOM_SPECIALS_LEN)?.chunks(4))
+                {
+                    *slot = read_u64(bytes) as u32;
+                }
+                DaysOfMonthKind::Specials(specials)
+            }
+            _ => return Err(CronDecodeError::InvalidValue),
+        };
+        let dow_kind = match (tags >> 4) & 0x07 {
+            0 => DaysOfWeekKind::Pattern,
+            1 => DaysOfWeekKind::Star,
+            2 => DaysOfWeekKind::Ignored,
+            3 => DaysOfWeekKind::Last,
+            4 => DaysOfWeekKind::Nth,
+            5 => DaysOfWeekKind::Specials(DowSpecials(read_u64(reader.take(DOW_SPECIALS_LEN)?))),
+            _ => return Err(CronDecodeError::InvalidValue),
+        };
+        let day_policy = match months >> POLICY_SHIFT {
+            0 => DayPolicy::Or,
+            1 => DayPolicy::And,
+            2 => DayPolicy::QuartzStrict,
+            _ => return Err(CronDecodeError::InvalidValue),
+        };
+        if !reader.0.is_empty() {
+            return Err(CronDecodeError::InvalidLength);
+        }
+
+        let cron = Cron {
+            minutes: Minutes(minutes),
+            hours: Hours(hours),
+            dom: DaysOfMonth(dom_kind, dom_value),
+            months: Months(months & Months::ALL),
+            dow: DaysOfWeek(dow_kind, dow_value),
+            weeks: Weeks(weeks),
+            day_policy,
+        };
+        let valid = is_mask(cron.minutes.0, Minutes::ALL)
+            && is_mask(cron.hours.0.into(), Hours::ALL.into())
+            && is_mask(cron.months.0.into(), Months::ALL.into())
+            && is_mask(cron.weeks.0, Weeks::ALL)
+            && is_valid_dom(&cron.dom)
+            && is_valid_dow(&cron.dow)
+            && (cron.day_policy != DayPolicy::QuartzStrict
+                || cron.dom.kind() == DaysOfMonthKind::Ignored
+                || cron.dow.kind() == DaysOfWeekKind::Ignored);
+        if valid {
+            Ok(cron)
+        } else {
+            Err(CronDecodeError::InvalidValue)
+        }
+    }
+
+    /// Returns a key identifying the schedule, for deduplicating cron values across processes
+    /// and versions of the crate. Unlike the `Hash` implementation, the key is stable: two
+    /// cron values get the same key if and only if they're equal, which is when they were
+    /// compiled with the same [`DayPolicy`] from expressions matching the same times, like
+    /// `*/15 * * * *` and `0,15,30,45 * * * *`, and later versions of the crate will always
+    /// compute the same key for them.
+    ///
+    /// The key is the 128-bit FNV-1a hash of the version 1 encoding written by
+    /// [`Cron::to_bytes`], so it can also be computed by other implementations.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let a: Cron = "*/15 * * * *".parse().expect("Couldn't parse expression!");
+    /// let b: Cron = "0,15,30,45 * * * *".parse().expect("Couldn't parse expression!");
+    /// let c: Cron = "0,15,30 * * * *".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(a.canonical_key(), b.canonical_key());
+    /// assert_ne!(a.canonical_key(), c.canonical_key());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn canonical_key(&self) -> u128 {
+        // the key is stable, so a new version of the encoding must keep hashing version 1
+        fnv1a_128(&self.to_bytes())
+    }
+}
+
+/// Computes the 128-bit FNV-1a hash of some bytes
+#[cfg(feature = "alloc")]
+fn fnv1a_128(bytes: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Reads the next parts of an encoded value
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CronDecodeError> {
+        if self.0.len() < len {
+            return Err(CronDecodeError::InvalidLength);
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+}
+
+/// Reads a little endian integer of up to 8 bytes
+fn read_u64(bytes: &[u8]) -> u64 {
+    let mut buf = [0; 8];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    u64::from_le_bytes(buf)
+}
+
+#[cfg(feature = "alloc")]
+fn dom_tag(kind: DaysOfMonthKind) -> u8 {
+    match kind {
+        DaysOfMonthKind::Pattern => 0,
+        DaysOfMonthKind::Star => 1,
+        DaysOfMonthKind::Ignored => 2,
+        DaysOfMonthKind::Last => 3,
+        DaysOfMonthKind::Weekday => 4,
+        DaysOfMonthKind::Weekdays => 5,
+        DaysOfMonthKind::LastWeekday => 6,
+        DaysOfMonthKind::BusinessDay => 7,
+        DaysOfMonthKind::LastWeekdayOffset => 8,
+        DaysOfMonthKind::Specials(_) => 9,
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn dow_tag(kind: DaysOfWeekKind) -> u8 {
+    match kind {
+        DaysOfWeekKind::Pattern => 0,
+        DaysOfWeekKind::Star => 1,
+        DaysOfWeekKind::Ignored => 2,
+        DaysOfWeekKind::Last => 3,
+        DaysOfWeekKind::Nth => 4,
+        DaysOfWeekKind::Specials(_) => 5,
+    }
+}
+
+/// Returns whether a mask has at least one value set and none outside of `all`
+fn is_mask(bits: u64, all: u64) -> bool {
+    bits != 0 && bits & !all == 0
+}
+
+/// Returns whether the value of a special day of the month expression is in range for its kind
+fn is_dom_special(kind: DaysOfMonthKind, value: u32) -> bool {
+    match kind {
+        DaysOfMonthKind::Last | DaysOfMonthKind::LastWeekday => value <= 30,
+        DaysOfMonthKind::Weekday => (1..=31).contains(&value),
+        DaysOfMonthKind::BusinessDay => (1..=23).contains(&value),
+        DaysOfMonthKind::LastWeekdayOffset => (1..=30).contains(&value),
+        _ => false,
+    }
+}
+
+fn is_valid_dom(dom: &DaysOfMonth) -> bool {
+    let all = u64::from(DaysOfMonth::DAY_BITS);
+    match dom.kind() {
+        DaysOfMonthKind::Pattern => {
+            let flag = DaysOfMonth::FEB_29_LAST;
+            is_mask((dom.1 & !flag).into(), all) && (dom.1 & flag == 0 || dom.1 & (1 << 28) != 0)
+        }
+        DaysOfMonthKind::Star | DaysOfMonthKind::Ignored => dom.1 == 0,
+        // a single closest weekday is compiled into a 'W' expression
+        DaysOfMonthKind::Weekdays => is_mask(dom.1.into(), all) && dom.1.count_ones() > 1,
+        DaysOfMonthKind::Specials(specials) => {
+            let weekdays = specials.0[DomSpecials::index(DaysOfMonthKind::Weekday)];
+            let count = specials.0.iter().map(|bits| bits.count_ones()).sum::<u32>();
+            // lists of one expression or only closest weekdays are compiled into other kinds
+            dom.1 == 0
+                && count > 1
+                && count != weekdays.count_ones()
+                && specials.entries().all(|day| is_dom_special(day.0, day.1))
+        }
+        kind => is_dom_special(kind, dom.1),
+    }
+}
+
+/// Returns whether the value of a special day of the week expression is in range for its kind
+fn is_dow_special(kind: DaysOfWeekKind, value: u8) -> bool {
+    match kind {
+        DaysOfWeekKind::Last => value < 7,
+        DaysOfWeekKind::Nth => {
+            value & DaysOfWeek::ONE_DAY_BITS < 7 && (1..=5).contains(&(value >> 3))
+        }
+        _ => false,
+    }
+}
+
+fn is_valid_dow(dow: &DaysOfWeek) -> bool {
+    match dow.kind() {
+        DaysOfWeekKind::Pattern => is_mask(dow.1.into(), DaysOfWeek::DAY_BITS.into()),
+        DaysOfWeekKind::Star | DaysOfWeekKind::Ignored => dow.1 == 0,
+        DaysOfWeekKind::Specials(specials) => {
+            dow.1 == 0
+                && specials.0.count_ones() > 1
+                && specials.entries().all(|day| is_dow_special(day.0, day.1))
+        }
+        kind => is_dow_special(kind, dow.1),
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::parse::Dialect;
+    use crate::CronOptions;
+
+    fn encode(expr: &str) -> Vec<u8> {
+        Cron::parse_with(expr, Dialect::Extended)
+            .unwrap()
+            .to_bytes()
+    }
+
+    #[track_caller]
+    fn round_trip(expr: &str, len: usize) {
+        let cron = Cron::parse_with(expr, Dialect::Extended).unwrap();
+        let bytes = cron.to_bytes();
+        assert_eq!(bytes.len(), len, "{}", expr);
+        assert_eq!(Cron::from_bytes(&bytes), Ok(cron), "{}", expr);
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip("* * * * *", 20);
+        round_trip("*/5 9-17 * JAN,JUL MON-FRI", 20);
+        round_trip("0 0 L-3 * ?", 20);
+        round_trip("0 0 LW * ?", 20);
+        round_trip("0 0 L-2W * ?", 20);
+        round_trip("0 0 LW-2 * ?", 20);
+        round_trip("0 0 15W * ?", 20);
+        round_trip("0 0 1-7W * ?", 20);
+        round_trip("0 0 3B * ?", 20);
+        round_trip("0 0 ? * 6L", 20);
+        round_trip("0 0 ? * MON#2", 20);
+        round_trip("0 0 * * * W1-10", 27);
+        round_trip("0 0 1W,L * ?", 40);
+        round_trip("0 0 ? * MON#1,FRIL", 28);
+        round_trip("0 0 1W,L * MON#1,FRIL W2/2", 55);
+    }
+
+    #[test]
+    fn keeps_day_policy() {
+        let options = CronOptions {
+            day_policy: DayPolicy::And,
+            ..CronOptions::new()
+        };
+        let cron = Cron::with_options("0 0 1-7 * MON".parse().unwrap(), options).unwrap();
+        assert_eq!(Cron::from_bytes(&cron.to_bytes()), Ok(cron));
+    }
+
+    #[test]
+    fn canonical_keys_are_stable() {
+        let key = |expr: &str| {
+            Cron::parse_with(expr, Dialect::Extended)
+                .unwrap()
+                .canonical_key()
+        };
+        assert_eq!(key("* * * * *"), 0x91d1_1753_0d0a_d605_f82a_2e3b_78a8_81f4);
+        assert_eq!(
+            key("*/15 9-17 * * MON-FRI"),
+            0x7c16_0c3a_560c_344e_61c7_7341_b1a5_a04f
+        );
+        assert_eq!(
+            key("0 0 1W,L * MON#1,FRIL W2/2"),
+            0x30ae_1f17_dd5f_4d11_e5ba_e943_f5e5_9704
+        );
+        assert_eq!(key("*/15 9-17 * * MON-FRI"), key("0,15,30,45 9-17 * * 2-6"));
+    }
+
+    #[test]
+    fn rejects_bad_lengths_and_versions() {
+        let bytes = encode("0 0 1W,L * ?");
+        assert_eq!(
+            Cron::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(CronDecodeError::InvalidLength)
+        );
+
+        let mut long = bytes.clone();
+        long.push(0);
+        assert_eq!(Cron::from_bytes(&long), Err(CronDecodeError::InvalidLength));
+
+        let mut version = bytes;
+        version[0] = 2;
+        assert_eq!(
+            Cron::from_bytes(&version),
+            Err(CronDecodeError::UnsupportedVersion(2))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let invalid = |expr: &str, changes: &[(usize, u8)]| {
+            let mut bytes = encode(expr);
+            for &(i, value) in changes {
+                bytes[i] = value;
+            }
+            assert_eq!(
+                Cron::from_bytes(&bytes),
+                Err(CronDecodeError::InvalidValue),
+                "{} {:?}",
+                expr,
+                changes
+            );
+        };
+        // an unknown day of month kind
+        invalid("* * * * *", &[(1, 0x1F)]);
+        // minute 63
+        invalid("* * * * *", &[(9, 0xFF)]);
+        // no hours
+        invalid("* 0 * * *", &[(10, 0)]);
+        // an unknown day policy
+        invalid("* * * * *", &[(14, 0x3F)]);
+        // a last day of the week expression for day 8
+        invalid("0 0 ? * 6L", &[(19, 7)]);
+        // an 'L-31' expression
+        invalid("0 0 L-30 * ?", &[(15, 31)]);
+        // a single closest weekday written as a list of them
+        invalid("0 0 1-7W * ?", &[(15, 1)]);
+        // QuartzStrict without an ignored day field
+        invalid("0 0 * * ?", &[(14, 0x2F), (1, 0x11)]);
+        // every week written as a restriction
+        invalid(
+            "0 0 * * * W1-10",
+            &[
+                (20, 0xFF),
+                (21, 0xFF),
+                (22, 0xFF),
+                (23, 0xFF),
+                (24, 0xFF),
+                (25, 0xFF),
+                (26, 0x1F),
+            ],
+        );
+    }
+}
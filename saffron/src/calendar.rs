@@ -0,0 +1,574 @@
+//! Calendars of dates excluded from a cron value, like the calendars of a Quartz trigger.
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, collections::BTreeSet};
+
+use crate::MINUTES_IN_DAY;
+use crate::{date_time, minute_bounds, minute_of_day, next_minute, previous_minute};
+use crate::{Cron, DaysOfMonth, DaysOfMonthKind};
+use chrono::prelude::*;
+use core::cmp;
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::{Bound, RangeBounds};
+
+#[cfg(feature = "std")]
+use std::{borrow::Cow, collections::BTreeSet};
+
+/// A set of dates a cron value shouldn't fire on, such as public holidays.
+///
+/// Calendars can be combined as a tuple, which excludes the dates excluded by either calendar.
+pub trait Calendar {
+    /// Returns whether the date is excluded.
+    fn is_excluded(&self, date: NaiveDate) -> bool;
+}
+
+impl<C: Calendar + ?Sized> Calendar for &C {
+    fn is_excluded(&self, date: NaiveDate) -> bool {
+        (**self).is_excluded(date)
+    }
+}
+
+impl<A: Calendar, B: Calendar> Calendar for (A, B) {
+    fn is_excluded(&self, date: NaiveDate) -> bool {
+        self.0.is_excluded(date) || self.1.is_excluded(date)
+    }
+}
+
+/// A calendar excluding days of the week.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct WeeklyCalendar(u8);
+
+impl WeeklyCalendar {
+    /// Creates a calendar that doesn't exclude any days.
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Creates a calendar excluding Saturday and Sunday.
+    pub const fn weekends() -> Self {
+        Self(1 << Weekday::Sat as u8 | 1 << Weekday::Sun as u8)
+    }
+
+    /// Excludes the day of the week.
+    pub fn exclude(&mut self, day: Weekday) {
+        self.0 |= 1 << day as u8;
+    }
+}
+
+impl Calendar for WeeklyCalendar {
+    fn is_excluded(&self, date: NaiveDate) -> bool {
+        self.0 & (1 << date.weekday() as u8) != 0
+    }
+}
+
+/// A calendar excluding the same days every year, like January 1st.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct AnnualCalendar([u32; 12]);
+
+impl AnnualCalendar {
+    /// Creates a calendar that doesn't exclude any days.
+    pub const fn new() -> Self {
+        Self([0; 12])
+    }
+
+    /// Excludes the day of the month (1-31) in the month (1-12) every year.
+    ///
+    /// # Panics
+    /// Panics if the month or day is out of range.
+    pub fn exclude(&mut self, month: u32, day: u32) {
+        assert!((1..=12).contains(&month), "month {} is out of range", month);
+        assert!((1..=31).contains(&day), "day {} is out of range", day);
+        self.0[month as usize - 1] |= 1 << (day - 1);
+    }
+}
+
+impl Calendar for AnnualCalendar {
+    fn is_excluded(&self, date: NaiveDate) -> bool {
+        self.0[date.month0() as usize] & (1 << date.day0()) != 0
+    }
+}
+
+/// A calendar excluding a set of dates, like the public holidays of a year.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct HolidayCalendar {
+    dates: BTreeSet<NaiveDate>,
+}
+
+impl HolidayCalendar {
+    /// Creates a calendar that doesn't exclude any days.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Excludes the date.
+    pub fn exclude(&mut self, date: NaiveDate) {
+        self.dates.insert(date);
+    }
+
+    /// Returns the excluded dates in order.
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.dates.iter().copied()
+    }
+}
+
+impl Calendar for HolidayCalendar {
+    fn is_excluded(&self, date: NaiveDate) -> bool {
+        self.dates.contains(&date)
+    }
+}
+
+impl FromIterator<NaiveDate> for HolidayCalendar {
+    fn from_iter<I: IntoIterator<Item = NaiveDate>>(iter: I) -> Self {
+        Self {
+            dates: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<NaiveDate> for HolidayCalendar {
+    fn extend<I: IntoIterator<Item = NaiveDate>>(&mut self, iter: I) {
+        self.dates.extend(iter)
+    }
+}
+
+impl Cron {
+    /// Pairs the cron value with a calendar of dates it shouldn't match on. Dates are checked in
+    /// UTC.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, HolidayCalendar};
+    /// use chrono::prelude::*;
+    ///
+    /// let mut holidays = HolidayCalendar::new();
+    /// holidays.exclude(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap());
+    ///
+    /// let cron = "0 9 * * MON-FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let cron = cron.with_calendar(holidays);
+    ///
+    /// // 2020-12-25 is a Friday
+    /// let start = Utc.with_ymd_and_hms(2020, 12, 24, 12, 0, 0).unwrap();
+    /// assert_eq!(cron.next_from(start), Some(Utc.with_ymd_and_hms(2020, 12, 28, 9, 0, 0).unwrap()));
+    /// ```
+    pub fn with_calendar<C: Calendar>(self, calendar: C) -> CalendarCron<C> {
+        CalendarCron {
+            cron: self,
+            calendar,
+        }
+    }
+}
+
+/// A cron value that doesn't match on the dates excluded by a calendar. Created with
+/// [`Cron::with_calendar`].
+///
+/// Business days (`3B` and `LW-1`) are counted skipping the excluded dates as well as weekends,
+/// so a holiday early in the month moves the day matched later, and a holiday late in the month
+/// moves a day counted back from the last weekday earlier.
+///
+/// Searching for the next time skips excluded dates one day at a time, so a calendar that
+/// excludes every date the cron value matches on makes searches run to the end of the
+/// supported range of dates.
+///
+/// [`Cron::with_calendar`]: struct.Cron.html#method.with_calendar
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct CalendarCron<C> {
+    cron: Cron,
+    calendar: C,
+}
+
+impl<C: Calendar> CalendarCron<C> {
+    /// Returns the underlying cron value.
+    pub fn cron(&self) -> &Cron {
+        &self.cron
+    }
+
+    /// Returns the calendar of excluded dates.
+    pub fn calendar(&self) -> &C {
+        &self.calendar
+    }
+
+    /// Returns whether the cron value matches the given time and its date isn't excluded.
+    pub fn contains(&self, dt: DateTime<Utc>) -> bool {
+        self.for_month(dt.date_naive()).contains(dt) && !self.calendar.is_excluded(dt.date_naive())
+    }
+
+    /// Returns the next time the cron value will match on a date that isn't excluded, including
+    /// the given date.
+    pub fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let (start, end) = minute_bounds((Bound::Included(start), Bound::Unbounded))?;
+        self.find_next(start, end)
+    }
+
+    /// Returns the next time the cron value will match on a date that isn't excluded, after the
+    /// given date.
+    pub fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let (start, end) = minute_bounds((Bound::Excluded(start), Bound::Unbounded))?;
+        self.find_next(start, end)
+    }
+
+    /// Creates an iterator of date times that match with the cron value on dates that aren't
+    /// excluded. This is short for `iter((Bound::Included(start), Bound::Unbounded))` or
+    /// `iter(start..)`.
+    #[inline]
+    pub fn iter_from(self, start: DateTime<Utc>) -> CalendarTimesIter<C> {
+        self.iter((Bound::Included(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match with the cron value on dates that aren't
+    /// excluded, after the given date. This is short for
+    /// `iter((Bound::Excluded(start), Bound::Unbounded))`.
+    #[inline]
+    pub fn iter_after(self, start: DateTime<Utc>) -> CalendarTimesIter<C> {
+        self.iter((Bound::Excluded(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match with the cron value on dates that aren't
+    /// excluded, using the given start and end range bounds.
+    pub fn iter<R: RangeBounds<DateTime<Utc>>>(self, bounds: R) -> CalendarTimesIter<C> {
+        let bounded = !matches!(bounds.start_bound(), Bound::Unbounded)
+            && !matches!(bounds.end_bound(), Bound::Unbounded);
+        CalendarTimesIter {
+            bounds: minute_bounds(bounds),
+            bounded,
+            cron: self,
+        }
+    }
+
+    /// Whether the cron value matches a business day, which moves with the excluded dates
+    #[inline]
+    fn counts_business_days(&self) -> bool {
+        fn is_business_day(day: &DaysOfMonth) -> bool {
+            matches!(
+                day.kind(),
+                DaysOfMonthKind::BusinessDay | DaysOfMonthKind::LastWeekdayOffset
+            )
+        }
+
+        match self.cron.dom.kind() {
+            DaysOfMonthKind::Specials(specials) => {
+                specials.entries().any(|day| is_business_day(&day))
+            }
+            _ => is_business_day(&self.cron.dom),
+        }
+    }
+
+    /// Returns the cron value to match in the month of the date. A business day is resolved
+    /// into the day it falls on that month, counting the excluded dates as holidays. In a list
+    /// of special expressions, every expression is resolved into the days it falls on.
+    fn for_month(&self, date: NaiveDate) -> Cow<'_, Cron> {
+        if !self.counts_business_days() {
+            return Cow::Borrowed(&self.cron);
+        }
+
+        let days = || {
+            date.with_day(1)
+                .into_iter()
+                .flat_map(|first| (0..31).filter_map(move |day0| first.with_day0(day0)))
+        };
+        let is_business_day = |day: &NaiveDate| {
+            !matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+                && !self.calendar.is_excluded(*day)
+        };
+        let resolve = |dom: &DaysOfMonth| {
+            let n = dom.one_value() as usize;
+            let day = match dom.kind() {
+                DaysOfMonthKind::BusinessDay => days().filter(is_business_day).nth(n - 1),
+                DaysOfMonthKind::LastWeekdayOffset => days().filter(is_business_day).nth_back(n),
+                _ => {
+                    return days()
+                        .filter(|&day| dom.contains_date(day))
+                        .fold(0, |bits, day| bits | 1 << day.day0())
+                }
+            };
+            day.map_or(0, |day| 1 << day.day0())
+        };
+        let bits = match self.cron.dom.kind() {
+            DaysOfMonthKind::Specials(specials) => {
+                specials.entries().fold(0, |bits, day| bits | resolve(&day))
+            }
+            _ => resolve(&self.cron.dom),
+        };
+        Cow::Owned(Cron {
+            dom: DaysOfMonth(DaysOfMonthKind::Pattern, bits),
+            ..self.cron.clone()
+        })
+    }
+
+    /// Finds the next time from the start to the end (inclusive) on a date that isn't excluded
+    fn find_next(&self, mut start: DateTime<Utc>, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if !self.cron.any() {
+            return None;
+        }
+
+        let business_days = self.counts_business_days();
+        while start <= end {
+            // business days are resolved a month at a time
+            let month_end = match first_of_next_month(start.date_naive())
+                .and_then(|next| previous_minute(date_time(next, NaiveTime::MIN)))
+            {
+                Some(last) if business_days => cmp::min(last, end),
+                _ => end,
+            };
+            match self
+                .for_month(start.date_naive())
+                .find_next(start, month_end)
+            {
+                Some(next) if !self.calendar.is_excluded(next.date_naive()) => return Some(next),
+                // skip the rest of the excluded day
+                Some(next) => start = date_time(next.date_naive().succ_opt()?, NaiveTime::MIN),
+                None if month_end < end => start = next_minute(month_end)?,
+                None => return None,
+            }
+        }
+        None
+    }
+
+    /// Finds the previous time from the end to the start (inclusive) on a date that isn't
+    /// excluded
+    fn find_previous(&self, start: DateTime<Utc>, mut end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if !self.cron.any() {
+            return None;
+        }
+
+        let business_days = self.counts_business_days();
+        while start <= end {
+            let month_start = match end.date_naive().with_day(1) {
+                Some(first) if business_days => cmp::max(date_time(first, NaiveTime::MIN), start),
+                _ => start,
+            };
+            match self
+                .for_month(end.date_naive())
+                .find_previous(month_start, end)
+            {
+                Some(previous) if !self.calendar.is_excluded(previous.date_naive()) => {
+                    return Some(previous)
+                }
+                // skip the rest of the excluded day
+                Some(previous) => {
+                    let last_minute = minute_of_day(MINUTES_IN_DAY - 1);
+                    end = date_time(previous.date_naive().pred_opt()?, last_minute)
+                }
+                None if month_start > start => end = previous_minute(month_start)?,
+                None => return None,
+            }
+        }
+        None
+    }
+}
+
+/// Returns the first day of the month after the month of the date
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        month => (date.year(), month + 1),
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+}
+
+/// An iterator over the times matching a cron value on the dates a calendar doesn't exclude.
+/// Created with [`CalendarCron::iter`], [`CalendarCron::iter_from`], and
+/// [`CalendarCron::iter_after`].
+///
+/// [`CalendarCron::iter`]: struct.CalendarCron.html#method.iter
+/// [`CalendarCron::iter_from`]: struct.CalendarCron.html#method.iter_from
+/// [`CalendarCron::iter_after`]: struct.CalendarCron.html#method.iter_after
+pub struct CalendarTimesIter<C> {
+    cron: CalendarCron<C>,
+    bounds: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    bounded: bool,
+}
+
+impl<C: Calendar> CalendarTimesIter<C> {
+    /// Returns the underlying cron value.
+    pub fn cron(&self) -> &Cron {
+        self.cron.cron()
+    }
+
+    /// Returns the calendar of excluded dates.
+    pub fn calendar(&self) -> &C {
+        self.cron.calendar()
+    }
+}
+
+impl<C: Calendar> Iterator for CalendarTimesIter<C> {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.bounds?;
+        let next = self.cron.find_next(start, end);
+        self.bounds = next.and_then(next_minute).map(|new_start| (new_start, end));
+        next
+    }
+
+    /// Returns an upper bound from the times the cron value matches without the calendar. A
+    /// business day moves with the excluded dates, so then the number of times isn't bounded.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.cron.counts_business_days() {
+            return (0, None);
+        }
+
+        let (_, upper) = self.cron.cron.times_size_hint(self.bounds, self.bounded);
+        (0, upper)
+    }
+}
+
+impl<C: Calendar> DoubleEndedIterator for CalendarTimesIter<C> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.bounds?;
+        let previous = self.cron.find_previous(start, end);
+        self.bounds = previous
+            .and_then(previous_minute)
+            .map(|new_end| (start, new_end));
+        previous
+    }
+}
+
+impl<C: Calendar> FusedIterator for CalendarTimesIter<C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn calendars() {
+        let weekends = WeeklyCalendar::weekends();
+        let mut fridays = WeeklyCalendar::new();
+        fridays.exclude(Weekday::Fri);
+        let mut new_year = AnnualCalendar::new();
+        new_year.exclude(1, 1);
+        let holidays: HolidayCalendar = [NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()]
+            .iter()
+            .copied()
+            .collect();
+
+        // 2021-01-01 is a Friday
+        let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        assert!(!weekends.is_excluded(date));
+        assert!(weekends.is_excluded(NaiveDate::from_ymd_opt(2021, 1, 2).unwrap()));
+        assert!(weekends.is_excluded(NaiveDate::from_ymd_opt(2021, 1, 3).unwrap()));
+        assert!(fridays.is_excluded(date));
+        assert!(new_year.is_excluded(date));
+        assert!(new_year.is_excluded(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()));
+        assert!(!new_year.is_excluded(NaiveDate::from_ymd_opt(2022, 1, 2).unwrap()));
+        assert!(holidays.is_excluded(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()));
+        assert!(!holidays.is_excluded(NaiveDate::from_ymd_opt(2021, 12, 25).unwrap()));
+        assert!((&weekends, &holidays).is_excluded(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()));
+        assert!((&weekends, &holidays).is_excluded(NaiveDate::from_ymd_opt(2020, 12, 26).unwrap()));
+    }
+
+    #[test]
+    fn skips_excluded_dates() {
+        let mut new_year = AnnualCalendar::new();
+        new_year.exclude(1, 1);
+        let holidays: HolidayCalendar = [NaiveDate::from_ymd_opt(2020, 12, 25).unwrap()]
+            .iter()
+            .copied()
+            .collect();
+        let cron = "0 9,17 * * MON-FRI"
+            .parse::<Cron>()
+            .unwrap()
+            .with_calendar((new_year, holidays));
+
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 12, 24, 9, 0, 0).unwrap()));
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2020, 12, 25, 9, 0, 0).unwrap()));
+        assert_eq!(
+            cron.next_after(Utc.with_ymd_and_hms(2020, 12, 24, 17, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2020, 12, 28, 9, 0, 0).unwrap())
+        );
+
+        let start = Utc.with_ymd_and_hms(2020, 12, 24, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 1, 5, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2020, 12, 24, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 24, 17, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 28, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 28, 17, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 29, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 29, 17, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 30, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 30, 17, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 31, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 12, 31, 17, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 1, 4, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 1, 4, 17, 0, 0).unwrap(),
+        ];
+        assert_eq!(cron.clone().iter(start..end).collect::<Vec<_>>(), expected);
+
+        let mut reversed = cron.iter(start..end).rev().collect::<Vec<_>>();
+        reversed.reverse();
+        assert_eq!(reversed, expected);
+    }
+
+    #[test]
+    fn business_days_skip_excluded_dates() {
+        let mut new_year = AnnualCalendar::new();
+        new_year.exclude(1, 1);
+        let cron = "0 9 1B * *"
+            .parse::<Cron>()
+            .unwrap()
+            .with_calendar(new_year);
+
+        // the first is a Friday, but it's a holiday
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2021, 1, 1, 9, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2021, 1, 4, 9, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2021, 2, 1, 9, 0, 0).unwrap()));
+        assert_eq!(
+            cron.next_after(Utc.with_ymd_and_hms(2020, 12, 1, 9, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2021, 1, 4, 9, 0, 0).unwrap())
+        );
+
+        let start = Utc.with_ymd_and_hms(2020, 12, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 3, 1, 0, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2020, 12, 1, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 1, 4, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 2, 1, 9, 0, 0).unwrap(),
+        ];
+        assert_eq!(cron.clone().iter(start..end).collect::<Vec<_>>(), expected);
+
+        let mut reversed = cron.iter(start..end).rev().collect::<Vec<_>>();
+        reversed.reverse();
+        assert_eq!(reversed, expected);
+    }
+
+    #[test]
+    fn weekdays_before_last_skip_excluded_dates() {
+        let mut new_years_eve = AnnualCalendar::new();
+        new_years_eve.exclude(12, 31);
+        let cron = "0 9 LW-1 * *"
+            .parse::<Cron>()
+            .unwrap()
+            .with_calendar(new_years_eve);
+
+        // the 31st is a Friday, but it's a holiday, so the 30th is the last business day
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2021, 12, 30, 9, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2021, 12, 29, 9, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2021, 11, 29, 9, 0, 0).unwrap()));
+        assert_eq!(
+            cron.next_after(Utc.with_ymd_and_hms(2021, 11, 30, 9, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2021, 12, 29, 9, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn lists_resolve_business_days() {
+        let mut new_years_day = AnnualCalendar::new();
+        new_years_day.exclude(1, 1);
+        let cron = "0 9 1B,L * *"
+            .parse::<Cron>()
+            .unwrap()
+            .with_calendar(new_years_day);
+
+        // the 1st is a Friday, but it's a holiday, so the next Monday is the first business day
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2021, 1, 1, 9, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2021, 1, 4, 9, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2021, 1, 31, 9, 0, 0).unwrap()));
+        assert_eq!(
+            cron.next_after(Utc.with_ymd_and_hms(2021, 1, 4, 9, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2021, 1, 31, 9, 0, 0).unwrap())
+        );
+    }
+}
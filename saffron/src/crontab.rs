@@ -0,0 +1,383 @@
+//! Parsing of whole crontab files, like the ones edited with `crontab -e` or the system crontab
+//! in `/etc/crontab`.
+//!
+//! Every line of a crontab is one of:
+//!  * A blank line or a comment starting with `#`, which are skipped
+//!  * An environment assignment like `MAILTO=ops@example.com` or `PATH = "/usr/bin:/bin"`
+//!  * A schedule in the [`Dialect::Unix`] dialect or a shortcut like `@daily`, followed by the
+//!    user to run the command as in system crontabs, and the command itself
+//!
+//! A line that fails to parse is reported as an error without stopping the lines after it, so a
+//! crontab can be migrated even if some of its entries need to be fixed by hand. The command is
+//! kept exactly as written, including any `%` characters which cron turns into newlines.
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::parse::{is_blank, CronExpr, CronParseError, Dialect, ParseOptions};
+use crate::spanned::split_fields;
+use core::fmt::{self, Display, Formatter};
+
+/// The layout of the lines in a crontab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CrontabFormat {
+    /// A user's crontab, where each schedule is followed by the command
+    User,
+    /// A system crontab like `/etc/crontab` or the files in `/etc/cron.d`, where each schedule
+    /// is followed by the user to run the command as and then the command
+    System,
+}
+
+/// A command and the schedule it runs on, read from a line of a crontab.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CrontabEntry {
+    /// The schedule the command runs on
+    pub schedule: CronExpr,
+    /// The user the command runs as. This is only set for entries of system crontabs.
+    pub user: Option<String>,
+    /// The command, as written in the crontab
+    pub command: String,
+    /// The line of the entry, starting from 1
+    pub line: usize,
+}
+
+/// An environment variable set by a line of a crontab, which applies to the entries after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CrontabVariable {
+    /// The name of the variable
+    pub name: String,
+    /// The value of the variable without any quotes around it
+    pub value: String,
+    /// The line of the assignment, starting from 1
+    pub line: usize,
+}
+
+/// The reason a line of a crontab failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CrontabErrorKind {
+    /// The schedule of the line is invalid. The position of the error is the byte offset in the
+    /// line.
+    InvalidSchedule(CronParseError),
+    /// The line runs its command at startup with `@reboot`, which isn't a schedule
+    Reboot,
+    /// The line of a system crontab has no user after its schedule
+    MissingUser,
+    /// The line has no command after its schedule
+    MissingCommand,
+}
+
+/// An error for a line of a crontab that failed to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CrontabError {
+    /// The line that failed to parse, starting from 1
+    pub line: usize,
+    /// Why the line failed to parse
+    pub kind: CrontabErrorKind,
+}
+
+impl Display for CrontabError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.kind {
+            CrontabErrorKind::InvalidSchedule(err) => write!(f, "Line {}: {}", self.line, err),
+            CrontabErrorKind::Reboot => write!(
+                f,
+                "Line {}: @reboot runs at startup and can't be written as a schedule",
+                self.line
+            ),
+            CrontabErrorKind::MissingUser => write!(f, "Line {}: Missing user", self.line),
+            CrontabErrorKind::MissingCommand => write!(f, "Line {}: Missing command", self.line),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CrontabError {}
+
+/// The entries, environment variables, and errors of a parsed crontab, each in the order of
+/// their lines.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Crontab {
+    /// The commands of the crontab and their schedules
+    pub entries: Vec<CrontabEntry>,
+    /// The environment variables set in the crontab
+    pub variables: Vec<CrontabVariable>,
+    /// The lines that failed to parse
+    pub errors: Vec<CrontabError>,
+}
+
+/// Parses every line of a crontab.
+///
+/// # Example
+/// ```
+/// use saffron::crontab::{self, CrontabErrorKind, CrontabFormat};
+///
+/// let text = "\
+/// ## m h dom mon dow command
+/// MAILTO=ops@example.com
+/// 0 9 * * 1-5\t/usr/local/bin/report --daily
+/// @weekly /usr/local/bin/cleanup
+/// 0 25 * * * /usr/local/bin/typo
+/// ";
+///
+/// let crontab = crontab::parse(text, CrontabFormat::User);
+/// assert_eq!(crontab.variables[0].value, "ops@example.com");
+///
+/// let entry = &crontab.entries[0];
+/// assert_eq!(entry.schedule.to_string(), "0 9 * * 2-6");
+/// assert_eq!(entry.command, "/usr/local/bin/report --daily");
+/// assert_eq!(entry.line, 3);
+/// assert_eq!(crontab.entries[1].command, "/usr/local/bin/cleanup");
+///
+/// assert_eq!(crontab.errors[0].line, 5);
+/// assert!(matches!(crontab.errors[0].kind, CrontabErrorKind::InvalidSchedule(_)));
+/// ```
+pub fn parse(text: &str, format: CrontabFormat) -> Crontab {
+    let mut crontab = Crontab::default();
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let content = line.trim_start_matches(is_blank);
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+
+        if let Some((name, value)) = variable(content) {
+            crontab.variables.push(CrontabVariable {
+                name: name.to_string(),
+                value: value.to_string(),
+                line: line_number,
+            });
+            continue;
+        }
+
+        match entry(line, format) {
+            Ok((schedule, user, command)) => crontab.entries.push(CrontabEntry {
+                schedule,
+                user: user.map(str::to_string),
+                command: command.to_string(),
+                line: line_number,
+            }),
+            Err(kind) => crontab.errors.push(CrontabError {
+                line: line_number,
+                kind,
+            }),
+        }
+    }
+    crontab
+}
+
+/// Reads an environment assignment like `NAME = value` or `NAME="value"`, returning the name and
+/// the unquoted value. Like in cron, a line is an assignment if its first word is followed by an
+/// `=`, so a schedule is never mistaken for one.
+fn variable(content: &str) -> Option<(&str, &str)> {
+    let name_len = content
+        .find(|c: char| is_blank(c) || c == '=')
+        .unwrap_or(content.len());
+    let (name, rest) = content.split_at(name_len);
+    let rest = rest.trim_start_matches(is_blank);
+    if name.is_empty() || !rest.starts_with('=') {
+        return None;
+    }
+
+    let value = rest[1..].trim_matches(is_blank);
+    let unquoted = ['"', '\'']
+        .iter()
+        .find(|&&quote| value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote))
+        .map_or(value, |_| &value[1..value.len() - 1]);
+    Some((name, unquoted))
+}
+
+/// Reads the schedule, user, and command of an entry line.
+fn entry(
+    line: &str,
+    format: CrontabFormat,
+) -> Result<(CronExpr, Option<&str>, &str), CrontabErrorKind> {
+    let fields = split_fields(line);
+    let schedule_fields = if line.trim_start_matches(is_blank).starts_with('@') {
+        1
+    } else {
+        5
+    };
+
+    let schedule_end = fields
+        .get(schedule_fields - 1)
+        .map_or(line.len(), |field| field.end);
+    let schedule = &line[..schedule_end];
+    let schedule = match schedule.trim_start_matches(is_blank) {
+        "@reboot" => return Err(CrontabErrorKind::Reboot),
+        // cron's alias for @daily, which isn't a shortcut of its own
+        "@midnight" => "@daily",
+        _ => schedule,
+    };
+
+    let mut options = ParseOptions::new();
+    options.dialect = Dialect::Unix;
+    options.trim_whitespace = true;
+    let schedule = CronExpr::from_str_with_options(schedule, options)
+        .map_err(CrontabErrorKind::InvalidSchedule)?;
+
+    let mut rest = fields[schedule_fields..].iter();
+    let user = match format {
+        CrontabFormat::User => None,
+        CrontabFormat::System => match rest.next() {
+            Some(field) => Some(&line[field.clone()]),
+            None => return Err(CrontabErrorKind::MissingUser),
+        },
+    };
+    match rest.next() {
+        Some(command) => Ok((
+            schedule,
+            user,
+            line[command.start..].trim_end_matches(is_blank),
+        )),
+        None => Err(CrontabErrorKind::MissingCommand),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::FieldKind;
+
+    #[test]
+    fn entries() {
+        let text = "
+  # comment
+\t
+0 0 * * * /bin/daily  --flag %input%  \r
+ */15\t9-17 * JAN-MAR MON-FRI   echo \"hi\"
+@hourly   /bin/hourly
+@midnight /bin/midnight
+";
+        let crontab = parse(text, CrontabFormat::User);
+        assert!(crontab.errors.is_empty());
+        assert!(crontab.variables.is_empty());
+        assert_eq!(
+            crontab
+                .entries
+                .iter()
+                .map(|entry| (
+                    entry.schedule.to_string(),
+                    entry.command.as_str(),
+                    entry.line
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("0 0 * * *".to_string(), "/bin/daily  --flag %input%", 4),
+                ("0/15 9-17 * 1-3 2-6".to_string(), "echo \"hi\"", 5),
+                ("@hourly".to_string(), "/bin/hourly", 6),
+                ("@daily".to_string(), "/bin/midnight", 7),
+            ]
+        );
+        assert!(crontab.entries.iter().all(|entry| entry.user.is_none()));
+    }
+
+    #[test]
+    fn system_entries() {
+        let crontab = parse(
+            "17 * * * * root cd / && run-parts /etc/cron.hourly\n@weekly nobody /bin/true\n0 0 * * * root\n",
+            CrontabFormat::System,
+        );
+        assert_eq!(crontab.entries.len(), 2);
+        assert_eq!(crontab.entries[0].user.as_deref(), Some("root"));
+        assert_eq!(
+            crontab.entries[0].command,
+            "cd / && run-parts /etc/cron.hourly"
+        );
+        assert_eq!(crontab.entries[1].user.as_deref(), Some("nobody"));
+        assert_eq!(
+            crontab.errors,
+            [CrontabError {
+                line: 3,
+                kind: CrontabErrorKind::MissingCommand
+            }]
+        );
+
+        let crontab = parse("0 0 * * *", CrontabFormat::System);
+        assert_eq!(crontab.errors[0].kind, CrontabErrorKind::MissingUser);
+    }
+
+    #[test]
+    fn variables() {
+        let crontab = parse(
+            "SHELL=/bin/bash\n  PATH = \"/usr/bin:/bin\"\nEMPTY=\nGREETING='hello world'\nX =a=b\n",
+            CrontabFormat::User,
+        );
+        assert!(crontab.errors.is_empty());
+        assert_eq!(
+            crontab
+                .variables
+                .iter()
+                .map(|var| (var.name.as_str(), var.value.as_str(), var.line))
+                .collect::<Vec<_>>(),
+            [
+                ("SHELL", "/bin/bash", 1),
+                ("PATH", "/usr/bin:/bin", 2),
+                ("EMPTY", "", 3),
+                ("GREETING", "hello world", 4),
+                ("X", "a=b", 5),
+            ]
+        );
+
+        // the first word of a schedule is never followed by an '='
+        let crontab = parse("* * * * * FOO=bar /bin/run", CrontabFormat::User);
+        assert!(crontab.variables.is_empty());
+        assert_eq!(crontab.entries[0].command, "FOO=bar /bin/run");
+    }
+
+    #[test]
+    fn errors() {
+        let crontab = parse(
+            "0 0 * *\n 0 24 * * * /bin/late\n@reboot /bin/start\n@fortnightly /bin/x\n0 0 * * *\n0 0 * * * /bin/ok\n",
+            CrontabFormat::User,
+        );
+        assert_eq!(crontab.entries.len(), 1);
+        assert_eq!(crontab.entries[0].line, 6);
+        assert_eq!(
+            crontab.errors,
+            [
+                CrontabError {
+                    line: 1,
+                    kind: CrontabErrorKind::InvalidSchedule(CronParseError::MissingField {
+                        field: FieldKind::DaysOfWeek,
+                        at: 7
+                    })
+                },
+                CrontabError {
+                    line: 2,
+                    kind: CrontabErrorKind::InvalidSchedule(CronParseError::ValueOutOfRange {
+                        field: FieldKind::Hours,
+                        value: 24,
+                        min: 0,
+                        max: 23,
+                        at: 3
+                    })
+                },
+                CrontabError {
+                    line: 3,
+                    kind: CrontabErrorKind::Reboot
+                },
+                CrontabError {
+                    line: 4,
+                    kind: CrontabErrorKind::InvalidSchedule(CronParseError::UnknownShortcut)
+                },
+                CrontabError {
+                    line: 5,
+                    kind: CrontabErrorKind::MissingCommand
+                },
+            ]
+        );
+        assert_eq!(
+            crontab.errors[2].to_string(),
+            "Line 3: @reboot runs at startup and can't be written as a schedule"
+        );
+    }
+}
@@ -1,7 +1,28 @@
-use crate::describe::{display, Language};
+use crate::describe::{
+    display, dom_specials, dow_specials, is_marker, list, mark, marked_segments,
+    DescriptionSegment, Language, ListStyle, Parts, SegmentKind, Times,
+};
 use crate::parse::*;
-use chrono::NaiveTime;
-use core::fmt::{self, Display, Formatter};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+
+const LIST: ListStyle = ListStyle {
+    pair: " and ",
+    separator: ", ",
+    last: ", and ",
+};
+
+const LIST_NO_OXFORD_COMMA: ListStyle = ListStyle {
+    pair: " and ",
+    separator: ", ",
+    last: " and ",
+};
+
+/// Returns whether a step expression covers every value of its type (i.e. */5)
+fn full_range<E: ExprValue + PartialEq>(start: E, end: E) -> bool {
+    start == E::min() && end == E::max()
+}
 
 fn postfixed<T: Into<usize>>(x: T) -> impl Display {
     let x: usize = x.into();
@@ -34,18 +55,24 @@ fn weekday<T: Into<chrono::Weekday>>(x: T) -> impl Display {
 }
 
 /// Specifies whether to display times with a 12 hour or 24 hour clock.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum HourFormat {
     /// Format using a 12 hour clock (i.e. 6:30 PM)
+    #[default]
     Hour12,
     /// Format using a 24 hour clock (i.e. 18:30)
     Hour24,
 }
 
-impl Default for HourFormat {
-    fn default() -> Self {
-        HourFormat::Hour12
-    }
+/// Specifies how much detail descriptions include.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Describe every part of an expression in full (i.e. every 5th minute from 0 through 59)
+    #[default]
+    Verbose,
+    /// Abbreviate units and leave out the bounds of steps covering every value (i.e. every 5
+    /// min)
+    Terse,
 }
 
 /// English language formatting
@@ -54,6 +81,15 @@ impl Default for HourFormat {
 pub struct English {
     /// Configures how hours are formatted in descriptions
     pub hour: HourFormat,
+    /// Configures how much detail is included in descriptions
+    pub verbosity: Verbosity,
+    /// Whether to place a comma before the "and" ending a list of three or more items
+    pub oxford_comma: bool,
+    /// Whether to capitalize the first letter of descriptions
+    pub capitalize: bool,
+    /// Whether to end descriptions restricting the days with "of every month" when every month
+    /// is matched
+    pub month_suffix: bool,
 }
 
 impl English {
@@ -61,6 +97,10 @@ impl English {
     pub const fn new() -> Self {
         Self {
             hour: HourFormat::Hour12,
+            verbosity: Verbosity::Verbose,
+            oxford_comma: true,
+            capitalize: true,
+            month_suffix: true,
         }
     }
 }
@@ -72,12 +112,36 @@ impl Default for English {
 }
 
 impl English {
-    fn minute(&self, h: OrsExpr<Minute>) -> impl Display {
+    fn terse(&self) -> bool {
+        self.verbosity == Verbosity::Terse
+    }
+    fn list_style(&self) -> &'static ListStyle {
+        if self.oxford_comma {
+            &LIST
+        } else {
+            &LIST_NO_OXFORD_COMMA
+        }
+    }
+    fn minutes_unit(&self) -> &'static str {
+        if self.terse() {
+            "min"
+        } else {
+            "minutes"
+        }
+    }
+    fn minute<'a>(&'a self, h: OrsExpr<Minute>) -> impl Display + 'a {
         display(move |f| match h {
             OrsExpr::One(minute) => write!(f, "{}", u8::from(minute)),
             OrsExpr::Range(start, end) => {
                 write!(f, "{} through {}", u8::from(start), u8::from(end))
             }
+            OrsExpr::Step { start, end, step } if self.terse() => {
+                write!(f, "every {} min", u8::from(step))?;
+                if !full_range(start, end) {
+                    write!(f, " from {} through {}", u8::from(start), u8::from(end))?;
+                }
+                Ok(())
+            }
             OrsExpr::Step { start, end, step } => write!(
                 f,
                 "every {} minute from {} through {}",
@@ -101,6 +165,18 @@ impl English {
                 self.time(start, 0),
                 self.time(end, 59)
             ),
+            OrsExpr::Step { start, end, step } if self.terse() => {
+                write!(f, "every {} hr", u8::from(step))?;
+                if !full_range(start, end) {
+                    write!(
+                        f,
+                        " between {} and {}",
+                        self.time(start, 0),
+                        self.time(end, 59)
+                    )?;
+                }
+                Ok(())
+            }
             OrsExpr::Step { start, end, step } => write!(
                 f,
                 "every {} hour between {} and {}",
@@ -110,7 +186,7 @@ impl English {
             ),
         })
     }
-    fn month(&self, h: OrsExpr<Month>) -> impl Display {
+    fn month<'a>(&'a self, h: OrsExpr<Month>) -> impl Display + 'a {
         display(move |f| match h {
             OrsExpr::One(month) => write!(f, "{}", chrono::Month::from(month).name()),
             OrsExpr::Range(start, end) => write!(
@@ -119,6 +195,18 @@ impl English {
                 chrono::Month::from(start).name(),
                 chrono::Month::from(end).name()
             ),
+            OrsExpr::Step { start, end, step } if self.terse() => {
+                write!(f, "every {} months", u8::from(step))?;
+                if !full_range(start, end) {
+                    write!(
+                        f,
+                        " from {} to {}",
+                        chrono::Month::from(start).name(),
+                        chrono::Month::from(end).name()
+                    )?;
+                }
+                Ok(())
+            }
             OrsExpr::Step { start, end, step } => write!(
                 f,
                 "every {} month from {} to {}",
@@ -128,10 +216,17 @@ impl English {
             ),
         })
     }
-    fn day_of_week(&self, h: OrsExpr<DayOfWeek>) -> impl Display {
+    fn day_of_week<'a>(&'a self, h: OrsExpr<DayOfWeek>) -> impl Display + 'a {
         display(move |f| match h {
             OrsExpr::One(dow) => write!(f, "{}", weekday(dow)),
             OrsExpr::Range(start, end) => write!(f, "{} through {}", weekday(start), weekday(end)),
+            OrsExpr::Step { start, end, step } if self.terse() => {
+                write!(f, "every {} days", u8::from(step))?;
+                if !full_range(start, end) {
+                    write!(f, " {} through {}", weekday(start), weekday(end))?;
+                }
+                Ok(())
+            }
             OrsExpr::Step { start, end, step } => write!(
                 f,
                 "every {} weekday {} through {}",
@@ -141,7 +236,27 @@ impl English {
             ),
         })
     }
-    fn day_of_month(&self, h: OrsExpr<DayOfMonth>) -> impl Display {
+    fn week<'a>(&'a self, w: OrsExpr<Week>) -> impl Display + 'a {
+        display(move |f| match w {
+            OrsExpr::One(week) => write!(f, "week {}", week),
+            OrsExpr::Range(start, end) => write!(f, "weeks {} through {}", start, end),
+            OrsExpr::Step { start, end, step } if self.terse() => {
+                write!(f, "every {} weeks", u8::from(step))?;
+                if !full_range(start, end) {
+                    write!(f, " from week {} through {}", start, end)?;
+                }
+                Ok(())
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "every {} week from week {} through {}",
+                postfixed(u8::from(step)),
+                start,
+                end
+            ),
+        })
+    }
+    fn day_of_month<'a>(&'a self, h: OrsExpr<DayOfMonth>) -> impl Display + 'a {
         display(move |f| match h {
             OrsExpr::One(dom) => write!(f, "{}", postfixed(u8::from(dom) + 1)),
             OrsExpr::Range(start, end) => write!(
@@ -150,6 +265,18 @@ impl English {
                 postfixed(u8::from(start) + 1),
                 postfixed(u8::from(end) + 1)
             ),
+            OrsExpr::Step { start, end, step } if self.terse() => {
+                write!(f, "every {} days", u8::from(step))?;
+                if !full_range(start, end) {
+                    write!(
+                        f,
+                        " from the {} to the {}",
+                        postfixed(u8::from(start) + 1),
+                        postfixed(u8::from(end) + 1)
+                    )?;
+                }
+                Ok(())
+            }
             OrsExpr::Step { start, end, step } => write!(
                 f,
                 "every {} day from the {} to the {}",
@@ -160,246 +287,297 @@ impl English {
         })
     }
     fn time<H: Into<u8>, M: Into<u8>>(&self, hour: H, minute: M) -> impl Display {
-        let time = NaiveTime::from_hms(hour.into() as u32, minute.into() as u32, 0);
-        let fmt = match self.hour {
-            HourFormat::Hour12 => "%-I:%M %p",
-            HourFormat::Hour24 => "%H:%M",
-        };
-        time.format(fmt)
+        let (hour, minute) = (hour.into(), minute.into());
+        let format = self.hour;
+        display(move |f| match format {
+            HourFormat::Hour12 => {
+                let period = if hour < 12 { "AM" } else { "PM" };
+                write!(f, "{}:{:02} {}", (hour + 11) % 12 + 1, minute, period)
+            }
+            HourFormat::Hour24 => write!(f, "{:02}:{:02}", hour, minute),
+        })
     }
 }
 impl Language for English {
     fn fmt_expr(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result {
-        match (&expr.minutes, &expr.hours) {
-            (Expr::All, Expr::All) => write!(f, "Every minute")?,
-            (Expr::All, Expr::Many(Exprs { first, tail })) => {
-                let first = first.normalize();
-                write!(f, "Every minute ")?;
-                match tail.as_slice() {
-                    [] => write!(f, "{}", self.hour(first))?,
-                    [second] => write!(
-                        f,
-                        "{} and {}",
-                        self.hour(first),
-                        self.hour(second.normalize())
-                    )?,
-                    [middle @ .., last] => {
-                        write!(f, "{}, ", self.hour(first))?;
-                        for expr in middle {
-                            write!(f, "{}, ", self.hour(expr.normalize()))?;
-                        }
-                        write!(f, "and {}", self.hour(last.normalize()))?;
-                    }
-                }
+        if self.capitalize {
+            self.fmt_description(expr, f)
+        } else {
+            let alternate = f.alternate();
+            let description = display(|f| self.fmt_description(expr, f));
+            let mut uncapitalized = Uncapitalized { f, first: true };
+            // keep writing segment markers if they were asked for
+            if alternate {
+                write!(uncapitalized, "{:#}", description)
+            } else {
+                write!(uncapitalized, "{}", description)
             }
-            (Expr::Many(Exprs { first, tail }), Expr::All) => {
-                let first = first.normalize();
-                match tail.as_slice() {
-                    [] => match first {
-                        OrsExpr::One(value) => match u8::from(value) {
-                            0 => write!(f, "Every hour"),
-                            1 => write!(f, "At 1 minute past the hour"),
-                            v => write!(f, "At {} minutes past the hour", v),
-                        }?,
-                        OrsExpr::Range(start, end) => write!(
+        }
+    }
+
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        marked_segments(|f| self.fmt_expr(expr, f))
+    }
+}
+
+impl English {
+    fn fmt_description(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result {
+        if let Some(shortcut) = expr.shortcut {
+            return match shortcut {
+                Shortcut::Hourly => write!(f, "Every hour"),
+                Shortcut::Daily => write!(f, "Every day at midnight"),
+                Shortcut::Weekly => write!(f, "Every Sunday at midnight"),
+                Shortcut::Monthly => write!(f, "At midnight on the 1st of every month"),
+                Shortcut::Yearly | Shortcut::Annually => write!(f, "At midnight on January 1st"),
+            };
+        }
+
+        let parts = Parts::new(expr);
+        mark(f, SegmentKind::Time)?;
+        match parts.times {
+            Times::EveryMinute => write!(f, "Every minute")?,
+            Times::EveryMinuteDuring(hours) => write!(
+                f,
+                "Every minute {}",
+                list(self.list_style(), hours, |hour| self.hour(hour))
+            )?,
+            Times::MinutesOfEveryHour(minutes) => match (minutes.first.normalize(), &*minutes.tail)
+            {
+                (OrsExpr::One(value), []) => match u8::from(value) {
+                    0 => write!(f, "Every hour"),
+                    1 if self.terse() => write!(f, "At 1 min past the hour"),
+                    1 => write!(f, "At 1 minute past the hour"),
+                    v => write!(f, "At {} {} past the hour", v, self.minutes_unit()),
+                }?,
+                (OrsExpr::Range(start, end), []) => write!(
+                    f,
+                    "Minutes {} through {} past the hour",
+                    u8::from(start),
+                    u8::from(end)
+                )?,
+                (OrsExpr::Step { start, end, step }, []) if self.terse() => {
+                    write!(f, "Every {} min", u8::from(step))?;
+                    if !full_range(start, end) {
+                        write!(
                             f,
-                            "Minutes {} through {} past the hour",
+                            " from minute {} to minute {} past the hour",
                             u8::from(start),
                             u8::from(end)
-                        )?,
-                        OrsExpr::Step { start, end, step } => write!(
-                            f,
-                            "Every {} minute starting from minute {} to minute {} past the hour",
-                            postfixed(u8::from(step)),
-                            u8::from(start),
-                            u8::from(end),
-                        )?,
-                    },
-                    [second] => write!(
-                        f,
-                        "At {} and {} minutes past the hour",
-                        self.minute(first),
-                        self.minute(second.normalize())
-                    )?,
-                    [middle @ .., last] => {
-                        write!(f, "At {}, ", self.minute(first))?;
-                        for expr in middle {
-                            write!(f, "{}, ", self.minute(expr.normalize()))?;
-                        }
-                        write!(
-                            f,
-                            "and {} minutes past the hour",
-                            self.minute(last.normalize())
                         )?;
                     }
                 }
-            }
-            (
-                Expr::Many(Exprs {
-                    first: first_minute,
-                    tail: tail_minutes,
-                }),
-                Expr::Many(Exprs {
-                    first: first_hour,
-                    tail: tail_hours,
-                }),
-            ) => {
-                let first_minute = first_minute.normalize();
-                let tail_minutes = tail_minutes.as_slice();
-                let first_hour = first_hour.normalize();
-                let tail_hours = tail_hours.as_slice();
-                if let (OrsExpr::One(minute), [], OrsExpr::One(hour), []) =
-                    (first_minute, tail_minutes, first_hour, tail_hours)
-                {
-                    write!(f, "At {}", self.time(hour, minute))?;
-                } else {
-                    match tail_minutes {
-                        [] => write!(
-                            f,
-                            "At {} minutes past the hour, ",
-                            self.minute(first_minute)
-                        )?,
-                        [second] => write!(
-                            f,
-                            "At {} and {} minutes past the hour, ",
-                            self.minute(first_minute),
-                            self.minute(second.normalize())
-                        )?,
-                        [middle @ .., last] => {
-                            write!(f, "At {}, ", self.minute(first_minute))?;
-                            for expr in middle {
-                                write!(f, "{}, ", self.minute(expr.normalize()))?;
-                            }
-                            write!(f, "and {}, ", self.minute(last.normalize()))?;
-                        }
-                    }
-
-                    match tail_hours {
-                        [] => write!(f, "{}", self.hour(first_hour))?,
-                        [second] => write!(
-                            f,
-                            "{} and {}",
-                            self.hour(first_hour),
-                            self.hour(second.normalize())
-                        )?,
-                        [middle @ .., last] => {
-                            write!(f, "{}, ", self.hour(first_hour))?;
-                            for expr in middle {
-                                write!(f, "{}, ", self.hour(expr.normalize()))?;
-                            }
-                            write!(f, "and {}", self.hour(last.normalize()))?;
-                        }
-                    }
+                (OrsExpr::Step { start, end, step }, []) => write!(
+                    f,
+                    "Every {} minute starting from minute {} to minute {} past the hour",
+                    postfixed(u8::from(step)),
+                    u8::from(start),
+                    u8::from(end),
+                )?,
+                _ => write!(
+                    f,
+                    "At {} {} past the hour",
+                    list(self.list_style(), minutes, |minute| self.minute(minute)),
+                    self.minutes_unit()
+                )?,
+            },
+            Times::At(hour, minute) => write!(f, "At {}", self.time(hour, minute))?,
+            Times::MinutesDuring(minutes, hours) => write!(
+                f,
+                "At {} {} past the hour, {}",
+                list(self.list_style(), minutes, |minute| self.minute(minute)),
+                self.minutes_unit(),
+                list(self.list_style(), hours, |hour| self.hour(hour))
+            )?,
+        }
+        let exclusions = &expr.exclusions;
+        match &exclusions.minutes {
+            None => {}
+            Some(minutes) => match (minutes.first.normalize(), &*minutes.tail) {
+                (OrsExpr::One(value), []) if u8::from(value) == 1 && self.terse() => {
+                    write!(f, " except 1 min past the hour")?
                 }
-            }
+                (OrsExpr::One(value), []) if u8::from(value) == 1 => {
+                    write!(f, " except 1 minute past the hour")?
+                }
+                _ => write!(
+                    f,
+                    " except {} {} past the hour",
+                    list(self.list_style(), minutes, |minute| self.minute(minute)),
+                    self.minutes_unit()
+                )?,
+            },
+        }
+        if let Some(hours) = &exclusions.hours {
+            write!(
+                f,
+                " except {}",
+                list(self.list_style(), hours, |hour| self.hour(hour))
+            )?;
         }
 
-        match &expr.doms {
-            DayOfMonthExpr::All => {}
-            &DayOfMonthExpr::ClosestWeekday(day) => write!(
+        mark(f, SegmentKind::DaysOfMonth)?;
+        match parts.doms {
+            DayOfMonthExpr::All | DayOfMonthExpr::Ignored => {}
+            DayOfMonthExpr::Many(exprs) => write!(
                 f,
-                " on the closest weekday to the {}",
-                postfixed(u8::from(day) + 1)
+                " on the {}",
+                list(self.list_style(), exprs, |day| self.day_of_month(day))
             )?,
-            DayOfMonthExpr::Last(Last::Day) => write!(f, " on the last day")?,
-            DayOfMonthExpr::Last(Last::Weekday) => write!(f, " on the last weekday")?,
-            &DayOfMonthExpr::Last(Last::Offset(offset)) => {
-                write!(f, " on the {} to last day", postfixed(u8::from(offset) + 1))?
-            }
-            &DayOfMonthExpr::Last(Last::OffsetWeekday(offset)) => write!(
+            DayOfMonthExpr::ClosestWeekdays(exprs) => write!(
                 f,
-                " on the closest weekday to the {} to last day",
-                postfixed(u8::from(offset) + 1)
+                " on the closest weekday to each of the {}",
+                list(self.list_style(), exprs, |day| self.day_of_month(day))
             )?,
-            DayOfMonthExpr::Many(Exprs { first, tail }) => {
-                let first = first.normalize();
-                match tail.as_slice() {
-                    [] => write!(f, " on the {}", self.day_of_month(first))?,
-                    [second] => write!(
-                        f,
-                        " on the {} and {}",
-                        self.day_of_month(first),
-                        self.day_of_month(second.normalize())
-                    )?,
-                    [middle @ .., last] => {
-                        write!(f, " on the {}, ", self.day_of_month(first))?;
-                        for expr in middle {
-                            write!(f, "{}, ", self.day_of_month(expr.normalize()))?;
-                        }
-                        write!(f, "and {}", self.day_of_month(last.normalize()))?;
-                    }
-                }
-            }
+            doms => write!(
+                f,
+                " on {}",
+                dom_specials(self.list_style(), doms, dom_special)
+            )?,
+        }
+        if let Some(doms) = &exclusions.doms {
+            write!(
+                f,
+                " except on the {}",
+                list(self.list_style(), doms, |day| self.day_of_month(day))
+            )?;
         }
 
-        match (&expr.doms, &expr.dows) {
-            (DayOfMonthExpr::All, _) | (_, DayOfWeekExpr::All) => {}
-            _ => write!(f, " and")?,
+        mark(f, SegmentKind::Text)?;
+        if parts.has_doms() && parts.has_dows() {
+            write!(f, " and")?;
         }
 
-        match &expr.dows {
-            DayOfWeekExpr::All => {}
-            &DayOfWeekExpr::Last(day) => write!(f, " on the last {}", weekday(day))?,
-            &DayOfWeekExpr::Nth(day, nth) => {
-                write!(f, " on the {} {}", postfixed(u8::from(nth)), weekday(day))?
-            }
-            DayOfWeekExpr::Many(Exprs { first, tail }) => {
-                let first = first.normalize();
-                match tail.as_slice() {
-                    [] => write!(f, " on {}", self.day_of_week(first))?,
-                    [second] => write!(
-                        f,
-                        " on {} and {}",
-                        self.day_of_week(first),
-                        self.day_of_week(second.normalize())
-                    )?,
-                    [middle @ .., last] => {
-                        write!(f, " on {}, ", self.day_of_week(first))?;
-                        for expr in middle {
-                            write!(f, "{}, ", self.day_of_week(expr.normalize()))?;
-                        }
-                        write!(f, "and {}", self.day_of_week(last.normalize()))?;
-                    }
-                }
-            }
+        mark(f, SegmentKind::DaysOfWeek)?;
+        match parts.dows {
+            DayOfWeekExpr::All | DayOfWeekExpr::Ignored => {}
+            DayOfWeekExpr::Many(exprs) => write!(
+                f,
+                " on {}",
+                list(self.list_style(), exprs, |day| self.day_of_week(day))
+            )?,
+            dows => write!(
+                f,
+                " on {}",
+                dow_specials(self.list_style(), dows, dow_special)
+            )?,
+        }
+        if let Some(dows) = &exclusions.dows {
+            write!(
+                f,
+                " except on {}",
+                list(self.list_style(), dows, |day| self.day_of_week(day))
+            )?;
         }
 
-        let Exprs { first, tail } = match (&expr.doms, &expr.months, &expr.dows) {
+        mark(f, SegmentKind::Months)?;
+        match (parts.doms, parts.months, parts.dows) {
             (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::All)
-            | (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::Many(_)) => return Ok(()),
-            (_, Expr::All, _) => {
-                write!(f, " of every month")?;
-                return Ok(());
-            }
-            (DayOfMonthExpr::All, Expr::Many(exprs), DayOfWeekExpr::All) => {
-                write!(f, " every day in ")?;
-                exprs
-            }
-            (_, Expr::Many(exprs), _) => {
-                write!(f, " of ")?;
-                exprs
+            | (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::Many(_)) => Ok(()),
+            (_, Expr::All, _) if self.month_suffix => write!(f, " of every month"),
+            (_, Expr::All, _) => Ok(()),
+            (DayOfMonthExpr::All, Expr::Many(months), DayOfWeekExpr::All) => write!(
+                f,
+                " every day in {}",
+                list(self.list_style(), months, |month| self.month(month))
+            ),
+            (_, Expr::Many(months), _) => {
+                write!(
+                    f,
+                    " of {}",
+                    list(self.list_style(), months, |month| self.month(month))
+                )
             }
-        };
+        }?;
+        if let Some(months) = &exclusions.months {
+            write!(
+                f,
+                " except in {}",
+                list(self.list_style(), months, |month| self.month(month))
+            )?;
+        }
 
-        let first = first.normalize();
-        match tail.as_slice() {
-            [] => write!(f, "{}", self.month(first))?,
-            [second] => write!(
+        mark(f, SegmentKind::Weeks)?;
+        match parts.weeks {
+            Expr::All => {}
+            Expr::Many(weeks) => write!(
                 f,
-                "{} and {}",
-                self.month(first),
-                self.month(second.normalize())
+                ", in {}",
+                list(self.list_style(), weeks, |week| self.week(week))
             )?,
-            [middle @ .., last] => {
-                write!(f, "{}, ", self.month(first))?;
-                for expr in middle {
-                    write!(f, "{}, ", self.month(expr.normalize()))?;
+        }
+        match &exclusions.weeks {
+            None => Ok(()),
+            Some(weeks) => write!(
+                f,
+                " except in {}",
+                list(self.list_style(), weeks, |week| self.week(week))
+            ),
+        }
+    }
+}
+
+/// Formats a special day of the month expression, like "the last weekday"
+fn dom_special(special: &DayOfMonthSpecial, f: &mut Formatter) -> fmt::Result {
+    match special {
+        &DayOfMonthSpecial::ClosestWeekday(day) => write!(
+            f,
+            "the closest weekday to the {}",
+            postfixed(u8::from(day) + 1)
+        ),
+        &DayOfMonthSpecial::BusinessDay(day) => {
+            write!(f, "the {} business day", postfixed(u8::from(day)))
+        }
+        DayOfMonthSpecial::Last(Last::Day) => f.write_str("the last day"),
+        DayOfMonthSpecial::Last(Last::Weekday) => f.write_str("the last weekday"),
+        &DayOfMonthSpecial::Last(Last::Offset(offset)) => {
+            write!(f, "the {} to last day", postfixed(u8::from(offset) + 1))
+        }
+        &DayOfMonthSpecial::Last(Last::OffsetWeekday(offset)) => write!(
+            f,
+            "the closest weekday to the {} to last day",
+            postfixed(u8::from(offset) + 1)
+        ),
+        &DayOfMonthSpecial::Last(Last::WeekdayOffset(offset)) => {
+            write!(f, "the {} to last weekday", postfixed(u8::from(offset) + 1))
+        }
+    }
+}
+
+/// Formats a special day of the week expression, like "the 2nd Monday"
+fn dow_special(special: &DayOfWeekSpecial, f: &mut Formatter) -> fmt::Result {
+    match *special {
+        DayOfWeekSpecial::Last(day) => write!(f, "the last {}", weekday(day)),
+        DayOfWeekSpecial::Nth(day, nth) => {
+            write!(f, "the {} {}", postfixed(u8::from(nth)), weekday(day))
+        }
+    }
+}
+
+/// A writer lowercasing the first character written to the formatter, skipping over any segment
+/// markers
+struct Uncapitalized<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    first: bool,
+}
+
+impl fmt::Write for Uncapitalized<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.first {
+            return self.f.write_str(s);
+        }
+        let mut chars = s.chars();
+        for c in &mut chars {
+            if is_marker(c) {
+                self.f.write_char(c)?;
+            } else {
+                self.first = false;
+                for c in c.to_lowercase() {
+                    self.f.write_char(c)?;
                 }
-                write!(f, "and {}", self.month(last.normalize()))?;
+                break;
             }
         }
-
-        Ok(())
+        self.f.write_str(chars.as_str())
     }
 }
 
@@ -408,7 +586,7 @@ mod tests {
     use super::*;
 
     #[cfg(not(feature = "std"))]
-    use alloc::string::ToString;
+    use alloc::{format, string::String, string::ToString};
 
     const CFG_24_HOURS: English = English {
         hour: HourFormat::Hour24,
@@ -425,7 +603,7 @@ mod tests {
 
     #[track_caller]
     fn assert(cron: &str, expected: &str) {
-        let expr: CronExpr = cron.parse().expect("Valid cron expression");
+        let expr = CronExpr::from_str_with(cron, Dialect::Extended).expect("Valid cron expression");
         let description = expr.describe(English::new()).to_string();
 
         assert_eq!(description, expected);
@@ -467,10 +645,34 @@ mod tests {
             "* * L-1W * *",
             "Every minute on the closest weekday to the 2nd to last day of every month",
         );
+        assert(
+            "* * LW-1 * *",
+            "Every minute on the 2nd to last weekday of every month",
+        );
+        assert(
+            "* * 1W,15W * *",
+            "Every minute on the closest weekday to the 1st and the closest weekday to the 15th of every month",
+        );
+        assert(
+            "* * ? * MON#1,MON#3,FRIL",
+            "Every minute on the 1st Monday, the 3rd Monday, and the last Friday of every month",
+        );
         assert(
             "* * 15W * *",
             "Every minute on the closest weekday to the 15th of every month",
         );
+        assert(
+            "* * 1-7W * *",
+            "Every minute on the closest weekday to each of the 1st to 7th of every month",
+        );
+        assert(
+            "* * 1,15W * *",
+            "Every minute on the closest weekday to each of the 1st and 15th of every month",
+        );
+        assert(
+            "* * 3B * *",
+            "Every minute on the 3rd business day of every month",
+        );
         assert("* * 15 * *", "Every minute on the 15th of every month");
         assert(
             "* * 1,15 * *",
@@ -482,6 +684,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn weeks() {
+        assert(
+            "0 9 * * MON W/2",
+            "At 9:00 AM on Monday, in every 2nd week from week 1 through 53",
+        );
+        assert("* * * * * W5", "Every minute, in week 5");
+        assert(
+            "0 0 1 * * W1-26,53",
+            "At 12:00 AM on the 1st of every month, in weeks 1 through 26 and week 53",
+        );
+    }
+
+    #[test]
+    fn year_days() {
+        assert("0 0 LY * ?", "At 12:00 AM on the 31st of December");
+        assert("30 17 FY * ?", "At 5:30 PM on the 1st of January");
+    }
+
+    #[test]
+    fn exclusions() {
+        assert(
+            "*!15 * * * *",
+            "Every minute except 15 minutes past the hour",
+        );
+        assert("*!1 * * * *", "Every minute except 1 minute past the hour");
+        assert(
+            "0 9-17!12 * * *",
+            "At 0 minutes past the hour, between 9:00 AM and 5:59 PM except between 12:00 PM and 12:59 PM",
+        );
+        assert("0 9 *!1-7 * ?", "At 9:00 AM except on the 1st to 7th");
+        assert("0 9 ? * *!MON", "At 9:00 AM except on Monday");
+        assert(
+            "0 9 * *!JAN,FEB ?",
+            "At 9:00 AM except in January and February",
+        );
+        assert("0 9 * * MON W*!1", "At 9:00 AM on Monday except in week 1");
+    }
+
     #[test]
     fn months() {
         assert("* * * FEB *", "Every minute every day in February");
@@ -508,6 +749,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shortcuts() {
+        assert("@hourly", "Every hour");
+        assert("@daily", "Every day at midnight");
+        assert("@weekly", "Every Sunday at midnight");
+        assert("@monthly", "At midnight on the 1st of every month");
+        assert("@yearly", "At midnight on January 1st");
+        assert("@annually", "At midnight on January 1st");
+    }
+
+    #[test]
+    fn ignored_days() {
+        assert("0 0 ? * MON", "At 12:00 AM on Monday");
+        assert("0 0 15 * ?", "At 12:00 AM on the 15th of every month");
+        assert("0 0 ? JAN *", "At 12:00 AM every day in January");
+    }
+
     #[test]
     fn day_of_week() {
         assert(
@@ -522,4 +780,126 @@ mod tests {
         assert("* * * * SUN,SAT", "Every minute on Sunday and Saturday");
         assert("* * * * */3,SAT,MON-FRI", "Every minute on every 3rd weekday Sunday through Saturday, Saturday, and Monday through Friday");
     }
+
+    #[test]
+    fn style() {
+        let terse = English {
+            verbosity: Verbosity::Terse,
+            ..English::new()
+        };
+        assert_cfg(terse.clone(), "*/5 * * * *", "Every 5 min");
+        assert_cfg(
+            terse.clone(),
+            "10-30/5 * * * *",
+            "Every 5 min from minute 10 to minute 30 past the hour",
+        );
+        assert_cfg(terse.clone(), "30 * * * *", "At 30 min past the hour");
+        assert_cfg(
+            terse.clone(),
+            "0 */6 * * *",
+            "At 0 min past the hour, every 6 hr",
+        );
+        assert_cfg(
+            terse,
+            "0 0 1 */3 *",
+            "At 12:00 AM on the 1st of every 3 months",
+        );
+
+        let no_oxford_comma = English {
+            oxford_comma: false,
+            ..English::new()
+        };
+        assert_cfg(
+            no_oxford_comma,
+            "* * * * MON,WED,FRI",
+            "Every minute on Monday, Wednesday and Friday",
+        );
+
+        let lowercase = English {
+            capitalize: false,
+            ..English::new()
+        };
+        assert_cfg(lowercase.clone(), "* * * * *", "every minute");
+        assert_cfg(lowercase, "@weekly", "every Sunday at midnight");
+
+        let no_month_suffix = English {
+            month_suffix: false,
+            ..English::new()
+        };
+        assert_cfg(
+            no_month_suffix.clone(),
+            "0 0 L * *",
+            "At 12:00 AM on the last day",
+        );
+        assert_cfg(
+            no_month_suffix,
+            "0 0 1 JAN *",
+            "At 12:00 AM on the 1st of January",
+        );
+    }
+
+    #[test]
+    fn segments() {
+        #[track_caller]
+        fn assert_segments(cfg: English, cron: &str, expected: &[(SegmentKind, &str)]) {
+            let expr: CronExpr = cron.parse().expect("Valid cron expression");
+            let segments = expr.describe(&cfg).segments();
+            let actual = segments
+                .iter()
+                .map(|segment| (segment.kind, segment.text.as_str()))
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected);
+
+            let joined = segments
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<String>();
+            assert_eq!(joined, expr.describe(&cfg).to_string());
+        }
+
+        use SegmentKind::*;
+        assert_segments(
+            English::default(),
+            "0 9 1 JAN MON",
+            &[
+                (Time, "At 9:00 AM"),
+                (DaysOfMonth, " on the 1st"),
+                (Text, " and"),
+                (DaysOfWeek, " on Monday"),
+                (Months, " of January"),
+            ],
+        );
+        assert_segments(
+            English::default(),
+            "*/5 * * * *",
+            &[(
+                Time,
+                "Every 5th minute starting from minute 0 to minute 59 past the hour",
+            )],
+        );
+        assert_segments(
+            English::default(),
+            "@daily",
+            &[(Text, "Every day at midnight")],
+        );
+        assert_segments(
+            English {
+                capitalize: false,
+                ..English::new()
+            },
+            "0 12 L * *",
+            &[
+                (Time, "at 12:00 PM"),
+                (DaysOfMonth, " on the last day"),
+                (Months, " of every month"),
+            ],
+        );
+    }
+
+    #[test]
+    fn alternate_display() {
+        let expr: CronExpr = "0 9 * * MON".parse().expect("Valid cron expression");
+        let description = expr.describe(English::default());
+        assert_eq!(format!("{:#}", description), description.to_string());
+    }
 }
@@ -0,0 +1,480 @@
+use crate::describe::{
+    display, dom_specials, dow_specials, list, mark, marked_segments, without_exclusions,
+    DescriptionSegment, HourFormat, Language, ListStyle, Parts, SegmentKind, Times,
+};
+use crate::parse::*;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+const LIST: ListStyle = ListStyle {
+    pair: " et ",
+    separator: ", ",
+    last: " et ",
+};
+
+const MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+
+fn weekday<T: Into<chrono::Weekday>>(x: T) -> &'static str {
+    use chrono::Weekday::*;
+    match x.into() {
+        Mon => "lundi",
+        Tue => "mardi",
+        Wed => "mercredi",
+        Thu => "jeudi",
+        Fri => "vendredi",
+        Sat => "samedi",
+        Sun => "dimanche",
+    }
+}
+
+fn month_name(x: Month) -> &'static str {
+    MONTHS[chrono::Month::from(x).number_from_month() as usize - 1]
+}
+
+/// Formats a day of the month, where the first is written as "1er"
+fn day(x: DayOfMonth) -> impl Display {
+    display(move |f| match u8::from(x) + 1 {
+        1 => write!(f, "1er"),
+        x => write!(f, "{}", x),
+    })
+}
+
+fn ordinal(x: u8) -> impl Display {
+    display(move |f| match x {
+        1 => write!(f, "1er"),
+        x => write!(f, "{}e", x),
+    })
+}
+
+fn days(x: u8) -> impl Display {
+    display(move |f| match x {
+        1 => write!(f, "1 jour"),
+        x => write!(f, "{} jours", x),
+    })
+}
+
+fn time<H: Into<u8>, M: Into<u8>>(format: HourFormat, hour: H, minute: M) -> impl Display {
+    let (hour, minute) = (hour.into(), minute.into());
+    display(move |f| match format {
+        HourFormat::Hour12 => {
+            let period = if hour < 12 { "AM" } else { "PM" };
+            write!(f, "{}:{:02} {}", (hour + 11) % 12 + 1, minute, period)
+        }
+        HourFormat::Hour24 => write!(f, "{:02}:{:02}", hour, minute),
+    })
+}
+
+/// French language formatting
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct French {
+    /// Configures how hours are formatted in descriptions, with a 24 hour clock by default
+    pub hour: HourFormat,
+}
+
+impl French {
+    /// Creates a new instance of the french configuration with its default values
+    pub const fn new() -> Self {
+        Self {
+            hour: HourFormat::Hour24,
+        }
+    }
+
+    fn minute(&self, m: OrsExpr<Minute>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(minute) => write!(f, "{}", u8::from(minute)),
+            OrsExpr::Range(start, end) => write!(f, "{} à {}", u8::from(start), u8::from(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "toutes les {} minutes de {} à {}",
+                u8::from(step),
+                u8::from(start),
+                u8::from(end)
+            ),
+        })
+    }
+    fn hour(&self, h: OrsExpr<Hour>) -> impl Display {
+        let format = self.hour;
+        display(move |f| match h {
+            OrsExpr::One(hour) => write!(
+                f,
+                "entre {} et {}",
+                time(format, hour, 0),
+                time(format, hour, 59)
+            ),
+            OrsExpr::Range(start, end) => {
+                write!(
+                    f,
+                    "entre {} et {}",
+                    time(format, start, 0),
+                    time(format, end, 59)
+                )
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "toutes les {} heures entre {} et {}",
+                u8::from(step),
+                time(format, start, 0),
+                time(format, end, 59)
+            ),
+        })
+    }
+    fn month(&self, m: OrsExpr<Month>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(month) => write!(f, "{}", month_name(month)),
+            OrsExpr::Range(start, end) => {
+                write!(f, "{} à {}", month_name(start), month_name(end))
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "tous les {} mois de {} à {}",
+                u8::from(step),
+                month_name(start),
+                month_name(end)
+            ),
+        })
+    }
+    fn day_of_week(&self, d: OrsExpr<DayOfWeek>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(day) => write!(f, "le {}", weekday(day)),
+            OrsExpr::Range(start, end) => write!(f, "du {} au {}", weekday(start), weekday(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "tous les {} jours de la semaine du {} au {}",
+                u8::from(step),
+                weekday(start),
+                weekday(end)
+            ),
+        })
+    }
+    fn week(&self, w: OrsExpr<Week>) -> impl Display {
+        display(move |f| match w {
+            OrsExpr::One(week) => write!(f, "la semaine {}", week),
+            OrsExpr::Range(start, end) => write!(f, "les semaines {} à {}", start, end),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "toutes les {} semaines de la semaine {} à {}",
+                u8::from(step),
+                start,
+                end
+            ),
+        })
+    }
+    fn day_of_month(&self, d: OrsExpr<DayOfMonth>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(x) => write!(f, "le {}", day(x)),
+            OrsExpr::Range(start, end) => write!(f, "du {} au {}", day(start), day(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "tous les {} jours du {} au {}",
+                u8::from(step),
+                day(start),
+                day(end)
+            ),
+        })
+    }
+}
+
+impl Default for French {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Language for French {
+    fn fmt_expr(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result {
+        let expr = &*without_exclusions(expr);
+        if let Some(shortcut) = expr.shortcut {
+            return match shortcut {
+                Shortcut::Hourly => write!(f, "Toutes les heures"),
+                Shortcut::Daily => write!(f, "Tous les jours à minuit"),
+                Shortcut::Weekly => write!(f, "Tous les dimanches à minuit"),
+                Shortcut::Monthly => write!(f, "À minuit le 1er de chaque mois"),
+                Shortcut::Yearly | Shortcut::Annually => write!(f, "À minuit le 1er janvier"),
+            };
+        }
+
+        let parts = Parts::new(expr);
+        mark(f, SegmentKind::Time)?;
+        match parts.times {
+            Times::EveryMinute => write!(f, "Toutes les minutes")?,
+            Times::EveryMinuteDuring(hours) => write!(
+                f,
+                "Toutes les minutes {}",
+                list(&LIST, hours, |hour| self.hour(hour))
+            )?,
+            Times::MinutesOfEveryHour(minutes) => match (minutes.first.normalize(), &*minutes.tail)
+            {
+                (OrsExpr::One(value), []) => match u8::from(value) {
+                    0 => write!(f, "Toutes les heures"),
+                    1 => write!(f, "À 1 minute après l'heure"),
+                    v => write!(f, "À {} minutes après l'heure", v),
+                }?,
+                (OrsExpr::Range(start, end), []) => write!(
+                    f,
+                    "Les minutes {} à {} après l'heure",
+                    u8::from(start),
+                    u8::from(end)
+                )?,
+                (OrsExpr::Step { start, end, step }, []) => write!(
+                    f,
+                    "Toutes les {} minutes de la minute {} à la minute {} après l'heure",
+                    u8::from(step),
+                    u8::from(start),
+                    u8::from(end)
+                )?,
+                _ => write!(
+                    f,
+                    "Aux minutes {} après l'heure",
+                    list(&LIST, minutes, |minute| self.minute(minute))
+                )?,
+            },
+            Times::At(hour, minute) => write!(f, "À {}", time(self.hour, hour, minute))?,
+            Times::MinutesDuring(minutes, hours) => {
+                match (minutes.first.normalize(), &*minutes.tail) {
+                    (OrsExpr::One(minute), []) => write!(f, "À la minute {}", u8::from(minute))?,
+                    _ => write!(
+                        f,
+                        "Aux minutes {}",
+                        list(&LIST, minutes, |minute| self.minute(minute))
+                    )?,
+                }
+                write!(f, ", {}", list(&LIST, hours, |hour| self.hour(hour)))?
+            }
+        }
+
+        mark(f, SegmentKind::DaysOfMonth)?;
+        match parts.doms {
+            DayOfMonthExpr::All | DayOfMonthExpr::Ignored => {}
+            DayOfMonthExpr::Many(exprs) => {
+                write!(f, " {}", list(&LIST, exprs, |day| self.day_of_month(day)))?
+            }
+            DayOfMonthExpr::ClosestWeekdays(exprs) => write!(
+                f,
+                " {} (ou le jour ouvré le plus proche)",
+                list(&LIST, exprs, |day| self.day_of_month(day))
+            )?,
+            doms => write!(f, " {}", dom_specials(&LIST, doms, dom_special))?,
+        }
+
+        mark(f, SegmentKind::Text)?;
+        if parts.has_doms() && parts.has_dows() {
+            write!(f, " et")?;
+        }
+
+        mark(f, SegmentKind::DaysOfWeek)?;
+        match parts.dows {
+            DayOfWeekExpr::All | DayOfWeekExpr::Ignored => {}
+            DayOfWeekExpr::Many(exprs) => {
+                write!(f, " {}", list(&LIST, exprs, |day| self.day_of_week(day)))?
+            }
+            dows => write!(f, " {}", dow_specials(&LIST, dows, dow_special))?,
+        }
+
+        mark(f, SegmentKind::Months)?;
+        match (parts.doms, parts.months, parts.dows) {
+            (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::All)
+            | (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::Many(_)) => Ok(()),
+            (_, Expr::All, _) => write!(f, " de chaque mois"),
+            (DayOfMonthExpr::All, Expr::Many(months), DayOfWeekExpr::All) => write!(
+                f,
+                " tous les jours en {}",
+                list(&LIST, months, |month| self.month(month))
+            ),
+            (_, Expr::Many(months), _) => {
+                write!(f, " en {}", list(&LIST, months, |month| self.month(month)))
+            }
+        }?;
+
+        mark(f, SegmentKind::Weeks)?;
+        match parts.weeks {
+            Expr::All => Ok(()),
+            Expr::Many(weeks) => write!(f, ", {}", list(&LIST, weeks, |week| self.week(week))),
+        }
+    }
+
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        marked_segments(|f| self.fmt_expr(expr, f))
+    }
+}
+
+/// Formats a special day of the month expression, like "le dernier jour ouvré"
+fn dom_special(special: &DayOfMonthSpecial, f: &mut Formatter) -> fmt::Result {
+    match special {
+        &DayOfMonthSpecial::ClosestWeekday(x) => {
+            write!(f, "le jour ouvré le plus proche du {}", day(x))
+        }
+        &DayOfMonthSpecial::BusinessDay(n) => write!(f, "le {} jour ouvré", ordinal(u8::from(n))),
+        DayOfMonthSpecial::Last(Last::Day) => f.write_str("le dernier jour"),
+        DayOfMonthSpecial::Last(Last::Weekday) => f.write_str("le dernier jour ouvré"),
+        &DayOfMonthSpecial::Last(Last::Offset(offset)) => {
+            write!(f, "{} avant le dernier jour", days(u8::from(offset)))
+        }
+        &DayOfMonthSpecial::Last(Last::OffsetWeekday(offset)) => write!(
+            f,
+            "le jour ouvré le plus proche de {} avant le dernier jour",
+            days(u8::from(offset))
+        ),
+        &DayOfMonthSpecial::Last(Last::WeekdayOffset(offset)) => match u8::from(offset) {
+            1 => f.write_str("1 jour ouvré avant le dernier jour ouvré"),
+            offset => write!(f, "{} jours ouvrés avant le dernier jour ouvré", offset),
+        },
+    }
+}
+
+/// Formats a special day of the week expression, like "le 2e lundi"
+fn dow_special(special: &DayOfWeekSpecial, f: &mut Formatter) -> fmt::Result {
+    match *special {
+        DayOfWeekSpecial::Last(day) => write!(f, "le dernier {}", weekday(day)),
+        DayOfWeekSpecial::Nth(day, nth) => {
+            write!(f, "le {} {}", ordinal(u8::from(nth)), weekday(day))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[track_caller]
+    fn assert(cron: &str, expected: &str) {
+        let expr = CronExpr::from_str_with(cron, Dialect::Extended).expect("Valid cron expression");
+        let description = expr.describe(French::new()).to_string();
+
+        assert_eq!(description, expected);
+    }
+
+    #[test]
+    fn time() {
+        assert("* * * * *", "Toutes les minutes");
+        assert("0 * * * *", "Toutes les heures");
+        assert("1 * * * *", "À 1 minute après l'heure");
+        assert("30 * * * *", "À 30 minutes après l'heure");
+        assert("0 0 * * *", "À 00:00");
+        assert("30 18 * * *", "À 18:30");
+        assert("0,30 * * * *", "Aux minutes 0 et 30 après l'heure");
+        assert(
+            "0 2,5-10,*/2 * * *",
+            "À la minute 0, entre 02:00 et 02:59, entre 05:00 et 10:59 et toutes les 2 heures entre 00:00 et 23:59",
+        );
+        assert("* 9-17 * * *", "Toutes les minutes entre 09:00 et 17:59");
+    }
+
+    #[test]
+    fn hour_12() {
+        let cfg = French {
+            hour: HourFormat::Hour12,
+        };
+        let describe = |cron: &str| {
+            let expr: CronExpr = cron.parse().expect("Valid cron expression");
+            expr.describe(cfg.clone()).to_string()
+        };
+        assert_eq!(describe("0 0 * * *"), "À 12:00 AM");
+        assert_eq!(describe("30 18 * * *"), "À 6:30 PM");
+        assert_eq!(
+            describe("* 9-17 * * *"),
+            "Toutes les minutes entre 9:00 AM et 5:59 PM"
+        );
+    }
+
+    #[test]
+    fn exclusions() {
+        // the values left after the exclusions are described instead
+        assert(
+            "*!15 * * * *",
+            "Aux minutes 0 à 14 et 16 à 59 après l'heure",
+        );
+        assert(
+            "0 9 * *!JAN-FEB ?",
+            "À 09:00 tous les jours en mars à décembre",
+        );
+    }
+
+    #[test]
+    fn days() {
+        assert("0 0 1 * *", "À 00:00 le 1er de chaque mois");
+        assert("0 0 1-15 * *", "À 00:00 du 1er au 15 de chaque mois");
+        assert("0 0 L * *", "À 00:00 le dernier jour de chaque mois");
+        assert(
+            "0 0 L-2 * *",
+            "À 00:00 2 jours avant le dernier jour de chaque mois",
+        );
+        assert(
+            "0 0 LW-2 * *",
+            "À 00:00 2 jours ouvrés avant le dernier jour ouvré de chaque mois",
+        );
+        assert(
+            "0 0 L,1W * *",
+            "À 00:00 le dernier jour et le jour ouvré le plus proche du 1er de chaque mois",
+        );
+        assert(
+            "0 0 * * MON#1,MON#3",
+            "À 00:00 le 1er lundi et le 3e lundi de chaque mois",
+        );
+        assert(
+            "0 0 15W * *",
+            "À 00:00 le jour ouvré le plus proche du 15 de chaque mois",
+        );
+        assert(
+            "0 0 1-7W * *",
+            "À 00:00 du 1er au 7 (ou le jour ouvré le plus proche) de chaque mois",
+        );
+        assert(
+            "0 0 1,15W * *",
+            "À 00:00 le 1er et le 15 (ou le jour ouvré le plus proche) de chaque mois",
+        );
+        assert("0 0 * * MON-FRI", "À 00:00 du lundi au vendredi");
+        assert("0 0 * * FRIL", "À 00:00 le dernier vendredi de chaque mois");
+        assert("0 0 * * MON#1", "À 00:00 le 1er lundi de chaque mois");
+        assert("0 0 * * MON#3", "À 00:00 le 3e lundi de chaque mois");
+        assert(
+            "0 0 13 * FRI",
+            "À 00:00 le 13 et le vendredi de chaque mois",
+        );
+    }
+
+    #[test]
+    fn weeks() {
+        assert(
+            "0 9 * * MON W/2",
+            "À 09:00 le lundi, toutes les 2 semaines de la semaine 1 à 53",
+        );
+        assert(
+            "0 0 1 * * W1-26,53",
+            "À 00:00 le 1er de chaque mois, les semaines 1 à 26 et la semaine 53",
+        );
+    }
+
+    #[test]
+    fn months() {
+        assert("0 0 * AUG *", "À 00:00 tous les jours en août");
+        assert(
+            "0 0 1 JAN,JUN-AUG *",
+            "À 00:00 le 1er en janvier et juin à août",
+        );
+    }
+
+    #[test]
+    fn shortcuts() {
+        assert("@daily", "Tous les jours à minuit");
+        assert("@monthly", "À minuit le 1er de chaque mois");
+        assert("@annually", "À minuit le 1er janvier");
+    }
+}
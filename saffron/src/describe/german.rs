@@ -0,0 +1,456 @@
+use crate::describe::{
+    display, dom_specials, dow_specials, list, mark, marked_segments, without_exclusions,
+    DescriptionSegment, HourFormat, Language, ListStyle, Parts, SegmentKind, Times,
+};
+use crate::parse::*;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+const LIST: ListStyle = ListStyle {
+    pair: " und ",
+    separator: ", ",
+    last: " und ",
+};
+
+const MONTHS: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+
+fn weekday<T: Into<chrono::Weekday>>(x: T) -> &'static str {
+    use chrono::Weekday::*;
+    match x.into() {
+        Mon => "Montag",
+        Tue => "Dienstag",
+        Wed => "Mittwoch",
+        Thu => "Donnerstag",
+        Fri => "Freitag",
+        Sat => "Samstag",
+        Sun => "Sonntag",
+    }
+}
+
+fn month_name(x: Month) -> &'static str {
+    MONTHS[chrono::Month::from(x).number_from_month() as usize - 1]
+}
+
+fn days(x: u8) -> impl Display {
+    display(move |f| match x {
+        1 => write!(f, "1 Tag"),
+        x => write!(f, "{} Tage", x),
+    })
+}
+
+fn time<H: Into<u8>, M: Into<u8>>(format: HourFormat, hour: H, minute: M) -> impl Display {
+    let (hour, minute) = (hour.into(), minute.into());
+    display(move |f| match format {
+        HourFormat::Hour12 => {
+            let period = if hour < 12 { "AM" } else { "PM" };
+            write!(f, "{}:{:02} {}", (hour + 11) % 12 + 1, minute, period)
+        }
+        HourFormat::Hour24 => write!(f, "{:02}:{:02}", hour, minute),
+    })
+}
+
+/// German language formatting
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct German {
+    /// Configures how hours are formatted in descriptions, with a 24 hour clock by default
+    pub hour: HourFormat,
+}
+
+impl German {
+    /// Creates a new instance of the german configuration with its default values
+    pub const fn new() -> Self {
+        Self {
+            hour: HourFormat::Hour24,
+        }
+    }
+
+    fn minute(&self, m: OrsExpr<Minute>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(minute) => write!(f, "{}", u8::from(minute)),
+            OrsExpr::Range(start, end) => write!(f, "{} bis {}", u8::from(start), u8::from(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "jede {}. Minute von {} bis {}",
+                u8::from(step),
+                u8::from(start),
+                u8::from(end)
+            ),
+        })
+    }
+    fn hour(&self, h: OrsExpr<Hour>) -> impl Display {
+        let format = self.hour;
+        display(move |f| match h {
+            OrsExpr::One(hour) => write!(
+                f,
+                "zwischen {} und {}",
+                time(format, hour, 0),
+                time(format, hour, 59)
+            ),
+            OrsExpr::Range(start, end) => {
+                write!(
+                    f,
+                    "zwischen {} und {}",
+                    time(format, start, 0),
+                    time(format, end, 59)
+                )
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "jede {}. Stunde zwischen {} und {}",
+                u8::from(step),
+                time(format, start, 0),
+                time(format, end, 59)
+            ),
+        })
+    }
+    fn month(&self, m: OrsExpr<Month>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(month) => write!(f, "{}", month_name(month)),
+            OrsExpr::Range(start, end) => {
+                write!(f, "{} bis {}", month_name(start), month_name(end))
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "jeden {}. Monat von {} bis {}",
+                u8::from(step),
+                month_name(start),
+                month_name(end)
+            ),
+        })
+    }
+    fn day_of_week(&self, d: OrsExpr<DayOfWeek>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(day) => write!(f, "{}", weekday(day)),
+            OrsExpr::Range(start, end) => write!(f, "{} bis {}", weekday(start), weekday(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "jeden {}. Wochentag von {} bis {}",
+                u8::from(step),
+                weekday(start),
+                weekday(end)
+            ),
+        })
+    }
+    fn week(&self, w: OrsExpr<Week>) -> impl Display {
+        display(move |f| match w {
+            OrsExpr::One(week) => write!(f, "in Kalenderwoche {}", week),
+            OrsExpr::Range(start, end) => {
+                write!(f, "in den Kalenderwochen {} bis {}", start, end)
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "in jeder {}. Kalenderwoche von {} bis {}",
+                u8::from(step),
+                start,
+                end
+            ),
+        })
+    }
+    fn day_of_month(&self, d: OrsExpr<DayOfMonth>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(day) => write!(f, "{}.", u8::from(day) + 1),
+            OrsExpr::Range(start, end) => {
+                write!(f, "{}. bis {}.", u8::from(start) + 1, u8::from(end) + 1)
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "jeden {}. Tag vom {}. bis {}.",
+                u8::from(step),
+                u8::from(start) + 1,
+                u8::from(end) + 1
+            ),
+        })
+    }
+}
+
+impl Default for German {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Language for German {
+    fn fmt_expr(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result {
+        let expr = &*without_exclusions(expr);
+        if let Some(shortcut) = expr.shortcut {
+            return match shortcut {
+                Shortcut::Hourly => write!(f, "Jede volle Stunde"),
+                Shortcut::Daily => write!(f, "Jeden Tag um Mitternacht"),
+                Shortcut::Weekly => write!(f, "Jeden Sonntag um Mitternacht"),
+                Shortcut::Monthly => write!(f, "Um Mitternacht am 1. jeden Monats"),
+                Shortcut::Yearly | Shortcut::Annually => write!(f, "Um Mitternacht am 1. Januar"),
+            };
+        }
+
+        let parts = Parts::new(expr);
+        mark(f, SegmentKind::Time)?;
+        match parts.times {
+            Times::EveryMinute => write!(f, "Jede Minute")?,
+            Times::EveryMinuteDuring(hours) => write!(
+                f,
+                "Jede Minute {}",
+                list(&LIST, hours, |hour| self.hour(hour))
+            )?,
+            Times::MinutesOfEveryHour(minutes) => match (minutes.first.normalize(), &*minutes.tail)
+            {
+                (OrsExpr::One(value), []) => match u8::from(value) {
+                    0 => write!(f, "Jede volle Stunde"),
+                    1 => write!(f, "1 Minute nach jeder vollen Stunde"),
+                    v => write!(f, "{} Minuten nach jeder vollen Stunde", v),
+                }?,
+                (OrsExpr::Range(start, end), []) => write!(
+                    f,
+                    "Minuten {} bis {} jeder Stunde",
+                    u8::from(start),
+                    u8::from(end)
+                )?,
+                (OrsExpr::Step { start, end, step }, []) => write!(
+                    f,
+                    "Jede {}. Minute von Minute {} bis {} jeder Stunde",
+                    u8::from(step),
+                    u8::from(start),
+                    u8::from(end)
+                )?,
+                _ => write!(
+                    f,
+                    "In den Minuten {} jeder Stunde",
+                    list(&LIST, minutes, |minute| self.minute(minute))
+                )?,
+            },
+            Times::At(hour, minute) => write!(f, "Um {}", time(self.hour, hour, minute))?,
+            Times::MinutesDuring(minutes, hours) => {
+                match (minutes.first.normalize(), &*minutes.tail) {
+                    (OrsExpr::One(minute), []) => write!(f, "In Minute {}", u8::from(minute))?,
+                    _ => write!(
+                        f,
+                        "In den Minuten {}",
+                        list(&LIST, minutes, |minute| self.minute(minute))
+                    )?,
+                }
+                write!(f, ", {}", list(&LIST, hours, |hour| self.hour(hour)))?
+            }
+        }
+
+        mark(f, SegmentKind::DaysOfMonth)?;
+        match parts.doms {
+            DayOfMonthExpr::All | DayOfMonthExpr::Ignored => {}
+            DayOfMonthExpr::Many(exprs) => write!(
+                f,
+                " am {}",
+                list(&LIST, exprs, |day| self.day_of_month(day))
+            )?,
+            DayOfMonthExpr::ClosestWeekdays(exprs) => write!(
+                f,
+                " am nächsten Werktag zum {}",
+                list(&LIST, exprs, |day| self.day_of_month(day))
+            )?,
+            doms => write!(f, " {}", dom_specials(&LIST, doms, dom_special))?,
+        }
+
+        mark(f, SegmentKind::Text)?;
+        if parts.has_doms() && parts.has_dows() {
+            write!(f, " und")?;
+        }
+
+        mark(f, SegmentKind::DaysOfWeek)?;
+        match parts.dows {
+            DayOfWeekExpr::All | DayOfWeekExpr::Ignored => {}
+            DayOfWeekExpr::Many(exprs) => {
+                write!(f, " am {}", list(&LIST, exprs, |day| self.day_of_week(day)))?
+            }
+            dows => write!(f, " {}", dow_specials(&LIST, dows, dow_special))?,
+        }
+
+        mark(f, SegmentKind::Months)?;
+        match (parts.doms, parts.months, parts.dows) {
+            (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::All)
+            | (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::Many(_)) => Ok(()),
+            (_, Expr::All, _) => write!(f, " jeden Monats"),
+            (DayOfMonthExpr::All, Expr::Many(months), DayOfWeekExpr::All) => write!(
+                f,
+                " an jedem Tag im {}",
+                list(&LIST, months, |month| self.month(month))
+            ),
+            (_, Expr::Many(months), _) => {
+                write!(f, " im {}", list(&LIST, months, |month| self.month(month)))
+            }
+        }?;
+
+        mark(f, SegmentKind::Weeks)?;
+        match parts.weeks {
+            Expr::All => Ok(()),
+            Expr::Many(weeks) => write!(f, ", {}", list(&LIST, weeks, |week| self.week(week))),
+        }
+    }
+
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        marked_segments(|f| self.fmt_expr(expr, f))
+    }
+}
+
+/// Formats a special day of the month expression, like "am letzten Werktag"
+fn dom_special(special: &DayOfMonthSpecial, f: &mut Formatter) -> fmt::Result {
+    match special {
+        &DayOfMonthSpecial::ClosestWeekday(day) => {
+            write!(f, "am nächsten Werktag zum {}.", u8::from(day) + 1)
+        }
+        &DayOfMonthSpecial::BusinessDay(day) => write!(f, "am {}. Werktag", u8::from(day)),
+        DayOfMonthSpecial::Last(Last::Day) => f.write_str("am letzten Tag"),
+        DayOfMonthSpecial::Last(Last::Weekday) => f.write_str("am letzten Werktag"),
+        &DayOfMonthSpecial::Last(Last::Offset(offset)) => {
+            write!(f, "{} vor dem letzten Tag", days(u8::from(offset)))
+        }
+        &DayOfMonthSpecial::Last(Last::OffsetWeekday(offset)) => write!(
+            f,
+            "am nächsten Werktag zu {} vor dem letzten Tag",
+            days(u8::from(offset))
+        ),
+        &DayOfMonthSpecial::Last(Last::WeekdayOffset(offset)) => match u8::from(offset) {
+            1 => f.write_str("1 Werktag vor dem letzten Werktag"),
+            offset => write!(f, "{} Werktage vor dem letzten Werktag", offset),
+        },
+    }
+}
+
+/// Formats a special day of the week expression, like "am 2. Montag"
+fn dow_special(special: &DayOfWeekSpecial, f: &mut Formatter) -> fmt::Result {
+    match *special {
+        DayOfWeekSpecial::Last(day) => write!(f, "am letzten {}", weekday(day)),
+        DayOfWeekSpecial::Nth(day, nth) => write!(f, "am {}. {}", u8::from(nth), weekday(day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[track_caller]
+    fn assert(cron: &str, expected: &str) {
+        let expr = CronExpr::from_str_with(cron, Dialect::Extended).expect("Valid cron expression");
+        let description = expr.describe(German::new()).to_string();
+
+        assert_eq!(description, expected);
+    }
+
+    #[test]
+    fn time() {
+        assert("* * * * *", "Jede Minute");
+        assert("0 * * * *", "Jede volle Stunde");
+        assert("1 * * * *", "1 Minute nach jeder vollen Stunde");
+        assert("30 * * * *", "30 Minuten nach jeder vollen Stunde");
+        assert("0 0 * * *", "Um 00:00");
+        assert("30 18 * * *", "Um 18:30");
+        assert("0,30 * * * *", "In den Minuten 0 und 30 jeder Stunde");
+        assert(
+            "0 2,5-10,*/2 * * *",
+            "In Minute 0, zwischen 02:00 und 02:59, zwischen 05:00 und 10:59 und jede 2. Stunde zwischen 00:00 und 23:59",
+        );
+        assert("* 9-17 * * *", "Jede Minute zwischen 09:00 und 17:59");
+    }
+
+    #[test]
+    fn hour_12() {
+        let cfg = German {
+            hour: HourFormat::Hour12,
+        };
+        let describe = |cron: &str| {
+            let expr: CronExpr = cron.parse().expect("Valid cron expression");
+            expr.describe(cfg.clone()).to_string()
+        };
+        assert_eq!(describe("0 0 * * *"), "Um 12:00 AM");
+        assert_eq!(describe("30 18 * * *"), "Um 6:30 PM");
+        assert_eq!(
+            describe("* 9-17 * * *"),
+            "Jede Minute zwischen 9:00 AM und 5:59 PM"
+        );
+    }
+
+    #[test]
+    fn days() {
+        assert("0 0 15 * *", "Um 00:00 am 15. jeden Monats");
+        assert("0 0 1,15 * *", "Um 00:00 am 1. und 15. jeden Monats");
+        assert("0 0 L * *", "Um 00:00 am letzten Tag jeden Monats");
+        assert(
+            "0 0 L-1 * *",
+            "Um 00:00 1 Tag vor dem letzten Tag jeden Monats",
+        );
+        assert(
+            "0 0 L-3W * *",
+            "Um 00:00 am nächsten Werktag zu 3 Tage vor dem letzten Tag jeden Monats",
+        );
+        assert(
+            "0 0 LW-2 * *",
+            "Um 00:00 2 Werktage vor dem letzten Werktag jeden Monats",
+        );
+        assert(
+            "0 0 L,1W * *",
+            "Um 00:00 am letzten Tag und am nächsten Werktag zum 1. jeden Monats",
+        );
+        assert(
+            "0 0 * * MON#1,MON#3",
+            "Um 00:00 am 1. Montag und am 3. Montag jeden Monats",
+        );
+        assert(
+            "0 0 15W * *",
+            "Um 00:00 am nächsten Werktag zum 15. jeden Monats",
+        );
+        assert(
+            "0 0 1-7W * *",
+            "Um 00:00 am nächsten Werktag zum 1. bis 7. jeden Monats",
+        );
+        assert(
+            "0 0 1,15W * *",
+            "Um 00:00 am nächsten Werktag zum 1. und 15. jeden Monats",
+        );
+        assert("0 0 * * MON-FRI", "Um 00:00 am Montag bis Freitag");
+        assert("0 0 * * FRIL", "Um 00:00 am letzten Freitag jeden Monats");
+        assert("0 0 * * MON#2", "Um 00:00 am 2. Montag jeden Monats");
+        assert(
+            "0 0 13 * FRI",
+            "Um 00:00 am 13. und am Freitag jeden Monats",
+        );
+    }
+
+    #[test]
+    fn weeks() {
+        assert(
+            "0 9 * * MON W/2",
+            "Um 09:00 am Montag, in jeder 2. Kalenderwoche von 1 bis 53",
+        );
+        assert("* * * * * W5", "Jede Minute, in Kalenderwoche 5");
+    }
+
+    #[test]
+    fn months() {
+        assert("0 0 * MAR *", "Um 00:00 an jedem Tag im März");
+        assert(
+            "0 0 1 JAN,JUN-AUG *",
+            "Um 00:00 am 1. im Januar und Juni bis August",
+        );
+    }
+
+    #[test]
+    fn shortcuts() {
+        assert("@hourly", "Jede volle Stunde");
+        assert("@weekly", "Jeden Sonntag um Mitternacht");
+        assert("@yearly", "Um Mitternacht am 1. Januar");
+    }
+}
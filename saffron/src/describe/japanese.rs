@@ -0,0 +1,414 @@
+use crate::describe::{
+    display, dom_specials, dow_specials, list, mark, marked_segments, without_exclusions,
+    DescriptionSegment, HourFormat, Language, ListStyle, Parts, SegmentKind, Times,
+};
+use crate::parse::*;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+const LIST: ListStyle = ListStyle {
+    pair: "と",
+    separator: "、",
+    last: "、",
+};
+
+fn weekday<T: Into<chrono::Weekday>>(x: T) -> &'static str {
+    use chrono::Weekday::*;
+    match x.into() {
+        Mon => "月曜日",
+        Tue => "火曜日",
+        Wed => "水曜日",
+        Thu => "木曜日",
+        Fri => "金曜日",
+        Sat => "土曜日",
+        Sun => "日曜日",
+    }
+}
+
+fn month_number(x: Month) -> u32 {
+    chrono::Month::from(x).number_from_month()
+}
+
+fn time<H: Into<u8>, M: Into<u8>>(format: HourFormat, hour: H, minute: M) -> impl Display {
+    let (hour, minute) = (hour.into(), minute.into());
+    display(move |f| match format {
+        HourFormat::Hour12 => {
+            let period = if hour < 12 { "午前" } else { "午後" };
+            write!(f, "{}{}:{:02}", period, hour % 12, minute)
+        }
+        HourFormat::Hour24 => write!(f, "{}:{:02}", hour, minute),
+    })
+}
+
+/// Japanese language formatting
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Japanese {
+    /// Configures how hours are formatted in descriptions, with a 24 hour clock by default
+    pub hour: HourFormat,
+}
+
+impl Japanese {
+    /// Creates a new instance of the japanese configuration with its default values
+    pub const fn new() -> Self {
+        Self {
+            hour: HourFormat::Hour24,
+        }
+    }
+
+    fn minute(&self, m: OrsExpr<Minute>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(minute) => write!(f, "{}分", u8::from(minute)),
+            OrsExpr::Range(start, end) => {
+                write!(f, "{}分から{}分", u8::from(start), u8::from(end))
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "{}分から{}分まで{}分ごと",
+                u8::from(start),
+                u8::from(end),
+                u8::from(step)
+            ),
+        })
+    }
+    fn hour(&self, h: OrsExpr<Hour>) -> impl Display {
+        let format = self.hour;
+        display(move |f| match h {
+            OrsExpr::One(hour) => write!(
+                f,
+                "{}から{}まで",
+                time(format, hour, 0),
+                time(format, hour, 59)
+            ),
+            OrsExpr::Range(start, end) => {
+                write!(
+                    f,
+                    "{}から{}まで",
+                    time(format, start, 0),
+                    time(format, end, 59)
+                )
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "{}から{}まで{}時間ごと",
+                time(format, start, 0),
+                time(format, end, 59),
+                u8::from(step)
+            ),
+        })
+    }
+    fn month(&self, m: OrsExpr<Month>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(month) => write!(f, "{}月", month_number(month)),
+            OrsExpr::Range(start, end) => {
+                write!(f, "{}月から{}月", month_number(start), month_number(end))
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "{}月から{}月まで{}か月ごと",
+                month_number(start),
+                month_number(end),
+                u8::from(step)
+            ),
+        })
+    }
+    fn day_of_week(&self, d: OrsExpr<DayOfWeek>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(day) => write!(f, "{}", weekday(day)),
+            OrsExpr::Range(start, end) => write!(f, "{}から{}", weekday(start), weekday(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "{}から{}まで{}日ごと",
+                weekday(start),
+                weekday(end),
+                u8::from(step)
+            ),
+        })
+    }
+    fn week(&self, w: OrsExpr<Week>) -> impl Display {
+        display(move |f| match w {
+            OrsExpr::One(week) => write!(f, "第{}週", week),
+            OrsExpr::Range(start, end) => write!(f, "第{}週から第{}週", start, end),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "第{}週から第{}週まで{}週ごと",
+                start,
+                end,
+                u8::from(step)
+            ),
+        })
+    }
+    fn day_of_month(&self, d: OrsExpr<DayOfMonth>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(day) => write!(f, "{}日", u8::from(day) + 1),
+            OrsExpr::Range(start, end) => {
+                write!(f, "{}日から{}日", u8::from(start) + 1, u8::from(end) + 1)
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "{}日から{}日まで{}日ごと",
+                u8::from(start) + 1,
+                u8::from(end) + 1,
+                u8::from(step)
+            ),
+        })
+    }
+}
+
+impl Default for Japanese {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Language for Japanese {
+    fn fmt_expr(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result {
+        let expr = &*without_exclusions(expr);
+        if let Some(shortcut) = expr.shortcut {
+            return match shortcut {
+                Shortcut::Hourly => write!(f, "毎時0分"),
+                Shortcut::Daily => write!(f, "毎日0:00"),
+                Shortcut::Weekly => write!(f, "毎週日曜日の0:00"),
+                Shortcut::Monthly => write!(f, "毎月1日の0:00"),
+                Shortcut::Yearly | Shortcut::Annually => write!(f, "毎年1月1日の0:00"),
+            };
+        }
+
+        // the date comes first in japanese, followed by the time of the day
+        let parts = Parts::new(expr);
+        let has_days = parts.has_doms() || parts.has_dows();
+        mark(f, SegmentKind::Weeks)?;
+        // the weeks of the year replace "every month" and "every week"
+        let has_weeks = match parts.weeks {
+            Expr::All => false,
+            Expr::Many(weeks) => {
+                write!(f, "{}の", list(&LIST, weeks, |week| self.week(week)))?;
+                true
+            }
+        };
+        mark(f, SegmentKind::Months)?;
+        match parts.months {
+            Expr::All if has_weeks => {}
+            Expr::All
+                if parts.has_doms()
+                    || matches!(
+                        parts.dows,
+                        DayOfWeekExpr::Last(_)
+                            | DayOfWeekExpr::Nth(..)
+                            | DayOfWeekExpr::Specials(_)
+                    ) =>
+            {
+                write!(f, "毎月")?
+            }
+            Expr::All if parts.has_dows() => write!(f, "毎週")?,
+            Expr::All => {}
+            Expr::Many(months) => {
+                write!(f, "{}の", list(&LIST, months, |month| self.month(month)))?
+            }
+        }
+
+        mark(f, SegmentKind::DaysOfMonth)?;
+        match parts.doms {
+            DayOfMonthExpr::All | DayOfMonthExpr::Ignored => {}
+            DayOfMonthExpr::Many(exprs) => {
+                write!(f, "{}", list(&LIST, exprs, |day| self.day_of_month(day)))?
+            }
+            DayOfMonthExpr::ClosestWeekdays(exprs) => write!(
+                f,
+                "{}に最も近い平日",
+                list(&LIST, exprs, |day| self.day_of_month(day))
+            )?,
+            doms => write!(f, "{}", dom_specials(&LIST, doms, dom_special))?,
+        }
+
+        mark(f, SegmentKind::Text)?;
+        if parts.has_doms() && parts.has_dows() {
+            write!(f, "と")?;
+        }
+
+        mark(f, SegmentKind::DaysOfWeek)?;
+        match parts.dows {
+            DayOfWeekExpr::All | DayOfWeekExpr::Ignored => {}
+            DayOfWeekExpr::Many(exprs) => {
+                write!(f, "{}", list(&LIST, exprs, |day| self.day_of_week(day)))?
+            }
+            dows => write!(f, "{}", dow_specials(&LIST, dows, dow_special))?,
+        }
+
+        mark(f, SegmentKind::Text)?;
+        // a time of the day directly follows "every day", everything else is joined with "の"
+        let at = matches!(parts.times, Times::At(..));
+        if !has_days && (at || matches!(parts.months, Expr::Many(_))) {
+            write!(f, "毎日")?;
+            if !at {
+                write!(f, "の")?;
+            }
+        } else if has_days {
+            write!(f, "の")?;
+        }
+
+        mark(f, SegmentKind::Time)?;
+        match parts.times {
+            Times::EveryMinute => write!(f, "毎分"),
+            Times::EveryMinuteDuring(hours) => {
+                write!(f, "{}の毎分", list(&LIST, hours, |hour| self.hour(hour)))
+            }
+            Times::MinutesOfEveryHour(minutes) => write!(
+                f,
+                "毎時{}",
+                list(&LIST, minutes, |minute| self.minute(minute))
+            ),
+            Times::At(hour, minute) => write!(f, "{}", time(self.hour, hour, minute)),
+            Times::MinutesDuring(minutes, hours) => write!(
+                f,
+                "{}の{}",
+                list(&LIST, hours, |hour| self.hour(hour)),
+                list(&LIST, minutes, |minute| self.minute(minute))
+            ),
+        }
+    }
+
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        marked_segments(|f| self.fmt_expr(expr, f))
+    }
+}
+
+/// Formats a special day of the month expression, like "最終平日"
+fn dom_special(special: &DayOfMonthSpecial, f: &mut Formatter) -> fmt::Result {
+    match special {
+        &DayOfMonthSpecial::ClosestWeekday(day) => {
+            write!(f, "{}日に最も近い平日", u8::from(day) + 1)
+        }
+        &DayOfMonthSpecial::BusinessDay(day) => write!(f, "第{}営業日", u8::from(day)),
+        DayOfMonthSpecial::Last(Last::Day) => f.write_str("末日"),
+        DayOfMonthSpecial::Last(Last::Weekday) => f.write_str("最終平日"),
+        &DayOfMonthSpecial::Last(Last::Offset(offset)) => {
+            write!(f, "末日の{}日前", u8::from(offset))
+        }
+        &DayOfMonthSpecial::Last(Last::OffsetWeekday(offset)) => {
+            write!(f, "末日の{}日前に最も近い平日", u8::from(offset))
+        }
+        &DayOfMonthSpecial::Last(Last::WeekdayOffset(offset)) => {
+            write!(f, "最終平日の{}営業日前", u8::from(offset))
+        }
+    }
+}
+
+/// Formats a special day of the week expression, like "第2月曜日"
+fn dow_special(special: &DayOfWeekSpecial, f: &mut Formatter) -> fmt::Result {
+    match *special {
+        DayOfWeekSpecial::Last(day) => write!(f, "最終{}", weekday(day)),
+        DayOfWeekSpecial::Nth(day, nth) => write!(f, "第{}{}", u8::from(nth), weekday(day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[track_caller]
+    fn assert(cron: &str, expected: &str) {
+        let expr = CronExpr::from_str_with(cron, Dialect::Extended).expect("Valid cron expression");
+        let description = expr.describe(Japanese::new()).to_string();
+
+        assert_eq!(description, expected);
+    }
+
+    #[test]
+    fn time() {
+        assert("* * * * *", "毎分");
+        assert("0 * * * *", "毎時0分");
+        assert("0,30 * * * *", "毎時0分と30分");
+        assert("0 0 * * *", "毎日0:00");
+        assert("30 18 * * *", "毎日18:30");
+        assert("* 9-17 * * *", "9:00から17:59までの毎分");
+        assert(
+            "0 2,5-10,*/2 * * *",
+            "2:00から2:59まで、5:00から10:59まで、0:00から23:59まで2時間ごとの0分",
+        );
+    }
+
+    #[test]
+    fn hour_12() {
+        let cfg = Japanese {
+            hour: HourFormat::Hour12,
+        };
+        let describe = |cron: &str| {
+            let expr: CronExpr = cron.parse().expect("Valid cron expression");
+            expr.describe(cfg.clone()).to_string()
+        };
+        assert_eq!(describe("0 0 * * *"), "毎日午前0:00");
+        assert_eq!(describe("30 18 * * *"), "毎日午後6:30");
+        assert_eq!(describe("* 9-17 * * *"), "午前9:00から午後5:59までの毎分");
+    }
+
+    #[test]
+    fn days() {
+        assert("0 0 15 * *", "毎月15日の0:00");
+        assert("0 0 1,15 * *", "毎月1日と15日の0:00");
+        assert("0 0 L * *", "毎月末日の0:00");
+        assert("0 0 LW * *", "毎月最終平日の0:00");
+        assert("0 0 LW-1 * *", "毎月最終平日の1営業日前の0:00");
+        assert("0 0 L,1W * *", "毎月末日と1日に最も近い平日の0:00");
+        assert("0 0 * * MON#1,MON#3", "毎月第1月曜日と第3月曜日の0:00");
+        assert("0 0 L-3 * *", "毎月末日の3日前の0:00");
+        assert("0 0 15W * *", "毎月15日に最も近い平日の0:00");
+        assert("0 0 1-7W * *", "毎月1日から7日に最も近い平日の0:00");
+        assert("0 0 1,15W * *", "毎月1日と15日に最も近い平日の0:00");
+        assert("0 0 * * MON", "毎週月曜日の0:00");
+        assert("0 0 * * MON-FRI", "毎週月曜日から金曜日の0:00");
+        assert("0 0 * * FRIL", "毎月最終金曜日の0:00");
+        assert("0 0 * * MON#2", "毎月第2月曜日の0:00");
+        assert("0 0 13 * FRI", "毎月13日と金曜日の0:00");
+        assert("* * 1 * *", "毎月1日の毎分");
+    }
+
+    #[test]
+    fn weeks() {
+        assert(
+            "0 9 * * MON W/2",
+            "第1週から第53週まで2週ごとの月曜日の9:00",
+        );
+        assert("* * * * * W5", "第5週の毎分");
+    }
+
+    #[test]
+    fn months() {
+        assert("0 0 * JAN *", "1月の毎日0:00");
+        assert("* * * JAN *", "1月の毎日の毎分");
+        assert("0 0 1 JAN,JUN-AUG *", "1月と6月から8月の1日の0:00");
+    }
+
+    #[test]
+    fn shortcuts() {
+        assert("@hourly", "毎時0分");
+        assert("@daily", "毎日0:00");
+        assert("@yearly", "毎年1月1日の0:00");
+    }
+
+    #[test]
+    fn segments() {
+        use SegmentKind::*;
+
+        let expr: CronExpr = "0 9 1 * MON".parse().expect("Valid cron expression");
+        let segments = expr.describe(Japanese::new()).segments();
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| (segment.kind, segment.text.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (Months, "毎月"),
+                (DaysOfMonth, "1日"),
+                (Text, "と"),
+                (DaysOfWeek, "月曜日"),
+                (Text, "の"),
+                (Time, "9:00"),
+            ]
+        );
+    }
+}
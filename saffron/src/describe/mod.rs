@@ -1,23 +1,193 @@
 mod english;
+mod french;
+mod german;
+mod japanese;
+mod spanish;
 
-pub use english::{English, HourFormat};
+pub use english::{English, HourFormat, Verbosity};
+pub use french::French;
+pub use german::German;
+pub use japanese::Japanese;
+pub use spanish::Spanish;
 
-use crate::parse::CronExpr;
-use core::fmt::{self, Display, Formatter};
+use crate::parse::{
+    CronExpr, DayOfMonthExpr, DayOfMonthSpecial, DayOfWeekExpr, DayOfWeekSpecial, Expr, ExprValue,
+    Exprs, Hour, Minute, Month, OrsExpr, Week,
+};
+use core::fmt::{self, Display, Formatter, Write};
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
 /// A language formatting configuration
 pub trait Language {
     /// Formats a cron expression into the specified formatter
     fn fmt_expr(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result;
+
+    /// Describes a cron expression as a list of segments, each holding the text describing one
+    /// part of the expression. Joining the text of every segment gives the same description as
+    /// [`fmt_expr`](Language::fmt_expr).
+    ///
+    /// By default the whole description is returned as one [`SegmentKind::Text`] segment.
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        let mut text = String::new();
+        write!(text, "{}", display(|f| self.fmt_expr(expr, f)))
+            .expect("A description can always be written");
+        if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![DescriptionSegment {
+                kind: SegmentKind::Text,
+                text,
+            }]
+        }
+    }
 }
 
-impl<'a, L: Language> Language for &'a L {
+impl<L: Language> Language for &L {
     fn fmt_expr(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result {
         (*self).fmt_expr(expr, f)
     }
+
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        (*self).segments(expr)
+    }
+}
+
+/// The part of a cron expression described by a segment of a description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SegmentKind {
+    /// Text joining the other segments, or describing a shortcut like `@daily`
+    Text,
+    /// The times of the day, from the minutes and hours fields
+    Time,
+    /// The days of the month
+    DaysOfMonth,
+    /// The days of the week
+    DaysOfWeek,
+    /// The months of the year
+    Months,
+    /// The weeks of the year
+    Weeks,
 }
 
-struct Displayer<F>(pub F);
+impl SegmentKind {
+    const ALL: [SegmentKind; 6] = [
+        SegmentKind::Text,
+        SegmentKind::Time,
+        SegmentKind::DaysOfMonth,
+        SegmentKind::DaysOfWeek,
+        SegmentKind::Months,
+        SegmentKind::Weeks,
+    ];
+
+    /// The private use character marking the start of a segment of this kind
+    fn marker(self) -> char {
+        let index = Self::ALL
+            .iter()
+            .position(|&kind| kind == self)
+            .expect("Every kind is listed");
+        char::from_u32(MARKER_START as u32 + index as u32).expect("A private use character")
+    }
+
+    fn from_marker(c: char) -> Option<Self> {
+        let index = (c as u32).checked_sub(MARKER_START as u32)?;
+        Self::ALL.get(index as usize).copied()
+    }
+}
+
+/// A piece of a description and the part of the expression it describes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DescriptionSegment {
+    /// The part of the expression the text describes
+    pub kind: SegmentKind,
+    /// The text of the segment, including any spaces or punctuation before it
+    pub text: String,
+}
+
+const MARKER_START: char = '\u{E000}';
+
+/// Marks the start of a segment of the given kind. Languages call this before writing each part
+/// of a description, and the markers are only written when formatting with `{:#}`, which is how
+/// [`marked_segments`] finds where each part starts.
+fn mark(f: &mut Formatter, kind: SegmentKind) -> fmt::Result {
+    if f.alternate() {
+        f.write_char(kind.marker())
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns whether a character is a segment marker written by [`mark`]
+fn is_marker(c: char) -> bool {
+    SegmentKind::from_marker(c).is_some()
+}
+
+/// Splits a description into segments by formatting it with segment markers. Empty segments are
+/// dropped and neighbouring segments of the same kind are merged.
+fn marked_segments<F>(fmt: F) -> Vec<DescriptionSegment>
+where
+    F: Fn(&mut Formatter) -> fmt::Result,
+{
+    let mut marked = String::new();
+    write!(marked, "{:#}", display(fmt)).expect("A description can always be written");
+
+    let mut segments: Vec<DescriptionSegment> = Vec::new();
+    let mut kind = SegmentKind::Text;
+    let mut rest = marked.as_str();
+    loop {
+        let (text, next) = match rest.char_indices().find(|&(_, c)| is_marker(c)) {
+            Some((i, c)) => (&rest[..i], Some((i + c.len_utf8(), c))),
+            None => (rest, None),
+        };
+        if !text.is_empty() {
+            match segments.last_mut() {
+                Some(last) if last.kind == kind => last.text.push_str(text),
+                _ => segments.push(DescriptionSegment {
+                    kind,
+                    text: text.into(),
+                }),
+            }
+        }
+        match next {
+            Some((end, c)) => {
+                kind = SegmentKind::from_marker(c).expect("Found a marker");
+                rest = &rest[end..];
+            }
+            None => return segments,
+        }
+    }
+}
+
+/// Formats a description without segment markers, even if the formatter is alternate.
+pub(crate) fn fmt_description<L: Language>(
+    lang: &L,
+    expr: &CronExpr,
+    f: &mut Formatter,
+) -> fmt::Result {
+    if f.alternate() {
+        write!(f, "{}", display(|f| lang.fmt_expr(expr, f)))
+    } else {
+        lang.fmt_expr(expr, f)
+    }
+}
+
+/// Returns the expression with its excluded values removed from its fields, for languages that
+/// describe the values left in each field instead of the values excluded.
+fn without_exclusions(expr: &CronExpr) -> Cow<'_, CronExpr> {
+    if expr.exclusions.is_empty() {
+        Cow::Borrowed(expr)
+    } else {
+        Cow::Owned(expr.fold_exclusions())
+    }
+}
+
+pub(crate) struct Displayer<F>(F);
 impl<F> Display for Displayer<F>
 where
     F: Fn(&mut Formatter) -> fmt::Result,
@@ -27,9 +197,172 @@ where
     }
 }
 
-fn display<F>(f: F) -> Displayer<F>
+pub(crate) fn display<F>(f: F) -> Displayer<F>
 where
     F: Fn(&mut Formatter) -> fmt::Result,
 {
     Displayer(f)
 }
+
+/// The separators used to join a list of expressions in a language
+struct ListStyle {
+    /// Placed between the items of a list with exactly two items
+    pair: &'static str,
+    /// Placed between the items of a list with more than two items
+    separator: &'static str,
+    /// Placed before the last item of a list with more than two items
+    last: &'static str,
+}
+
+/// Joins a first item and the rest of the items into a list, formatting each item with the given
+/// function.
+fn join<'a, T, F>(style: &'a ListStyle, first: T, rest: &'a [T], item: F) -> impl Display + 'a
+where
+    T: 'a,
+    F: Fn(&T, &mut Formatter) -> fmt::Result + 'a,
+{
+    display(move |f| {
+        item(&first, f)?;
+        match rest {
+            [] => Ok(()),
+            [second] => {
+                f.write_str(style.pair)?;
+                item(second, f)
+            }
+            [middle @ .., last] => {
+                for value in middle {
+                    f.write_str(style.separator)?;
+                    item(value, f)?;
+                }
+                f.write_str(style.last)?;
+                item(last, f)
+            }
+        }
+    })
+}
+
+/// Joins a set of expressions into a list, formatting each normalized expression with the given
+/// function.
+fn list<'a, E, D, F>(style: &'a ListStyle, exprs: &'a Exprs<E>, item: F) -> impl Display + 'a
+where
+    E: Copy + ExprValue + PartialEq,
+    D: Display,
+    F: Fn(OrsExpr<E>) -> D + 'a,
+{
+    join(style, exprs.first, &exprs.tail, move |expr, f| {
+        write!(f, "{}", item(expr.normalize()))
+    })
+}
+
+/// Joins the special expressions in a day of the month field into a list, where a field of one
+/// special expression is a list of one.
+fn dom_specials<'a, F>(style: &'a ListStyle, doms: &'a DayOfMonthExpr, item: F) -> impl Display + 'a
+where
+    F: Fn(&DayOfMonthSpecial, &mut Formatter) -> fmt::Result + 'a,
+{
+    let (first, rest) = match doms {
+        DayOfMonthExpr::Specials(specials) => {
+            let (first, rest) = specials
+                .split_first()
+                .expect("A list has at least one expression");
+            (first.clone(), rest)
+        }
+        DayOfMonthExpr::Last(last) => (DayOfMonthSpecial::Last(last.clone()), &[][..]),
+        &DayOfMonthExpr::ClosestWeekday(day) => (DayOfMonthSpecial::ClosestWeekday(day), &[][..]),
+        &DayOfMonthExpr::BusinessDay(day) => (DayOfMonthSpecial::BusinessDay(day), &[][..]),
+        _ => unreachable!("Only special expressions are listed"),
+    };
+    join(style, first, rest, item)
+}
+
+/// Joins the special expressions in a day of the week field into a list, where a field of one
+/// special expression is a list of one.
+fn dow_specials<'a, F>(style: &'a ListStyle, dows: &'a DayOfWeekExpr, item: F) -> impl Display + 'a
+where
+    F: Fn(&DayOfWeekSpecial, &mut Formatter) -> fmt::Result + 'a,
+{
+    let (first, rest) = match dows {
+        DayOfWeekExpr::Specials(specials) => {
+            let (first, rest) = specials
+                .split_first()
+                .expect("A list has at least one expression");
+            (first.clone(), rest)
+        }
+        &DayOfWeekExpr::Last(day) => (DayOfWeekSpecial::Last(day), &[][..]),
+        &DayOfWeekExpr::Nth(day, nth) => (DayOfWeekSpecial::Nth(day, nth), &[][..]),
+        _ => unreachable!("Only special expressions are listed"),
+    };
+    join(style, first, rest, item)
+}
+
+/// The times of the day matched by an expression
+enum Times<'a> {
+    /// `* *`
+    EveryMinute,
+    /// Every minute in the given hours, `* 1-5`
+    EveryMinuteDuring(&'a Exprs<Hour>),
+    /// The given minutes of every hour, `0,30 *`
+    MinutesOfEveryHour(&'a Exprs<Minute>),
+    /// One time of the day, `30 12`
+    At(Hour, Minute),
+    /// The given minutes in the given hours, `0,30 1-5`
+    MinutesDuring(&'a Exprs<Minute>, &'a Exprs<Hour>),
+}
+
+/// The parts of an expression every language describes. Walking an expression into its parts
+/// is shared between languages, so each language only has to phrase the parts.
+struct Parts<'a> {
+    times: Times<'a>,
+    /// The days of the month, where a `?` is the same as a `*`
+    doms: &'a DayOfMonthExpr,
+    /// The days of the week, where a `?` is the same as a `*`
+    dows: &'a DayOfWeekExpr,
+    months: &'a Expr<Month>,
+    weeks: &'a Expr<Week>,
+}
+
+impl<'a> Parts<'a> {
+    fn new(expr: &'a CronExpr) -> Self {
+        let times = match (&expr.minutes, &expr.hours) {
+            (Expr::All, Expr::All) => Times::EveryMinute,
+            (Expr::All, Expr::Many(hours)) => Times::EveryMinuteDuring(hours),
+            (Expr::Many(minutes), Expr::All) => Times::MinutesOfEveryHour(minutes),
+            (Expr::Many(minutes), Expr::Many(hours)) => match (
+                minutes.first.normalize(),
+                minutes.tail.as_slice(),
+                hours.first.normalize(),
+                hours.tail.as_slice(),
+            ) {
+                (OrsExpr::One(minute), [], OrsExpr::One(hour), []) => Times::At(hour, minute),
+                _ => Times::MinutesDuring(minutes, hours),
+            },
+        };
+        // a '?' field is described the same as a '*' field since neither restricts the days
+        let doms = match &expr.doms {
+            DayOfMonthExpr::Ignored => &DayOfMonthExpr::All,
+            doms => doms,
+        };
+        let dows = match &expr.dows {
+            DayOfWeekExpr::Ignored => &DayOfWeekExpr::All,
+            dows => dows,
+        };
+
+        Self {
+            times,
+            doms,
+            dows,
+            months: &expr.months,
+            weeks: &expr.weeks,
+        }
+    }
+
+    /// Returns whether the day of month field restricts the days matched
+    fn has_doms(&self) -> bool {
+        !matches!(self.doms, DayOfMonthExpr::All | DayOfMonthExpr::Ignored)
+    }
+
+    /// Returns whether the day of week field restricts the days matched
+    fn has_dows(&self) -> bool {
+        !matches!(self.dows, DayOfWeekExpr::All | DayOfWeekExpr::Ignored)
+    }
+}
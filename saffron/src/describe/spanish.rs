@@ -0,0 +1,460 @@
+use crate::describe::{
+    display, dom_specials, dow_specials, list, mark, marked_segments, without_exclusions,
+    DescriptionSegment, HourFormat, Language, ListStyle, Parts, SegmentKind, Times,
+};
+use crate::parse::*;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+const LIST: ListStyle = ListStyle {
+    pair: " y ",
+    separator: ", ",
+    last: " y ",
+};
+
+const MONTHS: [&str; 12] = [
+    "enero",
+    "febrero",
+    "marzo",
+    "abril",
+    "mayo",
+    "junio",
+    "julio",
+    "agosto",
+    "septiembre",
+    "octubre",
+    "noviembre",
+    "diciembre",
+];
+
+const NTH: [&str; 5] = ["primer", "segundo", "tercer", "cuarto", "quinto"];
+
+fn weekday<T: Into<chrono::Weekday>>(x: T) -> &'static str {
+    use chrono::Weekday::*;
+    match x.into() {
+        Mon => "lunes",
+        Tue => "martes",
+        Wed => "miércoles",
+        Thu => "jueves",
+        Fri => "viernes",
+        Sat => "sábado",
+        Sun => "domingo",
+    }
+}
+
+fn month_name(x: Month) -> &'static str {
+    MONTHS[chrono::Month::from(x).number_from_month() as usize - 1]
+}
+
+fn days(x: u8) -> impl Display {
+    display(move |f| match x {
+        1 => write!(f, "1 día"),
+        x => write!(f, "{} días", x),
+    })
+}
+
+fn time<H: Into<u8>, M: Into<u8>>(format: HourFormat, hour: H, minute: M) -> impl Display {
+    let (hour, minute) = (hour.into(), minute.into());
+    display(move |f| match format {
+        HourFormat::Hour12 => {
+            let period = if hour < 12 { "a. m." } else { "p. m." };
+            write!(f, "{}:{:02} {}", (hour + 11) % 12 + 1, minute, period)
+        }
+        HourFormat::Hour24 => write!(f, "{:02}:{:02}", hour, minute),
+    })
+}
+
+/// Spanish language formatting
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Spanish {
+    /// Configures how hours are formatted in descriptions, with a 24 hour clock by default
+    pub hour: HourFormat,
+}
+
+impl Spanish {
+    /// Creates a new instance of the spanish configuration with its default values
+    pub const fn new() -> Self {
+        Self {
+            hour: HourFormat::Hour24,
+        }
+    }
+
+    fn minute(&self, m: OrsExpr<Minute>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(minute) => write!(f, "{}", u8::from(minute)),
+            OrsExpr::Range(start, end) => write!(f, "{} a {}", u8::from(start), u8::from(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "cada {} minutos del {} al {}",
+                u8::from(step),
+                u8::from(start),
+                u8::from(end)
+            ),
+        })
+    }
+    fn hour(&self, h: OrsExpr<Hour>) -> impl Display {
+        let format = self.hour;
+        display(move |f| match h {
+            OrsExpr::One(hour) => write!(
+                f,
+                "entre las {} y las {}",
+                time(format, hour, 0),
+                time(format, hour, 59)
+            ),
+            OrsExpr::Range(start, end) => {
+                write!(
+                    f,
+                    "entre las {} y las {}",
+                    time(format, start, 0),
+                    time(format, end, 59)
+                )
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "cada {} horas entre las {} y las {}",
+                u8::from(step),
+                time(format, start, 0),
+                time(format, end, 59)
+            ),
+        })
+    }
+    fn month(&self, m: OrsExpr<Month>) -> impl Display {
+        display(move |f| match m {
+            OrsExpr::One(month) => write!(f, "{}", month_name(month)),
+            OrsExpr::Range(start, end) => {
+                write!(f, "{} a {}", month_name(start), month_name(end))
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "cada {} meses de {} a {}",
+                u8::from(step),
+                month_name(start),
+                month_name(end)
+            ),
+        })
+    }
+    fn day_of_week(&self, d: OrsExpr<DayOfWeek>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(day) => write!(f, "el {}", weekday(day)),
+            OrsExpr::Range(start, end) => write!(f, "de {} a {}", weekday(start), weekday(end)),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "cada {} días de la semana de {} a {}",
+                u8::from(step),
+                weekday(start),
+                weekday(end)
+            ),
+        })
+    }
+    fn week(&self, w: OrsExpr<Week>) -> impl Display {
+        display(move |f| match w {
+            OrsExpr::One(week) => write!(f, "en la semana {}", week),
+            OrsExpr::Range(start, end) => write!(f, "de la semana {} a la {}", start, end),
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "cada {} semanas de la semana {} a la {}",
+                u8::from(step),
+                start,
+                end
+            ),
+        })
+    }
+    fn day_of_month(&self, d: OrsExpr<DayOfMonth>) -> impl Display {
+        display(move |f| match d {
+            OrsExpr::One(day) => write!(f, "el día {}", u8::from(day) + 1),
+            OrsExpr::Range(start, end) => {
+                write!(f, "del {} al {}", u8::from(start) + 1, u8::from(end) + 1)
+            }
+            OrsExpr::Step { start, end, step } => write!(
+                f,
+                "cada {} días del {} al {}",
+                u8::from(step),
+                u8::from(start) + 1,
+                u8::from(end) + 1
+            ),
+        })
+    }
+}
+
+impl Default for Spanish {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Language for Spanish {
+    fn fmt_expr(&self, expr: &CronExpr, f: &mut Formatter) -> fmt::Result {
+        let expr = &*without_exclusions(expr);
+        if let Some(shortcut) = expr.shortcut {
+            return match shortcut {
+                Shortcut::Hourly => write!(f, "Cada hora"),
+                Shortcut::Daily => write!(f, "Todos los días a medianoche"),
+                Shortcut::Weekly => write!(f, "Todos los domingos a medianoche"),
+                Shortcut::Monthly => write!(f, "A medianoche el día 1 de cada mes"),
+                Shortcut::Yearly | Shortcut::Annually => write!(f, "A medianoche el 1 de enero"),
+            };
+        }
+
+        let parts = Parts::new(expr);
+        mark(f, SegmentKind::Time)?;
+        match parts.times {
+            Times::EveryMinute => write!(f, "Cada minuto")?,
+            Times::EveryMinuteDuring(hours) => write!(
+                f,
+                "Cada minuto {}",
+                list(&LIST, hours, |hour| self.hour(hour))
+            )?,
+            Times::MinutesOfEveryHour(minutes) => match (minutes.first.normalize(), &*minutes.tail)
+            {
+                (OrsExpr::One(value), []) => match u8::from(value) {
+                    0 => write!(f, "Cada hora"),
+                    1 => write!(f, "Al minuto 1 de cada hora"),
+                    v => write!(f, "A los {} minutos de cada hora", v),
+                }?,
+                (OrsExpr::Range(start, end), []) => write!(
+                    f,
+                    "Los minutos {} a {} de cada hora",
+                    u8::from(start),
+                    u8::from(end)
+                )?,
+                (OrsExpr::Step { start, end, step }, []) => write!(
+                    f,
+                    "Cada {} minutos desde el minuto {} hasta el minuto {} de cada hora",
+                    u8::from(step),
+                    u8::from(start),
+                    u8::from(end)
+                )?,
+                _ => write!(
+                    f,
+                    "En los minutos {} de cada hora",
+                    list(&LIST, minutes, |minute| self.minute(minute))
+                )?,
+            },
+            Times::At(hour, minute) => write!(f, "A las {}", time(self.hour, hour, minute))?,
+            Times::MinutesDuring(minutes, hours) => {
+                match (minutes.first.normalize(), &*minutes.tail) {
+                    (OrsExpr::One(minute), []) => write!(f, "En el minuto {}", u8::from(minute))?,
+                    _ => write!(
+                        f,
+                        "En los minutos {}",
+                        list(&LIST, minutes, |minute| self.minute(minute))
+                    )?,
+                }
+                write!(f, ", {}", list(&LIST, hours, |hour| self.hour(hour)))?
+            }
+        }
+
+        mark(f, SegmentKind::DaysOfMonth)?;
+        match parts.doms {
+            DayOfMonthExpr::All | DayOfMonthExpr::Ignored => {}
+            DayOfMonthExpr::Many(exprs) => {
+                write!(f, " {}", list(&LIST, exprs, |day| self.day_of_month(day)))?
+            }
+            DayOfMonthExpr::ClosestWeekdays(exprs) => write!(
+                f,
+                " {} (o el día laborable más cercano)",
+                list(&LIST, exprs, |day| self.day_of_month(day))
+            )?,
+            doms => write!(f, " {}", dom_specials(&LIST, doms, dom_special))?,
+        }
+
+        mark(f, SegmentKind::Text)?;
+        if parts.has_doms() && parts.has_dows() {
+            write!(f, " y")?;
+        }
+
+        mark(f, SegmentKind::DaysOfWeek)?;
+        match parts.dows {
+            DayOfWeekExpr::All | DayOfWeekExpr::Ignored => {}
+            DayOfWeekExpr::Many(exprs) => {
+                write!(f, " {}", list(&LIST, exprs, |day| self.day_of_week(day)))?
+            }
+            dows => write!(f, " {}", dow_specials(&LIST, dows, dow_special))?,
+        }
+
+        mark(f, SegmentKind::Months)?;
+        match (parts.doms, parts.months, parts.dows) {
+            (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::All)
+            | (DayOfMonthExpr::All, Expr::All, DayOfWeekExpr::Many(_)) => Ok(()),
+            (_, Expr::All, _) => write!(f, " de cada mes"),
+            (DayOfMonthExpr::All, Expr::Many(months), DayOfWeekExpr::All) => write!(
+                f,
+                " todos los días en {}",
+                list(&LIST, months, |month| self.month(month))
+            ),
+            (_, Expr::Many(months), _) => {
+                write!(f, " de {}", list(&LIST, months, |month| self.month(month)))
+            }
+        }?;
+
+        mark(f, SegmentKind::Weeks)?;
+        match parts.weeks {
+            Expr::All => Ok(()),
+            Expr::Many(weeks) => write!(f, ", {}", list(&LIST, weeks, |week| self.week(week))),
+        }
+    }
+
+    fn segments(&self, expr: &CronExpr) -> Vec<DescriptionSegment> {
+        marked_segments(|f| self.fmt_expr(expr, f))
+    }
+}
+
+/// Formats a special day of the month expression, like "el último día laborable"
+fn dom_special(special: &DayOfMonthSpecial, f: &mut Formatter) -> fmt::Result {
+    match special {
+        &DayOfMonthSpecial::ClosestWeekday(day) => write!(
+            f,
+            "el día laborable más cercano al día {}",
+            u8::from(day) + 1
+        ),
+        &DayOfMonthSpecial::BusinessDay(day) => {
+            write!(f, "el día laborable número {}", u8::from(day))
+        }
+        DayOfMonthSpecial::Last(Last::Day) => f.write_str("el último día"),
+        DayOfMonthSpecial::Last(Last::Weekday) => f.write_str("el último día laborable"),
+        &DayOfMonthSpecial::Last(Last::Offset(offset)) => {
+            write!(f, "{} antes del último día", days(u8::from(offset)))
+        }
+        &DayOfMonthSpecial::Last(Last::OffsetWeekday(offset)) => write!(
+            f,
+            "el día laborable más cercano a {} antes del último día",
+            days(u8::from(offset))
+        ),
+        &DayOfMonthSpecial::Last(Last::WeekdayOffset(offset)) => match u8::from(offset) {
+            1 => f.write_str("1 día laborable antes del último día laborable"),
+            offset => write!(
+                f,
+                "{} días laborables antes del último día laborable",
+                offset
+            ),
+        },
+    }
+}
+
+/// Formats a special day of the week expression, like "el segundo lunes"
+fn dow_special(special: &DayOfWeekSpecial, f: &mut Formatter) -> fmt::Result {
+    match *special {
+        DayOfWeekSpecial::Last(day) => write!(f, "el último {}", weekday(day)),
+        DayOfWeekSpecial::Nth(day, nth) => {
+            write!(f, "el {} {}", NTH[u8::from(nth) as usize - 1], weekday(day))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    #[track_caller]
+    fn assert(cron: &str, expected: &str) {
+        let expr = CronExpr::from_str_with(cron, Dialect::Extended).expect("Valid cron expression");
+        let description = expr.describe(Spanish::new()).to_string();
+
+        assert_eq!(description, expected);
+    }
+
+    #[test]
+    fn time() {
+        assert("* * * * *", "Cada minuto");
+        assert("0 * * * *", "Cada hora");
+        assert("1 * * * *", "Al minuto 1 de cada hora");
+        assert("30 * * * *", "A los 30 minutos de cada hora");
+        assert("0 0 * * *", "A las 00:00");
+        assert("30 18 * * *", "A las 18:30");
+        assert("0,30 * * * *", "En los minutos 0 y 30 de cada hora");
+        assert(
+            "0 2,5-10,*/2 * * *",
+            "En el minuto 0, entre las 02:00 y las 02:59, entre las 05:00 y las 10:59 y cada 2 horas entre las 00:00 y las 23:59",
+        );
+        assert("* 9-17 * * *", "Cada minuto entre las 09:00 y las 17:59");
+    }
+
+    #[test]
+    fn hour_12() {
+        let cfg = Spanish {
+            hour: HourFormat::Hour12,
+        };
+        let describe = |cron: &str| {
+            let expr: CronExpr = cron.parse().expect("Valid cron expression");
+            expr.describe(cfg.clone()).to_string()
+        };
+        assert_eq!(describe("0 0 * * *"), "A las 12:00 a. m.");
+        assert_eq!(describe("30 18 * * *"), "A las 6:30 p. m.");
+        assert_eq!(
+            describe("* 9-17 * * *"),
+            "Cada minuto entre las 9:00 a. m. y las 5:59 p. m."
+        );
+    }
+
+    #[test]
+    fn days() {
+        assert("0 0 15 * *", "A las 00:00 el día 15 de cada mes");
+        assert("0 0 1-15 * *", "A las 00:00 del 1 al 15 de cada mes");
+        assert("0 0 L * *", "A las 00:00 el último día de cada mes");
+        assert(
+            "0 0 LW * *",
+            "A las 00:00 el último día laborable de cada mes",
+        );
+        assert(
+            "0 0 LW-1 * *",
+            "A las 00:00 1 día laborable antes del último día laborable de cada mes",
+        );
+        assert(
+            "0 0 L,1W * *",
+            "A las 00:00 el último día y el día laborable más cercano al día 1 de cada mes",
+        );
+        assert(
+            "0 0 * * MON#1,MON#3",
+            "A las 00:00 el primer lunes y el tercer lunes de cada mes",
+        );
+        assert(
+            "0 0 15W * *",
+            "A las 00:00 el día laborable más cercano al día 15 de cada mes",
+        );
+        assert(
+            "0 0 1-7W * *",
+            "A las 00:00 del 1 al 7 (o el día laborable más cercano) de cada mes",
+        );
+        assert(
+            "0 0 1,15W * *",
+            "A las 00:00 el día 1 y el día 15 (o el día laborable más cercano) de cada mes",
+        );
+        assert("0 0 * * MON-FRI", "A las 00:00 de lunes a viernes");
+        assert("0 0 * * FRIL", "A las 00:00 el último viernes de cada mes");
+        assert("0 0 * * MON#3", "A las 00:00 el tercer lunes de cada mes");
+        assert(
+            "0 0 13 * FRI",
+            "A las 00:00 el día 13 y el viernes de cada mes",
+        );
+    }
+
+    #[test]
+    fn weeks() {
+        assert(
+            "0 9 * * MON W/2",
+            "A las 09:00 el lunes, cada 2 semanas de la semana 1 a la 53",
+        );
+        assert("* * * * * W5", "Cada minuto, en la semana 5");
+    }
+
+    #[test]
+    fn months() {
+        assert("0 0 * MAY *", "A las 00:00 todos los días en mayo");
+        assert(
+            "0 0 1 JAN,JUN-AUG *",
+            "A las 00:00 el día 1 de enero y junio a agosto",
+        );
+    }
+
+    #[test]
+    fn shortcuts() {
+        assert("@hourly", "Cada hora");
+        assert("@weekly", "Todos los domingos a medianoche");
+        assert("@monthly", "A medianoche el día 1 de cada mes");
+    }
+}
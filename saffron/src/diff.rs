@@ -0,0 +1,319 @@
+//! Comparing two cron expressions, to show how a schedule changed when it's edited.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::parse::{CronExpr, FieldKind, FieldView};
+use crate::{Cron, CronTimesIter, FieldValues};
+use chrono::{DateTime, Utc};
+use core::fmt::{self, Display, Formatter};
+use core::iter::{FusedIterator, Peekable};
+
+/// Compares two expressions, reporting the values added to and removed from each field and the
+/// times only one of them matches.
+///
+/// # Example
+/// ```
+/// use saffron::{diff, DiffChange, FieldChange};
+/// use saffron::parse::{CronExpr, FieldKind};
+/// use chrono::prelude::*;
+///
+/// let before: CronExpr = "0 3,12 * * MON-FRI".parse().unwrap();
+/// let after: CronExpr = "0 12 * * MON-SAT".parse().unwrap();
+/// let diff = diff(&before, &after);
+///
+/// assert_eq!(diff.fields().len(), 2);
+/// let hours = diff.field(FieldKind::Hours).unwrap();
+/// assert_eq!(hours.to_string(), "hours: no longer 3");
+/// let days = diff.field(FieldKind::DaysOfWeek).unwrap();
+/// assert_eq!(days.to_string(), "day of week: now also 7");
+///
+/// let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+/// let examples = diff.examples_from(start).take(2).collect::<Vec<_>>();
+/// assert_eq!(examples[0].time, Utc.with_ymd_and_hms(2021, 1, 1, 3, 0, 0).unwrap());
+/// assert_eq!(examples[0].change, DiffChange::Removed);
+/// assert_eq!(examples[1].time, Utc.with_ymd_and_hms(2021, 1, 2, 12, 0, 0).unwrap());
+/// assert_eq!(examples[1].change, DiffChange::Added);
+/// ```
+pub fn diff(before: &CronExpr, after: &CronExpr) -> CronDiff {
+    let fields = FieldKind::ALL
+        .iter()
+        .filter_map(|&field| {
+            let change = FieldChange::new(field, before.field(field), after.field(field))?;
+            Some(FieldDiff { field, change })
+        })
+        .collect();
+
+    CronDiff {
+        fields,
+        before: Cron::new(before.clone()),
+        after: Cron::new(after.clone()),
+    }
+}
+
+/// The differences between two expressions, returned by [`diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronDiff {
+    fields: Vec<FieldDiff>,
+    before: Cron,
+    after: Cron,
+}
+
+impl CronDiff {
+    /// Returns the changes to each field that changed, in the order the fields are written.
+    pub fn fields(&self) -> &[FieldDiff] {
+        &self.fields
+    }
+
+    /// Returns the change to the given field, if it changed.
+    pub fn field(&self, field: FieldKind) -> Option<&FieldDiff> {
+        self.fields.iter().find(|diff| diff.field == field)
+    }
+
+    /// Returns whether the expressions match the same times. Expressions written differently
+    /// can still match the same times, like `0 0 L APR *` and `0 0 30 APR *`.
+    pub fn is_equivalent(&self) -> bool {
+        self.before.equivalent_to(&self.after)
+    }
+
+    /// Creates an iterator over the times from the given date that only one of the expressions
+    /// matches, in order. The iterator is empty if the expressions are equivalent.
+    pub fn examples_from(&self, start: DateTime<Utc>) -> DiffExamples {
+        let (before, after) = if self.is_equivalent() {
+            (None, None)
+        } else {
+            (
+                Some(self.before.clone().iter_from(start).peekable()),
+                Some(self.after.clone().iter_from(start).peekable()),
+            )
+        };
+        DiffExamples { before, after }
+    }
+}
+
+/// The change to one field of an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FieldDiff {
+    /// The field that changed
+    pub field: FieldKind,
+    /// How the field changed
+    pub change: FieldChange,
+}
+
+impl Display for FieldDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        fn values(f: &mut Formatter, values: FieldValues) -> fmt::Result {
+            for (i, value) in values.enumerate() {
+                if i != 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", value)?;
+            }
+            Ok(())
+        }
+
+        write!(f, "{}: ", self.field)?;
+        match self.change {
+            FieldChange::Values { added, removed } => {
+                if added.len() != 0 {
+                    f.write_str("now also ")?;
+                    values(f, added)?;
+                }
+                if removed.len() != 0 {
+                    if added.len() != 0 {
+                        f.write_str("; ")?;
+                    }
+                    f.write_str("no longer ")?;
+                    values(f, removed)?;
+                }
+                Ok(())
+            }
+            FieldChange::Replaced { .. } => f.write_str("changed"),
+        }
+    }
+}
+
+/// How a field changed between two expressions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldChange {
+    /// The field matches a set of values in both expressions, and values were added, removed,
+    /// or both. Values are numbered like in an expression, so the days of the week are 1-7
+    /// starting from Sunday. A '?' in a day field matches every day.
+    Values {
+        /// The values only matched after the change
+        added: FieldValues,
+        /// The values only matched before the change
+        removed: FieldValues,
+    },
+    /// The field has special expressions like `L` or `MON#2` before or after the change, so it
+    /// isn't compared value by value
+    Replaced {
+        /// The field before the change
+        before: FieldView,
+        /// The field after the change
+        after: FieldView,
+    },
+}
+
+impl FieldChange {
+    /// Compares a field in two expressions, returning `None` if it didn't change.
+    fn new(kind: FieldKind, before: FieldView, after: FieldView) -> Option<Self> {
+        // a '?' doesn't restrict the days matched, so it's compared like a '*'
+        let values = |field: &FieldView| match field {
+            FieldView::Ignored => FieldView::all_of(kind).values(),
+            field => field.values(),
+        };
+        match (values(&before), values(&after)) {
+            (Some(old), Some(new)) if old == new => None,
+            (Some(old), Some(new)) => Some(FieldChange::Values {
+                added: FieldValues::new(new.bits & !old.bits, new.offset),
+                removed: FieldValues::new(old.bits & !new.bits, old.offset),
+            }),
+            _ if before == after => None,
+            _ => Some(FieldChange::Replaced { before, after }),
+        }
+    }
+}
+
+/// Whether a time is only matched after or before a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffChange {
+    /// The time is only matched by the expression after the change
+    Added,
+    /// The time is only matched by the expression before the change
+    Removed,
+}
+
+/// A time only one of two expressions matches, yielded by [`DiffExamples`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DiffExample {
+    /// The time matched
+    pub time: DateTime<Utc>,
+    /// Which expression matches the time
+    pub change: DiffChange,
+}
+
+/// An iterator over the times only one of two expressions matches. Created with
+/// [`CronDiff::examples_from`].
+///
+/// [`CronDiff::examples_from`]: struct.CronDiff.html#method.examples_from
+pub struct DiffExamples {
+    before: Option<Peekable<CronTimesIter>>,
+    after: Option<Peekable<CronTimesIter>>,
+}
+
+impl Iterator for DiffExamples {
+    type Item = DiffExample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (before, after) = match (&mut self.before, &mut self.after) {
+            (Some(before), Some(after)) => (before, after),
+            _ => return None,
+        };
+
+        loop {
+            let (time, change) = match (before.peek(), after.peek()) {
+                (None, None) => return None,
+                (Some(&old), Some(&new)) if old == new => {
+                    before.next();
+                    after.next();
+                    continue;
+                }
+                (Some(&old), Some(&new)) if old < new => (old, DiffChange::Removed),
+                (Some(_), Some(&new)) | (None, Some(&new)) => (new, DiffChange::Added),
+                (Some(&old), None) => (old, DiffChange::Removed),
+            };
+            match change {
+                DiffChange::Removed => before.next(),
+                DiffChange::Added => after.next(),
+            };
+            return Some(DiffExample { time, change });
+        }
+    }
+}
+
+impl FusedIterator for DiffExamples {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{string::ToString, vec::Vec};
+
+    fn diff_str(before: &str, after: &str) -> CronDiff {
+        diff(
+            &before.parse().expect("Valid cron expression"),
+            &after.parse().expect("Valid cron expression"),
+        )
+    }
+
+    #[test]
+    fn fields() {
+        let diff = diff_str("15,45 9-17 * * MON-FRI", "0,30 9-17 * * MON-FRI");
+        assert_eq!(diff.fields().len(), 1);
+        assert_eq!(
+            diff.fields()[0].to_string(),
+            "minutes: now also 0, 30; no longer 15, 45"
+        );
+        assert!(!diff.is_equivalent());
+
+        // a '?' is the same as a '*'
+        let diff = diff_str("0 0 ? * MON", "0 0 * * MON");
+        assert!(diff.fields().is_empty());
+        assert!(diff.is_equivalent());
+
+        let diff = diff_str("0 0 L * ?", "0 0 15W * ?");
+        let doms = diff.field(FieldKind::DaysOfMonth).unwrap();
+        assert!(matches!(doms.change, FieldChange::Replaced { .. }));
+        assert_eq!(doms.to_string(), "day of month: changed");
+    }
+
+    #[test]
+    fn equivalent_exprs_have_no_examples() {
+        let diff = diff_str("0 0 L APR *", "0 0 30 APR *");
+        assert!(diff.is_equivalent());
+        assert_eq!(
+            diff.fields()[0].field,
+            FieldKind::DaysOfMonth,
+            "the fields are still written differently"
+        );
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(diff.examples_from(start).next(), None);
+    }
+
+    #[test]
+    fn examples() {
+        let diff = diff_str("0 0 1 * *", "0 0 1,15 1-2 *");
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let examples = diff.examples_from(start).take(3).collect::<Vec<_>>();
+        assert_eq!(
+            examples,
+            [
+                DiffExample {
+                    time: Utc.with_ymd_and_hms(2021, 1, 15, 0, 0, 0).unwrap(),
+                    change: DiffChange::Added,
+                },
+                DiffExample {
+                    time: Utc.with_ymd_and_hms(2021, 2, 15, 0, 0, 0).unwrap(),
+                    change: DiffChange::Added,
+                },
+                DiffExample {
+                    time: Utc.with_ymd_and_hms(2021, 3, 1, 0, 0, 0).unwrap(),
+                    change: DiffChange::Removed,
+                },
+            ]
+        );
+
+        // an expression that never matches only has removed times
+        let diff = diff_str("0 0 1 * *", "0 0 30 2 *");
+        assert!(diff
+            .examples_from(start)
+            .take(3)
+            .all(|example| example.change == DiffChange::Removed));
+    }
+}
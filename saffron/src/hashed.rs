@@ -0,0 +1,451 @@
+//! Cron expressions with Jenkins style hashed values.
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::parse::{CronExpr, CronParseError, FieldKind};
+use crate::Cron;
+use core::fmt::{self, Display, Formatter, Write};
+use core::str::FromStr;
+
+/// The fields of an expression in order with the range of values a hashed value can take.
+const FIELDS: [(FieldKind, u8, u8); 5] = [
+    (FieldKind::Minutes, 0, 59),
+    (FieldKind::Hours, 0, 23),
+    (FieldKind::DaysOfMonth, 1, 31),
+    (FieldKind::Months, 1, 12),
+    (FieldKind::DaysOfWeek, 1, 7),
+];
+
+/// The last day of the month a single hashed day of the month can be, so it exists in every
+/// month.
+const LAST_HASHED_DAY: u8 = 28;
+
+/// A cron expression where values can be hashed from a seed, like the `H` syntax of Jenkins.
+/// Hashing spreads the times many schedules fire at, so workers given the same expression
+/// don't all fire at the same minute.
+///
+/// Any value in a list can be replaced with a hashed value:
+///
+/// | Syntax      | Meaning                                                     |
+/// | ----------- | ----------------------------------------------------------- |
+/// | `H`         | One value in the range of the field                         |
+/// | `H(a-b)`    | One value from `a` to `b`                                   |
+/// | `H/n`       | Every `n`th value of the field, starting at a hashed offset |
+/// | `H(a-b)/n`  | Every `n`th value from `a` to `b`, starting at a hashed offset |
+///
+/// A single hashed day of the month is picked from the 1st to the 28th so it exists in every
+/// month. Hashed values are resolved with [`resolve_hashed`], which always picks the same
+/// values for the same seed.
+///
+/// [`resolve_hashed`]: #method.resolve_hashed
+///
+/// # Example
+/// ```
+/// use saffron::HashedCron;
+///
+/// let hashed = "H H(0-7) * * *".parse::<HashedCron>().expect("Couldn't parse expression!");
+/// let cron = hashed.resolve_hashed(42);
+///
+/// // one time between midnight and 7:59 every day
+/// assert_eq!(cron.minutes().count(), 1);
+/// assert!(cron.hours().all(|hour| hour <= 7));
+///
+/// assert_eq!(cron, hashed.resolve_hashed(42));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HashedCron {
+    source: String,
+}
+
+impl HashedCron {
+    /// Resolves the hashed values in the expression using the given seed.
+    pub fn resolve_expr(&self, seed: u64) -> CronExpr {
+        resolve(&self.source, seed).expect("The expression was validated when parsed")
+    }
+
+    /// Resolves the hashed values in the expression using the given seed and compiles the
+    /// resulting expression into a cron value.
+    pub fn resolve_hashed(&self, seed: u64) -> Cron {
+        Cron::new(self.resolve_expr(seed))
+    }
+}
+
+impl FromStr for HashedCron {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // any seed picks values in range, so one is enough to check the rest of the expression
+        resolve(s, 0)?;
+        Ok(HashedCron {
+            source: s.to_string(),
+        })
+    }
+}
+
+impl Display for HashedCron {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A hashed value replaced in the resolved expression.
+struct Replacement {
+    /// The byte range of the value in the source expression
+    source: (usize, usize),
+    /// The byte range of the value in the resolved expression
+    resolved: (usize, usize),
+}
+
+/// Mixes the seed with the position of a hashed value.
+fn hash(seed: u64, field: usize, item: usize) -> u64 {
+    splitmix64(seed ^ ((field as u64) << 32 | item as u64))
+}
+
+/// Scrambles the bits of a value (splitmix64).
+pub(crate) fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Replaces the hashed values in the expression and parses it.
+fn resolve(s: &str, seed: u64) -> Result<CronExpr, CronParseError> {
+    if s.starts_with('@') {
+        return s.parse();
+    }
+
+    let bytes = s.as_bytes();
+    let mut resolved = String::with_capacity(s.len());
+    let mut replacements = Vec::new();
+    let mut field = 0;
+    let mut item = 0;
+    let mut copied = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            let next = i + 1;
+            if next < bytes.len() && !bytes[next].is_ascii_whitespace() {
+                field += 1;
+                item = 0;
+            }
+        } else if c == b',' {
+            item += 1;
+        }
+
+        let starts_item = i == 0 || matches!(bytes[i - 1], b',' | b' ' | b'\t');
+        if c != b'H' || !starts_item || field >= FIELDS.len() {
+            i += 1;
+            continue;
+        }
+
+        let (end, value) = hashed_value(s, i, FIELDS[field], hash(seed, field, item))?;
+        resolved.push_str(&s[copied..i]);
+        let start = resolved.len();
+        let _ = write!(resolved, "{}", value);
+        replacements.push(Replacement {
+            source: (i, end),
+            resolved: (start, resolved.len()),
+        });
+        i = end;
+        copied = end;
+    }
+    resolved.push_str(&s[copied..]);
+
+    resolved.parse().map_err(|err: CronParseError| {
+        let position = source_position(&replacements, err.position());
+        with_position(err, position)
+    })
+}
+
+/// A resolved hashed value
+enum Resolved {
+    One(u8),
+    Step { start: u8, end: u8, step: u8 },
+}
+
+impl Display for Resolved {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Resolved::One(value) => value.fmt(f),
+            Resolved::Step { start, end, step } => write!(f, "{}-{}/{}", start, end, step),
+        }
+    }
+}
+
+/// Reads the hashed value starting at the `H` at the given position, returning the end of the
+/// value and the resolved value.
+fn hashed_value(
+    s: &str,
+    start: usize,
+    (field, min, max): (FieldKind, u8, u8),
+    hash: u64,
+) -> Result<(usize, Resolved), CronParseError> {
+    let bytes = s.as_bytes();
+    let mut i = start + 1;
+    let mut range = None;
+    if bytes.get(i) == Some(&b'(') {
+        let (next, low) = number(s, i + 1, field, min, max)?;
+        if bytes.get(next) != Some(&b'-') {
+            return Err(CronParseError::InvalidField { field, at: next });
+        }
+        let (next, high) = number(s, next + 1, field, low, max)?;
+        if bytes.get(next) != Some(&b')') {
+            return Err(CronParseError::InvalidField { field, at: next });
+        }
+        range = Some((low, high));
+        i = next + 1;
+    }
+
+    let mut step = None;
+    if bytes.get(i) == Some(&b'/') {
+        let (next, value) = number(s, i + 1, field, 1, max - min)?;
+        step = Some(value);
+        i = next;
+    }
+
+    match bytes.get(i) {
+        None | Some(b',') | Some(b' ') | Some(b'\t') => {}
+        Some(_) => return Err(CronParseError::InvalidField { field, at: i }),
+    }
+
+    let resolved = match (range, step) {
+        (Some((low, high)), None) => Resolved::One(pick(hash, low, high)),
+        (None, None) if field == FieldKind::DaysOfMonth => {
+            Resolved::One(pick(hash, min, LAST_HASHED_DAY))
+        }
+        (None, None) => Resolved::One(pick(hash, min, max)),
+        (range, Some(step)) => {
+            let (low, high) = range.unwrap_or((min, max));
+            let offset = pick(hash, 0, core::cmp::min(step, high - low + 1) - 1);
+            Resolved::Step {
+                start: low + offset,
+                end: high,
+                step,
+            }
+        }
+    };
+    Ok((i, resolved))
+}
+
+/// Picks a value from `low` to `high` using the hash.
+fn pick(hash: u64, low: u8, high: u8) -> u8 {
+    low + (hash % (u64::from(high - low) + 1)) as u8
+}
+
+/// Reads a number from `min` to `max` at the given position.
+fn number(
+    s: &str,
+    at: usize,
+    field: FieldKind,
+    min: u8,
+    max: u8,
+) -> Result<(usize, u8), CronParseError> {
+    let digits = s[at..].bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return Err(CronParseError::InvalidField { field, at });
+    }
+
+    // saturate values that don't fit, they're out of range anyway
+    let value = s[at..at + digits].parse::<u32>().unwrap_or(u32::MAX);
+    if value < u32::from(min) || value > u32::from(max) {
+        return Err(CronParseError::ValueOutOfRange {
+            field,
+            value,
+            min,
+            max,
+            at,
+        });
+    }
+    Ok((at + digits, value as u8))
+}
+
+/// Maps a position in the resolved expression back to the source expression.
+fn source_position(replacements: &[Replacement], position: usize) -> usize {
+    match replacements
+        .iter()
+        .rev()
+        .find(|replacement| replacement.resolved.0 <= position)
+    {
+        Some(replacement) if position < replacement.resolved.1 => replacement.source.0,
+        Some(replacement) => position - replacement.resolved.1 + replacement.source.1,
+        None => position,
+    }
+}
+
+/// Replaces the position of an error.
+fn with_position(err: CronParseError, position: usize) -> CronParseError {
+    match err {
+        CronParseError::InvalidField { field, .. } => CronParseError::InvalidField {
+            field,
+            at: position,
+        },
+        CronParseError::ValueOutOfRange {
+            field,
+            value,
+            min,
+            max,
+            ..
+        } => CronParseError::ValueOutOfRange {
+            field,
+            value,
+            min,
+            max,
+            at: position,
+        },
+        CronParseError::MissingField { field, .. } => CronParseError::MissingField {
+            field,
+            at: position,
+        },
+        CronParseError::TrailingInput { .. } => CronParseError::TrailingInput { at: position },
+        CronParseError::TooManyTerms { field, max, .. } => CronParseError::TooManyTerms {
+            field,
+            max,
+            at: position,
+        },
+        err @ CronParseError::UnknownShortcut | err @ CronParseError::TooLong { .. } => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(s: &str, seed: u64) -> String {
+        s.parse::<HashedCron>()
+            .unwrap()
+            .resolve_expr(seed)
+            .to_string()
+    }
+
+    #[test]
+    fn plain_expressions() {
+        for &expr in &[
+            "* * * * *",
+            "0 9-17 1,15 JAN-MAR ?",
+            "0 0 ? * MON#2",
+            "@hourly",
+        ] {
+            let hashed: HashedCron = expr.parse().unwrap();
+            assert_eq!(hashed.resolve_expr(7), expr.parse::<CronExpr>().unwrap());
+            assert_eq!(hashed.to_string(), expr);
+        }
+    }
+
+    #[test]
+    fn values_in_range() {
+        for seed in 0..500 {
+            let cron = "H H(9-17) H * H"
+                .parse::<HashedCron>()
+                .unwrap()
+                .resolve_hashed(seed);
+            assert_eq!(cron.minutes().count(), 1);
+            let hour = cron.hours().next().unwrap();
+            assert!((9..=17).contains(&hour));
+            assert!(matches!(
+                cron.days_of_month(),
+                crate::DayOfMonthSpec::Days(days) if days.max() <= Some(28)
+            ));
+            assert!(matches!(
+                cron.days_of_week(),
+                crate::DayOfWeekSpec::Days(days) if days.count() == 1
+            ));
+        }
+    }
+
+    #[test]
+    fn steps() {
+        for seed in 0..500 {
+            let cron = "H/15 H(8-20)/4 * * *"
+                .parse::<HashedCron>()
+                .unwrap()
+                .resolve_hashed(seed);
+            let minutes: Vec<_> = cron.minutes().collect();
+            assert_eq!(minutes.len(), 4);
+            assert!(minutes[0] < 15);
+            assert!(minutes.windows(2).all(|pair| pair[1] - pair[0] == 15));
+
+            let hours: Vec<_> = cron.hours().collect();
+            assert!(hours[0] >= 8 && hours[0] < 12);
+            assert!(hours.iter().all(|&hour| hour <= 20));
+            assert!(hours.windows(2).all(|pair| pair[1] - pair[0] == 4));
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        let expr = "H,H(30-59) H * * H";
+        assert_eq!(resolve(expr, 1234), resolve(expr, 1234));
+        // different seeds spread the values
+        let distinct = (0..100)
+            .map(|seed| resolve("H * * * *", seed))
+            .collect::<Vec<_>>();
+        assert!(distinct.iter().any(|expr| *expr != distinct[0]));
+    }
+
+    #[test]
+    fn errors() {
+        let err = |s: &str| s.parse::<HashedCron>().unwrap_err();
+        assert_eq!(
+            err("H(0-60) * * * *"),
+            CronParseError::ValueOutOfRange {
+                field: FieldKind::Minutes,
+                value: 60,
+                min: 0,
+                max: 59,
+                at: 4,
+            }
+        );
+        assert_eq!(
+            err("0 H(5-2) * * *"),
+            CronParseError::ValueOutOfRange {
+                field: FieldKind::Hours,
+                value: 2,
+                min: 5,
+                max: 23,
+                at: 6,
+            }
+        );
+        assert_eq!(
+            err("0 H/0 * * *"),
+            CronParseError::ValueOutOfRange {
+                field: FieldKind::Hours,
+                value: 0,
+                min: 1,
+                max: 23,
+                at: 4,
+            }
+        );
+        assert_eq!(
+            err("H-5 * * * *"),
+            CronParseError::InvalidField {
+                field: FieldKind::Minutes,
+                at: 1,
+            }
+        );
+        assert_eq!(
+            err("H(0-5 * * * *"),
+            CronParseError::InvalidField {
+                field: FieldKind::Minutes,
+                at: 5,
+            }
+        );
+        // errors after a hashed value point at the source expression
+        assert_eq!(
+            err("H(0-59)/15 H 32 * *"),
+            CronParseError::ValueOutOfRange {
+                field: FieldKind::DaysOfMonth,
+                value: 32,
+                min: 1,
+                max: 31,
+                at: 13,
+            }
+        );
+        assert_eq!(err("H H * * * H"), CronParseError::TrailingInput { at: 9 });
+    }
+}
@@ -0,0 +1,361 @@
+//! Occupancy grids of the minutes a cron value matches, for drawing how dense a schedule is
+//! without iterating every time it matches.
+
+use crate::{days_in_month, Cron};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// The minutes of the day a cron value matches on every day it matches. Every matching day has
+/// the same times, since the time fields don't depend on the date.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+struct DayTimes {
+    minutes: u64,
+    hours: u32,
+}
+
+impl DayTimes {
+    fn new(cron: &Cron) -> Self {
+        Self {
+            minutes: cron.minutes.0,
+            hours: cron.hours.0,
+        }
+    }
+
+    fn minutes_in_hour(self, hour: u32) -> u32 {
+        if hour < 24 && self.hours & (1 << hour) != 0 {
+            self.minutes.count_ones()
+        } else {
+            0
+        }
+    }
+
+    fn contains(self, hour: u32, minute: u32) -> bool {
+        minute < 60 && self.minutes_in_hour(hour) != 0 && self.minutes & (1 << minute) != 0
+    }
+
+    fn to_hours(self) -> [u8; 24] {
+        let mut hours = [0; 24];
+        for (hour, count) in (0..).zip(hours.iter_mut()) {
+            *count = self.minutes_in_hour(hour) as u8;
+        }
+        hours
+    }
+}
+
+/// The minutes a cron value can match on each day of the week. Created with
+/// [`Cron::weekly_heatmap`].
+///
+/// [`Cron::weekly_heatmap`]: struct.Cron.html#method.weekly_heatmap
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct WeeklyHeatmap {
+    /// The days of the week matched, with Sunday as bit 0
+    weekdays: u8,
+    times: DayTimes,
+}
+
+impl WeeklyHeatmap {
+    /// Returns whether the cron value can match on the given day of the week.
+    pub fn contains_weekday(&self, weekday: Weekday) -> bool {
+        self.weekdays & (1 << weekday.num_days_from_sunday()) != 0
+    }
+
+    /// Returns the number of minutes (0-60) the cron value can match in the given hour (0-23)
+    /// of the given day of the week.
+    pub fn minutes_in_hour(&self, weekday: Weekday, hour: u32) -> u32 {
+        if self.contains_weekday(weekday) {
+            self.times.minutes_in_hour(hour)
+        } else {
+            0
+        }
+    }
+
+    /// Returns whether the cron value can match the given hour (0-23) and minute (0-59) of the
+    /// given day of the week.
+    pub fn contains(&self, weekday: Weekday, hour: u32, minute: u32) -> bool {
+        self.contains_weekday(weekday) && self.times.contains(hour, minute)
+    }
+
+    /// Returns the number of minutes matched in every hour of every day of the week, as rows of
+    /// 24 hours starting from Sunday.
+    pub fn to_hours(&self) -> [[u8; 24]; 7] {
+        let hours = self.times.to_hours();
+        let mut days = [[0; 24]; 7];
+        for (day, row) in days.iter_mut().enumerate() {
+            if self.weekdays & (1 << day) != 0 {
+                *row = hours;
+            }
+        }
+        days
+    }
+}
+
+/// The minutes a cron value matches on each day of a month. Created with
+/// [`Cron::monthly_heatmap`].
+///
+/// [`Cron::monthly_heatmap`]: struct.Cron.html#method.monthly_heatmap
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct MonthlyHeatmap {
+    /// The days of the month matched, with the first day as bit 0
+    days: u32,
+    len: u32,
+    times: DayTimes,
+}
+
+impl MonthlyHeatmap {
+    /// Returns the number of days in the month, 28-31.
+    pub fn days_in_month(&self) -> u32 {
+        self.len
+    }
+
+    /// Returns whether the cron value matches on the given day of the month (1-31).
+    pub fn contains_day(&self, day: u32) -> bool {
+        (1..=self.len).contains(&day) && self.days & (1 << (day - 1)) != 0
+    }
+
+    /// Returns the number of minutes (0-60) the cron value matches in the given hour (0-23) of
+    /// the given day of the month (1-31).
+    pub fn minutes_in_hour(&self, day: u32, hour: u32) -> u32 {
+        if self.contains_day(day) {
+            self.times.minutes_in_hour(hour)
+        } else {
+            0
+        }
+    }
+
+    /// Returns whether the cron value matches the given hour (0-23) and minute (0-59) of the
+    /// given day of the month (1-31).
+    pub fn contains(&self, day: u32, hour: u32, minute: u32) -> bool {
+        self.contains_day(day) && self.times.contains(hour, minute)
+    }
+
+    /// Returns the number of minutes matched in every hour of every day of the month, as rows
+    /// of 24 hours starting from the first day. The rows past the end of the month are empty.
+    pub fn to_hours(&self) -> [[u8; 24]; 31] {
+        let hours = self.times.to_hours();
+        let mut days = [[0; 24]; 31];
+        for (day, row) in (1..).zip(days.iter_mut()) {
+            if self.contains_day(day) {
+                *row = hours;
+            }
+        }
+        days
+    }
+}
+
+impl Cron {
+    /// Returns the minutes the cron value can match on each day of the week. A day of the week
+    /// is filled in if the cron value matches at least one date on it, so `0 0 1 * *` fills
+    /// every day of the week since the first of the month can be any of them, and `0 0 ? * MON#2`
+    /// only fills Monday.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::Weekday;
+    ///
+    /// let cron = "*/15 9-17 * * MON-FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let heatmap = cron.weekly_heatmap();
+    ///
+    /// assert_eq!(heatmap.minutes_in_hour(Weekday::Mon, 9), 4);
+    /// assert_eq!(heatmap.minutes_in_hour(Weekday::Mon, 18), 0);
+    /// assert_eq!(heatmap.minutes_in_hour(Weekday::Sat, 9), 0);
+    /// assert!(heatmap.contains(Weekday::Fri, 17, 45));
+    ///
+    /// // rows start from Sunday
+    /// let hours = heatmap.to_hours();
+    /// assert_eq!(hours[1][9], 4);
+    /// assert_eq!(hours[0], [0; 24]);
+    /// ```
+    pub fn weekly_heatmap(&self) -> WeeklyHeatmap {
+        // the days of the week, days of the month, months, and leap years repeat every 28 years
+        // when no year in between skips a leap year, so every date that could ever match falls
+        // on the same days of the week as a date in these years
+        let mut date = NaiveDate::from_ymd_opt(2001, 1, 1).expect("valid date");
+        let end = NaiveDate::from_ymd_opt(2029, 1, 1).expect("valid date");
+
+        let mut weekdays = 0;
+        while weekdays != 0x7F && date < end {
+            if self.contains_date(date) {
+                weekdays |= 1 << date.weekday().num_days_from_sunday();
+            }
+            date = date.succ_opt().expect("valid date");
+        }
+
+        WeeklyHeatmap {
+            weekdays,
+            times: DayTimes::new(self),
+        }
+    }
+
+    /// Returns the minutes the cron value matches on each day of the given month (1-12) of the
+    /// year, or none if the month doesn't exist. Days like `L`, `15W`, and `MON#2` are matched
+    /// on the dates they fall on in that month.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let cron = "0 12 LW * ?".parse::<Cron>().expect("Couldn't parse expression!");
+    /// // the last weekday of February 2021 is Friday the 26th
+    /// let heatmap = cron.monthly_heatmap(2021, 2).unwrap();
+    ///
+    /// assert_eq!(heatmap.days_in_month(), 28);
+    /// assert!(heatmap.contains_day(26));
+    /// assert!(!heatmap.contains_day(28));
+    /// assert_eq!(heatmap.minutes_in_hour(26, 12), 1);
+    /// assert_eq!(heatmap.to_hours()[25][12], 1);
+    /// ```
+    pub fn monthly_heatmap(&self, year: i32, month: u32) -> Option<MonthlyHeatmap> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let len = days_in_month(first);
+
+        let mut days = 0;
+        for day in 1..=len {
+            let date = first.with_day(day).expect("valid date");
+            if self.contains_date(date) {
+                days |= 1 << (day - 1);
+            }
+        }
+
+        Some(MonthlyHeatmap {
+            days,
+            len,
+            times: DayTimes::new(self),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use chrono::{prelude::*, Duration};
+
+    fn cron(s: &str) -> Cron {
+        s.parse().expect("Valid cron expression")
+    }
+
+    const WEEKDAYS: [Weekday; 7] = [
+        Weekday::Sun,
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+    ];
+
+    #[test]
+    fn weekly_days() {
+        let days = |s: &str| {
+            let heatmap = cron(s).weekly_heatmap();
+            let mut days = [false; 7];
+            for (day, &weekday) in days.iter_mut().zip(WEEKDAYS.iter()) {
+                *day = heatmap.contains_weekday(weekday);
+            }
+            days
+        };
+
+        assert_eq!(days("* * * * *"), [true; 7]);
+        assert_eq!(days("0 0 1 * *"), [true; 7]);
+        assert_eq!(
+            days("0 0 ? * MON#2"),
+            [false, true, false, false, false, false, false]
+        );
+        assert_eq!(
+            days("0 0 ? * 6L"),
+            [false, false, false, false, false, true, false]
+        );
+        assert_eq!(
+            days("0 0 ? * SAT,SUN"),
+            [true, false, false, false, false, false, true]
+        );
+        assert_eq!(days("0 0 30 2 *"), [false; 7]);
+        // February 29th falls on every day of the week
+        assert_eq!(days("0 0 29 2 *"), [true; 7]);
+    }
+
+    #[test]
+    fn weekly_hours() {
+        let heatmap = cron("0-9,30 8,20-21 * * TUE").weekly_heatmap();
+        let hours = heatmap.to_hours();
+        for (day, row) in hours.iter().enumerate() {
+            for (hour, &count) in row.iter().enumerate() {
+                let expected = if day == 2 && [8, 20, 21].contains(&hour) {
+                    11
+                } else {
+                    0
+                };
+                assert_eq!(count, expected, "day {} hour {}", day, hour);
+                assert_eq!(
+                    heatmap.minutes_in_hour(WEEKDAYS[day], hour as u32),
+                    u32::from(count)
+                );
+            }
+        }
+
+        assert!(heatmap.contains(Weekday::Tue, 8, 30));
+        assert!(!heatmap.contains(Weekday::Tue, 8, 31));
+        assert!(!heatmap.contains(Weekday::Tue, 9, 0));
+        assert!(!heatmap.contains(Weekday::Wed, 8, 0));
+        assert!(!heatmap.contains(Weekday::Tue, 24, 0));
+        assert!(!heatmap.contains(Weekday::Tue, 8, 60));
+        assert_eq!(heatmap.minutes_in_hour(Weekday::Tue, 24), 0);
+    }
+
+    #[test]
+    fn monthly_matches_contains() {
+        let crons = [
+            "*/20 */6 * * *",
+            "0 12 L * ?",
+            "0 12 LW * ?",
+            "0 12 15W * ?",
+            "0 12 L-3 * ?",
+            "0 12 ? * FRI#5",
+            "0 12 ? * 2L",
+            "0 12 13 * FRI",
+            "0 12 * 3 *",
+        ];
+        for &s in &crons {
+            let cron = cron(s);
+            for &(year, month) in &[(2021, 1), (2021, 2), (2024, 2), (2024, 3), (2021, 5)] {
+                let heatmap = cron.monthly_heatmap(year, month).unwrap();
+                let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+                let midnight = crate::date_time(first, NaiveTime::MIN);
+                let hours = heatmap.to_hours();
+                for day in 1..=31 {
+                    for hour in 0..24 {
+                        let mut count = 0;
+                        for minute in 0..60 {
+                            let offset = ((day - 1) * 24 + hour) * 60 + minute;
+                            let time = midnight + Duration::minutes(offset.into());
+                            let contains = time.month() == month && cron.contains(time);
+                            assert_eq!(
+                                heatmap.contains(day, hour, minute),
+                                contains,
+                                "{} at {}",
+                                s,
+                                time
+                            );
+                            count += u32::from(contains);
+                        }
+                        assert_eq!(heatmap.minutes_in_hour(day, hour), count);
+                        assert_eq!(u32::from(hours[day as usize - 1][hour as usize]), count);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn monthly_bounds() {
+        let cron = cron("0 0 * * *");
+        assert_eq!(cron.monthly_heatmap(2021, 0), None);
+        assert_eq!(cron.monthly_heatmap(2021, 13), None);
+
+        let heatmap = cron.monthly_heatmap(2024, 2).unwrap();
+        assert_eq!(heatmap.days_in_month(), 29);
+        assert!(heatmap.contains_day(29));
+        assert!(!heatmap.contains_day(30));
+        assert!(!heatmap.contains_day(0));
+        assert_eq!(heatmap.to_hours()[29], [0; 24]);
+    }
+}
@@ -0,0 +1,345 @@
+//! Schedules firing at a fixed period from an anchor time, for periods like 90 minutes that
+//! don't divide an hour or a day and so can't be written as a cron expression.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// A schedule matching an anchor time and every multiple of a period after it.
+///
+/// Unlike a cron value, an interval isn't truncated to the minute: it matches the exact times
+/// `anchor`, `anchor + period`, `anchor + 2 * period`, and so on. It never matches a time before
+/// its anchor.
+///
+/// # Example
+/// ```
+/// use saffron::Interval;
+/// use chrono::{prelude::*, Duration};
+///
+/// let anchor = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+/// let interval = Interval::new(anchor, Duration::minutes(90));
+///
+/// assert!(interval.contains(Utc.with_ymd_and_hms(2021, 1, 1, 1, 30, 0).unwrap()));
+/// assert!(!interval.contains(Utc.with_ymd_and_hms(2021, 1, 1, 2, 0, 0).unwrap()));
+/// assert_eq!(
+///     interval.next_after(Utc.with_ymd_and_hms(2021, 1, 1, 2, 0, 0).unwrap()),
+///     Some(Utc.with_ymd_and_hms(2021, 1, 1, 3, 0, 0).unwrap())
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Interval {
+    anchor: DateTime<Utc>,
+    period: Duration,
+}
+
+impl Interval {
+    /// Creates an interval matching the anchor and every multiple of the period after it.
+    ///
+    /// # Panics
+    /// Panics if the period isn't positive.
+    pub fn new(anchor: DateTime<Utc>, period: Duration) -> Self {
+        assert!(
+            period > Duration::zero(),
+            "interval period {} isn't positive",
+            period
+        );
+        Self { anchor, period }
+    }
+
+    /// Returns the first time the interval matches.
+    pub fn anchor(&self) -> DateTime<Utc> {
+        self.anchor
+    }
+
+    /// Returns the time between each match.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Returns whether the interval matches the given time.
+    pub fn contains(&self, dt: DateTime<Utc>) -> bool {
+        let offset = self.offset(dt);
+        offset >= 0 && offset % self.period_nanos() == 0
+    }
+
+    /// Returns the next time the interval will match including the given date.
+    pub fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.time(self.first_from(start))
+    }
+
+    /// Returns the next time the interval will match after the given date.
+    pub fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.time(self.first_after(start))
+    }
+
+    /// Returns the last time the interval matched including the given date, or none if the
+    /// date is before the anchor.
+    pub fn previous_from(&self, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.last_until(end).and_then(|index| self.time(index))
+    }
+
+    /// Creates an iterator of date times that match the interval. This is short for
+    /// `iter((Bound::Included(start), Bound::Unbounded))` or `iter(start..)`.
+    #[inline]
+    pub fn iter_from(self, start: DateTime<Utc>) -> IntervalTimesIter {
+        self.iter((Bound::Included(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match the interval after the given date. This is
+    /// short for `iter((Bound::Excluded(start), Bound::Unbounded))`.
+    #[inline]
+    pub fn iter_after(self, start: DateTime<Utc>) -> IntervalTimesIter {
+        self.iter((Bound::Excluded(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match the interval using the given start and end
+    /// range bounds. An unbounded end yields every time up to the max representable value for
+    /// DateTime<Utc>.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Interval;
+    /// use chrono::{prelude::*, Duration};
+    ///
+    /// let anchor = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+    /// let interval = Interval::new(anchor, Duration::minutes(90));
+    /// let end = Utc.with_ymd_and_hms(2021, 1, 2, 0, 0, 0).unwrap();
+    /// // 16 times a day, the same times every day
+    /// assert_eq!(interval.iter(anchor..end).count(), 16);
+    /// ```
+    pub fn iter<R: RangeBounds<DateTime<Utc>>>(self, bounds: R) -> IntervalTimesIter {
+        let front = match bounds.start_bound() {
+            Bound::Unbounded => 0,
+            Bound::Included(&start) => self.first_from(start),
+            Bound::Excluded(&start) => self.first_after(start),
+        };
+        let back = match bounds.end_bound() {
+            Bound::Unbounded => self.last_until(DateTime::<Utc>::MAX_UTC),
+            Bound::Included(&end) => self.last_until(end),
+            Bound::Excluded(&end) => self.last_until(end).map(|index| {
+                if self.time(index) == Some(end) {
+                    index - 1
+                } else {
+                    index
+                }
+            }),
+        };
+
+        IntervalTimesIter {
+            interval: self,
+            bounds: back.filter(|&back| front <= back).map(|back| (front, back)),
+        }
+    }
+
+    fn period_nanos(&self) -> i128 {
+        nanos(self.period)
+    }
+
+    /// Returns the nanoseconds from the anchor to the given time
+    fn offset(&self, dt: DateTime<Utc>) -> i128 {
+        timestamp_nanos(dt) - timestamp_nanos(self.anchor)
+    }
+
+    /// Returns the index of the first match at or after the given time
+    fn first_from(&self, start: DateTime<Utc>) -> i128 {
+        let offset = self.offset(start);
+        if offset <= 0 {
+            0
+        } else {
+            (offset + self.period_nanos() - 1) / self.period_nanos()
+        }
+    }
+
+    /// Returns the index of the first match after the given time
+    fn first_after(&self, start: DateTime<Utc>) -> i128 {
+        let offset = self.offset(start);
+        if offset < 0 {
+            0
+        } else {
+            offset / self.period_nanos() + 1
+        }
+    }
+
+    /// Returns the index of the last match at or before the given time, if any
+    fn last_until(&self, end: DateTime<Utc>) -> Option<i128> {
+        let offset = self.offset(end);
+        if offset < 0 {
+            None
+        } else {
+            Some(offset / self.period_nanos())
+        }
+    }
+
+    /// Returns the time of the match at the given index, or none if it can't be represented
+    fn time(&self, index: i128) -> Option<DateTime<Utc>> {
+        let nanos =
+            timestamp_nanos(self.anchor).checked_add(index.checked_mul(self.period_nanos())?)?;
+        let secs = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).ok()?;
+        let subsec = nanos.rem_euclid(NANOS_PER_SECOND) as u32;
+        Utc.timestamp_opt(secs, subsec).single()
+    }
+}
+
+fn nanos(duration: Duration) -> i128 {
+    let secs = duration.num_seconds();
+    let subsec = (duration - Duration::seconds(secs))
+        .num_nanoseconds()
+        .expect("Less than a second of nanoseconds fits in an i64");
+    i128::from(secs) * NANOS_PER_SECOND + i128::from(subsec)
+}
+
+fn timestamp_nanos(dt: DateTime<Utc>) -> i128 {
+    i128::from(dt.timestamp()) * NANOS_PER_SECOND + i128::from(dt.timestamp_subsec_nanos())
+}
+
+/// An iterator over the times matching an interval. Created with [`Interval::iter`],
+/// [`Interval::iter_from`], and [`Interval::iter_after`].
+///
+/// [`Interval::iter`]: struct.Interval.html#method.iter
+/// [`Interval::iter_from`]: struct.Interval.html#method.iter_from
+/// [`Interval::iter_after`]: struct.Interval.html#method.iter_after
+#[derive(Clone)]
+pub struct IntervalTimesIter {
+    interval: Interval,
+    /// The indices of the first and last matches left (inclusive)
+    bounds: Option<(i128, i128)>,
+}
+
+impl IntervalTimesIter {
+    /// Returns the underlying interval.
+    pub fn interval(&self) -> &Interval {
+        &self.interval
+    }
+}
+
+impl Iterator for IntervalTimesIter {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (front, back) = self.bounds?;
+        self.bounds = Some((front + 1, back)).filter(|&(front, back)| front <= back);
+        let time = self.interval.time(front);
+        if time.is_none() {
+            self.bounds = None;
+        }
+        time
+    }
+
+    /// The number of times left is always exact.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.bounds.map_or(0, |(front, back)| back - front + 1);
+        match usize::try_from(count) {
+            Ok(count) => (count, Some(count)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for IntervalTimesIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (front, back) = self.bounds?;
+        self.bounds = Some((front, back - 1)).filter(|&(front, back)| front <= back);
+        let time = self.interval.time(back);
+        if time.is_none() {
+            self.bounds = None;
+        }
+        time
+    }
+}
+
+impl FusedIterator for IntervalTimesIter {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn ninety_minutes() -> Interval {
+        Interval::new(
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+            Duration::minutes(90),
+        )
+    }
+
+    #[test]
+    fn contains() {
+        let interval = ninety_minutes();
+        assert!(interval.contains(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()));
+        assert!(interval.contains(Utc.with_ymd_and_hms(2021, 1, 1, 22, 30, 0).unwrap()));
+        assert!(interval.contains(Utc.with_ymd_and_hms(2021, 1, 2, 0, 0, 0).unwrap()));
+        assert!(!interval.contains(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 1).unwrap()));
+        assert!(!interval.contains(Utc.with_ymd_and_hms(2021, 1, 1, 1, 0, 0).unwrap()));
+        // times before the anchor never match
+        assert!(!interval.contains(Utc.with_ymd_and_hms(2020, 12, 31, 22, 30, 0).unwrap()));
+    }
+
+    #[test]
+    fn next_and_previous() {
+        let interval = ninety_minutes();
+        let anchor = interval.anchor();
+        assert_eq!(interval.next_from(anchor), Some(anchor));
+        assert_eq!(
+            interval.next_after(anchor),
+            Some(Utc.with_ymd_and_hms(2021, 1, 1, 1, 30, 0).unwrap())
+        );
+        assert_eq!(
+            interval.next_from(Utc.with_ymd_and_hms(2021, 1, 1, 1, 29, 59).unwrap()),
+            Some(Utc.with_ymd_and_hms(2021, 1, 1, 1, 30, 0).unwrap())
+        );
+        assert_eq!(
+            interval.next_from(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+            Some(anchor)
+        );
+        assert_eq!(
+            interval.previous_from(Utc.with_ymd_and_hms(2021, 1, 1, 2, 59, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2021, 1, 1, 1, 30, 0).unwrap())
+        );
+        assert_eq!(
+            interval.previous_from(Utc.with_ymd_and_hms(2020, 12, 31, 23, 59, 0).unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn iter() {
+        let interval = ninety_minutes();
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 1, 30, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 1, 1, 6, 0, 0).unwrap();
+        let expected = [
+            Utc.with_ymd_and_hms(2021, 1, 1, 1, 30, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 1, 1, 3, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2021, 1, 1, 4, 30, 0).unwrap(),
+        ];
+
+        let iter = interval.iter(start..end);
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+        let mut reversed = interval.iter(start..end).rev().collect::<Vec<_>>();
+        reversed.reverse();
+        assert_eq!(reversed, expected);
+        assert_eq!(interval.iter(start..=end).count(), 4);
+        assert_eq!(
+            interval.iter_after(start).take(2).collect::<Vec<_>>(),
+            expected[1..]
+        );
+        assert_eq!(interval.iter_from(start).next(), Some(start));
+        assert_eq!(interval.iter(end..start).next(), None);
+
+        let mut last = interval.iter(start..).rev();
+        assert!(last.next().is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn period_must_be_positive() {
+        Interval::new(
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+            Duration::zero(),
+        );
+    }
+}
@@ -0,0 +1,447 @@
+//! Support for matching and iterating cron values with `jiff`'s `Zoned`, in the local time of
+//! a time zone.
+//!
+//! Unlike the other date and time types, zoned date times are matched against their civil
+//! (wall clock) time rather than UTC, so a cron value like `0 9 * * *` matches 9 AM in the time
+//! zone all year round. Around daylight saving time transitions:
+//!  * A civil time skipped when clocks move forward, like 2:30 AM in most of the US on the day
+//!    DST starts, doesn't exist, so it never matches.
+//!  * A civil time repeated when clocks move back only matches the first time it occurs.
+
+use crate::{next_minute, Cron};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone as _, Timelike, Utc};
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+use core::iter::FusedIterator;
+use jiff::civil;
+use jiff::tz::{AmbiguousOffset, TimeZone};
+use jiff::{Timestamp, Zoned};
+
+/// Converts a civil time into the UTC date time cron values are matched against, dropping its
+/// seconds
+fn to_chrono(dt: civil::DateTime) -> Option<DateTime<Utc>> {
+    let naive = NaiveDate::from_ymd_opt(dt.year().into(), dt.month() as u32, dt.day() as u32)?
+        .and_hms_opt(dt.hour() as u32, dt.minute() as u32, 0)?;
+    Some(Utc.from_utc_datetime(&naive))
+}
+
+fn to_civil(dt: DateTime<Utc>) -> Option<civil::DateTime> {
+    civil::DateTime::new(
+        i16::try_from(dt.year()).ok()?,
+        dt.month() as i8,
+        dt.day() as i8,
+        dt.hour() as i8,
+        dt.minute() as i8,
+        0,
+        0,
+    )
+    .ok()
+}
+
+/// Returns the timestamp at the start of its minute
+fn minute_floor(ts: Timestamp) -> Option<Timestamp> {
+    Timestamp::from_second(ts.as_second().div_euclid(60) * 60).ok()
+}
+
+/// The seconds around a year searched for transitions, so transitions near midnight on new
+/// year's are found in time zones far from UTC
+const YEAR_MARGIN: i64 = 2 * 24 * 60 * 60;
+
+/// A time a cron value matches that a daylight saving time transition skips or repeats.
+/// Returned by [`Cron::dst_issues`].
+///
+/// [`Cron::dst_issues`]: struct.Cron.html#method.dst_issues
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DstIssue {
+    /// The civil time is skipped when clocks move forward, so the cron value doesn't fire that
+    /// day
+    Skipped(civil::DateTime),
+    /// The civil time happens twice when clocks move back, and the cron value only fires the
+    /// first time
+    Repeated(civil::DateTime),
+}
+
+impl DstIssue {
+    /// Returns the civil time the cron value matches.
+    pub fn time(&self) -> civil::DateTime {
+        match *self {
+            DstIssue::Skipped(time) | DstIssue::Repeated(time) => time,
+        }
+    }
+}
+
+impl Display for DstIssue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DstIssue::Skipped(time) => write!(f, "{} is skipped when clocks move forward", time),
+            DstIssue::Repeated(time) => {
+                write!(f, "{} happens twice when clocks move back", time)
+            }
+        }
+    }
+}
+
+impl Cron {
+    /// Returns the first civil time matching the cron value, starting from the given civil
+    /// time, that exists in the time zone at or after the given instant. Also returns the civil
+    /// time the search can continue from.
+    fn find_next_zoned(
+        &self,
+        mut civil: DateTime<Utc>,
+        min: Timestamp,
+        tz: &TimeZone,
+    ) -> Option<(Zoned, DateTime<Utc>)> {
+        loop {
+            let next = self.next_from(civil)?;
+            let ambiguous = tz.to_ambiguous_timestamp(to_civil(next)?);
+            // civil times in a gap don't exist, and repeated civil times only match the first
+            // time they occur
+            if !matches!(ambiguous.offset(), AmbiguousOffset::Gap { .. }) {
+                let ts = ambiguous.earlier().ok()?;
+                if ts >= min {
+                    return Some((ts.to_zoned(tz.clone()), next_minute(next)?));
+                }
+            }
+            civil = next_minute(next)?;
+        }
+    }
+
+    /// Returns whether this cron value matches the civil time of the given zoned date time.
+    /// A repeated civil time only matches the first time it occurs.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use jiff::{civil::date, tz::TimeZone};
+    ///
+    /// let tz = TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    /// let cron: Cron = "0 9 * * *".parse().expect("Couldn't parse expression!");
+    ///
+    /// // 9 AM in both standard and daylight saving time
+    /// assert!(cron.contains_zoned(&date(2021, 1, 4).at(9, 0, 0, 0).to_zoned(tz.clone()).unwrap()));
+    /// assert!(cron.contains_zoned(&date(2021, 7, 5).at(9, 0, 0, 0).to_zoned(tz).unwrap()));
+    /// ```
+    pub fn contains_zoned(&self, dt: &Zoned) -> bool {
+        let civil = match to_chrono(dt.datetime()) {
+            Some(civil) if self.contains(civil) => civil,
+            _ => return false,
+        };
+        let first = to_civil(civil)
+            .and_then(|civil| dt.time_zone().to_ambiguous_timestamp(civil).earlier().ok());
+        first.is_some() && first == minute_floor(dt.timestamp())
+    }
+
+    /// Returns the next time the cron will match including the given time, in the same time
+    /// zone as the given time.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use jiff::{civil::date, tz::TimeZone};
+    ///
+    /// let tz = TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    /// let cron: Cron = "30 2 * * *".parse().expect("Couldn't parse expression!");
+    ///
+    /// // 2:30 AM doesn't exist on the day daylight saving time starts
+    /// let start = date(2021, 3, 14).at(0, 0, 0, 0).to_zoned(tz.clone()).unwrap();
+    /// let next = cron.next_from_zoned(&start).unwrap();
+    /// assert_eq!(next, date(2021, 3, 15).at(2, 30, 0, 0).to_zoned(tz).unwrap());
+    /// ```
+    pub fn next_from_zoned(&self, start: &Zoned) -> Option<Zoned> {
+        let civil = to_chrono(start.datetime())?;
+        let min = minute_floor(start.timestamp())?;
+        self.find_next_zoned(civil, min, start.time_zone())
+            .map(|(next, _)| next)
+    }
+
+    /// Returns the next time the cron will match after the given time, in the same time zone
+    /// as the given time.
+    pub fn next_after_zoned(&self, start: &Zoned) -> Option<Zoned> {
+        let civil = next_minute(to_chrono(start.datetime())?)?;
+        let min = Timestamp::from_second(minute_floor(start.timestamp())?.as_second() + 60).ok()?;
+        self.find_next_zoned(civil, min, start.time_zone())
+            .map(|(next, _)| next)
+    }
+
+    /// Returns the times the cron value matches in the given year that are skipped or repeated
+    /// by daylight saving time transitions in the time zone, in order. A skipped time doesn't
+    /// fire that day, and a repeated time only fires the first time it happens, which schedulers
+    /// can warn about.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, DstIssue};
+    /// use jiff::{civil::date, tz::TimeZone};
+    ///
+    /// let tz = TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    /// let cron: Cron = "30 1,2 * * *".parse().expect("Couldn't parse expression!");
+    ///
+    /// assert_eq!(
+    ///     cron.dst_issues(&tz, 2021),
+    ///     [
+    ///         DstIssue::Skipped(date(2021, 3, 14).at(2, 30, 0, 0)),
+    ///         DstIssue::Repeated(date(2021, 11, 7).at(1, 30, 0, 0)),
+    ///     ]
+    /// );
+    /// ```
+    pub fn dst_issues(&self, tz: &TimeZone, year: i16) -> Vec<DstIssue> {
+        let mut issues = Vec::new();
+        let bound = |year| {
+            civil::date(year, 1, 1)
+                .to_zoned(TimeZone::UTC)
+                .map(|start| start.timestamp().as_second())
+        };
+        let (start, end) = match (bound(year), year.checked_add(1).map(bound)) {
+            (Ok(start), Some(Ok(end))) => (start - YEAR_MARGIN, end + YEAR_MARGIN),
+            _ => return issues,
+        };
+        let start = match Timestamp::from_second(start) {
+            Ok(start) => start,
+            Err(_) => return issues,
+        };
+
+        for transition in tz.following(start) {
+            let at = transition.timestamp();
+            if at.as_second() >= end {
+                break;
+            }
+            let before = match Timestamp::from_second(at.as_second() - 1) {
+                Ok(before) => tz.to_offset(before),
+                Err(_) => continue,
+            };
+            let after = transition.offset();
+            // the civil times between the transition in the smaller and larger offsets are
+            // skipped if the offset grows and repeated if it shrinks
+            let (from, to, issue): (_, _, fn(_) -> _) = if after > before {
+                (before, after, DstIssue::Skipped)
+            } else if after < before {
+                (after, before, DstIssue::Repeated)
+            } else {
+                continue;
+            };
+            let (from, to) = match (
+                to_chrono(from.to_datetime(at)),
+                to_chrono(to.to_datetime(at)),
+            ) {
+                (Some(from), Some(to)) => (from, to),
+                _ => continue,
+            };
+
+            let mut civil = from;
+            while let Some(next) = self.next_from(civil).filter(|&next| next < to) {
+                if next.year() == i32::from(year) {
+                    if let Some(time) = to_civil(next) {
+                        issues.push(issue(time));
+                    }
+                }
+                civil = match next_minute(next) {
+                    Some(civil) => civil,
+                    None => break,
+                };
+            }
+        }
+        issues
+    }
+
+    /// Creates an iterator of zoned date times that match with the cron value, starting from
+    /// the given time and in its time zone.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use jiff::{civil::date, tz::TimeZone};
+    ///
+    /// let tz = TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap();
+    /// let cron: Cron = "30 1 * * *".parse().expect("Couldn't parse expression!");
+    ///
+    /// // 1:30 AM happens twice on the day daylight saving time ends, but only matches once
+    /// let start = date(2021, 11, 7).at(0, 0, 0, 0).to_zoned(tz).unwrap();
+    /// let times: Vec<_> = cron
+    ///     .iter_from_zoned(&start)
+    ///     .take(2)
+    ///     .map(|z| (z.day(), z.hour(), z.minute(), z.offset().seconds() / 3600))
+    ///     .collect();
+    /// assert_eq!(times, [(7, 1, 30, -4), (8, 1, 30, -5)]);
+    /// ```
+    pub fn iter_from_zoned(self, start: &Zoned) -> ZonedTimesIter {
+        let next = to_chrono(start.datetime()).zip(minute_floor(start.timestamp()));
+        ZonedTimesIter {
+            cron: self,
+            next,
+            tz: start.time_zone().clone(),
+        }
+    }
+
+    /// Creates an iterator of zoned date times that match with the cron value after the given
+    /// time, in its time zone.
+    pub fn iter_after_zoned(self, start: &Zoned) -> ZonedTimesIter {
+        let next = to_chrono(start.datetime()).and_then(next_minute).zip(
+            minute_floor(start.timestamp())
+                .and_then(|ts| Timestamp::from_second(ts.as_second() + 60).ok()),
+        );
+        ZonedTimesIter {
+            cron: self,
+            next,
+            tz: start.time_zone().clone(),
+        }
+    }
+}
+
+/// An iterator over the times matching the contained cron value in the local time of a time
+/// zone. Created with [`Cron::iter_from_zoned`] and [`Cron::iter_after_zoned`].
+///
+/// [`Cron::iter_from_zoned`]: struct.Cron.html#method.iter_from_zoned
+/// [`Cron::iter_after_zoned`]: struct.Cron.html#method.iter_after_zoned
+pub struct ZonedTimesIter {
+    cron: Cron,
+    /// The civil time to continue searching from and the earliest instant the next time can be
+    next: Option<(DateTime<Utc>, Timestamp)>,
+    tz: TimeZone,
+}
+
+impl ZonedTimesIter {
+    /// Returns the underlying cron value.
+    pub fn cron(&self) -> &Cron {
+        &self.cron
+    }
+
+    /// Returns the time zone the times are in.
+    pub fn time_zone(&self) -> &TimeZone {
+        &self.tz
+    }
+}
+
+impl Iterator for ZonedTimesIter {
+    type Item = Zoned;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (civil, min) = self.next.take()?;
+        let (next, civil) = self.cron.find_next_zoned(civil, min, &self.tz)?;
+        self.next = Timestamp::from_second(next.timestamp().as_second() + 60)
+            .ok()
+            .map(|min| (civil, min));
+        Some(next)
+    }
+}
+
+impl FusedIterator for ZonedTimesIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jiff::civil::date;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn new_york() -> TimeZone {
+        TimeZone::posix("EST5EDT,M3.2.0,M11.1.0").unwrap()
+    }
+
+    fn zoned(s: &str) -> Zoned {
+        s.parse::<Timestamp>().unwrap().to_zoned(new_york())
+    }
+
+    #[test]
+    fn matches_civil_time() {
+        let cron: Cron = "0 9 * * MON-FRI".parse().unwrap();
+        let tz = new_york();
+        let at = |y, m, d, h, mi| date(y, m, d).at(h, mi, 0, 0).to_zoned(tz.clone()).unwrap();
+
+        assert!(cron.contains_zoned(&at(2021, 1, 4, 9, 0)));
+        assert!(cron.contains_zoned(&at(2021, 7, 5, 9, 0)));
+        assert!(!cron.contains_zoned(&at(2021, 7, 5, 13, 0)));
+        assert!(!cron.contains_zoned(&at(2021, 7, 3, 9, 0)));
+        assert!(cron.contains_zoned(&zoned("2021-07-05T09:00:59.5-04:00")));
+    }
+
+    #[test]
+    fn skips_gaps() {
+        let cron: Cron = "*/30 1-3 * * *".parse().unwrap();
+        let start = date(2021, 3, 14)
+            .at(0, 0, 0, 0)
+            .to_zoned(new_york())
+            .unwrap();
+        let times: Vec<_> = cron
+            .iter_from_zoned(&start)
+            .take(5)
+            .map(|z| (z.hour(), z.minute(), z.offset().seconds() / 3600))
+            .collect();
+        assert_eq!(
+            times,
+            [(1, 0, -5), (1, 30, -5), (3, 0, -4), (3, 30, -4), (1, 0, -4)]
+        );
+    }
+
+    #[test]
+    fn repeated_times_match_once() {
+        let cron: Cron = "*/30 1 * * *".parse().unwrap();
+        let first = zoned("2021-11-07T01:30:00-04:00");
+        let second = zoned("2021-11-07T01:30:00-05:00");
+        assert!(cron.contains_zoned(&first));
+        assert!(!cron.contains_zoned(&second));
+
+        assert_eq!(cron.next_from_zoned(&first), Some(first.clone()));
+        let next_day = zoned("2021-11-08T01:00:00-05:00");
+        assert_eq!(cron.next_after_zoned(&first), Some(next_day.clone()));
+        // starting during the repeated hour doesn't match times that already happened
+        assert_eq!(
+            cron.next_from_zoned(&zoned("2021-11-07T01:10:00-05:00")),
+            Some(next_day.clone())
+        );
+        assert_eq!(cron.clone().iter_after_zoned(&first).next(), Some(next_day));
+        assert_eq!(
+            cron.iter_from_zoned(&second).next().map(|z| z.day()),
+            Some(8)
+        );
+    }
+
+    #[test]
+    fn dst_issues() {
+        let tz = new_york();
+        let issues = |expr: &str| expr.parse::<Cron>().unwrap().dst_issues(&tz, 2021);
+
+        assert_eq!(
+            issues("30 2 * * *"),
+            [DstIssue::Skipped(date(2021, 3, 14).at(2, 30, 0, 0))]
+        );
+        assert_eq!(
+            issues("30 1 * * *"),
+            [DstIssue::Repeated(date(2021, 11, 7).at(1, 30, 0, 0))]
+        );
+        assert_eq!(issues("0 9 * * *"), []);
+        // DST starts and ends on Sundays
+        assert_eq!(issues("*/30 1,2 * * MON-SAT"), []);
+        assert_eq!(
+            issues("*/20 2 * * SUN")
+                .iter()
+                .map(|issue| issue.time().minute())
+                .collect::<Vec<_>>(),
+            [0, 20, 40]
+        );
+        assert_eq!(
+            "0 2 * * *"
+                .parse::<Cron>()
+                .unwrap()
+                .dst_issues(&TimeZone::UTC, 2021),
+            []
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        let cron: Cron = "0 0 1 JAN *".parse().unwrap();
+        let last = date(9999, 1, 1)
+            .at(0, 0, 0, 0)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        assert_eq!(cron.next_from_zoned(&last), Some(last.clone()));
+        assert_eq!(cron.next_after_zoned(&last), None);
+        assert_eq!(cron.iter_from_zoned(&last).count(), 1);
+    }
+}
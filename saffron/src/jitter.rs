@@ -0,0 +1,142 @@
+//! Offsetting the times a cron value matches by a pseudo-random amount, so many workers given
+//! the same expression don't all fire at the same instant.
+
+use crate::hashed::splitmix64;
+use crate::{Cron, CronTimesIter};
+
+use chrono::{DateTime, Duration, Utc};
+use core::iter::FusedIterator;
+
+impl CronTimesIter {
+    /// Offsets each time yielded by a pseudo-random amount from `-max` to `max`, to the
+    /// millisecond. The offset is picked from the seed and the time matched, so the same seed
+    /// always jitters a time by the same amount no matter where the iterator started, while
+    /// different seeds spread the times of many workers across the window.
+    ///
+    /// If the jitter is more than half the time between two matches, the times yielded may be
+    /// out of order.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::{prelude::*, Duration};
+    ///
+    /// let cron = "0 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+    /// let max = Duration::minutes(5);
+    ///
+    /// let times = cron.clone().iter(start..);
+    /// let jittered = cron.iter(start..).with_jitter(max, 42);
+    /// for (time, jittered) in times.zip(jittered).take(24) {
+    ///     assert!(jittered >= time - max && jittered <= time + max);
+    /// }
+    /// ```
+    pub fn with_jitter(self, max: Duration, seed: u64) -> JitteredTimesIter {
+        JitteredTimesIter {
+            inner: self,
+            max_millis: max.num_milliseconds().unsigned_abs(),
+            seed,
+        }
+    }
+}
+
+/// An iterator over the times a cron value matches, each offset by a pseudo-random amount.
+/// Created with [`CronTimesIter::with_jitter`].
+///
+/// [`CronTimesIter::with_jitter`]: struct.CronTimesIter.html#method.with_jitter
+pub struct JitteredTimesIter {
+    inner: CronTimesIter,
+    max_millis: u64,
+    seed: u64,
+}
+
+impl JitteredTimesIter {
+    /// Returns the underlying cron value.
+    pub fn cron(&self) -> &Cron {
+        self.inner.cron()
+    }
+
+    /// Offsets the time by its jitter. Times that can't be offset without overflowing are
+    /// returned as is.
+    fn jitter(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        if self.max_millis == 0 {
+            return time;
+        }
+
+        let span = self.max_millis * 2 + 1;
+        let hash = splitmix64(self.seed ^ splitmix64(time.timestamp() as u64));
+        let offset = (hash % span) as i64 - self.max_millis as i64;
+        time.checked_add_signed(Duration::milliseconds(offset))
+            .unwrap_or(time)
+    }
+}
+
+impl Iterator for JitteredTimesIter {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|time| self.jitter(time))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for JitteredTimesIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|time| self.jitter(time))
+    }
+}
+
+impl FusedIterator for JitteredTimesIter {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Cron;
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn jitter() {
+        let cron: Cron = "*/10 * * * *".parse().unwrap();
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 1, 2, 0, 0, 0).unwrap();
+        let max = Duration::seconds(90);
+
+        let times: Vec<_> = cron.clone().iter(start..end).collect();
+        let jittered: Vec<_> = cron.clone().iter(start..end).with_jitter(max, 1).collect();
+        assert_eq!(times.len(), jittered.len());
+        for (&time, &jittered) in times.iter().zip(&jittered) {
+            assert!(jittered >= time - max && jittered <= time + max);
+        }
+
+        // the times aren't all offset by the same amount
+        let offsets: Vec<_> = times.iter().zip(&jittered).map(|(t, j)| *j - *t).collect();
+        assert!(offsets.iter().any(|&offset| offset != offsets[0]));
+
+        // the same seed and time are always offset the same
+        let later = Utc.with_ymd_and_hms(2021, 1, 1, 12, 0, 0).unwrap();
+        let resumed: Vec<_> = cron.clone().iter(later..end).with_jitter(max, 1).collect();
+        assert_eq!(resumed, jittered[72..]);
+        let mut reversed: Vec<_> = cron
+            .clone()
+            .iter(start..end)
+            .with_jitter(max, 1)
+            .rev()
+            .collect();
+        reversed.reverse();
+        assert_eq!(reversed, jittered);
+
+        let other: Vec<_> = cron.clone().iter(start..end).with_jitter(max, 2).collect();
+        assert_ne!(other, jittered);
+
+        let none: Vec<_> = cron
+            .iter(start..end)
+            .with_jitter(Duration::zero(), 1)
+            .collect();
+        assert_eq!(none, times);
+    }
+}
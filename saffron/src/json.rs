@@ -0,0 +1,333 @@
+//! A JSON export of parsed cron expressions for services that can't use the parser directly.
+
+use crate::parse::{
+    CronExpr, DayOfMonthExpr, DayOfMonthSpecial, DayOfWeekExpr, DayOfWeekSpecial, Expr, Exprs,
+    Last, OrsExpr,
+};
+use core::fmt::{self, Display, Write};
+
+impl CronExpr {
+    /// Returns the parsed fields of the expression as a JSON object, so services written in other
+    /// languages can read the result of the parser without reimplementing the grammar.
+    ///
+    /// The object has the fields `minutes`, `hours`, `daysOfMonth`, `months`, `daysOfWeek`, and
+    /// `weeks`, along with `shortcut`, the name of the shortcut the expression was parsed from
+    /// (like `"daily"`) or `null`. Every field is an object with a `kind`:
+    ///  * `"all"` for a `*`
+    ///  * `"ignored"` for a `?` in one of the day fields
+    ///  * `"list"` for a list of `items`, where each item is one of `{"type": "value", "value"}`,
+    ///    `{"type": "range", "start", "end"}`, or `{"type": "step", "start", "end", "step"}`.
+    ///    The end of a step is always set, even if the expression leaves it out.
+    ///  * `"closestWeekdays"` for a list of days of the month followed by a `W`, like `1-7W`,
+    ///    with the same `items` as a list
+    ///  * `"specials"` for one or more special days in a list of `items`. The days of the month
+    ///    are `{"type": "last"}` for `L`, `{"type": "lastWeekday"}` for `LW`,
+    ///    `{"type": "lastOffset", "offset"}` for `L-3`, `{"type": "lastOffsetWeekday", "offset"}`
+    ///    for `L-3W`, `{"type": "lastWeekdayOffset", "offset"}` for `LW-1`,
+    ///    `{"type": "closestWeekday", "day"}` for `15W`, and `{"type": "businessDay", "day"}` for
+    ///    `3B`. The days of the week are `{"type": "last", "day"}` for `FRIL` and
+    ///    `{"type": "nth", "day", "nth"}` for `MON#2`.
+    ///
+    /// Expressions excluding values with a `!` also have an `exclusions` object, with the name of
+    /// each field that excludes values set to the `items` it excludes (i.e. `*!15` is
+    /// `"exclusions":{"minutes":[{"type":"value","value":15}]}`). Expressions without any
+    /// exclusions leave it out.
+    ///
+    /// Values are numbered like in an expression: months are 1-12 and days of the week are 1-7
+    /// starting from Sunday.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::CronExpr;
+    ///
+    /// let expr: CronExpr = "*/15 9-17 * * MON#2".parse().expect("Valid cron expression");
+    /// assert_eq!(
+    ///     expr.to_json_ast(),
+    ///     concat!(
+    ///         r#"{"minutes":{"kind":"list","items":[{"type":"step","start":0,"end":59,"step":15}]},"#,
+    ///         r#""hours":{"kind":"list","items":[{"type":"range","start":9,"end":17}]},"#,
+    ///         r#""daysOfMonth":{"kind":"all"},"#,
+    ///         r#""months":{"kind":"all"},"#,
+    ///         r#""daysOfWeek":{"kind":"specials","items":[{"type":"nth","day":2,"nth":2}]},"#,
+    ///         r#""weeks":{"kind":"all"},"#,
+    ///         r#""shortcut":null}"#,
+    ///     )
+    /// );
+    /// ```
+    pub fn to_json_ast(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json)
+            .expect("Writing to a string never fails");
+        json
+    }
+
+    fn write_json(&self, w: &mut String) -> fmt::Result {
+        w.write_str("{\"minutes\":")?;
+        write_expr(&self.minutes, w)?;
+        w.write_str(",\"hours\":")?;
+        write_expr(&self.hours, w)?;
+        w.write_str(",\"daysOfMonth\":")?;
+        write_doms(&self.doms, w)?;
+        w.write_str(",\"months\":")?;
+        write_expr(&self.months, w)?;
+        w.write_str(",\"daysOfWeek\":")?;
+        write_dows(&self.dows, w)?;
+        w.write_str(",\"weeks\":")?;
+        write_expr(&self.weeks, w)?;
+        w.write_str(",\"shortcut\":")?;
+        match self.shortcut {
+            Some(shortcut) => write!(w, "\"{}\"", shortcut.name())?,
+            None => w.write_str("null")?,
+        }
+        if !self.exclusions.is_empty() {
+            w.write_str(",\"exclusions\":{")?;
+            let exclusions = &self.exclusions;
+            let mut first = true;
+            write_excluded("minutes", &exclusions.minutes, &mut first, w)?;
+            write_excluded("hours", &exclusions.hours, &mut first, w)?;
+            write_excluded("daysOfMonth", &exclusions.doms, &mut first, w)?;
+            write_excluded("months", &exclusions.months, &mut first, w)?;
+            write_excluded("daysOfWeek", &exclusions.dows, &mut first, w)?;
+            write_excluded("weeks", &exclusions.weeks, &mut first, w)?;
+            w.write_str("}")?;
+        }
+        w.write_str("}")
+    }
+}
+
+/// Writes the items excluded from a field as a member of the exclusions object, if the field
+/// excludes any values
+fn write_excluded<E: Display>(
+    field: &str,
+    excluded: &Option<Exprs<E>>,
+    first: &mut bool,
+    w: &mut String,
+) -> fmt::Result {
+    let excluded = match excluded {
+        Some(excluded) => excluded,
+        None => return Ok(()),
+    };
+    if !*first {
+        w.write_str(",")?;
+    }
+    *first = false;
+    write!(w, "\"{}\":[", field)?;
+    for (i, expr) in excluded.iter().enumerate() {
+        if i != 0 {
+            w.write_str(",")?;
+        }
+        write_ors(expr, w)?;
+    }
+    w.write_str("]")
+}
+
+/// Writes a field object of the given kind with a list of items, writing each item with the
+/// given function
+fn write_items<'a, T: 'a, I, F>(kind: &str, items: I, w: &mut String, item: F) -> fmt::Result
+where
+    I: IntoIterator<Item = &'a T>,
+    F: Fn(&T, &mut String) -> fmt::Result,
+{
+    write!(w, "{{\"kind\":\"{}\",\"items\":[", kind)?;
+    for (i, value) in items.into_iter().enumerate() {
+        if i != 0 {
+            w.write_str(",")?;
+        }
+        item(value, w)?;
+    }
+    w.write_str("]}")
+}
+
+fn write_ors<E: Display>(expr: &OrsExpr<E>, w: &mut String) -> fmt::Result {
+    match expr {
+        OrsExpr::One(value) => write!(w, "{{\"type\":\"value\",\"value\":{}}}", value),
+        OrsExpr::Range(start, end) => write!(
+            w,
+            "{{\"type\":\"range\",\"start\":{},\"end\":{}}}",
+            start, end
+        ),
+        OrsExpr::Step { start, end, step } => write!(
+            w,
+            "{{\"type\":\"step\",\"start\":{},\"end\":{},\"step\":{}}}",
+            start, end, step
+        ),
+    }
+}
+
+fn write_list<E: Display>(kind: &str, exprs: &Exprs<E>, w: &mut String) -> fmt::Result {
+    write_items(kind, exprs, w, write_ors)
+}
+
+fn write_expr<E: Display>(expr: &Expr<E>, w: &mut String) -> fmt::Result {
+    match expr {
+        Expr::All => w.write_str("{\"kind\":\"all\"}"),
+        Expr::Many(exprs) => write_list("list", exprs, w),
+    }
+}
+
+fn write_dom_special(special: &DayOfMonthSpecial, w: &mut String) -> fmt::Result {
+    match special {
+        DayOfMonthSpecial::Last(Last::Day) => w.write_str("{\"type\":\"last\"}"),
+        DayOfMonthSpecial::Last(Last::Weekday) => w.write_str("{\"type\":\"lastWeekday\"}"),
+        DayOfMonthSpecial::Last(Last::Offset(offset)) => {
+            write!(w, "{{\"type\":\"lastOffset\",\"offset\":{}}}", offset)
+        }
+        DayOfMonthSpecial::Last(Last::OffsetWeekday(offset)) => write!(
+            w,
+            "{{\"type\":\"lastOffsetWeekday\",\"offset\":{}}}",
+            offset
+        ),
+        DayOfMonthSpecial::Last(Last::WeekdayOffset(offset)) => write!(
+            w,
+            "{{\"type\":\"lastWeekdayOffset\",\"offset\":{}}}",
+            offset
+        ),
+        DayOfMonthSpecial::ClosestWeekday(day) => {
+            write!(w, "{{\"type\":\"closestWeekday\",\"day\":{}}}", day)
+        }
+        DayOfMonthSpecial::BusinessDay(day) => {
+            write!(w, "{{\"type\":\"businessDay\",\"day\":{}}}", day)
+        }
+    }
+}
+
+fn write_doms(doms: &DayOfMonthExpr, w: &mut String) -> fmt::Result {
+    let special = match doms {
+        DayOfMonthExpr::All => return w.write_str("{\"kind\":\"all\"}"),
+        DayOfMonthExpr::Ignored => return w.write_str("{\"kind\":\"ignored\"}"),
+        DayOfMonthExpr::Many(exprs) => return write_list("list", exprs, w),
+        DayOfMonthExpr::ClosestWeekdays(exprs) => return write_list("closestWeekdays", exprs, w),
+        DayOfMonthExpr::Specials(specials) => {
+            return write_items("specials", specials, w, write_dom_special)
+        }
+        DayOfMonthExpr::Last(last) => DayOfMonthSpecial::Last(last.clone()),
+        &DayOfMonthExpr::ClosestWeekday(day) => DayOfMonthSpecial::ClosestWeekday(day),
+        &DayOfMonthExpr::BusinessDay(day) => DayOfMonthSpecial::BusinessDay(day),
+    };
+    write_items("specials", &[special], w, write_dom_special)
+}
+
+fn write_dow_special(special: &DayOfWeekSpecial, w: &mut String) -> fmt::Result {
+    match special {
+        DayOfWeekSpecial::Last(day) => write!(w, "{{\"type\":\"last\",\"day\":{}}}", day),
+        DayOfWeekSpecial::Nth(day, nth) => {
+            write!(w, "{{\"type\":\"nth\",\"day\":{},\"nth\":{}}}", day, nth)
+        }
+    }
+}
+
+fn write_dows(dows: &DayOfWeekExpr, w: &mut String) -> fmt::Result {
+    let special = match dows {
+        DayOfWeekExpr::All => return w.write_str("{\"kind\":\"all\"}"),
+        DayOfWeekExpr::Ignored => return w.write_str("{\"kind\":\"ignored\"}"),
+        DayOfWeekExpr::Many(exprs) => return write_list("list", exprs, w),
+        DayOfWeekExpr::Specials(specials) => {
+            return write_items("specials", specials, w, write_dow_special)
+        }
+        &DayOfWeekExpr::Last(day) => DayOfWeekSpecial::Last(day),
+        &DayOfWeekExpr::Nth(day, nth) => DayOfWeekSpecial::Nth(day, nth),
+    };
+    write_items("specials", &[special], w, write_dow_special)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{CronExpr, Dialect};
+
+    #[track_caller]
+    fn assert_field(cron: &str, field: &str, expected: &str) {
+        let expr = CronExpr::from_str_with(cron, Dialect::Extended).expect("Valid cron expression");
+        let json = expr.to_json_ast();
+        let key = format!("\"{}\":", field);
+        let start = json.find(&key).expect("Field exists") + key.len();
+        assert!(
+            json[start..].starts_with(expected),
+            "{} doesn't start with {}",
+            &json[start..],
+            expected
+        );
+    }
+
+    #[test]
+    fn lists() {
+        assert_field(
+            "0,5-10,20/5 * * * *",
+            "minutes",
+            r#"{"kind":"list","items":[{"type":"value","value":0},{"type":"range","start":5,"end":10},{"type":"step","start":20,"end":59,"step":5}]}"#,
+        );
+        assert_field(
+            "0 0 * JAN-MAR,DEC *",
+            "months",
+            r#"{"kind":"list","items":[{"type":"range","start":1,"end":3},{"type":"value","value":12}]}"#,
+        );
+        assert_field(
+            "0 0 * * MON-FRI",
+            "daysOfWeek",
+            r#"{"kind":"list","items":[{"type":"range","start":2,"end":6}]}"#,
+        );
+        assert_field(
+            "0 0 * * * W1,27",
+            "weeks",
+            r#"{"kind":"list","items":[{"type":"value","value":1},{"type":"value","value":27}]}"#,
+        );
+    }
+
+    #[test]
+    fn days_of_month() {
+        assert_field("0 0 ? * MON", "daysOfMonth", r#"{"kind":"ignored"}"#);
+        assert_field(
+            "0 0 L * *",
+            "daysOfMonth",
+            r#"{"kind":"specials","items":[{"type":"last"}]}"#,
+        );
+        assert_field(
+            "0 0 L-3W * *",
+            "daysOfMonth",
+            r#"{"kind":"specials","items":[{"type":"lastOffsetWeekday","offset":3}]}"#,
+        );
+        assert_field(
+            "0 0 1W,3B * *",
+            "daysOfMonth",
+            r#"{"kind":"specials","items":[{"type":"closestWeekday","day":1},{"type":"businessDay","day":3}]}"#,
+        );
+        assert_field(
+            "0 0 1-7W * *",
+            "daysOfMonth",
+            r#"{"kind":"closestWeekdays","items":[{"type":"range","start":1,"end":7}]}"#,
+        );
+    }
+
+    #[test]
+    fn days_of_week() {
+        assert_field(
+            "0 0 ? * FRIL",
+            "daysOfWeek",
+            r#"{"kind":"specials","items":[{"type":"last","day":6}]}"#,
+        );
+        assert_field(
+            "0 0 ? * MON#1,MON#3",
+            "daysOfWeek",
+            r#"{"kind":"specials","items":[{"type":"nth","day":2,"nth":1},{"type":"nth","day":2,"nth":3}]}"#,
+        );
+    }
+
+    #[test]
+    fn exclusions() {
+        assert_field(
+            "*!15,30-35 9-17 * * MON-FRI!WED",
+            "exclusions",
+            r#"{"minutes":[{"type":"value","value":15},{"type":"range","start":30,"end":35}],"daysOfWeek":[{"type":"value","value":4}]}}"#,
+        );
+        let expr: CronExpr = "0 0 * * *".parse().expect("Valid cron expression");
+        assert!(!expr.to_json_ast().contains("exclusions"));
+    }
+
+    #[test]
+    fn shortcut() {
+        let expr: CronExpr = "@weekly".parse().expect("Valid cron expression");
+        assert_eq!(
+            expr.to_json_ast(),
+            r#"{"minutes":{"kind":"list","items":[{"type":"value","value":0}]},"hours":{"kind":"list","items":[{"type":"value","value":0}]},"daysOfMonth":{"kind":"all"},"months":{"kind":"all"},"daysOfWeek":{"kind":"list","items":[{"type":"value","value":1}]},"weeks":{"kind":"all"},"shortcut":"weekly"}"#
+        );
+    }
+}
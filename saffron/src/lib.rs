@@ -1,119 +1,326 @@
 //! A "Quartz scheduler"-like cron parser powering Cron Triggers on Cloudflare Workers.
+//!
+//! Parsing and everything else needing an allocator is behind the `alloc` feature, which is on
+//! by default and enabled by every other feature that allocates. Without it the crate doesn't
+//! use the `alloc` crate, for schedulers on targets without a heap, and cron values built with
+//! [`Cron::from_parts`] or decoded with [`Cron::from_bytes`] match and iterate times without
+//! allocating.
+//!
+//! Times are matched and iterated with chrono's `DateTime<Utc>`, and chrono is always a
+//! dependency, built without its default features. The `jiff` feature adds methods taking and
+//! returning jiff's types, but they're converted to and from chrono's types internally, so it
+//! doesn't remove chrono from the dependency graph. There's no feature for the `time` crate,
+//! since one can't remove chrono either: every part of matching is written against chrono's
+//! types.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "alloc")]
+mod bits;
+mod bytes;
+#[cfg(feature = "alloc")]
+mod calendar;
+#[cfg(feature = "alloc")]
+pub mod crontab;
+#[cfg(feature = "alloc")]
 mod describe;
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "alloc")]
+mod hashed;
+mod heatmap;
+mod interval;
+#[cfg(feature = "jiff")]
+mod jiff_impl;
+#[cfg(feature = "alloc")]
+mod jitter;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "alloc")]
+pub mod lint;
+#[cfg(feature = "alloc")]
+mod locale;
+#[cfg(feature = "alloc")]
+mod many;
+#[cfg(feature = "alloc")]
+pub mod nl;
+#[cfg(feature = "alloc")]
 pub mod parse;
+mod parts;
+#[cfg(feature = "alloc")]
+pub mod rrule;
+#[cfg(feature = "alloc")]
+mod schedule;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "alloc")]
+mod set;
+mod shift;
+#[cfg(feature = "alloc")]
+mod source;
+#[cfg(feature = "alloc")]
+mod spanned;
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "alloc")]
+pub mod systemd;
+#[cfg(feature = "verify")]
+mod verify;
 
 use chrono::{prelude::*, Duration};
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 use core::cmp;
-use core::fmt::Debug;
-use core::iter::FusedIterator;
-use core::ops::{Bound, RangeBounds};
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::{self, FusedIterator};
+use core::ops::{Bound, Range, RangeBounds};
+#[cfg(feature = "alloc")]
 use core::str::FromStr;
 
-use self::parse::{CronExpr, ExprValue, OrsExpr};
-
+pub use bytes::CronDecodeError;
+#[cfg(feature = "alloc")]
+pub use calendar::{
+    AnnualCalendar, Calendar, CalendarCron, CalendarTimesIter, HolidayCalendar, WeeklyCalendar,
+};
+#[cfg(feature = "alloc")]
+pub use diff::{diff, CronDiff, DiffChange, DiffExample, DiffExamples, FieldChange, FieldDiff};
+#[cfg(feature = "alloc")]
+pub use hashed::HashedCron;
+pub use heatmap::{MonthlyHeatmap, WeeklyHeatmap};
+pub use interval::{Interval, IntervalTimesIter};
+#[cfg(feature = "jiff")]
+pub use jiff_impl::{DstIssue, ZonedTimesIter};
+#[cfg(feature = "alloc")]
+pub use jitter::JitteredTimesIter;
+#[cfg(feature = "alloc")]
+pub use many::{find_duplicates, parse_many};
+pub use parts::{DayOfMonthSet, DayOfWeekSet, HourSet, MinuteSet, MonthSet};
+#[cfg(feature = "alloc")]
+pub use schedule::{AnySchedule, Schedule, ScheduleTimesIter};
+#[cfg(feature = "alloc")]
+pub use set::{CronSet, CronSetTimesIter};
+pub use shift::ShiftError;
+#[cfg(feature = "alloc")]
+pub use source::WithSource;
+#[cfg(feature = "async")]
+pub use stream::{Clock, TickStream};
+#[cfg(feature = "verify")]
+pub use verify::Inconsistency;
+
+#[cfg(feature = "alloc")]
+use self::bits::BitField;
+#[cfg(feature = "alloc")]
+use self::parse::{
+    CronExpr, DayOfMonthExpr, DayOfMonthOffset, DayOfMonthSpecial, DayOfWeekExpr, DayOfWeekSpecial,
+    DescriptionSegment, Exclusions, Expr, Exprs, Language, Last, NthDay, OrsExpr,
+    ValueOutOfRangeError,
+};
+
+#[cfg(feature = "alloc")]
 pub(crate) mod internal {
     pub trait Sealed {}
 }
 
+/// The number of minutes in a day
+const MINUTES_IN_DAY: u32 = 24 * 60;
+
 /// Returns the number of days in the month, 28-31
-fn days_in_month(date: Date<Utc>) -> u32 {
+fn days_in_month(date: NaiveDate) -> u32 {
     match date.month() {
         1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
         4 | 6 | 9 | 11 => 30,
-        2 => {
-            let year = date.year();
-            if year % 4 != 0 {
-                28
-            } else if year % 100 != 0 {
-                29
-            } else if year % 400 != 0 {
-                28
-            } else {
-                29
-            }
-        }
+        2 if is_leap_year(date.year()) => 29,
+        2 => 28,
         _ => unreachable!(),
     }
 }
 
+/// Returns whether the year has a 29th of February
+#[inline]
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the weekday closest to the day (1-31) in the month of the date without leaving the
+/// month, or none if the month doesn't have that day.
+fn closest_weekday(date: NaiveDate, day: u32) -> Option<NaiveDate> {
+    let expected = date.with_day(day)?;
+    match expected.weekday() {
+        // a Saturday the 1st is followed by a Sunday, so the closest weekday in the month is
+        // the Monday after
+        Weekday::Sat if day == 1 => date.with_day(3),
+        Weekday::Sat => date.with_day(day - 1),
+        // likewise a Sunday at the end of the month is closest to the Friday before
+        Weekday::Sun if day == days_in_month(date) => date.with_day(day - 2),
+        Weekday::Sun => date.with_day(day + 1),
+        _ => Some(expected),
+    }
+}
+
+/// Returns the nth (1-23) business day, Monday to Friday, in the month of the date, or none if
+/// the month doesn't have that many business days.
+fn nth_business_day(date: NaiveDate, n: u32) -> Option<NaiveDate> {
+    let first = date.with_day(1)?;
+    let skipped = match first.weekday() {
+        Weekday::Sat => 2,
+        Weekday::Sun => 1,
+        _ => 0,
+    };
+    // count whole weeks of business days from the Monday of the first business day's week
+    let from_monday = (first.weekday().num_days_from_monday() + skipped) % 7;
+    let business_days = from_monday + n - 1;
+    date.with_day(1 + skipped + business_days / 5 * 7 + business_days % 5 - from_monday)
+}
+
+/// Returns the business day n (0-30) business days before the last business day, Monday to
+/// Friday, in the month of the date, or none if the month doesn't have that many business days.
+fn nth_last_business_day(date: NaiveDate, n: u32) -> Option<NaiveDate> {
+    let days_in_month = days_in_month(date);
+    let skipped = match date.with_day(days_in_month)?.weekday() {
+        Weekday::Sat => 1,
+        Weekday::Sun => 2,
+        _ => 0,
+    };
+    // count whole weeks of business days back to the Friday of the last business day's week
+    let to_friday = 4 - date
+        .with_day(days_in_month - skipped)?
+        .weekday()
+        .num_days_from_monday();
+    let business_days = to_friday + n;
+    let days_back = business_days / 5 * 7 + business_days % 5 - to_friday;
+    date.with_day(days_in_month.checked_sub(skipped + days_back)?)
+}
+
 trait TimePattern {
     /// A parsed time expression value
+    #[cfg(feature = "alloc")]
     type Expr;
 
     /// Compiles the expression into its most compressed form.
+    #[cfg(feature = "alloc")]
     fn compile(expr: Self::Expr) -> Self;
 
     /// Checks if the pattern contains the given DateTime.
     fn contains(&self, date: DateTime<Utc>) -> bool;
 }
 
-macro_rules! debug_assert_pattern {
-    ($pat:expr, $mask:expr) => {
-        debug_assert!(
-            ($pat & !($mask)) == 0,
-            "Value mapped out of range of valid bit patterns"
-        )
-    };
-}
-
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 enum DaysOfWeekKind {
     /// An expression over a set of values, ranges, or steps
     Pattern,
     /// A '*' expression
     Star,
+    /// A '?' expression, the field is unused and only the day of the month is matched
+    Ignored,
     /// A 'L' expression for the last day. One day is paired with this making it easier to access
     Last,
     /// A '#' expression for an nth day of the month. One day and one nth value is paired making it
     /// easier to access
     Nth,
+    /// A list of 'L' and '#' expressions, like `MON#1,MON#3`
+    Specials(DowSpecials),
 }
 
 /// A bit-mask of all the days of the week set in a cron expression.
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 struct DaysOfWeek(DaysOfWeekKind, u8);
 impl TimePattern for DaysOfWeek {
+    #[cfg(feature = "alloc")]
     type Expr = parse::DayOfWeekExpr;
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn compile(expr: Self::Expr) -> Self {
-        match expr {
-            parse::DayOfWeekExpr::All => Self(DaysOfWeekKind::Star, 0),
-            parse::DayOfWeekExpr::Last(day) => Self(DaysOfWeekKind::Last, u8::from(day)),
-            parse::DayOfWeekExpr::Nth(day, nth) => {
-                Self(DaysOfWeekKind::Nth, (u8::from(nth) << 3) | u8::from(day))
-            }
-            parse::DayOfWeekExpr::Many(exprs) => Self(
-                DaysOfWeekKind::Pattern,
-                exprs.into_iter().fold(0, Self::add_ors),
-            ),
-        }
+        Self::from_field(parse::DowField::from(expr).map(parse::collect))
     }
     #[inline]
     fn contains(&self, dt: DateTime<Utc>) -> bool {
-        self.contains_date(dt.date())
+        self.contains_date(dt.date_naive())
+    }
+}
+#[cfg(feature = "alloc")]
+impl parse::Collect<parse::DayOfWeek> for DaysOfWeek {
+    #[inline]
+    fn new(first: OrsExpr<parse::DayOfWeek>) -> Self {
+        Self(DaysOfWeekKind::Pattern, Self::add_ors(0, first))
+    }
+
+    #[inline]
+    fn push(&mut self, expr: OrsExpr<parse::DayOfWeek>) {
+        self.1 = Self::add_ors(self.1, expr);
     }
 }
 impl DaysOfWeek {
     const BITS: u8 = 8;
     const DAY_BITS: u8 = 0b0111_1111;
     const ONE_DAY_BITS: u8 = 0b0000_0111;
-    const UPPER_BIT_BOUND: u8 = Self::DAY_BITS.trailing_ones() as u8;
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn from_field(field: parse::DowField<Self>) -> Self {
+        match field {
+            parse::DowField::All => Self(DaysOfWeekKind::Star, 0),
+            parse::DowField::Ignored => Self(DaysOfWeekKind::Ignored, 0),
+            parse::DowField::Last(day) => Self(DaysOfWeekKind::Last, u8::from(day)),
+            parse::DowField::Nth(day, nth) => {
+                Self(DaysOfWeekKind::Nth, (u8::from(nth) << 3) | u8::from(day))
+            }
+            parse::DowField::Specials(specials) => {
+                let mut set = DowSpecials::default();
+                for special in specials {
+                    set.push(&Self::from_field(special.into()));
+                }
+                set.compile()
+            }
+            parse::DowField::Many(days) => days,
+        }
+    }
 
     #[inline]
     fn kind(&self) -> DaysOfWeekKind {
         self.0
     }
 
+    /// Returns whether the field places no restriction on the day, either because it's a '*'
+    /// or because it's ignored with '?'
     fn is_star(&self) -> bool {
-        matches!(self.kind(), DaysOfWeekKind::Star)
+        matches!(self.kind(), DaysOfWeekKind::Star | DaysOfWeekKind::Ignored)
+    }
+
+    /// Returns the most days the field can match in a month with the given number of days, or
+    /// `None` if it doesn't restrict the days
+    fn max_days_matched(&self, days_in_month: u8) -> Option<u32> {
+        match self.kind() {
+            DaysOfWeekKind::Star | DaysOfWeekKind::Ignored => None,
+            DaysOfWeekKind::Pattern => {
+                // every day of the week is in a month 4 times, and the days after the first 4
+                // weeks are each in it once more
+                let days = (self.1 & Self::DAY_BITS).count_ones();
+                Some(4 * days + days.min(u32::from(days_in_month) - 28))
+            }
+            DaysOfWeekKind::Specials(specials) => Some(specials.0.count_ones()),
+            DaysOfWeekKind::Last | DaysOfWeekKind::Nth => Some(1),
+        }
+    }
+
+    /// Removes the days excluded with a `!` from a '*' or pattern
+    #[cfg(feature = "alloc")]
+    fn exclude(&mut self, excluded: Self) {
+        match self.kind() {
+            DaysOfWeekKind::Star => *self = Self(DaysOfWeekKind::Pattern, Self::DAY_BITS),
+            DaysOfWeekKind::Pattern => {}
+            _ => return,
+        }
+        self.1 &= !excluded.1;
     }
 
     #[inline]
@@ -151,7 +358,7 @@ impl DaysOfWeek {
     }
 
     #[inline]
-    fn contains_date(&self, d: Date<Utc>) -> bool {
+    fn contains_date(&self, d: NaiveDate) -> bool {
         match *self {
             Self(DaysOfWeekKind::Pattern, pattern) => {
                 let mask = 1u8 << d.weekday().num_days_from_sunday();
@@ -168,183 +375,194 @@ impl DaysOfWeek {
                 let current_weekday = d.weekday().num_days_from_sunday() as u8;
                 weekday == current_weekday && d.day() + 7 > days_in_month(d)
             }
+            Self(DaysOfWeekKind::Specials(specials), _) => {
+                specials.entries().any(|day| day.contains_date(d))
+            }
             _ => true,
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn value_pattern<T>(value: T) -> u8
-    where
-        T: Into<u8>,
-    {
-        let pattern = 1 << value.into();
-
-        debug_assert_pattern!(pattern, Self::DAY_BITS);
-
-        pattern
-    }
-
-    #[inline]
-    fn add_ors(mut pattern: u8, expr: OrsExpr<parse::DayOfWeek>) -> u8 {
-        match expr.normalize() {
-            OrsExpr::One(one) => pattern |= Self::value_pattern(one),
-            OrsExpr::Range(start, end) => {
-                if start <= end {
-                    let start = u8::from(start);
-                    let end = u8::from(end);
-
-                    // example: MON-FRI (or 2-6) (true value: 1-5)
-                    // our bit map goes in reverse, so for weekdays
-                    // our final mask should look like this
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   0   1   1   1   1   1   0
-                    //
-                    // to start with, our mask looks like this
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   1   1   1   1   1   1   1
-                    let mut bits = Self::DAY_BITS;
-                    // remove the end bits by shifting the bits to the right
-                    // by the start value (1), then shift it back.
-                    //
-                    // shift right by 1
-                    //                                 truncated
-                    // ... ALL SAT FRI THU WED TUE MON SUN | (OOB)
-                    // ... 0   0   1   1   1   1   1   1   | 1
-                    //
-                    // shift left by 1
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   1   1   1   1   1   1   0
-                    bits = (bits >> start) << start;
-                    // remove the start bits in the same way, shift the bits
-                    // to the left by the number of bits in the integer (8) minus
-                    // the end value (5) minus 1 (8 - 5 - 1 = 2).
-                    // if we had a value that took up the whole bit map with a range
-                    // that reached the max value, this operation would result in -1.
-                    // In that case, we'd floor to 0 and not shift at all. but because
-                    // it's the max value, we don't actually need to shift to truncate at
-                    // all. so we can just skip this in that case.
-                    //
-                    // shift left by 2
-                    // truncated
-                    // (OOB)   | ALL SAT FRI THU WED TUE MON SUN
-                    // 0   1   | 1   1   1   1   1   0   0   0
-                    //
-                    // shift right by 2
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   0   1   1   1   1   1   0
-                    if end < Self::UPPER_BIT_BOUND {
-                        // this won't overflow, so we might as well use wrapping arithmetic anyway
-                        let end_shift = Self::BITS.wrapping_sub(end + 1);
-                        bits = (bits << end_shift) >> end_shift;
-                    }
+    fn add_ors(pattern: u8, expr: OrsExpr<parse::DayOfWeek>) -> u8 {
+        let mut field = BitField::<7>::from_bits(pattern.into());
+        field.set_expr(&expr);
+        field.bits() as u8
+    }
+
+    fn spec(&self) -> DayOfWeekSpec {
+        match self.kind() {
+            DaysOfWeekKind::Star => DayOfWeekSpec::All,
+            DaysOfWeekKind::Ignored => DayOfWeekSpec::Ignored,
+            DaysOfWeekKind::Pattern => {
+                DayOfWeekSpec::Days(Weekdays(FieldValues::new(self.1.into(), 0)))
+            }
+            DaysOfWeekKind::Last => DayOfWeekSpec::Last(Self::byte_to_weekday(self.1)),
+            DaysOfWeekKind::Nth => {
+                let (nth, weekday) = self.nth().unwrap();
+                DayOfWeekSpec::Nth(weekday, nth)
+            }
+            DaysOfWeekKind::Specials(specials) => {
+                DayOfWeekSpec::Specials(DayOfWeekSpecials(specials))
+            }
+        }
+    }
 
-                    debug_assert_pattern!(bits, Self::DAY_BITS);
+    /// Gets the next matching (current inclusive) day of the week in the month of the given date.
+    /// The returned matching day is a value 0-30.
+    fn find_next_day(&self, start: NaiveDate) -> Option<NaiveDate> {
+        let days_in_month = days_in_month(start);
+        match self.kind() {
+            DaysOfWeekKind::Last => {
+                let cron_weekday = self.last().unwrap().num_days_from_sunday();
+                let current_weekday = start.weekday().num_days_from_sunday();
+                // calculate an offset that can be added to the current day to get what would be a day
+                // of a week where that day is the expected weekday for the cron
+                let weekday_offset = if cron_weekday < current_weekday {
+                    // example:
+                    // current: Thursday, expected: Tuesday
+                    // 7 - (4 - 2) = 5
+                    // October 0th 2020 (Thursday) + 5 = October 5th 2020 (Tuesday)
+                    7 - (current_weekday - cron_weekday)
+                } else {
+                    // example:
+                    // expected: Thursday, current: Tuesday
+                    // (4 - 2) = 2
+                    // October 5th 2020 (Tuesday) + 2 = October 7th 2020 (Thursday)
+                    cron_weekday - current_weekday
+                };
+                // the remainder of 7 can be used with day0 to determine the first day0 of the
+                // current day of the week in the month. it doesn't matter if this calculation
+                // overflows the date out of the month (31st + 5 = 36th) since we're just looking
+                // for the first day.
+                let first_week_day = (start.day0() + weekday_offset) % 7;
+                // using that we can find the last day this weekday occurs in the month
+                let last_day = match (days_in_month, first_week_day) {
+                    // special 5 week weekday handling
+                    (29, day @ 0)
+                    | (30, day @ 0)
+                    | (30, day @ 1)
+                    | (31, day @ 0)
+                    | (31, day @ 1)
+                    | (31, day @ 2) => day + (7 * 4),
+                    (_, day) => day + (7 * 3),
+                };
 
-                    pattern |= bits;
+                start.with_day0(last_day)
+            }
+            DaysOfWeekKind::Nth => {
+                let (nth, day) = self.nth().unwrap();
+                let cron_weekday = day.num_days_from_sunday();
+                let current_weekday = start.weekday().num_days_from_sunday();
+                let weekday_offset = if cron_weekday < current_weekday {
+                    7 - (current_weekday - cron_weekday)
                 } else {
-                    // example : FRI-SUN (6-0)
-                    // to match up with quartz schedulers, we have to support wrapping
-                    // around, so for example with this expression, FRI,SAT,SUN,
-                    // which should look like this:
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   1   1   0   0   0   0   1
-                    //
-                    // we remove bits from the middle a bit differently
-                    // instead of like we do above. we have to make two
-                    // masks which are missing either the left or right side
-                    // and then OR those together.
-                    //
-                    // same as before, our first mask starts like this
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   1   1   1   1   1   1   1
-                    let mut top_bits = Self::DAY_BITS;
-                    // to remove the bottom bits, shift the top bits to the right
-                    // by the start value (6) minus one (5), then shift back.
-                    //
-                    // shift right by 5
-                    //                                 truncated
-                    // ... ALL SAT FRI THU WED TUE MON SUN | (OOB)
-                    // ... 0   0   0   0   0   0   1   1   | 1   ...
-                    //
-                    // shift left by 5
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   1   1   0   0   0   0   0
-                    let start = u8::from(start) - 1;
-                    top_bits = (top_bits >> start) << start;
-
-                    // make a separate mask
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   1   1   1   1   1   1   1
-                    let mut bottom_bits = Self::DAY_BITS;
-                    // to remove the top bits, shift the top bits to the left
-                    // by the number of bits in the integer (8) minus the end
-                    // value (0) plus one (8 - 0 + 1 = 7)
-                    //
-                    // shift left by 7
-                    // truncated
-                    // ... (OOB)  | Out of mask bounds  ...
-                    // ... 1   1  | 1   0   0   0   0   ...
-                    //
-                    //
-                    // shift right by 7
-                    //
-                    // ... ALL SAT FRI THU WED TUE MON SUN
-                    // ... 0   0   0   0   0   0   0   1
-                    let end = u8::from(end) + 1;
-                    let shift = Self::BITS.wrapping_sub(end);
-                    bottom_bits = (bottom_bits << shift) >> shift;
-
-                    let bits = top_bits | bottom_bits;
-
-                    debug_assert_pattern!(bits, Self::DAY_BITS);
-
-                    pattern |= bits;
-                }
+                    cron_weekday - current_weekday
+                };
+                let first_week_day = (start.day0() + weekday_offset) % 7;
+                let nth_day = first_week_day + (7 * (nth - 1) as u32);
+                start.with_day0(nth_day)
             }
-            OrsExpr::Step { start, end, step } => {
-                let start = u8::from(start);
-                let end = u8::from(end);
-                if start <= end {
-                    let range = (start..=end).step_by(u8::from(step) as usize);
-
-                    for shift in range {
-                        pattern |= Self::value_pattern(shift);
-                    }
+            DaysOfWeekKind::Pattern => {
+                let current_weekday = start.weekday().num_days_from_sunday();
+                let map = self.1 & DaysOfWeek::DAY_BITS;
+                let bottom_cleared = (map >> current_weekday) << current_weekday;
+                let trailing_zeros = bottom_cleared.trailing_zeros();
+                let next_day = if trailing_zeros < DaysOfWeek::BITS as u32 {
+                    // if there's another day in this week in the pattern, just add the number of
+                    // days required to reach it
+                    start.day0() + (trailing_zeros - current_weekday)
                 } else {
-                    let back = start..=parse::DayOfWeek::MAX;
-                    let front = parse::DayOfWeek::MIN..=end;
-                    let range = back.chain(front).step_by(u8::from(step) as usize);
+                    // otherwise, find the first matching day in the pattern and go to the next week
+                    let next_week = map.trailing_zeros();
+                    let remaining_days = (6 - current_weekday) + 1;
+                    start.day0() + remaining_days + next_week
+                };
+                start.with_day0(next_day)
+            }
+            DaysOfWeekKind::Specials(specials) => specials
+                .entries()
+                .filter_map(|day| day.find_next_day(start))
+                .min(),
+            _ => Some(start),
+        }
+        .filter(|&new_day| new_day >= start)
+    }
+}
+
+/// A list of 'L' and '#' expressions, like `MON#1,MON#3`, as a bit-mask of the values paired with
+/// each expression on its own. The values of an 'L' are the days of the week (0-6) and the values
+/// of a '#' always have an nth value in their upper bits, so the two never overlap.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+struct DowSpecials(u64);
+impl DowSpecials {
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn push(&mut self, day: &DaysOfWeek) {
+        self.0 |= 1 << day.1;
+    }
+
+    /// Simplifies the list into its one expression if it only has one
+    #[cfg(feature = "alloc")]
+    fn compile(self) -> DaysOfWeek {
+        match self.0.count_ones() {
+            1 => self.entries().next().expect("One expression is set"),
+            _ => DaysOfWeek(DaysOfWeekKind::Specials(self), 0),
+        }
+    }
 
-                    for shift in range {
-                        pattern |= Self::value_pattern(shift);
+    /// Removes and returns the first expression in the list
+    fn pop_first(&mut self) -> Option<DaysOfWeek> {
+        let value = FieldValues::new(self.0, 0).next()?;
+        self.0 &= !(1 << value);
+        if value < DaysOfWeek::BITS {
+            Some(DaysOfWeek(DaysOfWeekKind::Last, value))
+        } else {
+            Some(DaysOfWeek(DaysOfWeekKind::Nth, value))
+        }
+    }
+
+    /// Iterates over the expressions in the list
+    fn entries(mut self) -> impl Iterator<Item = DaysOfWeek> {
+        iter::from_fn(move || self.pop_first())
+    }
+}
+
+impl Display for DaysOfWeek {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.kind() {
+            DaysOfWeekKind::Star => f.write_str("*"),
+            DaysOfWeekKind::Ignored => f.write_str("?"),
+            DaysOfWeekKind::Pattern => fmt_bits(self.1.into(), 1, f),
+            DaysOfWeekKind::Last => write!(f, "{}L", self.1 + 1),
+            DaysOfWeekKind::Nth => {
+                let (nth, weekday) = self.nth().unwrap();
+                write!(f, "{}#{}", weekday.number_from_sunday(), nth)
+            }
+            DaysOfWeekKind::Specials(specials) => {
+                for (i, day) in specials.entries().enumerate() {
+                    if i != 0 {
+                        f.write_str(",")?;
                     }
+                    Display::fmt(&day, f)?;
                 }
+                Ok(())
             }
         }
-        pattern
     }
 }
 
 /// A bit-mask of all minutes in an hour set in a cron expression.
-#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 struct Minutes(u64);
 impl TimePattern for Minutes {
+    #[cfg(feature = "alloc")]
     type Expr = parse::Expr<parse::Minute>;
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn compile(expr: Self::Expr) -> Self {
-        match expr {
-            parse::Expr::All => Self(Self::ALL),
-            parse::Expr::Many(exprs) => exprs.into_iter().fold(Self(0), Self::add_ors),
-        }
+        Self::from_field(parse::Field::from(expr).map(parse::collect))
     }
 
     /// Returns whether this mask contains the minute value 0-59
@@ -354,94 +572,51 @@ impl TimePattern for Minutes {
         self.0 & mask != 0
     }
 }
+#[cfg(feature = "alloc")]
+impl parse::Collect<parse::Minute> for Minutes {
+    #[inline]
+    fn new(first: OrsExpr<parse::Minute>) -> Self {
+        Self(0).add_ors(first)
+    }
+
+    #[inline]
+    fn push(&mut self, expr: OrsExpr<parse::Minute>) {
+        *self = self.add_ors(expr);
+    }
+}
 impl Minutes {
     const BITS: u8 = 64;
     const ALL: u64 = 0x0FFFFFFFFFFFFFFF;
-    const UPPER_BIT_BOUND: u8 = Self::ALL.trailing_ones() as u8;
 
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn value_pattern<T>(value: T) -> u64
-    where
-        T: Into<u8>,
-    {
-        let pattern = 1 << value.into();
-
-        debug_assert_pattern!(pattern, Self::ALL);
-
-        pattern
+    fn from_field(field: parse::Field<Self>) -> Self {
+        match field {
+            parse::Field::All => Self(Self::ALL),
+            parse::Field::Many(pattern) => pattern,
+        }
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn add_ors(mut self, expr: OrsExpr<parse::Minute>) -> Self {
-        match expr.normalize() {
-            OrsExpr::One(one) => self.0 |= Self::value_pattern(one),
-            OrsExpr::Range(start, end) => {
-                if start <= end {
-                    let start = u8::from(start);
-                    let end = u8::from(end);
-
-                    // learn how this works in DayOfWeek's add_ors function
-                    let mut bits = Self::ALL;
-                    bits = (bits >> start) << start;
-                    if end < Self::UPPER_BIT_BOUND {
-                        let end_shift = Self::BITS.wrapping_sub(end + 1);
-                        bits = (bits << end_shift) >> end_shift;
-                    }
-                    debug_assert_pattern!(bits, Self::ALL);
-
-                    self.0 |= bits;
-                } else {
-                    let start = u8::from(start) - 1;
-                    let end = u8::from(end) + 1;
-
-                    let top_bits = (Self::ALL >> start) << start;
-
-                    let bottom_shift = Self::BITS.wrapping_sub(end);
-                    let bottom_bits = (Self::ALL << bottom_shift) >> bottom_shift;
-
-                    let bits = top_bits | bottom_bits;
-
-                    debug_assert_pattern!(bits, Self::ALL);
-
-                    self.0 |= bits;
-                }
-            }
-            OrsExpr::Step { start, end, step } => {
-                let start = u8::from(start);
-                let end = u8::from(end);
-                if start <= end {
-                    let range = (start..=end).step_by(u8::from(step) as usize);
-
-                    for shift in range {
-                        self.0 |= Self::value_pattern(shift);
-                    }
-                } else {
-                    let back = start..=parse::Minute::MAX;
-                    let front = parse::Minute::MIN..=end;
-                    let range = back.chain(front).step_by(u8::from(step) as usize);
-
-                    for shift in range {
-                        self.0 |= Self::value_pattern(shift);
-                    }
-                }
-            }
-        }
-        self
+    fn add_ors(self, expr: OrsExpr<parse::Minute>) -> Self {
+        let mut field = BitField::<60>::from_bits(self.0);
+        field.set_expr(&expr);
+        Self(field.bits())
     }
 }
 
 /// A bit-mask of all hours in a day set in a cron expression.
-#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 struct Hours(u32);
 impl TimePattern for Hours {
+    #[cfg(feature = "alloc")]
     type Expr = parse::Expr<parse::Hour>;
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn compile(expr: Self::Expr) -> Self {
-        match expr {
-            parse::Expr::All => Self(Self::ALL),
-            parse::Expr::Many(exprs) => exprs.into_iter().fold(Self(0), Self::add_ors),
-        }
+        Self::from_field(parse::Field::from(expr).map(parse::collect))
     }
 
     /// Returns whether this mask contains the hour value 0-23
@@ -450,162 +625,202 @@ impl TimePattern for Hours {
         self.contains_hour(dt.time())
     }
 }
+#[cfg(feature = "alloc")]
+impl parse::Collect<parse::Hour> for Hours {
+    #[inline]
+    fn new(first: OrsExpr<parse::Hour>) -> Self {
+        Self(0).add_ors(first)
+    }
+
+    #[inline]
+    fn push(&mut self, expr: OrsExpr<parse::Hour>) {
+        *self = self.add_ors(expr);
+    }
+}
 impl Hours {
     const BITS: u8 = 32;
     const ALL: u32 = 0x00FFFFFF;
-    const UPPER_BIT_BOUND: u8 = Self::ALL.trailing_ones() as u8;
 
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn contains_hour(&self, time: NaiveTime) -> bool {
-        let mask = 1u32 << time.hour();
-        self.0 & mask != 0
+    fn from_field(field: parse::Field<Self>) -> Self {
+        match field {
+            parse::Field::All => Self(Self::ALL),
+            parse::Field::Many(pattern) => pattern,
+        }
     }
 
     #[inline]
-    fn value_pattern<T>(value: T) -> u32
-    where
-        T: Into<u8>,
-    {
-        let pattern = 1 << value.into();
-
-        debug_assert_pattern!(pattern, Self::ALL);
-
-        pattern
+    fn contains_hour(&self, time: NaiveTime) -> bool {
+        let mask = 1u32 << time.hour();
+        self.0 & mask != 0
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn add_ors(mut self, expr: OrsExpr<parse::Hour>) -> Self {
-        match expr.normalize() {
-            OrsExpr::One(one) => self.0 |= Self::value_pattern(one),
-            OrsExpr::Range(start, end) => {
-                if start <= end {
-                    let start = u8::from(start);
-                    let end = u8::from(end);
-
-                    // learn how this works in DayOfWeek's add_ors function
-                    let mut bits = Self::ALL;
-                    bits = (bits >> start) << start;
-                    if end < Self::UPPER_BIT_BOUND {
-                        let end_shift = Self::BITS.wrapping_sub(end + 1);
-                        bits = (bits << end_shift) >> end_shift;
-                    }
-                    debug_assert_pattern!(bits, Self::ALL);
-
-                    self.0 |= bits;
-                } else {
-                    let start = u8::from(start) - 1;
-                    let end = u8::from(end) + 1;
-
-                    let top_bits = (Self::ALL >> start) << start;
-
-                    let bottom_shift = Self::BITS.wrapping_sub(end);
-                    let bottom_bits = (Self::ALL << bottom_shift) >> bottom_shift;
-
-                    let bits = top_bits | bottom_bits;
-
-                    debug_assert_pattern!(bits, Self::ALL);
-
-                    self.0 |= bits;
-                }
-            }
-            OrsExpr::Step { start, end, step } => {
-                let start = u8::from(start);
-                let end = u8::from(end);
-                if start <= end {
-                    let range = (start..=end).step_by(u8::from(step) as usize);
-
-                    for shift in range {
-                        self.0 |= Self::value_pattern(shift);
-                    }
-                } else {
-                    let back = start..=parse::Hour::MAX;
-                    let front = parse::Hour::MIN..=end;
-                    let range = back.chain(front).step_by(u8::from(step) as usize);
-
-                    for shift in range {
-                        self.0 |= Self::value_pattern(shift);
-                    }
-                }
-            }
-        }
-        self
+    fn add_ors(self, expr: OrsExpr<parse::Hour>) -> Self {
+        let mut field = BitField::<24>::from_bits(self.0.into());
+        field.set_expr(&expr);
+        Self(field.bits() as u32)
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 enum DaysOfMonthKind {
     Pattern,
     Star,
+    Ignored,
     Last,
     Weekday,
+    Weekdays,
     LastWeekday,
+    BusinessDay,
+    LastWeekdayOffset,
+    Specials(DomSpecials),
 }
 
 /// A bit-mask of all the days of the month set in a cron expression.
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 struct DaysOfMonth(DaysOfMonthKind, u32);
 impl TimePattern for DaysOfMonth {
+    #[cfg(feature = "alloc")]
     type Expr = parse::DayOfMonthExpr;
 
+    #[cfg(feature = "alloc")]
     fn compile(expr: Self::Expr) -> Self {
-        use parse::{DayOfMonthExpr, Last};
-        match expr {
-            DayOfMonthExpr::All => Self(DaysOfMonthKind::Star, 0),
-            DayOfMonthExpr::Last(Last::Day) => Self(DaysOfMonthKind::Last, 0),
-            DayOfMonthExpr::Last(Last::Weekday) => Self(DaysOfMonthKind::LastWeekday, 0),
-            DayOfMonthExpr::Last(Last::Offset(offset)) => {
-                Self(DaysOfMonthKind::Last, u8::from(offset) as u32)
-            }
-            DayOfMonthExpr::Last(Last::OffsetWeekday(offset)) => {
-                Self(DaysOfMonthKind::LastWeekday, u8::from(offset) as u32)
-            }
-            DayOfMonthExpr::ClosestWeekday(day) => {
-                Self(DaysOfMonthKind::Weekday, (u8::from(day) + 1) as u32)
-            }
-            DayOfMonthExpr::Many(exprs) => Self(
-                DaysOfMonthKind::Pattern,
-                exprs.into_iter().fold(0, Self::add_ors),
-            ),
-        }
+        Self::from_field(parse::DomField::from(expr).map(parse::collect))
     }
 
     #[inline]
     fn contains(&self, dt: DateTime<Utc>) -> bool {
-        self.contains_date(dt.date())
+        self.contains_date(dt.date_naive())
+    }
+}
+#[cfg(feature = "alloc")]
+impl parse::Collect<parse::DayOfMonth> for DaysOfMonth {
+    #[inline]
+    fn new(first: OrsExpr<parse::DayOfMonth>) -> Self {
+        Self(DaysOfMonthKind::Pattern, Self::add_ors(0, first))
+    }
+
+    #[inline]
+    fn push(&mut self, expr: OrsExpr<parse::DayOfMonth>) {
+        self.1 = Self::add_ors(self.1, expr);
     }
 }
 impl DaysOfMonth {
     const BITS: u8 = 32;
     const DAY_BITS: u32 = 0x0_7F_FF_FF_FF;
     const ONE_DAY_BITS: u32 = 0b0001_1111;
-    const UPPER_BIT_BOUND: u8 = Self::DAY_BITS.trailing_ones() as u8;
+    /// Set on a pattern matching the 29th to also match the 28th of February in common years.
+    /// See [`Feb29Policy::LastDayOfFeb`].
+    const FEB_29_LAST: u32 = 1 << 31;
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn from_field(field: parse::DomField<Self>) -> Self {
+        use parse::{DomField, Last};
+        match field {
+            DomField::All => Self(DaysOfMonthKind::Star, 0),
+            DomField::Ignored => Self(DaysOfMonthKind::Ignored, 0),
+            DomField::Last(Last::Day) => Self(DaysOfMonthKind::Last, 0),
+            DomField::Last(Last::Weekday) => Self(DaysOfMonthKind::LastWeekday, 0),
+            DomField::Last(Last::Offset(offset)) => {
+                Self(DaysOfMonthKind::Last, u8::from(offset) as u32)
+            }
+            DomField::Last(Last::OffsetWeekday(offset)) => {
+                Self(DaysOfMonthKind::LastWeekday, u8::from(offset) as u32)
+            }
+            DomField::ClosestWeekday(day) => {
+                Self(DaysOfMonthKind::Weekday, (u8::from(day) + 1) as u32)
+            }
+            DomField::ClosestWeekdays(days) => DomSpecials::closest_weekdays(days.1).compile(),
+            DomField::BusinessDay(day) => Self(DaysOfMonthKind::BusinessDay, u8::from(day) as u32),
+            DomField::Last(Last::WeekdayOffset(offset)) => {
+                Self(DaysOfMonthKind::LastWeekdayOffset, u8::from(offset) as u32)
+            }
+            DomField::Specials(specials) => {
+                let mut set = DomSpecials::default();
+                for special in specials {
+                    set.push(&Self::from_field(special.into()));
+                }
+                set.compile()
+            }
+            DomField::Many(days) => days,
+        }
+    }
 
     #[inline]
     fn kind(&self) -> DaysOfMonthKind {
         self.0
     }
 
-    #[inline]
-    fn is_last(&self) -> bool {
+    /// Returns whether the field places no restriction on the day, either because it's a '*'
+    /// or because it's ignored with '?'
+    fn is_star(&self) -> bool {
         matches!(
             self.kind(),
-            DaysOfMonthKind::Last | DaysOfMonthKind::LastWeekday
+            DaysOfMonthKind::Star | DaysOfMonthKind::Ignored
         )
     }
 
-    fn is_star(&self) -> bool {
-        matches!(self.kind(), DaysOfMonthKind::Star)
+    /// Returns the most days the field can match in a month with the given number of days, or
+    /// `None` if it doesn't restrict the days
+    fn max_days_matched(&self, days_in_month: u8) -> Option<u32> {
+        match self.kind() {
+            DaysOfMonthKind::Star | DaysOfMonthKind::Ignored => None,
+            DaysOfMonthKind::Pattern | DaysOfMonthKind::Weekdays => {
+                Some((self.1 & (Self::DAY_BITS >> (31 - days_in_month))).count_ones())
+            }
+            DaysOfMonthKind::Specials(specials) => Some(
+                specials
+                    .entries()
+                    .filter_map(|day| day.max_days_matched(days_in_month))
+                    .sum(),
+            ),
+            _ => Some(1),
+        }
     }
 
-    /// Returns the one day set in this expression. Used to get last day offsets and the day
-    /// in a closest weekday expression
-    #[inline]
-    fn one_value(&self) -> u8 {
-        (self.1 & Self::ONE_DAY_BITS) as u8
+    /// Removes the days excluded with a `!` from a '*' or pattern
+    #[cfg(feature = "alloc")]
+    fn exclude(&mut self, excluded: Self) {
+        match self.kind() {
+            DaysOfMonthKind::Star => *self = Self(DaysOfMonthKind::Pattern, Self::DAY_BITS),
+            DaysOfMonthKind::Pattern => {}
+            _ => return,
+        }
+        self.1 &= !excluded.1;
     }
 
+    /// Makes a pattern matching the 29th also match the 28th of February in common years
+    #[cfg(feature = "alloc")]
+    fn set_feb_29_last(&mut self) {
+        if self.kind() == DaysOfMonthKind::Pattern && self.1 & (1 << 28) != 0 {
+            self.1 |= Self::FEB_29_LAST;
+        }
+    }
+
+    /// Returns the days set in a pattern that match in the month of the given date
     #[inline]
-    fn first_set0(&self) -> Option<u8> {
+    fn pattern_in_month(&self, date: NaiveDate) -> u32 {
+        let days = self.1 & Self::DAY_BITS;
+        if self.1 & Self::FEB_29_LAST != 0 && date.month() == 2 && days_in_month(date) == 28 {
+            days | (1 << 27)
+        } else {
+            days
+        }
+    }
+
+    /// Returns the one day set in this expression. Used to get last day offsets and the day
+    /// in a closest weekday expression
+    #[inline]
+    fn one_value(&self) -> u8 {
+        (self.1 & Self::ONE_DAY_BITS) as u8
+    }
+
+    #[inline]
+    fn first_set0(&self) -> Option<u8> {
         let trailing = self.1.trailing_zeros() as u8;
         if trailing < Self::BITS {
             Some(trailing)
@@ -620,7 +835,7 @@ impl DaysOfMonth {
     }
 
     #[inline]
-    fn contains_date(&self, date: Date<Utc>) -> bool {
+    fn contains_date(&self, date: NaiveDate) -> bool {
         let is_weekend = |weekday| matches!(weekday, Weekday::Sat | Weekday::Sun);
         let is_weekday = |weekday| !is_weekend(weekday);
 
@@ -628,9 +843,9 @@ impl DaysOfMonth {
         let day = date.day();
 
         match self {
-            Self(DaysOfMonthKind::Pattern, pattern) => {
+            Self(DaysOfMonthKind::Pattern, _) => {
                 let mask = 1u32 << (day - 1);
-                pattern & mask != 0
+                self.pattern_in_month(date) & mask != 0
             }
             Self(DaysOfMonthKind::Last, 0) => {
                 // 'L'
@@ -653,203 +868,561 @@ impl DaysOfMonth {
             &Self(DaysOfMonthKind::LastWeekday, offset) => {
                 // 'L' with an offset with the nearest weekday.
                 // Example: 'L-3W'
-                let weekday = date.weekday();
-                let day_offsetted = day + offset;
-                (is_weekday(weekday) && day_offsetted == days_in_month)
-                    // don't check for weekend month ends since we're always offset by one
-                    // at least, so our "end" can't be on a weekend ending month
-                    // but do check if the month starts with a weekend and this is that weekend's
-                    // Saturday or Sunday
-                    || (weekday == Weekday::Mon && day_offsetted - days_in_month < 3)
-                    || (weekday == Weekday::Fri && day_offsetted + 1 == days_in_month)
+                // No day matches if the offset goes back past the start of the month.
+                days_in_month
+                    .checked_sub(offset)
+                    .and_then(|expected_day| closest_weekday(date, expected_day))
+                    == Some(date)
             }
             &Self(DaysOfMonthKind::Weekday, expected_day) => {
-                let weekday = date.weekday();
-                (is_weekday(weekday) && day == expected_day)
-                    || (weekday == Weekday::Mon && day - 1 == expected_day)
-                    // check for 1W on months where the 1st is Saturday and the 2nd is Sunday
-                    || (weekday == Weekday::Mon && day == 3 && expected_day == 1)
-                    || (weekday == Weekday::Fri && day + 1 == expected_day)
-                    // check for 31W, 30W, 29W, 28W where they're the last day of the month and are on Sunday
-                    || (weekday == Weekday::Fri && day + 2 == expected_day && expected_day == days_in_month)
+                closest_weekday(date, expected_day) == Some(date)
+            }
+            &Self(DaysOfMonthKind::Weekdays, pattern) => FieldValues::new(pattern.into(), 1)
+                .any(|expected_day| closest_weekday(date, expected_day as u32) == Some(date)),
+            &Self(DaysOfMonthKind::BusinessDay, n) => nth_business_day(date, n) == Some(date),
+            &Self(DaysOfMonthKind::LastWeekdayOffset, n) => {
+                nth_last_business_day(date, n) == Some(date)
+            }
+            Self(DaysOfMonthKind::Specials(specials), _) => {
+                specials.entries().any(|day| day.contains_date(date))
             }
             _ => true,
         }
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn value_pattern<T>(value: T) -> u32
-    where
-        T: Into<u8>,
-    {
-        let pattern = 1 << value.into();
-
-        debug_assert_pattern!(pattern, Self::DAY_BITS);
+    fn add_ors(pattern: u32, expr: OrsExpr<parse::DayOfMonth>) -> u32 {
+        let mut field = BitField::<31>::from_bits(pattern.into());
+        field.set_expr(&expr);
+        field.bits() as u32
+    }
 
-        pattern
+    fn spec(&self) -> DayOfMonthSpec {
+        match self.kind() {
+            DaysOfMonthKind::Star => DayOfMonthSpec::All,
+            DaysOfMonthKind::Ignored => DayOfMonthSpec::Ignored,
+            DaysOfMonthKind::Pattern => {
+                DayOfMonthSpec::Days(FieldValues::new((self.1 & Self::DAY_BITS).into(), 1))
+            }
+            DaysOfMonthKind::Last => DayOfMonthSpec::Last {
+                offset: self.one_value(),
+            },
+            DaysOfMonthKind::LastWeekday => DayOfMonthSpec::LastWeekday {
+                offset: self.one_value(),
+            },
+            DaysOfMonthKind::Weekday => DayOfMonthSpec::ClosestWeekday(self.one_value()),
+            DaysOfMonthKind::Weekdays => {
+                DayOfMonthSpec::ClosestWeekdays(FieldValues::new(self.1.into(), 1))
+            }
+            DaysOfMonthKind::BusinessDay => DayOfMonthSpec::BusinessDay(self.one_value()),
+            DaysOfMonthKind::LastWeekdayOffset => DayOfMonthSpec::LastWeekdayOffset {
+                weekdays: self.one_value(),
+            },
+            DaysOfMonthKind::Specials(specials) => {
+                DayOfMonthSpec::Specials(DayOfMonthSpecials(specials))
+            }
+        }
     }
 
-    #[inline]
-    fn add_ors(mut pattern: u32, expr: OrsExpr<parse::DayOfMonth>) -> u32 {
-        match expr.normalize() {
-            OrsExpr::One(day) => pattern |= Self::value_pattern(day),
-            OrsExpr::Range(start, end) => {
-                if start <= end {
-                    let start = u8::from(start);
-                    let end = u8::from(end);
+    /// Returns the fewest days a month can have for this field to match a day in it
+    fn min_days_in_month(&self) -> u8 {
+        match self.kind() {
+            DaysOfMonthKind::Star | DaysOfMonthKind::Ignored => 1,
+            DaysOfMonthKind::Last | DaysOfMonthKind::LastWeekday => self.one_value() + 1,
+            // the closest weekday is only found in months with the day
+            DaysOfMonthKind::Weekday => self.one_value(),
+            // a month has at most the days after its first 4 weeks as extra business days
+            // beyond 20, so the nth business day needs a month of n + 8 days
+            DaysOfMonthKind::BusinessDay => self.one_value() + 8,
+            // likewise, the last business day and n before it need a month of n + 9 days
+            DaysOfMonthKind::LastWeekdayOffset => self.one_value() + 9,
+            DaysOfMonthKind::Specials(specials) => specials
+                .entries()
+                .map(|day| day.min_days_in_month())
+                .min()
+                .expect("A list has at least two expressions"),
+            _ => self.first_set().expect("At least one day should be set"),
+        }
+    }
 
-                    // learn how this works in DayOfWeek's add_ors function
-                    let mut bits = Self::DAY_BITS;
-                    bits = (bits >> start) << start;
-                    if end < Self::UPPER_BIT_BOUND {
-                        let end_shift = Self::BITS.wrapping_sub(end + 1);
-                        bits = (bits << end_shift) >> end_shift;
+    /// Gets the next matching (current inclusive) day of the month in the month of the given date.
+    fn find_next_day(&self, start: NaiveDate) -> Option<NaiveDate> {
+        let days_in_month = days_in_month(start);
+        match self.kind() {
+            DaysOfMonthKind::Last => match self.one_value() {
+                // 'L'
+                0 => start.with_day(days_in_month),
+                // 'L-3'
+                offset => start.with_day(days_in_month.checked_sub(offset as u32)?),
+            },
+            DaysOfMonthKind::LastWeekday => match self.one_value() {
+                // 'LW'
+                0 => {
+                    let next_date = start.with_day(days_in_month)?;
+                    match next_date.weekday() {
+                        Weekday::Sat => start.with_day(days_in_month - 1),
+                        Weekday::Sun => start.with_day(days_in_month - 2),
+                        _ => Some(next_date),
                     }
-                    debug_assert_pattern!(bits, Self::DAY_BITS);
-
-                    pattern |= bits;
+                }
+                // 'L-3W'
+                offset => closest_weekday(start, days_in_month.checked_sub(offset as u32)?),
+            },
+            DaysOfMonthKind::Weekday => closest_weekday(start, self.one_value() as u32),
+            DaysOfMonthKind::Weekdays => FieldValues::new(self.1.into(), 1)
+                .filter_map(|expected_day| closest_weekday(start, expected_day as u32))
+                .filter(|&date| date >= start)
+                .min(),
+            DaysOfMonthKind::BusinessDay => nth_business_day(start, self.one_value() as u32),
+            DaysOfMonthKind::LastWeekdayOffset => {
+                nth_last_business_day(start, self.one_value() as u32)
+            }
+            DaysOfMonthKind::Specials(specials) => specials
+                .entries()
+                .filter_map(|day| day.find_next_day(start))
+                .min(),
+            _ => {
+                let map = self.pattern_in_month(start);
+                let current_day = start.day0();
+                let bottom_cleared = (map >> current_day) << current_day;
+                let trailing_zeros = bottom_cleared.trailing_zeros();
+                if trailing_zeros < days_in_month {
+                    start.with_day0(trailing_zeros)
                 } else {
-                    let start = u8::from(start) - 1;
-                    let end = u8::from(end) + 1;
+                    None
+                }
+            }
+        }
+        .filter(|&new_day| new_day >= start)
+    }
+}
 
-                    let top_bits = (Self::DAY_BITS >> start) << start;
+/// A list of special day of the month expressions, like `1W,15W`, as a bit-mask for each kind of
+/// expression of the values paired with the kind when the expression is on its own.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+struct DomSpecials([u32; 5]);
+impl DomSpecials {
+    const KINDS: [DaysOfMonthKind; 5] = [
+        DaysOfMonthKind::Last,
+        DaysOfMonthKind::LastWeekday,
+        DaysOfMonthKind::Weekday,
+        DaysOfMonthKind::BusinessDay,
+        DaysOfMonthKind::LastWeekdayOffset,
+    ];
 
-                    let bottom_shift = Self::BITS.wrapping_sub(end);
-                    let bottom_bits = (Self::DAY_BITS << bottom_shift) >> bottom_shift;
+    #[inline]
+    fn index(kind: DaysOfMonthKind) -> usize {
+        Self::KINDS
+            .iter()
+            .position(|&special| special == kind)
+            .expect("Only special expressions are in a list of specials")
+    }
 
-                    let bits = top_bits | bottom_bits;
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn push(&mut self, day: &DaysOfMonth) {
+        self.0[Self::index(day.kind())] |= 1 << day.1;
+    }
 
-                    debug_assert_pattern!(bits, Self::DAY_BITS);
+    /// Makes a list of closest weekday expressions from a pattern of days, like `1-7W`
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn closest_weekdays(pattern: u32) -> Self {
+        let mut specials = Self::default();
+        specials.0[Self::index(DaysOfMonthKind::Weekday)] = pattern << 1;
+        specials
+    }
 
-                    pattern |= bits;
-                }
+    /// Simplifies the list into its one expression if it only has one, or into a pattern of
+    /// days if it only has closest weekday expressions
+    #[cfg(feature = "alloc")]
+    fn compile(self) -> DaysOfMonth {
+        let weekdays = Self::index(DaysOfMonthKind::Weekday);
+        match self.0.iter().map(|bits| bits.count_ones()).sum::<u32>() {
+            1 => self.entries().next().expect("One expression is set"),
+            count if count == self.0[weekdays].count_ones() => {
+                DaysOfMonth(DaysOfMonthKind::Weekdays, self.0[weekdays] >> 1)
             }
-            OrsExpr::Step { start, end, step } => {
-                let start = u8::from(start);
-                let end = u8::from(end);
-                if start <= end {
-                    let range = (start..=end).step_by(u8::from(step) as usize);
+            _ => DaysOfMonth(DaysOfMonthKind::Specials(self), 0),
+        }
+    }
 
-                    for shift in range {
-                        pattern |= Self::value_pattern(shift);
-                    }
-                } else {
-                    let back = start..=parse::DayOfMonth::MAX;
-                    let front = parse::DayOfMonth::MIN..=end;
-                    let range = back.chain(front).step_by(u8::from(step) as usize);
+    /// Removes and returns the first expression in the list
+    fn pop_first(&mut self) -> Option<DaysOfMonth> {
+        let (i, bits) = self
+            .0
+            .iter_mut()
+            .enumerate()
+            .find(|(_, bits)| **bits != 0)?;
+        let value = bits.trailing_zeros();
+        *bits &= !(1 << value);
+        Some(DaysOfMonth(Self::KINDS[i], value))
+    }
+
+    /// Iterates over the expressions in the list
+    fn entries(mut self) -> impl Iterator<Item = DaysOfMonth> {
+        iter::from_fn(move || self.pop_first())
+    }
+}
 
-                    for shift in range {
-                        pattern |= Self::value_pattern(shift);
+impl Display for DaysOfMonth {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.kind() {
+            DaysOfMonthKind::Star => f.write_str("*"),
+            DaysOfMonthKind::Ignored => f.write_str("?"),
+            DaysOfMonthKind::Pattern => fmt_bits((self.1 & Self::DAY_BITS).into(), 1, f),
+            DaysOfMonthKind::Last => match self.one_value() {
+                0 => f.write_str("L"),
+                offset => write!(f, "L-{}", offset),
+            },
+            DaysOfMonthKind::LastWeekday => match self.one_value() {
+                0 => f.write_str("LW"),
+                offset => write!(f, "L-{}W", offset),
+            },
+            DaysOfMonthKind::Weekday => write!(f, "{}W", self.one_value()),
+            DaysOfMonthKind::Weekdays => {
+                fmt_bits(self.1.into(), 1, f)?;
+                f.write_str("W")
+            }
+            DaysOfMonthKind::BusinessDay => write!(f, "{}B", self.one_value()),
+            DaysOfMonthKind::LastWeekdayOffset => write!(f, "LW-{}", self.one_value()),
+            DaysOfMonthKind::Specials(specials) => {
+                for (i, day) in specials.entries().enumerate() {
+                    if i != 0 {
+                        f.write_str(",")?;
                     }
+                    Display::fmt(&day, f)?;
                 }
+                Ok(())
             }
         }
-        pattern
     }
 }
 
 /// A bit-mask of all the months set in a cron expression.
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 struct Months(u16);
 impl TimePattern for Months {
+    #[cfg(feature = "alloc")]
     type Expr = parse::Expr<parse::Month>;
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn compile(expr: Self::Expr) -> Self {
-        match expr {
-            parse::Expr::All => Self(Self::ALL),
-            parse::Expr::Many(exprs) => exprs.into_iter().fold(Self(0), Self::add_ors),
-        }
+        Self::from_field(parse::Field::from(expr).map(parse::collect))
     }
 
     /// Returns whether this mask contains the month value 0-11
     #[inline]
     fn contains(&self, date: DateTime<Utc>) -> bool {
-        self.contains_month(date.date())
+        self.contains_month(date.date_naive())
+    }
+}
+#[cfg(feature = "alloc")]
+impl parse::Collect<parse::Month> for Months {
+    #[inline]
+    fn new(first: OrsExpr<parse::Month>) -> Self {
+        Self(0).add_ors(first)
+    }
+
+    #[inline]
+    fn push(&mut self, expr: OrsExpr<parse::Month>) {
+        *self = self.add_ors(expr);
     }
 }
 impl Months {
     const BITS: u8 = 16;
     const ALL: u16 = 0x0FFF;
-    const UPPER_BIT_BOUND: u8 = Self::ALL.trailing_ones() as u8;
+    /// The months with 31 days
+    const MAX_31_MONTHS: u16 = 0b1010_1101_0101;
+    /// The months with 30 days
+    const MAX_30_MONTHS: u16 = 0b0101_0010_1000;
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn from_field(field: parse::Field<Self>) -> Self {
+        match field {
+            parse::Field::All => Self(Self::ALL),
+            parse::Field::Many(pattern) => pattern,
+        }
+    }
 
     #[inline]
-    fn contains_month(&self, date: Date<Utc>) -> bool {
+    fn contains_month(&self, date: NaiveDate) -> bool {
         let mask = 1u16 << date.month0();
         self.0 & mask != 0
     }
 
+    /// Returns the most days any of the months set can have.
+    #[inline]
+    fn max_days(&self) -> u8 {
+        if (self.0 & Self::MAX_31_MONTHS) != 0 {
+            31
+        } else if (self.0 & Self::MAX_30_MONTHS) != 0 {
+            30
+        } else {
+            29
+        }
+    }
+
+    /// Returns the number of days in every month set if they all have the same number of days.
     #[inline]
-    fn value_pattern<T>(value: T) -> u16
-    where
-        T: Into<u8>,
-    {
-        let pattern = 1 << value.into();
+    fn fixed_days(&self) -> Option<u8> {
+        if (self.0 & !Self::MAX_31_MONTHS) == 0 {
+            Some(31)
+        } else if (self.0 & !Self::MAX_30_MONTHS) == 0 {
+            Some(30)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn add_ors(self, expr: OrsExpr<parse::Month>) -> Self {
+        let mut field = BitField::<12>::from_bits(self.0.into());
+        field.set_expr(&expr);
+        Self(field.bits() as u16)
+    }
+}
 
-        debug_assert_pattern!(pattern, Self::ALL);
+/// A bit-mask of all the ISO weeks of the year set in a cron expression.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+struct Weeks(u64);
+impl TimePattern for Weeks {
+    #[cfg(feature = "alloc")]
+    type Expr = parse::Expr<parse::Week>;
 
-        pattern
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn compile(expr: Self::Expr) -> Self {
+        Self::from_field(parse::Field::from(expr).map(parse::collect))
     }
 
+    /// Returns whether this mask contains the ISO week 0-52 of the date
+    #[inline]
+    fn contains(&self, date: DateTime<Utc>) -> bool {
+        self.contains_week(date.date_naive())
+    }
+}
+#[cfg(feature = "alloc")]
+impl parse::Collect<parse::Week> for Weeks {
     #[inline]
-    fn add_ors(mut self, expr: OrsExpr<parse::Month>) -> Self {
-        match expr.normalize() {
-            OrsExpr::One(one) => self.0 |= Self::value_pattern(one),
-            OrsExpr::Range(start, end) => {
-                if start <= end {
-                    let start = u8::from(start);
-                    let end = u8::from(end);
+    fn new(first: OrsExpr<parse::Week>) -> Self {
+        Self(0).add_ors(first)
+    }
 
-                    // learn how this works in DayOfWeek's add_ors function
-                    let mut bits = Self::ALL;
-                    bits = (bits >> start) << start;
-                    if end < Self::UPPER_BIT_BOUND {
-                        let end_shift = Self::BITS.wrapping_sub(end + 1);
-                        bits = (bits << end_shift) >> end_shift;
-                    }
-                    debug_assert_pattern!(bits, Self::ALL);
+    #[inline]
+    fn push(&mut self, expr: OrsExpr<parse::Week>) {
+        *self = self.add_ors(expr);
+    }
+}
+impl Weeks {
+    const ALL: u64 = (1 << 53) - 1;
 
-                    self.0 |= bits;
-                } else {
-                    let start = u8::from(start) - 1;
-                    let end = u8::from(end) + 1;
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn from_field(field: parse::Field<Self>) -> Self {
+        match field {
+            parse::Field::All => Self(Self::ALL),
+            parse::Field::Many(pattern) => pattern,
+        }
+    }
 
-                    let top_bits = (Self::ALL >> start) << start;
+    #[inline]
+    fn is_all(&self) -> bool {
+        self.0 == Self::ALL
+    }
 
-                    let bottom_shift = Self::BITS.wrapping_sub(end);
-                    let bottom_bits = (Self::ALL << bottom_shift) >> bottom_shift;
+    #[inline]
+    fn contains_week(&self, date: NaiveDate) -> bool {
+        let mask = 1u64 << date.iso_week().week0();
+        self.0 & mask != 0
+    }
 
-                    let bits = top_bits | bottom_bits;
+    #[cfg(feature = "alloc")]
+    fn add_ors(self, expr: OrsExpr<parse::Week>) -> Self {
+        let mut field = BitField::<53>::from_bits(self.0);
+        field.set_expr(&expr);
+        Self(field.bits())
+    }
+}
 
-                    debug_assert_pattern!(bits, Self::ALL);
+/// Configures how the day of month and day of week fields are combined when both are set.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum DayPolicy {
+    /// A day matches if either field matches. If one of the fields is a '*' or a '?', only the
+    /// other field is used. This is the behavior of Vixie cron.
+    #[default]
+    Or,
+    /// A day matches only if both fields match. Expressions in the
+    /// [`Dialect::Extended`](parse::Dialect::Extended) dialect can also be written with a `&`
+    /// before the day of week field to use this policy, like `0 0 13 * &FRI`.
+    And,
+    /// Like Quartz, exactly one of the fields must be a '?' and only the other field is used.
+    /// Compiling an expression that doesn't ignore one of the day fields returns an error.
+    QuartzStrict,
+}
 
-                    self.0 |= bits;
-                }
+impl DayPolicy {
+    /// Returns the policy of an expression combining the day fields with a `&` or not
+    #[cfg(feature = "alloc")]
+    fn from_days_and(days_and: bool) -> Self {
+        if days_and {
+            DayPolicy::And
+        } else {
+            DayPolicy::Or
+        }
+    }
+}
+
+/// Configures whether a time with seconds or nanoseconds within a minute is matched by a cron
+/// value. See [`Cron::contains_with`].
+///
+/// [`Cron::contains_with`]: struct.Cron.html#method.contains_with
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum SecondsPolicy {
+    /// The time is truncated to the minute, so any time within a matching minute matches. This
+    /// is the behavior of [`Cron::contains`].
+    ///
+    /// [`Cron::contains`]: struct.Cron.html#method.contains
+    #[default]
+    Truncate,
+    /// Only the start of a matching minute matches, so the seconds and nanoseconds must be zero.
+    Exact,
+}
+
+/// Configures how the 29th of February is matched by cron values compiled with
+/// [`Cron::with_options`], since it only exists in leap years.
+///
+/// [`Cron::with_options`]: struct.Cron.html#method.with_options
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+#[non_exhaustive]
+pub enum Feb29Policy {
+    /// The 29th only matches the 29th, so February is skipped in common years
+    #[default]
+    Exact,
+    /// A day of month field matching the 29th also matches the 28th of February in common
+    /// years, the last day of the month, so an annual job on the 29th runs every year
+    LastDayOfFeb,
+    /// Compiling an expression that only matches the 29th of February, and so only fires in
+    /// leap years, returns an error
+    Reject,
+}
+
+/// Options used when compiling a cron expression into a cron value.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[non_exhaustive]
+pub struct CronOptions {
+    /// Configures how the day of month and day of week fields are combined
+    pub day_policy: DayPolicy,
+    /// Returns an error when compiling an expression that never matches any time, like
+    /// `* * 31 11 *`, instead of a cron value that never fires. The error says why, like the
+    /// days of the month not being in any of the months. See [`Cron::any`].
+    ///
+    /// [`Cron::any`]: struct.Cron.html#method.any
+    pub reject_impossible: bool,
+    /// Configures how the 29th of February is matched
+    pub feb_29_policy: Feb29Policy,
+}
+
+impl CronOptions {
+    /// Creates a new set of options with their default values
+    pub const fn new() -> Self {
+        Self {
+            day_policy: DayPolicy::Or,
+            reject_impossible: false,
+            feb_29_policy: Feb29Policy::Exact,
+        }
+    }
+}
+
+impl Default for CronOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An error returned if a cron expression can't be compiled with the given options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CronCompileError {
+    /// The expression was compiled with [`DayPolicy::QuartzStrict`] but neither the day of month
+    /// nor the day of week field is a '?'.
+    DaysNotIgnored,
+    /// The expression was compiled with [`CronOptions::reject_impossible`] set but never matches
+    /// any time.
+    ///
+    /// [`CronOptions::reject_impossible`]: struct.CronOptions.html#structfield.reject_impossible
+    NeverFires {
+        /// Why the expression never matches
+        reason: NeverFiresReason,
+    },
+    /// The expression was compiled with [`Feb29Policy::Reject`] but only matches the 29th of
+    /// February, so it only fires in leap years.
+    LeapYearsOnly,
+}
+
+impl Display for CronCompileError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CronCompileError::DaysNotIgnored => {
+                f.write_str("Either the day of month or the day of week field must be '?'")
             }
-            OrsExpr::Step { start, end, step } => {
-                let start = u8::from(start);
-                let end = u8::from(end);
-                if start <= end {
-                    let range = (start..=end).step_by(u8::from(step) as usize);
+            CronCompileError::NeverFires { reason } => {
+                write!(f, "The expression never matches any time, since {}", reason)
+            }
+            CronCompileError::LeapYearsOnly => {
+                f.write_str("The expression only matches the 29th of February in leap years")
+            }
+        }
+    }
+}
 
-                    for shift in range {
-                        self.0 |= Self::value_pattern(shift);
-                    }
-                } else {
-                    let back = start..=parse::Month::MAX;
-                    let front = parse::Month::MIN..=end;
-                    let range = back.chain(front).step_by(u8::from(step) as usize);
+/// Why an expression never matches any time, returned in [`CronCompileError::NeverFires`].
+///
+/// [`CronCompileError::NeverFires`]: enum.CronCompileError.html#variant.NeverFires
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NeverFiresReason {
+    /// None of the months is long enough for the days of the month, like the 31st of April
+    DayExceedsMonthLength {
+        /// The fewest days a month needs to have for the day of the month field to match a day
+        /// in it, like 31 for the 31st or 30 for `L-29`
+        day: u8,
+        /// The months (1-12) matched by the cron value, none of which has that many days
+        months: FieldValues,
+    },
+    /// Both day fields must match with [`DayPolicy::And`], but the days of the month never fall
+    /// on the days of the week, like the first Monday on the 31st
+    DaysNeverCoincide,
+    /// None of the dates matching the other fields is in the ISO weeks of the year, like the
+    /// first of January in week 30
+    WeeksNotInDates,
+}
 
-                    for shift in range {
-                        self.0 |= Self::value_pattern(shift);
-                    }
-                }
+impl Display for NeverFiresReason {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NeverFiresReason::DayExceedsMonthLength { day, .. } => {
+                write!(f, "none of the months has {} days", day)
+            }
+            NeverFiresReason::DaysNeverCoincide => {
+                f.write_str("the days of the month never fall on the days of the week")
+            }
+            NeverFiresReason::WeeksNotInDates => {
+                f.write_str("none of the matching dates is in the weeks of the year")
             }
         }
-        self
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for CronCompileError {}
+
 /// A cron value. This can be used to iterate over all future matching times or quickly check if
 /// a given time matches.
 ///
@@ -861,10 +1434,11 @@ impl Months {
 /// let cron: Cron = "*/10 0 * OCT MON".parse().expect("Couldn't parse expression!");
 ///
 /// // check if a given time is contained in an expression
-/// assert!(cron.contains(Utc.ymd(2020, 10, 19).and_hms(0, 30, 0)));
+/// assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 19, 0, 30, 0).unwrap()));
 ///
 /// // iterate over all future matching times
-/// for time in cron.clone().iter_from(Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)).take(5) {
+/// let start = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+/// for time in cron.clone().iter_from(start).take(5) {
 ///     // Prints
 ///     // 1970-10-05 00:00:00 UTC
 ///     // 1970-10-05 00:10:00 UTC
@@ -875,31 +1449,229 @@ impl Months {
 ///     assert!(cron.contains(time));
 /// }
 /// ```
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+///
+/// Cron values are ordered by their compiled form, so the order is total and two values are
+/// only equal if they match the same times with the same [`DayPolicy`]. The order isn't
+/// meaningful otherwise and may change between versions. Use [`Cron::cmp_next`] to order values
+/// by when they next match.
+///
+/// [`Cron::cmp_next`]: struct.Cron.html#method.cmp_next
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct Cron {
     minutes: Minutes,
     hours: Hours,
     dom: DaysOfMonth,
     months: Months,
     dow: DaysOfWeek,
+    weeks: Weeks,
+    day_policy: DayPolicy,
+}
+
+#[cfg(feature = "alloc")]
+impl parse::Collectors for Cron {
+    type Minutes = Minutes;
+    type Hours = Hours;
+    type Doms = DaysOfMonth;
+    type Months = Months;
+    type Dows = DaysOfWeek;
+    type Weeks = Weeks;
 }
 
+#[cfg(feature = "alloc")]
 impl FromStr for Cron {
     type Err = parse::CronParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Cron::parse_with(s, parse::Dialect::Quartz)
+    }
+}
+
+impl Cron {
+    /// Parses and compiles an expression written in the given dialect.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn parse_with(
+        s: &str,
+        dialect: parse::Dialect,
+    ) -> Result<Self, parse::CronParseError> {
         // parse and compile
         // Any parsed expression can have redundant info, but we can
         // easily compress it into a neat bit map where each of the bits
         // of an integer represent the minutes/hours/days/months/weekdays
         // in a cron expression. It might be compressable further but I
         // doubt we'll need to do that.
-        s.parse().map(Cron::new)
+        //
+        // Each field is compiled as it's parsed, so only shortcuts go through a `CronExpr`
+        parse::ParseOptions::new().check_limits(s)?;
+        if s.starts_with('@') {
+            return s.parse().map(Cron::new);
+        }
+
+        let mut options = parse::ParseOptions::new();
+        options.dialect = dialect;
+        parse::parse_fields::<Cron>(s, options).map(|fields| {
+            Cron {
+                minutes: Minutes::from_field(fields.minutes),
+                hours: Hours::from_field(fields.hours),
+                dom: DaysOfMonth::from_field(fields.doms),
+                months: Months::from_field(fields.months),
+                dow: DaysOfWeek::from_field(fields.dows),
+                weeks: Weeks::from_field(fields.weeks),
+                day_policy: DayPolicy::from_days_and(fields.days_and),
+            }
+            .exclude(fields.exclusions)
+        })
+    }
+
+    /// Removes the values excluded from each field with a `!` from the compiled fields.
+    #[cfg(feature = "alloc")]
+    fn exclude(mut self, exclusions: parse::Exclusions) -> Self {
+        if let Some(excluded) = exclusions.minutes {
+            self.minutes.0 &= !parse::collect::<_, Minutes>(excluded).0;
+        }
+        if let Some(excluded) = exclusions.hours {
+            self.hours.0 &= !parse::collect::<_, Hours>(excluded).0;
+        }
+        if let Some(excluded) = exclusions.doms {
+            self.dom.exclude(parse::collect(excluded));
+        }
+        if let Some(excluded) = exclusions.months {
+            self.months.0 &= !parse::collect::<_, Months>(excluded).0;
+        }
+        if let Some(excluded) = exclusions.dows {
+            self.dow.exclude(parse::collect(excluded));
+        }
+        if let Some(excluded) = exclusions.weeks {
+            self.weeks.0 &= !parse::collect::<_, Weeks>(excluded).0;
+        }
+        self
+    }
+}
+
+impl Display for Cron {
+    /// Formats the cron value as a canonical cron expression. Every field is written as a
+    /// list of values and ranges, so two cron values matching the same times are written the
+    /// same way, and the output parses back into an equal cron value. Values using
+    /// [`DayPolicy::And`] are written with a `&` before the day of the week field, and values
+    /// restricting the weeks of the year are written with a sixth field, both of which are only
+    /// parsed in the [`Dialect::Extended`](parse::Dialect::Extended) dialect. The other day
+    /// policies aren't part of the expression, so values compiled with them must be compiled
+    /// with the same options again.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, Dialect};
+    /// use saffron::Cron;
+    ///
+    /// let cron: Cron = "*/15 9-17 * * MON-FRI".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.to_string(), "0,15,30,45 9-17 * * 2-6");
+    /// assert_eq!(cron.to_string().parse::<Cron>().unwrap(), cron);
+    ///
+    /// let extended = |s: &str| CronExpr::from_str_with(s, Dialect::Extended).map(Cron::new);
+    /// let cron = extended("0 0 15 * &FRI").unwrap();
+    /// assert_eq!(cron.to_string(), "0 0 15 * &6");
+    /// assert_eq!(extended(&cron.to_string()), Ok(cron));
+    /// ```
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.minutes.0 == Minutes::ALL {
+            f.write_str("*")?;
+        } else {
+            fmt_bits(self.minutes.0, 0, f)?;
+        }
+        f.write_str(" ")?;
+        if self.hours.0 == Hours::ALL {
+            f.write_str("*")?;
+        } else {
+            fmt_bits(self.hours.0.into(), 0, f)?;
+        }
+        f.write_str(" ")?;
+        Display::fmt(&self.dom, f)?;
+        f.write_str(" ")?;
+        if self.months.0 == Months::ALL {
+            f.write_str("*")?;
+        } else {
+            fmt_bits(self.months.0.into(), 1, f)?;
+        }
+        f.write_str(" ")?;
+        if self.days_and() {
+            f.write_str("&")?;
+        }
+        Display::fmt(&self.dow, f)?;
+        if !self.weeks.is_all() {
+            f.write_str(" W")?;
+            fmt_bits(self.weeks.0, 1, f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A formatter for displaying a cron value description in a specified language. Created with
+/// [`Cron::describe`].
+///
+/// [`Cron::describe`]: struct.Cron.html#method.describe
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct CronDescription<L> {
+    expr: CronExpr,
+    lang: L,
+}
+
+#[cfg(feature = "alloc")]
+impl<L: Language> Display for CronDescription<L> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        describe::fmt_description(&self.lang, &self.expr, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L: Language> CronDescription<L> {
+    /// Returns the description split into segments, each describing one part of the expression.
+    pub fn segments(&self) -> Vec<DescriptionSegment> {
+        self.lang.segments(&self.expr)
+    }
+}
+
+/// Statistics about the gaps between consecutive times matched by a cron value. Created with
+/// [`Cron::interval_stats`].
+///
+/// [`Cron::interval_stats`]: struct.Cron.html#method.interval_stats
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub struct IntervalStats {
+    /// The number of times matched
+    pub count: u64,
+    /// The shortest gap between two consecutive times
+    pub min: Duration,
+    /// The longest gap between two consecutive times
+    pub max: Duration,
+    /// The average gap between two consecutive times, truncated to the millisecond
+    pub mean: Duration,
+}
+
+/// The times matched in a single day, as minutes of the day.
+#[derive(Clone, Copy)]
+struct DayTimes {
+    count: u64,
+    first: u32,
+    last: u32,
+    /// The shortest and longest gap between two times of the day, if there are at least two
+    gaps: Option<(u32, u32)>,
+}
+
+impl DayTimes {
+    fn first_time(&self, date: NaiveDate) -> DateTime<Utc> {
+        date_time(date, minute_of_day(self.first))
+    }
+
+    fn last_time(&self, date: NaiveDate) -> DateTime<Utc> {
+        date_time(date, minute_of_day(self.last))
     }
 }
 
 impl Cron {
-    /// Simplifies the cron expression into a cron value.
+    /// Simplifies the cron expression into a cron value. Expressions with
+    /// [`days_and`](parse/struct.CronExpr.html#structfield.days_and) set are compiled with
+    /// [`DayPolicy::And`], every other expression with [`DayPolicy::Or`].
+    #[cfg(feature = "alloc")]
     pub fn new(expr: CronExpr) -> Self {
         Self {
             minutes: TimePattern::compile(expr.minutes),
@@ -907,60 +1679,623 @@ impl Cron {
             dom: TimePattern::compile(expr.doms),
             months: TimePattern::compile(expr.months),
             dow: TimePattern::compile(expr.dows),
+            weeks: TimePattern::compile(expr.weeks),
+            day_policy: DayPolicy::from_days_and(expr.days_and),
         }
+        .exclude(expr.exclusions)
     }
 
-    /// Returns whether this cron value will ever match any giving time.
-    ///
-    /// Some values can never match any given time. If an value matches
-    /// for a day of the month that's beyond any of the valid days of the months matched
-    /// then the value can never match.
+    /// Simplifies the cron expression into a cron value using the given options. Expressions
+    /// with [`days_and`](parse/struct.CronExpr.html#structfield.days_and) set always use
+    /// [`DayPolicy::And`].
     ///
     /// # Example
     /// ```
-    /// use saffron::Cron;
+    /// use saffron::{Cron, CronCompileError, CronOptions, DayPolicy};
+    /// use chrono::prelude::*;
     ///
-    /// // Does have any since February has a 29th day on leap years
-    /// assert!("* * 29 2 *".parse::<Cron>().unwrap().any());
+    /// let mut options = CronOptions::new();
+    /// options.day_policy = DayPolicy::And;
     ///
-    /// // Does not have any since November does not have a 31st day
-    /// assert!(!"* * 31 11 *".parse::<Cron>().unwrap().any());
+    /// // every Friday the 13th
+    /// let cron = Cron::with_options("0 0 13 * FRI".parse().unwrap(), options.clone()).unwrap();
+    /// assert!(cron.contains(Utc.with_ymd_and_hms(2020, 11, 13, 0, 0, 0).unwrap()));
+    /// assert!(!cron.contains(Utc.with_ymd_and_hms(2020, 10, 13, 0, 0, 0).unwrap()));
+    ///
+    /// // November never has a 31st day
+    /// options.reject_impossible = true;
+    /// let err = Cron::with_options("* * 31 11 *".parse().unwrap(), options).unwrap_err();
+    /// let cron_reason = "* * 31 11 *".parse::<Cron>().unwrap().why_never().unwrap();
+    /// assert_eq!(
+    ///     err,
+    ///     CronCompileError::NeverFires { reason: cron_reason }
+    /// );
     /// ```
-    #[inline]
-    pub fn any(&self) -> bool {
-        if self.dow.is_star() {
-            if self.dom.is_star() {
-                return true;
+    #[cfg(feature = "alloc")]
+    pub fn with_options(expr: CronExpr, options: CronOptions) -> Result<Self, CronCompileError> {
+        let days_and = expr.days_and;
+        let mut cron = Self::new(expr);
+        if options.day_policy == DayPolicy::QuartzStrict
+            && cron.dom.kind() != DaysOfMonthKind::Ignored
+            && cron.dow.kind() != DaysOfWeekKind::Ignored
+        {
+            return Err(CronCompileError::DaysNotIgnored);
+        }
+        if !days_and {
+            cron.day_policy = options.day_policy;
+        }
+        match options.feb_29_policy {
+            Feb29Policy::Exact => {}
+            Feb29Policy::LastDayOfFeb => cron.dom.set_feb_29_last(),
+            Feb29Policy::Reject if cron.leap_years_only() => {
+                return Err(CronCompileError::LeapYearsOnly)
+            }
+            Feb29Policy::Reject => {}
+        }
+        if options.reject_impossible {
+            if let Some(reason) = cron.why_never() {
+                return Err(CronCompileError::NeverFires { reason });
             }
+        }
+        Ok(cron)
+    }
 
-            let first_set = if self.dom.is_last() {
-                match self.dom.one_value() {
-                    0 => return true,
-                    offset => offset + 1,
-                }
-            } else {
-                self.dom
-                    .first_set()
-                    .expect("At least one day should be set")
-            };
+    /// Returns the policy used to combine the day of month and day of week fields.
+    #[inline]
+    pub fn day_policy(&self) -> DayPolicy {
+        self.day_policy
+    }
 
-            const MAX_31_MONTHS: u16 = 0b1010_1101_0101;
-            const MAX_30_MONTHS: u16 = 0b0101_0010_1000;
-            let max = if (self.months.0 & MAX_31_MONTHS) != 0 {
-                31
-            } else if (self.months.0 & MAX_30_MONTHS) != 0 {
-                30
-            } else {
-                29
-            };
+    /// Returns the minutes (0-59) matched by the cron value in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let cron: Cron = "*/20 * * * *".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.minutes().collect::<Vec<_>>(), [0, 20, 40]);
+    /// ```
+    #[inline]
+    pub fn minutes(&self) -> FieldValues {
+        FieldValues::new(self.minutes.0, 0)
+    }
+
+    /// Returns the hours (0-23) matched by the cron value in ascending order.
+    #[inline]
+    pub fn hours(&self) -> FieldValues {
+        FieldValues::new(self.hours.0.into(), 0)
+    }
+
+    /// Returns the days of the month matched by the cron value.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, DayOfMonthSpec};
+    ///
+    /// let cron: Cron = "0 0 L-2 * *".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.days_of_month(), DayOfMonthSpec::Last { offset: 2 });
+    /// ```
+    pub fn days_of_month(&self) -> DayOfMonthSpec {
+        self.dom.spec()
+    }
+
+    /// Returns the months (1-12) matched by the cron value in ascending order.
+    #[inline]
+    pub fn months(&self) -> FieldValues {
+        FieldValues::new(self.months.0.into(), 1)
+    }
+
+    /// Returns the ISO weeks of the year (1-53) matched by the cron value in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use saffron::parse::{CronExpr, Dialect};
+    ///
+    /// let expr = CronExpr::from_str_with("0 9 * * MON W/26", Dialect::Extended).unwrap();
+    /// assert_eq!(Cron::new(expr).weeks().collect::<Vec<_>>(), [1, 27, 53]);
+    /// ```
+    #[inline]
+    pub fn weeks(&self) -> FieldValues {
+        FieldValues::new(self.weeks.0, 1)
+    }
+
+    /// Returns the days of the week matched by the cron value.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, DayOfWeekSpec};
+    /// use chrono::Weekday;
+    ///
+    /// let cron: Cron = "0 0 * * MON#2".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.days_of_week(), DayOfWeekSpec::Nth(Weekday::Mon, 2));
+    ///
+    /// let cron: Cron = "0 0 * * SAT,SUN".parse().expect("Couldn't parse expression!");
+    /// match cron.days_of_week() {
+    ///     DayOfWeekSpec::Days(days) => {
+    ///         assert_eq!(days.collect::<Vec<_>>(), [Weekday::Sun, Weekday::Sat])
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn days_of_week(&self) -> DayOfWeekSpec {
+        self.dow.spec()
+    }
+
+    /// Rebuilds an expression matching the same times as the cron value. The expression is
+    /// written the same way as the [`Display`] output, as lists of values and ranges, so steps
+    /// and names in the original expression aren't kept.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let cron: Cron = "*/20 9-17 * * MON".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.to_expr().to_string(), "0,20,40 9-17 * * 2");
+    /// assert_eq!(Cron::new(cron.to_expr()), cron);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_expr(&self) -> CronExpr {
+        fn many<E: TryFrom<u8, Error = ValueOutOfRangeError>>(bits: u64, offset: u8) -> Exprs<E> {
+            let mut runs = BitRuns(bits).map(|(start, end)| {
+                let value = |v: u8| E::try_from(v + offset).expect("Compiled values are in range");
+                if start == end {
+                    OrsExpr::One(value(start))
+                } else {
+                    OrsExpr::Range(value(start), value(end))
+                }
+            });
+            let mut exprs = Exprs::new(runs.next().expect("At least one value should be set"));
+            exprs.tail.extend(runs);
+            exprs
+        }
+        fn offset(value: u8) -> DayOfMonthOffset {
+            DayOfMonthOffset::try_from(value).expect("Compiled offsets are in range")
+        }
+
+        let minutes = match self.minutes.0 {
+            Minutes::ALL => Expr::All,
+            bits => Expr::Many(many(bits, 0)),
+        };
+        let hours = match self.hours.0 {
+            Hours::ALL => Expr::All,
+            bits => Expr::Many(many(bits.into(), 0)),
+        };
+        fn dom_special(day: &DaysOfMonth) -> DayOfMonthSpecial {
+            match (day.kind(), day.one_value()) {
+                (DaysOfMonthKind::Last, 0) => DayOfMonthSpecial::Last(Last::Day),
+                (DaysOfMonthKind::Last, value) => {
+                    DayOfMonthSpecial::Last(Last::Offset(offset(value)))
+                }
+                (DaysOfMonthKind::LastWeekday, 0) => DayOfMonthSpecial::Last(Last::Weekday),
+                (DaysOfMonthKind::LastWeekday, value) => {
+                    DayOfMonthSpecial::Last(Last::OffsetWeekday(offset(value)))
+                }
+                (DaysOfMonthKind::Weekday, value) => DayOfMonthSpecial::ClosestWeekday(
+                    parse::DayOfMonth::try_from(value).expect("Compiled days are in range"),
+                ),
+                (DaysOfMonthKind::BusinessDay, value) => DayOfMonthSpecial::BusinessDay(
+                    parse::BusinessDay::try_from(value)
+                        .expect("Compiled business days are in range"),
+                ),
+                (DaysOfMonthKind::LastWeekdayOffset, value) => {
+                    DayOfMonthSpecial::Last(Last::WeekdayOffset(offset(value)))
+                }
+                _ => unreachable!("Only special expressions are converted"),
+            }
+        }
+        fn dow_special(day: &DaysOfWeek) -> DayOfWeekSpecial {
+            match day.nth() {
+                Some((nth, weekday)) => {
+                    let nth = NthDay::try_from(nth).expect("Compiled nth values are in range");
+                    DayOfWeekSpecial::Nth(weekday.into(), nth)
+                }
+                None => DayOfWeekSpecial::Last(DaysOfWeek::byte_to_weekday(day.1).into()),
+            }
+        }
+
+        let doms = match self.dom.kind() {
+            DaysOfMonthKind::Star => DayOfMonthExpr::All,
+            DaysOfMonthKind::Ignored => DayOfMonthExpr::Ignored,
+            DaysOfMonthKind::Pattern => {
+                DayOfMonthExpr::Many(many((self.dom.1 & DaysOfMonth::DAY_BITS).into(), 1))
+            }
+            DaysOfMonthKind::Weekdays => {
+                DayOfMonthExpr::ClosestWeekdays(many(self.dom.1.into(), 1))
+            }
+            DaysOfMonthKind::Specials(specials) => {
+                DayOfMonthExpr::Specials(specials.entries().map(|day| dom_special(&day)).collect())
+            }
+            _ => dom_special(&self.dom).into(),
+        };
+        let months = match self.months.0 {
+            Months::ALL => Expr::All,
+            bits => Expr::Many(many(bits.into(), 1)),
+        };
+        let dows = match self.dow.kind() {
+            DaysOfWeekKind::Star => DayOfWeekExpr::All,
+            DaysOfWeekKind::Ignored => DayOfWeekExpr::Ignored,
+            DaysOfWeekKind::Pattern => DayOfWeekExpr::Many(many(self.dow.1.into(), 1)),
+            DaysOfWeekKind::Specials(specials) => {
+                DayOfWeekExpr::Specials(specials.entries().map(|day| dow_special(&day)).collect())
+            }
+            _ => dow_special(&self.dow).into(),
+        };
+
+        let weeks = match self.weeks.0 {
+            Weeks::ALL => Expr::All,
+            bits => Expr::Many(many(bits, 1)),
+        };
+
+        CronExpr {
+            minutes,
+            hours,
+            doms,
+            months,
+            dows,
+            weeks,
+            shortcut: None,
+            exclusions: Exclusions::default(),
+            days_and: self.days_and(),
+        }
+    }
+
+    /// Returns a formatter to display the cron value in the provided language. The value is
+    /// described using the expression returned by [`to_expr`](#method.to_expr).
+    ///
+    /// The [`DayPolicy`] isn't part of the description, so values compiled with
+    /// [`DayPolicy::And`] are described the same way as values compiled with [`DayPolicy::Or`].
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use saffron::parse::English;
+    ///
+    /// let cron: Cron = "0 */12 * * *".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(
+    ///     cron.describe(English::default()).to_string(),
+    ///     "At 0 minutes past the hour, between 12:00 AM and 12:59 AM and between 12:00 PM and 12:59 PM",
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn describe<L: Language>(&self, lang: L) -> CronDescription<L> {
+        CronDescription {
+            expr: self.to_expr(),
+            lang,
+        }
+    }
+
+    /// Returns whether a day must match both day fields when both are restricted.
+    #[inline]
+    fn days_and(&self) -> bool {
+        self.day_policy == DayPolicy::And
+    }
+
+    /// Returns whether this cron value will ever match any giving time.
+    ///
+    /// Some values can never match any given time. If an value matches
+    /// for a day of the month that's beyond any of the valid days of the months matched
+    /// then the value can never match.
+    /// With [`DayPolicy::And`], the value also never matches if the day of the month and the day
+    /// of the week never fall on the same date, and a value restricting the weeks of the year
+    /// never matches if none of its days fall in those weeks.
+    ///
+    /// The check is exact for every expression and day policy: if this returns true, some time
+    /// is matched and the value fires eventually, and if it returns false, no time is ever
+    /// matched. Values that can't be checked from their fields alone are checked by searching
+    /// one 400 year cycle of the calendar, after which every date repeats on the same day of the
+    /// week and in the same week of the year.
+    ///
+    /// Compiling with [`CronOptions::reject_impossible`] set returns an error instead of a value
+    /// that never matches.
+    ///
+    /// [`CronOptions::reject_impossible`]: struct.CronOptions.html#structfield.reject_impossible
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// // Does have any since February has a 29th day on leap years
+    /// assert!("* * 29 2 *".parse::<Cron>().unwrap().any());
+    ///
+    /// // Does not have any since November does not have a 31st day
+    /// assert!(!"* * 31 11 *".parse::<Cron>().unwrap().any());
+    /// ```
+    #[inline]
+    pub fn any(&self) -> bool {
+        if !self.weeks.is_all() {
+            return self.any_in_cycle();
+        }
+
+        if self.dow.is_star() {
+            if self.dom.is_star() {
+                return true;
+            }
 
-            first_set <= max
+            // every day of a month falls on a weekday in some year, so a day of the month only
+            // needs a month long enough for it
+            self.dom.min_days_in_month() <= self.months.max_days()
+        } else if self.days_and() && !self.dom.is_star() {
+            self.any_in_cycle()
         } else {
+            // a day of the week matched on its own falls in every month in some year, even the
+            // 5th Monday of February on a leap year starting on a Monday
             true
         }
     }
 
-    /// Returns whether this cron value matches the given time.
+    /// Returns whether any date in one cycle of the calendar matches. This is always exact, but
+    /// slow compared to checking the fields.
+    fn any_in_cycle(&self) -> bool {
+        // the calendar repeats every 400 years, so searching one full cycle finds a match if
+        // there is one
+        (2000..2400).any(|year| {
+            let start = NaiveDate::from_ymd_opt(year, 1, 1).expect("valid date");
+            let end = NaiveDate::from_ymd_opt(year, 12, 31).expect("valid date");
+            matches!(self.find_next_date(start, end), Ok(Some(_)))
+        })
+    }
+
+    /// Returns why the cron value never matches any time, or `None` if it matches some time
+    /// (when [`any`] returns true). UIs can use the reason to explain how to fix the expression.
+    ///
+    /// [`any`]: #method.any
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, NeverFiresReason};
+    ///
+    /// let cron: Cron = "0 0 31 4,6 *".parse().unwrap();
+    /// match cron.why_never() {
+    ///     Some(NeverFiresReason::DayExceedsMonthLength { day, months }) => {
+    ///         assert_eq!(day, 31);
+    ///         assert_eq!(months.collect::<Vec<_>>(), [4, 6]);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// assert_eq!("0 0 29 2 *".parse::<Cron>().unwrap().why_never(), None);
+    /// ```
+    pub fn why_never(&self) -> Option<NeverFiresReason> {
+        if self.any() {
+            return None;
+        }
+
+        let day = self.dom.min_days_in_month();
+        let days_alone = self.dow.is_star() || self.days_and();
+        let every_week = Cron {
+            weeks: Weeks(Weeks::ALL),
+            ..self.clone()
+        };
+        if days_alone && day > self.months.max_days() {
+            Some(NeverFiresReason::DayExceedsMonthLength {
+                day,
+                months: self.months(),
+            })
+        } else if !self.weeks.is_all() && every_week.any() {
+            Some(NeverFiresReason::WeeksNotInDates)
+        } else {
+            Some(NeverFiresReason::DaysNeverCoincide)
+        }
+    }
+
+    /// Returns whether this cron value matches the exact same times as another cron value, even if
+    /// they were written differently or compiled with different options.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let a: Cron = "*/15 * ? * MON-FRI".parse().unwrap();
+    /// let b: Cron = "0,15,30,45 * * * 2-6".parse().unwrap();
+    /// assert!(a.equivalent_to(&b));
+    ///
+    /// // April has no 31st day
+    /// let a: Cron = "0 0 L APR *".parse().unwrap();
+    /// let b: Cron = "0 0 30,31 APR *".parse().unwrap();
+    /// assert!(a.equivalent_to(&b));
+    ///
+    /// // neither value ever matches
+    /// let a: Cron = "* * 31 11 *".parse().unwrap();
+    /// let b: Cron = "* * 30 2 *".parse().unwrap();
+    /// assert!(a.equivalent_to(&b));
+    /// ```
+    pub fn equivalent_to(&self, other: &Cron) -> bool {
+        match (self.any(), other.any()) {
+            (false, false) => return true,
+            (true, true) => {}
+            _ => return false,
+        }
+
+        self.minutes == other.minutes
+            && self.hours == other.hours
+            && self.months == other.months
+            && self.weeks == other.weeks
+            && self.effective_days() == other.effective_days()
+    }
+
+    /// Simplifies the day of month and day of week fields into the days they actually match
+    /// given the months set.
+    fn effective_days(&self) -> EffectiveDays {
+        let dom = match self.dom.kind() {
+            DaysOfMonthKind::Star | DaysOfMonthKind::Ignored => None,
+            DaysOfMonthKind::Pattern => {
+                // remove days that are never in any of the months, and the 28th of February
+                // matched by the 29th if the 28th is already matched
+                let max_days = self.months.max_days();
+                let mut pattern = self.dom.1 & (DaysOfMonth::DAY_BITS >> (31 - max_days));
+                if self.dom.1 & DaysOfMonth::FEB_29_LAST != 0
+                    && pattern & (1 << 28) != 0
+                    && pattern & (1 << 27) == 0
+                    && self.months.0 & (1 << 1) != 0
+                {
+                    pattern |= DaysOfMonth::FEB_29_LAST;
+                }
+                Some(DaysOfMonth(DaysOfMonthKind::Pattern, pattern))
+            }
+            DaysOfMonthKind::Last => match self.months.fixed_days() {
+                // an 'L' in months of the same length is always the same day
+                Some(days) => {
+                    let pattern = match days.checked_sub(self.dom.one_value()) {
+                        Some(day) if day > 0 => 1 << (day - 1),
+                        _ => 0,
+                    };
+                    Some(DaysOfMonth(DaysOfMonthKind::Pattern, pattern))
+                }
+                None => Some(self.dom.clone()),
+            },
+            _ => Some(self.dom.clone()),
+        };
+        let dow = match self.dow.kind() {
+            DaysOfWeekKind::Star | DaysOfWeekKind::Ignored => None,
+            _ => Some(self.dow.clone()),
+        };
+
+        let all_days = DaysOfMonth::DAY_BITS >> (31 - self.months.max_days());
+        let dom_all = match dom {
+            Some(DaysOfMonth(DaysOfMonthKind::Pattern, pattern)) => pattern == all_days,
+            Some(_) => false,
+            None => true,
+        };
+        let dom_none = matches!(dom, Some(DaysOfMonth(DaysOfMonthKind::Pattern, 0)));
+        let dow_all = match dow {
+            Some(DaysOfWeek(DaysOfWeekKind::Pattern, pattern)) => pattern == DaysOfWeek::DAY_BITS,
+            Some(_) => false,
+            None => true,
+        };
+
+        match (dom, dow) {
+            (None, None) => EffectiveDays::All,
+            (Some(_), None) if dom_all => EffectiveDays::All,
+            (Some(dom), None) => EffectiveDays::DaysOfMonth(dom),
+            (None, Some(_)) if dow_all => EffectiveDays::All,
+            (None, Some(dow)) => EffectiveDays::DaysOfWeek(dow),
+            (Some(dom), Some(dow)) if self.days_and() => match (dom_all, dow_all) {
+                (true, true) => EffectiveDays::All,
+                (true, false) => EffectiveDays::DaysOfWeek(dow),
+                (false, true) => EffectiveDays::DaysOfMonth(dom),
+                (false, false) => EffectiveDays::Both(dom, dow),
+            },
+            (Some(_), Some(_)) if dom_all || dow_all => EffectiveDays::All,
+            (Some(_), Some(dow)) if dom_none => EffectiveDays::DaysOfWeek(dow),
+            (Some(dom), Some(dow)) => EffectiveDays::Either(dom, dow),
+        }
+    }
+
+    /// Returns whether this cron value and another cron value ever match the same time.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let a: Cron = "*/20 * * * *".parse().unwrap();
+    /// let b: Cron = "*/30 * * * *".parse().unwrap();
+    /// assert!(a.overlaps(&b));
+    ///
+    /// let a: Cron = "0 * * * *".parse().unwrap();
+    /// let b: Cron = "30 * * * *".parse().unwrap();
+    /// assert!(!a.overlaps(&b));
+    /// ```
+    pub fn overlaps(&self, other: &Cron) -> bool {
+        // the calendar repeats every 400 years, so searching one cycle from any start is enough
+        self.next_common(other, Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap())
+            .is_some()
+    }
+
+    /// Returns the next time (current inclusive) that both this cron value and another cron
+    /// value match, or none if they never match the same time.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let a: Cron = "0 12 * * FRI".parse().unwrap();
+    /// let b: Cron = "0 */6 13 * *".parse().unwrap();
+    /// let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    /// let common = Utc.with_ymd_and_hms(2020, 3, 13, 12, 0, 0).unwrap();
+    /// assert_eq!(a.next_common(&b, start), Some(common));
+    /// ```
+    pub fn next_common(&self, other: &Cron, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        // both values have to match the same time of the day in the same months, so limit both
+        // of them to those
+        let minutes = Minutes(self.minutes.0 & other.minutes.0);
+        let hours = Hours(self.hours.0 & other.hours.0);
+        let months = Months(self.months.0 & other.months.0);
+        let weeks = Weeks(self.weeks.0 & other.weeks.0);
+        if minutes.0 == 0
+            || hours.0 == 0
+            || months.0 == 0
+            || weeks.0 == 0
+            || self.days_disjoint(other)
+        {
+            return None;
+        }
+
+        let this = Cron {
+            minutes,
+            hours,
+            months,
+            weeks,
+            ..self.clone()
+        };
+        let other = Cron {
+            minutes,
+            hours,
+            months,
+            weeks,
+            ..other.clone()
+        };
+        if !this.any() || !other.any() {
+            return None;
+        }
+
+        // jump between the next times of both values until they land on the same time, giving up
+        // after a full 400 year cycle of the calendar
+        let start = minute_floor(start);
+        let end = NaiveDate::from_ymd_opt(start.year() + 400, 1, 1)
+            .map_or(DateTime::<Utc>::MAX_UTC, |end| {
+                date_time(end, NaiveTime::MIN)
+            });
+        let mut next = this.find_next(start, end)?;
+        loop {
+            let other_next = other.find_next(next, end)?;
+            if other_next == next {
+                return Some(next);
+            }
+
+            next = this.find_next(other_next, end)?;
+            if next == other_next {
+                return Some(next);
+            }
+        }
+    }
+
+    /// Returns whether the day fields of two cron values can be quickly shown to never match the
+    /// same day.
+    fn days_disjoint(&self, other: &Cron) -> bool {
+        let dow_patterns = self.dow.kind() == DaysOfWeekKind::Pattern
+            && other.dow.kind() == DaysOfWeekKind::Pattern;
+        // a pattern matching the 29th can also match the 28th of February
+        let dom_patterns = self.dom.kind() == DaysOfMonthKind::Pattern
+            && other.dom.kind() == DaysOfMonthKind::Pattern
+            && (self.dom.1 | other.dom.1) & DaysOfMonth::FEB_29_LAST == 0;
+
+        if self.dom.is_star() && other.dom.is_star() && dow_patterns {
+            self.dow.1 & other.dow.1 == 0
+        } else if self.dow.is_star() && other.dow.is_star() && dom_patterns {
+            self.dom.1 & other.dom.1 == 0
+        } else {
+            false
+        }
+    }
+
+    /// Returns whether this cron value matches the given time. The time is truncated to the
+    /// minute, so any second within a matching minute matches. Use [`contains_exact`] to only
+    /// match the start of the minute.
+    ///
+    /// [`contains_exact`]: #method.contains_exact
+    ///
     /// # Example
     /// ```
     /// use saffron::Cron;
@@ -969,12 +2304,15 @@ impl Cron {
     /// let cron: Cron = "*/10 0 * OCT MON".parse().expect("Couldn't parse expression!");
     ///
     /// // check if a given time is contained in an expression
-    /// assert!(cron.contains(Utc.ymd(2020, 10, 19).and_hms(0, 30, 0)));
+    /// assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 19, 0, 30, 0).unwrap()));
+    /// assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 19, 0, 30, 59).unwrap()));
     /// ```
     #[inline]
     pub fn contains(&self, dt: DateTime<Utc>) -> bool {
-        let contains_minutes_hour_months =
-            self.minutes.contains(dt) && self.hours.contains(dt) && self.months.contains(dt);
+        let contains_minutes_hour_months = self.minutes.contains(dt)
+            && self.hours.contains(dt)
+            && self.months.contains(dt)
+            && self.weeks.contains(dt);
 
         if !contains_minutes_hour_months {
             return false;
@@ -984,13 +2322,56 @@ impl Cron {
             (true, true) => true,
             (true, false) => self.dow.contains(dt),
             (false, true) => self.dom.contains(dt),
+            (false, false) if self.days_and() => self.dow.contains(dt) && self.dom.contains(dt),
             (false, false) => self.dow.contains(dt) || self.dom.contains(dt),
         }
     }
 
+    /// Returns whether this cron value matches the given time exactly, so the time must be at
+    /// the start of a matching minute with no seconds or nanoseconds. This is short for
+    /// `contains_with(dt, SecondsPolicy::Exact)`.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::{prelude::*, Duration};
+    ///
+    /// let cron: Cron = "*/10 0 * OCT MON".parse().expect("Couldn't parse expression!");
+    ///
+    /// let time = Utc.with_ymd_and_hms(2020, 10, 19, 0, 30, 0).unwrap();
+    /// assert!(cron.contains_exact(time));
+    /// assert!(!cron.contains_exact(time + Duration::seconds(1)));
+    /// assert!(!cron.contains_exact(time + Duration::milliseconds(1)));
+    /// ```
+    #[inline]
+    pub fn contains_exact(&self, dt: DateTime<Utc>) -> bool {
+        self.contains_with(dt, SecondsPolicy::Exact)
+    }
+
+    /// Returns whether this cron value matches the given time, using the given policy for
+    /// times with seconds or nanoseconds within the minute.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, SecondsPolicy};
+    /// use chrono::prelude::*;
+    ///
+    /// let cron: Cron = "0 12 * * *".parse().expect("Couldn't parse expression!");
+    /// let time = Utc.with_ymd_and_hms(2020, 10, 19, 12, 0, 30).unwrap();
+    ///
+    /// assert!(cron.contains_with(time, SecondsPolicy::Truncate));
+    /// assert!(!cron.contains_with(time, SecondsPolicy::Exact));
+    /// ```
+    pub fn contains_with(&self, dt: DateTime<Utc>, policy: SecondsPolicy) -> bool {
+        match policy {
+            SecondsPolicy::Truncate => self.contains(dt),
+            SecondsPolicy::Exact => dt.second() == 0 && dt.nanosecond() == 0 && self.contains(dt),
+        }
+    }
+
     #[inline]
-    fn contains_date(&self, date: Date<Utc>) -> bool {
-        if !self.months.contains_month(date) {
+    fn contains_date(&self, date: NaiveDate) -> bool {
+        if !self.months.contains_month(date) || !self.weeks.contains_week(date) {
             return false;
         }
 
@@ -998,6 +2379,9 @@ impl Cron {
             (true, true) => true,
             (true, false) => self.dow.contains_date(date),
             (false, true) => self.dom.contains_date(date),
+            (false, false) if self.days_and() => {
+                self.dow.contains_date(date) && self.dom.contains_date(date)
+            }
             (false, false) => self.dow.contains_date(date) || self.dom.contains_date(date),
         }
     }
@@ -1011,7 +2395,7 @@ impl Cron {
     /// use chrono::prelude::*;
     ///
     /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
-    /// for time in cron.iter_from(Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)).take(5) {
+    /// for time in cron.iter_from(Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap()).take(5) {
     ///     // Prints
     ///     // 1970-01-01 00:00:00 UTC
     ///     // 1970-01-01 00:10:00 UTC
@@ -1035,7 +2419,7 @@ impl Cron {
     /// use chrono::prelude::*;
     ///
     /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
-    /// for time in cron.iter_after(Utc.ymd(1970, 1, 1).and_hms(0, 0, 0)).take(5) {
+    /// for time in cron.iter_after(Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap()).take(5) {
     ///     // Prints
     ///     // 1970-01-01 00:10:00 UTC
     ///     // 1970-01-01 00:20:00 UTC
@@ -1061,7 +2445,7 @@ impl Cron {
     /// use chrono::prelude::*;
     ///
     /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
-    /// let start = Utc.ymd(1970, 1, 1).and_hms(0, 0, 0);
+    /// let start = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
     ///
     /// // effectively the same as iter_from
     /// let _ = cron.clone().iter(start..);
@@ -1070,34 +2454,17 @@ impl Cron {
     /// let _ = cron.clone().iter(start..(start + chrono::Duration::seconds(60 * 30)));
     /// ```
     pub fn iter<R: RangeBounds<DateTime<Utc>>>(self, bounds: R) -> CronTimesIter {
-        if !self.any() {
-            return CronTimesIter {
-                cron: self,
-                bounds: None,
-            };
-        }
-
-        let front = match bounds.start_bound() {
-            Bound::Unbounded => Some(chrono::MIN_DATETIME),
-            Bound::Included(start) => Some(*start),
-            Bound::Excluded(start) => next_minute(*start),
-        }
-        .map(minute_floor);
-
-        let back = match bounds.end_bound() {
-            Bound::Unbounded => Some(chrono::MAX_DATETIME),
-            Bound::Included(end) => Some(*end),
-            Bound::Excluded(end) => previous_minute(*end),
-        }
-        .map(minute_floor);
-
+        let (bounds, bounded) = self.iter_bounds(bounds);
         CronTimesIter {
             cron: self,
-            bounds: front.zip(back).filter(|(front, back)| front <= back),
+            bounds,
+            bounded,
         }
     }
 
-    /// Returns the next time the cron will match including the given date.
+    /// Creates an iterator of date times that match with the cron value including the given
+    /// date, borrowing the cron value instead of consuming it. This is short for
+    /// `iter_ref((Bound::Included(start), Bound::Unbounded))` or `iter_ref(start..)`.
     ///
     /// # Example
     /// ```
@@ -1105,296 +2472,948 @@ impl Cron {
     /// use chrono::prelude::*;
     ///
     /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
-    /// let date = Utc.ymd(1970, 1, 1).and_hms(0, 0, 0);
-    /// // the given date matches the expression, so we get the same date back (truncated)
-    /// assert_eq!(cron.next_from(date), Some(date));
+    /// let start = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    /// for day in 0..3 {
+    ///     let day_start = start + chrono::Duration::days(day);
+    ///     assert_eq!(cron.iter_from_ref(day_start).next(), Some(day_start));
+    /// }
     /// ```
     #[inline]
-    pub fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
-        let start = minute_floor(start);
-        if self.any() {
-            self.find_next(start, chrono::MAX_DATETIME)
-        } else {
-            None
+    pub fn iter_from_ref(&self, start: DateTime<Utc>) -> CronTimesRefIter<'_> {
+        self.iter_ref((Bound::Included(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match with the cron value after the given date,
+    /// borrowing the cron value instead of consuming it. This is short for
+    /// `iter_ref((Bound::Excluded(start), Bound::Unbounded))`.
+    #[inline]
+    pub fn iter_after_ref(&self, start: DateTime<Utc>) -> CronTimesRefIter<'_> {
+        self.iter_ref((Bound::Excluded(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times contained in the cron value using the given start and
+    /// end range bounds, like [`iter`], but borrowing the cron value instead of consuming it.
+    ///
+    /// [`iter`]: #method.iter
+    pub fn iter_ref<R: RangeBounds<DateTime<Utc>>>(&self, bounds: R) -> CronTimesRefIter<'_> {
+        let (bounds, bounded) = self.iter_bounds(bounds);
+        CronTimesRefIter {
+            cron: self,
+            bounds,
+            bounded,
         }
     }
 
-    /// Returns the next time the cron will match after the given date.
+    /// Returns the minutes an iterator over the given range bounds starts and ends with, and
+    /// whether both bounds are set
+    fn iter_bounds<R: RangeBounds<DateTime<Utc>>>(&self, bounds: R) -> (IterBounds, bool) {
+        if !self.any() {
+            return (None, true);
+        }
+
+        let bounded = !matches!(bounds.start_bound(), Bound::Unbounded)
+            && !matches!(bounds.end_bound(), Bound::Unbounded);
+        (minute_bounds(bounds), bounded)
+    }
+
+    /// Returns the first time the cron will match within the given range bounds, or none if it
+    /// doesn't match any time in the range. Like [`iter`], the bounds are truncated to the minute.
+    /// This is the same as `iter(bounds).next()` without consuming the cron value.
+    ///
+    /// [`iter`]: #method.iter
     ///
     /// # Example
     /// ```
     /// use saffron::Cron;
     /// use chrono::prelude::*;
+    /// use chrono::Duration;
     ///
-    /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
-    /// let date = Utc.ymd(1970, 1, 1).and_hms(0, 0, 0);
-    /// assert_eq!(cron.next_after(date), date.with_minute(10));
+    /// let cron = "0 */2 * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let now = Utc.with_ymd_and_hms(1970, 1, 1, 1, 30, 0).unwrap();
+    /// // does the cron fire in the next hour?
+    /// assert_eq!(
+    ///     cron.next_in_range(now..now + Duration::hours(1)),
+    ///     Some(Utc.with_ymd_and_hms(1970, 1, 1, 2, 0, 0).unwrap())
+    /// );
+    /// assert_eq!(cron.next_in_range(now..now + Duration::minutes(30)), None);
     /// ```
-    #[inline]
-    pub fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
-        let start = next_minute(minute_floor(start))?;
-        if self.any() {
-            self.find_next(start, chrono::MAX_DATETIME)
-        } else {
-            None
+    pub fn next_in_range<R: RangeBounds<DateTime<Utc>>>(&self, bounds: R) -> Option<DateTime<Utc>> {
+        if !self.any() {
+            return None;
         }
+
+        let (start, end) = minute_bounds(bounds)?;
+        self.find_next(start, end)
     }
 
-    /// Finds the next (current inclusive) matching date time in the future within the specified
-    /// date time bound, or none if the search exceeds the bound.
-    fn find_next(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
-        if self.contains_date(start.date()) {
-            match self.find_next_time(start.time(), time_bound_for_date(start.date(), end)) {
-                Ok(Some(next_time)) => return start.date().and_time(next_time),
-                Err(OutOfBound) => return None,
-                Ok(None) => {}
-            }
+    /// Returns the last time the cron will match within the given range bounds, or none if it
+    /// doesn't match any time in the range. Like [`iter`], the bounds are truncated to the minute.
+    /// This is the same as `iter(bounds).next_back()` without consuming the cron value, and
+    /// searches backwards from the end of the range instead of through every earlier time.
+    ///
+    /// [`iter`]: #method.iter
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "0 9 * * MON-FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+    /// // March 31st 2024 is a Sunday
+    /// assert_eq!(
+    ///     cron.latest_in_range(start..end),
+    ///     Some(Utc.with_ymd_and_hms(2024, 3, 29, 9, 0, 0).unwrap())
+    /// );
+    /// ```
+    pub fn latest_in_range<R: RangeBounds<DateTime<Utc>>>(
+        &self,
+        bounds: R,
+    ) -> Option<DateTime<Utc>> {
+        if !self.any() {
+            return None;
         }
 
-        let midnight = NaiveTime::from_hms(0, 0, 0);
-        let mut search_date = start.date().succ_opt().filter(|&t| t <= end.date())?;
-        loop {
-            match self.find_next_date(search_date, end.date()) {
-                Ok(Some(next_date)) => {
-                    return match self.find_next_time(midnight, time_bound_for_date(next_date, end))
-                    {
-                        Ok(Some(next_time)) => next_date.and_time(next_time),
-                        _ => None,
-                    }
-                }
-                Err(OutOfBound) => return None,
-                Ok(None) => {
-                    search_date = Utc
-                        .ymd_opt(search_date.year() + 1, 1, 1)
-                        .single()
-                        .filter(|&date| date <= end.date())?;
-                }
-            }
+        let (start, end) = minute_bounds(bounds)?;
+        self.find_previous(start, end)
+    }
+
+    /// Returns the last time the cron matched before the given date.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let date = Utc.with_ymd_and_hms(1970, 1, 1, 1, 0, 0).unwrap();
+    /// let latest = Utc.with_ymd_and_hms(1970, 1, 1, 0, 50, 0).unwrap();
+    /// assert_eq!(cron.latest_before(date), Some(latest));
+    /// ```
+    #[inline]
+    pub fn latest_before(&self, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.latest_in_range(..end)
+    }
+
+    /// Returns the last time the cron matches in the given month (1-12) of the year, or none if
+    /// it doesn't match any time in the month or the month doesn't exist.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "30 17 * * FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let latest = Utc.with_ymd_and_hms(2024, 3, 29, 17, 30, 0).unwrap();
+    /// assert_eq!(cron.latest_in_month(2024, 3), Some(latest));
+    /// assert_eq!(cron.latest_in_month(2024, 13), None);
+    /// ```
+    pub fn latest_in_month(&self, year: i32, month: u32) -> Option<DateTime<Utc>> {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let last = first.with_day(days_in_month(first))?;
+        let end = date_time(last, minute_of_day(MINUTES_IN_DAY - 1));
+        self.latest_in_range(date_time(first, NaiveTime::MIN)..=end)
+    }
+
+    /// Returns an iterator over the windows of time the cron value is active on the given date,
+    /// in order. Each window is a `[start, end)` range of the consecutive matching minutes of the
+    /// day, so `0-29 9-10 * * *` has a window from 9:00 to 9:30 and another from 10:00 to 10:30,
+    /// and `* 9-17 * * *` has one window from 9:00 to 18:00. A window ending at the end of the day
+    /// ends at midnight of the next date, and windows are never merged across days.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "*/30,1 9-10 * * MON-FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let windows = cron
+    ///     .active_windows_for_date(NaiveDate::from_ymd_opt(2021, 3, 1).unwrap())
+    ///     .collect::<Vec<_>>();
+    /// let at = |hour, minute| Utc.with_ymd_and_hms(2021, 3, 1, hour, minute, 0).unwrap();
+    /// assert_eq!(
+    ///     windows,
+    ///     [
+    ///         at(9, 0)..at(9, 2),
+    ///         at(9, 30)..at(9, 31),
+    ///         at(10, 0)..at(10, 2),
+    ///         at(10, 30)..at(10, 31),
+    ///     ]
+    /// );
+    ///
+    /// // 2021-03-06 is a Saturday
+    /// let saturday = NaiveDate::from_ymd_opt(2021, 3, 6).unwrap();
+    /// assert_eq!(cron.active_windows_for_date(saturday).next(), None);
+    /// ```
+    pub fn active_windows_for_date(&self, date: NaiveDate) -> ActiveWindows {
+        ActiveWindows {
+            minutes: self.minutes.0,
+            hours: self.hours.0,
+            date,
+            from: if self.contains_date(date) {
+                0
+            } else {
+                MINUTES_IN_DAY
+            },
         }
     }
 
-    /// Gets the next minute (current inclusive) matching the cron expression, or none if the current
-    /// minute / no upcoming minute in the hour matches.
-    fn find_next_minute(&self, start: NaiveTime) -> Option<NaiveTime> {
-        let Minutes(map) = self.minutes;
-        let current_minute = start.minute();
-        // clear the minutes we're already past
-        let bottom_cleared = (map >> current_minute) << current_minute;
-        // count trailing zeros to find the first set. if none is set, we get back the number of
-        // bits in the integer
-        let trailing_zeros = bottom_cleared.trailing_zeros();
-        if trailing_zeros < Minutes::BITS as u32 {
-            start.with_minute(trailing_zeros)
+    /// Returns the next time the cron will match including the given date.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let date = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    /// // the given date matches the expression, so we get the same date back (truncated)
+    /// assert_eq!(cron.next_from(date), Some(date));
+    /// ```
+    #[inline]
+    pub fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = minute_floor(start);
+        if self.any() {
+            self.find_next(start, DateTime::<Utc>::MAX_UTC)
         } else {
             None
         }
     }
 
-    /// Gets the next hour (current inclusive) in the cron expression, or none if the current hour /
-    /// no upcoming hour in the day matches.
-    fn find_next_hour(&self, start: NaiveTime) -> Option<NaiveTime> {
-        let Hours(map) = self.hours;
-        let current_hour = start.hour();
-        let bottom_cleared = (map >> current_hour) << current_hour;
-        let trailing_zeros = bottom_cleared.trailing_zeros();
-        if trailing_zeros < Hours::BITS as u32 {
-            NaiveTime::from_hms_opt(trailing_zeros, 0, 0)
+    /// Returns the next time the cron will match after the given date.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let date = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(cron.next_after(date), date.with_minute(10));
+    /// ```
+    #[inline]
+    pub fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = next_minute(minute_floor(start))?;
+        if self.any() {
+            self.find_next(start, DateTime::<Utc>::MAX_UTC)
         } else {
             None
         }
     }
 
-    /// Finds the next matching time, limited inclusive by a optional bound.
-    fn find_next_time(
-        &self,
-        start: NaiveTime,
-        end: Option<NaiveTime>,
-    ) -> Result<Option<NaiveTime>, OutOfBound> {
-        if self.hours.contains_hour(start) {
-            match (self.find_next_minute(start), end) {
-                (Some(next_minute), Some(end)) if next_minute > end => return Err(OutOfBound),
-                (Some(next_minute), _) => return Ok(Some(next_minute)),
-                (None, _) => {}
-            }
+    /// Compares when this cron value and another next match including the given date, for
+    /// ordering schedules by which fires first. A value that never matches again is ordered
+    /// after any value that does, and values next matching at the same time are equal.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    /// use std::cmp::{Ordering, Reverse};
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let hourly: Cron = "0 * * * *".parse().expect("Couldn't parse expression!");
+    /// let half_past: Cron = "30 * * * *".parse().expect("Couldn't parse expression!");
+    /// let never: Cron = "0 0 30 FEB *".parse().expect("Couldn't parse expression!");
+    ///
+    /// let now = Utc.with_ymd_and_hms(2021, 1, 1, 0, 10, 0).unwrap();
+    /// assert_eq!(half_past.cmp_next(&hourly, now), Ordering::Less);
+    /// assert_eq!(never.cmp_next(&hourly, now), Ordering::Greater);
+    ///
+    /// // cron values are ordered, so a heap keyed by the next time can hold them directly
+    /// let mut heap = BinaryHeap::new();
+    /// for cron in vec![hourly, half_past, never] {
+    ///     if let Some(next) = cron.next_from(now) {
+    ///         heap.push(Reverse((next, cron)));
+    ///     }
+    /// }
+    /// let Reverse((next, _)) = heap.pop().unwrap();
+    /// assert_eq!(next, Utc.with_ymd_and_hms(2021, 1, 1, 0, 30, 0).unwrap());
+    /// ```
+    pub fn cmp_next(&self, other: &Cron, start: DateTime<Utc>) -> cmp::Ordering {
+        match (self.next_from(start), other.next_from(start)) {
+            (Some(next), Some(other)) => next.cmp(&other),
+            (Some(_), None) => cmp::Ordering::Less,
+            (None, Some(_)) => cmp::Ordering::Greater,
+            (None, None) => cmp::Ordering::Equal,
         }
+    }
 
-        let next_minute = NaiveTime::from_hms_opt(start.hour() + 1, 0, 0)
-            .and_then(|time| self.find_next_hour(time))
-            .and_then(|time| self.find_next_minute(time));
+    /// Returns the nth time (zero based) the cron will match including the given date. This is
+    /// the same as `iter_from(start).nth(n)`, but skips over whole days at a time using the
+    /// number of times matched each day instead of stepping through every matching time.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "*/10 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let date = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(cron.nth_from(date, 0), Some(date));
+    /// // six times an hour, 144 times a day
+    /// assert_eq!(cron.nth_from(date, 150), Some(Utc.with_ymd_and_hms(1970, 1, 2, 1, 0, 0).unwrap()));
+    /// ```
+    pub fn nth_from(&self, start: DateTime<Utc>, n: u64) -> Option<DateTime<Utc>> {
+        let first = self.next_from(start)?;
+        self.nth_from_match(first, n)
+    }
 
-        match (next_minute, end) {
-            (Some(next_minute), Some(end)) if next_minute > end => Err(OutOfBound),
-            (Some(next_minute), _) => Ok(Some(next_minute)),
-            (None, _) => Ok(None),
+    /// Returns the nth time (zero based) the cron will match after the given date. This is the
+    /// same as `iter_after(start).nth(n)`.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "0 12 * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let date = Utc.with_ymd_and_hms(1970, 1, 1, 12, 0, 0).unwrap();
+    /// let nth = Utc.with_ymd_and_hms(1970, 4, 11, 12, 0, 0).unwrap();
+    /// assert_eq!(cron.nth_after(date, 99), Some(nth));
+    /// ```
+    pub fn nth_after(&self, start: DateTime<Utc>, n: u64) -> Option<DateTime<Utc>> {
+        let first = self.next_after(start)?;
+        self.nth_from_match(first, n)
+    }
+
+    /// Returns the next `n` times the cron will match including the given date, or fewer if it
+    /// stops matching. This is the same as `iter_from(start).take(n).collect()` without
+    /// consuming or cloning the cron value.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "0 */6 * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let date = Utc.with_ymd_and_hms(1970, 1, 1, 1, 0, 0).unwrap();
+    /// assert_eq!(
+    ///     cron.next_n_from(date, 3),
+    ///     [
+    ///         Utc.with_ymd_and_hms(1970, 1, 1, 6, 0, 0).unwrap(),
+    ///         Utc.with_ymd_and_hms(1970, 1, 1, 12, 0, 0).unwrap(),
+    ///         Utc.with_ymd_and_hms(1970, 1, 1, 18, 0, 0).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn next_n_from(&self, start: DateTime<Utc>, n: usize) -> Vec<DateTime<Utc>> {
+        let mut times = Vec::with_capacity(cmp::min(n, 64));
+        let mut next = if n != 0 { self.next_from(start) } else { None };
+        while let Some(time) = next {
+            times.push(time);
+            next = if times.len() < n {
+                self.next_after(time)
+            } else {
+                None
+            };
         }
+        times
     }
 
-    /// Gets the next matching (current inclusive) day of the month or day of the week that
-    /// matches the cron expression. The returned matching day is a value 0-30.
-    fn find_next_day(&self, start: Date<Utc>) -> Option<Date<Utc>> {
-        match (self.dom.is_star(), self.dow.is_star()) {
-            (true, true) => Some(start),
-            (true, false) => self.find_next_weekday(start),
-            (false, true) => self.find_next_day_of_month(start),
-            (false, false) => {
-                let next_weekday = self.find_next_weekday(start);
-                let next_day = self.find_next_day_of_month(start);
-                match (next_day, next_weekday) {
-                    (Some(day), Some(weekday)) => Some(cmp::min(day, weekday)),
-                    (Some(day), None) => Some(day),
-                    (None, Some(day)) => Some(day),
-                    (None, None) => None,
-                }
-            }
+    /// Fills the slice with the next times the cron will match including the given date, and
+    /// returns the number of times written. This is less than the length of the slice if the
+    /// cron stops matching. Unlike [`next_n_from`], this doesn't allocate.
+    ///
+    /// [`next_n_from`]: #method.next_n_from
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "0 0 29 2 *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let date = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    /// let mut times = [date; 2];
+    /// assert_eq!(cron.fill_from(date, &mut times), 2);
+    /// assert_eq!(
+    ///     times,
+    ///     [
+    ///         Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap(),
+    ///         Utc.with_ymd_and_hms(2024, 2, 29, 0, 0, 0).unwrap(),
+    ///     ]
+    /// );
+    /// ```
+    pub fn fill_from(&self, start: DateTime<Utc>, times: &mut [DateTime<Utc>]) -> usize {
+        let mut filled = 0;
+        let mut next = if !times.is_empty() {
+            self.next_from(start)
+        } else {
+            None
+        };
+        while let Some(time) = next {
+            times[filled] = time;
+            filled += 1;
+            next = if filled < times.len() {
+                self.next_after(time)
+            } else {
+                None
+            };
         }
+        filled
     }
 
-    /// Gets the next matching (current inclusive) day of the month that matches the cron expression.
-    fn find_next_day_of_month(&self, start: Date<Utc>) -> Option<Date<Utc>> {
-        let days_in_month = days_in_month(start);
-        match self.dom.kind() {
-            DaysOfMonthKind::Last => match self.dom.one_value() {
-                // 'L'
-                0 => start.with_day(days_in_month),
-                // 'L-3'
-                offset => start.with_day(days_in_month.checked_sub(offset as u32)?),
-            },
-            DaysOfMonthKind::LastWeekday => match self.dom.one_value() {
-                // 'LW'
-                0 => {
-                    let next_date = start.with_day(days_in_month)?;
-                    match next_date.weekday() {
-                        Weekday::Sat => start.with_day(days_in_month - 1),
-                        Weekday::Sun => start.with_day(days_in_month - 2),
-                        _ => Some(next_date),
-                    }
-                }
-                // 'L-3W'
-                offset => {
-                    let expected_day = days_in_month.checked_sub(offset as u32)?;
-                    let next_date = start.with_day(expected_day)?;
-                    match next_date.weekday() {
-                        Weekday::Sat if expected_day == 1 => start.with_day(3),
-                        Weekday::Sat => start.with_day(expected_day - 1),
-                        Weekday::Sun => start.with_day(expected_day + 1),
-                        _ => Some(next_date),
-                    }
-                }
-            },
-            DaysOfMonthKind::Weekday => {
-                let expected_day = self.dom.one_value() as u32;
-                let new_date = start.with_day(expected_day)?;
-                match new_date.weekday() {
-                    Weekday::Sat if expected_day == 1 => start.with_day(3),
-                    Weekday::Sat => start.with_day(expected_day - 1),
-                    Weekday::Sun if expected_day == days_in_month => {
-                        start.with_day(days_in_month - 2)
-                    }
-                    Weekday::Sun => start.with_day(expected_day + 1),
-                    _ => Some(new_date),
-                }
-            }
-            _ => {
-                let map = self.dom.1 & DaysOfMonth::DAY_BITS;
-                let current_day = start.day0();
-                let bottom_cleared = (map >> current_day) << current_day;
-                let trailing_zeros = bottom_cleared.trailing_zeros();
-                if trailing_zeros < days_in_month {
-                    start.with_day0(trailing_zeros)
-                } else {
-                    None
-                }
+    /// Returns the most times the cron value can match in an hour, the number of minutes set.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let cron = "*/15 9-17 * * MON-FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.firings_per_hour(), 4);
+    /// assert_eq!(cron.firings_per_day(), 36);
+    /// // 23 weekdays in a month of 31 days starting on a Monday
+    /// assert_eq!(cron.firings_per_month(), 23 * 36);
+    /// ```
+    #[inline]
+    pub fn firings_per_hour(&self) -> u32 {
+        self.minutes.0.count_ones()
+    }
+
+    /// Returns the most times the cron value can match in a day, the number of minutes set
+    /// in each of the hours set. Like [`firings_per_hour`](#method.firings_per_hour), this is
+    /// computed from the fields without checking that any day matches.
+    #[inline]
+    pub fn firings_per_day(&self) -> u32 {
+        self.firings_per_hour() * self.hours.0.count_ones()
+    }
+
+    /// Returns an upper bound on the times the cron value can match in a month, from the most
+    /// days the day fields can match in the longest month set. Weeks of the year aren't taken
+    /// into account, and days like `15W` or `MON#2` are counted as one day each, so some months
+    /// may match fewer times.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    ///
+    /// let cron = "0 9 1,15 * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.firings_per_month(), 2);
+    ///
+    /// // the 31st isn't in April
+    /// let cron = "0 9 31 APR *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.firings_per_month(), 0);
+    /// ```
+    pub fn firings_per_month(&self) -> u32 {
+        let days_in_month = self.months.max_days();
+        let dom = self.dom.max_days_matched(days_in_month);
+        let dow = self.dow.max_days_matched(days_in_month);
+        let days = match (dom, dow) {
+            (None, None) => u32::from(days_in_month),
+            (Some(days), None) | (None, Some(days)) => days,
+            (Some(dom), Some(dow)) if self.days_and() => dom.min(dow),
+            (Some(dom), Some(dow)) => dom + dow,
+        };
+        days.min(u32::from(days_in_month)) * self.firings_per_day()
+    }
+
+    /// Returns the number of times the cron will match from the start date (inclusive) up to the
+    /// end date (exclusive). This is the same as `iter(start..end).count()`, but counts the
+    /// times matched each day from the minute and hour fields instead of visiting every time.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "*/15 9-17 * * MON-FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let start = Utc.with_ymd_and_hms(2020, 6, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2020, 7, 1, 0, 0, 0).unwrap();
+    /// // 22 weekdays with 36 times each
+    /// assert_eq!(cron.count_between(start, end), 22 * 36);
+    /// ```
+    pub fn count_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> u64 {
+        // like the iterator bounds, both dates are truncated to the minute
+        let end = minute_floor(end);
+        let first = match self.next_from(start) {
+            Some(first) if first < end => first,
+            _ => return 0,
+        };
+
+        let end_date = end.date_naive();
+        let until = end.hour() * 60 + end.minute();
+
+        let mut count = 0;
+        let mut date = first.date_naive();
+        let mut from = first.hour() * 60 + first.minute();
+        loop {
+            if date == end_date {
+                return count + self.count_times(from, until);
             }
+
+            count += self.count_times(from, MINUTES_IN_DAY);
+            date = match date
+                .succ_opt()
+                .and_then(|next| self.find_next_matching_date(next, end_date))
+            {
+                Some(date) => date,
+                None => return count,
+            };
+            from = 0;
         }
-        .filter(|&new_day| new_day >= start)
     }
 
-    /// Gets the next matching (current inclusive) day of the week that matches the cron expression.
-    /// The returned matching day is a value 0-30.
-    fn find_next_weekday(&self, start: Date<Utc>) -> Option<Date<Utc>> {
-        let days_in_month = days_in_month(start);
-        match self.dow.kind() {
-            DaysOfWeekKind::Last => {
-                let cron_weekday = self.dow.last().unwrap().num_days_from_sunday();
-                let current_weekday = start.weekday().num_days_from_sunday();
-                // calculate an offset that can be added to the current day to get what would be a day
-                // of a week where that day is the expected weekday for the cron
-                let weekday_offset = if cron_weekday < current_weekday {
-                    // example:
-                    // current: Thursday, expected: Tuesday
-                    // 7 - (4 - 2) = 5
-                    // October 0th 2020 (Thursday) + 5 = October 5th 2020 (Tuesday)
-                    7 - (current_weekday - cron_weekday)
-                } else {
-                    // example:
-                    // expected: Thursday, current: Tuesday
-                    // (4 - 2) = 2
-                    // October 5th 2020 (Tuesday) + 2 = October 7th 2020 (Thursday)
-                    cron_weekday - current_weekday
-                };
-                // the remainder of 7 can be used with day0 to determine the first day0 of the
-                // current day of the week in the month. it doesn't matter if this calculation
-                // overflows the date out of the month (31st + 5 = 36th) since we're just looking
-                // for the first day.
-                let first_week_day = (start.day0() + weekday_offset) % 7;
-                // using that we can find the last day this weekday occurs in the month
-                let last_day = match (days_in_month, first_week_day) {
-                    // special 5 week weekday handling
-                    (29, day @ 0)
-                    | (30, day @ 0)
-                    | (30, day @ 1)
-                    | (31, day @ 0)
-                    | (31, day @ 1)
-                    | (31, day @ 2) => day + (7 * 4),
-                    (_, day) => day + (7 * 3),
-                };
+    /// Returns the number of times matched and the shortest, longest, and average gap between
+    /// consecutive times the cron will match in the window, or none if it matches less than
+    /// twice. Like [`count_between`], the times are counted per day instead of visited one by
+    /// one, so a representative window such as a year can be used to check how often a cron
+    /// value fires.
+    ///
+    /// [`count_between`]: #method.count_between
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    /// use chrono::Duration;
+    ///
+    /// let cron = "0 9,17 * * MON-FRI".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+    /// let stats = cron.interval_stats(start..end).unwrap();
+    /// assert_eq!(stats.count, 2 * 261);
+    /// assert_eq!(stats.min, Duration::hours(8));
+    /// // from friday evening to monday morning
+    /// assert_eq!(stats.max, Duration::hours(64));
+    /// ```
+    pub fn interval_stats(&self, window: Range<DateTime<Utc>>) -> Option<IntervalStats> {
+        // like the iterator bounds, both dates are truncated to the minute
+        let end = minute_floor(window.end);
+        let first = self.next_from(window.start).filter(|&first| first < end)?;
+
+        let end_date = end.date_naive();
+        let until = end.hour() * 60 + end.minute();
+        let full_day = self.day_times(0, MINUTES_IN_DAY);
+
+        let mut count = 0;
+        let mut gaps: Option<(Duration, Duration)> = None;
+        let mut add_gap = |gap: Duration| {
+            gaps = Some(match gaps {
+                Some((min, max)) => (cmp::min(min, gap), cmp::max(max, gap)),
+                None => (gap, gap),
+            })
+        };
+
+        let mut last = first;
+        let mut date = first.date_naive();
+        let mut from = first.hour() * 60 + first.minute();
+        loop {
+            let day = if date == end_date {
+                self.day_times(from, until)
+            } else if from == 0 {
+                full_day
+            } else {
+                self.day_times(from, MINUTES_IN_DAY)
+            };
 
-                start.with_day0(last_day)
+            if let Some(day) = day {
+                if count != 0 {
+                    add_gap(day.first_time(date) - last);
+                }
+                if let Some((min, max)) = day.gaps {
+                    add_gap(Duration::minutes(min.into()));
+                    add_gap(Duration::minutes(max.into()));
+                }
+                count += day.count;
+                last = day.last_time(date);
             }
-            DaysOfWeekKind::Nth => {
-                let (nth, day) = self.dow.nth().unwrap();
-                let cron_weekday = day.num_days_from_sunday();
-                let current_weekday = start.weekday().num_days_from_sunday();
-                let weekday_offset = if cron_weekday < current_weekday {
-                    7 - (current_weekday - cron_weekday)
-                } else {
-                    cron_weekday - current_weekday
-                };
-                let first_week_day = (start.day0() + weekday_offset) % 7;
-                let nth_day = first_week_day + (7 * (nth - 1) as u32);
-                start.with_day0(nth_day)
+
+            if date == end_date {
+                break;
             }
-            DaysOfWeekKind::Pattern => {
-                let current_weekday = start.weekday().num_days_from_sunday();
-                let map = self.dow.1 & DaysOfWeek::DAY_BITS;
-                let bottom_cleared = (map >> current_weekday) << current_weekday;
-                let trailing_zeros = bottom_cleared.trailing_zeros();
-                let next_day = if trailing_zeros < DaysOfWeek::BITS as u32 {
-                    // if there's another day in this week in the pattern, just add the number of
-                    // days required to reach it
-                    start.day0() + (trailing_zeros - current_weekday)
-                } else {
-                    // otherwise, find the first matching day in the pattern and go to the next week
-                    let next_week = map.trailing_zeros();
-                    let remaining_days = (6 - current_weekday) + 1;
-                    start.day0() + remaining_days + next_week
-                };
-                start.with_day0(next_day)
+            date = match date
+                .succ_opt()
+                .and_then(|next| self.find_next_matching_date(next, end_date))
+            {
+                Some(date) => date,
+                None => break,
+            };
+            from = 0;
+        }
+
+        let (min, max) = gaps?;
+        let mean = (last - first).num_milliseconds() / (count as i64 - 1);
+        Some(IntervalStats {
+            count,
+            min,
+            max,
+            mean: Duration::milliseconds(mean),
+        })
+    }
+
+    /// Returns whether two consecutive times the cron will match can ever be less than the
+    /// interval apart. The shortest gap in a day is found from the minute and hour fields,
+    /// and the shortest gap between days by searching the matching days in one 400 year cycle
+    /// of the calendar, after which matching days repeat.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::Duration;
+    ///
+    /// let cron = "*/5 * * * *".parse::<Cron>().expect("Couldn't parse expression!");
+    /// assert!(cron.fires_more_often_than(Duration::minutes(10)));
+    /// assert!(!cron.fires_more_often_than(Duration::minutes(5)));
+    ///
+    /// // the last friday of a month can be the day before the first of the next month
+    /// let cron = "0 0 1 * FRIL".parse::<Cron>().expect("Couldn't parse expression!");
+    /// assert!(cron.fires_more_often_than(Duration::days(2)));
+    /// assert!(!cron.fires_more_often_than(Duration::days(1)));
+    /// ```
+    pub fn fires_more_often_than(&self, interval: Duration) -> bool {
+        let day = match self.day_times(0, MINUTES_IN_DAY) {
+            Some(day) if self.any() => day,
+            _ => return false,
+        };
+
+        let interval = interval.num_seconds();
+        if matches!(day.gaps, Some((min, _)) if i64::from(min) * 60 < interval) {
+            return true;
+        }
+
+        // the gap from the last time of a day to the first time of a matching day `n` days
+        // later is shorter than the interval for every `n` up to this
+        let overnight = i64::from(MINUTES_IN_DAY - day.last + day.first) * 60;
+        if overnight >= interval {
+            return false;
+        }
+        let max_days = (interval - overnight - 1) / (i64::from(MINUTES_IN_DAY) * 60) + 1;
+
+        let cycle_start = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid date");
+        let cycle_end = NaiveDate::from_ymd_opt(2400, 1, 1).expect("valid date");
+        let end = DateTime::<Utc>::MAX_UTC.date_naive();
+        let mut previous = match self.find_next_matching_date(cycle_start, end) {
+            Some(date) => date,
+            None => return false,
+        };
+        while previous < cycle_end {
+            let next = match previous
+                .succ_opt()
+                .and_then(|next| self.find_next_matching_date(next, end))
+            {
+                Some(next) => next,
+                None => return false,
+            };
+            if (next - previous).num_days() <= max_days {
+                return true;
+            }
+            previous = next;
+        }
+        false
+    }
+
+    /// Returns the times of the day matching the cron value in the range of minutes of the day
+    /// `from..until`, or none if no times match.
+    fn day_times(&self, from: u32, until: u32) -> Option<DayTimes> {
+        let mut times = self.hours().flat_map(|hour| {
+            FieldValues::new(self.minutes_in_hour(hour, from, until), 0)
+                .map(move |minute| u32::from(hour) * 60 + u32::from(minute))
+        });
+
+        let first = times.next()?;
+        let mut day = DayTimes {
+            count: 1,
+            first,
+            last: first,
+            gaps: None,
+        };
+        for time in times {
+            let gap = time - day.last;
+            day.gaps = Some(match day.gaps {
+                Some((min, max)) => (cmp::min(min, gap), cmp::max(max, gap)),
+                None => (gap, gap),
+            });
+            day.count += 1;
+            day.last = time;
+        }
+        Some(day)
+    }
+
+    /// Returns the nth time (zero based) the cron will match starting from the given matching
+    /// time.
+    fn nth_from_match(&self, first: DateTime<Utc>, mut n: u64) -> Option<DateTime<Utc>> {
+        let mut date = first.date_naive();
+        let mut from = first.hour() * 60 + first.minute();
+        loop {
+            let count = self.count_times(from, MINUTES_IN_DAY);
+            if n < count {
+                return Some(date_time(date, self.nth_time(from, n)));
+            }
+
+            n -= count;
+            date = self
+                .find_next_matching_date(date.succ_opt()?, DateTime::<Utc>::MAX_UTC.date_naive())?;
+            from = 0;
+        }
+    }
+
+    /// Returns the minutes of the given hour matching the cron value in the range of minutes of
+    /// the day `from..until`.
+    fn minutes_in_hour(&self, hour: u8, from: u32, until: u32) -> u64 {
+        let start = u32::from(hour) * 60;
+        if start + 60 <= from || start >= until {
+            return 0;
+        }
+
+        let mut minutes = self.minutes.0;
+        if from > start {
+            minutes &= !0 << (from - start);
+        }
+        if until < start + 60 {
+            minutes &= (1 << (until - start)) - 1;
+        }
+        minutes
+    }
+
+    /// Counts the times of the day matching the cron value in the range of minutes of the day
+    /// `from..until`.
+    fn count_times(&self, from: u32, until: u32) -> u64 {
+        self.hours()
+            .map(|hour| u64::from(self.minutes_in_hour(hour, from, until).count_ones()))
+            .sum()
+    }
+
+    /// Returns the nth time of the day (zero based) matching the cron value, starting from the
+    /// given minute of the day. The time must exist.
+    fn nth_time(&self, from: u32, mut n: u64) -> NaiveTime {
+        for hour in self.hours() {
+            let mut minutes = self.minutes_in_hour(hour, from, MINUTES_IN_DAY);
+            let count = u64::from(minutes.count_ones());
+            if n < count {
+                // clear the lowest set bits until the nth set bit is the lowest
+                for _ in 0..n {
+                    minutes &= minutes - 1;
+                }
+                return NaiveTime::from_hms_opt(hour.into(), minutes.trailing_zeros(), 0)
+                    .expect("valid time");
+            }
+            n -= count;
+        }
+        unreachable!("The nth time should be in the day")
+    }
+
+    /// Finds the next (current inclusive) date matching the cron value within the specified
+    /// date bound, or none if the search exceeds the bound.
+    fn find_next_matching_date(&self, start: NaiveDate, end: NaiveDate) -> Option<NaiveDate> {
+        let mut search_date = start;
+        loop {
+            match self.find_next_date(search_date, end) {
+                Ok(Some(date)) => return Some(date),
+                Err(OutOfBound) => return None,
+                Ok(None) => {
+                    search_date = NaiveDate::from_ymd_opt(search_date.year() + 1, 1, 1)
+                        .filter(|&date| date <= end)?;
+                }
+            }
+        }
+    }
+
+    /// Returns the first time in the bounds of an iterator and moves the start of the bounds
+    /// past it, or clears the bounds if there isn't one.
+    fn next_in_bounds(&self, bounds: &mut IterBounds) -> Option<DateTime<Utc>> {
+        if let Some((start, end)) = *bounds {
+            if let Some(next) = self.find_next(start, end) {
+                *bounds = next_minute(next).map(|new_start| (new_start, end));
+                return Some(next);
+            }
+
+            *bounds = None;
+        }
+
+        None
+    }
+
+    /// Returns the last time in the bounds of an iterator and moves the end of the bounds
+    /// before it, or clears the bounds if there isn't one.
+    fn previous_in_bounds(&self, bounds: &mut IterBounds) -> Option<DateTime<Utc>> {
+        if let Some((start, end)) = *bounds {
+            if let Some(previous) = self.find_previous(start, end) {
+                *bounds = previous_minute(previous).map(|new_end| (start, new_end));
+                return Some(previous);
+            }
+
+            *bounds = None;
+        }
+
+        None
+    }
+
+    /// Returns the size hint of an iterator over the times in the given bounds.
+    fn times_size_hint(&self, bounds: IterBounds, bounded: bool) -> (usize, Option<usize>) {
+        let (start, end) = match bounds {
+            Some(bounds) => bounds,
+            None => return (0, Some(0)),
+        };
+
+        if bounded {
+            let count = match next_minute(end) {
+                Some(after_end) => self.count_between(start, after_end),
+                None => self.count_between(start, end) + u64::from(self.contains(end)),
+            };
+            match usize::try_from(count) {
+                Ok(count) => (count, Some(count)),
+                Err(_) => (usize::MAX, None),
+            }
+        } else {
+            let days = (end.date_naive() - start.date_naive()).num_days() as u64 + 1;
+            let upper = days
+                .checked_mul(self.count_times(0, MINUTES_IN_DAY))
+                .and_then(|upper| usize::try_from(upper).ok());
+            (0, upper)
+        }
+    }
+
+    /// Finds the next (current inclusive) matching date time in the future within the specified
+    /// date time bound, or none if the search exceeds the bound.
+    fn find_next(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if start > end {
+            return None;
+        }
+
+        if self.contains_date(start.date_naive()) {
+            match self.find_next_time(start.time(), time_bound_for_date(start.date_naive(), end)) {
+                Ok(Some(next_time)) => return Some(date_time(start.date_naive(), next_time)),
+                Err(OutOfBound) => return None,
+                Ok(None) => {}
+            }
+        }
+
+        let mut search_date = start
+            .date_naive()
+            .succ_opt()
+            .filter(|&t| t <= end.date_naive())?;
+        loop {
+            match self.find_next_date(search_date, end.date_naive()) {
+                Ok(Some(next_date)) => {
+                    let bound = time_bound_for_date(next_date, end);
+                    return match self.find_next_time(NaiveTime::MIN, bound) {
+                        Ok(Some(next_time)) => Some(date_time(next_date, next_time)),
+                        _ => None,
+                    };
+                }
+                Err(OutOfBound) => return None,
+                Ok(None) => {
+                    let year = self.next_possible_year(search_date.year() + 1)?;
+                    search_date = NaiveDate::from_ymd_opt(year, 1, 1)
+                        .filter(|&date| date <= end.date_naive())?;
+                }
+            }
+        }
+    }
+
+    /// Gets the next minute (current inclusive) matching the cron expression, or none if the current
+    /// minute / no upcoming minute in the hour matches.
+    fn find_next_minute(&self, start: NaiveTime) -> Option<NaiveTime> {
+        let Minutes(map) = self.minutes;
+        let current_minute = start.minute();
+        // clear the minutes we're already past
+        let bottom_cleared = (map >> current_minute) << current_minute;
+        // count trailing zeros to find the first set. if none is set, we get back the number of
+        // bits in the integer
+        let trailing_zeros = bottom_cleared.trailing_zeros();
+        if trailing_zeros < Minutes::BITS as u32 {
+            start.with_minute(trailing_zeros)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the next hour (current inclusive) in the cron expression, or none if the current hour /
+    /// no upcoming hour in the day matches.
+    fn find_next_hour(&self, start: NaiveTime) -> Option<NaiveTime> {
+        let Hours(map) = self.hours;
+        let current_hour = start.hour();
+        let bottom_cleared = (map >> current_hour) << current_hour;
+        let trailing_zeros = bottom_cleared.trailing_zeros();
+        if trailing_zeros < Hours::BITS as u32 {
+            NaiveTime::from_hms_opt(trailing_zeros, 0, 0)
+        } else {
+            None
+        }
+    }
+
+    /// Finds the next matching time, limited inclusive by a optional bound.
+    fn find_next_time(
+        &self,
+        start: NaiveTime,
+        end: Option<NaiveTime>,
+    ) -> Result<Option<NaiveTime>, OutOfBound> {
+        if self.hours.contains_hour(start) {
+            match (self.find_next_minute(start), end) {
+                (Some(next_minute), Some(end)) if next_minute > end => return Err(OutOfBound),
+                (Some(next_minute), _) => return Ok(Some(next_minute)),
+                (None, _) => {}
+            }
+        }
+
+        let next_minute = NaiveTime::from_hms_opt(start.hour() + 1, 0, 0)
+            .and_then(|time| self.find_next_hour(time))
+            .and_then(|time| self.find_next_minute(time));
+
+        match (next_minute, end) {
+            (Some(next_minute), Some(end)) if next_minute > end => Err(OutOfBound),
+            (Some(next_minute), _) => Ok(Some(next_minute)),
+            (None, _) => Ok(None),
+        }
+    }
+
+    /// Gets the next matching (current inclusive) day in the month of the given date.
+    fn find_next_day(&self, start: NaiveDate) -> Option<NaiveDate> {
+        let mut date = start;
+        loop {
+            let day = self.find_next_day_in_any_week(date)?;
+            if self.weeks.contains_week(day) {
+                return Some(day);
+            }
+            date = day
+                .succ_opt()
+                .filter(|next| next.month() == start.month())?;
+        }
+    }
+
+    /// Gets the next matching (current inclusive) day of the month or day of the week that
+    /// matches the cron expression, ignoring the weeks of the year.
+    fn find_next_day_in_any_week(&self, start: NaiveDate) -> Option<NaiveDate> {
+        match (self.dom.is_star(), self.dow.is_star()) {
+            (true, true) => Some(start),
+            (true, false) => self.dow.find_next_day(start),
+            (false, true) => self.dom.find_next_day(start),
+            (false, false) if self.days_and() => {
+                let mut date = start;
+                loop {
+                    let day = self.dom.find_next_day(date)?;
+                    if self.dow.contains_date(day) {
+                        return Some(day);
+                    }
+                    date = day
+                        .succ_opt()
+                        .filter(|next| next.month() == start.month())?;
+                }
+            }
+            (false, false) => {
+                let next_weekday = self.dow.find_next_day(start);
+                let next_day = self.dom.find_next_day(start);
+                match (next_day, next_weekday) {
+                    (Some(day), Some(weekday)) => Some(cmp::min(day, weekday)),
+                    (Some(day), None) => Some(day),
+                    (None, Some(day)) => Some(day),
+                    (None, None) => None,
+                }
             }
-            _ => Some(start),
         }
-        .filter(|&new_day| new_day >= start)
     }
 
     /// Gets the start of the next matching (current inclusive) month that matches the cron
     /// expression.
-    fn find_next_month(&self, start: Date<Utc>) -> Option<Date<Utc>> {
+    fn find_next_month(&self, start: NaiveDate) -> Option<NaiveDate> {
         let Months(map) = self.months;
         let current_month = start.month0();
         let bottom_cleared = (map >> current_month) << current_month;
         let trailing_zeros = bottom_cleared.trailing_zeros();
         if trailing_zeros < Months::BITS as u32 {
-            Utc.ymd_opt(start.year(), trailing_zeros + 1, 1).single()
+            NaiveDate::from_ymd_opt(start.year(), trailing_zeros + 1, 1)
         } else {
             None
         }
@@ -1402,9 +3421,9 @@ impl Cron {
 
     fn find_next_date(
         &self,
-        mut start: Date<Utc>,
-        end: Date<Utc>,
-    ) -> Result<Option<Date<Utc>>, OutOfBound> {
+        mut start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Option<NaiveDate>, OutOfBound> {
         if self.months.contains_month(start) {
             match self.find_next_day(start) {
                 Some(next_day) if next_day > end => return Err(OutOfBound),
@@ -1433,42 +3452,283 @@ impl Cron {
             }
         }
     }
-}
 
-struct OutOfBound;
+    /// Finds the previous (current inclusive) matching date time in the past within the specified
+    /// date time bound, or none if the search passes the bound.
+    fn find_previous(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if start > end {
+            return None;
+        }
 
-#[inline]
-fn minute_floor(dt: DateTime<Utc>) -> DateTime<Utc> {
-    dt.with_second(0)
-        .expect("zero is a valid second value")
-        .with_nanosecond(0)
-        .expect("zero is a valid nanosecond value")
-}
+        if self.contains_date(end.date_naive()) {
+            if let Some(time) = self.find_previous_time(end.time()) {
+                return Some(date_time(end.date_naive(), time)).filter(|&time| time >= start);
+            }
+        }
 
-#[inline]
-fn previous_minute(dt: DateTime<Utc>) -> Option<DateTime<Utc>> {
-    dt.checked_sub_signed(Duration::minutes(1))
-}
+        let search_date = end
+            .date_naive()
+            .pred_opt()
+            .filter(|&d| d >= start.date_naive())?;
+        let date = self.find_previous_date(search_date, start.date_naive())?;
+        let time = self.find_previous_time(minute_of_day(MINUTES_IN_DAY - 1))?;
+        Some(date_time(date, time)).filter(|&time| time >= start)
+    }
 
-#[inline]
-fn next_minute(dt: DateTime<Utc>) -> Option<DateTime<Utc>> {
-    dt.checked_add_signed(Duration::minutes(1))
+    /// Finds the previous (current inclusive) matching time of the day, or none if no earlier time
+    /// in the day matches.
+    fn find_previous_time(&self, end: NaiveTime) -> Option<NaiveTime> {
+        let Minutes(minutes) = self.minutes;
+        let Hours(hours) = self.hours;
+        if self.hours.contains_hour(end) {
+            // clear the minutes after the end
+            let top_cleared = minutes & ((2 << end.minute()) - 1);
+            if top_cleared != 0 {
+                let minute = 63 - top_cleared.leading_zeros();
+                return end.with_minute(minute);
+            }
+        }
+
+        let top_cleared = hours & ((1 << end.hour()) - 1);
+        if top_cleared == 0 {
+            return None;
+        }
+
+        let hour = 31 - top_cleared.leading_zeros();
+        let minute = 63 - minutes.leading_zeros();
+        NaiveTime::from_hms_opt(hour, minute, 0)
+    }
+
+    /// Finds the previous (current inclusive) matching date no earlier than the start date.
+    fn find_previous_date(&self, end: NaiveDate, start: NaiveDate) -> Option<NaiveDate> {
+        let mut search_date = end;
+        loop {
+            if self.months.contains_month(search_date) {
+                if let Some(date) = self.find_previous_day(search_date) {
+                    return Some(date).filter(|&date| date >= start);
+                }
+            }
+
+            search_date = self
+                .find_previous_month(search_date)
+                .filter(|&date| date >= start)?;
+        }
+    }
+
+    /// Gets the previous matching (current inclusive) day in the month of the given date.
+    fn find_previous_day(&self, end: NaiveDate) -> Option<NaiveDate> {
+        // days in some weeks of the year are checked one by one
+        let any_week = self.weeks.is_all();
+        match (self.dom.kind(), self.dow.kind()) {
+            (DaysOfMonthKind::Star, DaysOfWeekKind::Star) if any_week => Some(end),
+            (DaysOfMonthKind::Pattern, DaysOfWeekKind::Star) if any_week => {
+                let map = self.dom.pattern_in_month(end);
+                let top_cleared = map & ((2 << end.day0()) - 1);
+                if top_cleared == 0 {
+                    return None;
+                }
+                end.with_day0(31 - top_cleared.leading_zeros())
+            }
+            (DaysOfMonthKind::Star, DaysOfWeekKind::Pattern) if any_week => {
+                let map = u32::from(self.dow.1 & DaysOfWeek::DAY_BITS);
+                let current_weekday = end.weekday().num_days_from_sunday();
+                let top_cleared = map & ((2 << current_weekday) - 1);
+                let days_back = if top_cleared != 0 {
+                    // go back to the latest day in the pattern earlier in this week
+                    current_weekday - (31 - top_cleared.leading_zeros())
+                } else {
+                    // otherwise, go back to the last day in the pattern in the previous week
+                    current_weekday + 7 - (31 - map.leading_zeros())
+                };
+                end.day0()
+                    .checked_sub(days_back)
+                    .and_then(|day0| end.with_day0(day0))
+            }
+            _ => {
+                let mut date = end;
+                while !self.contains_date(date) {
+                    date = date.pred_opt().filter(|d| d.month() == end.month())?;
+                }
+                Some(date)
+            }
+        }
+    }
+
+    /// Returns whether the cron only matches the 29th of February, so it can only match in leap
+    /// years.
+    fn leap_years_only(&self) -> bool {
+        const FEBRUARY: u16 = 1 << 1;
+        const DAYS_1_TO_28: u32 = (1 << 28) - 1;
+        self.months.0 == FEBRUARY
+            && self.dom.kind() == DaysOfMonthKind::Pattern
+            && self.dom.1 & (DAYS_1_TO_28 | DaysOfMonth::FEB_29_LAST) == 0
+            && (self.dow.is_star() || self.days_and())
+    }
+
+    /// Returns the first year from the given year (inclusive) the cron can match in. Crons only
+    /// matching the 29th of February skip straight to the next leap year instead of searching
+    /// every year in between.
+    fn next_possible_year(&self, year: i32) -> Option<i32> {
+        if !self.leap_years_only() {
+            return Some(year);
+        }
+
+        // there's a leap year at least every 8 years
+        (year..year.checked_add(8)?).find(|&year| is_leap_year(year))
+    }
+
+    /// Returns the last year up to the given year (inclusive) the cron can match in, like
+    /// [`next_possible_year`].
+    ///
+    /// [`next_possible_year`]: #method.next_possible_year
+    fn previous_possible_year(&self, year: i32) -> Option<i32> {
+        if !self.leap_years_only() {
+            return Some(year);
+        }
+
+        (year.checked_sub(7)?..=year)
+            .rev()
+            .find(|&year| is_leap_year(year))
+    }
+
+    /// Gets the last day of the previous matching month before the month of the given date.
+    fn find_previous_month(&self, end: NaiveDate) -> Option<NaiveDate> {
+        let Months(map) = self.months;
+        let top_cleared = map & ((1 << end.month0()) - 1);
+        let (year, month0) = if top_cleared != 0 {
+            (end.year(), 15 - top_cleared.leading_zeros())
+        } else {
+            (
+                self.previous_possible_year(end.year() - 1)?,
+                15 - map.leading_zeros(),
+            )
+        };
+
+        let first = NaiveDate::from_ymd_opt(year, month0 + 1, 1)?;
+        first.with_day(days_in_month(first))
+    }
+}
+
+struct OutOfBound;
+
+/// The days matched by a cron value, used to compare day fields written in different ways.
+#[derive(PartialEq)]
+enum EffectiveDays {
+    All,
+    DaysOfMonth(DaysOfMonth),
+    DaysOfWeek(DaysOfWeek),
+    /// Days matching either field
+    Either(DaysOfMonth, DaysOfWeek),
+    /// Days matching both fields
+    Both(DaysOfMonth, DaysOfWeek),
+}
+
+/// Writes the set bits of a mask as a comma separated list of values and ranges, adding the
+/// given offset to every bit index.
+fn fmt_bits(bits: u64, offset: u8, f: &mut Formatter) -> fmt::Result {
+    for (i, (start, end)) in BitRuns(bits).enumerate() {
+        if i != 0 {
+            f.write_str(",")?;
+        }
+
+        if start == end {
+            write!(f, "{}", start + offset)?;
+        } else {
+            write!(f, "{}-{}", start + offset, end + offset)?;
+        }
+    }
+    Ok(())
+}
+
+/// An iterator over the runs of set bits in a mask, yielding the first and last bit index of
+/// every run in ascending order.
+struct BitRuns(u64);
+
+impl Iterator for BitRuns {
+    type Item = (u8, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let start = self.0.trailing_zeros() as u8;
+        // count the ones in this run by looking for the first unset bit after the start
+        let len = (self.0 >> start).trailing_ones() as u8;
+        let end = start + len - 1;
+        self.0 &= !(((1u128 << (end + 1)) - 1) as u64);
+        Some((start, end))
+    }
+}
+
+/// Returns the date time at the given time of day on the date
+#[inline]
+fn date_time(date: NaiveDate, time: NaiveTime) -> DateTime<Utc> {
+    date.and_time(time).and_utc()
+}
+
+/// Returns the time of day the given number of minutes after midnight
+#[inline]
+fn minute_of_day(minutes: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0)
+        .expect("minutes in a day are a valid time")
+}
+
+#[inline]
+fn minute_floor(dt: DateTime<Utc>) -> DateTime<Utc> {
+    dt.with_second(0)
+        .expect("zero is a valid second value")
+        .with_nanosecond(0)
+        .expect("zero is a valid nanosecond value")
+}
+
+/// The first and last minute (inclusive) left in an iterator, or none if it's finished
+type IterBounds = Option<(DateTime<Utc>, DateTime<Utc>)>;
+
+/// Converts range bounds into the first and last minute (inclusive) in the range, or none if the
+/// range doesn't contain a minute.
+fn minute_bounds<R: RangeBounds<DateTime<Utc>>>(bounds: R) -> IterBounds {
+    let front = match bounds.start_bound() {
+        Bound::Unbounded => Some(DateTime::<Utc>::MIN_UTC),
+        Bound::Included(start) => Some(*start),
+        Bound::Excluded(start) => next_minute(*start),
+    }
+    .map(minute_floor);
+
+    let back = match bounds.end_bound() {
+        Bound::Unbounded => Some(DateTime::<Utc>::MAX_UTC),
+        Bound::Included(end) => Some(*end),
+        Bound::Excluded(end) => previous_minute(*end),
+    }
+    .map(minute_floor);
+
+    front.zip(back).filter(|(front, back)| front <= back)
+}
+
+#[inline]
+fn previous_minute(dt: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    dt.checked_sub_signed(Duration::minutes(1))
+}
+
+#[inline]
+fn next_minute(dt: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    dt.checked_add_signed(Duration::minutes(1))
 }
 
 /// Gets the next month in the year if one exists.
 #[inline]
-fn next_month_in_year(d: Date<Utc>) -> Option<Date<Utc>> {
+fn next_month_in_year(d: NaiveDate) -> Option<NaiveDate> {
     let month = d.month();
     if month <= 11 {
-        Utc.ymd_opt(d.year(), month + 1, 1).single()
+        NaiveDate::from_ymd_opt(d.year(), month + 1, 1)
     } else {
         None
     }
 }
 
 #[inline]
-fn time_bound_for_date(d: Date<Utc>, end: DateTime<Utc>) -> Option<NaiveTime> {
-    if d == end.date() {
+fn time_bound_for_date(d: NaiveDate, end: DateTime<Utc>) -> Option<NaiveTime> {
+    if d == end.date_naive() {
         Some(end.time())
     } else {
         None
@@ -1481,425 +3741,2268 @@ fn time_bound_for_date(d: Date<Utc>, end: DateTime<Utc>) -> Option<NaiveTime> {
 /// [`Cron::iter`]: struct.Cron.html#method.iter
 /// [`Cron::iter_from`]: struct.Cron.html#method.iter_from
 /// [`Cron::iter_after`]: struct.Cron.html#method.iter_after
+#[derive(Clone)]
 pub struct CronTimesIter {
     cron: Cron,
     bounds: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Whether the iterator was created with a start and end bound, so the times left can be
+    /// counted in a reasonable amount of time
+    bounded: bool,
+}
+
+impl CronTimesIter {
+    /// Returns the underlying cron value.
+    pub fn cron(&self) -> &Cron {
+        &self.cron
+    }
+}
+
+impl Iterator for CronTimesIter {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cron.next_in_bounds(&mut self.bounds)
+    }
+
+    /// Returns the exact number of times left if the iterator was created with a start and end
+    /// bound. These are counted per day like [`Cron::count_between`], so this takes time
+    /// proportional to the number of days left that match. Otherwise the upper bound is the
+    /// number of times matched each day times the number of days left.
+    ///
+    /// [`Cron::count_between`]: struct.Cron.html#method.count_between
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cron.times_size_hint(self.bounds, self.bounded)
+    }
+}
+
+impl DoubleEndedIterator for CronTimesIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cron.previous_in_bounds(&mut self.bounds)
+    }
+}
+
+impl FusedIterator for CronTimesIter {}
+
+/// An iterator over the times matching a borrowed cron value. Created with [`Cron::iter_ref`],
+/// [`Cron::iter_from_ref`], and [`Cron::iter_after_ref`].
+///
+/// [`Cron::iter_ref`]: struct.Cron.html#method.iter_ref
+/// [`Cron::iter_from_ref`]: struct.Cron.html#method.iter_from_ref
+/// [`Cron::iter_after_ref`]: struct.Cron.html#method.iter_after_ref
+#[derive(Clone)]
+pub struct CronTimesRefIter<'a> {
+    cron: &'a Cron,
+    bounds: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    bounded: bool,
+}
+
+impl<'a> CronTimesRefIter<'a> {
+    /// Returns the underlying cron value.
+    pub fn cron(&self) -> &'a Cron {
+        self.cron
+    }
+}
+
+impl Iterator for CronTimesRefIter<'_> {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cron.next_in_bounds(&mut self.bounds)
+    }
+
+    /// Returns the same hint as [`CronTimesIter`].
+    ///
+    /// [`CronTimesIter`]: struct.CronTimesIter.html
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cron.times_size_hint(self.bounds, self.bounded)
+    }
+}
+
+impl DoubleEndedIterator for CronTimesRefIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cron.previous_in_bounds(&mut self.bounds)
+    }
+}
+
+impl FusedIterator for CronTimesRefIter<'_> {}
+
+/// An iterator over the windows of time a cron value is active on a date. Created with
+/// [`Cron::active_windows_for_date`].
+///
+/// [`Cron::active_windows_for_date`]: struct.Cron.html#method.active_windows_for_date
+#[derive(Debug, Clone)]
+pub struct ActiveWindows {
+    minutes: u64,
+    hours: u32,
+    date: NaiveDate,
+    /// The minute of the day to search for the next window from
+    from: u32,
+}
+
+impl ActiveWindows {
+    /// Returns the first minute of the day from the given minute that is active, or inactive if
+    /// `active` is false. Returns the end of the day if there isn't one.
+    fn first_minute(&self, from: u32, active: bool) -> u32 {
+        let mut hour = from / 60;
+        let mut minute = from % 60;
+        while hour < 24 {
+            let mut minutes = if self.hours & (1 << hour) != 0 {
+                self.minutes
+            } else {
+                0
+            };
+            if !active {
+                minutes = !minutes & Minutes::ALL;
+            }
+            minutes &= !0 << minute;
+            if minutes != 0 {
+                return hour * 60 + minutes.trailing_zeros();
+            }
+            hour += 1;
+            minute = 0;
+        }
+        MINUTES_IN_DAY
+    }
+}
+
+impl Iterator for ActiveWindows {
+    type Item = Range<DateTime<Utc>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.first_minute(self.from, true);
+        if start == MINUTES_IN_DAY {
+            self.from = MINUTES_IN_DAY;
+            return None;
+        }
+        let end = self.first_minute(start, false);
+        self.from = end;
+
+        let midnight = date_time(self.date, NaiveTime::MIN);
+        let start = midnight + Duration::minutes(start.into());
+        let end = midnight.checked_add_signed(Duration::minutes(end.into()));
+        // the last window of the last date ends after the last time there is
+        Some(start..end.unwrap_or(DateTime::<Utc>::MAX_UTC))
+    }
+}
+
+impl FusedIterator for ActiveWindows {}
+
+/// An iterator over the values set in a field of a cron value, in ascending order.
+/// Created with [`Cron::minutes`], [`Cron::hours`], [`Cron::months`], and
+/// [`Cron::days_of_month`], or from a field of an expression with [`FieldView::values`].
+///
+/// [`FieldView::values`]: parse/enum.FieldView.html#method.values
+/// [`Cron::minutes`]: struct.Cron.html#method.minutes
+/// [`Cron::hours`]: struct.Cron.html#method.hours
+/// [`Cron::months`]: struct.Cron.html#method.months
+/// [`Cron::days_of_month`]: struct.Cron.html#method.days_of_month
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct FieldValues {
+    bits: u64,
+    offset: u8,
+}
+
+impl FieldValues {
+    #[inline]
+    fn new(bits: u64, offset: u8) -> Self {
+        Self { bits, offset }
+    }
+}
+
+impl Iterator for FieldValues {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        let value = self.bits.trailing_zeros() as u8;
+        self.bits &= self.bits - 1;
+        Some(value + self.offset)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for FieldValues {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+
+        let value = 63 - self.bits.leading_zeros() as u8;
+        self.bits &= !(1 << value);
+        Some(value + self.offset)
+    }
+}
+
+impl ExactSizeIterator for FieldValues {}
+impl FusedIterator for FieldValues {}
+
+/// An iterator over the days of the week set in a cron value, starting from Sunday.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Weekdays(FieldValues);
+
+impl Iterator for Weekdays {
+    type Item = Weekday;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(DaysOfWeek::byte_to_weekday)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Weekdays {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(DaysOfWeek::byte_to_weekday)
+    }
+}
+
+impl ExactSizeIterator for Weekdays {}
+impl FusedIterator for Weekdays {}
+
+/// The days of the month matched by a cron value. Returned by [`Cron::days_of_month`].
+///
+/// [`Cron::days_of_month`]: struct.Cron.html#method.days_of_month
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum DayOfMonthSpec {
+    /// A '*', every day of the month
+    All,
+    /// A '?', the field is unused
+    Ignored,
+    /// A set of days of the month (1-31)
+    Days(FieldValues),
+    /// The last day of the month offsetted by a value, `L` or `L-3`
+    Last {
+        /// The number of days before the last day of the month
+        offset: u8,
+    },
+    /// The closest weekday to the last day of the month offsetted by a value, `LW` or `L-3W`
+    LastWeekday {
+        /// The number of days before the last day of the month
+        offset: u8,
+    },
+    /// The closest weekday to a day of the month (1-31), `15W`
+    ClosestWeekday(u8),
+    /// The closest weekdays to a set of days of the month (1-31), `1-7W`
+    ClosestWeekdays(FieldValues),
+    /// The nth business day (1-23) of the month, counting Monday to Friday, `3B`
+    BusinessDay(u8),
+    /// The last weekday of the month offsetted by a number of weekdays, `LW-1`
+    LastWeekdayOffset {
+        /// The number of weekdays before the last weekday of the month
+        weekdays: u8,
+    },
+    /// A list of the other special expressions, `1W,15W`
+    Specials(DayOfMonthSpecials),
+}
+
+/// An iterator over a list of special day of the month expressions set in a cron value, like
+/// `1W,15W`. Each item is one of the special [`DayOfMonthSpec`] variants.
+///
+/// [`DayOfMonthSpec`]: enum.DayOfMonthSpec.html
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct DayOfMonthSpecials(DomSpecials);
+
+impl Iterator for DayOfMonthSpecials {
+    type Item = DayOfMonthSpec;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_first().map(|day| day.spec())
+    }
+}
+
+impl FusedIterator for DayOfMonthSpecials {}
+
+/// The days of the week matched by a cron value. Returned by [`Cron::days_of_week`].
+///
+/// [`Cron::days_of_week`]: struct.Cron.html#method.days_of_week
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum DayOfWeekSpec {
+    /// A '*', every day of the week
+    All,
+    /// A '?', the field is unused
+    Ignored,
+    /// A set of days of the week
+    Days(Weekdays),
+    /// The last of a day of the week in the month, `5L`
+    Last(Weekday),
+    /// The nth of a day of the week in the month, `MON#2`
+    Nth(Weekday, u8),
+    /// A list of the other special expressions, `MON#1,MON#3`
+    Specials(DayOfWeekSpecials),
+}
+
+/// An iterator over a list of special day of the week expressions set in a cron value, like
+/// `MON#1,MON#3`. Each item is either a [`DayOfWeekSpec::Last`] or a [`DayOfWeekSpec::Nth`].
+///
+/// [`DayOfWeekSpec::Last`]: enum.DayOfWeekSpec.html#variant.Last
+/// [`DayOfWeekSpec::Nth`]: enum.DayOfWeekSpec.html#variant.Nth
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct DayOfWeekSpecials(DowSpecials);
+
+impl Iterator for DayOfWeekSpecials {
+    type Item = DayOfWeekSpec;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_first().map(|day| day.spec())
+    }
 }
 
-impl CronTimesIter {
-    /// Returns the underlying cron value.
-    pub fn cron(&self) -> &Cron {
-        &self.cron
+impl FusedIterator for DayOfWeekSpecials {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::ToString, vec, vec::Vec};
+
+    const FORMAT: &str = "%F %R";
+
+    fn check_does_contain(cron: &str, dates: impl IntoIterator<Item = impl AsRef<str>>) {
+        let parsed: Cron = cron.parse().unwrap();
+
+        for date in dates.into_iter().map(|s| {
+            NaiveDateTime::parse_from_str(s.as_ref(), FORMAT)
+                .map(|time| time.and_utc())
+                .expect("Failed to parse expected date")
+        }) {
+            assert!(
+                parsed.contains(date),
+                "Cron \"{}\" should contain {}. Compiled: {:#?}",
+                cron,
+                date,
+                parsed
+            );
+        }
+    }
+
+    fn check_does_not_contain(cron: &str, dates: impl IntoIterator<Item = impl AsRef<str>>) {
+        let parsed: Cron = cron.parse().unwrap();
+
+        for date in dates.into_iter().map(|s| {
+            NaiveDateTime::parse_from_str(s.as_ref(), FORMAT)
+                .map(|time| time.and_utc())
+                .expect("Failed to parse expected date")
+        }) {
+            assert!(
+                !parsed.contains(date),
+                "Cron \"{}\" shouldn't contain {}. Compiled {:#?}",
+                cron,
+                date,
+                parsed
+            );
+        }
+    }
+
+    #[test]
+    fn parse_check_anytime() {
+        check_does_contain(
+            "* * * * *",
+            &[
+                "1970-01-01 00:00",
+                "2016-11-08 23:53",
+                "2020-07-04 15:42",
+                "2072-02-29 01:15",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_specific_time() {
+        let cron = "5 0 23 8 *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-08-23 00:05",
+                "2021-08-23 00:05",
+                "2022-08-23 00:05",
+                "2023-08-23 00:05",
+            ],
+        );
+
+        check_does_not_contain(
+            cron,
+            &[
+                "1970-01-01 00:00",
+                "2016-11-08 23:53",
+                "2020-07-04 15:42",
+                "2072-02-29 01:15",
+                "2020-08-23 11:05",
+            ],
+        );
+    }
+
+    /// check to make sure we don't accidentally include any off-by-one errors with ranges
+    #[test]
+    fn parse_check_specific_time_as_ranges() {
+        let cron = "5-5 0-0 23-23 8-8 *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-08-23 00:05",
+                "2021-08-23 00:05",
+                "2022-08-23 00:05",
+                "2023-08-23 00:05",
+            ],
+        );
+
+        check_does_not_contain(
+            cron,
+            &[
+                "1970-01-01 00:00",
+                "2016-11-08 23:53",
+                "2020-07-04 15:42",
+                "2072-02-29 01:15",
+                "2020-08-23 11:05",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_overflow_time_ranges() {
+        // The 31st and 1st of January and December,
+        // at 11:00 PM, 11:59 PM, 12:00 AM, and 12:59 AM
+        let cron = "59-0 23-0 31-1 12-1 *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-01-31 00:59",
+                "2020-01-31 00:00",
+                "2020-01-31 23:59",
+                "2020-01-31 23:00",
+                "2020-01-01 00:59",
+                "2020-01-01 00:00",
+                "2020-01-01 23:59",
+                "2020-01-01 23:00",
+                "2020-12-31 00:59",
+                "2020-12-31 00:00",
+                "2020-12-31 23:59",
+                "2020-12-31 23:00",
+                "2020-12-01 00:59",
+                "2020-12-01 00:00",
+                "2020-12-01 23:59",
+                "2020-12-01 23:00",
+            ],
+        );
+
+        // Midnight on every Saturday and Sunday in January
+        let cron = "0 0 * JAN SAT-SUN";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-01-04 00:00",
+                "2020-01-05 00:00",
+                "2020-01-11 00:00",
+                "2020-01-12 00:00",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_limits() {
+        let cron = "0,59 0,23 1,31 1,12 *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-01-01 00:00",
+                "2020-01-01 00:59",
+                "2020-01-01 23:59",
+                "2020-01-31 23:59",
+                "2020-12-31 23:59",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_anytime_but_its_ranges() {
+        let cron = "0-59 0-23 1-31 1-12 *";
+
+        check_does_contain(
+            cron,
+            &[
+                "1970-01-01 00:00",
+                "2016-11-08 23:53",
+                "2020-07-04 15:42",
+                "2072-02-29 01:15",
+            ],
+        );
+
+        let cron = "0-59 0-23 * 1-12 1-7";
+
+        check_does_contain(
+            cron,
+            &[
+                "1970-01-01 00:00",
+                "2016-11-08 23:53",
+                "2020-07-04 15:42",
+                "2072-02-29 01:15",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_leap_days() {
+        let cron = "0 0 L FEB *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2400-02-29 00:00",
+                "2300-02-28 00:00",
+                "2200-02-28 00:00",
+                "2100-02-28 00:00",
+                "2024-02-29 00:00",
+                "2020-02-29 00:00",
+                "2004-02-29 00:00",
+                "2000-02-29 00:00",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_offset_leap_days() {
+        let cron = "0 0 L-1 FEB *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2400-02-28 00:00",
+                "2300-02-27 00:00",
+                "2200-02-27 00:00",
+                "2100-02-27 00:00",
+                "2024-02-28 00:00",
+                "2020-02-28 00:00",
+                "2004-02-28 00:00",
+                "2000-02-28 00:00",
+            ],
+        );
+
+        check_does_not_contain(
+            cron,
+            &[
+                "2400-02-29 00:00",
+                "2300-02-28 00:00",
+                "2200-02-28 00:00",
+                "2100-02-28 00:00",
+                "2024-02-29 00:00",
+                "2020-02-29 00:00",
+                "2004-02-29 00:00",
+                "2000-02-29 00:00",
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_offset_weekend_start_months() {
+        let cron = "0 0 L-30W * *";
+
+        check_does_contain(cron, &["2021-05-3 00:00", "2022-01-3 00:00"]);
+    }
+
+    #[test]
+    fn parse_check_offset_weekend_start_months_beyond_days() {
+        let cron = "0 0 L-28W FEB *";
+
+        check_does_not_contain(cron, &["2021-05-3 00:00", "2022-01-3 00:00"]);
+    }
+
+    #[test]
+    fn parse_check_last_weekdays() {
+        let cron = "0 0 LW MAY *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2025-05-30 00:00", // Last day is a Saturday
+                "2021-05-31 00:00", // Last day is a Monday
+                "2020-05-29 00:00", // Last day is a Sunday
+            ],
+        );
+    }
+
+    #[test]
+    fn parse_check_last_weekdays_offset() {
+        let cron = "0 0 L-1W MAY *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2025-05-30 00:00", // Offset last day is a Friday
+                "2021-05-31 00:00", // Offset last day is a Sunday
+                "2020-05-29 00:00", // Offset last day is a Saturday
+            ],
+        );
+        // Mondays early in the month
+        check_does_not_contain(cron, &["2020-05-04 00:00", "2021-05-03 00:00"]);
+    }
+
+    #[test]
+    fn parse_check_business_day() {
+        let cron = "0 0 3B * *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-05-05 00:00", // First day is a Friday
+                "2021-05-05 00:00", // First day is a Saturday
+                "2022-05-04 00:00", // First day is a Sunday
+                "2021-06-03 00:00", // First day is a Tuesday
+            ],
+        );
+        check_does_not_contain(cron, &["2020-05-03 00:00", "2021-05-03 00:00"]);
+
+        // only months of 31 days starting on a Monday, Tuesday or Wednesday have 23 business days
+        check_does_contain("0 0 23B * *", &["2021-03-31 00:00"]);
+        check_does_not_contain("0 0 23B * *", &["2021-05-31 00:00"]);
+        assert!(!"0 0 22B FEB *".parse::<Cron>().unwrap().any());
+        assert!("0 0 21B FEB *".parse::<Cron>().unwrap().any());
+    }
+
+    #[test]
+    fn parse_check_last_weekday_offset() {
+        let cron = "0 0 LW-1 * *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2021-04-29 00:00", // Last day is a Friday
+                "2021-05-28 00:00", // Last day is a Monday
+                "2021-01-28 00:00", // Last day is a Sunday
+                "2022-07-28 00:00", // Last two days are a weekend
+            ],
+        );
+        check_does_not_contain(cron, &["2021-04-30 00:00", "2021-05-31 00:00"]);
+
+        // counting back crosses weekends
+        check_does_contain("0 0 LW-4 * *", &["2021-01-25 00:00"]);
+        check_does_contain("0 0 LW-5 * *", &["2021-01-22 00:00"]);
+
+        // only leap years can have 21 weekdays in February
+        check_does_contain("0 0 LW-20 FEB *", &["2024-02-01 00:00"]);
+        assert!(!"0 0 LW-21 FEB *".parse::<Cron>().unwrap().any());
+    }
+
+    #[test]
+    fn parse_check_specials() {
+        let cron = "0 0 ? * MON#1,MON#3";
+        check_does_contain(cron, &["2021-03-01 00:00", "2021-03-15 00:00"]);
+        check_does_not_contain(cron, &["2021-03-08 00:00", "2021-03-22 00:00"]);
+
+        let parsed: Cron = cron.parse().unwrap();
+        let at = |m, d| Utc.with_ymd_and_hms(2021, m, d, 0, 0, 0).unwrap();
+        assert_eq!(parsed.next_after(at(3, 1)), Some(at(3, 15)));
+        assert_eq!(parsed.next_after(at(3, 15)), Some(at(4, 5)));
+        assert_eq!(parsed.to_string(), "0 0 ? * 2#1,2#3");
+        match parsed.days_of_week() {
+            DayOfWeekSpec::Specials(specials) => assert_eq!(
+                specials.collect::<Vec<_>>(),
+                [
+                    DayOfWeekSpec::Nth(Weekday::Mon, 1),
+                    DayOfWeekSpec::Nth(Weekday::Mon, 3)
+                ]
+            ),
+            spec => panic!("Unexpected days of the week {:?}", spec),
+        }
+
+        // 2021-05-01 and 2021-05-15 are both Saturdays
+        let cron = "0 0 15W,1W,L * ?";
+        check_does_contain(
+            cron,
+            &["2021-05-03 00:00", "2021-05-14 00:00", "2021-05-31 00:00"],
+        );
+        check_does_not_contain(cron, &["2021-05-01 00:00", "2021-05-15 00:00"]);
+
+        let parsed: Cron = cron.parse().unwrap();
+        assert_eq!(parsed.next_after(at(5, 3)), Some(at(5, 14)));
+        assert_eq!(parsed.to_string(), "0 0 L,1W,15W * ?");
+        assert_eq!(parsed, "0 0 L,15W,1W * ?".parse().unwrap());
+        assert_eq!(Cron::new(parsed.to_expr()), parsed);
+
+        // a list of one expression is the expression on its own
+        let parsed: Cron = "0 0 1W,1W * ?".parse().unwrap();
+        assert_eq!(parsed, "0 0 1W * ?".parse().unwrap());
+        assert_eq!(parsed.days_of_month(), DayOfMonthSpec::ClosestWeekday(1));
+
+        // a list matches a month long enough for any one expression in it
+        assert!("0 0 LW-20,LW-21 FEB *".parse::<Cron>().unwrap().any());
+        assert!(!"0 0 LW-21,LW-22 FEB *".parse::<Cron>().unwrap().any());
+    }
+
+    #[test]
+    fn year_days() {
+        let extended = |cron: &str| Cron::parse_with(cron, parse::Dialect::Extended).unwrap();
+
+        let cron = extended("0 0 LY * ?");
+        assert_eq!(cron, "0 0 31 12 ?".parse().unwrap());
+        assert_eq!(
+            cron.next_after(Utc.with_ymd_and_hms(2020, 6, 1, 0, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2020, 12, 31, 0, 0, 0).unwrap())
+        );
+        assert!(extended("0 0 FY * ?").equivalent_to(&"@yearly".parse().unwrap()));
+    }
+
+    #[test]
+    fn exclusions() {
+        let extended = |cron: &str| Cron::parse_with(cron, parse::Dialect::Extended).unwrap();
+        let at = |h, m| Utc.with_ymd_and_hms(2021, 1, 4, h, m, 0).unwrap();
+
+        let cron = extended("*!15 * * * *");
+        assert!(cron.contains(at(9, 14)));
+        assert!(!cron.contains(at(9, 15)));
+        assert_eq!(cron.next_after(at(9, 14)), Some(at(9, 16)));
+        assert_eq!(cron, "0-14,16-59 * * * *".parse().unwrap());
+
+        // excluded days of a '*' restrict the day field
+        assert_eq!(extended("0 9 *!1-7 * *"), "0 9 8-31 * *".parse().unwrap());
+        assert_eq!(
+            extended("0 9 ? * *!SAT,SUN"),
+            "0 9 ? * MON-FRI".parse().unwrap()
+        );
+        assert_eq!(
+            extended("*/15!30 9-17!12 * JAN-JUN!MAR ? W/2!1"),
+            extended("0,15,45 9-11,13-17 * JAN,FEB,APR-JUN ? W3/2")
+        );
+
+        let expr =
+            CronExpr::from_str_with("*!15 9-17!12 * * MON-FRI", parse::Dialect::Extended).unwrap();
+        assert_eq!(Cron::new(expr.clone()), Cron::new(expr.normalize()));
+        assert_eq!(Cron::new(expr), extended("*!15 9-17!12 * * MON-FRI"));
+    }
+
+    #[test]
+    fn weeks() {
+        let extended = |cron: &str| Cron::parse_with(cron, parse::Dialect::Extended).unwrap();
+        let at = |y, m, d| Utc.with_ymd_and_hms(y, m, d, 9, 0, 0).unwrap();
+
+        // Mondays of odd weeks, where 2020 has 53 weeks
+        let cron = extended("0 9 * * MON W/2");
+        assert!(cron.contains(at(2020, 12, 28)));
+        assert!(cron.contains(at(2021, 1, 4)));
+        assert!(!cron.contains(at(2021, 1, 11)));
+        assert_eq!(cron.next_after(at(2020, 12, 14)), Some(at(2020, 12, 28)));
+        assert_eq!(cron.next_after(at(2021, 1, 4)), Some(at(2021, 1, 18)));
+
+        let start = Utc.with_ymd_and_hms(2020, 12, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 2, 1, 0, 0, 0).unwrap();
+        let expected = [
+            at(2020, 12, 14),
+            at(2020, 12, 28),
+            at(2021, 1, 4),
+            at(2021, 1, 18),
+        ];
+        assert_eq!(cron.clone().iter(start..end).collect::<Vec<_>>(), expected);
+        let mut reversed = cron.clone().iter(start..end).rev().collect::<Vec<_>>();
+        reversed.reverse();
+        assert_eq!(reversed, expected);
+        assert_eq!(cron.count_between(start, end), 4);
+
+        assert_eq!(Cron::new(cron.to_expr()), cron);
+        assert_eq!(extended(&cron.to_string()), cron);
+        assert!(!cron.equivalent_to(&"0 9 * * MON".parse().unwrap()));
+
+        // the first of January is only ever in the first or last weeks
+        assert!(!extended("0 0 1 1 * W30").any());
+        assert!(extended("0 0 1 1 * W53").any());
+        assert!(extended("0 0 L DEC * W1").any());
+    }
+
+    #[test]
+    fn parse_check_closest_weekday() {
+        let cron = "0 0 1W MAY *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-05-01 00:00", // First day is a Friday
+                "2022-05-02 00:00", // First day is a Sunday
+                "2021-05-03 00:00", // First day is a Saturday
+            ],
+        )
+    }
+
+    #[test]
+    fn parse_check_closest_weekdays() {
+        let at = |y, m, d| Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap();
+
+        // 2021-05-01 is a Saturday and 2021-05-02 is a Sunday, so both move forward to the 3rd
+        // instead of back into April
+        let cron = "0 0 1-2W * *";
+        check_does_contain(cron, &["2021-05-03 00:00"]);
+        check_does_not_contain(
+            cron,
+            &["2021-04-30 00:00", "2021-05-01 00:00", "2021-05-04 00:00"],
+        );
+
+        // 2021-01-30 is a Saturday and 2021-01-31 is a Sunday at the end of the month, so both
+        // move back to Friday the 29th instead of into February
+        let cron = "0 0 29-31W * *";
+        check_does_contain(cron, &["2021-01-29 00:00", "2021-03-29 00:00"]);
+        check_does_not_contain(
+            cron,
+            &["2021-02-01 00:00", "2021-02-26 00:00", "2021-03-01 00:00"],
+        );
+        let parsed: Cron = cron.parse().unwrap();
+        assert_eq!(parsed.next_after(at(2021, 1, 29)), Some(at(2021, 3, 29)));
+        assert_eq!(parsed.next_after(at(2021, 3, 29)), Some(at(2021, 3, 30)));
+
+        // days past the end of the month are skipped, 2022-04-30 is a Saturday
+        let parsed: Cron = "0 0 30,31W * *".parse().unwrap();
+        assert_eq!(parsed.next_after(at(2022, 4, 1)), Some(at(2022, 4, 29)));
+        assert_eq!(parsed.next_after(at(2022, 4, 29)), Some(at(2022, 5, 30)));
+
+        // 2020-02-29 is a Saturday
+        check_does_contain("0 0 29-31W FEB *", &["2020-02-28 00:00"]);
+        assert!(!"0 0 30-31W FEB *".parse::<Cron>().unwrap().any());
+
+        let parsed: Cron = "0 0 1-7,15W * ?".parse().unwrap();
+        assert_eq!(parsed.to_string(), "0 0 1-7,15W * ?");
+        assert_eq!(Cron::new(parsed.to_expr()), parsed);
+        match parsed.days_of_month() {
+            DayOfMonthSpec::ClosestWeekdays(days) => {
+                assert_eq!(days.collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6, 7, 15])
+            }
+            spec => panic!("Unexpected days of the month {:?}", spec),
+        }
+
+        // lists of closest weekdays are the same no matter how they're written
+        let parsed: Cron = "0 0 1,15W * ?".parse().unwrap();
+        assert_eq!(parsed, "0 0 15W,1W * ?".parse().unwrap());
+        assert_eq!(parsed.to_string(), "0 0 1,15W * ?");
+        assert_eq!(
+            "0 0 1-1W * ?".parse::<Cron>().unwrap(),
+            "0 0 1W * ?".parse().unwrap()
+        );
+        assert_eq!(
+            "0 0 */10W * ?".parse::<Cron>().unwrap().to_string(),
+            "0 0 1,11,21,31W * ?"
+        );
+    }
+
+    #[test]
+    fn parse_check_last_weekday() {
+        let cron = "0 0 * * 7L"; // the last saturday of every month
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-01-25 00:00",
+                "2020-02-29 00:00",
+                "2020-03-28 00:00",
+                "2020-04-25 00:00",
+                "2020-05-30 00:00",
+            ],
+        );
+
+        check_does_not_contain(
+            cron,
+            &[
+                "2020-01-31 00:00",
+                "2020-02-28 00:00",
+                "2020-03-31 00:00",
+                "2020-04-30 00:00",
+                "2020-05-31 00:00",
+            ],
+        )
+    }
+
+    #[test]
+    fn parse_check_nth_weekday() {
+        let cron = "0 0 * * SAT#5"; // the 5th saturday of every month
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-02-29 00:00",
+                "2020-05-30 00:00",
+                "2020-08-29 00:00",
+                "2020-10-31 00:00",
+            ],
+        );
+
+        check_does_not_contain(
+            cron,
+            &[
+                "2020-01-31 00:00",
+                "2020-02-28 00:00",
+                "2020-03-31 00:00",
+                "2020-04-30 00:00",
+                "2020-05-31 00:00",
+            ],
+        )
+    }
+
+    #[test]
+    fn parse_check_steps() {
+        // all the impls step impls follow the same code, so i'll just test minutes for now
+        let cron = "*/15,30-59/10 0 * * *";
+
+        check_does_contain(
+            cron,
+            &[
+                "2020-01-01 00:00",
+                "2020-01-01 00:15",
+                "2020-01-01 00:30",
+                "2020-01-01 00:40",
+                "2020-01-01 00:45",
+                "2020-01-01 00:50",
+            ],
+        )
+    }
+
+    #[test]
+    fn parse_check_wrapping_steps() {
+        check_does_contain("0 0 30-2/2 * *", &["2020-06-30 00:00", "2020-07-01 00:00"]);
+        check_does_not_contain("0 0 30-2/2 * *", &["2020-07-31 00:00", "2020-07-02 00:00"]);
+
+        check_does_contain(
+            "0 0 1 11-3/2 *",
+            &["2020-11-01 00:00", "2021-01-01 00:00", "2021-03-01 00:00"],
+        );
+        check_does_not_contain("0 0 1 11-3/2 *", &["2020-12-01 00:00", "2021-02-01 00:00"]);
+
+        // 2020-06-05 is a Friday
+        check_does_contain("0 0 ? * 6-2/2", &["2020-06-05 00:00", "2020-06-07 00:00"]);
+        check_does_not_contain("0 0 ? * 6-2/2", &["2020-06-06 00:00", "2020-06-08 00:00"]);
     }
-}
 
-impl Iterator for CronTimesIter {
-    type Item = DateTime<Utc>;
+    #[test]
+    fn parse_check_ignored_days() {
+        // 2020-06-01 is a Monday
+        check_does_contain("0 0 ? * MON", &["2020-06-01 00:00", "2020-06-08 00:00"]);
+        check_does_not_contain("0 0 ? * MON", &["2020-06-02 00:00", "2020-06-15 12:00"]);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some((start, end)) = self.bounds {
-            if let Some(next) = self.cron.find_next(start, end) {
-                self.bounds = next_minute(next).map(|new_start| (new_start, end));
-                return Some(next);
-            }
+        check_does_contain("0 0 15 * ?", &["2020-06-15 00:00", "2020-07-15 00:00"]);
+        check_does_not_contain("0 0 15 * ?", &["2020-06-01 00:00", "2020-06-16 00:00"]);
+    }
 
-            self.bounds = None;
+    #[test]
+    fn parse_matches_compiled_expr() {
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0,30 */5 1,15 * *",
+            "5-50/5,59 0,12-14 1-7,L-3 JAN-MAR/2,DEC *",
+            "*/10 * */3 * 2-6/2,SUN",
+            "0 0 L * ?",
+            "0 0 LW * ?",
+            "0 0 L-2W * ?",
+            "0 0 15W * ?",
+            "0 0 ? * 6L",
+            "0 0 ? * MON#2",
+            "0 0 ? * L",
+            "0 0 13 * FRI",
+            "@daily",
+            "@weekly",
+            "",
+            "* * * *",
+            "60 * * * *",
+            "0 0 ? * ?",
+            "* * * * * *",
+            "* * * * *x",
+            "@sometimes",
+        ] {
+            assert_eq!(
+                cron.parse::<Cron>(),
+                cron.parse::<CronExpr>().map(Cron::new),
+                "{}",
+                cron
+            );
         }
+    }
 
-        None
+    fn with_policy(cron: &str, day_policy: DayPolicy) -> Result<Cron, CronCompileError> {
+        let mut options = CronOptions::new();
+        options.day_policy = day_policy;
+        Cron::with_options(cron.parse().unwrap(), options)
     }
-}
 
-impl FusedIterator for CronTimesIter {}
+    #[test]
+    fn day_policy_or() {
+        let cron = with_policy("0 0 13 * FRI", DayPolicy::Or).unwrap();
+        assert_eq!(cron, "0 0 13 * FRI".parse().unwrap());
+        // 2020-11-13 is a Friday
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 11, 13, 0, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 13, 0, 0, 0).unwrap()));
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 16, 0, 0, 0).unwrap()));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn day_policy_and() {
+        let cron = with_policy("0 0 13 * FRI", DayPolicy::And).unwrap();
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 11, 13, 0, 0, 0).unwrap()));
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2020, 10, 13, 0, 0, 0).unwrap()));
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2020, 10, 16, 0, 0, 0).unwrap()));
+        assert!(cron.any());
+
+        // a star in one field still only uses the other field
+        let cron = with_policy("0 0 * * FRI", DayPolicy::And).unwrap();
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 16, 0, 0, 0).unwrap()));
+
+        // the 31st is always in the 5th week of the month
+        assert!(!with_policy("0 0 31 * MON#1", DayPolicy::And).unwrap().any());
+        assert!(with_policy("0 0 31 * MON#5", DayPolicy::And).unwrap().any());
+    }
 
-    #[cfg(not(feature = "std"))]
-    use alloc::{string::ToString, vec::Vec};
+    #[test]
+    fn day_policy_quartz_strict() {
+        assert_eq!(
+            with_policy("0 0 13 * FRI", DayPolicy::QuartzStrict),
+            Err(CronCompileError::DaysNotIgnored)
+        );
+        assert_eq!(
+            with_policy("0 0 * * *", DayPolicy::QuartzStrict),
+            Err(CronCompileError::DaysNotIgnored)
+        );
 
-    const FORMAT: &str = "%F %R";
+        let cron = with_policy("0 0 ? * FRI", DayPolicy::QuartzStrict).unwrap();
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 16, 0, 0, 0).unwrap()));
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2020, 10, 13, 0, 0, 0).unwrap()));
 
-    fn check_does_contain(cron: &str, dates: impl IntoIterator<Item = impl AsRef<str>>) {
-        let parsed: Cron = cron.parse().unwrap();
+        let cron = with_policy("0 0 13 * ?", DayPolicy::QuartzStrict).unwrap();
+        assert!(cron.contains(Utc.with_ymd_and_hms(2020, 10, 13, 0, 0, 0).unwrap()));
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2020, 10, 16, 0, 0, 0).unwrap()));
+    }
 
-        for date in dates.into_iter().map(|s| {
-            Utc.datetime_from_str(s.as_ref(), FORMAT)
-                .expect("Failed to parse expected date")
-        }) {
-            assert!(
-                parsed.contains(date),
-                "Cron \"{}\" should contain {}. Compiled: {:#?}",
-                cron,
-                date,
-                parsed
-            );
+    #[test]
+    fn any_is_exact() {
+        for &cron in &[
+            "0 0 30 2 *",
+            "0 0 29 2 *",
+            "0 0 31 4,6,9,11 *",
+            "0 0 30W FEB ?",
+            "0 0 29W FEB ?",
+            "0 0 L-28 FEB ?",
+            "0 0 L-28W FEB ?",
+            "0 0 L-29 FEB ?",
+            "0 0 21B FEB ?",
+            "0 0 22B FEB ?",
+            "0 0 23B JAN ?",
+            "0 0 LW-20 FEB ?",
+            "0 0 LW-21 FEB ?",
+            "0 0 30-31W FEB ?",
+            "0 0 ? FEB MON#5",
+            "0 0 ? FEB 1L",
+            "0 0 30 2 MON#5",
+            "0 0 L-30 FEB MON#5",
+            "0 0 29 2 MON#5",
+            "0 0 29 2 MON#4",
+            "0 0 1 * MON#5",
+            "0 0 31 * MON#1",
+            "0 0 31 * MON#5",
+            "0 0 L * MON#1",
+            "0 0 1-7 * SUN#2",
+            "0 0 8-14 * SUN#2",
+            "0 0 1W FEB SAT,SUN",
+            "0 0 LW * 1,7",
+            "0 0 3B,LW * 7L",
+        ] {
+            for &day_policy in &[DayPolicy::Or, DayPolicy::And] {
+                let cron = with_policy(cron, day_policy).unwrap();
+                assert_eq!(
+                    cron.any(),
+                    cron.any_in_cycle(),
+                    "{} with {:?}",
+                    cron,
+                    day_policy
+                );
+            }
         }
     }
 
-    fn check_does_not_contain(cron: &str, dates: impl IntoIterator<Item = impl AsRef<str>>) {
-        let parsed: Cron = cron.parse().unwrap();
+    #[test]
+    fn why_never() {
+        let why = |cron: &str| cron.parse::<Cron>().unwrap().why_never();
+        let exceeds = |day, months: &[u8]| {
+            let bits = months.iter().fold(0, |bits, month| bits | 1 << (month - 1));
+            Some(NeverFiresReason::DayExceedsMonthLength {
+                day,
+                months: FieldValues::new(bits, 1),
+            })
+        };
+
+        assert_eq!(why("* * * * *"), None);
+        assert_eq!(why("0 0 29 2 *"), None);
+        assert_eq!(why("0 0 30 2 *"), exceeds(30, &[2]));
+        assert_eq!(why("0 0 31 2,4,6 *"), exceeds(31, &[2, 4, 6]));
+        assert_eq!(why("0 0 L-29 FEB ?"), exceeds(30, &[2]));
+        // the day of the week matches on its own
+        assert_eq!(why("0 0 31 4 MON"), None);
+
+        let expr = CronExpr::from_str_with("0 0 1 1 * W30", parse::Dialect::Extended).unwrap();
+        assert_eq!(
+            Cron::new(expr).why_never(),
+            Some(NeverFiresReason::WeeksNotInDates)
+        );
+    }
 
-        for date in dates.into_iter().map(|s| {
-            Utc.datetime_from_str(s.as_ref(), FORMAT)
-                .expect("Failed to parse expected date")
-        }) {
+    #[test]
+    fn reject_impossible() {
+        let compile = |cron: &str, day_policy| {
+            let mut options = CronOptions::new();
+            options.day_policy = day_policy;
+            options.reject_impossible = true;
+            Cron::with_options(cron.parse().unwrap(), options)
+        };
+
+        let never_fires = |reason| Err(CronCompileError::NeverFires { reason });
+        for &cron in &[
+            "* * 31 11 *",
+            "0 0 30 2 ?",
+            "0 0 31W FEB ?",
+            "0 0 L-29 FEB ?",
+            "0 0 LW-21 FEB ?",
+        ] {
             assert!(
-                !parsed.contains(date),
-                "Cron \"{}\" shouldn't contain {}. Compiled {:#?}",
-                cron,
-                date,
-                parsed
+                matches!(
+                    compile(cron, DayPolicy::Or),
+                    Err(CronCompileError::NeverFires {
+                        reason: NeverFiresReason::DayExceedsMonthLength { .. }
+                    })
+                ),
+                "{}",
+                cron
             );
         }
+
+        assert_eq!(
+            compile("* * 31 11 *", DayPolicy::Or)
+                .unwrap_err()
+                .to_string(),
+            "The expression never matches any time, since none of the months has 31 days"
+        );
+
+        // the first of January is never in the 30th week
+        let mut options = CronOptions::new();
+        options.reject_impossible = true;
+        let expr = CronExpr::from_str_with("0 0 1 1 * W30", parse::Dialect::Extended).unwrap();
+        assert_eq!(
+            Cron::with_options(expr, options),
+            never_fires(NeverFiresReason::WeeksNotInDates)
+        );
+
+        // the day of the week still matches on its own
+        assert!(compile("0 0 L-30 FEB MON#5", DayPolicy::Or).is_ok());
+        assert_eq!(
+            compile("0 0 L-30 FEB MON#5", DayPolicy::And),
+            never_fires(NeverFiresReason::DayExceedsMonthLength {
+                day: 31,
+                months: FieldValues::new(1 << 1, 1),
+            })
+        );
+        assert_eq!(
+            compile("0 0 31 * MON#1", DayPolicy::And),
+            never_fires(NeverFiresReason::DaysNeverCoincide)
+        );
+        assert!(compile("0 0 29 FEB MON", DayPolicy::And).is_ok());
+
+        // 2021-02-28 is a Sunday, so 28W is on Friday the 26th
+        assert!(compile("0 0 28W FEB ?", DayPolicy::Or).is_ok());
+        assert!(compile("0 0 30W JAN,APR ?", DayPolicy::Or).is_ok());
+        assert!("0 0 31W FEB,APR ?"
+            .parse::<Cron>()
+            .map(|cron| !cron.any())
+            .unwrap());
     }
 
     #[test]
-    fn parse_check_anytime() {
+    fn parse_check_overflow_range_step() {
+        // previous code assumed the start was before the end
+        let cron = "0 20-4/2 * * *";
+
         check_does_contain(
-            "* * * * *",
+            cron,
             &[
-                "1970-01-01 00:00",
-                "2016-11-08 23:53",
-                "2020-07-04 15:42",
-                "2072-02-29 01:15",
+                "2020-01-01 20:00",
+                "2020-01-01 22:00",
+                "2020-01-01 00:00",
+                "2020-01-01 02:00",
+                "2020-01-01 04:00",
             ],
         );
     }
 
+    #[track_caller]
+    fn assert_canonical(cron: &str, expected: &str) {
+        let parsed: Cron = cron.parse().unwrap();
+        let string = parsed.to_string();
+        assert_eq!(string, expected);
+        assert_eq!(string.parse::<Cron>().unwrap(), parsed);
+    }
+
     #[test]
-    fn parse_check_specific_time() {
-        let cron = "5 0 23 8 *";
+    fn display_canonical_form() {
+        assert_canonical("* * * * *", "* * * * *");
+        assert_canonical("0-59 0-23 * 1-12 *", "* * * * *");
+        assert_canonical("*/20 */6 * */3 *", "0,20,40 0,6,12,18 * 1,4,7,10 *");
+        assert_canonical("0,59 0,23 1,31 1,12 *", "0,59 0,23 1,31 1,12 *");
+        // a full day of week pattern is not the same as '*' since it's OR'd with the day of month
+        assert_canonical("0 0 15 * 1-7", "0 0 15 * 1-7");
+        assert_canonical("0 0 1-31 * *", "0 0 1-31 * *");
+        assert_canonical("0 0 * * SAT,SUN", "0 0 * * 1,7");
+    }
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-08-23 00:05",
-                "2021-08-23 00:05",
-                "2022-08-23 00:05",
-                "2023-08-23 00:05",
-            ],
+    #[test]
+    fn display_canonical_form_specials() {
+        assert_canonical("0 0 L * *", "0 0 L * *");
+        assert_canonical("0 0 LW * *", "0 0 LW * *");
+        assert_canonical("0 0 L-30 * *", "0 0 L-30 * *");
+        assert_canonical("0 0 L-2W * *", "0 0 L-2W * *");
+        assert_canonical("0 0 31W * *", "0 0 31W * *");
+        assert_canonical("0 0 * * SUNL", "0 0 * * 1L");
+        assert_canonical("0 0 * * SAT#5", "0 0 * * 7#5");
+        assert_canonical("0 0 ? * MON", "0 0 ? * 2");
+        assert_canonical("0 0 1 * ?", "0 0 1 * ?");
+    }
+
+    #[test]
+    fn equal_crons_display_equal() {
+        let a: Cron = "*/5 * * * *".parse().unwrap();
+        let b: Cron = "0-59/5 * * * *".parse().unwrap();
+        assert_eq!(a.to_string(), b.to_string());
+    }
+
+    #[track_caller]
+    fn assert_equivalent(a: &str, b: &str, equivalent: bool) {
+        let a: Cron = a.parse().unwrap();
+        let b: Cron = b.parse().unwrap();
+        assert_eq!(a.equivalent_to(&b), equivalent, "{} and {}", a, b);
+        assert_eq!(b.equivalent_to(&a), equivalent, "{} and {}", b, a);
+    }
+
+    #[test]
+    fn equivalent_crons() {
+        assert_equivalent("*/5 * * * *", "0-59/5 * * * *", true);
+        assert_equivalent("* * * * *", "0-59 0-23 1-31 1-12 1-7", true);
+        assert_equivalent("* * ? * MON", "* * * * 2", true);
+        assert_equivalent("* * 1-31 * MON", "* * * * *", true);
+        assert_equivalent("* * 1-29 FEB *", "* * * FEB *", true);
+        assert_equivalent("* * 1,31 APR,JUN *", "* * 1 4,6 *", true);
+        assert_equivalent("* * L APR *", "* * 30 APR *", true);
+        assert_equivalent("* * L-2 JAN,MAR *", "* * 29 1,3 *", true);
+        assert_equivalent("* * L-30 APR MON", "* * ? APR MON", true);
+        assert_equivalent("* * 31 4 *", "* * 30 2 *", true);
+
+        assert_equivalent("* * * * *", "*/2 * * * *", false);
+        assert_equivalent("* * 1 * MON", "* * 1 * *", false);
+        assert_equivalent("* * L FEB *", "* * 28,29 FEB *", false);
+        assert_equivalent("* * L JAN,FEB *", "* * 31 JAN,FEB *", false);
+        assert_equivalent("* * * * MON#1", "* * 1-7 * MON", false);
+        assert_equivalent("* * 31 11 *", "* * * * *", false);
+    }
+
+    #[test]
+    fn equivalent_crons_with_day_policies() {
+        let with_policy = |cron: &str, day_policy| {
+            let mut options = CronOptions::new();
+            options.day_policy = day_policy;
+            Cron::with_options(cron.parse().unwrap(), options).unwrap()
+        };
+
+        let or = with_policy("* * 1-31 * MON", DayPolicy::Or);
+        let and = with_policy("* * 1-31 * MON", DayPolicy::And);
+        assert!(or.equivalent_to(&"* * * * *".parse().unwrap()));
+        assert!(and.equivalent_to(&"* * * * MON".parse().unwrap()));
+
+        let or = with_policy("* * 13 * FRI", DayPolicy::Or);
+        let and = with_policy("* * 13 * FRI", DayPolicy::And);
+        assert!(!or.equivalent_to(&and));
+        assert!(with_policy("* * ? * FRI", DayPolicy::QuartzStrict)
+            .equivalent_to(&with_policy("* * * * FRI", DayPolicy::And)));
+    }
+
+    #[test]
+    fn overlapping_crons() {
+        let overlaps = |a: &str, b: &str| {
+            let a: Cron = a.parse().unwrap();
+            let b: Cron = b.parse().unwrap();
+            assert_eq!(a.overlaps(&b), b.overlaps(&a));
+            a.overlaps(&b)
+        };
+
+        assert!(overlaps("* * * * *", "0 0 1 1 *"));
+        assert!(overlaps("*/20 * * * *", "*/30 * * * *"));
+        assert!(overlaps("0 0 13 * *", "0 0 * * FRI"));
+        assert!(overlaps("0 0 29 2 *", "0 0 * FEB MON"));
+        assert!(overlaps("0 0 L * *", "0 0 31 * *"));
+
+        assert!(!overlaps("0 * * * *", "30 * * * *"));
+        assert!(!overlaps("0 1 * * *", "0 2 * * *"));
+        assert!(!overlaps("0 0 * 1-6 *", "0 0 * 7-12 *"));
+        assert!(!overlaps("0 0 ? * MON", "0 0 ? * TUE"));
+        assert!(!overlaps("0 0 1 * *", "0 0 2 * *"));
+        assert!(!overlaps("0 0 31 * *", "0 0 * 4 *"));
+        assert!(!overlaps("0 0 * * MON#1", "0 0 8-31 * ?"));
+        assert!(!overlaps("0 0 * * *", "0 0 31 11 *"));
+    }
+
+    #[test]
+    fn next_common_times() {
+        let a: Cron = "*/20 * * * *".parse().unwrap();
+        let b: Cron = "*/30 * * * *".parse().unwrap();
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 1, 0).unwrap();
+        assert_eq!(
+            a.next_common(&b, start),
+            Some(Utc.with_ymd_and_hms(2020, 1, 1, 1, 0, 0).unwrap())
         );
 
-        check_does_not_contain(
-            cron,
-            &[
-                "1970-01-01 00:00",
-                "2016-11-08 23:53",
-                "2020-07-04 15:42",
-                "2072-02-29 01:15",
-                "2020-08-23 11:05",
-            ],
+        // the next leap day on a monday
+        let a: Cron = "0 0 29 2 *".parse().unwrap();
+        let b: Cron = "0 0 * * MON".parse().unwrap();
+        assert_eq!(
+            a.next_common(&b, start),
+            Some(Utc.with_ymd_and_hms(2044, 2, 29, 0, 0, 0).unwrap())
         );
     }
 
-    /// check to make sure we don't accidentally include any off-by-one errors with ranges
     #[test]
-    fn parse_check_specific_time_as_ranges() {
-        let cron = "5-5 0-0 23-23 8-8 *";
+    fn field_values() {
+        let cron: Cron = "0,30-32 20-4/4 * 1,2,11,12 *".parse().unwrap();
+        assert_eq!(cron.minutes().collect::<Vec<_>>(), [0, 30, 31, 32]);
+        assert_eq!(cron.minutes().rev().collect::<Vec<_>>(), [32, 31, 30, 0]);
+        assert_eq!(cron.minutes().len(), 4);
+        assert_eq!(cron.hours().collect::<Vec<_>>(), [0, 4, 20]);
+        assert_eq!(cron.months().collect::<Vec<_>>(), [1, 2, 11, 12]);
+
+        let cron: Cron = "* * * * *".parse().unwrap();
+        assert_eq!(cron.minutes().len(), 60);
+        assert_eq!(cron.hours().next_back(), Some(23));
+        assert_eq!(cron.months().len(), 12);
+        assert_eq!(cron.days_of_month(), DayOfMonthSpec::All);
+        assert_eq!(cron.days_of_week(), DayOfWeekSpec::All);
+    }
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-08-23 00:05",
-                "2021-08-23 00:05",
-                "2022-08-23 00:05",
-                "2023-08-23 00:05",
-            ],
+    #[test]
+    fn day_specs() {
+        let days_of_month = |cron: &str| cron.parse::<Cron>().unwrap().days_of_month();
+        match days_of_month("0 0 1,15,31 * *") {
+            DayOfMonthSpec::Days(days) => assert_eq!(days.collect::<Vec<_>>(), [1, 15, 31]),
+            spec => panic!("Unexpected spec {:?}", spec),
+        }
+        assert_eq!(days_of_month("0 0 ? * MON"), DayOfMonthSpec::Ignored);
+        assert_eq!(
+            days_of_month("0 0 L * *"),
+            DayOfMonthSpec::Last { offset: 0 }
+        );
+        assert_eq!(
+            days_of_month("0 0 L-5W * *"),
+            DayOfMonthSpec::LastWeekday { offset: 5 }
+        );
+        assert_eq!(
+            days_of_month("0 0 LW * *"),
+            DayOfMonthSpec::LastWeekday { offset: 0 }
+        );
+        assert_eq!(
+            days_of_month("0 0 31W * *"),
+            DayOfMonthSpec::ClosestWeekday(31)
         );
 
-        check_does_not_contain(
-            cron,
-            &[
-                "1970-01-01 00:00",
-                "2016-11-08 23:53",
-                "2020-07-04 15:42",
-                "2072-02-29 01:15",
-                "2020-08-23 11:05",
-            ],
+        let days_of_week = |cron: &str| cron.parse::<Cron>().unwrap().days_of_week();
+        match days_of_week("0 0 * * MON-WED,SUN") {
+            DayOfWeekSpec::Days(days) => assert_eq!(
+                days.collect::<Vec<_>>(),
+                [Weekday::Sun, Weekday::Mon, Weekday::Tue, Weekday::Wed]
+            ),
+            spec => panic!("Unexpected spec {:?}", spec),
+        }
+        assert_eq!(days_of_week("0 0 1 * ?"), DayOfWeekSpec::Ignored);
+        assert_eq!(
+            days_of_week("0 0 * * FRIL"),
+            DayOfWeekSpec::Last(Weekday::Fri)
+        );
+        assert_eq!(
+            days_of_week("0 0 * * SAT#5"),
+            DayOfWeekSpec::Nth(Weekday::Sat, 5)
         );
     }
 
     #[test]
-    fn parse_check_overflow_time_ranges() {
-        // The 31st and 1st of January and December,
-        // at 11:00 PM, 11:59 PM, 12:00 AM, and 12:59 AM
-        let cron = "59-0 23-0 31-1 12-1 *";
+    fn to_expr() {
+        for &(expr, canonical) in &[
+            ("* * * * *", "* * * * *"),
+            ("*/15 0-3,5 * * *", "0,15,30,45 0-3,5 * * *"),
+            ("0 0 1,15,31 JAN-MAR *", "0 0 1,15,31 1-3 *"),
+            ("0 0 L * *", "0 0 L * *"),
+            ("0 0 L-3 * *", "0 0 L-3 * *"),
+            ("0 0 LW * *", "0 0 LW * *"),
+            ("0 0 L-2W * *", "0 0 L-2W * *"),
+            ("0 0 15W * *", "0 0 15W * *"),
+            ("0 0 ? * MON-FRI", "0 0 ? * 2-6"),
+            ("0 0 1 * ?", "0 0 1 * ?"),
+            ("0 0 * * FRIL", "0 0 * * 6L"),
+            ("0 0 * * SAT#5", "0 0 * * 7#5"),
+            ("@weekly", "0 0 * * 1"),
+        ] {
+            let cron: Cron = expr.parse().unwrap();
+            let rebuilt = cron.to_expr();
+            assert_eq!(rebuilt.to_string(), canonical, "{}", expr);
+            assert_eq!(rebuilt.to_string(), cron.to_string(), "{}", expr);
+            assert_eq!(Cron::new(rebuilt), cron, "{}", expr);
+        }
+    }
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-01-31 00:59",
-                "2020-01-31 00:00",
-                "2020-01-31 23:59",
-                "2020-01-31 23:00",
-                "2020-01-01 00:59",
-                "2020-01-01 00:00",
-                "2020-01-01 23:59",
-                "2020-01-01 23:00",
-                "2020-12-31 00:59",
-                "2020-12-31 00:00",
-                "2020-12-31 23:59",
-                "2020-12-31 23:00",
-                "2020-12-01 00:59",
-                "2020-12-01 00:00",
-                "2020-12-01 23:59",
-                "2020-12-01 23:00",
-            ],
+    #[test]
+    fn describe() {
+        use crate::parse::English;
+
+        let describe = |cron: &str| {
+            let cron: Cron = cron.parse().unwrap();
+            cron.describe(English::default()).to_string()
+        };
+        assert_eq!(describe("0 0 * * *"), "At 12:00 AM");
+        assert_eq!(describe("@hourly"), "Every hour");
+        assert_eq!(
+            describe("0 0 1,2,3 * ?"),
+            "At 12:00 AM on the 1st to 3rd of every month"
         );
+    }
 
-        // Midnight on every Saturday and Sunday in January
-        let cron = "0 0 * JAN SAT-SUN";
+    #[test]
+    fn nth_from() {
+        let start = Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 42).unwrap();
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0,30 */5 1,15 * *",
+            "0 12 29 2 *",
+            "15 3 L * *",
+            "0 0 * * MON#2",
+            "0 0 13 * FRI",
+            "59 23 31 12 *",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            for &n in &[0, 1, 5, 59, 60, 143, 1000, 5000] {
+                assert_eq!(
+                    parsed.nth_from(start, n),
+                    parsed.clone().iter_from(start).nth(n as usize),
+                    "{} nth_from {}",
+                    cron,
+                    n
+                );
+                assert_eq!(
+                    parsed.nth_after(start, n),
+                    parsed.clone().iter_after(start).nth(n as usize),
+                    "{} nth_after {}",
+                    cron,
+                    n
+                );
+            }
+        }
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-01-04 00:00",
-                "2020-01-05 00:00",
-                "2020-01-11 00:00",
-                "2020-01-12 00:00",
-            ],
+        let never: Cron = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(never.nth_from(start, 0), None);
+    }
+
+    #[test]
+    fn next_n_from() {
+        let start = Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 42).unwrap();
+        for &cron in &["* * * * *", "*/7 9-17 * * *", "0 12 29 2 *", "15 3 L * *"] {
+            let parsed: Cron = cron.parse().unwrap();
+            for &n in &[0, 1, 5, 60] {
+                let expected = parsed.clone().iter_from(start).take(n).collect::<Vec<_>>();
+                assert_eq!(parsed.next_n_from(start, n), expected, "{} {}", cron, n);
+
+                let mut times = [start; 60];
+                assert_eq!(
+                    parsed.fill_from(start, &mut times[..n]),
+                    n,
+                    "{} {}",
+                    cron,
+                    n
+                );
+                assert_eq!(&times[..n], &expected[..], "{} {}", cron, n);
+            }
+        }
+
+        let never: Cron = "0 0 30 2 *".parse().unwrap();
+        assert!(never.next_n_from(start, 5).is_empty());
+        assert_eq!(never.fill_from(start, &mut [start; 5]), 0);
+    }
+
+    #[test]
+    fn next_in_range() {
+        let start = Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 42).unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 3, 2, 0, 0, 0).unwrap();
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0 12 29 2 *",
+            "15 3 L * *",
+            "0 0 1 3 *",
+            "0 0 13 * FRI",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            assert_eq!(
+                parsed.next_in_range(start..end),
+                parsed.clone().iter(start..end).next(),
+                "{}",
+                cron
+            );
+            assert_eq!(
+                parsed.next_in_range(start..=end),
+                parsed.clone().iter(start..=end).next(),
+                "{}",
+                cron
+            );
+            assert_eq!(
+                parsed.next_in_range(start..),
+                parsed.next_from(start),
+                "{}",
+                cron
+            );
+        }
+
+        let cron: Cron = "0 0 * * *".parse().unwrap();
+        assert_eq!(cron.next_in_range(end..end), None);
+        assert_eq!(cron.next_in_range(end..=end), Some(end));
+        assert_eq!(cron.next_in_range(end..start), None);
+    }
+
+    #[test]
+    fn contains_with_seconds_policy() {
+        let cron: Cron = "30 9 * * *".parse().unwrap();
+        let minute = Utc.with_ymd_and_hms(2021, 6, 1, 9, 30, 0).unwrap();
+        let times = [
+            (minute, true),
+            (minute + Duration::seconds(59), false),
+            (minute + Duration::nanoseconds(1), false),
+            // a leap second is still within the minute
+            (
+                NaiveDate::from_ymd_opt(2021, 6, 1)
+                    .and_then(|date| date.and_hms_nano_opt(9, 30, 59, 1_500_000_000))
+                    .unwrap()
+                    .and_utc(),
+                false,
+            ),
+        ];
+        for &(time, exact) in &times {
+            assert!(cron.contains(time), "{}", time);
+            assert!(
+                cron.contains_with(time, SecondsPolicy::Truncate),
+                "{}",
+                time
+            );
+            assert_eq!(
+                cron.contains_with(time, SecondsPolicy::Exact),
+                exact,
+                "{}",
+                time
+            );
+            assert_eq!(cron.contains_exact(time), exact, "{}", time);
+        }
+
+        assert!(!cron.contains_exact(minute + Duration::minutes(1)));
+        assert_eq!(SecondsPolicy::default(), SecondsPolicy::Truncate);
+    }
+
+    #[test]
+    fn active_windows_for_date() {
+        fn windows(cron: &str, date: NaiveDate) -> Vec<(u32, u32)> {
+            let midnight = date_time(date, NaiveTime::MIN);
+            cron.parse::<Cron>()
+                .unwrap()
+                .active_windows_for_date(date)
+                .map(|window| {
+                    let minutes = |time: DateTime<Utc>| (time - midnight).num_minutes() as u32;
+                    (minutes(window.start), minutes(window.end))
+                })
+                .collect()
+        }
+
+        let date = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+        assert_eq!(windows("* * * * *", date), [(0, 1440)]);
+        assert_eq!(windows("* 9-17 * * *", date), [(540, 1080)]);
+        assert_eq!(windows("0-29 9-10 * * *", date), [(540, 570), (600, 630)]);
+        // minutes 58-59 of one hour run into minutes 0-1 of the next
+        assert_eq!(
+            windows("58-1 22-23 * * *", date),
+            [(1320, 1322), (1378, 1382), (1438, 1440)]
+        );
+        assert_eq!(windows("0 0 * * *", date), [(0, 1)]);
+        assert_eq!(windows("0 0 2 * *", date), []);
+
+        // the windows cover every matching minute of the day and nothing else
+        for &cron in &[
+            "*/7 */5 * * *",
+            "0-10,50-59 * * * *",
+            "15 3 L * *",
+            "* 0,23 1 * *",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            let midnight = date_time(date, NaiveTime::MIN);
+            let mut active = [false; MINUTES_IN_DAY as usize];
+            for (start, end) in windows(cron, date) {
+                assert!(start < end, "{}", cron);
+                assert!(
+                    end == MINUTES_IN_DAY
+                        || !parsed.contains(midnight + Duration::minutes(end.into()))
+                );
+                for minute in start..end {
+                    active[minute as usize] = true;
+                }
+            }
+            for (minute, &active) in active.iter().enumerate() {
+                let time = midnight + Duration::minutes(minute as i64);
+                assert_eq!(parsed.contains(time), active, "{} at {}", cron, time);
+            }
+        }
+
+        let last = NaiveDate::MAX;
+        let mut iter = "* * * * *"
+            .parse::<Cron>()
+            .unwrap()
+            .active_windows_for_date(last);
+        assert_eq!(
+            iter.next().map(|window| window.end),
+            Some(DateTime::<Utc>::MAX_UTC)
         );
+        assert_eq!(iter.next(), None);
     }
 
     #[test]
-    fn parse_check_limits() {
-        let cron = "0,59 0,23 1,31 1,12 *";
+    fn latest_in_range() {
+        let start = Utc.with_ymd_and_hms(2024, 2, 27, 13, 17, 42).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 3, 3, 9, 30, 0).unwrap();
+        for &cron in &[
+            "*/7 9-17 * * *",
+            "0 12 29 2 *",
+            "15 3 L * *",
+            "0 0 ? * 6L",
+            "0 0 1 1 *",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            assert_eq!(
+                parsed.latest_in_range(start..end),
+                parsed.clone().iter(start..end).next_back(),
+                "{}",
+                cron
+            );
+            assert_eq!(
+                parsed.latest_in_range(start..=end),
+                parsed.clone().iter(start..=end).next_back(),
+                "{}",
+                cron
+            );
+            assert_eq!(
+                parsed.latest_before(end),
+                parsed.clone().iter(..end).next_back(),
+                "{}",
+                cron
+            );
+        }
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-01-01 00:00",
-                "2020-01-01 00:59",
-                "2020-01-01 23:59",
-                "2020-01-31 23:59",
-                "2020-12-31 23:59",
-            ],
+        let hourly: Cron = "0 * * * *".parse().unwrap();
+        let hour = Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        assert_eq!(hourly.latest_before(hour), Some(hour - Duration::hours(1)));
+        assert_eq!(hourly.latest_in_range(hour..=hour), Some(hour));
+        assert_eq!(hourly.latest_in_range(hour..hour), None);
+
+        let never: Cron = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(never.latest_before(end), None);
+
+        // the closest weekday to a Saturday is the Friday before, not the Monday after
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        for &cron in &["0 0 L-24W * *", "0 0 L-5W * *", "0 0 L-30W * *"] {
+            let parsed: Cron = cron.parse().unwrap();
+            for month in 1..=12 {
+                let end = Utc.with_ymd_and_hms(2021, month, 12, 0, 0, 0).unwrap();
+                assert_eq!(
+                    parsed.latest_before(end),
+                    // iterated forward, not with next_back
+                    parsed.clone().iter(start..end).collect::<Vec<_>>().pop(),
+                    "{} before {}",
+                    cron,
+                    end
+                );
+            }
+        }
+        let cron: Cron = "0 0 L-24W * *".parse().unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 8, 12, 0, 0, 0).unwrap();
+        assert_eq!(
+            cron.latest_before(end),
+            Some(Utc.with_ymd_and_hms(2021, 8, 6, 0, 0, 0).unwrap())
         );
     }
 
     #[test]
-    fn parse_check_anytime_but_its_ranges() {
-        let cron = "0-59 0-23 1-31 1-12 *";
+    fn latest_in_month() {
+        let last_minute: Cron = "* * * * *".parse().unwrap();
+        assert_eq!(
+            last_minute.latest_in_month(2024, 2),
+            Some(Utc.with_ymd_and_hms(2024, 2, 29, 23, 59, 0).unwrap())
+        );
+        assert_eq!(
+            last_minute.latest_in_month(2023, 12),
+            Some(Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 0).unwrap())
+        );
 
-        check_does_contain(
-            cron,
-            &[
-                "1970-01-01 00:00",
-                "2016-11-08 23:53",
-                "2020-07-04 15:42",
-                "2072-02-29 01:15",
-            ],
+        let first_of_month: Cron = "0 0 1 * *".parse().unwrap();
+        assert_eq!(
+            first_of_month.latest_in_month(2024, 3),
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap())
         );
 
-        let cron = "0-59 0-23 * 1-12 1-7";
+        let leap_day: Cron = "0 0 29 2 *".parse().unwrap();
+        assert_eq!(leap_day.latest_in_month(2023, 2), None);
+        assert_eq!(leap_day.latest_in_month(2024, 3), None);
+        assert_eq!(leap_day.latest_in_month(2024, 0), None);
+    }
 
-        check_does_contain(
-            cron,
-            &[
-                "1970-01-01 00:00",
-                "2016-11-08 23:53",
-                "2020-07-04 15:42",
-                "2072-02-29 01:15",
-            ],
+    #[test]
+    fn count_between() {
+        let start = Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 42).unwrap();
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0,30 */5 1,15 * *",
+            "0 12 29 2 *",
+            "15 3 L * *",
+            "0 0 * * MON#2",
+            "0 0 13 * FRI",
+            "17 13 * * *",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            for &end in &[
+                start,
+                Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 59).unwrap(),
+                Utc.with_ymd_and_hms(2020, 2, 27, 13, 18, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 3, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 3, 28, 13, 17, 30).unwrap(),
+                Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+            ] {
+                assert_eq!(
+                    parsed.count_between(start, end),
+                    parsed.clone().iter(start..end).count() as u64,
+                    "{} until {}",
+                    cron,
+                    end
+                );
+            }
+        }
+
+        let never: Cron = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(
+            never.count_between(start, Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap()),
+            0
         );
     }
 
     #[test]
-    fn parse_check_leap_days() {
-        let cron = "0 0 L FEB *";
+    fn firings() {
+        let crons = [
+            ("* * * * *", 60, 1440, 31 * 1440),
+            ("*/7 9-17 * * *", 9, 81, 31 * 81),
+            ("0,30 */5 1,15 * *", 2, 10, 20),
+            ("0 12 29 2 *", 1, 1, 1),
+            ("15 3 L * *", 1, 1, 1),
+            ("0 0 * * MON#2", 1, 1, 1),
+            ("0 0 ? * MON#2,FRI#3,6L", 1, 1, 3),
+            ("0 0 13 * FRI", 1, 1, 6),
+            ("0 0 * FEB MON-FRI", 1, 1, 21),
+            ("0 0 * * SAT,SUN", 1, 1, 10),
+            ("0 0 1-15 * MON,TUE", 1, 1, 25),
+            ("0 0 31 APR,JUN *", 1, 1, 0),
+        ];
+        for &(cron, per_hour, per_day, per_month) in &crons {
+            let parsed: Cron = cron.parse().unwrap();
+            assert_eq!(parsed.firings_per_hour(), per_hour, "{}", cron);
+            assert_eq!(parsed.firings_per_day(), per_day, "{}", cron);
+            assert_eq!(parsed.firings_per_month(), per_month, "{}", cron);
+
+            // no month between 2020 and 2030 has more times than the bound
+            let most = (2020..2030)
+                .flat_map(|year| (1..=12).map(move |month| (year, month)))
+                .map(|(year, month)| {
+                    let start = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).unwrap();
+                    let end = Utc
+                        .with_ymd_and_hms(year + month as i32 / 12, month % 12 + 1, 1, 0, 0, 0)
+                        .unwrap();
+                    parsed.count_between(start, end)
+                })
+                .max()
+                .unwrap();
+            assert!(most <= u64::from(per_month), "{}", cron);
+        }
 
-        check_does_contain(
-            cron,
-            &[
-                "2400-02-29 00:00",
-                "2300-02-28 00:00",
-                "2200-02-28 00:00",
-                "2100-02-28 00:00",
-                "2024-02-29 00:00",
-                "2020-02-29 00:00",
-                "2004-02-29 00:00",
-                "2000-02-29 00:00",
-            ],
-        );
+        let mut and = CronOptions::new();
+        and.day_policy = DayPolicy::And;
+        let cron = Cron::with_options("0 0 13 * FRI".parse().unwrap(), and).unwrap();
+        assert_eq!(cron.firings_per_month(), 1);
     }
 
     #[test]
-    fn parse_check_offset_leap_days() {
-        let cron = "0 0 L-1 FEB *";
+    fn iter_rev() {
+        let start = Utc.with_ymd_and_hms(2019, 12, 20, 13, 17, 42).unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 3, 3, 9, 30, 0).unwrap();
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0,30 */5 1,15 * *",
+            "0 12 29 2 *",
+            "15 3 L * *",
+            "0 0 LW * ?",
+            "0 0 15W * ?",
+            "0 0 * * MON#2",
+            "0 0 ? * 6L",
+            "0 0 13 * FRI",
+            "0 0 * * 1,4,7",
+            "59 23 31 12 *",
+            "0 0 1 2,3 *",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            for &bounds in &[
+                (Bound::Included(start), Bound::Excluded(end)),
+                (Bound::Excluded(start), Bound::Included(end)),
+            ] {
+                let mut forward: Vec<_> = parsed.clone().iter(bounds).collect();
+                forward.reverse();
+                let backward: Vec<_> = parsed.clone().iter(bounds).rev().collect();
+                assert_eq!(forward, backward, "{}", cron);
+            }
 
-        check_does_contain(
-            cron,
-            &[
-                "2400-02-28 00:00",
-                "2300-02-27 00:00",
-                "2200-02-27 00:00",
-                "2100-02-27 00:00",
-                "2024-02-28 00:00",
-                "2020-02-28 00:00",
-                "2004-02-28 00:00",
-                "2000-02-28 00:00",
-            ],
-        );
+            // both ends meet in the middle
+            let mut iter = parsed.clone().iter(start..end);
+            let mut count = 0;
+            while iter.next().is_some() {
+                count += 1;
+                if iter.next_back().is_some() {
+                    count += 1;
+                }
+            }
+            assert_eq!(count, parsed.count_between(start, end), "{}", cron);
+        }
 
-        check_does_not_contain(
-            cron,
-            &[
-                "2400-02-29 00:00",
-                "2300-02-28 00:00",
-                "2200-02-28 00:00",
-                "2100-02-28 00:00",
-                "2024-02-29 00:00",
-                "2020-02-29 00:00",
-                "2004-02-29 00:00",
-                "2000-02-29 00:00",
-            ],
+        let cron: Cron = "0 0 * * *".parse().unwrap();
+        let last = cron.clone().iter(..end).next_back();
+        assert_eq!(
+            last,
+            Some(Utc.with_ymd_and_hms(2020, 3, 3, 0, 0, 0).unwrap())
         );
     }
 
     #[test]
-    fn parse_check_offset_weekend_start_months() {
-        let cron = "0 0 L-30W * *";
-
-        check_does_contain(cron, &["2021-05-3 00:00", "2022-01-3 00:00"]);
+    fn iter_rev_closest_weekdays() {
+        let start = Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let exprs = (1..=30)
+            .flat_map(|n| {
+                vec![
+                    format!("0 0 L-{}W * *", n),
+                    format!("0 0 {}W * *", n),
+                    format!("0 0 LW-{} * *", n.min(22)),
+                ]
+            })
+            .chain(vec!["0 0 LW * *".to_string(), "0 0 31W * *".to_string()]);
+        for cron in exprs {
+            let parsed: Cron = cron.parse().unwrap();
+            let mut forward: Vec<_> = parsed.clone().iter(start..end).collect();
+            forward.reverse();
+            let backward: Vec<_> = parsed.clone().iter(start..end).rev().collect();
+            assert_eq!(forward, backward, "{}", cron);
+            assert!(
+                forward.iter().all(|&time| parsed.contains(time)),
+                "{}",
+                cron
+            );
+        }
     }
 
     #[test]
-    fn parse_check_offset_weekend_start_months_beyond_days() {
-        let cron = "0 0 L-28W FEB *";
+    fn iter_size_hint() {
+        let start = Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 42).unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 3, 28, 13, 17, 0).unwrap();
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0 12 29 2 *",
+            "15 3 L * *",
+            "17 13 * * *",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            for &bounds in &[
+                (Bound::Included(start), Bound::Excluded(end)),
+                (Bound::Included(start), Bound::Included(end)),
+            ] {
+                let count = parsed.clone().iter(bounds).count();
+                let mut iter = parsed.clone().iter(bounds);
+                assert_eq!(iter.size_hint(), (count, Some(count)), "{}", cron);
+                iter.next();
+                let count = count.saturating_sub(1);
+                assert_eq!(iter.size_hint(), (count, Some(count)), "{}", cron);
+            }
 
-        check_does_not_contain(cron, &["2021-05-3 00:00", "2022-01-3 00:00"]);
+            let (lower, upper) = parsed.clone().iter_from(start).size_hint();
+            assert_eq!(lower, 0);
+            assert!(upper.is_some(), "{}", cron);
+        }
+
+        let never: Cron = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(never.iter_from(start).size_hint(), (0, Some(0)));
     }
 
     #[test]
-    fn parse_check_last_weekdays() {
-        let cron = "0 0 LW MAY *";
-
-        check_does_contain(
-            cron,
-            &[
-                "2025-05-30 00:00", // Last day is a Saturday
-                "2021-05-31 00:00", // Last day is a Monday
-                "2020-05-29 00:00", // Last day is a Sunday
-            ],
-        );
+    fn wrapping_ranges() {
+        let cron: Cron = "50-5 22-2 28-3 NOV-FEB FRI-SUN".parse().unwrap();
+        let minutes = (0..=5).chain(50..=59).collect::<Vec<_>>();
+        assert_eq!(cron.minutes().collect::<Vec<_>>(), minutes);
+        assert_eq!(cron.hours().collect::<Vec<_>>(), [0, 1, 2, 22, 23]);
+        assert_eq!(cron.months().collect::<Vec<_>>(), [1, 2, 11, 12]);
+
+        // 2021-01-01 is a Friday
+        let days = [1, 2, 3, 8, 9, 10, 15, 16, 17, 22, 23, 24, 28, 29, 30, 31];
+        let matched = (1..=31)
+            .filter(|&day| cron.contains(Utc.with_ymd_and_hms(2021, 1, day, 0, 0, 0).unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(matched, days);
     }
 
     #[test]
-    fn parse_check_last_weekdays_offset() {
-        let cron = "0 0 L-1W MAY *";
+    fn leap_days() {
+        let start = Utc.with_ymd_and_hms(1895, 6, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2105, 6, 1, 0, 0, 0).unwrap();
+        let mut and = CronOptions::new();
+        and.day_policy = DayPolicy::And;
+        for cron in &[
+            "0 0 29 2 *".parse::<Cron>().unwrap(),
+            "0 12 29-31 2 ?".parse().unwrap(),
+            Cron::with_options("0 0 29 2 SAT".parse().unwrap(), and).unwrap(),
+        ] {
+            assert!(cron.leap_years_only());
+            let expected = (1895..2106)
+                .filter_map(|year| Utc.with_ymd_and_hms(year, 2, 29, 0, 0, 0).single())
+                .flat_map(|day| {
+                    cron.clone()
+                        .iter(day..=day + Duration::minutes(23 * 60 + 59))
+                })
+                .collect::<Vec<_>>();
+            assert!(expected.len() > 1);
+            assert_eq!(cron.clone().iter(start..end).collect::<Vec<_>>(), expected);
+            assert!(cron
+                .clone()
+                .iter(start..end)
+                .rev()
+                .eq(expected.iter().rev().copied()));
+        }
 
-        check_does_contain(
-            cron,
-            &[
-                "2025-05-30 00:00", // Offset last day is a Friday
-                "2021-05-31 00:00", // Offset last day is a Sunday
-                "2020-05-29 00:00", // Offset last day is a Saturday
-            ],
+        // 1900 and 2100 aren't leap years
+        let cron: Cron = "0 0 29 2 *".parse().unwrap();
+        let years = cron
+            .iter_from_ref(Utc.with_ymd_and_hms(1896, 3, 1, 0, 0, 0).unwrap())
+            .take(3)
+            .map(|time| time.year());
+        assert!(years.eq([1904, 1908, 1912].iter().copied()));
+        assert_eq!(
+            cron.next_after(Utc.with_ymd_and_hms(2096, 2, 29, 0, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2104, 2, 29, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            cron.iter_ref(..Utc.with_ymd_and_hms(1904, 1, 1, 0, 0, 0).unwrap())
+                .next_back(),
+            Some(Utc.with_ymd_and_hms(1896, 2, 29, 0, 0, 0).unwrap())
         );
+
+        let or: Cron = "0 0 29 2 SAT".parse().unwrap();
+        assert!(!or.leap_years_only());
     }
 
     #[test]
-    fn parse_check_closest_weekday() {
-        let cron = "0 0 1W MAY *";
-
-        check_does_contain(
-            cron,
-            &[
-                "2020-05-01 00:00", // First day is a Friday
-                "2022-05-02 00:00", // First day is a Sunday
-                "2021-05-03 00:00", // First day is a Saturday
-            ],
-        )
+    fn feb_29_policy() {
+        let compile = |cron: &str, policy| {
+            let mut options = CronOptions::new();
+            options.feb_29_policy = policy;
+            Cron::with_options(cron.parse().unwrap(), options)
+        };
+
+        let cron = compile("0 0 29 2 *", Feb29Policy::LastDayOfFeb).unwrap();
+        assert!(!cron.leap_years_only());
+        let times = cron
+            .iter_from_ref(Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).unwrap())
+            .take(3)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            [
+                Utc.with_ymd_and_hms(2019, 2, 28, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 2, 29, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2021, 2, 28, 0, 0, 0).unwrap(),
+            ]
+        );
+        assert!(!cron.contains(Utc.with_ymd_and_hms(2020, 2, 28, 0, 0, 0).unwrap()));
+        assert_eq!(
+            cron.iter_ref(..Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap())
+                .next_back(),
+            Some(Utc.with_ymd_and_hms(2021, 2, 28, 0, 0, 0).unwrap())
+        );
+        assert_eq!(cron.to_string(), "0 0 29 2 *");
+        assert_eq!(Cron::from_bytes(&cron.to_bytes()), Ok(cron.clone()));
+        assert!(!cron.equivalent_to(&"0 0 29 2 *".parse().unwrap()));
+
+        // other months with the 29th aren't changed
+        let cron = compile("0 0 1,29 * *", Feb29Policy::LastDayOfFeb).unwrap();
+        let days = cron
+            .iter_from_ref(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap())
+            .take(6)
+            .map(|time| (time.month(), time.day()))
+            .collect::<Vec<_>>();
+        assert_eq!(days, [(1, 1), (1, 29), (2, 1), (2, 28), (3, 1), (3, 29)]);
+        let cron = compile("0 0 28,29 2 *", Feb29Policy::LastDayOfFeb).unwrap();
+        assert!(cron.equivalent_to(&"0 0 28,29 2 *".parse().unwrap()));
+
+        assert_eq!(
+            compile("0 0 29 2 *", Feb29Policy::Reject),
+            Err(CronCompileError::LeapYearsOnly)
+        );
+        assert!(compile("0 0 29 * *", Feb29Policy::Reject).is_ok());
+        assert!(compile("0 0 28,29 2 *", Feb29Policy::Reject).is_ok());
+        assert_eq!(
+            compile("0 0 29 2 *", Feb29Policy::Exact),
+            Ok("0 0 29 2 *".parse().unwrap())
+        );
     }
 
     #[test]
-    fn parse_check_last_weekday() {
-        let cron = "0 0 * * 7L"; // the last saturday of every month
+    fn ends_of_time() {
+        let yearly: Cron = "0 0 1 1 *".parse().unwrap();
+        let first = DateTime::<Utc>::MIN_UTC;
+        let last_year = DateTime::<Utc>::MAX_UTC.year();
+        assert_eq!(yearly.next_from(first), Some(first));
+        assert_eq!(yearly.clone().iter(..).next(), Some(first));
+        assert_eq!(
+            yearly.clone().iter(..).next_back(),
+            Some(Utc.with_ymd_and_hms(last_year, 1, 1, 0, 0, 0).unwrap())
+        );
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-01-25 00:00",
-                "2020-02-29 00:00",
-                "2020-03-28 00:00",
-                "2020-04-25 00:00",
-                "2020-05-30 00:00",
-            ],
+        let new_years_eve: Cron = "59 23 31 12 *".parse().unwrap();
+        let last = Utc.with_ymd_and_hms(last_year, 12, 31, 23, 59, 0).unwrap();
+        assert_eq!(
+            new_years_eve.next_from(Utc.with_ymd_and_hms(last_year, 1, 1, 0, 0, 0).unwrap()),
+            Some(last)
+        );
+        assert_eq!(new_years_eve.next_after(last), None);
+        assert_eq!(new_years_eve.clone().iter(..).next_back(), Some(last));
+        assert_eq!(
+            new_years_eve
+                .clone()
+                .iter(..first.with_year(first.year() + 1).unwrap())
+                .next_back(),
+            Some(
+                Utc.with_ymd_and_hms(first.year(), 12, 31, 23, 59, 0)
+                    .unwrap()
+            )
         );
 
-        check_does_not_contain(
-            cron,
-            &[
-                "2020-01-31 00:00",
-                "2020-02-28 00:00",
-                "2020-03-31 00:00",
-                "2020-04-30 00:00",
-                "2020-05-31 00:00",
-            ],
-        )
+        // crossing a year, and the last day of February in and out of leap years
+        let start = Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 30).unwrap();
+        assert_eq!(
+            yearly.next_from(start),
+            Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+        );
+        let last_of_feb: Cron = "30 12 L 2 *".parse().unwrap();
+        let times = last_of_feb
+            .clone()
+            .iter_from(start)
+            .take(2)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            [
+                Utc.with_ymd_and_hms(2024, 2, 29, 12, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 2, 28, 12, 30, 0).unwrap(),
+            ]
+        );
+        assert_eq!(
+            last_of_feb.iter_ref(..start).next_back(),
+            Some(Utc.with_ymd_and_hms(2023, 2, 28, 12, 30, 0).unwrap())
+        );
     }
 
     #[test]
-    fn parse_check_nth_weekday() {
-        let cron = "0 0 * * SAT#5"; // the 5th saturday of every month
+    fn iter_ref() {
+        let start = Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 42).unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 3, 3, 9, 30, 0).unwrap();
+        for &cron in &["*/7 9-17 * * *", "0 12 29 2 *", "15 3 L * *", "0 0 ? * 6L"] {
+            let parsed: Cron = cron.parse().unwrap();
+            assert!(parsed
+                .iter_from_ref(start)
+                .take(50)
+                .eq(parsed.clone().iter_from(start).take(50)));
+            assert!(parsed
+                .iter_after_ref(start)
+                .take(50)
+                .eq(parsed.clone().iter_after(start).take(50)));
+
+            let times = parsed.iter_ref(start..end);
+            assert_eq!(
+                times.size_hint(),
+                parsed.clone().iter(start..end).size_hint()
+            );
+            assert!(
+                times.rev().eq(parsed.clone().iter(start..end).rev()),
+                "{}",
+                cron
+            );
+        }
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-02-29 00:00",
-                "2020-05-30 00:00",
-                "2020-08-29 00:00",
-                "2020-10-31 00:00",
-            ],
+        let never: Cron = "0 0 30 2 *".parse().unwrap();
+        assert_eq!(never.iter_from_ref(start).next(), None);
+    }
+
+    #[test]
+    fn interval_stats() {
+        let start = Utc.with_ymd_and_hms(2020, 2, 27, 13, 17, 42).unwrap();
+        let end = Utc.with_ymd_and_hms(2021, 3, 1, 9, 30, 0).unwrap();
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0,30 */5 1,15 * *",
+            "15 3 L * *",
+            "0 0 * * MON#2",
+            "0 0 13 * FRI",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            let times: Vec<_> = parsed.clone().iter(start..end).collect();
+            let gaps: Vec<_> = times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+            let expected = gaps.iter().min().map(|&min| IntervalStats {
+                count: times.len() as u64,
+                min,
+                max: *gaps.iter().max().unwrap(),
+                mean: Duration::milliseconds(
+                    (times[times.len() - 1] - times[0]).num_milliseconds() / gaps.len() as i64,
+                ),
+            });
+            assert_eq!(parsed.interval_stats(start..end), expected, "{}", cron);
+        }
+
+        // only matches once in the window
+        let cron: Cron = "0 12 29 2 *".parse().unwrap();
+        assert_eq!(cron.interval_stats(start..end), None);
+    }
+
+    #[test]
+    fn ord() {
+        let parse = |cron: &str| cron.parse::<Cron>().unwrap();
+        assert_eq!(
+            parse("*/15 * * * *").cmp(&parse("0,15,30,45 * * * *")),
+            cmp::Ordering::Equal
         );
 
-        check_does_not_contain(
-            cron,
-            &[
-                "2020-01-31 00:00",
-                "2020-02-28 00:00",
-                "2020-03-31 00:00",
-                "2020-04-30 00:00",
-                "2020-05-31 00:00",
-            ],
-        )
+        let mut crons = [
+            parse("0 0 L * ?"),
+            parse("0 12 * * MON"),
+            parse("0 0 L * ?"),
+            parse("0 12 * * 2"),
+            parse("*/5 * * * *"),
+        ]
+        .to_vec();
+        crons.sort();
+        crons.dedup();
+        assert_eq!(crons.len(), 3);
+        assert!(crons.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let mut and = CronOptions::new();
+        and.day_policy = DayPolicy::And;
+        let with_and = Cron::with_options("0 0 13 * FRI".parse().unwrap(), and).unwrap();
+        assert_ne!(with_and.cmp(&parse("0 0 13 * FRI")), cmp::Ordering::Equal);
     }
 
     #[test]
-    fn parse_check_steps() {
-        // all the impls step impls follow the same code, so i'll just test minutes for now
-        let cron = "*/15,30-59/10 0 * * *";
+    fn cmp_next() {
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 12, 0, 0).unwrap();
+        let noon: Cron = "0 12 * * *".parse().unwrap();
+        let midnight: Cron = "0 0 * * *".parse().unwrap();
+        let never: Cron = "0 0 31 2 *".parse().unwrap();
+
+        // the start itself counts as the next time
+        assert_eq!(noon.cmp_next(&midnight, start), cmp::Ordering::Less);
+        assert_eq!(midnight.cmp_next(&noon, start), cmp::Ordering::Greater);
+        assert_eq!(noon.cmp_next(&noon, start), cmp::Ordering::Equal);
+        assert_eq!(
+            midnight.cmp_next(&noon, start + Duration::minutes(1)),
+            cmp::Ordering::Less
+        );
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-01-01 00:00",
-                "2020-01-01 00:15",
-                "2020-01-01 00:30",
-                "2020-01-01 00:40",
-                "2020-01-01 00:45",
-                "2020-01-01 00:50",
-            ],
-        )
+        assert_eq!(never.cmp_next(&midnight, start), cmp::Ordering::Greater);
+        assert_eq!(midnight.cmp_next(&never, start), cmp::Ordering::Less);
+        assert_eq!(never.cmp_next(&never, start), cmp::Ordering::Equal);
     }
 
     #[test]
-    fn parse_check_overflow_range_step() {
-        // previous code assumed the start was before the end
-        let cron = "0 20-4/2 * * *";
+    fn fires_more_often_than() {
+        let minutes = Duration::minutes;
+        for &(cron, shortest) in &[
+            ("* * * * *", minutes(1)),
+            ("*/10 9-17 * * *", minutes(10)),
+            ("*/7 * * * *", minutes(4)),
+            ("0,45 * * * *", minutes(15)),
+            ("0 9,17 * * MON-FRI", minutes(8 * 60)),
+            ("59 23 * * *", minutes(24 * 60)),
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            assert!(!parsed.fires_more_often_than(shortest), "{}", cron);
+            assert!(
+                parsed.fires_more_often_than(shortest + minutes(1)),
+                "{}",
+                cron
+            );
+        }
 
-        check_does_contain(
-            cron,
-            &[
-                "2020-01-01 20:00",
-                "2020-01-01 22:00",
-                "2020-01-01 00:00",
-                "2020-01-01 02:00",
-                "2020-01-01 04:00",
-            ],
-        );
+        // compare against the shortest gap over one 400 year cycle
+        let start = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2401, 1, 1, 0, 0, 0).unwrap();
+        for &cron in &[
+            "0 0 L * *",
+            "30 0,23 L * *",
+            "0 0 LW * *",
+            "0 0 L-3W * *",
+            "0 12 15W * *",
+            "0 12 1W * *",
+            "0 0 * * MON#5",
+            "0 0 * * FRIL",
+            "0 0 1 * FRIL",
+            "0 0 13 * FRI",
+            "0 0 29 2 *",
+            "0 0 31 */2 *",
+        ] {
+            let parsed: Cron = cron.parse().unwrap();
+            let times: Vec<_> = parsed.clone().iter(start..end).collect();
+            let shortest = times
+                .windows(2)
+                .map(|pair| pair[1] - pair[0])
+                .min()
+                .unwrap();
+            assert!(!parsed.fires_more_often_than(shortest), "{}", cron);
+            assert!(
+                parsed.fires_more_often_than(shortest + minutes(1)),
+                "{}",
+                cron
+            );
+        }
+
+        let never: Cron = "0 0 30 2 *".parse().unwrap();
+        assert!(!never.fires_more_often_than(Duration::weeks(1000)));
     }
 
     /// Tests for future time iteration
@@ -1913,22 +6016,26 @@ mod tests {
             let start = match range.start_bound() {
                 Bound::Unbounded => Bound::Unbounded,
                 Bound::Included(start) => Bound::Included(
-                    Utc.datetime_from_str(start, FORMAT)
+                    NaiveDateTime::parse_from_str(start, FORMAT)
+                        .map(|time| time.and_utc())
                         .expect("Failed to parse start date"),
                 ),
                 Bound::Excluded(start) => Bound::Excluded(
-                    Utc.datetime_from_str(start, FORMAT)
+                    NaiveDateTime::parse_from_str(start, FORMAT)
+                        .map(|time| time.and_utc())
                         .expect("Failed to parse start date"),
                 ),
             };
             let end = match range.end_bound() {
                 Bound::Unbounded => Bound::Unbounded,
                 Bound::Included(end) => Bound::Included(
-                    Utc.datetime_from_str(end, FORMAT)
+                    NaiveDateTime::parse_from_str(end, FORMAT)
+                        .map(|time| time.and_utc())
                         .expect("Failed to parse start date"),
                 ),
                 Bound::Excluded(end) => Bound::Excluded(
-                    Utc.datetime_from_str(end, FORMAT)
+                    NaiveDateTime::parse_from_str(end, FORMAT)
+                        .map(|time| time.and_utc())
                         .expect("Failed to parse start date"),
                 ),
             };
@@ -1937,7 +6044,8 @@ mod tests {
             let times = times
                 .into_iter()
                 .map(|&time| {
-                    Utc.datetime_from_str(time, FORMAT)
+                    NaiveDateTime::parse_from_str(time, FORMAT)
+                        .map(|time| time.and_utc())
                         .expect("Failed to parse expected date")
                 })
                 .collect::<Vec<_>>();
@@ -1956,6 +6064,15 @@ mod tests {
             );
         }
 
+        #[test]
+        fn range_ending_at_midnight_stops_at_the_bound() {
+            assert(
+                "* * * * *",
+                "2021-01-01 23:58".."2021-01-02 00:00",
+                &["2021-01-01 23:58", "2021-01-01 23:59"],
+            );
+        }
+
         #[test]
         fn exclusive_bound_range_over_three_minutes_only_has_one() {
             assert(
@@ -1994,7 +6111,7 @@ mod tests {
             assert(
                 "* * * * *",
                 (
-                    Bound::Excluded(&chrono::MAX_DATETIME.format(FORMAT).to_string().as_str()),
+                    Bound::Excluded(&DateTime::<Utc>::MAX_UTC.format(FORMAT).to_string().as_str()),
                     Bound::Unbounded,
                 ),
                 &[],
@@ -2007,7 +6124,7 @@ mod tests {
                 "* * * * *",
                 (
                     Bound::Unbounded,
-                    Bound::Excluded(chrono::MIN_DATETIME.format(FORMAT).to_string().as_str()),
+                    Bound::Excluded(DateTime::<Utc>::MIN_UTC.format(FORMAT).to_string().as_str()),
                 ),
                 &[],
             )
@@ -2037,6 +6154,38 @@ mod tests {
             )
         }
 
+        #[test]
+        fn ignored_days() {
+            assert(
+                "0 0 ? * MON",
+                "2020-06-01 00:00".."2020-06-16 00:00",
+                &["2020-06-01 00:00", "2020-06-08 00:00", "2020-06-15 00:00"],
+            );
+            assert(
+                "0 0 1 * ?",
+                "2020-06-01 00:00".."2020-09-01 00:00",
+                &["2020-06-01 00:00", "2020-07-01 00:00", "2020-08-01 00:00"],
+            );
+        }
+
+        #[test]
+        fn day_policy_and() {
+            let mut options = CronOptions::new();
+            options.day_policy = DayPolicy::And;
+            let cron = Cron::with_options("0 0 13 * FRI".parse().unwrap(), options).unwrap();
+            let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+
+            let times = cron.iter_from(start).take(3).collect::<Vec<_>>();
+            assert_eq!(
+                times,
+                [
+                    Utc.with_ymd_and_hms(2020, 3, 13, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2020, 11, 13, 0, 0, 0).unwrap(),
+                    Utc.with_ymd_and_hms(2021, 8, 13, 0, 0, 0).unwrap(),
+                ]
+            );
+        }
+
         #[test]
         fn feb_edges() {
             // fun edge cases in february
@@ -2099,3 +6248,51 @@ mod tests {
         }
     }
 }
+
+#[cfg(all(test, not(feature = "alloc")))]
+mod no_alloc_tests {
+    use super::*;
+
+    /// `0 0,12 * * MON-FRI` encoded with `Cron::to_bytes`
+    const WEEKDAYS: [u8; 20] = [
+        1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1, 16, 0, 255, 15, 0, 0, 0, 0, 62,
+    ];
+    /// `30 9 L * ?` encoded with `Cron::to_bytes`
+    const LAST_DAY: [u8; 20] = [
+        1, 35, 0, 0, 0, 64, 0, 0, 0, 0, 0, 2, 0, 255, 15, 0, 0, 0, 0, 0,
+    ];
+
+    static FROM_PARTS: Cron = Cron::from_parts(
+        MinuteSet::single(0),
+        HourSet::single(0).with(12),
+        DayOfMonthSet::ALL,
+        MonthSet::ALL,
+        DayOfWeekSet::range(Weekday::Mon, Weekday::Fri),
+    );
+
+    fn time(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn matches_decoded_and_const_values() {
+        let weekdays = Cron::from_bytes(&WEEKDAYS).unwrap();
+        assert_eq!(weekdays, FROM_PARTS);
+
+        // 2021-01-01 is a Friday
+        assert!(weekdays.contains(time(2021, 1, 1, 12, 0)));
+        assert!(!weekdays.contains(time(2021, 1, 2, 12, 0)));
+        assert_eq!(
+            weekdays.next_after(time(2021, 1, 1, 12, 0)),
+            Some(time(2021, 1, 4, 0, 0))
+        );
+        let mut times = FROM_PARTS.iter_from_ref(time(2021, 1, 1, 0, 0));
+        assert_eq!(times.nth(3), Some(time(2021, 1, 4, 12, 0)));
+
+        let last_day = Cron::from_bytes(&LAST_DAY).unwrap();
+        assert_eq!(
+            last_day.next_from(time(2021, 2, 1, 0, 0)),
+            Some(time(2021, 2, 28, 9, 30))
+        );
+    }
+}
@@ -0,0 +1,542 @@
+//! Warnings about cron expressions that parse fine but likely don't do what was meant, like an
+//! expression that never fires or a step that only ever matches one value.
+//!
+//! Every lint has a machine-readable [`LintCode`] and the byte range of the expression it's
+//! about, so tools can point at the part of the expression to fix.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::ToString, vec::Vec};
+
+use crate::parse::{
+    exprs_mask, CronExpr, CronParseError, DayOfMonthExpr, DayOfWeekExpr, Dialect, Expr, ExprValue,
+    Exprs, FieldKind, OrsExpr,
+};
+use crate::Cron;
+use core::fmt::{self, Display, Formatter};
+use core::ops::Range;
+
+/// The kind of mistake a [`Lint`] warns about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LintCode {
+    /// The expression never matches any time, like `0 0 30 2 *`
+    NeverFires,
+    /// Both day fields are restricted, so a day matching either field matches, like
+    /// `0 0 13 * FRI` matching every 13th and every Friday
+    DaysEitherMatch,
+    /// A step is larger than the range it steps over, so only the start of the range is
+    /// matched, like `5-10/10`
+    StepLargerThanRange,
+    /// A value, range, step, or special expression only matches days or times the rest of the
+    /// field already matches, like the `1` in `1,1-5`
+    RedundantValues,
+    /// A range or step ends before it starts, so it wraps around past the last value of the
+    /// field, like `22-2` or `FRI-MON`. Wrapping ranges are usually meant, so this is only
+    /// reported by [`Cron::from_expr_checked`].
+    ///
+    /// [`Cron::from_expr_checked`]: ../struct.Cron.html#method.from_expr_checked
+    WrappingRange,
+}
+
+impl LintCode {
+    /// Returns the name of the code, a stable kebab-case identifier like `never-fires`
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintCode::NeverFires => "never-fires",
+            LintCode::DaysEitherMatch => "days-either-match",
+            LintCode::StepLargerThanRange => "step-larger-than-range",
+            LintCode::RedundantValues => "redundant-values",
+            LintCode::WrappingRange => "wrapping-range",
+        }
+    }
+}
+
+impl Display for LintCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// A warning about part of a cron expression, returned by [`lint`] and [`lint_str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Lint {
+    /// What the lint warns about
+    pub code: LintCode,
+    /// The field the lint is about, or `None` if it's about more than one field
+    pub field: Option<FieldKind>,
+    /// The byte range of the part of the expression the lint is about. Ranges from [`lint_str`]
+    /// are in the string linted, and ranges from [`lint`] are in the expression written with its
+    /// `Display` implementation.
+    pub span: Range<usize>,
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.code {
+            LintCode::NeverFires => f.write_str("The expression never matches any time"),
+            LintCode::DaysEitherMatch => f.write_str(
+                "Both day fields are restricted, so a day matching either field matches",
+            ),
+            LintCode::StepLargerThanRange => {
+                f.write_str("The step is larger than its range, so only the start is matched")
+            }
+            LintCode::RedundantValues => {
+                f.write_str("The values are already matched by the rest of the field")
+            }
+            LintCode::WrappingRange => {
+                f.write_str("The range ends before it starts, so it wraps around the field")
+            }
+        }?;
+        write!(f, " ({}", self.code)?;
+        if let Some(field) = self.field {
+            write!(f, " in {}", field)?;
+        }
+        write!(f, " at {}..{})", self.span.start, self.span.end)
+    }
+}
+
+/// Lints an expression. Spans are byte ranges in the expression as written by its `Display`
+/// implementation.
+///
+/// # Example
+/// ```
+/// use saffron::lint::{lint, LintCode};
+/// use saffron::parse::FieldKind;
+///
+/// let lints = lint(&"5-10/10 0 30 2 *".parse().unwrap());
+/// assert_eq!(lints[0].code, LintCode::NeverFires);
+/// assert_eq!(lints[1].code, LintCode::StepLargerThanRange);
+/// assert_eq!(lints[1].field, Some(FieldKind::Minutes));
+/// assert_eq!(lints[1].span, 0..7);
+/// ```
+pub fn lint(expr: &CronExpr) -> Vec<Lint> {
+    Linter::new(&expr.to_string()).lint(expr, &Cron::new(expr.clone()))
+}
+
+/// Parses and lints an expression in a dialect. Spans are byte ranges in the string.
+///
+/// # Example
+/// ```
+/// use saffron::lint::{lint_str, LintCode};
+/// use saffron::parse::{Dialect, FieldKind};
+///
+/// let lints = lint_str("0 0  1,1-5 * ?", Dialect::Quartz).unwrap();
+/// assert_eq!(lints.len(), 1);
+/// assert_eq!(lints[0].code, LintCode::RedundantValues);
+/// assert_eq!(lints[0].field, Some(FieldKind::DaysOfMonth));
+/// assert_eq!(lints[0].span, 5..6);
+/// ```
+pub fn lint_str(expr: &str, dialect: Dialect) -> Result<Vec<Lint>, CronParseError> {
+    let parsed = CronExpr::from_str_with(expr, dialect)?;
+    let cron = Cron::new(parsed.clone());
+    Ok(Linter::new(expr).lint(&parsed, &cron))
+}
+
+impl Cron {
+    /// Compiles an expression like [`Cron::new`], also returning the [`Lint`]s found in it.
+    /// Besides the lints returned by [`lint`], this reports ranges and steps that wrap around
+    /// past the last value of their field with [`LintCode::WrappingRange`]. Spans are byte
+    /// ranges in the expression as written by its `Display` implementation.
+    ///
+    /// [`Cron::new`]: ../struct.Cron.html#method.new
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use saffron::lint::LintCode;
+    ///
+    /// let (cron, lints) = Cron::from_expr_checked("0 22-2 30 2 *".parse().unwrap());
+    /// assert!(!cron.any());
+    /// assert_eq!(lints[0].code, LintCode::NeverFires);
+    /// assert_eq!(lints[1].code, LintCode::WrappingRange);
+    /// assert_eq!(lints[1].span, 2..6);
+    /// ```
+    pub fn from_expr_checked(expr: CronExpr) -> (Cron, Vec<Lint>) {
+        let mut linter = Linter::new(&expr.to_string());
+        linter.wrapping = true;
+        let cron = Cron::new(expr.clone());
+        let lints = linter.lint(&expr, &cron);
+        (cron, lints)
+    }
+}
+
+/// Finds the lints in an expression, placing them in the text the expression was read from.
+struct Linter {
+    /// The range of the whole expression, without leading or trailing spaces
+    whole: Range<usize>,
+    /// The range of each field, if the text is split into fields like the expression
+    fields: Vec<Range<usize>>,
+    /// The ranges of the items in each field, split by commas
+    items: Vec<Vec<Range<usize>>>,
+    /// Whether to report ranges and steps wrapping around their field
+    wrapping: bool,
+    lints: Vec<Lint>,
+}
+
+impl Linter {
+    fn new(text: &str) -> Self {
+        let mut fields = Vec::new();
+        let mut start = None;
+        for (i, c) in text.char_indices().chain(Some((text.len(), ' '))) {
+            match (start, c == ' ' || c == '\t') {
+                (None, false) => start = Some(i),
+                (Some(field_start), true) => {
+                    fields.push(field_start..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        let items = fields
+            .iter()
+            .map(|field| {
+                let mut items = Vec::new();
+                let mut start = field.start;
+                for (i, c) in text[field.clone()].char_indices() {
+                    if c == ',' {
+                        items.push(start..field.start + i);
+                        start = field.start + i + 1;
+                    }
+                }
+                items.push(start..field.end);
+                items
+            })
+            .collect();
+
+        let whole = match (fields.first(), fields.last()) {
+            (Some(first), Some(last)) => first.start..last.end,
+            _ => 0..text.len(),
+        };
+        Self {
+            whole,
+            fields,
+            items,
+            wrapping: false,
+            lints: Vec::new(),
+        }
+    }
+
+    fn lint(mut self, expr: &CronExpr, cron: &Cron) -> Vec<Lint> {
+        if !cron.any() {
+            self.push(LintCode::NeverFires, None, self.whole.clone());
+        }
+
+        let is_restricted_dom = !matches!(expr.doms, DayOfMonthExpr::All | DayOfMonthExpr::Ignored);
+        let is_restricted_dow = !matches!(expr.dows, DayOfWeekExpr::All | DayOfWeekExpr::Ignored);
+        if is_restricted_dom && is_restricted_dow {
+            let span = match (
+                self.field(FieldKind::DaysOfMonth),
+                self.field(FieldKind::DaysOfWeek),
+            ) {
+                (Some(doms), Some(dows)) => doms.start..dows.end,
+                _ => self.whole.clone(),
+            };
+            self.push(LintCode::DaysEitherMatch, None, span);
+        }
+
+        self.expr(FieldKind::Minutes, &expr.minutes);
+        self.expr(FieldKind::Hours, &expr.hours);
+        match &expr.doms {
+            DayOfMonthExpr::Many(exprs) | DayOfMonthExpr::ClosestWeekdays(exprs) => {
+                self.exprs(FieldKind::DaysOfMonth, exprs)
+            }
+            DayOfMonthExpr::Specials(specials) => self.specials(FieldKind::DaysOfMonth, specials),
+            _ => {}
+        }
+        self.expr(FieldKind::Months, &expr.months);
+        match &expr.dows {
+            DayOfWeekExpr::Many(exprs) => self.exprs(FieldKind::DaysOfWeek, exprs),
+            DayOfWeekExpr::Specials(specials) => self.specials(FieldKind::DaysOfWeek, specials),
+            _ => {}
+        }
+        self.expr(FieldKind::Weeks, &expr.weeks);
+
+        self.lints
+    }
+
+    fn push(&mut self, code: LintCode, field: Option<FieldKind>, span: Range<usize>) {
+        self.lints.push(Lint { code, field, span });
+    }
+
+    /// Returns the position of a field in the text, if the text has a field in that position
+    fn field(&self, field: FieldKind) -> Option<Range<usize>> {
+        let index = FieldKind::ALL.iter().position(|&kind| kind == field)?;
+        self.fields.get(index).cloned()
+    }
+
+    /// Returns the position of an item in a field, falling back to the whole field or the whole
+    /// expression if the text doesn't have the item
+    fn item(&self, field: FieldKind, index: usize) -> Range<usize> {
+        let position = FieldKind::ALL
+            .iter()
+            .position(|&kind| kind == field)
+            .expect("Every field kind is listed");
+        match self.items.get(position) {
+            Some(items) => match items.get(index) {
+                // the weeks field starts with a 'W' before the first item
+                Some(item) if field == FieldKind::Weeks && index == 0 => item.start + 1..item.end,
+                Some(item) => item.clone(),
+                None => self.fields[position].clone(),
+            },
+            None => self.whole.clone(),
+        }
+    }
+
+    fn expr<E>(&mut self, field: FieldKind, expr: &Expr<E>)
+    where
+        E: Copy + ExprValue,
+        u8: From<E>,
+    {
+        if let Expr::Many(exprs) = expr {
+            self.exprs(field, exprs);
+        }
+    }
+
+    fn exprs<E>(&mut self, field: FieldKind, exprs: &Exprs<E>)
+    where
+        E: Copy + ExprValue,
+        u8: From<E>,
+    {
+        let masks = exprs
+            .iter()
+            .map(|&expr| exprs_mask(&Exprs::new(expr)))
+            .collect::<Vec<_>>();
+
+        let mut redundant = Vec::with_capacity(masks.len());
+        for (i, expr) in exprs.iter().enumerate() {
+            // a step past the end of its range only matches the start
+            if let OrsExpr::Step { .. } = expr {
+                if masks[i].count_ones() == 1 {
+                    let span = self.item(field, i);
+                    self.push(LintCode::StepLargerThanRange, Some(field), span);
+                }
+            }
+
+            if self.wrapping {
+                if let OrsExpr::Range(start, end) | OrsExpr::Step { start, end, .. } = *expr {
+                    if u8::from(end) < u8::from(start) {
+                        let span = self.item(field, i);
+                        self.push(LintCode::WrappingRange, Some(field), span);
+                    }
+                }
+            }
+
+            // an item is redundant if the other items still in the field match its values
+            let others = masks
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i && redundant.get(j) != Some(&true))
+                .fold(0, |others, (_, mask)| others | mask);
+            let is_redundant = masks[i] & !others == 0;
+            if is_redundant {
+                let span = self.item(field, i);
+                self.push(LintCode::RedundantValues, Some(field), span);
+            }
+            redundant.push(is_redundant);
+        }
+    }
+
+    fn specials<T: PartialEq>(&mut self, field: FieldKind, specials: &[T]) {
+        for (i, special) in specials.iter().enumerate() {
+            if specials[..i].contains(special) {
+                let span = self.item(field, i);
+                self.push(LintCode::RedundantValues, Some(field), span);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[track_caller]
+    fn codes(expr: &str) -> Vec<(LintCode, Option<FieldKind>, &str)> {
+        lint_str(expr, Dialect::Extended)
+            .expect("Valid cron expression")
+            .into_iter()
+            .map(|lint| (lint.code, lint.field, &expr[lint.span]))
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        for &expr in &[
+            "* * * * *",
+            "*/15 9-17 ? * MON-FRI",
+            "0 0 L * ?",
+            "0 0 1W,15W * ?",
+            "0 22-2 * * *",
+            "0 0 29 2 *",
+            "@daily",
+        ] {
+            assert_eq!(codes(expr), [], "{}", expr);
+        }
+    }
+
+    #[test]
+    fn never_fires() {
+        assert_eq!(
+            codes("0 0 30 2 *"),
+            [(LintCode::NeverFires, None, "0 0 30 2 *")]
+        );
+        assert_eq!(
+            codes("0 0 31 APR,JUN ?"),
+            [(LintCode::NeverFires, None, "0 0 31 APR,JUN ?")]
+        );
+    }
+
+    #[test]
+    fn days_either_match() {
+        assert_eq!(
+            codes("0 0 13 * FRI"),
+            [(LintCode::DaysEitherMatch, None, "13 * FRI")]
+        );
+        assert_eq!(
+            codes("0 0 L * 6L"),
+            [(LintCode::DaysEitherMatch, None, "L * 6L")]
+        );
+    }
+
+    #[test]
+    fn steps() {
+        let minutes = Some(FieldKind::Minutes);
+        assert_eq!(
+            codes("5-10/10,30 * * * *"),
+            [(LintCode::StepLargerThanRange, minutes, "5-10/10")]
+        );
+        assert_eq!(
+            codes("0 22-1/4 * * *"),
+            [(
+                LintCode::StepLargerThanRange,
+                Some(FieldKind::Hours),
+                "22-1/4"
+            )]
+        );
+        assert_eq!(codes("0 22-2/4 * * *"), []);
+        assert_eq!(
+            codes("* * * * * W50-2/6"),
+            [(
+                LintCode::StepLargerThanRange,
+                Some(FieldKind::Weeks),
+                "50-2/6"
+            )]
+        );
+    }
+
+    #[test]
+    fn redundant_values() {
+        let doms = Some(FieldKind::DaysOfMonth);
+        assert_eq!(
+            codes("0 0 1,1-5 * ?"),
+            [(LintCode::RedundantValues, doms, "1")]
+        );
+        // only one of a repeated value is redundant
+        assert_eq!(
+            codes("0 0 3,3 * ?"),
+            [(LintCode::RedundantValues, doms, "3")]
+        );
+        assert_eq!(
+            codes("0 0 1-5,3,3 * ?"),
+            [
+                (LintCode::RedundantValues, doms, "3"),
+                (LintCode::RedundantValues, doms, "3")
+            ]
+        );
+        assert_eq!(
+            codes("0 0 1,1W * ?"),
+            [(LintCode::RedundantValues, doms, "1")]
+        );
+        assert_eq!(
+            codes("0 0 ? * MON#1,FRIL,MON#1"),
+            [(
+                LintCode::RedundantValues,
+                Some(FieldKind::DaysOfWeek),
+                "MON#1"
+            )]
+        );
+        assert_eq!(
+            codes("*/20,0-40/20 * * * *"),
+            [(LintCode::RedundantValues, Some(FieldKind::Minutes), "*/20")]
+        );
+    }
+
+    #[test]
+    fn from_expr_checked() {
+        let checked = |expr: &str| {
+            let (cron, lints) = Cron::from_expr_checked(expr.parse().unwrap());
+            assert_eq!(cron, Cron::new(expr.parse().unwrap()));
+            let written = expr.parse::<CronExpr>().unwrap().to_string();
+            lints
+                .into_iter()
+                .map(|lint| (lint.code, lint.field, written[lint.span].to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(checked("*/15 9-17 * * MON-FRI"), []);
+        assert_eq!(
+            checked("50-10 22-2/2 * * FRI-MON"),
+            [
+                (
+                    LintCode::WrappingRange,
+                    Some(FieldKind::Minutes),
+                    "50-10".to_string()
+                ),
+                (
+                    LintCode::WrappingRange,
+                    Some(FieldKind::Hours),
+                    "22-2/2".to_string()
+                ),
+                (
+                    LintCode::WrappingRange,
+                    Some(FieldKind::DaysOfWeek),
+                    "6-2".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            checked("0,0 0 30 2 *"),
+            [
+                (LintCode::NeverFires, None, "0,0 0 30 2 *".to_string()),
+                (
+                    LintCode::RedundantValues,
+                    Some(FieldKind::Minutes),
+                    "0".to_string()
+                ),
+            ]
+        );
+
+        // plain linting doesn't report wrapping ranges
+        assert_eq!(codes("0 22-2 * * FRI-MON"), []);
+    }
+
+    #[test]
+    fn display_spans() {
+        let lints = lint(&"5-10/10 0 30 2 *".parse().unwrap());
+        assert_eq!(
+            lints,
+            vec![
+                Lint {
+                    code: LintCode::NeverFires,
+                    field: None,
+                    span: 0..16,
+                },
+                Lint {
+                    code: LintCode::StepLargerThanRange,
+                    field: Some(FieldKind::Minutes),
+                    span: 0..7,
+                },
+            ]
+        );
+        assert_eq!(
+            lints[1].to_string(),
+            "The step is larger than its range, so only the start is matched \
+             (step-larger-than-range in minutes at 0..7)"
+        );
+    }
+}
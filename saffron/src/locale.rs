@@ -0,0 +1,400 @@
+//! Localized month and day of the week names, translated to the English names before an
+//! expression is parsed so users can write schedules in their own language.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::spanned::split_fields;
+use chrono::Weekday;
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAYS_OF_WEEK: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// A table of localized names for the months and days of the week, used when parsing with
+/// [`ParseOptions::locale`]. Names are matched without regard to case, and a name can be used
+/// anywhere the English name can, like `LUN-VEN` or `VENL`. The English names still parse, but
+/// a name in the table takes precedence over an English name written the same way.
+///
+/// [`ParseOptions::locale`]: parse/struct.ParseOptions.html#structfield.locale
+///
+/// # Example
+/// ```
+/// use saffron::parse::{CronExpr, Locale, ParseOptions};
+///
+/// let mut options = ParseOptions::new();
+/// options.locale = Some(&Locale::GERMAN);
+///
+/// let expr = CronExpr::from_str_with_options("0 9 * MÄR-MAI MO-FR", options).unwrap();
+/// assert_eq!(expr, "0 9 * MAR-MAY MON-FRI".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Locale {
+    months: &'static [(&'static str, u8)],
+    days_of_week: &'static [(&'static str, Weekday)],
+}
+
+impl Locale {
+    /// German names, like `MÄR` and `MO`
+    pub const GERMAN: Locale = Locale::new(
+        &[
+            ("JAN", 1),
+            ("FEB", 2),
+            ("MÄR", 3),
+            ("MRZ", 3),
+            ("APR", 4),
+            ("MAI", 5),
+            ("JUN", 6),
+            ("JUL", 7),
+            ("AUG", 8),
+            ("SEP", 9),
+            ("OKT", 10),
+            ("NOV", 11),
+            ("DEZ", 12),
+        ],
+        &[
+            ("SO", Weekday::Sun),
+            ("MO", Weekday::Mon),
+            ("DI", Weekday::Tue),
+            ("MI", Weekday::Wed),
+            ("DO", Weekday::Thu),
+            ("FR", Weekday::Fri),
+            ("SA", Weekday::Sat),
+        ],
+    );
+
+    /// French names, like `FÉVR` and `LUN`
+    pub const FRENCH: Locale = Locale::new(
+        &[
+            ("JANV", 1),
+            ("FÉVR", 2),
+            ("FEVR", 2),
+            ("MARS", 3),
+            ("AVR", 4),
+            ("MAI", 5),
+            ("JUIN", 6),
+            ("JUIL", 7),
+            ("AOÛT", 8),
+            ("AOUT", 8),
+            ("SEPT", 9),
+            ("OCT", 10),
+            ("NOV", 11),
+            ("DÉC", 12),
+            ("DEC", 12),
+        ],
+        &[
+            ("DIM", Weekday::Sun),
+            ("LUN", Weekday::Mon),
+            ("MAR", Weekday::Tue),
+            ("MER", Weekday::Wed),
+            ("JEU", Weekday::Thu),
+            ("VEN", Weekday::Fri),
+            ("SAM", Weekday::Sat),
+        ],
+    );
+
+    /// Spanish names, like `ENE` and `MIÉ`
+    pub const SPANISH: Locale = Locale::new(
+        &[
+            ("ENE", 1),
+            ("FEB", 2),
+            ("MAR", 3),
+            ("ABR", 4),
+            ("MAY", 5),
+            ("JUN", 6),
+            ("JUL", 7),
+            ("AGO", 8),
+            ("SEP", 9),
+            ("SET", 9),
+            ("OCT", 10),
+            ("NOV", 11),
+            ("DIC", 12),
+        ],
+        &[
+            ("DOM", Weekday::Sun),
+            ("LUN", Weekday::Mon),
+            ("MAR", Weekday::Tue),
+            ("MIÉ", Weekday::Wed),
+            ("MIE", Weekday::Wed),
+            ("JUE", Weekday::Thu),
+            ("VIE", Weekday::Fri),
+            ("SÁB", Weekday::Sat),
+            ("SAB", Weekday::Sat),
+        ],
+    );
+
+    /// Italian names, like `GIU` and `LUN`
+    pub const ITALIAN: Locale = Locale::new(
+        &[
+            ("GEN", 1),
+            ("FEB", 2),
+            ("MAR", 3),
+            ("APR", 4),
+            ("MAG", 5),
+            ("GIU", 6),
+            ("LUG", 7),
+            ("AGO", 8),
+            ("SET", 9),
+            ("OTT", 10),
+            ("NOV", 11),
+            ("DIC", 12),
+        ],
+        &[
+            ("DOM", Weekday::Sun),
+            ("LUN", Weekday::Mon),
+            ("MAR", Weekday::Tue),
+            ("MER", Weekday::Wed),
+            ("GIO", Weekday::Thu),
+            ("VEN", Weekday::Fri),
+            ("SAB", Weekday::Sat),
+        ],
+    );
+
+    /// Creates a table from the names of the months, numbered 1-12, and the names of the days of
+    /// the week. A value can have more than one name, like a name with and without accents.
+    /// Months outside of 1-12 are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::Weekday;
+    /// use saffron::parse::{CronExpr, Locale, ParseOptions};
+    ///
+    /// const DUTCH: Locale = Locale::new(
+    ///     &[("MRT", 3), ("MEI", 5), ("OKT", 10)],
+    ///     &[("MA", Weekday::Mon), ("WO", Weekday::Wed), ("ZA", Weekday::Sat), ("ZO", Weekday::Sun)],
+    /// );
+    ///
+    /// let mut options = ParseOptions::new();
+    /// options.locale = Some(&DUTCH);
+    ///
+    /// let expr = CronExpr::from_str_with_options("0 0 * MRT,MEI ZA,ZO", options).unwrap();
+    /// assert_eq!(expr, "0 0 * MAR,MAY SAT,SUN".parse().unwrap());
+    /// ```
+    pub const fn new(
+        months: &'static [(&'static str, u8)],
+        days_of_week: &'static [(&'static str, Weekday)],
+    ) -> Self {
+        Self {
+            months,
+            days_of_week,
+        }
+    }
+
+    /// Returns the month (1-12) with the given name, if it's in the table
+    pub fn month(&self, name: &str) -> Option<u8> {
+        self.months
+            .iter()
+            .find(|&&(month_name, month)| (1..=12).contains(&month) && eq_names(month_name, name))
+            .map(|&(_, month)| month)
+    }
+
+    /// Returns the day of the week with the given name, if it's in the table
+    pub fn day_of_week(&self, name: &str) -> Option<Weekday> {
+        self.days_of_week
+            .iter()
+            .find(|&&(day_name, _)| eq_names(day_name, name))
+            .map(|&(_, day)| day)
+    }
+
+    /// Returns the English name of a localized name in the months or days of the week field.
+    /// A name in the day of the week field can end with the `L` of the last day expressions.
+    fn english(&self, field: usize, name: &str) -> Option<(&'static str, bool)> {
+        match field {
+            3 => self
+                .month(name)
+                .map(|month| (MONTHS[usize::from(month) - 1], false)),
+            4 => {
+                let day = |name| {
+                    self.day_of_week(name)
+                        .map(|day: Weekday| day.num_days_from_sunday())
+                };
+                match (day(name), name.strip_suffix('L').and_then(day)) {
+                    (Some(day), _) => Some((DAYS_OF_WEEK[day as usize], false)),
+                    (None, Some(day)) => Some((DAYS_OF_WEEK[day as usize], true)),
+                    (None, None) => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Replaces the localized names in the months and days of the week fields of an expression
+    /// with the English names.
+    pub(crate) fn translate(&self, s: &str) -> Translated {
+        let mut translated = Translated {
+            text: String::with_capacity(s.len()),
+            replaced: Vec::new(),
+        };
+        let mut copied = 0;
+        for (field, range) in split_fields(s).into_iter().enumerate() {
+            let mut name_start = None;
+            let text = &s[range.clone()];
+            for (i, c) in text.char_indices().chain(Some((text.len(), ' '))) {
+                match (name_start, c.is_alphabetic()) {
+                    (None, true) => name_start = Some(i),
+                    (Some(start), false) => {
+                        name_start = None;
+                        let name = &text[start..i];
+                        if let Some((english, last)) = self.english(field, name) {
+                            let start = range.start + start;
+                            translated.text.push_str(&s[copied..start]);
+                            let at = translated.text.len();
+                            translated.text.push_str(english);
+                            if last {
+                                translated.text.push('L');
+                            }
+                            translated.replaced.push(Replaced {
+                                at,
+                                len: translated.text.len() - at,
+                                original_at: start,
+                                original_len: name.len(),
+                            });
+                            copied = range.start + i;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        translated.text.push_str(&s[copied..]);
+        translated
+    }
+}
+
+/// Returns whether two names are the same, ignoring case
+fn eq_names(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_uppercase)
+        .eq(b.chars().flat_map(char::to_uppercase))
+}
+
+/// An expression with its localized names replaced with the English names.
+pub(crate) struct Translated {
+    /// The translated expression
+    pub(crate) text: String,
+    replaced: Vec<Replaced>,
+}
+
+/// A name replaced in a translated expression
+struct Replaced {
+    /// The byte offset of the English name in the translated expression
+    at: usize,
+    len: usize,
+    /// The byte offset of the localized name in the original expression
+    original_at: usize,
+    original_len: usize,
+}
+
+impl Translated {
+    /// Converts a byte offset in the translated expression to the matching offset in the
+    /// original expression. An offset in a replaced name is the start of the localized name.
+    pub(crate) fn original_at(&self, at: usize) -> usize {
+        let mut original = at;
+        for replaced in &self.replaced {
+            if at < replaced.at {
+                break;
+            }
+            if at < replaced.at + replaced.len {
+                return replaced.original_at;
+            }
+            original = original - replaced.len + replaced.original_len;
+        }
+        original
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{CronExpr, CronParseError, Dialect, FieldKind, Locale, ParseOptions};
+
+    fn parse(s: &str, locale: &'static Locale) -> Result<CronExpr, CronParseError> {
+        let mut options = ParseOptions::new();
+        options.locale = Some(locale);
+        CronExpr::from_str_with_options(s, options)
+    }
+
+    #[test]
+    fn built_in_locales() {
+        let expected: CronExpr = "0 0 * MAR,DEC MON-FRI".parse().unwrap();
+        assert_eq!(
+            parse("0 0 * mär,DEZ Mo-Fr", &Locale::GERMAN),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse("0 0 * MARS,DÉC LUN-VEN", &Locale::FRENCH),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse("0 0 * MAR,DIC LUN-VIE", &Locale::SPANISH),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            parse("0 0 * MAR,DIC LUN-VEN", &Locale::ITALIAN),
+            Ok(expected)
+        );
+
+        assert_eq!(
+            parse("0 0 * * MIÉ,sáb", &Locale::SPANISH),
+            parse("0 0 * * MIE,SAB", &Locale::SPANISH)
+        );
+    }
+
+    #[test]
+    fn names_only_in_their_fields() {
+        // MAR is Tuesday in the day of the week field and March in the months field
+        assert_eq!(
+            parse("0 0 * MAR MAR", &Locale::SPANISH),
+            Ok("0 0 * MAR TUE".parse().unwrap())
+        );
+        // the day of the month field has no names
+        assert!(parse("0 0 L-MO * ?", &Locale::GERMAN).is_err());
+    }
+
+    #[test]
+    fn english_and_special_expressions() {
+        assert_eq!(
+            parse("0 0 ? JAN-MAI FRI#2,VENL", &Locale::FRENCH),
+            Ok("0 0 ? JAN-MAY FRI#2,FRIL".parse().unwrap())
+        );
+        assert_eq!(
+            parse("0 0 ? * L", &Locale::FRENCH),
+            Ok("0 0 ? * L".parse().unwrap())
+        );
+
+        let mut options = ParseOptions::new();
+        options.dialect = Dialect::Unix;
+        options.locale = Some(&Locale::GERMAN);
+        assert_eq!(
+            CronExpr::from_str_with_options("0 0 * * SO", options),
+            CronExpr::from_str_with("0 0 * * 0", Dialect::Unix)
+        );
+    }
+
+    #[test]
+    fn error_positions() {
+        // the error is after a name longer than the English name
+        assert_eq!(
+            parse("0 0 * AOÛT-DÉC 8", &Locale::FRENCH),
+            Err(CronParseError::ValueOutOfRange {
+                field: FieldKind::DaysOfWeek,
+                value: 8,
+                min: 1,
+                max: 7,
+                at: 17,
+            })
+        );
+        assert_eq!(
+            parse("0 0 * JANV-FOO *", &Locale::FRENCH).map_err(|err| err.position()),
+            Err(11)
+        );
+        let mut options = ParseOptions::new();
+        options.trim_whitespace = true;
+        options.locale = Some(&Locale::GERMAN);
+        assert_eq!(
+            CronExpr::from_str_with_options("  0 0 * * MI-XX", options)
+                .map_err(|err| err.position()),
+            Err(13)
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Parsing lists of cron expressions at once, like the schedules of a Worker, with errors
+//! indexed by the expression they're for.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::parse::CronParseError;
+use crate::Cron;
+
+/// Parses and compiles every expression in a list. If any expression fails to parse, returns
+/// the error of each one that failed along with its index in the list.
+///
+/// # Example
+/// ```
+/// use saffron::parse::{CronParseError, FieldKind};
+///
+/// let crons = saffron::parse_many(vec!["0 9 * * MON", "@daily"]).unwrap();
+/// assert_eq!(crons, ["0 9 * * MON".parse().unwrap(), "@daily".parse().unwrap()]);
+///
+/// let errors = saffron::parse_many(vec!["0 9 * * MON", "0 24 * * *", "0 0 * *"]).unwrap_err();
+/// assert_eq!(errors.iter().map(|&(i, _)| i).collect::<Vec<_>>(), [1, 2]);
+/// assert_eq!(errors[1].1, CronParseError::MissingField { field: FieldKind::DaysOfWeek, at: 7 });
+/// ```
+pub fn parse_many<'a, I>(exprs: I) -> Result<Vec<Cron>, Vec<(usize, CronParseError)>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut crons = Vec::new();
+    let mut errors = Vec::new();
+    for (i, expr) in exprs.into_iter().enumerate() {
+        match expr.parse() {
+            Ok(cron) => crons.push(cron),
+            Err(err) => errors.push((i, err)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(crons)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Finds the cron values in a list that match the same times as a value before them, checked
+/// with [`Cron::equivalent_to`]. Returns the index of each duplicate along with the index of the
+/// first value it's equivalent to, in order.
+///
+/// [`Cron::equivalent_to`]: struct.Cron.html#method.equivalent_to
+///
+/// # Example
+/// ```
+/// let crons = saffron::parse_many(vec!["0 0 * * *", "0 12 * * *", "@daily", "0 0 ? * *"]).unwrap();
+/// assert_eq!(saffron::find_duplicates(&crons), [(2, 0), (3, 0)]);
+/// ```
+pub fn find_duplicates(crons: &[Cron]) -> Vec<(usize, usize)> {
+    crons
+        .iter()
+        .enumerate()
+        .filter_map(|(i, cron)| {
+            crons[..i]
+                .iter()
+                .position(|earlier| earlier.equivalent_to(cron))
+                .map(|first| (i, first))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::FieldKind;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn parses_every_expression() {
+        assert_eq!(parse_many(vec![]), Ok(vec![]));
+        assert_eq!(
+            parse_many(vec!["*/5 * * * *", "0 0 L * ?"]),
+            Ok(vec![
+                "*/5 * * * *".parse().unwrap(),
+                "0 0 L * ?".parse().unwrap()
+            ])
+        );
+
+        let errors = parse_many(vec!["@weekly", "@often", "* * * * *", "60 * * * *"]).unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                (1, CronParseError::UnknownShortcut),
+                (
+                    3,
+                    CronParseError::ValueOutOfRange {
+                        field: FieldKind::Minutes,
+                        value: 60,
+                        min: 0,
+                        max: 59,
+                        at: 0,
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        let crons = parse_many(vec![
+            "0 0 * * MON",
+            "0 0 ? * 2",
+            "0 12 * * *",
+            "0 0 * * mon",
+            "0,0 12 * * *",
+            "0 0 31 2 *",
+            "0 0 30 2 *",
+        ])
+        .unwrap();
+        // the values that never match are equivalent too
+        assert_eq!(find_duplicates(&crons), [(1, 0), (3, 0), (4, 2), (6, 5)]);
+        assert_eq!(find_duplicates(&[]), []);
+    }
+}
@@ -0,0 +1,791 @@
+//! A parser for simple english phrases describing a schedule, the inverse of
+//! [`CronExpr::describe`].
+//!
+//! The grammar is limited to a set of common phrases. A phrase is made of clauses in any order:
+//!
+//! | Clause                                        | Example                        |
+//! | --------------------------------------------- | ------------------------------ |
+//! | `every minute`, `every N minutes`             | `every 15 minutes`             |
+//! | `every hour`, `every N hours`, `hourly`       | `every 2 hours`                |
+//! | `every day`, `every N days`, `daily`          | `every day`                    |
+//! | `every month`, `every N months`, `monthly`    | `every 3 months`               |
+//! | `every year`, `yearly`, `annually`            | `every year`                   |
+//! | `every`/`on` days of the week                 | `every weekday`, `on mondays`  |
+//! | `on the` days of the month                    | `on the 1st and 15th`          |
+//! | nth and last days of the week                 | `first monday of each month`   |
+//! | last days of the month                        | `on the last day of the month` |
+//! | `in` months                                   | `in january and july`          |
+//! | `at` times of the day                         | `at 9am`, `at 9:30 and 17:30`  |
+//! | `between` hours of the day                    | `between 9am and 5pm`          |
+//!
+//! Parts of the schedule left out of a phrase default to the first value, so `every monday`
+//! matches midnight on every Monday.
+//!
+//! [`CronExpr::describe`]: ../parse/struct.CronExpr.html#method.describe
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::parse::{
+    CronExpr, DayOfMonth, DayOfMonthExpr, DayOfWeek, DayOfWeekExpr, Exclusions, Expr, Exprs,
+    FieldKind, Hour, Last, Minute, Month, OrsExpr, Step, ValueOutOfRangeError,
+};
+use chrono::Weekday;
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+
+/// An error indicating that a phrase failed to parse. Every error except
+/// [`UnexpectedEnd`](#variant.UnexpectedEnd) carries the byte offset in the phrase of the word
+/// where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NlParseError {
+    /// A word isn't understood at its position in the phrase.
+    UnexpectedWord {
+        /// The byte offset of the word
+        at: usize,
+    },
+    /// The phrase ended before a clause was complete, or the phrase is empty.
+    UnexpectedEnd,
+    /// A number is out of the range of valid values for its use.
+    ValueOutOfRange {
+        /// The byte offset of the number
+        at: usize,
+    },
+    /// A clause sets a part of the schedule that was already set by another clause, or the
+    /// times set can't be written in one expression.
+    Conflict {
+        /// The field set more than once
+        field: FieldKind,
+        /// The byte offset of the clause
+        at: usize,
+    },
+}
+
+impl NlParseError {
+    /// Returns the byte offset in the phrase where the error occurred, if the error is specific
+    /// to one word.
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            NlParseError::UnexpectedWord { at }
+            | NlParseError::ValueOutOfRange { at }
+            | NlParseError::Conflict { at, .. } => Some(at),
+            NlParseError::UnexpectedEnd => None,
+        }
+    }
+}
+
+impl Display for NlParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NlParseError::UnexpectedWord { at } => {
+                write!(f, "Unexpected word at position {}", at)
+            }
+            NlParseError::UnexpectedEnd => "Unexpected end of phrase".fmt(f),
+            NlParseError::ValueOutOfRange { at } => {
+                write!(f, "Value at position {} is out of range", at)
+            }
+            NlParseError::Conflict { field, at } => {
+                write!(f, "Conflicting {} at position {}", field, at)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NlParseError {}
+
+/// Parses a phrase into a cron expression.
+///
+/// # Example
+/// ```
+/// use saffron::nl;
+///
+/// let expr = nl::parse("every weekday at 9am").expect("Valid phrase");
+/// assert_eq!(expr.to_string(), "0 9 * * 2-6");
+///
+/// let expr = nl::parse("first Monday of each month").expect("Valid phrase");
+/// assert_eq!(expr.to_string(), "0 0 * * 2#1");
+/// ```
+pub fn parse(phrase: &str) -> Result<CronExpr, NlParseError> {
+    // lowercasing only ascii characters keeps the byte offsets of every word the same
+    let phrase: String = phrase.to_ascii_lowercase();
+    let mut words = Words::new(&phrase);
+    if words.peek().is_none() {
+        return Err(NlParseError::UnexpectedEnd);
+    }
+
+    let mut schedule = Schedule::default();
+    while let Some((at, word)) = words.next() {
+        match word {
+            "every" | "each" => schedule.every(&mut words)?,
+            "hourly" => schedule.every_hour = true,
+            "daily" => {}
+            "weekly" => {
+                let sunday = DayOfWeekExpr::Many(Exprs::new(OrsExpr::One(Weekday::Sun.into())));
+                set(&mut schedule.dows, sunday, FieldKind::DaysOfWeek, at)?
+            }
+            "monthly" => schedule.every_month = true,
+            "yearly" | "annually" => schedule.every_year = true,
+            "at" => schedule.at(&mut words, at)?,
+            "between" => schedule.between(&mut words, at)?,
+            "in" | "during" | "of" => {
+                // "of the month" and "of each month" are implied by days of the month
+                if words.eat("the") || words.eat("each") || words.eat("every") {
+                    words.expect("month")?;
+                } else {
+                    let months = months(&mut words)?;
+                    set(&mut schedule.months, months, FieldKind::Months, at)?
+                }
+            }
+            "and" => {}
+            "on" => schedule.on(&mut words)?,
+            _ => {
+                words.back();
+                schedule.on(&mut words)?
+            }
+        }
+    }
+
+    Ok(schedule.into_expr())
+}
+
+/// The words of a phrase paired with their byte offsets. Commas are treated as whitespace.
+struct Words<'a> {
+    words: Vec<(usize, &'a str)>,
+    pos: usize,
+}
+
+impl<'a> Words<'a> {
+    fn new(phrase: &'a str) -> Self {
+        let mut words = Vec::new();
+        let mut start = None;
+        for (i, c) in phrase.char_indices() {
+            let separator = c.is_whitespace() || c == ',';
+            match start {
+                Some(s) if separator => {
+                    words.push((s, &phrase[s..i]));
+                    start = None;
+                }
+                None if !separator => start = Some(i),
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            words.push((s, &phrase[s..]));
+        }
+        Self { words, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&'a str> {
+        self.words.get(self.pos + n).map(|&(_, word)| word)
+    }
+
+    fn peek_is(&self, check: impl Fn(&str) -> bool) -> bool {
+        matches!(self.peek(), Some(word) if check(word))
+    }
+
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let word = self.words.get(self.pos).copied();
+        if word.is_some() {
+            self.pos += 1;
+        }
+        word
+    }
+
+    /// Returns the next word, failing if the phrase has ended
+    fn next_word(&mut self) -> Result<(usize, &'a str), NlParseError> {
+        self.next().ok_or(NlParseError::UnexpectedEnd)
+    }
+
+    fn back(&mut self) {
+        self.pos -= 1;
+    }
+
+    /// Consumes the next word if it's the given word
+    fn eat(&mut self, word: &str) -> bool {
+        if self.peek() == Some(word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the next word, failing if it isn't the given word
+    fn expect(&mut self, word: &str) -> Result<(), NlParseError> {
+        match self.next_word()? {
+            (_, next) if next == word => Ok(()),
+            (at, _) => Err(NlParseError::UnexpectedWord { at }),
+        }
+    }
+
+    /// Consumes an "and" if the word after it passes the given check, so "and" can join both
+    /// items of a list and clauses of a phrase
+    fn eat_and_then(&mut self, check: impl Fn(&str) -> bool) -> bool {
+        match (self.peek(), self.peek_nth(1)) {
+            (Some("and"), Some(next)) if check(next) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The parts of a schedule set by the clauses of a phrase
+#[derive(Default)]
+struct Schedule {
+    minutes: Option<Expr<Minute>>,
+    hours: Option<Expr<Hour>>,
+    doms: Option<DayOfMonthExpr>,
+    months: Option<Expr<Month>>,
+    dows: Option<DayOfWeekExpr>,
+    /// Set by clauses repeating more than once an hour, so the hours default to every hour
+    every_hour: bool,
+    /// Set by clauses repeating every month, so the days default to the first of the month
+    every_month: bool,
+    /// Set by clauses repeating every year, so the date defaults to the first of January
+    every_year: bool,
+}
+
+fn set<T>(slot: &mut Option<T>, value: T, field: FieldKind, at: usize) -> Result<(), NlParseError> {
+    match slot {
+        Some(_) => Err(NlParseError::Conflict { field, at }),
+        None => {
+            *slot = Some(value);
+            Ok(())
+        }
+    }
+}
+
+fn value<E: TryFrom<u8, Error = ValueOutOfRangeError>>(
+    value: u8,
+    at: usize,
+) -> Result<E, NlParseError> {
+    E::try_from(value).map_err(|_| NlParseError::ValueOutOfRange { at })
+}
+
+fn step<E>(start: u8, end: u8, step: u8, at: usize) -> Result<OrsExpr<E>, NlParseError>
+where
+    E: TryFrom<u8, Error = ValueOutOfRangeError>,
+    Step<E>: TryFrom<u8, Error = ValueOutOfRangeError>,
+{
+    Ok(OrsExpr::Step {
+        start: value(start, at)?,
+        end: value(end, at)?,
+        step: value(step, at)?,
+    })
+}
+
+fn exprs<E>(mut values: Vec<OrsExpr<E>>) -> Exprs<E> {
+    let first = values.remove(0);
+    Exprs {
+        first,
+        tail: values,
+    }
+}
+
+impl Schedule {
+    /// Parses the rest of an "every" clause
+    fn every(&mut self, words: &mut Words) -> Result<(), NlParseError> {
+        let (at, word) = words.next_word()?;
+        let count = match word {
+            "minute" => {
+                self.every_hour = true;
+                return set(&mut self.minutes, Expr::All, FieldKind::Minutes, at);
+            }
+            "hour" => {
+                self.every_hour = true;
+                return Ok(());
+            }
+            "day" => return Ok(()),
+            "month" => {
+                self.every_month = true;
+                return Ok(());
+            }
+            "year" => {
+                self.every_year = true;
+                return Ok(());
+            }
+            "other" => 2,
+            word => match number(word) {
+                Some(count) => count,
+                None => {
+                    words.back();
+                    return self.on(words);
+                }
+            },
+        };
+
+        let (unit_at, unit) = words.next_word()?;
+        match unit {
+            "minute" | "minutes" | "min" | "mins" => {
+                self.every_hour = true;
+                let minutes = Expr::Many(Exprs::new(step(0, 59, count, at)?));
+                set(&mut self.minutes, minutes, FieldKind::Minutes, at)
+            }
+            "hour" | "hours" => {
+                let hours = Expr::Many(Exprs::new(step(0, 23, count, at)?));
+                set(&mut self.hours, hours, FieldKind::Hours, at)
+            }
+            "day" | "days" => {
+                let days = DayOfMonthExpr::Many(Exprs::new(step(1, 31, count, at)?));
+                set(&mut self.doms, days, FieldKind::DaysOfMonth, at)
+            }
+            "month" | "months" => {
+                self.every_month = true;
+                let months = Expr::Many(Exprs::new(step(1, 12, count, at)?));
+                set(&mut self.months, months, FieldKind::Months, at)
+            }
+            _ => Err(NlParseError::UnexpectedWord { at: unit_at }),
+        }
+    }
+
+    /// Parses the rest of an "at" clause, a list of times of the day
+    fn at(&mut self, words: &mut Words, at: usize) -> Result<(), NlParseError> {
+        let mut times = Vec::new();
+        loop {
+            times.push(time(words)?);
+            if !(words.eat_and_then(is_time) || words.peek_is(is_time)) {
+                break;
+            }
+        }
+        times.sort_unstable();
+        times.dedup();
+
+        let (hour, minute) = times[0];
+        let (hours, minutes) = if times.iter().all(|&(_, m)| m == minute) {
+            let hours = times
+                .iter()
+                .map(|&(h, _)| value(h, at).map(OrsExpr::One))
+                .collect::<Result<_, _>>()?;
+            (exprs(hours), Exprs::new(OrsExpr::One(value(minute, at)?)))
+        } else if times.iter().all(|&(h, _)| h == hour) {
+            let minutes = times
+                .iter()
+                .map(|&(_, m)| value(m, at).map(OrsExpr::One))
+                .collect::<Result<_, _>>()?;
+            (Exprs::new(OrsExpr::One(value(hour, at)?)), exprs(minutes))
+        } else {
+            return Err(NlParseError::Conflict {
+                field: FieldKind::Minutes,
+                at,
+            });
+        };
+
+        set(
+            &mut self.minutes,
+            Expr::Many(minutes),
+            FieldKind::Minutes,
+            at,
+        )?;
+        set(&mut self.hours, Expr::Many(hours), FieldKind::Hours, at)
+    }
+
+    /// Parses the rest of a "between" clause, a range of hours ending before the end time
+    fn between(&mut self, words: &mut Words, at: usize) -> Result<(), NlParseError> {
+        let (start, _) = time(words)?;
+        words.expect("and")?;
+        let (end, end_minute) = time(words)?;
+        let end = match (end, end_minute) {
+            (0, 0) => 23,
+            (end, 0) => end - 1,
+            (end, _) => end,
+        };
+        if end < start {
+            return Err(NlParseError::ValueOutOfRange { at });
+        }
+
+        let hours = Expr::Many(Exprs::new(OrsExpr::Range(
+            value(start, at)?,
+            value(end, at)?,
+        )));
+        set(&mut self.hours, hours, FieldKind::Hours, at)
+    }
+
+    /// Parses a clause setting the days of the month or week, with or without a leading "on"
+    fn on(&mut self, words: &mut Words) -> Result<(), NlParseError> {
+        words.eat("the");
+        let (at, word) = words.next_word()?;
+
+        if word == "last" {
+            let (day_at, day) = words.next_word()?;
+            let doms = match day {
+                "day" => DayOfMonthExpr::Last(Last::Day),
+                "weekday" => DayOfMonthExpr::Last(Last::Weekday),
+                day => match weekday(day) {
+                    Some(day) => {
+                        let dows = DayOfWeekExpr::Last(day.into());
+                        return set(&mut self.dows, dows, FieldKind::DaysOfWeek, at);
+                    }
+                    None => return Err(NlParseError::UnexpectedWord { at: day_at }),
+                },
+            };
+            return set(&mut self.doms, doms, FieldKind::DaysOfMonth, at);
+        }
+
+        if let Some(nth) = ordinal(word) {
+            if let Some(day) = words.peek().and_then(weekday) {
+                words.next();
+                let dows = DayOfWeekExpr::Nth(day.into(), value(nth, at)?);
+                return set(&mut self.dows, dows, FieldKind::DaysOfWeek, at);
+            }
+        }
+
+        if word == "day" || ordinal(word).is_some() {
+            if word != "day" {
+                words.back();
+            }
+            let doms = DayOfMonthExpr::Many(days(words)?);
+            return set(&mut self.doms, doms, FieldKind::DaysOfMonth, at);
+        }
+
+        if month(word).is_some() {
+            words.back();
+            let months = months(words)?;
+            set(&mut self.months, months, FieldKind::Months, at)?;
+            // a date, like "january 1st"
+            if words.peek().and_then(ordinal).is_some() {
+                let doms = DayOfMonthExpr::Many(days(words)?);
+                set(&mut self.doms, doms, FieldKind::DaysOfMonth, at)?;
+            }
+            return Ok(());
+        }
+
+        if is_weekday(word) {
+            words.back();
+            let dows = weekdays(words)?;
+            return set(&mut self.dows, dows, FieldKind::DaysOfWeek, at);
+        }
+
+        Err(NlParseError::UnexpectedWord { at })
+    }
+
+    fn into_expr(self) -> CronExpr {
+        fn first<E: TryFrom<u8, Error = ValueOutOfRangeError>>() -> OrsExpr<E> {
+            OrsExpr::One(E::try_from(1).expect("One is a valid day and month"))
+        }
+
+        let minutes = self
+            .minutes
+            .unwrap_or_else(|| Expr::Many(Exprs::new(OrsExpr::One(Minute::try_from(0).unwrap()))));
+        let hours = match self.hours {
+            Some(hours) => hours,
+            None if self.every_hour => Expr::All,
+            None => Expr::Many(Exprs::new(OrsExpr::One(Hour::try_from(0).unwrap()))),
+        };
+        let months = match self.months {
+            Some(months) => months,
+            None if self.every_year => Expr::Many(Exprs::new(first())),
+            None => Expr::All,
+        };
+        let doms = match self.doms {
+            Some(doms) => doms,
+            None if (self.every_month || self.every_year) && self.dows.is_none() => {
+                DayOfMonthExpr::Many(Exprs::new(first()))
+            }
+            None => DayOfMonthExpr::All,
+        };
+
+        CronExpr {
+            minutes,
+            hours,
+            doms,
+            months,
+            dows: self.dows.unwrap_or(DayOfWeekExpr::All),
+            weeks: Expr::All,
+            shortcut: None,
+            exclusions: Exclusions::default(),
+            days_and: false,
+        }
+    }
+}
+
+fn number(word: &str) -> Option<u8> {
+    if word.bytes().all(|b| b.is_ascii_digit()) {
+        word.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Reads an ordinal like "1st" or "first", or a plain number
+fn ordinal(word: &str) -> Option<u8> {
+    match word {
+        "first" => Some(1),
+        "second" => Some(2),
+        "third" => Some(3),
+        "fourth" => Some(4),
+        "fifth" => Some(5),
+        _ => {
+            let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            match &word[digits.len()..] {
+                "" | "st" | "nd" | "rd" | "th" => number(digits),
+                _ => None,
+            }
+        }
+    }
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    // plurals, like "mondays"
+    let word = match word.strip_suffix('s') {
+        Some(day) if day.ends_with("day") => day,
+        _ => word,
+    };
+    match word {
+        "sunday" | "sun" => Some(Weekday::Sun),
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        _ => None,
+    }
+}
+
+fn is_weekday(word: &str) -> bool {
+    matches!(word, "weekday" | "weekdays" | "weekend" | "weekends") || weekday(word).is_some()
+}
+
+fn month(word: &str) -> Option<chrono::Month> {
+    use chrono::Month::*;
+    match word {
+        "january" | "jan" => Some(January),
+        "february" | "feb" => Some(February),
+        "march" | "mar" => Some(March),
+        "april" | "apr" => Some(April),
+        "may" => Some(May),
+        "june" | "jun" => Some(June),
+        "july" | "jul" => Some(July),
+        "august" | "aug" => Some(August),
+        "september" | "sep" | "sept" => Some(September),
+        "october" | "oct" => Some(October),
+        "november" | "nov" => Some(November),
+        "december" | "dec" => Some(December),
+        _ => None,
+    }
+}
+
+fn is_range(word: &str) -> bool {
+    matches!(word, "through" | "thru" | "to")
+}
+
+/// Parses a list of days of the week, like "monday through friday and sunday"
+fn weekdays(words: &mut Words) -> Result<DayOfWeekExpr, NlParseError> {
+    let mut days = Vec::new();
+    loop {
+        let (at, word) = words.next_word()?;
+        match word {
+            "weekday" | "weekdays" => {
+                days.push(OrsExpr::Range(Weekday::Mon.into(), Weekday::Fri.into()))
+            }
+            "weekend" | "weekends" => {
+                days.push(OrsExpr::One(Weekday::Sat.into()));
+                days.push(OrsExpr::One(Weekday::Sun.into()));
+            }
+            word => {
+                let start = weekday(word).ok_or(NlParseError::UnexpectedWord { at })?;
+                if words.peek_is(is_range) {
+                    words.next();
+                    let (end_at, end) = words.next_word()?;
+                    let end = weekday(end).ok_or(NlParseError::UnexpectedWord { at: end_at })?;
+                    days.push(OrsExpr::Range(start.into(), end.into()));
+                } else {
+                    days.push(OrsExpr::One(start.into()));
+                }
+            }
+        }
+
+        if !(words.eat_and_then(is_weekday) || words.peek_is(is_weekday)) {
+            break;
+        }
+    }
+    Ok(DayOfWeekExpr::Many(exprs::<DayOfWeek>(days)))
+}
+
+/// Parses a list of months, like "january through march and july"
+fn months(words: &mut Words) -> Result<Expr<Month>, NlParseError> {
+    let mut months = Vec::new();
+    loop {
+        let (at, word) = words.next_word()?;
+        let start = month(word).ok_or(NlParseError::UnexpectedWord { at })?;
+        if words.peek_is(is_range) {
+            words.next();
+            let (end_at, end) = words.next_word()?;
+            let end = month(end).ok_or(NlParseError::UnexpectedWord { at: end_at })?;
+            months.push(OrsExpr::Range(start.into(), end.into()));
+        } else {
+            months.push(OrsExpr::One(start.into()));
+        }
+
+        let is_month = |word: &str| month(word).is_some();
+        if !(words.eat_and_then(is_month) || words.peek_is(is_month)) {
+            break;
+        }
+    }
+    Ok(Expr::Many(exprs(months)))
+}
+
+/// Parses a list of days of the month, like "1st and the 15th" or "1 through 7"
+fn days(words: &mut Words) -> Result<Exprs<DayOfMonth>, NlParseError> {
+    let mut days = Vec::new();
+    loop {
+        let (at, word) = words.next_word()?;
+        let start = ordinal(word).ok_or(NlParseError::UnexpectedWord { at })?;
+        if words.peek_is(is_range) {
+            words.next();
+            words.eat("the");
+            let (end_at, end) = words.next_word()?;
+            let end = ordinal(end).ok_or(NlParseError::UnexpectedWord { at: end_at })?;
+            days.push(OrsExpr::Range(value(start, at)?, value(end, end_at)?));
+        } else {
+            days.push(OrsExpr::One(value(start, at)?));
+        }
+
+        let is_day = |word: &str| word == "the" || ordinal(word).is_some();
+        if !words.eat_and_then(is_day) {
+            break;
+        }
+        words.eat("the");
+    }
+    Ok(exprs(days))
+}
+
+fn is_time(word: &str) -> bool {
+    matches!(word, "noon" | "midnight") || word.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Parses a time of the day, like "9am", "9:30 pm", "17:30", or "noon"
+fn time(words: &mut Words) -> Result<(u8, u8), NlParseError> {
+    let (at, word) = words.next_word()?;
+    match word {
+        "noon" => return Ok((12, 0)),
+        "midnight" => return Ok((0, 0)),
+        _ => {}
+    }
+
+    let clock = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let (hour, minute) = match clock.find(':') {
+        Some(i) => (&clock[..i], &clock[i + 1..]),
+        None => (clock, "0"),
+    };
+    let unexpected = NlParseError::UnexpectedWord { at };
+    let (hour, minute) = match (number(hour), number(minute)) {
+        (Some(hour), Some(minute)) => (hour, minute),
+        _ => return Err(unexpected),
+    };
+
+    let meridiem = match &word[clock.len()..] {
+        "" if matches!(words.peek(), Some("am") | Some("pm")) => words.next().unwrap().1,
+        suffix => suffix,
+    };
+    let hour = match meridiem {
+        "" => hour,
+        "am" | "pm" if hour == 0 || hour > 12 => return Err(NlParseError::ValueOutOfRange { at }),
+        "am" => hour % 12,
+        "pm" => hour % 12 + 12,
+        _ => return Err(unexpected),
+    };
+    if hour > 23 || minute > 59 {
+        return Err(NlParseError::ValueOutOfRange { at });
+    }
+    Ok((hour, minute))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn assert(phrase: &str, cron: &str) {
+        let expected: CronExpr = cron.parse().expect("Valid cron expression");
+        assert_eq!(parse(phrase), Ok(expected), "{}", phrase);
+    }
+
+    #[test]
+    fn frequencies() {
+        assert("every minute", "* * * * *");
+        assert("every 15 minutes", "*/15 * * * *");
+        assert("every hour", "0 * * * *");
+        assert("hourly", "0 * * * *");
+        assert("every 2 hours", "0 */2 * * *");
+        assert("every day", "0 0 * * *");
+        assert("every other day", "0 0 */2 * *");
+        assert("every month", "0 0 1 * *");
+        assert("every 3 months", "0 0 1 */3 *");
+        assert("Every Year", "0 0 1 1 *");
+        assert("weekly", "0 0 * * SUN");
+    }
+
+    #[test]
+    fn times() {
+        assert("every day at 9am", "0 9 * * *");
+        assert("at 9:30 pm", "30 21 * * *");
+        assert("at noon and midnight", "0 0,12 * * *");
+        assert("at 17:00, 17:15", "0,15 17 * * *");
+        assert("every 15 minutes between 9am and 5pm", "*/15 9-16 * * *");
+        assert("every hour between 9:00 and 17:30", "0 9-17 * * *");
+    }
+
+    #[test]
+    fn days() {
+        assert("every weekday at 9am", "0 9 * * MON-FRI");
+        assert("at 10am on weekends", "0 10 * * SAT,SUN");
+        assert("every monday, wednesday and friday", "0 0 * * MON,WED,FRI");
+        assert("mondays through thursdays", "0 0 * * MON-THU");
+        assert("first Monday of each month", "0 0 * * MON#1");
+        assert("on the last friday of the month", "0 0 * * FRIL");
+        assert("on the last day of every month", "0 0 L * *");
+        assert("on the last weekday", "0 0 LW * *");
+        assert("on the 1st and 15th at 8am", "0 8 1,15 * *");
+        assert("on the 1st and the 15th", "0 0 1,15 * *");
+        assert("on day 10 through 20", "0 0 10-20 * *");
+    }
+
+    #[test]
+    fn months() {
+        assert("every day in january", "0 0 * JAN *");
+        assert("every minute in june through august", "* * * JUN-AUG *");
+        assert("on december 25th at 7am", "0 7 25 DEC *");
+        assert("on the 1st of january and july", "0 0 1 JAN,JUL *");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse(""), Err(NlParseError::UnexpectedEnd));
+        assert_eq!(parse("every"), Err(NlParseError::UnexpectedEnd));
+        assert_eq!(
+            parse("every fortnight"),
+            Err(NlParseError::UnexpectedWord { at: 6 })
+        );
+        assert_eq!(
+            parse("at 13pm"),
+            Err(NlParseError::ValueOutOfRange { at: 3 })
+        );
+        assert_eq!(
+            parse("every 75 minutes"),
+            Err(NlParseError::ValueOutOfRange { at: 6 })
+        );
+        assert_eq!(
+            parse("every minute at 9am"),
+            Err(NlParseError::Conflict {
+                field: FieldKind::Minutes,
+                at: 13
+            })
+        );
+        assert_eq!(
+            parse("at 9am and 5:30pm"),
+            Err(NlParseError::Conflict {
+                field: FieldKind::Minutes,
+                at: 0
+            })
+        );
+    }
+}
@@ -4,7 +4,11 @@
 #[cfg(not(feature = "std"))]
 use alloc::vec::{self, Vec};
 
+use crate::bits;
 use crate::internal::Sealed;
+use crate::spanned::split_fields;
+use crate::FieldValues;
+use chrono::{Datelike, Timelike};
 use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::fmt::{self, Display, Formatter};
@@ -14,17 +18,21 @@ use core::slice;
 use core::str::FromStr;
 use nom::{
     branch::alt,
-    bytes::complete::tag_no_case,
+    bytes::complete::tag,
     character::complete::{char, digit1, space1},
-    combinator::{all_consuming, map, map_res, opt},
-    sequence::tuple,
-    IResult,
+    combinator::{map, opt},
+    error::{ErrorKind, ParseError},
+    sequence::{preceded, tuple},
 };
 
 #[cfg(feature = "std")]
 use std::vec;
 
+#[cfg(feature = "arbitrary")]
+pub use crate::arbitrary_impl::CronString;
 pub use crate::describe::*;
+pub use crate::locale::Locale;
+pub use crate::spanned::{SpannedCronExpr, SpannedField, SpannedItem};
 
 /// An error returned if an expression type value is out of range.
 #[derive(Debug)]
@@ -92,6 +100,13 @@ impl PartialEq<u8> for Minute {
         &self.0 == other
     }
 }
+impl Display for Minute {
+    /// Formats the value, 0-59
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 /// An hour value, 0-23
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -133,6 +148,13 @@ impl PartialEq<u8> for Hour {
         &self.0 == other
     }
 }
+impl Display for Hour {
+    /// Formats the value, 0-23
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 /// A day of the month, 1-31
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -161,7 +183,7 @@ impl TryFrom<u8> for DayOfMonth {
 
     #[inline]
     fn try_from(value: u8) -> Result<Self, Self::Error> {
-        if value >= Self::MIN && value <= Self::MAX {
+        if (Self::MIN..=Self::MAX).contains(&value) {
             Ok(Self(value))
         } else {
             Err(ValueOutOfRangeError)
@@ -174,6 +196,13 @@ impl PartialEq<u8> for DayOfMonth {
         &self.0 == other
     }
 }
+impl Display for DayOfMonth {
+    /// Formats the one based day of the month, 1-31
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 /// A last day of the month offset, 1-30
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DayOfMonthOffset(u8);
@@ -201,7 +230,7 @@ impl TryFrom<u8> for DayOfMonthOffset {
 
     #[inline]
     fn try_from(value: u8) -> Result<Self, Self::Error> {
-        if value >= Self::MIN && value <= Self::MAX {
+        if (Self::MIN..=Self::MAX).contains(&value) {
             Ok(Self(value))
         } else {
             Err(ValueOutOfRangeError)
@@ -214,6 +243,61 @@ impl PartialEq<u8> for DayOfMonthOffset {
         &self.0 == other
     }
 }
+impl Display for DayOfMonthOffset {
+    /// Formats the offset, 1-30
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A business day of the month, counting only Monday to Friday, 1-23
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BusinessDay(u8);
+impl Sealed for BusinessDay {}
+impl ExprValue for BusinessDay {
+    const MAX: u8 = 23;
+    const MIN: u8 = 1;
+
+    fn max() -> Self {
+        Self(Self::MAX)
+    }
+    fn min() -> Self {
+        Self(Self::MIN)
+    }
+}
+impl From<BusinessDay> for u8 {
+    #[inline]
+    /// Returns the one based business day, 1-23
+    fn from(m: BusinessDay) -> Self {
+        m.0
+    }
+}
+impl TryFrom<u8> for BusinessDay {
+    type Error = ValueOutOfRangeError;
+
+    #[inline]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err(ValueOutOfRangeError)
+        }
+    }
+}
+impl PartialEq<u8> for BusinessDay {
+    #[inline]
+    fn eq(&self, other: &u8) -> bool {
+        &self.0 == other
+    }
+}
+impl Display for BusinessDay {
+    /// Formats the one based business day, 1-23
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 /// A month, 1-12
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -281,7 +365,7 @@ impl TryFrom<u8> for Month {
 
     #[inline]
     fn try_from(value: u8) -> Result<Self, Self::Error> {
-        if value >= Self::MIN && value <= Self::MAX {
+        if (Self::MIN..=Self::MAX).contains(&value) {
             Ok(Self(value))
         } else {
             Err(ValueOutOfRangeError)
@@ -294,6 +378,13 @@ impl PartialEq<u8> for Month {
         &self.0 == other
     }
 }
+impl Display for Month {
+    /// Formats the one based month, 1-12
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 /// An "nth" day, 1-5
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -321,7 +412,7 @@ impl TryFrom<u8> for NthDay {
 
     #[inline]
     fn try_from(value: u8) -> Result<Self, Self::Error> {
-        if value >= Self::MIN && value <= Self::MAX {
+        if (Self::MIN..=Self::MAX).contains(&value) {
             Ok(Self(value))
         } else {
             Err(ValueOutOfRangeError)
@@ -334,6 +425,61 @@ impl PartialEq<u8> for NthDay {
         &self.0 == other
     }
 }
+impl Display for NthDay {
+    /// Formats the value, 1-5
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An ISO week of the year, 1-53
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Week(u8);
+impl Sealed for Week {}
+impl ExprValue for Week {
+    const MAX: u8 = 53;
+    const MIN: u8 = 1;
+
+    fn max() -> Self {
+        Self(Self::MAX)
+    }
+    fn min() -> Self {
+        Self(Self::MIN)
+    }
+}
+impl From<Week> for u8 {
+    #[inline]
+    /// Returns the zero based week, 0-52
+    fn from(w: Week) -> Self {
+        w.0 - 1
+    }
+}
+impl TryFrom<u8> for Week {
+    type Error = ValueOutOfRangeError;
+
+    #[inline]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if (Self::MIN..=Self::MAX).contains(&value) {
+            Ok(Self(value))
+        } else {
+            Err(ValueOutOfRangeError)
+        }
+    }
+}
+impl PartialEq<u8> for Week {
+    #[inline]
+    fn eq(&self, other: &u8) -> bool {
+        &self.0 == other
+    }
+}
+impl Display for Week {
+    /// Formats the one based week, 1-53
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
 
 /// A day of the week, 1-7 (Sun-Sat)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -353,9 +499,7 @@ impl ExprValue for DayOfWeek {
 impl PartialOrd for DayOfWeek {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.0
-            .number_from_sunday()
-            .partial_cmp(&other.0.number_from_sunday())
+        Some(self.cmp(other))
     }
 }
 impl Ord for DayOfWeek {
@@ -410,6 +554,133 @@ impl PartialEq<chrono::Weekday> for DayOfWeek {
         &self.0 == other
     }
 }
+impl Display for DayOfWeek {
+    /// Formats the one based day of the week, 1-7 (Sun-Sat)
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.0.number_from_sunday().fmt(f)
+    }
+}
+
+macro_rules! value_helpers {
+    ($($name:ident),+) => {$(
+        impl $name {
+            /// Returns an iterator over every value, from the min value to the max value.
+            pub fn all() -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + Clone {
+                (Self::MIN..=Self::MAX).map(Self)
+            }
+
+            /// Adds to the value, returning `None` if the result is out of range.
+            #[inline]
+            pub fn checked_add(self, rhs: u8) -> Option<Self> {
+                Self::try_from(self.0.checked_add(rhs)?).ok()
+            }
+
+            /// Subtracts from the value, returning `None` if the result is out of range.
+            #[inline]
+            pub fn checked_sub(self, rhs: u8) -> Option<Self> {
+                Self::try_from(self.0.checked_sub(rhs)?).ok()
+            }
+        }
+    )+};
+}
+
+value_helpers!(
+    Minute,
+    Hour,
+    DayOfMonth,
+    DayOfMonthOffset,
+    BusinessDay,
+    Month,
+    NthDay,
+    Week
+);
+
+impl Minute {
+    /// Returns the minute of the given time.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::Minute;
+    /// use chrono::prelude::*;
+    ///
+    /// let minute = Minute::from_time(&NaiveTime::from_hms(12, 30, 15));
+    /// assert_eq!(minute, 30);
+    /// assert_eq!(Minute::from_time(&Utc.with_ymd_and_hms(2021, 1, 1, 0, 5, 0).unwrap()), 5);
+    /// ```
+    #[inline]
+    pub fn from_time<T: Timelike>(time: &T) -> Self {
+        Self(time.minute() as u8)
+    }
+}
+
+impl Hour {
+    /// Returns the hour of the given time.
+    #[inline]
+    pub fn from_time<T: Timelike>(time: &T) -> Self {
+        Self(time.hour() as u8)
+    }
+}
+
+impl DayOfMonth {
+    /// Returns the day of the month of the given date.
+    #[inline]
+    pub fn from_date<D: Datelike>(date: &D) -> Self {
+        Self(date.day() as u8)
+    }
+}
+
+impl Month {
+    /// Returns the month of the given date.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::Month;
+    /// use chrono::prelude::*;
+    ///
+    /// let month = Month::from_date(&NaiveDate::from_ymd_opt(2021, 3, 14).unwrap());
+    /// assert_eq!(month, 3);
+    /// assert_eq!(month.checked_add(9), Some(Month::from(chrono::Month::December)));
+    /// assert_eq!(month.checked_add(10), None);
+    /// ```
+    #[inline]
+    pub fn from_date<D: Datelike>(date: &D) -> Self {
+        Self(date.month() as u8)
+    }
+}
+
+impl Week {
+    /// Returns the ISO week of the given date.
+    #[inline]
+    pub fn from_date<D: Datelike>(date: &D) -> Self {
+        Self(date.iso_week().week() as u8)
+    }
+}
+
+impl DayOfWeek {
+    /// Returns an iterator over every day of the week, from Sunday to Saturday.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::DayOfWeek;
+    /// use chrono::Weekday;
+    ///
+    /// let days = DayOfWeek::all().map(Weekday::from).collect::<Vec<_>>();
+    /// assert_eq!(days.first(), Some(&Weekday::Sun));
+    /// assert_eq!(days.last(), Some(&Weekday::Sat));
+    /// ```
+    pub fn all() -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + Clone {
+        use chrono::Weekday::*;
+
+        [Sun, Mon, Tue, Wed, Thu, Fri, Sat].iter().map(|&w| Self(w))
+    }
+
+    /// Returns the day of the week of the given date.
+    #[inline]
+    pub fn from_date<D: Datelike>(date: &D) -> Self {
+        Self(date.weekday())
+    }
+}
 
 /// A step value constrained by a expression value. The max value of this type differs depending
 /// on the type `E`. The minimum value is always 1.
@@ -474,6 +745,12 @@ impl<E: ExprValue> TryFrom<u8> for Step<E> {
         }
     }
 }
+impl<E> Display for Step<E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
 
 /// A day of the week expression.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -481,14 +758,71 @@ impl<E: ExprValue> TryFrom<u8> for Step<E> {
 pub enum DayOfWeekExpr {
     /// A '*' character
     All,
+    /// A '?' character, marking the field as unused so only the day of the month is matched
+    Ignored,
     /// A `L` character, the last day of the week for the month, paired with a value
     Last(DayOfWeek),
     /// A '#' character
     Nth(DayOfWeek, NthDay),
+    /// Two or more 'L' or '#' expressions, like `MON#1,MON#3`, matching the days any of them
+    /// match
+    Specials(Vec<DayOfWeekSpecial>),
     /// Possibly multiple unique, ranges, or steps
     Many(Exprs<DayOfWeek>),
 }
 
+impl Display for DayOfWeekExpr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DayOfWeekExpr::All => f.write_str("*"),
+            DayOfWeekExpr::Ignored => f.write_str("?"),
+            DayOfWeekExpr::Last(day) => write!(f, "{}L", day),
+            DayOfWeekExpr::Nth(day, nth) => write!(f, "{}#{}", day, nth),
+            DayOfWeekExpr::Specials(specials) => fmt_specials(specials, f),
+            DayOfWeekExpr::Many(exprs) => exprs.fmt(f),
+        }
+    }
+}
+
+/// A special day of the week expression in a list of them, like the `MON#1` in `MON#1,MON#3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DayOfWeekSpecial {
+    /// A `L` character, the last day of the week for the month, paired with a value
+    Last(DayOfWeek),
+    /// A '#' character
+    Nth(DayOfWeek, NthDay),
+}
+
+impl From<DayOfWeekSpecial> for DayOfWeekExpr {
+    fn from(special: DayOfWeekSpecial) -> Self {
+        match special {
+            DayOfWeekSpecial::Last(day) => DayOfWeekExpr::Last(day),
+            DayOfWeekSpecial::Nth(day, nth) => DayOfWeekExpr::Nth(day, nth),
+        }
+    }
+}
+
+impl Display for DayOfWeekSpecial {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DayOfWeekSpecial::Last(day) => write!(f, "{}L", day),
+            DayOfWeekSpecial::Nth(day, nth) => write!(f, "{}#{}", day, nth),
+        }
+    }
+}
+
+/// Formats a list of special expressions separated by commas
+fn fmt_specials<S: Display>(specials: &[S], f: &mut Formatter) -> fmt::Result {
+    for (i, special) in specials.iter().enumerate() {
+        if i != 0 {
+            f.write_str(",")?;
+        }
+        special.fmt(f)?;
+    }
+    Ok(())
+}
+
 /// A "last" expression for [`DayOfMonthExpr`]
 ///
 /// [`DayOfMonthExpr`]: enum.DayOfMonthExpr.html
@@ -505,6 +839,21 @@ pub enum Last {
     /// The closest weekday to the last day of the month offsetted by a value.
     /// For example, a `L-3W`, the weekday closest to the 3rd to last day of the month.
     OffsetWeekday(DayOfMonthOffset),
+    /// The last weekday of the month offsetted by a number of weekdays.
+    /// For example, a `LW-1`, the 2nd to last weekday of the month.
+    WeekdayOffset(DayOfMonthOffset),
+}
+
+impl Display for Last {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Last::Day => f.write_str("L"),
+            Last::Weekday => f.write_str("LW"),
+            Last::Offset(offset) => write!(f, "L-{}", offset),
+            Last::OffsetWeekday(offset) => write!(f, "L-{}W", offset),
+            Last::WeekdayOffset(offset) => write!(f, "LW-{}", offset),
+        }
+    }
 }
 
 /// A day of the month expression.
@@ -513,14 +862,71 @@ pub enum Last {
 pub enum DayOfMonthExpr {
     /// A '*' character
     All,
+    /// A '?' character, marking the field as unused so only the day of the week is matched
+    Ignored,
     /// An expression containing an 'L' character.
     Last(Last),
     /// A 'W' expression, used to mean the closest weekday to the specified day of the month
     ClosestWeekday(DayOfMonth),
+    /// A 'W' after a list of days, like `1-7W` or `1,15W`, used to mean the closest weekday to
+    /// any of the days in the list
+    ClosestWeekdays(Exprs<DayOfMonth>),
+    /// A 'B' expression, used to mean the nth business day (Monday to Friday) of the month
+    BusinessDay(BusinessDay),
+    /// Two or more 'L', 'W', or 'B' expressions, like `1W,15W`, matching the days any of them
+    /// match
+    Specials(Vec<DayOfMonthSpecial>),
     /// Possibly multiple unique, ranges, or steps
     Many(Exprs<DayOfMonth>),
 }
 
+impl Display for DayOfMonthExpr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DayOfMonthExpr::All => f.write_str("*"),
+            DayOfMonthExpr::Ignored => f.write_str("?"),
+            DayOfMonthExpr::Last(last) => last.fmt(f),
+            DayOfMonthExpr::ClosestWeekday(day) => write!(f, "{}W", day),
+            DayOfMonthExpr::ClosestWeekdays(exprs) => write!(f, "{}W", exprs),
+            DayOfMonthExpr::BusinessDay(day) => write!(f, "{}B", day),
+            DayOfMonthExpr::Specials(specials) => fmt_specials(specials, f),
+            DayOfMonthExpr::Many(exprs) => exprs.fmt(f),
+        }
+    }
+}
+
+/// A special day of the month expression in a list of them, like the `1W` in `1W,15W`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DayOfMonthSpecial {
+    /// An expression containing an 'L' character.
+    Last(Last),
+    /// A 'W' expression, used to mean the closest weekday to the specified day of the month
+    ClosestWeekday(DayOfMonth),
+    /// A 'B' expression, used to mean the nth business day (Monday to Friday) of the month
+    BusinessDay(BusinessDay),
+}
+
+impl From<DayOfMonthSpecial> for DayOfMonthExpr {
+    fn from(special: DayOfMonthSpecial) -> Self {
+        match special {
+            DayOfMonthSpecial::Last(last) => DayOfMonthExpr::Last(last),
+            DayOfMonthSpecial::ClosestWeekday(day) => DayOfMonthExpr::ClosestWeekday(day),
+            DayOfMonthSpecial::BusinessDay(day) => DayOfMonthExpr::BusinessDay(day),
+        }
+    }
+}
+
+impl Display for DayOfMonthSpecial {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DayOfMonthSpecial::Last(last) => last.fmt(f),
+            DayOfMonthSpecial::ClosestWeekday(day) => write!(f, "{}W", day),
+            DayOfMonthSpecial::BusinessDay(day) => write!(f, "{}B", day),
+        }
+    }
+}
+
 /// A generic expression that can take a '*' or many exprs.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
@@ -531,6 +937,40 @@ pub enum Expr<E> {
     Many(Exprs<E>),
 }
 
+impl<E: ExprValue + Display + PartialEq> Display for Expr<E> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Expr::All => f.write_str("*"),
+            Expr::Many(exprs) => exprs.fmt(f),
+        }
+    }
+}
+
+impl<E> Expr<E>
+where
+    E: Copy + ExprValue + TryFrom<u8>,
+    u8: From<E>,
+{
+    /// Returns an iterator over the values matched by the field in ascending order, the same
+    /// values a cron value compiled from it matches. Values matched by more than one expression
+    /// are only yielded once.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::CronExpr;
+    ///
+    /// let expr: CronExpr = "0 22-2,*/8 * * *".parse().unwrap();
+    /// let hours = expr.hours.values().map(u8::from).collect::<Vec<_>>();
+    /// assert_eq!(hours, [0, 1, 2, 8, 16, 22, 23]);
+    /// ```
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = E> + ExactSizeIterator + Clone {
+        expr_values(match self {
+            Expr::All => OrsExpr::Range(E::min(), E::max()).mask(),
+            Expr::Many(exprs) => exprs.mask(),
+        })
+    }
+}
+
 /// Either one value, a range, or a step expression
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
@@ -550,6 +990,21 @@ pub enum OrsExpr<E> {
     },
 }
 
+impl<E: ExprValue + Display + PartialEq> Display for OrsExpr<E> {
+    /// Formats the expression. Steps ending on the max value of E are written without an end
+    /// value (i.e. 5/10 instead of 5-59/10).
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OrsExpr::One(value) => value.fmt(f),
+            OrsExpr::Range(start, end) => write!(f, "{}-{}", start, end),
+            OrsExpr::Step { start, end, step } if *end == E::max() => {
+                write!(f, "{}/{}", start, step)
+            }
+            OrsExpr::Step { start, end, step } => write!(f, "{}-{}/{}", start, end, step),
+        }
+    }
+}
+
 impl<E: Copy + ExprValue + PartialEq> OrsExpr<E> {
     /// Normalizes the expression, simplifying it.
     ///
@@ -570,17 +1025,74 @@ impl<E: Copy + ExprValue + PartialEq> OrsExpr<E> {
     }
 }
 
-/// A set of expressions with at least one item.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Exprs<E> {
-    /// The first expression
-    pub first: OrsExpr<E>,
-    /// The rest of the other expressions in the set.
-    pub tail: Vec<OrsExpr<E>>,
-}
+impl<E> OrsExpr<E>
+where
+    E: Copy + ExprValue + TryFrom<u8>,
+    u8: From<E>,
+{
+    /// Returns an iterator over the values matched by the expression in ascending order, the
+    /// same values a cron value compiled from it matches. A range or step ending before it
+    /// starts wraps around past the max value, so `FRI-MON` matches Sunday, Monday, Friday, and
+    /// Saturday.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{Minute, OrsExpr, Step};
+    /// use std::convert::TryFrom;
+    ///
+    /// let minute = |m| Minute::try_from(m).unwrap();
+    /// let expr = OrsExpr::Step {
+    ///     start: minute(50),
+    ///     end: minute(10),
+    ///     step: Step::try_from(7).unwrap(),
+    /// };
+    /// let minutes = expr.values().map(u8::from).collect::<Vec<_>>();
+    /// assert_eq!(minutes, [4, 50, 57]);
+    /// ```
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = E> + ExactSizeIterator + Clone {
+        expr_values(self.mask())
+    }
 
-/// An immutable iterator over all expressions in a set of [`Exprs`]
-///
+    /// Returns a mask of the values matched, numbered from zero like a compiled field
+    fn mask(&self) -> u64 {
+        let min = u8::from(E::min());
+        let count = u8::from(E::max()) - min + 1;
+        let index = |value: E| u8::from(value) - min;
+        match *self {
+            OrsExpr::One(value) => bits::fill(index(value), index(value), 1, count),
+            OrsExpr::Range(start, end) => bits::fill(index(start), index(end), 1, count),
+            OrsExpr::Step { start, end, step } => {
+                bits::fill(index(start), index(end), step.into(), count)
+            }
+        }
+    }
+}
+
+/// Returns an iterator over the values set in a mask from [`OrsExpr::mask`], starting from the
+/// min value of E.
+///
+/// [`OrsExpr::mask`]: enum.OrsExpr.html#method.mask
+fn expr_values<E>(mask: u64) -> impl DoubleEndedIterator<Item = E> + ExactSizeIterator + Clone
+where
+    E: ExprValue + TryFrom<u8>,
+{
+    FieldValues::new(mask, E::MIN).map(|value| match E::try_from(value) {
+        Ok(value) => value,
+        Err(_) => unreachable!("values in the mask are in range"),
+    })
+}
+
+/// A set of expressions with at least one item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exprs<E> {
+    /// The first expression
+    pub first: OrsExpr<E>,
+    /// The rest of the other expressions in the set.
+    pub tail: Vec<OrsExpr<E>>,
+}
+
+/// An immutable iterator over all expressions in a set of [`Exprs`]
+///
 /// [`Exprs`]: struct.Exprs.html
 pub type ExprsIter<'a, E> = Chain<Once<&'a OrsExpr<E>>, slice::Iter<'a, OrsExpr<E>>>;
 
@@ -602,17 +1114,44 @@ impl<E> Exprs<E> {
     }
 
     /// Iterates over all expressions in this set
-    pub fn iter(&self) -> ExprsIter<E> {
+    pub fn iter(&self) -> ExprsIter<'_, E> {
         core::iter::once(&self.first).chain(self.tail.iter())
     }
 }
 
+impl<E> Exprs<E>
+where
+    E: Copy + ExprValue + TryFrom<u8>,
+    u8: From<E>,
+{
+    /// Returns an iterator over the values matched by any of the expressions in ascending order.
+    /// Values matched by more than one expression are only yielded once.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = E> + ExactSizeIterator + Clone {
+        expr_values(self.mask())
+    }
+
+    fn mask(&self) -> u64 {
+        self.iter().fold(0, |mask, expr| mask | expr.mask())
+    }
+}
+
 impl<E> IntoIterator for Exprs<E> {
     type Item = OrsExpr<E>;
     type IntoIter = IntoExprsIter<E>;
 
     fn into_iter(self) -> Self::IntoIter {
-        core::iter::once(self.first).chain(self.tail.into_iter())
+        core::iter::once(self.first).chain(self.tail)
+    }
+}
+
+impl<E: ExprValue + Display + PartialEq> Display for Exprs<E> {
+    /// Formats the set as a comma separated list of expressions
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.first.fmt(f)?;
+        for expr in &self.tail {
+            write!(f, ",{}", expr)?;
+        }
+        Ok(())
     }
 }
 
@@ -640,6 +1179,182 @@ pub struct CronExpr {
     pub months: Expr<Month>,
     /// The day of the week part of the expression.
     pub dows: DayOfWeekExpr,
+    /// The ISO week of the year part of the expression. Only expressions parsed in the
+    /// [`Dialect::Extended`] dialect can restrict the weeks, every other expression matches every
+    /// week.
+    pub weeks: Expr<Week>,
+    /// The shortcut this expression was parsed from, if any. The fields of the expression are
+    /// still set to the equivalent values of the shortcut.
+    pub shortcut: Option<Shortcut>,
+    /// The values removed from each field with a `!`. Only expressions parsed in the
+    /// [`Dialect::Extended`] dialect can exclude values, every other expression has no
+    /// exclusions.
+    pub exclusions: Exclusions,
+    /// Whether a day must match both the day of month and day of week fields instead of
+    /// either, written with a `&` before the day of week field (i.e. `0 0 13 * &FRI`). Only
+    /// expressions parsed in the [`Dialect::Extended`] dialect can combine the day fields,
+    /// and compiling one sets the value's [`DayPolicy`] to [`DayPolicy::And`].
+    ///
+    /// [`DayPolicy`]: ../enum.DayPolicy.html
+    /// [`DayPolicy::And`]: ../enum.DayPolicy.html#variant.And
+    pub days_and: bool,
+}
+
+/// The values excluded from the fields of an expression, written after a `!` at the end of a
+/// field (i.e. the `15` in `*!15`). A field matches the values of its expressions that aren't
+/// excluded. Values can only be excluded from fields of a `*` or values, ranges, and steps.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Exclusions {
+    /// The minutes excluded from the minutes field
+    pub minutes: Option<Exprs<Minute>>,
+    /// The hours excluded from the hours field
+    pub hours: Option<Exprs<Hour>>,
+    /// The days excluded from the day of the month field
+    pub doms: Option<Exprs<DayOfMonth>>,
+    /// The months excluded from the months field
+    pub months: Option<Exprs<Month>>,
+    /// The days excluded from the day of the week field
+    pub dows: Option<Exprs<DayOfWeek>>,
+    /// The weeks excluded from the weeks field
+    pub weeks: Option<Exprs<Week>>,
+}
+
+impl Exclusions {
+    /// Returns whether no field excludes any values
+    pub fn is_empty(&self) -> bool {
+        self == &Exclusions::default()
+    }
+}
+
+/// A nickname for a common cron expression, like `@daily`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Shortcut {
+    /// `@hourly`, equivalent to `0 * * * *`
+    Hourly,
+    /// `@daily`, equivalent to `0 0 * * *`
+    Daily,
+    /// `@weekly`, equivalent to `0 0 * * SUN`
+    Weekly,
+    /// `@monthly`, equivalent to `0 0 1 * *`
+    Monthly,
+    /// `@yearly`, equivalent to `0 0 1 1 *`
+    Yearly,
+    /// `@annually`, equivalent to `0 0 1 1 *`
+    Annually,
+}
+
+impl Shortcut {
+    pub(crate) const ALL: [Shortcut; 6] = [
+        Shortcut::Hourly,
+        Shortcut::Daily,
+        Shortcut::Weekly,
+        Shortcut::Monthly,
+        Shortcut::Yearly,
+        Shortcut::Annually,
+    ];
+
+    /// Returns the name of the shortcut without the leading `@`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shortcut::Hourly => "hourly",
+            Shortcut::Daily => "daily",
+            Shortcut::Weekly => "weekly",
+            Shortcut::Monthly => "monthly",
+            Shortcut::Yearly => "yearly",
+            Shortcut::Annually => "annually",
+        }
+    }
+
+    /// Returns the expression equivalent to this shortcut
+    pub fn expr(self) -> CronExpr {
+        // every shortcut is made of the min value of a field or '*'
+        fn min<E: ExprValue>() -> Exprs<E> {
+            Exprs::new(OrsExpr::One(E::min()))
+        }
+
+        let mut expr = CronExpr {
+            minutes: Expr::Many(min()),
+            hours: Expr::Many(min()),
+            doms: DayOfMonthExpr::All,
+            months: Expr::All,
+            dows: DayOfWeekExpr::All,
+            weeks: Expr::All,
+            shortcut: Some(self),
+            exclusions: Exclusions::default(),
+            days_and: false,
+        };
+        match self {
+            Shortcut::Hourly => expr.hours = Expr::All,
+            Shortcut::Daily => {}
+            Shortcut::Weekly => expr.dows = DayOfWeekExpr::Many(min()),
+            Shortcut::Monthly => expr.doms = DayOfMonthExpr::Many(min()),
+            Shortcut::Yearly | Shortcut::Annually => {
+                expr.doms = DayOfMonthExpr::Many(min());
+                expr.months = Expr::Many(min());
+            }
+        }
+        expr
+    }
+}
+
+impl Display for Shortcut {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "@{}", self.name())
+    }
+}
+
+impl Display for CronExpr {
+    /// Formats the expression as a cron string. The output can be parsed back into an
+    /// equal expression. Expressions restricting the weeks of the year are written with a sixth
+    /// field, which is only parsed in the [`Dialect::Extended`] dialect.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::CronExpr;
+    ///
+    /// let expr: CronExpr = "*/5 0 * JAN-MAR MON".parse().expect("Valid cron expression");
+    /// assert_eq!(expr.to_string(), "0/5 0 * 1-3 2");
+    /// assert_eq!(expr.to_string().parse::<CronExpr>().unwrap(), expr);
+    /// ```
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(shortcut) = self.shortcut {
+            return shortcut.fmt(f);
+        }
+
+        /// Writes the values excluded from a field, if any
+        fn excluded<E>(exprs: &Option<Exprs<E>>) -> impl Display + '_
+        where
+            E: ExprValue + Display + PartialEq,
+        {
+            crate::describe::display(move |f| match exprs {
+                Some(exprs) => write!(f, "!{}", exprs),
+                None => Ok(()),
+            })
+        }
+
+        let exclusions = &self.exclusions;
+        write!(
+            f,
+            "{}{} {}{} {}{} {}{} {}{}{}",
+            self.minutes,
+            excluded(&exclusions.minutes),
+            self.hours,
+            excluded(&exclusions.hours),
+            self.doms,
+            excluded(&exclusions.doms),
+            self.months,
+            excluded(&exclusions.months),
+            if self.days_and { "&" } else { "" },
+            self.dows,
+            excluded(&exclusions.dows)
+        )?;
+        match (&self.weeks, &exclusions.weeks) {
+            (Expr::All, None) => Ok(()),
+            (weeks, excluded_weeks) => write!(f, " W{}{}", weeks, excluded(excluded_weeks)),
+        }
+    }
 }
 
 /// A formatter for displaying a cron expression description in a specified language
@@ -651,7 +1366,27 @@ pub struct LanguageFormatter<'a, L> {
 
 impl<'a, L: Language> Display for LanguageFormatter<'a, L> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        self.lang.fmt_expr(self.expr, f)
+        crate::describe::fmt_description(&self.lang, self.expr, f)
+    }
+}
+
+impl<'a, L: Language> LanguageFormatter<'a, L> {
+    /// Returns the description split into segments, each describing one part of the expression.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, English, SegmentKind};
+    ///
+    /// let cron: CronExpr = "0 9 * * MON".parse().expect("Valid cron expression");
+    ///
+    /// let segments = cron.describe(English::default()).segments();
+    /// assert_eq!(segments[0].kind, SegmentKind::Time);
+    /// assert_eq!(segments[0].text, "At 9:00 AM");
+    /// assert_eq!(segments[1].kind, SegmentKind::DaysOfWeek);
+    /// assert_eq!(segments[1].text, " on Monday");
+    /// ```
+    pub fn segments(&self) -> Vec<DescriptionSegment> {
+        self.lang.segments(self.expr)
     }
 }
 
@@ -667,479 +1402,3428 @@ impl CronExpr {
     /// let description = cron.describe(English::default()).to_string();
     /// assert_eq!("Every minute", description);
     /// ```
-    pub fn describe<L: Language>(&self, lang: L) -> LanguageFormatter<L> {
+    pub fn describe<L: Language>(&self, lang: L) -> LanguageFormatter<'_, L> {
         LanguageFormatter { expr: self, lang }
     }
-}
 
-/// An error indicating that the provided cron expression failed to parse
-#[derive(Debug)]
-pub struct CronParseError(());
+    /// Returns a view of one field of the expression.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, FieldKind, FieldView};
+    ///
+    /// let expr: CronExpr = "*/15 9-11 L * ?".parse().expect("Valid cron expression");
+    ///
+    /// let minutes = expr.field(FieldKind::Minutes).values().unwrap();
+    /// assert_eq!(minutes.collect::<Vec<_>>(), [0, 15, 30, 45]);
+    /// assert!(expr.field(FieldKind::Months).is_all());
+    /// assert_eq!(expr.field(FieldKind::DaysOfWeek), FieldView::Ignored);
+    /// assert!(matches!(
+    ///     expr.field(FieldKind::DaysOfMonth),
+    ///     FieldView::DayOfMonthSpecials(specials) if specials.len() == 1
+    /// ));
+    /// ```
+    pub fn field(&self, kind: FieldKind) -> FieldView {
+        let view = match kind {
+            FieldKind::Minutes => FieldView::from_expr(&self.minutes),
+            FieldKind::Hours => FieldView::from_expr(&self.hours),
+            FieldKind::DaysOfMonth => match &self.doms {
+                DayOfMonthExpr::All => FieldView::all::<DayOfMonth>(),
+                DayOfMonthExpr::Ignored => FieldView::Ignored,
+                DayOfMonthExpr::Last(last) => FieldView::DayOfMonthSpecials(
+                    core::iter::once(DayOfMonthSpecial::Last(last.clone())).collect(),
+                ),
+                &DayOfMonthExpr::ClosestWeekday(day) => FieldView::DayOfMonthSpecials(
+                    core::iter::once(DayOfMonthSpecial::ClosestWeekday(day)).collect(),
+                ),
+                DayOfMonthExpr::ClosestWeekdays(exprs) => {
+                    FieldView::ClosestWeekdays(FieldView::exprs_values(exprs))
+                }
+                &DayOfMonthExpr::BusinessDay(day) => FieldView::DayOfMonthSpecials(
+                    core::iter::once(DayOfMonthSpecial::BusinessDay(day)).collect(),
+                ),
+                DayOfMonthExpr::Specials(specials) => {
+                    FieldView::DayOfMonthSpecials(specials.clone())
+                }
+                DayOfMonthExpr::Many(exprs) => FieldView::Values(FieldView::exprs_values(exprs)),
+            },
+            FieldKind::Months => FieldView::from_expr(&self.months),
+            FieldKind::DaysOfWeek => match &self.dows {
+                DayOfWeekExpr::All => FieldView::all::<DayOfWeek>(),
+                DayOfWeekExpr::Ignored => FieldView::Ignored,
+                &DayOfWeekExpr::Last(day) => FieldView::DayOfWeekSpecials(
+                    core::iter::once(DayOfWeekSpecial::Last(day)).collect(),
+                ),
+                &DayOfWeekExpr::Nth(day, nth) => FieldView::DayOfWeekSpecials(
+                    core::iter::once(DayOfWeekSpecial::Nth(day, nth)).collect(),
+                ),
+                DayOfWeekExpr::Specials(specials) => FieldView::DayOfWeekSpecials(specials.clone()),
+                DayOfWeekExpr::Many(exprs) => FieldView::Values(FieldView::exprs_values(exprs)),
+            },
+            FieldKind::Weeks => FieldView::from_expr(&self.weeks),
+        };
+
+        let exclusions = &self.exclusions;
+        let excluded = match kind {
+            FieldKind::Minutes => exclusions.minutes.as_ref().map(exprs_mask),
+            FieldKind::Hours => exclusions.hours.as_ref().map(exprs_mask),
+            FieldKind::DaysOfMonth => exclusions.doms.as_ref().map(exprs_mask),
+            FieldKind::Months => exclusions.months.as_ref().map(exprs_mask),
+            FieldKind::DaysOfWeek => exclusions.dows.as_ref().map(exprs_mask),
+            FieldKind::Weeks => exclusions.weeks.as_ref().map(exprs_mask),
+        };
+        match (view, excluded) {
+            (FieldView::All(values), Some(excluded))
+            | (FieldView::Values(values), Some(excluded)) => {
+                FieldView::Values(FieldValues::new(values.bits & !excluded, values.offset))
+            }
+            (view, _) => view,
+        }
+    }
 
-impl Display for CronParseError {
-    #[inline]
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        "Failed to parse cron expression".fmt(f)
+    /// Returns the expression in a canonical form, so two expressions matching the same values
+    /// in every field normalize into equal expressions.
+    ///
+    /// Normalizations:
+    ///  * Every field is rewritten as a list of unique values and ranges in ascending order,
+    ///    merging overlapping values, ranges, and steps (i.e. 5,1-3,2 is written as 1-3,5)
+    ///  * A field matching three or more values spaced evenly apart is written as one step
+    ///    (i.e. 0,15,30,45 is written as 0/15)
+    ///  * A field matching every value is written as '*'. A day of the month or day of the week
+    ///    field is only rewritten as '*' if the other day field is '*', '?', or also matches every
+    ///    value, since a restricted day field changes how the other is matched.
+    ///  * The shortcut the expression was parsed from is removed
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::CronExpr;
+    ///
+    /// let expr: CronExpr = "5,1-3,2 */6,12 1-31 * MON-SUN".parse().expect("Valid cron expression");
+    /// assert_eq!(expr.normalize().to_string(), "1-3,5 0/6 * * *");
+    /// ```
+    pub fn normalize(&self) -> CronExpr {
+        fn expr<E>(expr: &Expr<E>) -> Expr<E>
+        where
+            E: Copy + ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+            u8: From<E>,
+        {
+            match expr {
+                Expr::All => Expr::All,
+                Expr::Many(exprs) => match normalize_exprs(exprs) {
+                    Some(exprs) => Expr::Many(exprs),
+                    None => Expr::All,
+                },
+            }
+        }
+
+        let folded = self.fold_exclusions();
+
+        // a day field matching every day is the same as '*' if the other field places no
+        // restriction on the day or also matches every day
+        let doms = match &folded.doms {
+            DayOfMonthExpr::Many(exprs) => Ok(normalize_exprs(exprs)),
+            DayOfMonthExpr::ClosestWeekdays(exprs) => Err(DayOfMonthExpr::ClosestWeekdays(
+                normalize_exprs(exprs).unwrap_or_else(|| {
+                    Exprs::new(OrsExpr::Range(
+                        <DayOfMonth as ExprValue>::min(),
+                        <DayOfMonth as ExprValue>::max(),
+                    ))
+                }),
+            )),
+            doms => Err(doms.clone()),
+        };
+        let dows = match &folded.dows {
+            DayOfWeekExpr::Many(exprs) => Ok(normalize_exprs(exprs)),
+            dows => Err(dows.clone()),
+        };
+        let doms_all = matches!(
+            doms,
+            Ok(None) | Err(DayOfMonthExpr::All) | Err(DayOfMonthExpr::Ignored)
+        );
+        let dows_all = matches!(
+            dows,
+            Ok(None) | Err(DayOfWeekExpr::All) | Err(DayOfWeekExpr::Ignored)
+        );
+        let doms = match doms {
+            Ok(Some(exprs)) => DayOfMonthExpr::Many(exprs),
+            Ok(None) if dows_all => DayOfMonthExpr::All,
+            Ok(None) => DayOfMonthExpr::Many(Exprs::new(OrsExpr::Range(
+                <DayOfMonth as ExprValue>::min(),
+                <DayOfMonth as ExprValue>::max(),
+            ))),
+            Err(doms) => doms,
+        };
+        let dows = match dows {
+            Ok(Some(exprs)) => DayOfWeekExpr::Many(exprs),
+            Ok(None) if doms_all => DayOfWeekExpr::All,
+            Ok(None) => DayOfWeekExpr::Many(Exprs::new(OrsExpr::Range(
+                <DayOfWeek as ExprValue>::min(),
+                <DayOfWeek as ExprValue>::max(),
+            ))),
+            Err(dows) => dows,
+        };
+
+        CronExpr {
+            minutes: expr(&folded.minutes),
+            hours: expr(&folded.hours),
+            doms,
+            months: expr(&folded.months),
+            dows,
+            weeks: expr(&folded.weeks),
+            shortcut: None,
+            exclusions: folded.exclusions,
+            days_and: folded.days_and,
+        }
     }
-}
 
-#[cfg(feature = "std")]
-impl std::error::Error for CronParseError {}
+    /// Returns the expression with its excluded values removed from the values of each field. A
+    /// field left without any values, or a day field that isn't a list of values, keeps its
+    /// exclusions.
+    pub(crate) fn fold_exclusions(&self) -> CronExpr {
+        fn fold<E>(exprs: Option<&Exprs<E>>, excluded: &Option<Exprs<E>>) -> Option<Exprs<E>>
+        where
+            E: Copy + ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+            u8: From<E>,
+        {
+            let excluded = excluded.as_ref()?;
+            let mask = exprs.map_or_else(all_mask::<E>, exprs_mask) & !exprs_mask(excluded);
+            mask_exprs(mask)
+        }
 
-/// A parser that can parse a single value, a range of values, or a step expression
-fn ors_expr<E, F>(f: F) -> impl Fn(&str) -> IResult<&str, OrsExpr<E>>
-where
-    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Ord + Copy,
-    F: Fn(&str) -> IResult<&str, E>,
-{
-    move |input: &str| {
-        let (input, value) = alt((&f, map(char('*'), |_| ExprValue::min())))(input)?;
-        match opt(alt((char('/'), char('-'))))(input)? {
-            (input, Some('/')) => map(step_digit::<E>(), |step| OrsExpr::Step {
-                start: value,
-                end: ExprValue::max(),
-                step,
-            })(input),
-            (input, Some('-')) => {
-                let (input, end) = f(input)?;
-                match opt(char('/'))(input)? {
-                    (input, Some(_)) => map(step_digit::<E>(), |step| OrsExpr::Step {
-                        start: value,
-                        end,
-                        step,
-                    })(input),
-                    (input, None) => Ok((input, OrsExpr::Range(value, end))),
+        fn expr<E>(expr: &Expr<E>, excluded: &mut Option<Exprs<E>>) -> Expr<E>
+        where
+            E: Copy + ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+            u8: From<E>,
+        {
+            let exprs = match expr {
+                Expr::All => None,
+                Expr::Many(exprs) => Some(exprs),
+            };
+            match fold(exprs, excluded) {
+                Some(exprs) => {
+                    *excluded = None;
+                    Expr::Many(exprs)
                 }
+                None => expr.clone(),
             }
-            (input, _) => Ok((input, OrsExpr::One(value))),
+        }
+
+        let mut exclusions = self.exclusions.clone();
+        let doms = match &self.doms {
+            DayOfMonthExpr::All => fold(None, &exclusions.doms),
+            DayOfMonthExpr::Many(exprs) => fold(Some(exprs), &exclusions.doms),
+            _ => None,
+        };
+        let doms = match doms {
+            Some(exprs) => {
+                exclusions.doms = None;
+                DayOfMonthExpr::Many(exprs)
+            }
+            None => self.doms.clone(),
+        };
+        let dows = match &self.dows {
+            DayOfWeekExpr::All => fold(None, &exclusions.dows),
+            DayOfWeekExpr::Many(exprs) => fold(Some(exprs), &exclusions.dows),
+            _ => None,
+        };
+        let dows = match dows {
+            Some(exprs) => {
+                exclusions.dows = None;
+                DayOfWeekExpr::Many(exprs)
+            }
+            None => self.dows.clone(),
+        };
+
+        CronExpr {
+            minutes: expr(&self.minutes, &mut exclusions.minutes),
+            hours: expr(&self.hours, &mut exclusions.hours),
+            doms,
+            months: expr(&self.months, &mut exclusions.months),
+            dows,
+            weeks: expr(&self.weeks, &mut exclusions.weeks),
+            shortcut: self.shortcut,
+            exclusions,
+            days_and: self.days_and,
         }
     }
 }
 
-/// Consumes a set of trailing ORS expressions
-fn tail_ors_exprs<'a, E, F>(
-    mut input: &'a str,
-    f: F,
-    mut exprs: Exprs<E>,
-) -> IResult<&'a str, Exprs<E>>
+/// Returns a mask of the values matched by a set of expressions, with every value stored as its
+/// zero based offset from the min value
+pub(crate) fn exprs_mask<E>(exprs: &Exprs<E>) -> u64
 where
-    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Ord + Copy,
-    F: Fn(&str) -> IResult<&str, E>,
+    E: Copy + ExprValue,
+    u8: From<E>,
 {
-    loop {
-        let comma = opt(char(','))(input)?;
-        input = comma.0;
-        if comma.1.is_none() {
-            break Ok((input, exprs));
-        }
-
-        let expr = ors_expr::<E, _>(&f)(input)?;
-        input = expr.0;
-        exprs.tail.push(expr.1);
-    }
+    exprs.iter().fold(0, |mask, expr| mask | expr_mask(expr))
 }
 
-/// A parser that can parse delimited expressions given a parser for that part.
-/// This can't parse day of the month or week expressions.
-fn expr<E, F>(f: F) -> impl Fn(&str) -> IResult<&str, Expr<E>>
+/// Returns a mask of the values matched by one expression, like [`exprs_mask`]
+pub(crate) fn expr_mask<E>(expr: &OrsExpr<E>) -> u64
 where
-    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Ord + Copy,
-    F: Fn(&str) -> IResult<&str, E>,
+    E: Copy + ExprValue,
+    u8: From<E>,
 {
-    move |mut input: &str| {
-        let expressions: Exprs<E>;
-        // Attempt to read a `*`. If that succeeds,
-        // try to read a `/` for a step expr.
-        // If this isn't a step expr, return Expr::All,
-        // If it's not a `*`, initialize the expressions
-        // list with an ors_expr.
-        let star = opt(char('*'))(input)?;
-        input = star.0;
-        if star.1.is_some() {
-            let slash = opt(char('/'))(input)?;
-            input = slash.0;
-            // If there is no slash after this, just return All and expect the next
-            // parser to fail if it's invalid
-            if slash.1.is_none() {
-                return Ok((input, Expr::All));
-            }
-            let step = step_digit::<E>()(input)?;
-            input = step.0;
-            expressions = Exprs::new(OrsExpr::Step {
-                start: ExprValue::min(),
-                end: ExprValue::max(),
-                step: step.1,
-            })
-        } else {
-            let expr = ors_expr::<E, _>(&f)(input)?;
-            input = expr.0;
-            expressions = Exprs::new(expr.1)
-        }
-
-        let (input, exprs) = tail_ors_exprs(input, &f, expressions)?;
+    let (start, end, step) = match *expr {
+        OrsExpr::One(value) => (u8::from(value), u8::from(value), 1),
+        OrsExpr::Range(start, end) => (u8::from(start), u8::from(end), 1),
+        OrsExpr::Step { start, end, step } => (u8::from(start), u8::from(end), step.value),
+    };
+    bits::fill(start, end, step, E::MAX - E::MIN + 1)
+}
 
-        Ok((input, Expr::Many(exprs)))
-    }
+/// Returns a mask of every value of an expression value type, like [`exprs_mask`]
+fn all_mask<E: ExprValue>() -> u64 {
+    (1 << (E::MAX - E::MIN + 1)) - 1
 }
 
-#[inline]
-fn map_digit1<E>() -> impl Fn(&str) -> IResult<&str, E>
+/// Normalizes a set of expressions into a list of unique values and ranges, or one step. Returns
+/// `None` if the expressions match every value.
+fn normalize_exprs<E>(exprs: &Exprs<E>) -> Option<Exprs<E>>
 where
-    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+    E: Copy + ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+    u8: From<E>,
 {
-    move |input: &str| {
-        map_res(digit1, |s: &str| {
-            let value = s
-                .parse::<u8>()
-                // discard error, we won't see it anyway
-                .map_err(|_| ValueOutOfRangeError)?;
+    let count = E::MAX - E::MIN + 1;
+    let mask = exprs_mask(exprs);
+    if mask == all_mask::<E>() {
+        return None;
+    }
 
-            E::try_from(value)
-        })(input)
+    let value = |offset: u8| E::try_from(offset + E::MIN).expect("Offsets are in range");
+    let offsets = (0..count)
+        .filter(|offset| mask & (1 << offset) != 0)
+        .collect::<Vec<_>>();
+
+    if let [first, second, _, ..] = *offsets.as_slice() {
+        let step = second - first;
+        if step > 1 && offsets.windows(2).all(|pair| pair[1] - pair[0] == step) {
+            // end the step on the max value if no other value would be matched before it
+            let last = offsets[offsets.len() - 1];
+            let end = if last + step >= count {
+                count - 1
+            } else {
+                last
+            };
+            return Some(Exprs::new(OrsExpr::Step {
+                start: value(first),
+                end: value(end),
+                step: Step::try_from(step).expect("Steps are less than the number of values"),
+            }));
+        }
     }
+
+    mask_exprs(mask)
 }
 
-#[inline]
-fn step_digit<E>() -> impl Fn(&str) -> IResult<&str, Step<E>>
+/// Writes the values of a mask, like the one returned by [`exprs_mask`], as a list of unique
+/// values and ranges in ascending order. Returns `None` if the mask has no values.
+fn mask_exprs<E>(mask: u64) -> Option<Exprs<E>>
 where
-    E: ExprValue,
+    E: Copy + ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
 {
-    map_digit1()
+    let count = E::MAX - E::MIN + 1;
+    let value = |offset: u8| E::try_from(offset + E::MIN).expect("Offsets are in range");
+    let mut runs = Vec::new();
+    let mut offsets = (0..count)
+        .filter(|offset| mask & (1 << offset) != 0)
+        .peekable();
+    while let Some(start) = offsets.next() {
+        let mut end = start;
+        while offsets.peek() == Some(&(end + 1)) {
+            end = offsets.next().expect("Peeked offset");
+        }
+
+        runs.push(if start == end {
+            OrsExpr::One(value(start))
+        } else {
+            OrsExpr::Range(value(start), value(end))
+        });
+    }
+
+    let mut runs = runs.into_iter();
+    let mut exprs = Exprs::new(runs.next()?);
+    exprs.tail.extend(runs);
+    Some(exprs)
 }
 
-fn month(s: &str) -> IResult<&str, Month> {
-    alt((
-        map_digit1::<Month>(),
-        map(tag_no_case("JAN"), |_| Month(1)),
-        map(tag_no_case("FEB"), |_| Month(2)),
-        map(tag_no_case("MAR"), |_| Month(3)),
-        map(tag_no_case("APR"), |_| Month(4)),
-        map(tag_no_case("MAY"), |_| Month(5)),
-        map(tag_no_case("JUN"), |_| Month(6)),
-        map(tag_no_case("JUL"), |_| Month(7)),
-        map(tag_no_case("AUG"), |_| Month(8)),
-        map(tag_no_case("SEP"), |_| Month(9)),
-        map(tag_no_case("OCT"), |_| Month(10)),
-        map(tag_no_case("NOV"), |_| Month(11)),
-        map(tag_no_case("DEC"), |_| Month(12)),
-    ))(s)
+/// The flavor of cron syntax an expression is parsed as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Dialect {
+    /// Quartz numbering, where the days of the week are 1-7 (SUN-SAT). This is the default.
+    #[default]
+    Quartz,
+    /// POSIX and Vixie cron numbering, where the days of the week are 0-6 (SUN-SAT) and 7 is
+    /// also Sunday. Like in crontabs, both day fields can be restricted without a '?', in which
+    /// case a day matching either field matches.
+    Unix,
+    /// Quartz numbering with saffron's extensions. An optional sixth field restricts the ISO
+    /// weeks of the year (1-53), written as a `W` followed by weeks like any other field (i.e.
+    /// `W1-26`). A step without a start counts from the first week, so `0 9 * * MON W/2` runs on
+    /// Mondays of odd weeks. Values can be excluded from a field of a `*` or values, ranges, and
+    /// steps by writing them after a `!` (i.e. `*!15` runs every minute except at 15 minutes
+    /// past the hour). The day of the month can be `FY` or `LY` for the first or last day of the
+    /// year, if the months field is a `*` (i.e. `0 0 LY * ?` is `0 0 31 12 ?`). A `&` before the
+    /// day of week field makes a day match both day fields instead of either (i.e.
+    /// `0 0 15 * &FRI` runs on the 15th, but only if it's a Friday).
+    Extended,
 }
 
-#[inline]
-fn minutes_expr(s: &str) -> IResult<&str, Expr<Minute>> {
-    expr(map_digit1())(s)
+impl Dialect {
+    /// Returns how the days of the week are numbered in the dialect
+    pub fn weekday_numbering(self) -> WeekdayNumbering {
+        match self {
+            Dialect::Quartz | Dialect::Extended => WeekdayNumbering::Quartz,
+            Dialect::Unix => WeekdayNumbering::Unix,
+        }
+    }
 }
 
-#[inline]
-fn hours_expr(s: &str) -> IResult<&str, Expr<Hour>> {
-    expr(map_digit1())(s)
+/// How the days of the week are numbered in the day of the week field. The names of the days
+/// are the same in every numbering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WeekdayNumbering {
+    /// 1-7 starting from Sunday, like in Quartz. This is the numbering expressions are
+    /// displayed in.
+    Quartz,
+    /// 0-6 starting from Sunday, where 7 is also Sunday, like in crontabs
+    Unix,
+    /// 1-7 starting from Monday, like in ISO 8601
+    Iso,
 }
 
-fn dom_expr(input: &str) -> IResult<&str, DayOfMonthExpr> {
-    let dom = map_digit1::<DayOfMonth>();
+impl WeekdayNumbering {
+    /// Returns the lowest number of a day of the week
+    fn min(self) -> u8 {
+        match self {
+            WeekdayNumbering::Quartz | WeekdayNumbering::Iso => 1,
+            WeekdayNumbering::Unix => 0,
+        }
+    }
+}
 
-    let (input, start) = opt(alt((char('*'), char('L'))))(input)?;
-    match start {
-        Some('*') => {
-            let (input, maybe_step) = opt(tuple((char('/'), step_digit::<DayOfMonth>())))(input)?;
+/// Options used when parsing a cron expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// The dialect the expression is written in
+    pub dialect: Dialect,
+    /// Ignores spaces and tabs before and after the expression, like in the lines of a crontab.
+    /// Error positions are still byte offsets in the untrimmed expression.
+    pub trim_whitespace: bool,
+    /// The longest expression in bytes that's parsed, after trimming whitespace. Longer
+    /// expressions fail with [`CronParseError::TooLong`] before anything is parsed. The default
+    /// is 1024 bytes.
+    pub max_len: usize,
+    /// The most comma separated terms in one field, including any values excluded with a `!`.
+    /// Fields with more fail with [`CronParseError::TooManyTerms`] before anything is parsed.
+    /// The default is 256 terms, which is more than any field has values.
+    pub max_terms: usize,
+    /// Localized names of the months and days of the week accepted along with the English names,
+    /// like `LUN` or `MÄR`. Error positions are still byte offsets in the expression as written.
+    /// The default is `None`, which only accepts the English names.
+    pub locale: Option<&'static Locale>,
+    /// Accepts the full names of the months and days of the week, like `SEPTEMBER` or `MONDAY`,
+    /// along with the three letter abbreviations. Names are read without regard to case.
+    pub allow_full_names: bool,
+    /// How the days of the week are numbered, overriding the numbering of the dialect. The
+    /// default is `None`, which uses the numbering of the dialect. Expressions are still
+    /// displayed with Quartz numbering.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, ParseOptions, WeekdayNumbering};
+    ///
+    /// let mut options = ParseOptions::new();
+    /// options.weekday_numbering = Some(WeekdayNumbering::Iso);
+    ///
+    /// let expr = CronExpr::from_str_with_options("0 9 ? * 1-5,7", options).unwrap();
+    /// assert_eq!(expr, "0 9 ? * MON-FRI,SUN".parse().unwrap());
+    /// ```
+    pub weekday_numbering: Option<WeekdayNumbering>,
+    /// Ignores a `#` comment after the expression and any spaces, tabs, and newlines at its
+    /// end, like in an expression copied from a crontab. The comment has to come after a space
+    /// or tab, so the `#` of an nth day of the week isn't read as one. The comment is returned
+    /// by [`CronExpr::from_str_with_comment`].
+    pub allow_trailing_comment: bool,
+}
 
-            if let Some((_, step)) = maybe_step {
-                let exprs = Exprs::new(OrsExpr::Step {
-                    start: DayOfMonth(1),
-                    end: ExprValue::max(),
-                    step,
-                });
+impl ParseOptions {
+    /// Creates a new set of options with their default values
+    pub const fn new() -> Self {
+        Self {
+            dialect: Dialect::Quartz,
+            trim_whitespace: false,
+            max_len: 1024,
+            max_terms: 256,
+            locale: None,
+            allow_full_names: false,
+            weekday_numbering: None,
+            allow_trailing_comment: false,
+        }
+    }
 
-                let (input, exprs) = tail_ors_exprs(input, dom, exprs)?;
-                Ok((input, DayOfMonthExpr::Many(exprs)))
-            } else {
-                Ok((input, DayOfMonthExpr::All))
-            }
+    /// Returns how the days of the week are numbered with these options
+    fn weekday_numbering(&self) -> WeekdayNumbering {
+        self.weekday_numbering
+            .unwrap_or_else(|| self.dialect.weekday_numbering())
+    }
+
+    /// Checks the expression against the length and term limits without parsing it, so a
+    /// hostile expression is rejected before its fields are allocated.
+    pub(crate) fn check_limits(&self, s: &str) -> Result<(), CronParseError> {
+        if s.len() > self.max_len {
+            return Err(CronParseError::TooLong {
+                len: s.len(),
+                max: self.max_len,
+            });
         }
-        Some('L') => {
-            let (input, modifier) = opt(alt((char('-'), char('W'))))(input)?;
-            match modifier {
-                Some('-') => {
-                    let offset = map_digit1::<DayOfMonthOffset>();
-                    let (input, (offset, weekday)) = tuple((offset, opt(char('W'))))(input)?;
 
-                    if weekday.is_some() {
-                        Ok((input, DayOfMonthExpr::Last(Last::OffsetWeekday(offset))))
-                    } else {
-                        Ok((input, DayOfMonthExpr::Last(Last::Offset(offset))))
-                    }
+        let mut fields = FieldKind::ALL.iter();
+        let mut field = None;
+        let mut terms = 0;
+        for (at, c) in s.char_indices() {
+            if is_blank(c) {
+                field = None;
+                continue;
+            }
+            if field.is_none() {
+                // input after the last field is left for the parser to report
+                field = Some(match fields.next() {
+                    Some(&field) => field,
+                    None => return Ok(()),
+                });
+                terms = 1;
+            }
+            if c == ',' {
+                terms += 1;
+                if terms > self.max_terms {
+                    return Err(CronParseError::TooManyTerms {
+                        field: field.expect("A field is being read"),
+                        max: self.max_terms,
+                        at,
+                    });
                 }
-                Some('W') => Ok((input, DayOfMonthExpr::Last(Last::Weekday))),
-                _ => Ok((input, DayOfMonthExpr::Last(Last::Day))),
             }
         }
-        _ => {
-            let (input, day) = dom(input)?;
+        Ok(())
+    }
+}
 
-            let (input, maybe_char) = opt(alt((char('W'), char('-'), char('/'))))(input)?;
-            match maybe_char {
-                Some('W') => Ok((input, DayOfMonthExpr::ClosestWeekday(day))),
-                Some('-') => {
-                    let (input, (end, slash)) = tuple((&dom, opt(char('/'))))(input)?;
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-                    let (input, exprs) = if slash.is_none() {
-                        (input, Exprs::new(OrsExpr::Range(day, end)))
-                    } else {
-                        let (input, step) = step_digit::<DayOfMonth>()(input)?;
-                        (
-                            input,
-                            Exprs::new(OrsExpr::Step {
-                                start: day,
-                                end,
-                                step,
-                            }),
-                        )
-                    };
+/// Returns whether the character is horizontal whitespace, which separates the fields of an
+/// expression.
+pub(crate) fn is_blank(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
 
-                    let (input, exprs) = tail_ors_exprs(input, dom, exprs)?;
-                    Ok((input, DayOfMonthExpr::Many(exprs)))
-                }
-                Some('/') => {
-                    let (input, step) = step_digit::<DayOfMonth>()(input)?;
-                    let exprs = Exprs::new(OrsExpr::Step {
-                        start: day,
-                        end: ExprValue::max(),
-                        step,
-                    });
+/// Splits the `#` comment after an expression from it, returning the expression without any
+/// spaces, tabs, or newlines at its end and the trimmed text of the comment. A `#` is only a
+/// comment at the start or after a space or tab, since days of the week like `MON#2` use it.
+fn split_comment(s: &str) -> (&str, Option<&str>) {
+    let start = s
+        .match_indices('#')
+        .map(|(at, _)| at)
+        .find(|&at| at == 0 || s[..at].ends_with(is_blank));
+    let (expr, comment) = match start {
+        Some(at) => (&s[..at], Some(s[at + 1..].trim())),
+        None => (s, None),
+    };
+    let expr = expr.trim_end_matches(|c| is_blank(c) || c == '\n' || c == '\r');
+    (expr, comment)
+}
 
-                    let (input, exprs) = tail_ors_exprs(input, dom, exprs)?;
-                    Ok((input, DayOfMonthExpr::Many(exprs)))
-                }
-                _ => {
-                    let (input, exprs) = tail_ors_exprs(input, dom, Exprs::new(OrsExpr::One(day)))?;
-                    Ok((input, DayOfMonthExpr::Many(exprs)))
-                }
-            }
+/// A field in a cron expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldKind {
+    /// The minutes field, the first field in an expression
+    Minutes,
+    /// The hours field, the second field in an expression
+    Hours,
+    /// The day of the month field, the third field in an expression
+    DaysOfMonth,
+    /// The months field, the fourth field in an expression
+    Months,
+    /// The day of the week field, the fifth field in an expression
+    DaysOfWeek,
+    /// The ISO week of the year field, the optional sixth field in an expression in the
+    /// [`Dialect::Extended`] dialect
+    Weeks,
+}
+
+impl FieldKind {
+    /// Every field, in the order they're written in an expression
+    pub const ALL: [FieldKind; 6] = [
+        FieldKind::Minutes,
+        FieldKind::Hours,
+        FieldKind::DaysOfMonth,
+        FieldKind::Months,
+        FieldKind::DaysOfWeek,
+        FieldKind::Weeks,
+    ];
+}
+
+impl Display for FieldKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FieldKind::Minutes => "minutes",
+            FieldKind::Hours => "hours",
+            FieldKind::DaysOfMonth => "day of month",
+            FieldKind::Months => "months",
+            FieldKind::DaysOfWeek => "day of week",
+            FieldKind::Weeks => "weeks",
         }
+        .fmt(f)
     }
 }
 
-#[inline]
-fn months_expr(s: &str) -> IResult<&str, Expr<Month>> {
-    expr(month)(s)
+/// A view of one field of an expression, returned by [`CronExpr::field`]. Every field is viewed
+/// the same way, so tools can inspect an expression without matching the type of each field.
+///
+/// [`CronExpr::field`]: struct.CronExpr.html#method.field
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FieldView {
+    /// A '*' character, with every value in the field
+    All(FieldValues),
+    /// A '?' character, only allowed in the day fields
+    Ignored,
+    /// Values, ranges, and steps, as the values they match. Values are numbered the way they're
+    /// written, so the days of the week are 1-7 (SUN-SAT).
+    Values(FieldValues),
+    /// Days of the month followed by a 'W', matching the closest weekday to any of the days,
+    /// like `1-7W`
+    ClosestWeekdays(FieldValues),
+    /// One or more 'L', 'W', or 'B' expressions in the day of the month field, in the order
+    /// they're written
+    DayOfMonthSpecials(Vec<DayOfMonthSpecial>),
+    /// One or more 'L' or '#' expressions in the day of the week field, in the order they're
+    /// written
+    DayOfWeekSpecials(Vec<DayOfWeekSpecial>),
 }
 
-fn dow_expr(input: &str) -> IResult<&str, DayOfWeekExpr> {
-    fn dow(s: &str) -> IResult<&str, DayOfWeek> {
-        alt((
-            map_digit1::<DayOfWeek>(),
-            map(tag_no_case("SUN"), |_| DayOfWeek(chrono::Weekday::Sun)),
-            map(tag_no_case("MON"), |_| DayOfWeek(chrono::Weekday::Mon)),
-            map(tag_no_case("TUE"), |_| DayOfWeek(chrono::Weekday::Tue)),
-            map(tag_no_case("WED"), |_| DayOfWeek(chrono::Weekday::Wed)),
-            map(tag_no_case("THU"), |_| DayOfWeek(chrono::Weekday::Thu)),
-            map(tag_no_case("FRI"), |_| DayOfWeek(chrono::Weekday::Fri)),
-            map(tag_no_case("SAT"), |_| DayOfWeek(chrono::Weekday::Sat)),
-        ))(s)
+impl FieldView {
+    #[inline]
+    fn from_expr<E>(expr: &Expr<E>) -> Self
+    where
+        E: Copy + ExprValue,
+        u8: From<E>,
+    {
+        match expr {
+            Expr::All => Self::all::<E>(),
+            Expr::Many(exprs) => FieldView::Values(Self::exprs_values(exprs)),
+        }
     }
 
-    let (input, start) = opt(alt((char('*'), char('L'))))(input)?;
+    #[inline]
+    fn all<E: ExprValue>() -> Self {
+        FieldView::All(FieldValues::new(all_mask::<E>(), E::MIN))
+    }
 
-    match start {
-        Some('*') => {
-            let (input, maybe_step) = opt(tuple((char('/'), step_digit::<DayOfWeek>())))(input)?;
-            if let Some((_, step)) = maybe_step {
-                let exprs = Exprs::new(OrsExpr::Step {
-                    start: DayOfWeek(chrono::Weekday::Sun),
-                    end: ExprValue::max(),
-                    step,
-                });
+    /// Returns the view of a '*' in the given field
+    pub(crate) fn all_of(kind: FieldKind) -> Self {
+        match kind {
+            FieldKind::Minutes => Self::all::<Minute>(),
+            FieldKind::Hours => Self::all::<Hour>(),
+            FieldKind::DaysOfMonth => Self::all::<DayOfMonth>(),
+            FieldKind::Months => Self::all::<Month>(),
+            FieldKind::DaysOfWeek => Self::all::<DayOfWeek>(),
+            FieldKind::Weeks => Self::all::<Week>(),
+        }
+    }
 
-                let (input, exprs) = tail_ors_exprs(input, dow, exprs)?;
-                Ok((input, DayOfWeekExpr::Many(exprs)))
-            } else {
-                Ok((input, DayOfWeekExpr::All))
-            }
+    #[inline]
+    fn exprs_values<E>(exprs: &Exprs<E>) -> FieldValues
+    where
+        E: Copy + ExprValue,
+        u8: From<E>,
+    {
+        FieldValues::new(exprs_mask(exprs), E::MIN)
+    }
+
+    /// Returns whether the field is a '*'
+    pub fn is_all(&self) -> bool {
+        matches!(self, FieldView::All(_))
+    }
+
+    /// Returns the values matched by the field if it's a '*' or a list of values, ranges, and
+    /// steps. Fields of '?' or special expressions don't match a fixed set of values, so return
+    /// `None`.
+    pub fn values(&self) -> Option<FieldValues> {
+        match *self {
+            FieldView::All(values) | FieldView::Values(values) => Some(values),
+            _ => None,
         }
-        Some('L') => Ok((
-            input,
-            DayOfWeekExpr::Many(Exprs::new(OrsExpr::One(DayOfWeek(chrono::Weekday::Sat)))),
-        )),
-        _ => {
-            let (input, day) = dow(input)?;
-            let (input, maybe_char) =
-                opt(alt((char('L'), char('#'), char('-'), char('/'))))(input)?;
+    }
+}
 
-            match maybe_char {
-                Some('L') => Ok((input, DayOfWeekExpr::Last(day))),
-                Some('#') => map(map_digit1::<NthDay>(), move |nth| {
-                    DayOfWeekExpr::Nth(day, nth)
-                })(input),
-                Some('-') => {
-                    let (input, (end, slash)) = tuple((&dow, opt(char('/'))))(input)?;
+/// An error indicating that the provided cron expression failed to parse. Every error carries the
+/// byte offset in the expression string where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CronParseError {
+    /// A field contains an unexpected character or is otherwise malformed.
+    InvalidField {
+        /// The field that failed to parse
+        field: FieldKind,
+        /// The byte offset of the unexpected input
+        at: usize,
+    },
+    /// A value in a field is out of the range of valid values for its position. This includes
+    /// step values, last day offsets, and nth day values.
+    ValueOutOfRange {
+        /// The field containing the value
+        field: FieldKind,
+        /// The value read from the expression. Values too large to fit are saturated to
+        /// `u32::MAX`.
+        value: u32,
+        /// The min valid value
+        min: u8,
+        /// The max valid value
+        max: u8,
+        /// The byte offset of the value
+        at: usize,
+    },
+    /// The expression ended before all fields were read.
+    MissingField {
+        /// The first field that wasn't found
+        field: FieldKind,
+        /// The byte offset where the field was expected
+        at: usize,
+    },
+    /// The expression has input remaining after the day of week field.
+    TrailingInput {
+        /// The byte offset of the remaining input
+        at: usize,
+    },
+    /// The expression starts with an `@` but isn't a known shortcut like `@daily`.
+    UnknownShortcut,
+    /// The expression is longer than [`ParseOptions::max_len`] allows.
+    TooLong {
+        /// The length of the expression in bytes
+        len: usize,
+        /// The longest length allowed
+        max: usize,
+    },
+    /// A field has more comma separated terms than [`ParseOptions::max_terms`] allows.
+    TooManyTerms {
+        /// The field with too many terms
+        field: FieldKind,
+        /// The most terms allowed
+        max: usize,
+        /// The byte offset of the comma before the first term over the limit
+        at: usize,
+    },
+}
 
-                    let (input, exprs) = if slash.is_none() {
-                        (input, Exprs::new(OrsExpr::Range(day, end)))
-                    } else {
-                        let (input, step) = step_digit::<DayOfWeek>()(input)?;
-                        (
-                            input,
-                            Exprs::new(OrsExpr::Step {
-                                start: day,
-                                end,
-                                step,
-                            }),
-                        )
-                    };
+impl CronParseError {
+    /// Returns the field that failed to parse, if the error is specific to one field.
+    pub fn field(&self) -> Option<FieldKind> {
+        match *self {
+            CronParseError::InvalidField { field, .. }
+            | CronParseError::ValueOutOfRange { field, .. }
+            | CronParseError::MissingField { field, .. }
+            | CronParseError::TooManyTerms { field, .. } => Some(field),
+            CronParseError::TrailingInput { .. }
+            | CronParseError::UnknownShortcut
+            | CronParseError::TooLong { .. } => None,
+        }
+    }
 
-                    let (input, exprs) = tail_ors_exprs(input, dow, exprs)?;
-                    Ok((input, DayOfWeekExpr::Many(exprs)))
-                }
-                Some('/') => {
-                    let (input, step) = step_digit::<DayOfWeek>()(input)?;
-                    let exprs = Exprs::new(OrsExpr::Step {
-                        start: day,
-                        end: ExprValue::max(),
-                        step,
-                    });
+    /// Returns the byte offset in the expression string where the error occurred.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, CronParseError, FieldKind};
+    ///
+    /// let err = "0 24 * * *".parse::<CronExpr>().unwrap_err();
+    /// assert_eq!(err.position(), 2);
+    /// assert_eq!(err.field(), Some(FieldKind::Hours));
+    /// assert!(matches!(err, CronParseError::ValueOutOfRange { value: 24, max: 23, .. }));
+    /// ```
+    pub fn position(&self) -> usize {
+        match *self {
+            CronParseError::InvalidField { at, .. }
+            | CronParseError::ValueOutOfRange { at, .. }
+            | CronParseError::MissingField { at, .. }
+            | CronParseError::TrailingInput { at }
+            | CronParseError::TooManyTerms { at, .. } => at,
+            CronParseError::UnknownShortcut | CronParseError::TooLong { .. } => 0,
+        }
+    }
 
-                    let (input, exprs) = tail_ors_exprs(input, dow, exprs)?;
-                    Ok((input, DayOfWeekExpr::Many(exprs)))
-                }
-                _ => {
-                    let (input, exprs) = tail_ors_exprs(input, dow, Exprs::new(OrsExpr::One(day)))?;
-                    Ok((input, DayOfWeekExpr::Many(exprs)))
-                }
+    /// Moves the position of the error forward by the given number of bytes
+    fn offset(self, by: usize) -> Self {
+        self.map_position(|at| at + by)
+    }
+
+    /// Replaces the position of the error with the position returned by the function
+    fn map_position<F: FnOnce(usize) -> usize>(self, f: F) -> Self {
+        match self {
+            CronParseError::InvalidField { field, at } => {
+                CronParseError::InvalidField { field, at: f(at) }
+            }
+            CronParseError::ValueOutOfRange {
+                field,
+                value,
+                min,
+                max,
+                at,
+            } => CronParseError::ValueOutOfRange {
+                field,
+                value,
+                min,
+                max,
+                at: f(at),
+            },
+            CronParseError::MissingField { field, at } => {
+                CronParseError::MissingField { field, at: f(at) }
             }
+            CronParseError::TrailingInput { at } => CronParseError::TrailingInput { at: f(at) },
+            CronParseError::TooManyTerms { field, max, at } => CronParseError::TooManyTerms {
+                field,
+                max,
+                at: f(at),
+            },
+            err @ CronParseError::UnknownShortcut | err @ CronParseError::TooLong { .. } => err,
         }
     }
 }
 
-impl FromStr for CronExpr {
-    type Err = CronParseError;
+impl Display for CronParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CronParseError::InvalidField { field, at } => {
+                write!(f, "Invalid {} field at position {}", field, at)
+            }
+            CronParseError::ValueOutOfRange {
+                field,
+                value,
+                min,
+                max,
+                at,
+            } => write!(
+                f,
+                "Value {} in {} field at position {} is out of range ({}-{})",
+                value, field, at, min, max
+            ),
+            CronParseError::MissingField { field, at } => {
+                write!(f, "Missing {} field at position {}", field, at)
+            }
+            CronParseError::TrailingInput { at } => {
+                write!(f, "Unexpected input at position {}", at)
+            }
+            CronParseError::UnknownShortcut => "Unknown shortcut expression".fmt(f),
+            CronParseError::TooLong { len, max } => write!(
+                f,
+                "Expression is {} bytes long, longer than the limit of {}",
+                len, max
+            ),
+            CronParseError::TooManyTerms { field, max, at } => write!(
+                f,
+                "Too many terms in {} field at position {}, the limit is {}",
+                field, at, max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CronParseError {}
+
+/// Collects the ORS expressions of a field as they're parsed. [`Exprs`] keeps every expression,
+/// while [`Cron`](crate::Cron) folds them straight into its bit-masks so it can be parsed without
+/// allocating.
+pub(crate) trait Collect<E>: Sized {
+    /// Starts a collection with the first expression of a field
+    fn new(first: OrsExpr<E>) -> Self;
+
+    /// Adds another expression to the collection
+    fn push(&mut self, expr: OrsExpr<E>);
+}
 
+impl<E> Collect<E> for Exprs<E> {
     #[inline]
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, expr) = all_consuming(map(
-            tuple((
-                minutes_expr,
-                space1,
-                hours_expr,
-                space1,
-                dom_expr,
-                space1,
-                months_expr,
-                space1,
-                dow_expr,
-            )),
-            |(minutes, _, hours, _, doms, _, months, _, dows)| CronExpr {
-                minutes,
-                hours,
-                doms,
-                months,
-                dows,
-            },
-        ))(s)
-        .map_err(|_| CronParseError(()))?;
+    fn new(first: OrsExpr<E>) -> Self {
+        Exprs::new(first)
+    }
 
-        Ok(expr)
+    #[inline]
+    fn push(&mut self, expr: OrsExpr<E>) {
+        self.tail.push(expr)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use core::convert::TryFrom;
-    use core::fmt::Debug;
+/// Collects a set of expressions that has already been parsed.
+pub(crate) fn collect<E, C: Collect<E>>(exprs: Exprs<E>) -> C {
+    let mut collection = C::new(exprs.first);
+    for expr in exprs.tail {
+        collection.push(expr);
+    }
+    collection
+}
 
-    #[cfg(not(feature = "std"))]
-    use alloc::vec;
+/// A parsed minutes, hours, or months field with its expressions collected into `C`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Field<C> {
+    All,
+    Many(C),
+}
 
-    use super::*;
+impl<C> Field<C> {
+    #[inline]
+    pub(crate) fn map<T>(self, f: impl FnOnce(C) -> T) -> Field<T> {
+        match self {
+            Field::All => Field::All,
+            Field::Many(c) => Field::Many(f(c)),
+        }
+    }
+}
 
-    fn exprs<E, I>(iter: I) -> Exprs<E>
-    where
-        I: IntoIterator<Item = OrsExpr<E>>,
-    {
-        let mut iter = iter.into_iter();
-        let first = iter.next().expect("Iterator must have at least one item");
-        let tail = iter.collect();
-        Exprs { first, tail }
+impl<E> From<Expr<E>> for Field<Exprs<E>> {
+    fn from(expr: Expr<E>) -> Self {
+        match expr {
+            Expr::All => Field::All,
+            Expr::Many(exprs) => Field::Many(exprs),
+        }
     }
+}
 
-    fn e<E>(value: u8) -> E
-    where
-        E: TryFrom<u8>,
-        E::Error: Debug,
-    {
-        E::try_from(value).unwrap()
+impl<E> From<Field<Exprs<E>>> for Expr<E> {
+    fn from(field: Field<Exprs<E>>) -> Self {
+        match field {
+            Field::All => Expr::All,
+            Field::Many(exprs) => Expr::Many(exprs),
+        }
     }
+}
 
-    fn o<E>(value: u8) -> OrsExpr<E>
-    where
-        E: TryFrom<u8>,
-        E::Error: Debug,
-    {
-        OrsExpr::One(e(value))
+/// A parsed day of the month field with its expressions collected into `C`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DomField<C> {
+    All,
+    Ignored,
+    Last(Last),
+    ClosestWeekday(DayOfMonth),
+    ClosestWeekdays(C),
+    BusinessDay(BusinessDay),
+    Specials(Vec<DayOfMonthSpecial>),
+    Many(C),
+}
+
+impl<C> DomField<C> {
+    #[inline]
+    pub(crate) fn map<T>(self, f: impl FnOnce(C) -> T) -> DomField<T> {
+        match self {
+            DomField::All => DomField::All,
+            DomField::Ignored => DomField::Ignored,
+            DomField::Last(last) => DomField::Last(last),
+            DomField::ClosestWeekday(day) => DomField::ClosestWeekday(day),
+            DomField::ClosestWeekdays(c) => DomField::ClosestWeekdays(f(c)),
+            DomField::BusinessDay(day) => DomField::BusinessDay(day),
+            DomField::Specials(specials) => DomField::Specials(specials),
+            DomField::Many(c) => DomField::Many(f(c)),
+        }
     }
+}
 
-    fn r<E>(start: u8, end: u8) -> OrsExpr<E>
-    where
-        E: TryFrom<u8>,
-        E::Error: Debug,
-    {
-        let start = e(start);
-        let end = e(end);
-        OrsExpr::Range(start, end)
+impl<C> From<DayOfMonthSpecial> for DomField<C> {
+    fn from(special: DayOfMonthSpecial) -> Self {
+        match special {
+            DayOfMonthSpecial::Last(last) => DomField::Last(last),
+            DayOfMonthSpecial::ClosestWeekday(day) => DomField::ClosestWeekday(day),
+            DayOfMonthSpecial::BusinessDay(day) => DomField::BusinessDay(day),
+        }
     }
+}
 
-    fn s<E>(value: u8, step: u8) -> OrsExpr<E>
-    where
-        E: TryFrom<u8> + ExprValue,
-        E::Error: Debug,
-    {
-        let start = e(value);
-        let step = e(step);
-        OrsExpr::Step {
-            start,
-            end: E::max(),
-            step,
+impl From<DayOfMonthExpr> for DomField<Exprs<DayOfMonth>> {
+    fn from(expr: DayOfMonthExpr) -> Self {
+        match expr {
+            DayOfMonthExpr::All => DomField::All,
+            DayOfMonthExpr::Ignored => DomField::Ignored,
+            DayOfMonthExpr::Last(last) => DomField::Last(last),
+            DayOfMonthExpr::ClosestWeekday(day) => DomField::ClosestWeekday(day),
+            DayOfMonthExpr::ClosestWeekdays(exprs) => DomField::ClosestWeekdays(exprs),
+            DayOfMonthExpr::BusinessDay(day) => DomField::BusinessDay(day),
+            DayOfMonthExpr::Specials(specials) => DomField::Specials(specials),
+            DayOfMonthExpr::Many(exprs) => DomField::Many(exprs),
+        }
+    }
+}
+
+impl From<DomField<Exprs<DayOfMonth>>> for DayOfMonthExpr {
+    fn from(field: DomField<Exprs<DayOfMonth>>) -> Self {
+        match field {
+            DomField::All => DayOfMonthExpr::All,
+            DomField::Ignored => DayOfMonthExpr::Ignored,
+            DomField::Last(last) => DayOfMonthExpr::Last(last),
+            DomField::ClosestWeekday(day) => DayOfMonthExpr::ClosestWeekday(day),
+            DomField::ClosestWeekdays(exprs) => DayOfMonthExpr::ClosestWeekdays(exprs),
+            DomField::BusinessDay(day) => DayOfMonthExpr::BusinessDay(day),
+            DomField::Specials(specials) => DayOfMonthExpr::Specials(specials),
+            DomField::Many(exprs) => DayOfMonthExpr::Many(exprs),
+        }
+    }
+}
+
+/// A parsed day of the week field with its expressions collected into `C`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DowField<C> {
+    All,
+    Ignored,
+    Last(DayOfWeek),
+    Nth(DayOfWeek, NthDay),
+    Specials(Vec<DayOfWeekSpecial>),
+    Many(C),
+}
+
+impl<C> DowField<C> {
+    #[inline]
+    pub(crate) fn map<T>(self, f: impl FnOnce(C) -> T) -> DowField<T> {
+        match self {
+            DowField::All => DowField::All,
+            DowField::Ignored => DowField::Ignored,
+            DowField::Last(day) => DowField::Last(day),
+            DowField::Nth(day, nth) => DowField::Nth(day, nth),
+            DowField::Specials(specials) => DowField::Specials(specials),
+            DowField::Many(c) => DowField::Many(f(c)),
+        }
+    }
+}
+
+impl<C> From<DayOfWeekSpecial> for DowField<C> {
+    fn from(special: DayOfWeekSpecial) -> Self {
+        match special {
+            DayOfWeekSpecial::Last(day) => DowField::Last(day),
+            DayOfWeekSpecial::Nth(day, nth) => DowField::Nth(day, nth),
+        }
+    }
+}
+
+impl From<DayOfWeekExpr> for DowField<Exprs<DayOfWeek>> {
+    fn from(expr: DayOfWeekExpr) -> Self {
+        match expr {
+            DayOfWeekExpr::All => DowField::All,
+            DayOfWeekExpr::Ignored => DowField::Ignored,
+            DayOfWeekExpr::Last(day) => DowField::Last(day),
+            DayOfWeekExpr::Nth(day, nth) => DowField::Nth(day, nth),
+            DayOfWeekExpr::Specials(specials) => DowField::Specials(specials),
+            DayOfWeekExpr::Many(exprs) => DowField::Many(exprs),
+        }
+    }
+}
+
+impl From<DowField<Exprs<DayOfWeek>>> for DayOfWeekExpr {
+    fn from(field: DowField<Exprs<DayOfWeek>>) -> Self {
+        match field {
+            DowField::All => DayOfWeekExpr::All,
+            DowField::Ignored => DayOfWeekExpr::Ignored,
+            DowField::Last(day) => DayOfWeekExpr::Last(day),
+            DowField::Nth(day, nth) => DayOfWeekExpr::Nth(day, nth),
+            DowField::Specials(specials) => DayOfWeekExpr::Specials(specials),
+            DowField::Many(exprs) => DayOfWeekExpr::Many(exprs),
+        }
+    }
+}
+
+/// The collections each field of a cron expression is parsed into.
+pub(crate) trait Collectors {
+    type Minutes: Collect<Minute>;
+    type Hours: Collect<Hour>;
+    type Doms: Collect<DayOfMonth>;
+    type Months: Collect<Month>;
+    type Dows: Collect<DayOfWeek>;
+    type Weeks: Collect<Week>;
+}
+
+impl Collectors for CronExpr {
+    type Minutes = Exprs<Minute>;
+    type Hours = Exprs<Hour>;
+    type Doms = Exprs<DayOfMonth>;
+    type Months = Exprs<Month>;
+    type Dows = Exprs<DayOfWeek>;
+    type Weeks = Exprs<Week>;
+}
+
+/// The fields of a parsed cron expression.
+pub(crate) struct Fields<C: Collectors> {
+    pub(crate) minutes: Field<C::Minutes>,
+    pub(crate) hours: Field<C::Hours>,
+    pub(crate) doms: DomField<C::Doms>,
+    pub(crate) months: Field<C::Months>,
+    pub(crate) dows: DowField<C::Dows>,
+    pub(crate) weeks: Field<C::Weeks>,
+    /// The values excluded from each field, which are always parsed as expressions
+    pub(crate) exclusions: Exclusions,
+    /// Whether the day of week field was written with a `&`
+    pub(crate) days_and: bool,
+}
+
+/// The internal error type used by all parsers. Keeps the bounds of out of range values so they
+/// can be reported back in a [`CronParseError`].
+#[derive(Debug, Clone, PartialEq)]
+struct ExprError<I> {
+    input: I,
+    kind: ExprErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprErrorKind {
+    Nom(ErrorKind),
+    OutOfRange { value: u32, min: u8, max: u8 },
+}
+
+impl<I> ParseError<I> for ExprError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        ExprError {
+            input,
+            kind: ExprErrorKind::Nom(kind),
+        }
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn or(self, other: Self) -> Self {
+        // prefer out of range errors over errors from other branches, since a name
+        // can't have been meant if we read a number
+        match self.kind {
+            ExprErrorKind::OutOfRange { .. } => self,
+            _ => other,
+        }
+    }
+}
+
+type IResult<I, O> = nom::IResult<I, O, ExprError<I>>;
+
+/// A parser that can parse a single value, a range of values, or a step expression
+fn ors_expr<E, F>(f: F) -> impl Fn(&str) -> IResult<&str, OrsExpr<E>>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Ord + Copy,
+    F: Fn(&str) -> IResult<&str, E>,
+{
+    move |input: &str| {
+        let (input, value) = alt((&f, map(char('*'), |_| ExprValue::min())))(input)?;
+        match opt(alt((char('/'), char('-'))))(input)? {
+            (input, Some('/')) => map(step_digit::<E>(), |step| OrsExpr::Step {
+                start: value,
+                end: ExprValue::max(),
+                step,
+            })(input),
+            (input, Some('-')) => {
+                let (input, end) = f(input)?;
+                match opt(char('/'))(input)? {
+                    (input, Some(_)) => map(step_digit::<E>(), |step| OrsExpr::Step {
+                        start: value,
+                        end,
+                        step,
+                    })(input),
+                    (input, None) => Ok((input, OrsExpr::Range(value, end))),
+                }
+            }
+            (input, _) => Ok((input, OrsExpr::One(value))),
+        }
+    }
+}
+
+/// Consumes a set of trailing ORS expressions
+fn tail_ors_exprs<E, F, C>(mut input: &str, f: F, mut exprs: C) -> IResult<&str, C>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Ord + Copy,
+    F: Fn(&str) -> IResult<&str, E>,
+    C: Collect<E>,
+{
+    loop {
+        let comma = opt(char(','))(input)?;
+        input = comma.0;
+        if comma.1.is_none() {
+            break Ok((input, exprs));
+        }
+
+        let expr = ors_expr::<E, _>(&f)(input)?;
+        input = expr.0;
+        exprs.push(expr.1);
+    }
+}
+
+/// Parses the special expressions after the first in a list of them, like the `15W` in `1W,15W`.
+/// A list of specials can't have other values in it, so the parse stops at the first item that
+/// isn't a special.
+fn tail_specials<'a, S, F>(mut input: &'a str, first: S, special: F) -> IResult<&'a str, Vec<S>>
+where
+    F: Fn(&'a str) -> IResult<&'a str, S>,
+{
+    let mut specials = Vec::new();
+    specials.push(first);
+    loop {
+        match opt(preceded(char(','), &special))(input)? {
+            (rest, Some(next)) => {
+                input = rest;
+                specials.push(next);
+            }
+            (_, None) => break Ok((input, specials)),
+        }
+    }
+}
+
+/// A parser that can parse delimited expressions given a parser for that part.
+/// This can't parse day of the month or week expressions.
+fn field<E, F, C>(f: F) -> impl Fn(&str) -> IResult<&str, Field<C>>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Ord + Copy,
+    F: Fn(&str) -> IResult<&str, E>,
+    C: Collect<E>,
+{
+    move |mut input: &str| {
+        // Attempt to read a `*`. If that succeeds,
+        // try to read a `/` for a step expr.
+        // If this isn't a step expr, return Field::All,
+        // If it's not a `*`, initialize the expressions
+        // list with an ors_expr.
+        let star = opt(char('*'))(input)?;
+        input = star.0;
+        let expressions = if star.1.is_some() {
+            let slash = opt(char('/'))(input)?;
+            input = slash.0;
+            // If there is no slash after this, just return All and expect the next
+            // parser to fail if it's invalid
+            if slash.1.is_none() {
+                return Ok((input, Field::All));
+            }
+            let step = step_digit::<E>()(input)?;
+            input = step.0;
+            C::new(OrsExpr::Step {
+                start: ExprValue::min(),
+                end: ExprValue::max(),
+                step: step.1,
+            })
+        } else {
+            let expr = ors_expr::<E, _>(&f)(input)?;
+            input = expr.0;
+            C::new(expr.1)
+        };
+
+        let (input, exprs) = tail_ors_exprs(input, &f, expressions)?;
+
+        Ok((input, Field::Many(exprs)))
+    }
+}
+
+#[inline]
+fn map_digit1<E>() -> impl Fn(&str) -> IResult<&str, E>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+{
+    move |input: &str| {
+        let (rest, digits) = digit1(input)?;
+        // saturate values that don't fit, they're out of range anyway
+        let value = digits.parse::<u32>().unwrap_or(u32::MAX);
+        match u8::try_from(value).ok().and_then(|v| E::try_from(v).ok()) {
+            Some(value) => Ok((rest, value)),
+            None => Err(nom::Err::Error(ExprError {
+                input,
+                kind: ExprErrorKind::OutOfRange {
+                    value,
+                    min: E::MIN,
+                    max: E::MAX,
+                },
+            })),
+        }
+    }
+}
+
+#[inline]
+fn step_digit<E>() -> impl Fn(&str) -> IResult<&str, Step<E>>
+where
+    E: ExprValue,
+{
+    map_digit1()
+}
+
+/// The full names of the months, the first three letters of which are their abbreviations
+const MONTH_NAMES: [&str; 12] = [
+    "JANUARY",
+    "FEBRUARY",
+    "MARCH",
+    "APRIL",
+    "MAY",
+    "JUNE",
+    "JULY",
+    "AUGUST",
+    "SEPTEMBER",
+    "OCTOBER",
+    "NOVEMBER",
+    "DECEMBER",
+];
+
+/// The full names of the days of the week starting from Sunday, the first three letters of which
+/// are their abbreviations
+const DAY_NAMES: [&str; 7] = [
+    "SUNDAY",
+    "MONDAY",
+    "TUESDAY",
+    "WEDNESDAY",
+    "THURSDAY",
+    "FRIDAY",
+    "SATURDAY",
+];
+
+/// Returns a parser reading one of the given names, returning its index. Names are read without
+/// regard to case as the three letter abbreviation, or the full name if `full_names` is set.
+fn name(names: &'static [&'static str], full_names: bool) -> impl Fn(&str) -> IResult<&str, u8> {
+    move |input: &str| {
+        for (i, name) in names.iter().enumerate() {
+            let (abbreviation, rest_of_name) = name.split_at(3);
+            let rest = match input.get(..3) {
+                Some(start) if start.eq_ignore_ascii_case(abbreviation) => &input[3..],
+                _ => continue,
+            };
+            let rest = match rest.get(..rest_of_name.len()) {
+                Some(end) if full_names && end.eq_ignore_ascii_case(rest_of_name) => {
+                    &rest[rest_of_name.len()..]
+                }
+                _ => rest,
+            };
+            return Ok((rest, i as u8));
+        }
+        Err(nom::Err::Error(ExprError::from_error_kind(
+            input,
+            ErrorKind::Tag,
+        )))
+    }
+}
+
+fn month(full_names: bool) -> impl Fn(&str) -> IResult<&str, Month> {
+    move |input: &str| {
+        alt((
+            map_digit1::<Month>(),
+            map(name(&MONTH_NAMES, full_names), |month| Month(month + 1)),
+        ))(input)
+    }
+}
+
+#[inline]
+fn minutes_field<C: Collect<Minute>>(s: &str) -> IResult<&str, Field<C>> {
+    field(map_digit1())(s)
+}
+
+#[inline]
+fn hours_field<C: Collect<Hour>>(s: &str) -> IResult<&str, Field<C>> {
+    field(map_digit1())(s)
+}
+
+fn dom_field<C: Collect<DayOfMonth>>(input: &str) -> IResult<&str, DomField<C>> {
+    let dom = map_digit1::<DayOfMonth>();
+
+    let (input, start) = opt(alt((char('*'), char('?'), char('L'))))(input)?;
+    match start {
+        Some('*') => {
+            let (input, slash) = opt(char('/'))(input)?;
+
+            if slash.is_some() {
+                let (input, step) = step_digit::<DayOfMonth>()(input)?;
+                let exprs = C::new(OrsExpr::Step {
+                    start: DayOfMonth(1),
+                    end: ExprValue::max(),
+                    step,
+                });
+
+                let (input, exprs) = tail_ors_exprs(input, dom, exprs)?;
+                dom_many(input, exprs)
+            } else {
+                Ok((input, DomField::All))
+            }
+        }
+        Some('?') => Ok((input, DomField::Ignored)),
+        Some('L') => {
+            let (input, last) = last_expr(input)?;
+            dom_specials(input, DayOfMonthSpecial::Last(last))
+        }
+        _ => {
+            let day_input = input;
+            let (input, day) = dom(input)?;
+
+            let (input, maybe_char) =
+                opt(alt((char('W'), char('B'), char('-'), char('/'))))(input)?;
+            match maybe_char {
+                Some('W') => dom_specials(input, DayOfMonthSpecial::ClosestWeekday(day)),
+                Some('B') => {
+                    let day = business_day(day_input, day)?;
+                    dom_specials(input, DayOfMonthSpecial::BusinessDay(day))
+                }
+                Some('-') => {
+                    let (input, (end, slash)) = tuple((&dom, opt(char('/'))))(input)?;
+
+                    let (input, exprs) = if slash.is_none() {
+                        (input, C::new(OrsExpr::Range(day, end)))
+                    } else {
+                        let (input, step) = step_digit::<DayOfMonth>()(input)?;
+                        (
+                            input,
+                            C::new(OrsExpr::Step {
+                                start: day,
+                                end,
+                                step,
+                            }),
+                        )
+                    };
+
+                    let (input, exprs) = tail_ors_exprs(input, dom, exprs)?;
+                    dom_many(input, exprs)
+                }
+                Some('/') => {
+                    let (input, step) = step_digit::<DayOfMonth>()(input)?;
+                    let exprs = C::new(OrsExpr::Step {
+                        start: day,
+                        end: ExprValue::max(),
+                        step,
+                    });
+
+                    let (input, exprs) = tail_ors_exprs(input, dom, exprs)?;
+                    dom_many(input, exprs)
+                }
+                _ => {
+                    let (input, exprs) = tail_ors_exprs(input, dom, C::new(OrsExpr::One(day)))?;
+                    dom_many(input, exprs)
+                }
+            }
+        }
+    }
+}
+
+/// Parses the rest of a last day of the month expression after its 'L'
+fn last_expr(input: &str) -> IResult<&str, Last> {
+    let (input, modifier) = opt(alt((char('-'), char('W'))))(input)?;
+    match modifier {
+        Some('-') => {
+            let offset = map_digit1::<DayOfMonthOffset>();
+            let (input, (offset, weekday)) = tuple((offset, opt(char('W'))))(input)?;
+
+            if weekday.is_some() {
+                Ok((input, Last::OffsetWeekday(offset)))
+            } else {
+                Ok((input, Last::Offset(offset)))
+            }
+        }
+        Some('W') => {
+            let (input, dash) = opt(char('-'))(input)?;
+            if dash.is_some() {
+                let (input, offset) = map_digit1::<DayOfMonthOffset>()(input)?;
+                Ok((input, Last::WeekdayOffset(offset)))
+            } else {
+                Ok((input, Last::Weekday))
+            }
+        }
+        _ => Ok((input, Last::Day)),
+    }
+}
+
+/// Converts the day of a 'B' expression into a business day, failing at the input of the day if
+/// it's out of range
+fn business_day(input: &str, day: DayOfMonth) -> Result<BusinessDay, nom::Err<ExprError<&str>>> {
+    BusinessDay::try_from(day.0).map_err(|_| {
+        nom::Err::Error(ExprError {
+            input,
+            kind: ExprErrorKind::OutOfRange {
+                value: day.0.into(),
+                min: BusinessDay::MIN,
+                max: BusinessDay::MAX,
+            },
+        })
+    })
+}
+
+/// Parses a special day of the month expression in a list of them
+fn dom_special(input: &str) -> IResult<&str, DayOfMonthSpecial> {
+    if let (input, Some(_)) = opt(char('L'))(input)? {
+        return map(last_expr, DayOfMonthSpecial::Last)(input);
+    }
+
+    let (rest, day) = map_digit1::<DayOfMonth>()(input)?;
+    match alt((char('W'), char('B')))(rest)? {
+        (rest, 'W') => Ok((rest, DayOfMonthSpecial::ClosestWeekday(day))),
+        (rest, _) => Ok((
+            rest,
+            DayOfMonthSpecial::BusinessDay(business_day(input, day)?),
+        )),
+    }
+}
+
+/// Finishes a list of days of the month, which may be followed by a 'W' to match the closest
+/// weekday to each day in the list
+fn dom_many<C>(input: &str, exprs: C) -> IResult<&str, DomField<C>> {
+    match opt(char('W'))(input)? {
+        (input, Some(_)) => Ok((input, DomField::ClosestWeekdays(exprs))),
+        (input, None) => Ok((input, DomField::Many(exprs))),
+    }
+}
+
+/// Parses any other specials after the first special expression in a day of the month field
+fn dom_specials<C>(input: &str, first: DayOfMonthSpecial) -> IResult<&str, DomField<C>> {
+    let (input, mut specials) = tail_specials(input, first, dom_special)?;
+    if specials.len() == 1 {
+        let special = specials.pop().expect("The list has one special");
+        Ok((input, DomField::from(special)))
+    } else {
+        Ok((input, DomField::Specials(specials)))
+    }
+}
+
+#[inline]
+fn months_field<C: Collect<Month>>(full_names: bool) -> impl Fn(&str) -> IResult<&str, Field<C>> {
+    field(month(full_names))
+}
+
+/// Parses a weeks field in the extended dialect, a `W` followed by the weeks
+fn weeks_field<C: Collect<Week>>(input: &str) -> IResult<&str, Field<C>> {
+    let week = map_digit1::<Week>();
+
+    let (input, _) = char('W')(input)?;
+    // a step without a start counts from the first week, like a '*'
+    match opt(char('/'))(input)? {
+        (input, Some(_)) => {
+            let (input, step) = step_digit::<Week>()(input)?;
+            let exprs = C::new(OrsExpr::Step {
+                start: ExprValue::min(),
+                end: ExprValue::max(),
+                step,
+            });
+            let (input, exprs) = tail_ors_exprs(input, &week, exprs)?;
+            Ok((input, Field::Many(exprs)))
+        }
+        (input, None) => field(week)(input),
+    }
+}
+
+fn dow(full_names: bool) -> impl Fn(&str) -> IResult<&str, DayOfWeek> {
+    move |input: &str| {
+        alt((
+            map_digit1::<DayOfWeek>(),
+            map(name(&DAY_NAMES, full_names), |day| {
+                DayOfWeek::try_from(day + 1).expect("Days of the week are 1-7")
+            }),
+        ))(input)
+    }
+}
+
+fn dow_field<C: Collect<DayOfWeek>>(
+    full_names: bool,
+) -> impl Fn(&str) -> IResult<&str, DowField<C>> {
+    move |input: &str| {
+        let dow = dow(full_names);
+        let (input, start) = opt(alt((char('*'), char('?'), char('L'))))(input)?;
+
+        match start {
+            Some('*') => {
+                let (input, slash) = opt(char('/'))(input)?;
+                if slash.is_some() {
+                    let (input, step) = step_digit::<DayOfWeek>()(input)?;
+                    let exprs = C::new(OrsExpr::Step {
+                        start: DayOfWeek(chrono::Weekday::Sun),
+                        end: ExprValue::max(),
+                        step,
+                    });
+
+                    let (input, exprs) = tail_ors_exprs(input, &dow, exprs)?;
+                    Ok((input, DowField::Many(exprs)))
+                } else {
+                    Ok((input, DowField::All))
+                }
+            }
+            Some('?') => Ok((input, DowField::Ignored)),
+            Some('L') => Ok((
+                input,
+                DowField::Many(C::new(OrsExpr::One(DayOfWeek(chrono::Weekday::Sat)))),
+            )),
+            _ => {
+                let (input, day) = dow(input)?;
+                let (input, maybe_char) =
+                    opt(alt((char('L'), char('#'), char('-'), char('/'))))(input)?;
+
+                match maybe_char {
+                    Some('L') => dow_specials(input, DayOfWeekSpecial::Last(day), &dow),
+                    Some('#') => {
+                        let (input, nth) = map_digit1::<NthDay>()(input)?;
+                        dow_specials(input, DayOfWeekSpecial::Nth(day, nth), &dow)
+                    }
+                    Some('-') => {
+                        let (input, (end, slash)) = tuple((&dow, opt(char('/'))))(input)?;
+
+                        let (input, exprs) = if slash.is_none() {
+                            (input, C::new(OrsExpr::Range(day, end)))
+                        } else {
+                            let (input, step) = step_digit::<DayOfWeek>()(input)?;
+                            (
+                                input,
+                                C::new(OrsExpr::Step {
+                                    start: day,
+                                    end,
+                                    step,
+                                }),
+                            )
+                        };
+
+                        let (input, exprs) = tail_ors_exprs(input, &dow, exprs)?;
+                        Ok((input, DowField::Many(exprs)))
+                    }
+                    Some('/') => {
+                        let (input, step) = step_digit::<DayOfWeek>()(input)?;
+                        let exprs = C::new(OrsExpr::Step {
+                            start: day,
+                            end: ExprValue::max(),
+                            step,
+                        });
+
+                        let (input, exprs) = tail_ors_exprs(input, &dow, exprs)?;
+                        Ok((input, DowField::Many(exprs)))
+                    }
+                    _ => {
+                        let (input, exprs) =
+                            tail_ors_exprs(input, &dow, C::new(OrsExpr::One(day)))?;
+                        Ok((input, DowField::Many(exprs)))
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parses any other specials after the first special expression in a day of the week field,
+/// given a parser for the days
+fn dow_specials<'a, C, F>(
+    input: &'a str,
+    first: DayOfWeekSpecial,
+    dow: F,
+) -> IResult<&'a str, DowField<C>>
+where
+    F: Fn(&'a str) -> IResult<&'a str, DayOfWeek>,
+{
+    let special = |input| {
+        let (input, day) = dow(input)?;
+        match alt((char('L'), char('#')))(input)? {
+            (input, 'L') => Ok((input, DayOfWeekSpecial::Last(day))),
+            (input, _) => map(map_digit1::<NthDay>(), |nth| {
+                DayOfWeekSpecial::Nth(day, nth)
+            })(input),
+        }
+    };
+    let (input, mut specials) = tail_specials(input, first, special)?;
+    if specials.len() == 1 {
+        let special = specials.pop().expect("The list has one special");
+        Ok((input, DowField::from(special)))
+    } else {
+        Ok((input, DowField::Specials(specials)))
+    }
+}
+
+/// A day of the week in the unix dialect, 0-7 where both 0 and 7 are Sunday. In ISO numbering
+/// the days are 1-7, so only 7 is Sunday.
+fn unix_dow(full_names: bool, numbering: WeekdayNumbering) -> impl Fn(&str) -> IResult<&str, u8> {
+    fn value(input: &str, min: u8) -> IResult<&str, u8> {
+        let (rest, digits) = digit1(input)?;
+        // saturate values that don't fit, they're out of range anyway
+        let value = digits.parse::<u32>().unwrap_or(u32::MAX);
+        if (u32::from(min)..=7).contains(&value) {
+            Ok((rest, value as u8))
+        } else {
+            Err(nom::Err::Error(ExprError {
+                input,
+                kind: ExprErrorKind::OutOfRange { value, min, max: 7 },
+            }))
+        }
+    }
+
+    let min = numbering.min();
+    move |input: &str| alt((|input| value(input, min), name(&DAY_NAMES, full_names)))(input)
+}
+
+/// A day of the week numbered like in [`unix_dow`], converted into a day of the week
+fn unix_day(
+    full_names: bool,
+    numbering: WeekdayNumbering,
+) -> impl Fn(&str) -> IResult<&str, DayOfWeek> {
+    let unix_dow = unix_dow(full_names, numbering);
+    move |input: &str| map(&unix_dow, unix_weekday)(input)
+}
+
+/// Converts a day of the week in the unix dialect into a day of the week
+fn unix_weekday(value: u8) -> DayOfWeek {
+    DayOfWeek::try_from(value % 7 + 1).expect("Unix days of the week are 0-7")
+}
+
+/// Adds a value, range, or step of days of the week in the unix dialect to a set of expressions.
+/// Ranges ending on 7 end on Saturday with Sunday added as its own value, so they don't wrap
+/// around the end of the week.
+fn push_unix_dows<C: Collect<DayOfWeek>>(
+    exprs: &mut Option<C>,
+    start: u8,
+    end: Option<u8>,
+    step: Option<Step<DayOfWeek>>,
+) {
+    fn push<C: Collect<DayOfWeek>>(exprs: &mut Option<C>, expr: OrsExpr<DayOfWeek>) {
+        match exprs {
+            Some(exprs) => exprs.push(expr),
+            None => *exprs = Some(C::new(expr)),
+        }
+    }
+
+    let sunday = unix_weekday(0);
+    let saturday = unix_weekday(6);
+    match (end, step) {
+        (None, None) => push(exprs, OrsExpr::One(unix_weekday(start))),
+        (Some(7), _) | (None, Some(_)) if start == 7 => push(exprs, OrsExpr::One(sunday)),
+        (Some(7), None) => {
+            push(exprs, OrsExpr::Range(unix_weekday(start), saturday));
+            if start != 0 {
+                push(exprs, OrsExpr::One(sunday));
+            }
+        }
+        (Some(end), None) => push(
+            exprs,
+            OrsExpr::Range(unix_weekday(start), unix_weekday(end)),
+        ),
+        (Some(7), Some(step)) | (None, Some(step)) => {
+            push(
+                exprs,
+                OrsExpr::Step {
+                    start: unix_weekday(start),
+                    end: saturday,
+                    step,
+                },
+            );
+            if start != 0 && (7 - start).is_multiple_of(u8::from(step)) {
+                push(exprs, OrsExpr::One(sunday));
+            }
+        }
+        (Some(end), Some(step)) => push(
+            exprs,
+            OrsExpr::Step {
+                start: unix_weekday(start),
+                end: unix_weekday(end),
+                step,
+            },
+        ),
+    }
+}
+
+/// Parses a day of the week field numbered from Sunday as 0 like in the unix dialect, or from
+/// Monday as 1 in ISO numbering
+fn unix_dow_field<C: Collect<DayOfWeek>>(
+    full_names: bool,
+    numbering: WeekdayNumbering,
+) -> impl Fn(&str) -> IResult<&str, DowField<C>> {
+    fn range_and_step<'a>(
+        input: &'a str,
+        unix_dow: impl Fn(&'a str) -> IResult<&'a str, u8>,
+    ) -> IResult<&'a str, (Option<u8>, Option<Step<DayOfWeek>>)> {
+        let (input, end) = match opt(char('-'))(input)? {
+            (input, Some(_)) => map(unix_dow, Some)(input)?,
+            (input, None) => (input, None),
+        };
+        let (input, step) = match opt(char('/'))(input)? {
+            (input, Some(_)) => map(step_digit::<DayOfWeek>(), Some)(input)?,
+            (input, None) => (input, None),
+        };
+        Ok((input, (end, step)))
+    }
+
+    move |input: &str| {
+        let unix_dow = unix_dow(full_names, numbering);
+
+        let mut exprs = None;
+        let (mut input, start) = opt(alt((char('*'), char('?'))))(input)?;
+        match start {
+            Some('?') => return Ok((input, DowField::Ignored)),
+            Some(_) => {
+                let (rest, slash) = opt(char('/'))(input)?;
+                if slash.is_none() {
+                    return Ok((rest, DowField::All));
+                }
+
+                let (rest, step) = step_digit::<DayOfWeek>()(rest)?;
+                push_unix_dows(&mut exprs, numbering.min(), Some(7), Some(step));
+                input = rest;
+            }
+            _ => {
+                let (rest, day) = unix_dow(input)?;
+                let (rest, special) = opt(alt((char('L'), char('#'))))(rest)?;
+                let unix_day = map(&unix_dow, unix_weekday);
+                match special {
+                    Some('L') => {
+                        return dow_specials(
+                            rest,
+                            DayOfWeekSpecial::Last(unix_weekday(day)),
+                            unix_day,
+                        )
+                    }
+                    Some(_) => {
+                        let (rest, nth) = map_digit1::<NthDay>()(rest)?;
+                        let first = DayOfWeekSpecial::Nth(unix_weekday(day), nth);
+                        return dow_specials(rest, first, unix_day);
+                    }
+                    None => {}
+                }
+
+                let (rest, (end, step)) = range_and_step(rest, &unix_dow)?;
+                push_unix_dows(&mut exprs, day, end, step);
+                input = rest;
+            }
+        }
+
+        loop {
+            let (rest, comma) = opt(char(','))(input)?;
+            if comma.is_none() {
+                break;
+            }
+
+            let (rest, day) = unix_dow(rest)?;
+            let (rest, (end, step)) = range_and_step(rest, &unix_dow)?;
+            push_unix_dows(&mut exprs, day, end, step);
+            input = rest;
+        }
+
+        let exprs = exprs.expect("At least one expression was read");
+        Ok((input, DowField::Many(exprs)))
+    }
+}
+
+/// A field parsed into expressions, which values can be excluded from with a `!`
+trait Excludable<E> {
+    /// Returns a mask of the values matched by the field like [`exprs_mask`], or `None` if
+    /// values can't be excluded from the field because it isn't a '*' or a list of values,
+    /// ranges, and steps
+    fn values_mask(&self) -> Option<u64>;
+}
+
+impl<E> Excludable<E> for Field<Exprs<E>>
+where
+    E: Copy + ExprValue,
+    u8: From<E>,
+{
+    fn values_mask(&self) -> Option<u64> {
+        match self {
+            Field::All => Some(all_mask::<E>()),
+            Field::Many(exprs) => Some(exprs_mask(exprs)),
+        }
+    }
+}
+
+impl Excludable<DayOfMonth> for DomField<Exprs<DayOfMonth>> {
+    fn values_mask(&self) -> Option<u64> {
+        match self {
+            DomField::All => Some(all_mask::<DayOfMonth>()),
+            DomField::Many(exprs) => Some(exprs_mask(exprs)),
+            _ => None,
+        }
+    }
+}
+
+impl Excludable<DayOfWeek> for DowField<Exprs<DayOfWeek>> {
+    fn values_mask(&self) -> Option<u64> {
+        match self {
+            DowField::All => Some(all_mask::<DayOfWeek>()),
+            DowField::Many(exprs) => Some(exprs_mask(exprs)),
+            _ => None,
+        }
+    }
+}
+
+/// Wraps a field parser to read the values excluded from the field after a `!`, given a parser
+/// reading the same field as expressions and a parser for the values. Exclusions are only read
+/// in the extended dialect, in any other dialect the `!` is left for the next parser to fail on.
+/// Excluding every value matched by the field fails at the `!`, since the field could never
+/// match.
+fn excluding<'a, T, X, E, P, F, V>(
+    dialect: Dialect,
+    field: P,
+    values: F,
+    value: V,
+) -> impl Fn(&'a str) -> IResult<&'a str, (T, Option<Exprs<E>>)>
+where
+    X: Excludable<E>,
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Ord + Copy,
+    u8: From<E>,
+    P: Fn(&'a str) -> IResult<&'a str, T>,
+    F: Fn(&'a str) -> IResult<&'a str, X>,
+    V: Fn(&str) -> IResult<&str, E>,
+{
+    move |input: &'a str| {
+        let (rest, parsed) = field(input)?;
+        if dialect != Dialect::Extended || !rest.starts_with('!') {
+            return Ok((rest, (parsed, None)));
+        }
+
+        // read the field again as expressions to check which values are left
+        let mask = match values(input)?.1.values_mask() {
+            Some(mask) => mask,
+            None => return Ok((rest, (parsed, None))),
+        };
+        let (excluded_input, _) = char('!')(rest)?;
+        let (excluded_input, first) = ors_expr::<E, _>(&value)(excluded_input)?;
+        let (excluded_input, excluded) = tail_ors_exprs(excluded_input, &value, Exprs::new(first))?;
+        if mask & !exprs_mask(&excluded) == 0 {
+            return Err(nom::Err::Failure(ExprError::from_error_kind(
+                rest,
+                ErrorKind::Verify,
+            )));
+        }
+        Ok((excluded_input, (parsed, Some(excluded))))
+    }
+}
+
+/// A day of the year written in the day of the month field in the extended dialect
+#[derive(Clone, Copy)]
+enum YearDay {
+    /// `FY`, January 1st
+    First,
+    /// `LY`, December 31st
+    Last,
+}
+
+impl YearDay {
+    fn day(self) -> DayOfMonth {
+        match self {
+            YearDay::First => DayOfMonth(1),
+            YearDay::Last => DayOfMonth(31),
+        }
+    }
+
+    fn month(self) -> Month {
+        match self {
+            YearDay::First => Month(1),
+            YearDay::Last => Month(12),
+        }
+    }
+}
+
+fn year_day(s: &str) -> IResult<&str, YearDay> {
+    alt((
+        map(tag("FY"), |_| YearDay::First),
+        map(tag("LY"), |_| YearDay::Last),
+    ))(s)
+}
+
+/// A day of the month field parsed along with its exclusions and the day of the year it was
+/// written as, if any
+type DomFieldWithYearDay<C> = ((DomField<C>, Option<Exprs<DayOfMonth>>), Option<YearDay>);
+
+/// Wraps a day of the month field parser to also read `FY` and `LY` in the extended dialect,
+/// which are parsed as the day of the month of the day of the year. The months field is set to
+/// the month of the day afterwards with [`year_day_months`].
+fn year_day_or<'a, C, P>(
+    dialect: Dialect,
+    field: P,
+) -> impl Fn(&'a str) -> IResult<&'a str, DomFieldWithYearDay<C>>
+where
+    C: Collect<DayOfMonth>,
+    P: Fn(&'a str) -> IResult<&'a str, (DomField<C>, Option<Exprs<DayOfMonth>>)>,
+{
+    move |input: &'a str| {
+        if dialect == Dialect::Extended {
+            if let Ok((input, day)) = year_day(input) {
+                let doms = DomField::Many(C::new(OrsExpr::One(day.day())));
+                return Ok((input, ((doms, None), Some(day))));
+            }
+        }
+        map(&field, |doms| (doms, None))(input)
+    }
+}
+
+/// Wraps a day of the week field parser to also read a `&` before the field in the extended
+/// dialect, which makes days match both day fields instead of either.
+fn days_and_or<'a, T, P>(
+    dialect: Dialect,
+    field: P,
+) -> impl Fn(&'a str) -> IResult<&'a str, (T, bool)>
+where
+    P: Fn(&'a str) -> IResult<&'a str, T>,
+{
+    move |input: &'a str| match input.strip_prefix('&') {
+        Some(rest) if dialect == Dialect::Extended => map(&field, |dows| (dows, true))(rest),
+        _ => map(&field, |dows| (dows, false))(input),
+    }
+}
+
+/// Sets the months field of an expression with a `FY` or `LY` day of the month to the month of
+/// the day. Returns `None` if the months field isn't a `*`, since the day is only in one month.
+fn year_day_months<C: Collect<Month>>(
+    day: Option<YearDay>,
+    months: (Field<C>, Option<Exprs<Month>>),
+) -> Option<(Field<C>, Option<Exprs<Month>>)> {
+    match (day, months) {
+        (None, months) => Some(months),
+        (Some(day), (Field::All, None)) => {
+            Some((Field::Many(C::new(OrsExpr::One(day.month()))), None))
+        }
+        (Some(_), _) => None,
+    }
+}
+
+/// Runs a field parser, advancing the input and converting any error into a [`CronParseError`]
+/// for the field.
+fn parse_field<'a, T>(
+    source: &str,
+    input: &mut &'a str,
+    field: FieldKind,
+    parser: impl Fn(&'a str) -> IResult<&'a str, T>,
+) -> Result<T, CronParseError> {
+    let at = |remaining: &str| source.len() - remaining.len();
+    if input.is_empty() {
+        return Err(CronParseError::MissingField {
+            field,
+            at: at(input),
+        });
+    }
+
+    match parser(input) {
+        Ok((remaining, value)) => {
+            *input = remaining;
+            Ok(value)
+        }
+        Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => Err(match err.kind {
+            ExprErrorKind::OutOfRange { value, min, max } => CronParseError::ValueOutOfRange {
+                field,
+                value,
+                min,
+                max,
+                at: at(err.input),
+            },
+            ExprErrorKind::Nom(_) => CronParseError::InvalidField {
+                field,
+                at: at(err.input),
+            },
+        }),
+        Err(nom::Err::Incomplete(_)) => Err(CronParseError::InvalidField {
+            field,
+            at: source.len(),
+        }),
+    }
+}
+
+/// Consumes the whitespace between two fields. If no whitespace is found, the previous field
+/// ended on an invalid character or the next field is missing.
+fn parse_separator(
+    source: &str,
+    input: &mut &str,
+    previous: FieldKind,
+    next: FieldKind,
+) -> Result<(), CronParseError> {
+    let at = source.len() - input.len();
+    match space1::<_, ExprError<&str>>(*input) {
+        Ok((remaining, _)) => {
+            *input = remaining;
+            Ok(())
+        }
+        Err(_) if input.is_empty() => Err(CronParseError::MissingField { field: next, at }),
+        Err(_) => Err(CronParseError::InvalidField {
+            field: previous,
+            at,
+        }),
+    }
+}
+
+/// Parses the fields of a cron expression written in the given dialect, collecting the
+/// expressions of each field with `C`. Shortcuts are left to the caller.
+pub(crate) fn parse_fields<C: Collectors>(
+    s: &str,
+    options: ParseOptions,
+) -> Result<Fields<C>, CronParseError> {
+    use FieldKind::*;
+
+    let dialect = options.dialect;
+    let full_names = options.allow_full_names;
+    let mut input = s;
+    let minutes_field = excluding(
+        dialect,
+        minutes_field,
+        minutes_field::<Exprs<Minute>>,
+        map_digit1::<Minute>(),
+    );
+    let (minutes, excluded_minutes) = parse_field(s, &mut input, Minutes, minutes_field)?;
+    parse_separator(s, &mut input, Minutes, Hours)?;
+    let hours_field = excluding(
+        dialect,
+        hours_field,
+        hours_field::<Exprs<Hour>>,
+        map_digit1::<Hour>(),
+    );
+    let (hours, excluded_hours) = parse_field(s, &mut input, Hours, hours_field)?;
+    parse_separator(s, &mut input, Hours, DaysOfMonth)?;
+    let dom_field = year_day_or(
+        dialect,
+        excluding(
+            dialect,
+            dom_field,
+            dom_field::<Exprs<DayOfMonth>>,
+            map_digit1::<DayOfMonth>(),
+        ),
+    );
+    let ((doms, excluded_doms), year_day) = parse_field(s, &mut input, DaysOfMonth, dom_field)?;
+    parse_separator(s, &mut input, DaysOfMonth, Months)?;
+    let months_at = s.len() - input.len();
+    let months_field = excluding(
+        dialect,
+        months_field(full_names),
+        months_field::<Exprs<Month>>(full_names),
+        month(full_names),
+    );
+    let months = parse_field(s, &mut input, Months, months_field)?;
+    let (months, excluded_months) =
+        year_day_months(year_day, months).ok_or(CronParseError::InvalidField {
+            field: Months,
+            at: months_at,
+        })?;
+    parse_separator(s, &mut input, Months, DaysOfWeek)?;
+    let dows_at = s.len() - input.len();
+    let ((dows, excluded_dows), days_and) = match options.weekday_numbering() {
+        WeekdayNumbering::Quartz => parse_field(
+            s,
+            &mut input,
+            DaysOfWeek,
+            days_and_or(
+                dialect,
+                excluding(
+                    dialect,
+                    dow_field(full_names),
+                    dow_field::<Exprs<DayOfWeek>>(full_names),
+                    dow(full_names),
+                ),
+            ),
+        )?,
+        numbering => parse_field(
+            s,
+            &mut input,
+            DaysOfWeek,
+            days_and_or(
+                dialect,
+                excluding(
+                    dialect,
+                    unix_dow_field(full_names, numbering),
+                    unix_dow_field::<Exprs<DayOfWeek>>(full_names, numbering),
+                    unix_day(full_names, numbering),
+                ),
+            ),
+        )?,
+    };
+
+    // Only one of the day fields can be left unused
+    if matches!(doms, DomField::Ignored) && matches!(dows, DowField::Ignored) {
+        return Err(CronParseError::InvalidField {
+            field: DaysOfWeek,
+            at: dows_at,
+        });
+    }
+
+    // the weeks field is optional, so an expression without one matches every week
+    let mut last = DaysOfWeek;
+    let (weeks, excluded_weeks) =
+        if dialect == Dialect::Extended && !input.trim_start_matches(is_blank).is_empty() {
+            parse_separator(s, &mut input, DaysOfWeek, Weeks)?;
+            last = Weeks;
+            let weeks_field = excluding(
+                dialect,
+                weeks_field,
+                weeks_field::<Exprs<Week>>,
+                map_digit1::<Week>(),
+            );
+            parse_field(s, &mut input, Weeks, weeks_field)?
+        } else {
+            (Field::All, None)
+        };
+
+    if !input.is_empty() {
+        let at = s.len() - input.len();
+        return Err(if input.starts_with(is_blank) {
+            CronParseError::TrailingInput { at }
+        } else {
+            CronParseError::InvalidField { field: last, at }
+        });
+    }
+
+    Ok(Fields {
+        minutes,
+        hours,
+        doms,
+        months,
+        dows,
+        weeks,
+        exclusions: Exclusions {
+            minutes: excluded_minutes,
+            hours: excluded_hours,
+            doms: excluded_doms,
+            months: excluded_months,
+            dows: excluded_dows,
+            weeks: excluded_weeks,
+        },
+        days_and,
+    })
+}
+
+/// Reads the fields of an expression one whitespace separated token at a time, recording the
+/// error of each field that fails instead of stopping at it.
+struct LenientFields<'a> {
+    source: &'a str,
+    /// The ranges of the tokens in the source that haven't been read yet
+    tokens: vec::IntoIter<(usize, usize)>,
+    /// The end of the last token read
+    end: usize,
+    errors: Vec<CronParseError>,
+}
+
+impl<'a> LenientFields<'a> {
+    fn new(source: &'a str) -> Self {
+        let tokens: Vec<(usize, usize)> = split_fields(source)
+            .into_iter()
+            .map(|token| (token.start, token.end))
+            .collect();
+
+        let mut errors = Vec::new();
+        // like the strict parser, the minutes field must start the expression
+        if !source.is_empty() && tokens.first().map(|&(start, _)| start) != Some(0) {
+            errors.push(CronParseError::InvalidField {
+                field: FieldKind::Minutes,
+                at: 0,
+            });
+        }
+
+        Self {
+            source,
+            tokens: tokens.into_iter(),
+            end: 0,
+            errors,
+        }
+    }
+
+    /// Reads the next token as the given field. If there's no token left, the field is reported
+    /// missing, unless an earlier field already was.
+    fn field<T>(
+        &mut self,
+        field: FieldKind,
+        parser: impl Fn(&'a str) -> IResult<&'a str, T>,
+    ) -> Option<T> {
+        let (start, end) = match self.tokens.next() {
+            Some(token) => token,
+            None => {
+                if !matches!(
+                    self.errors.last(),
+                    Some(CronParseError::MissingField { .. })
+                ) {
+                    self.errors.push(CronParseError::MissingField {
+                        field,
+                        at: self.source.len(),
+                    });
+                }
+                return None;
+            }
+        };
+        self.end = end;
+
+        let mut input = &self.source[start..];
+        match parse_field(self.source, &mut input, field, parser) {
+            Ok(value) if self.source.len() - input.len() == end => Some(value),
+            Ok(_) => {
+                self.errors.push(CronParseError::InvalidField {
+                    field,
+                    at: self.source.len() - input.len(),
+                });
+                None
+            }
+            Err(err) => {
+                self.errors.push(err);
+                None
+            }
+        }
+    }
+}
+
+/// Parses the fields of a cron expression like [`parse_fields`], but keeps parsing the fields
+/// after one fails, returning every error found in the order they appear in the expression.
+fn parse_fields_lenient<C: Collectors>(
+    s: &str,
+    options: ParseOptions,
+) -> Result<Fields<C>, Vec<CronParseError>> {
+    use FieldKind::*;
+
+    let dialect = options.dialect;
+    let full_names = options.allow_full_names;
+    let mut fields = LenientFields::new(s);
+    let minutes = fields.field(
+        Minutes,
+        excluding(
+            dialect,
+            minutes_field,
+            minutes_field::<Exprs<Minute>>,
+            map_digit1::<Minute>(),
+        ),
+    );
+    let hours = fields.field(
+        Hours,
+        excluding(
+            dialect,
+            hours_field,
+            hours_field::<Exprs<Hour>>,
+            map_digit1::<Hour>(),
+        ),
+    );
+    let doms = fields.field(
+        DaysOfMonth,
+        year_day_or(
+            dialect,
+            excluding(
+                dialect,
+                dom_field,
+                dom_field::<Exprs<DayOfMonth>>,
+                map_digit1::<DayOfMonth>(),
+            ),
+        ),
+    );
+    let year_day = doms.as_ref().and_then(|&(_, year_day)| year_day);
+    let doms = doms.map(|(doms, _)| doms);
+    let months_at = fields.tokens.as_slice().first().map(|&(start, _)| start);
+    let months = fields
+        .field(
+            Months,
+            excluding(
+                dialect,
+                months_field(full_names),
+                months_field::<Exprs<Month>>(full_names),
+                month(full_names),
+            ),
+        )
+        .and_then(|months| {
+            let months = year_day_months(year_day, months);
+            if let (None, Some(at)) = (&months, months_at) {
+                fields
+                    .errors
+                    .push(CronParseError::InvalidField { field: Months, at });
+            }
+            months
+        });
+    let dows_at = fields.tokens.as_slice().first().map(|&(start, _)| start);
+    let dows = match options.weekday_numbering() {
+        WeekdayNumbering::Quartz => fields.field(
+            DaysOfWeek,
+            days_and_or(
+                dialect,
+                excluding(
+                    dialect,
+                    dow_field(full_names),
+                    dow_field::<Exprs<DayOfWeek>>(full_names),
+                    dow(full_names),
+                ),
+            ),
+        ),
+        numbering => fields.field(
+            DaysOfWeek,
+            days_and_or(
+                dialect,
+                excluding(
+                    dialect,
+                    unix_dow_field(full_names, numbering),
+                    unix_dow_field::<Exprs<DayOfWeek>>(full_names, numbering),
+                    unix_day(full_names, numbering),
+                ),
+            ),
+        ),
+    };
+    let days_and = matches!(dows, Some((_, true)));
+    let dows = dows.map(|(dows, _)| dows);
+
+    if let (Some((DomField::Ignored, _)), Some((DowField::Ignored, _)), Some(at)) =
+        (&doms, &dows, dows_at)
+    {
+        fields.errors.push(CronParseError::InvalidField {
+            field: DaysOfWeek,
+            at,
+        });
+    }
+
+    let mut last = DaysOfWeek;
+    let rest = &s[fields.end..];
+    let weeks = if dialect == Dialect::Extended && !rest.trim_start_matches(is_blank).is_empty() {
+        last = Weeks;
+        fields.field(
+            Weeks,
+            excluding(
+                dialect,
+                weeks_field,
+                weeks_field::<Exprs<Week>>,
+                map_digit1::<Week>(),
+            ),
+        )
+    } else {
+        Some((Field::All, None))
+    };
+
+    let missing = matches!(
+        fields.errors.last(),
+        Some(CronParseError::MissingField { .. })
+    );
+    if !missing && fields.end < s.len() {
+        let at = fields.end;
+        fields.errors.push(if s[at..].starts_with(is_blank) {
+            CronParseError::TrailingInput { at }
+        } else {
+            CronParseError::InvalidField { field: last, at }
+        });
+    }
+
+    match (minutes, hours, doms, months, dows, weeks) {
+        (Some(minutes), Some(hours), Some(doms), Some(months), Some(dows), Some(weeks))
+            if fields.errors.is_empty() =>
+        {
+            Ok(Fields {
+                minutes: minutes.0,
+                hours: hours.0,
+                doms: doms.0,
+                months: months.0,
+                dows: dows.0,
+                weeks: weeks.0,
+                exclusions: Exclusions {
+                    minutes: minutes.1,
+                    hours: hours.1,
+                    doms: doms.1,
+                    months: months.1,
+                    dows: dows.1,
+                    weeks: weeks.1,
+                },
+                days_and,
+            })
+        }
+        _ => Err(fields.errors),
+    }
+}
+
+impl FromStr for CronExpr {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CronExpr::from_str_with(s, Dialect::Quartz)
+    }
+}
+
+impl CronExpr {
+    /// Parses a cron expression written in the given dialect. Fields are separated by any run of
+    /// spaces and tabs. Expressions are always displayed in the default Quartz dialect.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, Dialect};
+    ///
+    /// let expr = CronExpr::from_str_with("0 9 * * 1-5", Dialect::Unix).expect("Valid cron expression");
+    /// assert_eq!(expr, "0 9 * * MON-FRI".parse().unwrap());
+    /// assert_eq!(expr.to_string(), "0 9 * * 2-6");
+    /// ```
+    pub fn from_str_with(s: &str, dialect: Dialect) -> Result<Self, CronParseError> {
+        let mut options = ParseOptions::new();
+        options.dialect = dialect;
+        CronExpr::from_str_with_options(s, options)
+    }
+
+    /// Parses a cron expression with the given options.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, CronParseError, Dialect, ParseOptions};
+    ///
+    /// let mut options = ParseOptions::new();
+    /// options.dialect = Dialect::Unix;
+    /// options.trim_whitespace = true;
+    ///
+    /// let expr = CronExpr::from_str_with_options("\t0 9\t* *  1-5 ", options).unwrap();
+    /// assert_eq!(expr, CronExpr::from_str_with("0 9 * * 1-5", Dialect::Unix).unwrap());
+    ///
+    /// let err = CronExpr::from_str_with_options("  0 24 * * *", options).unwrap_err();
+    /// assert!(matches!(err, CronParseError::ValueOutOfRange { at: 4, .. }));
+    ///
+    /// options.max_terms = 2;
+    /// let err = CronExpr::from_str_with_options("0 1,2,3 * * *", options).unwrap_err();
+    /// assert!(matches!(err, CronParseError::TooManyTerms { at: 5, .. }));
+    /// ```
+    pub fn from_str_with_options(s: &str, options: ParseOptions) -> Result<Self, CronParseError> {
+        CronExpr::from_str_with_comment(s, options).map(|(expr, _)| expr)
+    }
+
+    /// Parses a cron expression with the given options like [`from_str_with_options`], also
+    /// returning the text of the comment after it if
+    /// [`ParseOptions::allow_trailing_comment`] is set. The text is trimmed and doesn't include
+    /// the `#`.
+    ///
+    /// [`from_str_with_options`]: #method.from_str_with_options
+    /// [`ParseOptions::allow_trailing_comment`]: struct.ParseOptions.html#structfield.allow_trailing_comment
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, ParseOptions};
+    ///
+    /// let mut options = ParseOptions::new();
+    /// options.allow_trailing_comment = true;
+    ///
+    /// let (expr, comment) =
+    ///     CronExpr::from_str_with_comment("0 9 ? * MON#1 # first Monday\n", options).unwrap();
+    /// assert_eq!(expr, "0 9 ? * MON#1".parse().unwrap());
+    /// assert_eq!(comment, Some("first Monday"));
+    ///
+    /// let (_, comment) = CronExpr::from_str_with_comment("0 9 * * ?\r\n", options).unwrap();
+    /// assert_eq!(comment, None);
+    /// ```
+    pub fn from_str_with_comment(
+        s: &str,
+        options: ParseOptions,
+    ) -> Result<(Self, Option<&str>), CronParseError> {
+        let (s, comment) = if options.allow_trailing_comment {
+            split_comment(s)
+        } else {
+            (s, None)
+        };
+        if !options.trim_whitespace {
+            return CronExpr::parse_checked(s, options).map(|expr| (expr, comment));
+        }
+
+        let trimmed = s.trim_start_matches(is_blank);
+        let start = s.len() - trimmed.len();
+        CronExpr::parse_checked(trimmed.trim_end_matches(is_blank), options)
+            .map(|expr| (expr, comment))
+            .map_err(|err| err.offset(start))
+    }
+
+    /// Parses a trimmed expression after checking it against the limits in the options
+    fn parse_checked(s: &str, options: ParseOptions) -> Result<Self, CronParseError> {
+        options.check_limits(s)?;
+        if let Some(name) = s.strip_prefix('@') {
+            return Shortcut::ALL
+                .iter()
+                .find(|shortcut| shortcut.name().eq_ignore_ascii_case(name))
+                .map(|shortcut| shortcut.expr())
+                .ok_or(CronParseError::UnknownShortcut);
+        }
+
+        if let Some(locale) = options.locale {
+            let translated = locale.translate(s);
+            return parse_fields::<CronExpr>(&translated.text, options)
+                .map(CronExpr::from_fields)
+                .map_err(|err| err.map_position(|at| translated.original_at(at)));
+        }
+
+        parse_fields::<CronExpr>(s, options).map(CronExpr::from_fields)
+    }
+
+    /// Parses a cron expression in the default Quartz dialect like [`CronExpr::from_str`], but
+    /// keeps parsing the remaining fields after one fails, so every problem in the expression
+    /// can be reported at once. See [`CronExpr::parse_lenient_with`].
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::{CronExpr, CronParseError, FieldKind};
+    ///
+    /// let (expr, errors) = CronExpr::parse_lenient("60 * 32 * FOO");
+    /// assert_eq!(expr, None);
+    /// assert_eq!(
+    ///     errors.iter().map(|err| err.field()).collect::<Vec<_>>(),
+    ///     [Some(FieldKind::Minutes), Some(FieldKind::DaysOfMonth), Some(FieldKind::DaysOfWeek)],
+    /// );
+    ///
+    /// let (expr, errors) = CronExpr::parse_lenient("0 * * * *");
+    /// assert_eq!(expr, Some("0 * * * *".parse().unwrap()));
+    /// assert!(errors.is_empty());
+    /// ```
+    ///
+    /// [`CronExpr::from_str`]: #method.from_str
+    pub fn parse_lenient(s: &str) -> (Option<CronExpr>, Vec<CronParseError>) {
+        CronExpr::parse_lenient_with(s, Dialect::Quartz)
+    }
+
+    /// Parses a cron expression written in the given dialect, but keeps parsing the remaining
+    /// fields after one fails instead of stopping at the first error. Each field is read up to
+    /// the next whitespace, so an error in one field doesn't hide the errors in the others.
+    ///
+    /// The errors are returned in the order they appear in the expression, and the first is
+    /// always the error [`CronExpr::from_str_with`] returns. The expression is only returned if
+    /// there are no errors.
+    pub fn parse_lenient_with(
+        s: &str,
+        dialect: Dialect,
+    ) -> (Option<CronExpr>, Vec<CronParseError>) {
+        if let Err(err) = ParseOptions::new().check_limits(s) {
+            return (None, core::iter::once(err).collect());
+        }
+        if s.starts_with('@') {
+            return match CronExpr::from_str_with(s, dialect) {
+                Ok(expr) => (Some(expr), Vec::new()),
+                Err(err) => (None, core::iter::once(err).collect()),
+            };
+        }
+
+        let mut options = ParseOptions::new();
+        options.dialect = dialect;
+        match parse_fields_lenient::<CronExpr>(s, options) {
+            Ok(fields) => (Some(CronExpr::from_fields(fields)), Vec::new()),
+            Err(errors) => (None, errors),
+        }
+    }
+
+    fn from_fields(fields: Fields<CronExpr>) -> CronExpr {
+        CronExpr {
+            minutes: fields.minutes.into(),
+            hours: fields.hours.into(),
+            doms: fields.doms.into(),
+            months: fields.months.into(),
+            dows: fields.dows.into(),
+            weeks: fields.weeks.into(),
+            shortcut: None,
+            exclusions: fields.exclusions,
+            days_and: fields.days_and,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+    use core::fmt::Debug;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    use super::*;
+
+    fn minutes_expr(s: &str) -> IResult<&str, Expr<Minute>> {
+        map(minutes_field, Expr::from)(s)
+    }
+
+    fn hours_expr(s: &str) -> IResult<&str, Expr<Hour>> {
+        map(hours_field, Expr::from)(s)
+    }
+
+    fn dom_expr(s: &str) -> IResult<&str, DayOfMonthExpr> {
+        map(dom_field, DayOfMonthExpr::from)(s)
+    }
+
+    fn months_expr(s: &str) -> IResult<&str, Expr<Month>> {
+        map(months_field(false), Expr::from)(s)
+    }
+
+    fn dow_expr(s: &str) -> IResult<&str, DayOfWeekExpr> {
+        map(dow_field(false), DayOfWeekExpr::from)(s)
+    }
+
+    fn exprs<E, I>(iter: I) -> Exprs<E>
+    where
+        I: IntoIterator<Item = OrsExpr<E>>,
+    {
+        let mut iter = iter.into_iter();
+        let first = iter.next().expect("Iterator must have at least one item");
+        let tail = iter.collect();
+        Exprs { first, tail }
+    }
+
+    fn e<E>(value: u8) -> E
+    where
+        E: TryFrom<u8>,
+        E::Error: Debug,
+    {
+        E::try_from(value).unwrap()
+    }
+
+    fn o<E>(value: u8) -> OrsExpr<E>
+    where
+        E: TryFrom<u8>,
+        E::Error: Debug,
+    {
+        OrsExpr::One(e(value))
+    }
+
+    fn r<E>(start: u8, end: u8) -> OrsExpr<E>
+    where
+        E: TryFrom<u8>,
+        E::Error: Debug,
+    {
+        let start = e(start);
+        let end = e(end);
+        OrsExpr::Range(start, end)
+    }
+
+    fn s<E>(value: u8, step: u8) -> OrsExpr<E>
+    where
+        E: TryFrom<u8> + ExprValue,
+        E::Error: Debug,
+    {
+        let start = e(value);
+        let step = e(step);
+        OrsExpr::Step {
+            start,
+            end: E::max(),
+            step,
+        }
+    }
+
+    fn rs<E>(start: u8, end: u8, step: u8) -> OrsExpr<E>
+    where
+        E: TryFrom<u8> + ExprValue,
+        E::Error: Debug,
+    {
+        let start = e(start);
+        let end = e(end);
+        let step = e(step);
+        OrsExpr::Step { start, end, step }
+    }
+
+    mod values {
+        use super::*;
+        use chrono::{TimeZone, Utc, Weekday};
+
+        #[test]
+        fn all() {
+            assert!(Minute::all().eq((0..60).map(e::<Minute>)));
+            assert!(Hour::all().rev().eq((0..24).rev().map(e::<Hour>)));
+            assert_eq!(DayOfMonth::all().len(), 31);
+            assert_eq!(DayOfMonth::all().next(), Some(e(1)));
+            assert_eq!(Month::all().last(), Some(e(12)));
+            assert_eq!(Week::all().len(), 53);
+            assert!(DayOfWeek::all().eq((1..=7).map(e::<DayOfWeek>)));
+        }
+
+        #[test]
+        fn ors_expr_values() {
+            let values = |expr: OrsExpr<Minute>| expr.values().map(u8::from).collect::<Vec<_>>();
+            assert_eq!(values(o(5)), [5]);
+            assert_eq!(values(r(57, 2)), [0, 1, 2, 57, 58, 59]);
+            assert_eq!(values(rs(10, 30, 10)), [10, 20, 30]);
+            assert_eq!(values(s(45, 5)), [45, 50, 55]);
+
+            let days = |expr: OrsExpr<DayOfWeek>| expr.values().map(u8::from).collect::<Vec<_>>();
+            // FRI-MON, zero based from Sunday
+            assert_eq!(days(r(6, 2)), [0, 1, 5, 6]);
+            assert_eq!(days(rs(1, 7, 3)), [0, 3, 6]);
+
+            let months = r::<Month>(11, 2).values().collect::<Vec<Month>>();
+            assert_eq!(months, [e::<Month>(1), e(2), e(11), e(12)]);
+            assert_eq!(r::<DayOfMonth>(1, 31).values().len(), 31);
+            assert_eq!(s::<DayOfMonth>(31, 1).values().next_back(), Some(e(31)));
+        }
+
+        #[test]
+        fn values_match_compiled_fields() {
+            use crate::Cron;
+
+            let exprs = [
+                "50-10/7,3 22-2/3 * * *",
+                "* * L 11-2/2 SAT-TUE",
+                "0,30 */5 1-31/8 * MON-FRI/2",
+                "59-0 23-0 31 12 SUN",
+            ];
+            for &s in &exprs {
+                let expr: CronExpr = s.parse().unwrap();
+                let cron = Cron::new(expr.clone());
+                assert!(
+                    expr.minutes.values().map(u8::from).eq(cron.minutes()),
+                    "{}",
+                    s
+                );
+                assert!(expr.hours.values().map(u8::from).eq(cron.hours()), "{}", s);
+                assert!(
+                    expr.months
+                        .values()
+                        .map(|m| u8::from(m) + 1)
+                        .eq(cron.months()),
+                    "{}",
+                    s
+                );
+            }
+
+            let all = Expr::<Hour>::All.values();
+            assert_eq!(all.len(), 24);
+            assert!(all.rev().eq(Hour::all().rev()));
+        }
+
+        #[test]
+        fn checked_arithmetic() {
+            assert_eq!(e::<Minute>(58).checked_add(1), Some(e(59)));
+            assert_eq!(e::<Minute>(58).checked_add(2), None);
+            assert_eq!(e::<Minute>(0).checked_sub(1), None);
+            assert_eq!(e::<Hour>(23).checked_add(u8::MAX), None);
+            assert_eq!(e::<DayOfMonth>(2).checked_sub(1), Some(e(1)));
+            assert_eq!(e::<DayOfMonth>(1).checked_sub(1), None);
+            assert_eq!(e::<NthDay>(4).checked_add(1), Some(e(5)));
+            assert_eq!(e::<NthDay>(5).checked_add(1), None);
+        }
+
+        #[test]
+        fn from_chrono() {
+            let time = Utc.with_ymd_and_hms(2021, 1, 3, 23, 59, 30).unwrap();
+            assert_eq!(Minute::from_time(&time), e::<Minute>(59));
+            assert_eq!(Minute::from_time(&time.time()), e::<Minute>(59));
+            assert_eq!(Hour::from_time(&time), e::<Hour>(23));
+            assert_eq!(DayOfMonth::from_date(&time), e::<DayOfMonth>(3));
+            assert_eq!(Month::from_date(&time.date_naive()), e::<Month>(1));
+            // January 3rd 2021 is a Sunday in the last ISO week of 2020
+            assert_eq!(Week::from_date(&time), e::<Week>(53));
+            assert_eq!(DayOfWeek::from_date(&time), DayOfWeek::from(Weekday::Sun));
+        }
+    }
+
+    mod minutes {
+        use super::*;
+
+        #[test]
+        fn all() {
+            assert_eq!(minutes_expr("*"), Ok(("", Expr::All)))
+        }
+
+        #[test]
+        fn only_match_first_star() {
+            // make sure we only match the first star.
+            // it'll fail on the next parser
+            assert_eq!(minutes_expr("*,*"), Ok((",*", Expr::All)))
+        }
+
+        #[test]
+        fn star_step() {
+            assert_eq!(
+                minutes_expr("*/5"),
+                Ok(("", Expr::Many(exprs(vec![s(0, 5)]))))
+            )
+        }
+
+        #[test]
+        fn multi_star_step() {
+            assert_eq!(
+                minutes_expr("*/5,*/3"),
+                Ok(("", Expr::Many(exprs(vec![s(0, 5), s(0, 3)]))))
+            )
+        }
+
+        #[test]
+        fn star_range_doesnt_make_sense() {
+            // make sure we only match the first star.
+            // it'll fail on the next parser
+            assert_eq!(minutes_expr("*-30/5,*/3"), Ok(("-30/5,*/3", Expr::All)))
+        }
+
+        #[test]
+        fn one_value() {
+            assert_eq!(minutes_expr("0"), Ok(("", Expr::Many(exprs(vec![o(0)])))))
+        }
+
+        #[test]
+        fn many_one_value() {
+            assert_eq!(
+                minutes_expr("5,15,25,35,45,55"),
+                Ok((
+                    "",
+                    Expr::Many(exprs(vec![o(5), o(15), o(25), o(35), o(45), o(55)]))
+                ))
+            )
+        }
+
+        #[test]
+        fn one_range() {
+            assert_eq!(
+                minutes_expr("0-30"),
+                Ok(("", Expr::Many(exprs(vec![r(0, 30)]))))
+            )
+        }
+
+        #[test]
+        fn overflow_range() {
+            assert_eq!(
+                minutes_expr("50-10"),
+                Ok(("", Expr::Many(exprs(vec![r(50, 10)]))))
+            )
+        }
+
+        #[test]
+        fn many_range() {
+            assert_eq!(
+                minutes_expr("0-5,10-15,20-25,30-35,40-45,50-55"),
+                Ok((
+                    "",
+                    Expr::Many(exprs(vec![
+                        r(0, 5),
+                        r(10, 15),
+                        r(20, 25),
+                        r(30, 35),
+                        r(40, 45),
+                        r(50, 55)
+                    ]))
+                ))
+            )
+        }
+
+        #[test]
+        fn step() {
+            assert_eq!(
+                minutes_expr("0/5"),
+                Ok(("", Expr::Many(exprs(vec![s(0, 5)]))))
+            )
+        }
+
+        #[test]
+        fn step_with_star_step() {
+            assert_eq!(
+                minutes_expr("1/3,*/5"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 3), s(0, 5)]))))
+            )
+        }
+
+        #[test]
+        fn many_steps() {
+            assert_eq!(
+                minutes_expr("1/3,2/3,5/10"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 3), s(2, 3), s(5, 10)]))))
+            )
+        }
+
+        #[test]
+        fn range_step() {
+            assert_eq!(
+                minutes_expr("0-30/5"),
+                Ok(("", Expr::Many(exprs(vec![rs(0, 30, 5)]))))
+            )
+        }
+
+        #[test]
+        fn many_range_step() {
+            assert_eq!(
+                minutes_expr("0-30/5,30-59/3"),
+                Ok(("", Expr::Many(exprs(vec![rs(0, 30, 5), rs(30, 59, 3)]))))
+            )
+        }
+
+        #[test]
+        fn values_ranges_steps_and_ranges() {
+            assert_eq!(
+                minutes_expr("0,5-10,10-30/3,30/3"),
+                Ok((
+                    "",
+                    Expr::Many(exprs(vec![o(0), r(5, 10), rs(10, 30, 3), s(30, 3)]))
+                ))
+            )
+        }
+
+        #[test]
+        fn limits() {
+            assert!(matches!(minutes_expr("60"), Err(_)));
+            assert!(matches!(minutes_expr("0-60"), Err(_)));
+            // a step greater than the max value is not allowed (since it doesn't make sense)
+            assert!(matches!(minutes_expr("0/60"), Err(_)));
+            assert!(matches!(minutes_expr("0-60/5"), Err(_)));
+            // a step of 0 is not allowed (since it doesn't make sense)
+            assert!(matches!(minutes_expr("0/0"), Err(_)));
+            assert!(matches!(minutes_expr("0-59/0"), Err(_)));
+        }
+    }
+
+    mod hours {
+        use super::*;
+
+        #[test]
+        fn all() {
+            assert_eq!(hours_expr("*"), Ok(("", Expr::All)))
+        }
+
+        #[test]
+        fn only_match_first_star() {
+            // make sure we only match the first star.
+            // it'll fail on the next parser
+            assert_eq!(hours_expr("*,*"), Ok((",*", Expr::All)))
+        }
+
+        #[test]
+        fn star_step() {
+            assert_eq!(
+                hours_expr("*/3"),
+                Ok(("", Expr::Many(exprs(vec![s(0, 3)]))))
+            )
+        }
+
+        #[test]
+        fn multi_star_step() {
+            assert_eq!(
+                hours_expr("*/3,*/4"),
+                Ok(("", Expr::Many(exprs(vec![s(0, 3), s(0, 4)]))))
+            )
+        }
+
+        #[test]
+        fn star_range_doesnt_make_sense() {
+            // make sure we only match the first star.
+            // it'll fail on the next parser
+            assert_eq!(hours_expr("*-6/3,*/4"), Ok(("-6/3,*/4", Expr::All)))
+        }
+
+        #[test]
+        fn one_value() {
+            assert_eq!(hours_expr("0"), Ok(("", Expr::Many(exprs(vec![o(0)])))))
+        }
+
+        #[test]
+        fn many_one_value() {
+            assert_eq!(
+                hours_expr("0,3,6,9,12,15,18,21"),
+                Ok((
+                    "",
+                    Expr::Many(exprs(vec![
+                        o(0),
+                        o(3),
+                        o(6),
+                        o(9),
+                        o(12),
+                        o(15),
+                        o(18),
+                        o(21)
+                    ]))
+                ))
+            )
+        }
+
+        #[test]
+        fn one_range() {
+            assert_eq!(
+                hours_expr("0-12"),
+                Ok(("", Expr::Many(exprs(vec![r(0, 12)]))))
+            )
+        }
+
+        #[test]
+        fn overflow_range() {
+            assert_eq!(
+                hours_expr("22-2"),
+                Ok(("", Expr::Many(exprs(vec![r(22, 2)]))))
+            )
+        }
+
+        #[test]
+        fn many_range() {
+            assert_eq!(
+                hours_expr("0-3,6-9,12-15,18-21"),
+                Ok((
+                    "",
+                    Expr::Many(exprs(vec![r(0, 3), r(6, 9), r(12, 15), r(18, 21)]))
+                ))
+            )
+        }
+
+        #[test]
+        fn step() {
+            assert_eq!(
+                hours_expr("0/3"),
+                Ok(("", Expr::Many(exprs(vec![s(0, 3)]))))
+            )
+        }
+
+        #[test]
+        fn step_with_star_step() {
+            assert_eq!(
+                hours_expr("1/2,*/4"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 2), s(0, 4)]))))
+            )
+        }
+
+        #[test]
+        fn many_steps() {
+            assert_eq!(
+                hours_expr("1/2,2/3,3/4"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))))
+            )
+        }
+
+        #[test]
+        fn range_step() {
+            assert_eq!(
+                hours_expr("0-12/4"),
+                Ok(("", Expr::Many(exprs(vec![rs(0, 12, 4)]))))
+            )
+        }
+
+        #[test]
+        fn many_range_step() {
+            assert_eq!(
+                hours_expr("0-12/4,12-23/3"),
+                Ok(("", Expr::Many(exprs(vec![rs(0, 12, 4), rs(12, 23, 3)]))))
+            )
+        }
+
+        #[test]
+        fn values_ranges_steps_and_ranges() {
+            assert_eq!(
+                hours_expr("0,0-6/3,6-12,12/3"),
+                Ok((
+                    "",
+                    Expr::Many(exprs(vec![o(0), rs(0, 6, 3), r(6, 12), s(12, 3)]))
+                ))
+            )
+        }
+
+        #[test]
+        fn limits() {
+            assert!(matches!(hours_expr("24"), Err(_)));
+            assert!(matches!(hours_expr("0-24"), Err(_)));
+            // a step greater than the max value is not allowed (since it doesn't make sense)
+            assert!(matches!(hours_expr("0/24"), Err(_)));
+            assert!(matches!(hours_expr("0-24/2"), Err(_)));
+            // a step of 0 is not allowed (since it doesn't make sense)
+            assert!(matches!(hours_expr("0/0"), Err(_)));
+            assert!(matches!(hours_expr("0-23/0"), Err(_)));
+        }
+    }
+
+    mod months {
+        use super::*;
+
+        #[test]
+        fn all() {
+            assert_eq!(months_expr("*"), Ok(("", Expr::All)))
+        }
+
+        #[test]
+        fn only_match_first_star() {
+            // make sure we only match the first star.
+            // it'll fail on the next parser
+            assert_eq!(months_expr("*,*"), Ok((",*", Expr::All)))
+        }
+
+        #[test]
+        fn star_step() {
+            assert_eq!(
+                months_expr("*/3"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 3)]))))
+            )
+        }
+
+        #[test]
+        fn multi_star_step() {
+            assert_eq!(
+                months_expr("*/3,*/4"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 3), s(1, 4)]))))
+            )
+        }
+
+        #[test]
+        fn star_range_doesnt_make_sense() {
+            // make sure we only match the first star.
+            // it'll fail on the next parser
+            assert_eq!(months_expr("*-6/3,*/4"), Ok(("-6/3,*/4", Expr::All)))
+        }
+
+        #[test]
+        fn one_value() {
+            assert_eq!(months_expr("1"), Ok(("", Expr::Many(exprs(vec![o(1)])))))
+        }
+
+        #[test]
+        fn word_values() {
+            // caps
+            assert_eq!(months_expr("JAN"), Ok(("", Expr::Many(exprs(vec![o(1)])))));
+            assert_eq!(months_expr("FEB"), Ok(("", Expr::Many(exprs(vec![o(2)])))));
+            assert_eq!(months_expr("MAR"), Ok(("", Expr::Many(exprs(vec![o(3)])))));
+            assert_eq!(months_expr("APR"), Ok(("", Expr::Many(exprs(vec![o(4)])))));
+
+            // lower
+            assert_eq!(months_expr("may"), Ok(("", Expr::Many(exprs(vec![o(5)])))));
+            assert_eq!(months_expr("jun"), Ok(("", Expr::Many(exprs(vec![o(6)])))));
+            assert_eq!(months_expr("jul"), Ok(("", Expr::Many(exprs(vec![o(7)])))));
+            assert_eq!(months_expr("aug"), Ok(("", Expr::Many(exprs(vec![o(8)])))));
+
+            // mixed
+            assert_eq!(months_expr("sEp"), Ok(("", Expr::Many(exprs(vec![o(9)])))));
+            assert_eq!(months_expr("ocT"), Ok(("", Expr::Many(exprs(vec![o(10)])))));
+            assert_eq!(months_expr("NOv"), Ok(("", Expr::Many(exprs(vec![o(11)])))));
+            assert_eq!(months_expr("Dec"), Ok(("", Expr::Many(exprs(vec![o(12)])))));
+        }
+
+        #[test]
+        fn many_one_value() {
+            assert_eq!(
+                months_expr("1,MAR,6,SEP,12"),
+                Ok(("", Expr::Many(exprs(vec![o(1), o(3), o(6), o(9), o(12)]))))
+            )
+        }
+
+        #[test]
+        fn one_range() {
+            assert_eq!(
+                months_expr("1-12"),
+                Ok(("", Expr::Many(exprs(vec![r(1, 12)]))))
+            );
+            assert_eq!(
+                months_expr("JAN-DEC"),
+                Ok(("", Expr::Many(exprs(vec![r(1, 12)]))))
+            )
+        }
+
+        #[test]
+        fn overflow_range() {
+            assert_eq!(
+                months_expr("11-FEB"),
+                Ok(("", Expr::Many(exprs(vec![r(11, 2)]))))
+            );
+            assert_eq!(
+                months_expr("NOV-2"),
+                Ok(("", Expr::Many(exprs(vec![r(11, 2)]))))
+            )
+        }
+
+        #[test]
+        fn many_range() {
+            assert_eq!(
+                months_expr("1-MAR,MAY-7,SEP-11"),
+                Ok(("", Expr::Many(exprs(vec![r(1, 3), r(5, 7), r(9, 11)]))))
+            )
+        }
+
+        #[test]
+        fn step() {
+            assert_eq!(
+                months_expr("1/3"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 3)]))))
+            )
+        }
+
+        #[test]
+        fn step_with_star_step() {
+            assert_eq!(
+                months_expr("2/2,*/4"),
+                Ok(("", Expr::Many(exprs(vec![s(2, 2), s(1, 4)]))))
+            )
+        }
+
+        #[test]
+        fn many_steps() {
+            assert_eq!(
+                months_expr("1/2,FEB/3,3/4"),
+                Ok(("", Expr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))))
+            )
+        }
+
+        #[test]
+        fn range_step() {
+            assert_eq!(
+                months_expr("1-DEC/4"),
+                Ok(("", Expr::Many(exprs(vec![rs(1, 12, 4)]))))
+            )
+        }
+
+        #[test]
+        fn many_range_step() {
+            assert_eq!(
+                months_expr("1-JUN/4,JUN-12/3"),
+                Ok(("", Expr::Many(exprs(vec![rs(1, 6, 4), rs(6, 12, 3)]))))
+            )
+        }
+
+        #[test]
+        fn values_ranges_steps_and_ranges() {
+            assert_eq!(
+                months_expr("1,JAN-6/3,JUN-12,DEC/3"),
+                Ok((
+                    "",
+                    Expr::Many(exprs(vec![o(1), rs(1, 6, 3), r(6, 12), s(12, 3)]))
+                ))
+            )
+        }
+
+        #[test]
+        fn limits() {
+            assert!(matches!(months_expr("0"), Err(_)));
+            assert!(matches!(months_expr("13"), Err(_)));
+            assert!(matches!(months_expr("0-12"), Err(_)));
+            assert!(matches!(months_expr("1-13"), Err(_)));
+            // a step greater than the max value is not allowed (since it doesn't make sense)
+            assert!(matches!(months_expr("1/13"), Err(_)));
+            assert!(matches!(months_expr("1-13/2"), Err(_)));
+            assert!(matches!(months_expr("0/12"), Err(_)));
+            assert!(matches!(months_expr("0-12/2"), Err(_)));
+            // a step of 0 is not allowed (since it doesn't make sense)
+            assert!(matches!(months_expr("1/0"), Err(_)));
+            assert!(matches!(months_expr("1-12/0"), Err(_)));
         }
     }
 
-    fn rs<E>(start: u8, end: u8, step: u8) -> OrsExpr<E>
-    where
-        E: TryFrom<u8> + ExprValue,
-        E::Error: Debug,
-    {
-        let start = e(start);
-        let end = e(end);
-        let step = e(step);
-        OrsExpr::Step { start, end, step }
-    }
-
-    mod minutes {
+    mod days_of_month {
         use super::*;
 
         #[test]
         fn all() {
-            assert_eq!(minutes_expr("*"), Ok(("", Expr::All)))
+            assert_eq!(dom_expr("*"), Ok(("", DayOfMonthExpr::All)))
         }
 
         #[test]
         fn only_match_first_star() {
             // make sure we only match the first star.
             // it'll fail on the next parser
-            assert_eq!(minutes_expr("*,*"), Ok((",*", Expr::All)))
+            assert_eq!(dom_expr("*,*"), Ok((",*", DayOfMonthExpr::All)))
+        }
+
+        #[test]
+        fn ignored() {
+            assert_eq!(dom_expr("?"), Ok(("", DayOfMonthExpr::Ignored)));
+            assert_eq!(dom_expr("?,1"), Ok((",1", DayOfMonthExpr::Ignored)));
+        }
+
+        #[test]
+        fn last() {
+            assert_eq!(dom_expr("L"), Ok(("", DayOfMonthExpr::Last(Last::Day))))
+        }
+
+        #[test]
+        fn last_weekday() {
+            assert_eq!(
+                dom_expr("LW"),
+                Ok(("", DayOfMonthExpr::Last(Last::Weekday)))
+            )
+        }
+
+        #[test]
+        fn last_offset() {
+            assert_eq!(
+                dom_expr("L-3"),
+                Ok(("", DayOfMonthExpr::Last(Last::Offset(e(3)))))
+            )
+        }
+
+        // a zero offset makes no sense, should just be L
+        // a 32 offset will never execute
+        #[test]
+        fn last_offset_limit() {
+            assert!(matches!(dom_expr("L-0"), Err(_)));
+            assert!(matches!(dom_expr("L-31"), Err(_)));
+            assert!(matches!(dom_expr("L-0W"), Err(_)));
+            assert!(matches!(dom_expr("L-31W"), Err(_)));
+        }
+
+        #[test]
+        fn last_offset_weekday() {
+            assert_eq!(
+                dom_expr("L-3W"),
+                Ok(("", DayOfMonthExpr::Last(Last::OffsetWeekday(e(3)))))
+            )
+        }
+
+        #[test]
+        fn last_weekday_offset() {
+            assert_eq!(
+                dom_expr("LW-1"),
+                Ok(("", DayOfMonthExpr::Last(Last::WeekdayOffset(e(1)))))
+            );
+            assert!(matches!(dom_expr("LW-0"), Err(_)));
+            assert!(matches!(dom_expr("LW-"), Err(_)));
+        }
+
+        // last is not allowed with other expressions
+        #[test]
+        fn last_with_other_exprs() {
+            assert!(matches!(dom_expr("3,L"), Err(_)))
+        }
+
+        #[test]
+        fn closest_weekday() {
+            assert_eq!(
+                dom_expr("1W"),
+                Ok(("", DayOfMonthExpr::ClosestWeekday(e(1))))
+            )
+        }
+
+        #[test]
+        fn closest_weekday_with_other_exprs() {
+            // make sure we only match the 1W.
+            // it'll fail on the next parser
+            assert_eq!(
+                dom_expr("1W,3"),
+                Ok((",3", DayOfMonthExpr::ClosestWeekday(e(1))))
+            )
+        }
+
+        #[test]
+        fn business_day() {
+            assert_eq!(
+                dom_expr("3B"),
+                Ok(("", DayOfMonthExpr::BusinessDay(BusinessDay(3))))
+            );
+            assert!(matches!(dom_expr("24B"), Err(_)));
+        }
+
+        #[test]
+        fn specials() {
+            assert_eq!(
+                dom_expr("1W,15W"),
+                Ok((
+                    "",
+                    DayOfMonthExpr::Specials(vec![
+                        DayOfMonthSpecial::ClosestWeekday(e(1)),
+                        DayOfMonthSpecial::ClosestWeekday(e(15)),
+                    ])
+                ))
+            );
+            assert_eq!(
+                dom_expr("L,LW-1,3B"),
+                Ok((
+                    "",
+                    DayOfMonthExpr::Specials(vec![
+                        DayOfMonthSpecial::Last(Last::Day),
+                        DayOfMonthSpecial::Last(Last::WeekdayOffset(e(1))),
+                        DayOfMonthSpecial::BusinessDay(BusinessDay(3)),
+                    ])
+                ))
+            );
+            // a special expression can't be listed with plain days
+            assert_eq!(dom_expr("L,3"), Ok((",3", DayOfMonthExpr::Last(Last::Day))));
+        }
+
+        #[test]
+        fn closest_weekdays() {
+            assert_eq!(
+                dom_expr("1-7W"),
+                Ok(("", DayOfMonthExpr::ClosestWeekdays(exprs(vec![r(1, 7)]))))
+            );
+            assert_eq!(
+                dom_expr("1,15W"),
+                Ok((
+                    "",
+                    DayOfMonthExpr::ClosestWeekdays(exprs(vec![o(1), o(15)]))
+                ))
+            );
+            assert_eq!(
+                dom_expr("*/10W"),
+                Ok(("", DayOfMonthExpr::ClosestWeekdays(exprs(vec![s(1, 10)]))))
+            );
+            assert_eq!(
+                dom_expr("1-31W"),
+                Ok(("", DayOfMonthExpr::ClosestWeekdays(exprs(vec![r(1, 31)]))))
+            );
+            assert_eq!(
+                dom_expr("1W"),
+                Ok(("", DayOfMonthExpr::ClosestWeekday(e(1))))
+            );
+            // the W applies to the whole list, so it can't be followed by more days
+            assert_eq!(
+                dom_expr("1-7W,15"),
+                Ok((",15", DayOfMonthExpr::ClosestWeekdays(exprs(vec![r(1, 7)]))))
+            );
         }
 
         #[test]
         fn star_step() {
             assert_eq!(
-                minutes_expr("*/5"),
-                Ok(("", Expr::Many(exprs(vec![s(0, 5)]))))
+                dom_expr("*/3"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(1, 3)]))))
             )
         }
 
         #[test]
         fn multi_star_step() {
             assert_eq!(
-                minutes_expr("*/5,*/3"),
-                Ok(("", Expr::Many(exprs(vec![s(0, 5), s(0, 3)]))))
+                dom_expr("*/3,*/4"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(1, 3), s(1, 4)]))))
             )
         }
 
@@ -1147,21 +4831,36 @@ mod tests {
         fn star_range_doesnt_make_sense() {
             // make sure we only match the first star.
             // it'll fail on the next parser
-            assert_eq!(minutes_expr("*-30/5,*/3"), Ok(("-30/5,*/3", Expr::All)))
+            assert_eq!(dom_expr("*-6/3,*/4"), Ok(("-6/3,*/4", DayOfMonthExpr::All)))
         }
 
         #[test]
         fn one_value() {
-            assert_eq!(minutes_expr("0"), Ok(("", Expr::Many(exprs(vec![o(0)])))))
+            assert_eq!(
+                dom_expr("1"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![o(1)]))))
+            )
         }
 
         #[test]
         fn many_one_value() {
             assert_eq!(
-                minutes_expr("5,15,25,35,45,55"),
+                dom_expr("1,4,7,10,13,16,19,22,25,28,31"),
                 Ok((
                     "",
-                    Expr::Many(exprs(vec![o(5), o(15), o(25), o(35), o(45), o(55)]))
+                    DayOfMonthExpr::Many(exprs(vec![
+                        o(1),
+                        o(4),
+                        o(7),
+                        o(10),
+                        o(13),
+                        o(16),
+                        o(19),
+                        o(22),
+                        o(25),
+                        o(28),
+                        o(31),
+                    ]))
                 ))
             )
         }
@@ -1169,33 +4868,26 @@ mod tests {
         #[test]
         fn one_range() {
             assert_eq!(
-                minutes_expr("0-30"),
-                Ok(("", Expr::Many(exprs(vec![r(0, 30)]))))
+                dom_expr("1-15"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![r(1, 15)]))))
             )
         }
 
         #[test]
         fn overflow_range() {
             assert_eq!(
-                minutes_expr("50-10"),
-                Ok(("", Expr::Many(exprs(vec![r(50, 10)]))))
+                dom_expr("30-1"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![r(30, 1)]))))
             )
         }
 
         #[test]
         fn many_range() {
             assert_eq!(
-                minutes_expr("0-5,10-15,20-25,30-35,40-45,50-55"),
+                dom_expr("1-4,5-8,9-12,13-15"),
                 Ok((
                     "",
-                    Expr::Many(exprs(vec![
-                        r(0, 5),
-                        r(10, 15),
-                        r(20, 25),
-                        r(30, 35),
-                        r(40, 45),
-                        r(50, 55)
-                    ]))
+                    DayOfMonthExpr::Many(exprs(vec![r(1, 4), r(5, 8), r(9, 12), r(13, 15)]))
                 ))
             )
         }
@@ -1203,95 +4895,164 @@ mod tests {
         #[test]
         fn step() {
             assert_eq!(
-                minutes_expr("0/5"),
-                Ok(("", Expr::Many(exprs(vec![s(0, 5)]))))
+                dom_expr("1/3"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(1, 3)]))))
             )
         }
 
         #[test]
         fn step_with_star_step() {
             assert_eq!(
-                minutes_expr("1/3,*/5"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 3), s(0, 5)]))))
+                dom_expr("2/2,*/4"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(2, 2), s(1, 4)]))))
             )
         }
 
         #[test]
         fn many_steps() {
             assert_eq!(
-                minutes_expr("1/3,2/3,5/10"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 3), s(2, 3), s(5, 10)]))))
+                dom_expr("1/2,2/3,3/4"),
+                Ok((
+                    "",
+                    DayOfMonthExpr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))
+                ))
             )
         }
 
         #[test]
         fn range_step() {
             assert_eq!(
-                minutes_expr("0-30/5"),
-                Ok(("", Expr::Many(exprs(vec![rs(0, 30, 5)]))))
+                dom_expr("1-15/4"),
+                Ok(("", DayOfMonthExpr::Many(exprs(vec![rs(1, 15, 4)]))))
             )
         }
 
         #[test]
         fn many_range_step() {
             assert_eq!(
-                minutes_expr("0-30/5,30-59/3"),
-                Ok(("", Expr::Many(exprs(vec![rs(0, 30, 5), rs(30, 59, 3)]))))
+                dom_expr("1-15/3,16-31/4"),
+                Ok((
+                    "",
+                    DayOfMonthExpr::Many(exprs(vec![rs(1, 15, 3), rs(16, 31, 4)]))
+                ))
             )
         }
 
         #[test]
         fn values_ranges_steps_and_ranges() {
             assert_eq!(
-                minutes_expr("0,5-10,10-30/3,30/3"),
+                dom_expr("1,1-10/3,10-20,20/3"),
                 Ok((
                     "",
-                    Expr::Many(exprs(vec![o(0), r(5, 10), rs(10, 30, 3), s(30, 3)]))
+                    DayOfMonthExpr::Many(exprs(vec![o(1), rs(1, 10, 3), r(10, 20), s(20, 3)]))
                 ))
             )
         }
 
         #[test]
         fn limits() {
-            assert!(matches!(minutes_expr("60"), Err(_)));
-            assert!(matches!(minutes_expr("0-60"), Err(_)));
+            assert!(matches!(dom_expr("32"), Err(_)));
+            assert!(matches!(dom_expr("0-31"), Err(_)));
+            assert!(matches!(dom_expr("1-32"), Err(_)));
             // a step greater than the max value is not allowed (since it doesn't make sense)
-            assert!(matches!(minutes_expr("0/60"), Err(_)));
-            assert!(matches!(minutes_expr("0-60/5"), Err(_)));
+            assert!(matches!(dom_expr("1/32"), Err(_)));
+            assert!(matches!(dom_expr("0/31"), Err(_)));
+            assert!(matches!(dom_expr("1-31/32"), Err(_)));
+            assert!(matches!(dom_expr("0-31/32"), Err(_)));
+            assert!(matches!(dom_expr("0-32/31"), Err(_)));
             // a step of 0 is not allowed (since it doesn't make sense)
-            assert!(matches!(minutes_expr("0/0"), Err(_)));
-            assert!(matches!(minutes_expr("0-59/0"), Err(_)));
+            assert!(matches!(dom_expr("0/0"), Err(_)));
+            assert!(matches!(dom_expr("0-23/0"), Err(_)));
+        }
+    }
+
+    mod days_of_week {
+        use super::*;
+
+        #[test]
+        fn all() {
+            assert_eq!(dow_expr("*"), Ok(("", DayOfWeekExpr::All)))
+        }
+
+        #[test]
+        fn only_match_first_star() {
+            // make sure we only match the first star.
+            // it'll fail on the next parser
+            assert_eq!(dow_expr("*,*"), Ok((",*", DayOfWeekExpr::All)))
+        }
+
+        #[test]
+        fn ignored() {
+            assert_eq!(dow_expr("?"), Ok(("", DayOfWeekExpr::Ignored)));
+            assert_eq!(dow_expr("?,MON"), Ok((",MON", DayOfWeekExpr::Ignored)));
+        }
+
+        #[test]
+        fn last() {
+            assert_eq!(
+                dow_expr("L"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(7)]))))
+            )
         }
-    }
 
-    mod hours {
-        use super::*;
+        #[test]
+        fn last_day() {
+            assert_eq!(dow_expr("3L"), Ok(("", DayOfWeekExpr::Last(e(3)))))
+        }
 
+        // last is not allowed with other expressions
         #[test]
-        fn all() {
-            assert_eq!(hours_expr("*"), Ok(("", Expr::All)))
+        fn last_with_other_exprs() {
+            assert!(matches!(dow_expr("3,L"), Err(_)))
         }
 
         #[test]
-        fn only_match_first_star() {
-            // make sure we only match the first star.
-            // it'll fail on the next parser
-            assert_eq!(hours_expr("*,*"), Ok((",*", Expr::All)))
+        fn nth() {
+            assert_eq!(dow_expr("MON#1"), Ok(("", DayOfWeekExpr::Nth(e(2), e(1)))));
+            assert_eq!(dow_expr("5#4"), Ok(("", DayOfWeekExpr::Nth(e(5), e(4)))));
+        }
+
+        #[test]
+        fn specials() {
+            assert_eq!(
+                dow_expr("MON#1,MON#3"),
+                Ok((
+                    "",
+                    DayOfWeekExpr::Specials(vec![
+                        DayOfWeekSpecial::Nth(e(2), e(1)),
+                        DayOfWeekSpecial::Nth(e(2), e(3)),
+                    ])
+                ))
+            );
+            assert_eq!(
+                dow_expr("5L,FRI#1"),
+                Ok((
+                    "",
+                    DayOfWeekExpr::Specials(vec![
+                        DayOfWeekSpecial::Last(e(5)),
+                        DayOfWeekSpecial::Nth(e(6), e(1)),
+                    ])
+                ))
+            );
+            assert_eq!(
+                dow_expr("MON#1,3"),
+                Ok((",3", DayOfWeekExpr::Nth(e(2), e(1))))
+            );
         }
 
         #[test]
         fn star_step() {
             assert_eq!(
-                hours_expr("*/3"),
-                Ok(("", Expr::Many(exprs(vec![s(0, 3)]))))
+                dow_expr("*/3"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(1, 3)]))))
             )
         }
 
         #[test]
         fn multi_star_step() {
             assert_eq!(
-                hours_expr("*/3,*/4"),
-                Ok(("", Expr::Many(exprs(vec![s(0, 3), s(0, 4)]))))
+                dow_expr("*/3,*/4"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(1, 3), s(1, 4)]))))
             )
         }
 
@@ -1299,57 +5060,85 @@ mod tests {
         fn star_range_doesnt_make_sense() {
             // make sure we only match the first star.
             // it'll fail on the next parser
-            assert_eq!(hours_expr("*-6/3,*/4"), Ok(("-6/3,*/4", Expr::All)))
+            assert_eq!(dow_expr("*-6/3,*/4"), Ok(("-6/3,*/4", DayOfWeekExpr::All)))
         }
 
         #[test]
         fn one_value() {
-            assert_eq!(hours_expr("0"), Ok(("", Expr::Many(exprs(vec![o(0)])))))
+            assert_eq!(
+                dow_expr("1"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(1)]))))
+            )
+        }
+
+        #[test]
+        fn word_values() {
+            // caps
+            assert_eq!(
+                dow_expr("SUN"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(1)]))))
+            );
+            assert_eq!(
+                dow_expr("MON"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(2)]))))
+            );
+            assert_eq!(
+                dow_expr("TUE"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(3)]))))
+            );
+
+            // lower
+            assert_eq!(
+                dow_expr("WED"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(4)]))))
+            );
+            assert_eq!(
+                dow_expr("THU"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(5)]))))
+            );
+            assert_eq!(
+                dow_expr("FRI"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(6)]))))
+            );
+
+            // mixed
+            assert_eq!(
+                dow_expr("SaT"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(7)]))))
+            );
         }
 
         #[test]
         fn many_one_value() {
             assert_eq!(
-                hours_expr("0,3,6,9,12,15,18,21"),
-                Ok((
-                    "",
-                    Expr::Many(exprs(vec![
-                        o(0),
-                        o(3),
-                        o(6),
-                        o(9),
-                        o(12),
-                        o(15),
-                        o(18),
-                        o(21)
-                    ]))
-                ))
+                dow_expr("2,WED,FRI,7"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(2), o(4), o(6), o(7)]))))
             )
         }
 
         #[test]
         fn one_range() {
             assert_eq!(
-                hours_expr("0-12"),
-                Ok(("", Expr::Many(exprs(vec![r(0, 12)]))))
+                dow_expr("MON-5"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![r(2, 5)]))))
             )
         }
 
         #[test]
         fn overflow_range() {
             assert_eq!(
-                hours_expr("22-2"),
-                Ok(("", Expr::Many(exprs(vec![r(22, 2)]))))
+                dow_expr("7-1"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![r(7, 1)]))))
             )
         }
 
         #[test]
         fn many_range() {
             assert_eq!(
-                hours_expr("0-3,6-9,12-15,18-21"),
+                dow_expr("1-3,4-4,5-7"),
                 Ok((
                     "",
-                    Expr::Many(exprs(vec![r(0, 3), r(6, 9), r(12, 15), r(18, 21)]))
+                    DayOfWeekExpr::Many(exprs(vec![r(1, 3), r(4, 4), r(5, 7)]))
                 ))
             )
         }
@@ -1357,691 +5146,1291 @@ mod tests {
         #[test]
         fn step() {
             assert_eq!(
-                hours_expr("0/3"),
-                Ok(("", Expr::Many(exprs(vec![s(0, 3)]))))
+                dow_expr("2/2"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(2, 2)]))))
             )
         }
 
         #[test]
         fn step_with_star_step() {
             assert_eq!(
-                hours_expr("1/2,*/4"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 2), s(0, 4)]))))
+                dow_expr("2/2,*/4"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(2, 2), s(1, 4)]))))
             )
         }
 
         #[test]
         fn many_steps() {
             assert_eq!(
-                hours_expr("1/2,2/3,3/4"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))))
+                dow_expr("1/2,2/3,3/4"),
+                Ok((
+                    "",
+                    DayOfWeekExpr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))
+                ))
             )
         }
 
         #[test]
         fn range_step() {
             assert_eq!(
-                hours_expr("0-12/4"),
-                Ok(("", Expr::Many(exprs(vec![rs(0, 12, 4)]))))
+                dow_expr("2-5/2"),
+                Ok(("", DayOfWeekExpr::Many(exprs(vec![rs(2, 5, 2)]))))
             )
         }
 
         #[test]
         fn many_range_step() {
             assert_eq!(
-                hours_expr("0-12/4,12-23/3"),
-                Ok(("", Expr::Many(exprs(vec![rs(0, 12, 4), rs(12, 23, 3)]))))
+                dow_expr("1-4/2,5-7/2"),
+                Ok((
+                    "",
+                    DayOfWeekExpr::Many(exprs(vec![rs(1, 4, 2), rs(5, 7, 2)]))
+                ))
             )
         }
 
         #[test]
         fn values_ranges_steps_and_ranges() {
             assert_eq!(
-                hours_expr("0,0-6/3,6-12,12/3"),
+                dow_expr("1,2-FRI/2,6-7,3/3"),
                 Ok((
                     "",
-                    Expr::Many(exprs(vec![o(0), rs(0, 6, 3), r(6, 12), s(12, 3)]))
+                    DayOfWeekExpr::Many(exprs(vec![o(1), rs(2, 6, 2), r(6, 7), s(3, 3)]))
                 ))
             )
         }
 
         #[test]
         fn limits() {
-            assert!(matches!(hours_expr("24"), Err(_)));
-            assert!(matches!(hours_expr("0-24"), Err(_)));
+            assert!(matches!(dow_expr("8"), Err(_)));
+            assert!(matches!(dow_expr("0"), Err(_)));
+            assert!(matches!(dow_expr("0-7"), Err(_)));
+            assert!(matches!(dow_expr("1-8"), Err(_)));
             // a step greater than the max value is not allowed (since it doesn't make sense)
-            assert!(matches!(hours_expr("0/24"), Err(_)));
-            assert!(matches!(hours_expr("0-24/2"), Err(_)));
+            assert!(matches!(dow_expr("1/8"), Err(_)));
+            assert!(matches!(dow_expr("0/7"), Err(_)));
+            assert!(matches!(dow_expr("1-7/8"), Err(_)));
+            assert!(matches!(dow_expr("0-7/7"), Err(_)));
+            assert!(matches!(dow_expr("0-8/7"), Err(_)));
             // a step of 0 is not allowed (since it doesn't make sense)
-            assert!(matches!(hours_expr("0/0"), Err(_)));
-            assert!(matches!(hours_expr("0-23/0"), Err(_)));
+            assert!(matches!(dow_expr("1/0"), Err(_)));
+            assert!(matches!(dow_expr("1-5/0"), Err(_)));
+            // 0th day doesn't make sense
+            assert!(matches!(dow_expr("SUN#0"), Err(_)));
+            // 6th day of the month will never happen
+            assert!(matches!(dow_expr("MON#6"), Err(_)));
         }
     }
 
-    mod months {
+    mod display {
         use super::*;
 
-        #[test]
-        fn all() {
-            assert_eq!(months_expr("*"), Ok(("", Expr::All)))
+        #[cfg(not(feature = "std"))]
+        use alloc::string::ToString;
+
+        #[track_caller]
+        fn assert_round_trip(cron: &str, expected: &str) {
+            let expr: CronExpr = cron.parse().expect("Valid cron expression");
+            let string = expr.to_string();
+            assert_eq!(string, expected);
+
+            let reparsed: CronExpr = string.parse().expect("Display output should parse");
+            assert_eq!(reparsed, expr);
         }
 
         #[test]
-        fn only_match_first_star() {
-            // make sure we only match the first star.
-            // it'll fail on the next parser
-            assert_eq!(months_expr("*,*"), Ok((",*", Expr::All)))
+        fn stars() {
+            assert_round_trip("* * * * *", "* * * * *");
         }
 
         #[test]
-        fn star_step() {
-            assert_eq!(
-                months_expr("*/3"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 3)]))))
-            )
+        fn values_ranges_and_steps() {
+            assert_round_trip("0,5-10,10-30/3,30/3 * * * *", "0,5-10,10-30/3,30/3 * * * *");
+            assert_round_trip("*/5 */2 */3 */4 */2", "0/5 0/2 1/3 1/4 1/2");
+            assert_round_trip(
+                "50-10 20-4/2 31-1 12-1 SAT-SUN",
+                "50-10 20-4/2 31-1 12-1 7-1",
+            );
         }
 
         #[test]
-        fn multi_star_step() {
-            assert_eq!(
-                months_expr("*/3,*/4"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 3), s(1, 4)]))))
-            )
+        fn names_are_written_as_numbers() {
+            assert_round_trip("0 0 * JAN,JUN-AUG MON-FRI", "0 0 * 1,6-8 2-6");
         }
 
         #[test]
-        fn star_range_doesnt_make_sense() {
-            // make sure we only match the first star.
-            // it'll fail on the next parser
-            assert_eq!(months_expr("*-6/3,*/4"), Ok(("-6/3,*/4", Expr::All)))
+        fn specials() {
+            assert_round_trip("0 0 L * *", "0 0 L * *");
+            assert_round_trip("0 0 LW * *", "0 0 LW * *");
+            assert_round_trip("0 0 L-5 * *", "0 0 L-5 * *");
+            assert_round_trip("0 0 L-5W * *", "0 0 L-5W * *");
+            assert_round_trip("0 0 15W * *", "0 0 15W * *");
+            assert_round_trip("0 0 ? * MON", "0 0 ? * 2");
+            assert_round_trip("0 0 15 * ?", "0 0 15 * ?");
+            assert_round_trip("0 0 * * FRIL", "0 0 * * 6L");
+            assert_round_trip("0 0 * * MON#3", "0 0 * * 2#3");
+            assert_round_trip("0 0 * * L", "0 0 * * 7");
+        }
+    }
+
+    mod errors {
+        use super::*;
+
+        #[track_caller]
+        fn assert_err(cron: &str, expected: CronParseError) {
+            assert_eq!(cron.parse::<CronExpr>(), Err(expected));
         }
 
         #[test]
-        fn one_value() {
-            assert_eq!(months_expr("1"), Ok(("", Expr::Many(exprs(vec![o(1)])))))
+        fn values_out_of_range() {
+            assert_err(
+                "60 * * * *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::Minutes,
+                    value: 60,
+                    min: 0,
+                    max: 59,
+                    at: 0,
+                },
+            );
+            assert_err(
+                "0 0-24 * * *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::Hours,
+                    value: 24,
+                    min: 0,
+                    max: 23,
+                    at: 4,
+                },
+            );
+            assert_err(
+                "0 0 0 * *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfMonth,
+                    value: 0,
+                    min: 1,
+                    max: 31,
+                    at: 4,
+                },
+            );
+            assert_err(
+                "0 0 24B * *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfMonth,
+                    value: 24,
+                    min: 1,
+                    max: 23,
+                    at: 4,
+                },
+            );
+            assert_err(
+                "0 0 * 13 *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::Months,
+                    value: 13,
+                    min: 1,
+                    max: 12,
+                    at: 6,
+                },
+            );
+            assert_err(
+                "0 0 * * 99999999999",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfWeek,
+                    value: u32::MAX,
+                    min: 1,
+                    max: 7,
+                    at: 8,
+                },
+            );
         }
 
         #[test]
-        fn word_values() {
-            // caps
-            assert_eq!(months_expr("JAN"), Ok(("", Expr::Many(exprs(vec![o(1)])))));
-            assert_eq!(months_expr("FEB"), Ok(("", Expr::Many(exprs(vec![o(2)])))));
-            assert_eq!(months_expr("MAR"), Ok(("", Expr::Many(exprs(vec![o(3)])))));
-            assert_eq!(months_expr("APR"), Ok(("", Expr::Many(exprs(vec![o(4)])))));
+        fn steps_offsets_and_nths_out_of_range() {
+            assert_err(
+                "*/60 * * * *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::Minutes,
+                    value: 60,
+                    min: 1,
+                    max: 59,
+                    at: 2,
+                },
+            );
+            assert_err(
+                "0 0 */31 * *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfMonth,
+                    value: 31,
+                    min: 1,
+                    max: 30,
+                    at: 6,
+                },
+            );
+            assert_err(
+                "0 0 L-31 * *",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfMonth,
+                    value: 31,
+                    min: 1,
+                    max: 30,
+                    at: 6,
+                },
+            );
+            assert_err(
+                "0 0 * * MON#6",
+                CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfWeek,
+                    value: 6,
+                    min: 1,
+                    max: 5,
+                    at: 12,
+                },
+            );
+        }
 
-            // lower
-            assert_eq!(months_expr("may"), Ok(("", Expr::Many(exprs(vec![o(5)])))));
-            assert_eq!(months_expr("jun"), Ok(("", Expr::Many(exprs(vec![o(6)])))));
-            assert_eq!(months_expr("jul"), Ok(("", Expr::Many(exprs(vec![o(7)])))));
-            assert_eq!(months_expr("aug"), Ok(("", Expr::Many(exprs(vec![o(8)])))));
+        #[test]
+        fn invalid_fields() {
+            assert_err(
+                "*,* * * * *",
+                CronParseError::InvalidField {
+                    field: FieldKind::Minutes,
+                    at: 1,
+                },
+            );
+            assert_err(
+                "0 0 * FOO *",
+                CronParseError::InvalidField {
+                    field: FieldKind::Months,
+                    at: 6,
+                },
+            );
+            assert_err(
+                "0 0 * * MON,",
+                CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 12,
+                },
+            );
+            assert_err(
+                " * * * * *",
+                CronParseError::InvalidField {
+                    field: FieldKind::Minutes,
+                    at: 0,
+                },
+            );
+            assert_err(
+                "0 0 ? * ?",
+                CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 8,
+                },
+            );
+            assert_err(
+                "0 0 ?,1 * *",
+                CronParseError::InvalidField {
+                    field: FieldKind::DaysOfMonth,
+                    at: 5,
+                },
+            );
+        }
 
-            // mixed
-            assert_eq!(months_expr("sEp"), Ok(("", Expr::Many(exprs(vec![o(9)])))));
-            assert_eq!(months_expr("ocT"), Ok(("", Expr::Many(exprs(vec![o(10)])))));
-            assert_eq!(months_expr("NOv"), Ok(("", Expr::Many(exprs(vec![o(11)])))));
-            assert_eq!(months_expr("Dec"), Ok(("", Expr::Many(exprs(vec![o(12)])))));
+        #[test]
+        fn missing_fields() {
+            assert_err(
+                "",
+                CronParseError::MissingField {
+                    field: FieldKind::Minutes,
+                    at: 0,
+                },
+            );
+            assert_err(
+                "* * * *",
+                CronParseError::MissingField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 7,
+                },
+            );
+            assert_err(
+                "* * ",
+                CronParseError::MissingField {
+                    field: FieldKind::DaysOfMonth,
+                    at: 4,
+                },
+            );
         }
 
         #[test]
-        fn many_one_value() {
+        fn trailing_input() {
+            assert_err("* * * * * *", CronParseError::TrailingInput { at: 9 });
+            assert_err(
+                "* * * * *x",
+                CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 9,
+                },
+            );
+            assert_err("* * * * *\t", CronParseError::TrailingInput { at: 9 });
             assert_eq!(
-                months_expr("1,MAR,6,SEP,12"),
-                Ok(("", Expr::Many(exprs(vec![o(1), o(3), o(6), o(9), o(12)]))))
-            )
+                CronExpr::from_str_with("* * * * * \t", Dialect::Extended),
+                Err(CronParseError::TrailingInput { at: 9 })
+            );
         }
 
-        #[test]
-        fn one_range() {
+        #[test]
+        fn trailing_comments() {
+            let mut options = ParseOptions::new();
+            options.allow_trailing_comment = true;
+            let parse = |s| CronExpr::from_str_with_comment(s, options);
+            let expected: CronExpr = "0 9 * * MON-FRI".parse().unwrap();
+
+            assert_eq!(parse("0 9 * * MON-FRI"), Ok((expected.clone(), None)));
+            assert_eq!(parse("0 9 * * MON-FRI\n"), Ok((expected.clone(), None)));
+            assert_eq!(
+                parse("0 9 * * MON-FRI \t\r\n"),
+                Ok((expected.clone(), None))
+            );
+            assert_eq!(
+                parse("0 9 * * MON-FRI # weekdays\n"),
+                Ok((expected.clone(), Some("weekdays")))
+            );
+            assert_eq!(
+                parse("0 9 * * MON-FRI\t#weekdays # at 9"),
+                Ok((expected, Some("weekdays # at 9")))
+            );
+            assert_eq!(
+                parse("0 0 ? * MON#2 #"),
+                Ok(("0 0 ? * MON#2".parse().unwrap(), Some("")))
+            );
+            assert_eq!(
+                parse("0 0 ? * MON#2#x"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 13
+                })
+            );
+            assert_eq!(
+                parse("# 0 0 * * *"),
+                Err(CronParseError::MissingField {
+                    field: FieldKind::Minutes,
+                    at: 0
+                })
+            );
+
+            options.trim_whitespace = true;
             assert_eq!(
-                months_expr("1-12"),
-                Ok(("", Expr::Many(exprs(vec![r(1, 12)]))))
+                CronExpr::from_str_with_options("  @daily # every day", options),
+                Ok("@daily".parse().unwrap())
             );
+
+            // comments are an error without the option
             assert_eq!(
-                months_expr("JAN-DEC"),
-                Ok(("", Expr::Many(exprs(vec![r(1, 12)]))))
-            )
+                CronExpr::from_str_with_options("0 0 * * * # daily", ParseOptions::new()),
+                Err(CronParseError::TrailingInput { at: 9 })
+            );
         }
 
         #[test]
-        fn overflow_range() {
+        fn whitespace() {
+            let expected: CronExpr = "0 9 * * MON-FRI".parse().unwrap();
+            assert_eq!("0\t9\t*\t*\tMON-FRI".parse(), Ok(expected.clone()));
+            assert_eq!("0 \t 9  *\t\t* MON-FRI".parse(), Ok(expected.clone()));
             assert_eq!(
-                months_expr("11-FEB"),
-                Ok(("", Expr::Many(exprs(vec![r(11, 2)]))))
+                CronExpr::from_str_with("0\t9\t*\t*\tMON-FRI\tW1", Dialect::Extended),
+                CronExpr::from_str_with("0 9 * * MON-FRI W1", Dialect::Extended)
             );
+
+            let mut options = ParseOptions::new();
             assert_eq!(
-                months_expr("NOV-2"),
-                Ok(("", Expr::Many(exprs(vec![r(11, 2)]))))
-            )
-        }
+                CronExpr::from_str_with_options(" 0 9 * * MON-FRI", options),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Minutes,
+                    at: 0
+                })
+            );
 
-        #[test]
-        fn many_range() {
+            options.trim_whitespace = true;
+            for &text in &[
+                " 0 9 * * MON-FRI",
+                "0 9 * * MON-FRI\t",
+                "\t \t0\t9 * * MON-FRI  ",
+            ] {
+                assert_eq!(
+                    CronExpr::from_str_with_options(text, options),
+                    Ok(expected.clone()),
+                    "{:?}",
+                    text
+                );
+            }
             assert_eq!(
-                months_expr("1-MAR,MAY-7,SEP-11"),
-                Ok(("", Expr::Many(exprs(vec![r(1, 3), r(5, 7), r(9, 11)]))))
-            )
+                CronExpr::from_str_with_options(" \t@daily ", options),
+                Ok("@daily".parse().unwrap())
+            );
+            assert_eq!(
+                CronExpr::from_str_with_options("\t\t0 0 * *  ", options),
+                Err(CronParseError::MissingField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 9
+                })
+            );
+            assert_eq!(
+                CronExpr::from_str_with_options("   ", options),
+                Err(CronParseError::MissingField {
+                    field: FieldKind::Minutes,
+                    at: 3
+                })
+            );
         }
 
         #[test]
-        fn step() {
+        fn limits() {
+            use crate::Cron;
+
+            #[cfg(not(feature = "std"))]
+            use alloc::string::ToString;
+
+            // every minute written out is well within the limits
+            let minutes = (0..60u8)
+                .map(|minute| minute.to_string())
+                .collect::<Vec<_>>();
+            let mut every_minute = minutes.join(",");
+            every_minute.push_str(" * * * *");
             assert_eq!(
-                months_expr("1/3"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 3)]))))
-            )
-        }
+                every_minute.parse::<CronExpr>().map(Cron::new),
+                "* * * * *".parse::<Cron>()
+            );
 
-        #[test]
-        fn step_with_star_step() {
+            // the 256th comma starts the 257th term
+            let mut many_terms = "0,".repeat(300);
+            many_terms.push_str("0 * * * *");
+            let too_many = CronParseError::TooManyTerms {
+                field: FieldKind::Minutes,
+                max: 256,
+                at: 511,
+            };
+            assert_err(&many_terms, too_many.clone());
+            assert_eq!(many_terms.parse::<Cron>(), Err(too_many.clone()));
+            assert_eq!(CronExpr::parse_lenient(&many_terms).1, [too_many]);
+
+            let long = "0 0 * * *".to_string() + &" ".repeat(1024);
+            assert_err(
+                &long,
+                CronParseError::TooLong {
+                    len: 1033,
+                    max: 1024,
+                },
+            );
+
+            let mut options = ParseOptions::new();
+            options.dialect = Dialect::Extended;
+            options.trim_whitespace = true;
+            options.max_len = 20;
+            options.max_terms = 2;
+            assert!(CronExpr::from_str_with_options("  0 0 1,15 * ?  ", options).is_ok());
             assert_eq!(
-                months_expr("2/2,*/4"),
-                Ok(("", Expr::Many(exprs(vec![s(2, 2), s(1, 4)]))))
-            )
+                CronExpr::from_str_with_options("  0 0 * * MON,WED,FRI", options),
+                Err(CronParseError::TooManyTerms {
+                    field: FieldKind::DaysOfWeek,
+                    max: 2,
+                    at: 17,
+                })
+            );
+            assert_eq!(
+                CronExpr::from_str_with_options("0 0 * * ? W1,2!1,3", options),
+                Err(CronParseError::TooManyTerms {
+                    field: FieldKind::Weeks,
+                    max: 2,
+                    at: 16,
+                })
+            );
+            assert_eq!(
+                CronExpr::from_str_with_options("0 0 * * MON-FRI W1-26", options),
+                Err(CronParseError::TooLong { len: 21, max: 20 })
+            );
         }
+    }
+
+    mod lenient {
+        use super::*;
 
         #[test]
-        fn many_steps() {
+        fn reports_every_field() {
+            let (expr, errors) = CronExpr::parse_lenient("60 0-24 L-31 FOO MON#6");
+            assert_eq!(expr, None);
             assert_eq!(
-                months_expr("1/2,FEB/3,3/4"),
-                Ok(("", Expr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))))
-            )
-        }
+                errors.iter().map(|err| err.field()).collect::<Vec<_>>(),
+                [
+                    Some(FieldKind::Minutes),
+                    Some(FieldKind::Hours),
+                    Some(FieldKind::DaysOfMonth),
+                    Some(FieldKind::Months),
+                    Some(FieldKind::DaysOfWeek),
+                ]
+            );
 
-        #[test]
-        fn range_step() {
+            let (_, errors) = CronExpr::parse_lenient("*x * ? * ? extra");
             assert_eq!(
-                months_expr("1-DEC/4"),
-                Ok(("", Expr::Many(exprs(vec![rs(1, 12, 4)]))))
-            )
-        }
+                errors,
+                [
+                    CronParseError::InvalidField {
+                        field: FieldKind::Minutes,
+                        at: 1
+                    },
+                    CronParseError::InvalidField {
+                        field: FieldKind::DaysOfWeek,
+                        at: 9
+                    },
+                    CronParseError::TrailingInput { at: 10 },
+                ]
+            );
 
-        #[test]
-        fn many_range_step() {
+            let (_, errors) = CronExpr::parse_lenient("60 *");
+            assert_eq!(errors.len(), 2);
             assert_eq!(
-                months_expr("1-JUN/4,JUN-12/3"),
-                Ok(("", Expr::Many(exprs(vec![rs(1, 6, 4), rs(6, 12, 3)]))))
-            )
+                errors[1],
+                CronParseError::MissingField {
+                    field: FieldKind::DaysOfMonth,
+                    at: 4
+                }
+            );
         }
 
         #[test]
-        fn values_ranges_steps_and_ranges() {
-            assert_eq!(
-                months_expr("1,JAN-6/3,JUN-12,DEC/3"),
-                Ok((
-                    "",
-                    Expr::Many(exprs(vec![o(1), rs(1, 6, 3), r(6, 12), s(12, 3)]))
-                ))
-            )
+        fn first_error_is_strict_error() {
+            for &(cron, dialect) in &[
+                ("60 * * * *", Dialect::Quartz),
+                ("0 0 * * 99999999999", Dialect::Quartz),
+                ("0 0 */31 * *", Dialect::Quartz),
+                ("*,* * * * *", Dialect::Quartz),
+                ("0 0 * * MON,", Dialect::Quartz),
+                (" * * * * *", Dialect::Quartz),
+                ("0 0 ? * ?", Dialect::Quartz),
+                ("0 0 ?,1 * *", Dialect::Quartz),
+                ("", Dialect::Quartz),
+                ("* * * *", Dialect::Quartz),
+                ("* * ", Dialect::Quartz),
+                ("* * * * * *", Dialect::Quartz),
+                ("* * * * *x", Dialect::Quartz),
+                ("@fortnightly", Dialect::Quartz),
+                ("0 9 * * 8", Dialect::Unix),
+                ("* * * * * W54", Dialect::Extended),
+                ("* * * * * W5x", Dialect::Extended),
+                ("* * * * * W5 ", Dialect::Extended),
+                ("0 0 LY JAN ?", Dialect::Extended),
+                ("0 0 LY * ?", Dialect::Quartz),
+            ] {
+                let strict = CronExpr::from_str_with(cron, dialect).unwrap_err();
+                let (expr, errors) = CronExpr::parse_lenient_with(cron, dialect);
+                assert_eq!(expr, None, "{:?}", cron);
+                assert_eq!(errors.first(), Some(&strict), "{:?}", cron);
+            }
         }
 
         #[test]
-        fn limits() {
-            assert!(matches!(months_expr("0"), Err(_)));
-            assert!(matches!(months_expr("13"), Err(_)));
-            assert!(matches!(months_expr("0-12"), Err(_)));
-            assert!(matches!(months_expr("1-13"), Err(_)));
-            // a step greater than the max value is not allowed (since it doesn't make sense)
-            assert!(matches!(months_expr("1/13"), Err(_)));
-            assert!(matches!(months_expr("1-13/2"), Err(_)));
-            assert!(matches!(months_expr("0/12"), Err(_)));
-            assert!(matches!(months_expr("0-12/2"), Err(_)));
-            // a step of 0 is not allowed (since it doesn't make sense)
-            assert!(matches!(months_expr("1/0"), Err(_)));
-            assert!(matches!(months_expr("1-12/0"), Err(_)));
+        fn valid_exprs() {
+            for &(cron, dialect) in &[
+                ("0 0 1W,L * ?", Dialect::Quartz),
+                ("@daily", Dialect::Quartz),
+                ("*/5\t9-17  * * 1-5", Dialect::Unix),
+                ("0 9 * * MON W/2", Dialect::Extended),
+                ("0 0 LY * ?", Dialect::Extended),
+            ] {
+                let (expr, errors) = CronExpr::parse_lenient_with(cron, dialect);
+                assert_eq!(
+                    expr,
+                    CronExpr::from_str_with(cron, dialect).ok(),
+                    "{:?}",
+                    cron
+                );
+                assert!(expr.is_some() && errors.is_empty(), "{:?}", cron);
+            }
         }
     }
 
-    mod days_of_month {
+    mod shortcuts {
         use super::*;
 
-        #[test]
-        fn all() {
-            assert_eq!(dom_expr("*"), Ok(("", DayOfMonthExpr::All)))
-        }
+        #[cfg(not(feature = "std"))]
+        use alloc::string::ToString;
 
-        #[test]
-        fn only_match_first_star() {
-            // make sure we only match the first star.
-            // it'll fail on the next parser
-            assert_eq!(dom_expr("*,*"), Ok((",*", DayOfMonthExpr::All)))
-        }
+        #[track_caller]
+        fn assert_shortcut(shortcut: &str, equivalent: &str) {
+            let expr: CronExpr = shortcut.parse().expect("Valid shortcut");
+            let mut expected: CronExpr = equivalent.parse().expect("Valid cron expression");
+            expected.shortcut = expr.shortcut;
 
-        #[test]
-        fn last() {
-            assert_eq!(dom_expr("L"), Ok(("", DayOfMonthExpr::Last(Last::Day))))
+            assert!(expr.shortcut.is_some());
+            assert_eq!(expr, expected);
+            assert_eq!(expr.to_string(), shortcut.to_lowercase());
         }
 
         #[test]
-        fn last_weekday() {
-            assert_eq!(
-                dom_expr("LW"),
-                Ok(("", DayOfMonthExpr::Last(Last::Weekday)))
-            )
+        fn equivalents() {
+            assert_shortcut("@hourly", "0 * * * *");
+            assert_shortcut("@daily", "0 0 * * *");
+            assert_shortcut("@weekly", "0 0 * * SUN");
+            assert_shortcut("@monthly", "0 0 1 * *");
+            assert_shortcut("@yearly", "0 0 1 JAN *");
+            assert_shortcut("@annually", "0 0 1 JAN *");
+            assert_shortcut("@DAILY", "0 0 * * *");
         }
 
         #[test]
-        fn last_offset() {
+        fn unknown() {
             assert_eq!(
-                dom_expr("L-3"),
-                Ok(("", DayOfMonthExpr::Last(Last::Offset(e(3)))))
-            )
-        }
-
-        // a zero offset makes no sense, should just be L
-        // a 32 offset will never execute
-        #[test]
-        fn last_offset_limit() {
-            assert!(matches!(dom_expr("L-0"), Err(_)));
-            assert!(matches!(dom_expr("L-31"), Err(_)));
-            assert!(matches!(dom_expr("L-0W"), Err(_)));
-            assert!(matches!(dom_expr("L-31W"), Err(_)));
-        }
-
-        #[test]
-        fn last_offset_weekday() {
+                "@reboot".parse::<CronExpr>(),
+                Err(CronParseError::UnknownShortcut)
+            );
             assert_eq!(
-                dom_expr("L-3W"),
-                Ok(("", DayOfMonthExpr::Last(Last::OffsetWeekday(e(3)))))
-            )
+                "@".parse::<CronExpr>(),
+                Err(CronParseError::UnknownShortcut)
+            );
+            assert_eq!(
+                "@daily ".parse::<CronExpr>(),
+                Err(CronParseError::UnknownShortcut)
+            );
         }
+    }
 
-        // last is not allowed with other expressions
-        #[test]
-        fn last_with_other_exprs() {
-            assert!(matches!(dom_expr("3,L"), Err(_)))
-        }
+    mod normalize {
+        use super::*;
 
-        #[test]
-        fn closest_weekday() {
-            assert_eq!(
-                dom_expr("1W"),
-                Ok(("", DayOfMonthExpr::ClosestWeekday(e(1))))
-            )
-        }
+        #[cfg(not(feature = "std"))]
+        use alloc::string::ToString;
 
-        #[test]
-        fn closest_weekday_with_other_exprs() {
-            // make sure we only match the 1W.
-            // it'll fail on the next parser
-            assert_eq!(
-                dom_expr("1W,3"),
-                Ok((",3", DayOfMonthExpr::ClosestWeekday(e(1))))
-            )
+        #[track_caller]
+        fn assert_normalized(expr: &str, normalized: &str) {
+            let expr: CronExpr = expr.parse().expect("Valid cron expression");
+            let expected: CronExpr = normalized.parse().expect("Valid cron expression");
+            assert_eq!(expr.normalize(), expected);
+            assert_eq!(expr.normalize().normalize(), expected);
         }
 
         #[test]
-        fn star_step() {
-            assert_eq!(
-                dom_expr("*/3"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(1, 3)]))))
-            )
+        fn merges_values() {
+            assert_normalized("1-1 * * * *", "1 * * * *");
+            assert_normalized("5,1-3,2,3 * * * *", "1-3,5 * * * *");
+            assert_normalized("0-10,5-20,30 * * * *", "0-20,30 * * * *");
+            assert_normalized("1,2 * * * *", "1-2 * * * *");
+            assert_normalized("* 0-5/2,8 * * *", "* 0,2,4,8 * * *");
+            assert_normalized("* * * JAN,FEB-MAR,DEC *", "* * * 1-3,12 *");
         }
 
         #[test]
-        fn multi_star_step() {
-            assert_eq!(
-                dom_expr("*/3,*/4"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(1, 3), s(1, 4)]))))
-            )
+        fn wraps_ranges() {
+            assert_normalized("58-1 * * * *", "0-1,58-59 * * * *");
+            assert_normalized("* 22-2/2 * * *", "* 0,2,22 * * *");
+            assert_normalized("* * * * SAT-MON", "* * * * SUN-MON,SAT");
         }
 
         #[test]
-        fn star_range_doesnt_make_sense() {
-            // make sure we only match the first star.
-            // it'll fail on the next parser
-            assert_eq!(dom_expr("*-6/3,*/4"), Ok(("-6/3,*/4", DayOfMonthExpr::All)))
+        fn steps() {
+            assert_normalized("0,15,30,45 * * * *", "*/15 * * * *");
+            assert_normalized("0-45/15 * * * *", "*/15 * * * *");
+            assert_normalized("5,25,45 * * * *", "5/20 * * * *");
+            assert_normalized("10,20,30 * * * *", "10-30/10 * * * *");
+            assert_normalized("*/30 * * * *", "0,30 * * * *");
+            assert_normalized("* * */5 * *", "* * */5 * *");
         }
 
         #[test]
-        fn one_value() {
-            assert_eq!(
-                dom_expr("1"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![o(1)]))))
-            )
+        fn full_coverage() {
+            assert_normalized("0-59 0-11,12-23 * 1-12 *", "* * * * *");
+            assert_normalized("*/1 * * * *", "* * * * *");
+            assert_normalized("* * 1-31 * *", "* * * * *");
+            assert_normalized("* * * * SUN-SAT", "* * * * *");
+            assert_normalized("* * ? * SUN-SAT", "* * ? * *");
+            assert_normalized("* * 1-31 * MON", "* * 1-31 * MON");
+            assert_normalized("* * 15 * 1-7", "* * 15 * 1-7");
+            assert_normalized("* * 1-31 * 1-7", "* * * * *");
         }
 
         #[test]
-        fn many_one_value() {
-            assert_eq!(
-                dom_expr("1,4,7,10,13,16,19,22,25,28,31"),
-                Ok((
-                    "",
-                    DayOfMonthExpr::Many(exprs(vec![
-                        o(1),
-                        o(4),
-                        o(7),
-                        o(10),
-                        o(13),
-                        o(16),
-                        o(19),
-                        o(22),
-                        o(25),
-                        o(28),
-                        o(31),
-                    ]))
-                ))
-            )
+        fn keeps_special_days() {
+            assert_normalized("0 0 L-2 * *", "0 0 L-2 * *");
+            assert_normalized("0 0 15W * *", "0 0 15W * *");
+            assert_normalized("0 0 7,1-6W * *", "0 0 1-7W * *");
+            assert_normalized("0 0 1-31W * *", "0 0 1-31W * *");
+            assert_normalized("0 0 * * FRI#2", "0 0 * * FRI#2");
+            assert_normalized("0 0 ? * 5L", "0 0 ? * 5L");
         }
 
         #[test]
-        fn one_range() {
+        fn removes_shortcuts() {
+            assert_normalized("@daily", "0 0 * * *");
             assert_eq!(
-                dom_expr("1-15"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![r(1, 15)]))))
-            )
+                "@weekly"
+                    .parse::<CronExpr>()
+                    .unwrap()
+                    .normalize()
+                    .to_string(),
+                "0 0 * * 1"
+            );
         }
+    }
 
-        #[test]
-        fn overflow_range() {
-            assert_eq!(
-                dom_expr("30-1"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![r(30, 1)]))))
-            )
-        }
+    mod field_view {
+        use super::*;
 
-        #[test]
-        fn many_range() {
-            assert_eq!(
-                dom_expr("1-4,5-8,9-12,13-15"),
-                Ok((
-                    "",
-                    DayOfMonthExpr::Many(exprs(vec![r(1, 4), r(5, 8), r(9, 12), r(13, 15)]))
-                ))
-            )
+        #[track_caller]
+        fn field(expr: &str, kind: FieldKind) -> FieldView {
+            CronExpr::from_str_with(expr, Dialect::Extended)
+                .expect("Valid cron expression")
+                .field(kind)
         }
 
-        #[test]
-        fn step() {
-            assert_eq!(
-                dom_expr("1/3"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(1, 3)]))))
-            )
+        #[track_caller]
+        fn values(expr: &str, kind: FieldKind) -> Vec<u8> {
+            field(expr, kind)
+                .values()
+                .expect("Field matches a set of values")
+                .collect()
         }
 
         #[test]
-        fn step_with_star_step() {
+        fn all() {
+            for &kind in &FieldKind::ALL {
+                assert!(field("* * * * *", kind).is_all(), "{}", kind);
+            }
             assert_eq!(
-                dom_expr("2/2,*/4"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![s(2, 2), s(1, 4)]))))
-            )
-        }
-
-        #[test]
-        fn many_steps() {
+                values("* * * * *", FieldKind::Hours),
+                (0..=23).collect::<Vec<_>>()
+            );
             assert_eq!(
-                dom_expr("1/2,2/3,3/4"),
-                Ok((
-                    "",
-                    DayOfMonthExpr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))
-                ))
-            )
-        }
-
-        #[test]
-        fn range_step() {
+                values("* * * * *", FieldKind::DaysOfWeek),
+                [1, 2, 3, 4, 5, 6, 7]
+            );
+            assert_eq!(values("* * * * *", FieldKind::Weeks).len(), 53);
             assert_eq!(
-                dom_expr("1-15/4"),
-                Ok(("", DayOfMonthExpr::Many(exprs(vec![rs(1, 15, 4)]))))
-            )
+                field("* * ? * *", FieldKind::DaysOfMonth),
+                FieldView::Ignored
+            );
+            assert_eq!(field("* * ? * *", FieldKind::DaysOfMonth).values(), None);
         }
 
         #[test]
-        fn many_range_step() {
+        fn values_in_field_numbering() {
+            assert_eq!(values("50-5/5 * * * *", FieldKind::Minutes), [0, 5, 50, 55]);
+            assert_eq!(values("* 22-1 * * *", FieldKind::Hours), [0, 1, 22, 23]);
+            assert_eq!(values("* * 15,1-2 * *", FieldKind::DaysOfMonth), [1, 2, 15]);
+            assert_eq!(values("* * * DEC-FEB *", FieldKind::Months), [1, 2, 12]);
             assert_eq!(
-                dom_expr("1-15/3,16-31/4"),
-                Ok((
-                    "",
-                    DayOfMonthExpr::Many(exprs(vec![rs(1, 15, 3), rs(16, 31, 4)]))
-                ))
-            )
+                values("* * * * MON-FRI", FieldKind::DaysOfWeek),
+                [2, 3, 4, 5, 6]
+            );
+            assert_eq!(values("* * * * * W52-2", FieldKind::Weeks), [1, 2, 52, 53]);
+            assert!(!field("* * * * SUN-SAT", FieldKind::DaysOfWeek).is_all());
         }
 
         #[test]
-        fn values_ranges_steps_and_ranges() {
+        fn specials() {
             assert_eq!(
-                dom_expr("1,1-10/3,10-20,20/3"),
-                Ok((
-                    "",
-                    DayOfMonthExpr::Many(exprs(vec![o(1), rs(1, 10, 3), r(10, 20), s(20, 3)]))
-                ))
-            )
-        }
+                field("* * L,15W * ?", FieldKind::DaysOfMonth),
+                FieldView::DayOfMonthSpecials(vec![
+                    DayOfMonthSpecial::Last(Last::Day),
+                    DayOfMonthSpecial::ClosestWeekday(DayOfMonth(15)),
+                ])
+            );
+            assert_eq!(
+                field("* * 3B * ?", FieldKind::DaysOfMonth),
+                FieldView::DayOfMonthSpecials(vec![DayOfMonthSpecial::BusinessDay(BusinessDay(3))])
+            );
+            assert_eq!(
+                field("* * ? * FRI#2", FieldKind::DaysOfWeek),
+                FieldView::DayOfWeekSpecials(vec![DayOfWeekSpecial::Nth(
+                    DayOfWeek(chrono::Weekday::Fri),
+                    NthDay(2)
+                )])
+            );
+            assert_eq!(field("* * ? * 6L", FieldKind::DaysOfWeek).values(), None);
 
-        #[test]
-        fn limits() {
-            assert!(matches!(dom_expr("32"), Err(_)));
-            assert!(matches!(dom_expr("0-31"), Err(_)));
-            assert!(matches!(dom_expr("1-32"), Err(_)));
-            // a step greater than the max value is not allowed (since it doesn't make sense)
-            assert!(matches!(dom_expr("1/32"), Err(_)));
-            assert!(matches!(dom_expr("0/31"), Err(_)));
-            assert!(matches!(dom_expr("1-31/32"), Err(_)));
-            assert!(matches!(dom_expr("0-31/32"), Err(_)));
-            assert!(matches!(dom_expr("0-32/31"), Err(_)));
-            // a step of 0 is not allowed (since it doesn't make sense)
-            assert!(matches!(dom_expr("0/0"), Err(_)));
-            assert!(matches!(dom_expr("0-23/0"), Err(_)));
+            match field("* * 1-3,10W * ?", FieldKind::DaysOfMonth) {
+                FieldView::ClosestWeekdays(days) => {
+                    assert_eq!(days.collect::<Vec<_>>(), [1, 2, 3, 10])
+                }
+                view => panic!("Unexpected view {:?}", view),
+            }
         }
     }
 
-    mod days_of_week {
+    mod dialects {
         use super::*;
+        use crate::Cron;
 
-        #[test]
-        fn all() {
-            assert_eq!(dow_expr("*"), Ok(("", DayOfWeekExpr::All)))
+        #[cfg(not(feature = "std"))]
+        use alloc::string::ToString;
+
+        #[track_caller]
+        fn assert_unix(unix: &str, quartz: &str) {
+            let expr = CronExpr::from_str_with(unix, Dialect::Unix).expect("Valid unix expression");
+            let expected: CronExpr = quartz.parse().expect("Valid cron expression");
+            assert_eq!(Cron::new(expr), Cron::new(expected));
         }
 
         #[test]
-        fn only_match_first_star() {
-            // make sure we only match the first star.
-            // it'll fail on the next parser
-            assert_eq!(dow_expr("*,*"), Ok((",*", DayOfWeekExpr::All)))
+        fn days_of_week() {
+            assert_unix("* * * * 0", "* * * * SUN");
+            assert_unix("* * * * 7", "* * * * SUN");
+            assert_unix("* * * * 1-5", "* * * * MON-FRI");
+            assert_unix("* * * * 0,6", "* * * * SAT,SUN");
+            assert_unix("* * * * 0-6", "* * * * SUN-SAT");
+            assert_unix("* * * * 0-7", "* * * * SUN-SAT");
+            assert_unix("* * * * 1-7", "* * * * SUN-SAT");
+            assert_unix("* * * * 5-7", "* * * * FRI,SAT,SUN");
+            assert_unix("* * * * 7-7", "* * * * SUN");
+            assert_unix("* * * * MON-FRI", "* * * * MON-FRI");
         }
 
         #[test]
-        fn last() {
+        fn weekday_numbering() {
+            let parse = |s, dialect, numbering| {
+                let mut options = ParseOptions::new();
+                options.dialect = dialect;
+                options.weekday_numbering = Some(numbering);
+                CronExpr::from_str_with_options(s, options).map(Cron::new)
+            };
+            let iso = |s| parse(s, Dialect::Quartz, WeekdayNumbering::Iso);
+            let quartz = |s: &str| s.parse::<Cron>();
+
+            assert_eq!(iso("0 0 ? * 1-5"), quartz("0 0 ? * MON-FRI"));
+            assert_eq!(iso("0 0 ? * 6-7"), quartz("0 0 ? * SAT,SUN"));
+            assert_eq!(iso("0 0 ? * 7-1"), quartz("0 0 ? * SUN,MON"));
+            assert_eq!(iso("0 0 ? * */2"), quartz("0 0 ? * MON,WED,FRI,SUN"));
+            assert_eq!(iso("0 0 ? * 5L,1#2"), quartz("0 0 ? * FRIL,MON#2"));
+            assert_eq!(iso("0 0 ? * SUN,MON"), quartz("0 0 ? * SUN,MON"));
             assert_eq!(
-                dow_expr("L"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(7)]))))
-            )
-        }
+                iso("0 0 ? * 0"),
+                Err(CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfWeek,
+                    value: 0,
+                    min: 1,
+                    max: 7,
+                    at: 8,
+                })
+            );
 
-        #[test]
-        fn last_day() {
-            assert_eq!(dow_expr("3L"), Ok(("", DayOfWeekExpr::Last(e(3)))))
+            // the numbering overrides the dialect's
+            assert_eq!(
+                parse("0 0 ? * 0,6", Dialect::Quartz, WeekdayNumbering::Unix),
+                quartz("0 0 ? * SUN,SAT")
+            );
+            assert_eq!(
+                parse("0 0 * * 1-5", Dialect::Unix, WeekdayNumbering::Quartz),
+                CronExpr::from_str_with("0 0 * * 0-4", Dialect::Unix).map(Cron::new)
+            );
+            assert_eq!(
+                parse("0 0 ? * *!6-7", Dialect::Extended, WeekdayNumbering::Iso),
+                quartz("0 0 ? * MON-FRI")
+            );
+            assert_eq!(Dialect::Unix.weekday_numbering(), WeekdayNumbering::Unix);
         }
 
-        // last is not allowed with other expressions
         #[test]
-        fn last_with_other_exprs() {
-            assert!(matches!(dow_expr("3,L"), Err(_)))
+        fn steps() {
+            assert_unix("* * * * */2", "* * * * SUN,TUE,THU,SAT");
+            assert_unix("* * * * 1/2", "* * * * MON,WED,FRI,SUN");
+            assert_unix("* * * * 2/2", "* * * * TUE,THU,SAT");
+            assert_unix("* * * * 1-5/2", "* * * * MON,WED,FRI");
+            assert_unix("* * * * 0-7/3", "* * * * SUN,WED,SAT");
         }
 
         #[test]
-        fn nth() {
-            assert_eq!(dow_expr("MON#1"), Ok(("", DayOfWeekExpr::Nth(e(2), e(1)))));
-            assert_eq!(dow_expr("5#4"), Ok(("", DayOfWeekExpr::Nth(e(5), e(4)))));
+        fn specials() {
+            assert_unix("* * * * 5L", "* * * * FRIL");
+            assert_unix("* * * * 0L", "* * * * SUNL");
+            assert_unix("* * * * 1#2", "* * * * MON#2");
+            assert_unix("* * ? * 1", "* * ? * MON");
+            assert_unix("0 0 1,15 * 1", "0 0 1,15 * MON");
         }
 
         #[test]
-        fn star_step() {
+        fn other_fields_are_unchanged() {
             assert_eq!(
-                dow_expr("*/3"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(1, 3)]))))
-            )
+                CronExpr::from_str_with("*/5 9-17 L JAN-MAR ?", Dialect::Unix),
+                "*/5 9-17 L JAN-MAR ?".parse()
+            );
         }
 
         #[test]
-        fn multi_star_step() {
+        fn errors() {
             assert_eq!(
-                dow_expr("*/3,*/4"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(1, 3), s(1, 4)]))))
-            )
+                CronExpr::from_str_with("* * * * 8", Dialect::Unix),
+                Err(CronParseError::ValueOutOfRange {
+                    field: FieldKind::DaysOfWeek,
+                    value: 8,
+                    min: 0,
+                    max: 7,
+                    at: 8,
+                })
+            );
+            assert_eq!(
+                CronExpr::from_str_with("* * * * 1-", Dialect::Unix),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 10,
+                })
+            );
         }
 
         #[test]
-        fn star_range_doesnt_make_sense() {
-            // make sure we only match the first star.
-            // it'll fail on the next parser
-            assert_eq!(dow_expr("*-6/3,*/4"), Ok(("-6/3,*/4", DayOfWeekExpr::All)))
+        fn weeks() {
+            let extended = |cron: &str| CronExpr::from_str_with(cron, Dialect::Extended);
+
+            let expr = extended("0 9 * * MON W/2").unwrap();
+            assert_eq!(
+                expr.weeks,
+                Expr::Many(exprs(vec![OrsExpr::Step {
+                    start: e(1),
+                    end: e(53),
+                    step: e(2),
+                }]))
+            );
+            assert_eq!(expr.to_string(), "0 9 * * 2 W1/2");
+            assert_eq!(extended(&expr.to_string()), Ok(expr));
+
+            let expr = extended("0 9 * * MON W1-26,53").unwrap();
+            assert_eq!(
+                expr.weeks,
+                Expr::Many(exprs(vec![
+                    OrsExpr::Range(e(1), e(26)),
+                    OrsExpr::One(e(53))
+                ]))
+            );
+            assert_eq!(extended("* * * * * W*"), "* * * * *".parse());
+            assert_eq!(extended("* * * * *"), "* * * * *".parse());
+
+            // the weeks field is an extension
+            assert_eq!(
+                "0 9 * * MON W/2".parse::<CronExpr>(),
+                Err(CronParseError::TrailingInput { at: 11 })
+            );
+            assert_eq!(
+                extended("* * * * * W54"),
+                Err(CronParseError::ValueOutOfRange {
+                    field: FieldKind::Weeks,
+                    value: 54,
+                    min: 1,
+                    max: 53,
+                    at: 11,
+                })
+            );
+            assert_eq!(
+                extended("* * * * * 5"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Weeks,
+                    at: 10,
+                })
+            );
+            assert_eq!(
+                extended("* * * * * W5x"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Weeks,
+                    at: 12,
+                })
+            );
+            assert_eq!(
+                extended("* * * * * W5 "),
+                Err(CronParseError::TrailingInput { at: 12 })
+            );
         }
 
         #[test]
-        fn one_value() {
+        fn year_days() {
+            let extended = |cron: &str| CronExpr::from_str_with(cron, Dialect::Extended);
+
+            assert_eq!(extended("0 0 LY * ?"), extended("0 0 31 12 ?"));
+            assert_eq!(extended("30 17 LY * *"), extended("30 17 31 12 *"));
+            assert_eq!(extended("0 0 FY * ?"), extended("0 0 1 1 ?"));
+            assert_eq!(extended("0 0 FY * ?").unwrap().to_string(), "0 0 1 1 ?");
+
+            // the day is already in one month
             assert_eq!(
-                dow_expr("1"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(1)]))))
-            )
+                extended("0 0 LY DEC ?"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Months,
+                    at: 7,
+                })
+            );
+            assert_eq!(
+                extended("0 0 LY *!1 ?"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Months,
+                    at: 7,
+                })
+            );
+            assert_eq!(
+                extended("0 0 LYW * ?"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfMonth,
+                    at: 6,
+                })
+            );
+            // the days of the year are an extension
+            assert_eq!(
+                "0 0 LY * ?".parse::<CronExpr>(),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfMonth,
+                    at: 5,
+                })
+            );
         }
 
         #[test]
-        fn word_values() {
-            // caps
+        fn exclusions() {
+            let extended = |cron: &str| CronExpr::from_str_with(cron, Dialect::Extended);
+
+            let expr = extended("*!15 9-17 * * MON-FRI!WED W*!1").unwrap();
+            assert_eq!(expr.minutes, Expr::All);
             assert_eq!(
-                dow_expr("SUN"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(1)]))))
+                expr.exclusions.minutes,
+                Some(exprs(vec![OrsExpr::One(e(15))]))
             );
+            assert_eq!(expr.exclusions.hours, None);
             assert_eq!(
-                dow_expr("MON"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(2)]))))
+                expr.exclusions.dows,
+                Some(exprs(vec![OrsExpr::One(DayOfWeek(chrono::Weekday::Wed))]))
             );
+            assert_eq!(expr.exclusions.weeks, Some(exprs(vec![OrsExpr::One(e(1))])));
+            assert_eq!(expr.to_string(), "*!15 9-17 * * 2-6!4 W*!1");
+            assert_eq!(extended(&expr.to_string()), Ok(expr.clone()));
             assert_eq!(
-                dow_expr("TUE"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(3)]))))
+                expr.normalize().to_string(),
+                "0-14,16-59 9-17 * * 2-3,5-6 W2-53"
+            );
+            assert_eq!(
+                expr.field(FieldKind::Minutes),
+                extended("0-14,16-59 * * * *")
+                    .unwrap()
+                    .field(FieldKind::Minutes)
             );
+            assert_eq!(extended("0 9 * *!JAN-MAR ?"), extended("0 9 * *!1-3 ?"));
+            assert!(extended("0 0 * * *").unwrap().exclusions.is_empty());
 
-            // lower
+            // exclusions are an extension
             assert_eq!(
-                dow_expr("WED"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(4)]))))
+                "*!15 * * * *".parse::<CronExpr>(),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Minutes,
+                    at: 1,
+                })
             );
+            // only a '*' or values, ranges, and steps can exclude values
             assert_eq!(
-                dow_expr("THU"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(5)]))))
+                extended("0 0 L!1 * ?"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfMonth,
+                    at: 5,
+                })
             );
             assert_eq!(
-                dow_expr("FRI"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(6)]))))
+                extended("0 0 ? * *!MON#2"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 13,
+                })
             );
-
-            // mixed
+            // excluding every value leaves nothing to match
             assert_eq!(
-                dow_expr("SaT"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(7)]))))
+                extended("10-20!0-30 * * * *"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Minutes,
+                    at: 5,
+                })
             );
-        }
-
-        #[test]
-        fn many_one_value() {
             assert_eq!(
-                dow_expr("2,WED,FRI,7"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![o(2), o(4), o(6), o(7)]))))
-            )
+                extended("* 24!1 * * *"),
+                Err(CronParseError::ValueOutOfRange {
+                    field: FieldKind::Hours,
+                    value: 24,
+                    min: 0,
+                    max: 23,
+                    at: 2,
+                })
+            );
+            assert_eq!(
+                extended("* *!24 * * *"),
+                Err(CronParseError::ValueOutOfRange {
+                    field: FieldKind::Hours,
+                    value: 24,
+                    min: 0,
+                    max: 23,
+                    at: 4,
+                })
+            );
         }
 
         #[test]
-        fn one_range() {
+        fn days_and() {
+            use crate::{Cron, DayPolicy};
+            use chrono::{TimeZone, Utc};
+
+            let extended = |cron: &str| CronExpr::from_str_with(cron, Dialect::Extended);
+
+            let expr = extended("0 0 15 * &FRI").unwrap();
+            assert!(expr.days_and);
+            assert_eq!(expr.to_string(), "0 0 15 * &6");
+            assert_eq!(extended(&expr.to_string()), Ok(expr.clone()));
+            assert_eq!(expr.normalize().to_string(), "0 0 15 * &6");
+            assert!(!extended("0 0 15 * FRI").unwrap().days_and);
             assert_eq!(
-                dow_expr("MON-5"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![r(2, 5)]))))
-            )
-        }
+                extended("0 0 15 * &MON-FRI!WED").map(Cron::new),
+                extended("0 0 15 * &2-3,5-6").map(Cron::new)
+            );
 
-        #[test]
-        fn overflow_range() {
+            // the 15th, but only on Fridays
+            let cron = Cron::new(expr);
+            assert_eq!(cron.day_policy(), DayPolicy::And);
             assert_eq!(
-                dow_expr("7-1"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![r(7, 1)]))))
-            )
-        }
+                Cron::parse_with("0 0 15 * &FRI", Dialect::Extended),
+                Ok(cron.clone())
+            );
+            assert_eq!(cron.to_string(), "0 0 15 * &6");
+            assert_eq!(
+                Cron::parse_with(&cron.to_string(), Dialect::Extended),
+                Ok(cron.clone())
+            );
+            assert_eq!(Cron::new(cron.to_expr()), cron);
+            assert_eq!(
+                cron.next_from(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()),
+                Some(Utc.with_ymd_and_hms(2021, 1, 15, 0, 0, 0).unwrap())
+            );
+            assert_eq!(
+                cron.next_after(Utc.with_ymd_and_hms(2021, 1, 15, 0, 0, 0).unwrap()),
+                Some(Utc.with_ymd_and_hms(2021, 10, 15, 0, 0, 0).unwrap())
+            );
 
-        #[test]
-        fn many_range() {
+            // combining the day fields is an extension
             assert_eq!(
-                dow_expr("1-3,4-4,5-7"),
-                Ok((
-                    "",
-                    DayOfWeekExpr::Many(exprs(vec![r(1, 3), r(4, 4), r(5, 7)]))
-                ))
-            )
+                "0 0 15 * &FRI".parse::<CronExpr>(),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 9,
+                })
+            );
+            assert_eq!(
+                extended("0 0 15 * FRI&"),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 12,
+                })
+            );
         }
+    }
 
-        #[test]
-        fn step() {
-            assert_eq!(
-                dow_expr("2/2"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(2, 2)]))))
-            )
+    mod full_names {
+        use super::*;
+
+        #[cfg(not(feature = "std"))]
+        use alloc::{format, string::String};
+
+        fn parse(s: &str, dialect: Dialect) -> Result<CronExpr, CronParseError> {
+            let mut options = ParseOptions::new();
+            options.dialect = dialect;
+            options.allow_full_names = true;
+            CronExpr::from_str_with_options(s, options)
         }
 
-        #[test]
-        fn step_with_star_step() {
-            assert_eq!(
-                dow_expr("2/2,*/4"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![s(2, 2), s(1, 4)]))))
-            )
+        /// Returns the name in upper case, lower case, and with only the first letter capitalized
+        fn cases(name: &str) -> [String; 3] {
+            let (first, rest) = name.split_at(1);
+            [
+                name.to_ascii_uppercase(),
+                name.to_ascii_lowercase(),
+                format!("{}{}", first, rest.to_ascii_lowercase()),
+            ]
         }
 
         #[test]
-        fn many_steps() {
-            assert_eq!(
-                dow_expr("1/2,2/3,3/4"),
-                Ok((
-                    "",
-                    DayOfWeekExpr::Many(exprs(vec![s(1, 2), s(2, 3), s(3, 4)]))
-                ))
-            )
+        fn every_month() {
+            for (i, name) in MONTH_NAMES.iter().enumerate() {
+                let expected = format!("0 0 1 {} ?", i + 1).parse::<CronExpr>().unwrap();
+                for name in cases(name).iter().chain(cases(&name[..3]).iter()) {
+                    let expr = format!("0 0 1 {} ?", name);
+                    assert_eq!(
+                        parse(&expr, Dialect::Quartz),
+                        Ok(expected.clone()),
+                        "{}",
+                        expr
+                    );
+                }
+            }
         }
 
         #[test]
-        fn range_step() {
-            assert_eq!(
-                dow_expr("2-5/2"),
-                Ok(("", DayOfWeekExpr::Many(exprs(vec![rs(2, 5, 2)]))))
-            )
+        fn every_day_of_week() {
+            for (i, name) in DAY_NAMES.iter().enumerate() {
+                let expected = format!("0 0 ? * {}", i + 1).parse::<CronExpr>().unwrap();
+                let unix = CronExpr::from_str_with(&format!("0 0 * * {}", i), Dialect::Unix);
+                for name in cases(name).iter().chain(cases(&name[..3]).iter()) {
+                    let expr = format!("0 0 ? * {}", name);
+                    assert_eq!(
+                        parse(&expr, Dialect::Quartz),
+                        Ok(expected.clone()),
+                        "{}",
+                        expr
+                    );
+                    let expr = format!("0 0 * * {}", name);
+                    assert_eq!(parse(&expr, Dialect::Unix), unix, "{}", expr);
+                }
+            }
         }
 
         #[test]
-        fn many_range_step() {
+        fn in_expressions() {
+            let quartz = |s| parse(s, Dialect::Quartz);
             assert_eq!(
-                dow_expr("1-4/2,5-7/2"),
-                Ok((
-                    "",
-                    DayOfWeekExpr::Many(exprs(vec![rs(1, 4, 2), rs(5, 7, 2)]))
-                ))
-            )
+                quartz("0 0 ? JANUARY-MARCH,sep MONDAY-FRIDAY/2,sunday"),
+                "0 0 ? JAN-MAR,SEP MON-FRI/2,SUN".parse()
+            );
+            assert_eq!(
+                quartz("0 0 ? * FRIDAYL,MONDAY#2"),
+                "0 0 ? * FRIL,MON#2".parse()
+            );
+            assert_eq!(
+                parse("0 0 * * *!SATURDAY,SUNDAY", Dialect::Extended),
+                CronExpr::from_str_with("0 0 * * *!SAT,SUN", Dialect::Extended)
+            );
+            assert_eq!(
+                parse("0 0 * * MONDAY-SATURDAY/2,SUNDAY", Dialect::Unix),
+                CronExpr::from_str_with("0 0 * * 1-6/2,0", Dialect::Unix)
+            );
+            assert_eq!(
+                parse("0 0 * * FRIDAYL", Dialect::Unix),
+                CronExpr::from_str_with("0 0 * * 5L", Dialect::Unix)
+            );
         }
 
         #[test]
-        fn values_ranges_steps_and_ranges() {
-            assert_eq!(
-                dow_expr("1,2-FRI/2,6-7,3/3"),
-                Ok((
-                    "",
-                    DayOfWeekExpr::Many(exprs(vec![o(1), rs(2, 6, 2), r(6, 7), s(3, 3)]))
-                ))
-            )
+        fn partial_names() {
+            for expr in &[
+                "0 0 ? * MOND",
+                "0 0 ? * MONDAYS",
+                "0 0 ? * WEDNES",
+                "0 0 1 SEPT ?",
+                "0 0 1 JANUAR ?",
+                "0 0 1 MAYY ?",
+            ] {
+                assert!(parse(expr, Dialect::Quartz).is_err(), "{}", expr);
+            }
         }
 
         #[test]
-        fn limits() {
-            assert!(matches!(dow_expr("8"), Err(_)));
-            assert!(matches!(dow_expr("0"), Err(_)));
-            assert!(matches!(dow_expr("0-7"), Err(_)));
-            assert!(matches!(dow_expr("1-8"), Err(_)));
-            // a step greater than the max value is not allowed (since it doesn't make sense)
-            assert!(matches!(dow_expr("1/8"), Err(_)));
-            assert!(matches!(dow_expr("0/7"), Err(_)));
-            assert!(matches!(dow_expr("1-7/8"), Err(_)));
-            assert!(matches!(dow_expr("0-7/7"), Err(_)));
-            assert!(matches!(dow_expr("0-8/7"), Err(_)));
-            // a step of 0 is not allowed (since it doesn't make sense)
-            assert!(matches!(dow_expr("1/0"), Err(_)));
-            assert!(matches!(dow_expr("1-5/0"), Err(_)));
-            // 0th day doesn't make sense
-            assert!(matches!(dow_expr("SUN#0"), Err(_)));
-            // 6th day of the month will never happen
-            assert!(matches!(dow_expr("MON#6"), Err(_)));
+        fn only_with_option() {
+            assert_eq!(
+                "0 0 ? * MONDAY".parse::<CronExpr>(),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::DaysOfWeek,
+                    at: 11,
+                })
+            );
+            assert_eq!(
+                "0 0 1 SEPTEMBER ?".parse::<CronExpr>(),
+                Err(CronParseError::InvalidField {
+                    field: FieldKind::Months,
+                    at: 9,
+                })
+            );
+            assert!(CronExpr::from_str_with("0 0 * * SUNDAY", Dialect::Unix).is_err());
         }
     }
 }
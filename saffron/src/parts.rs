@@ -0,0 +1,297 @@
+//! Sets of field values that can be compiled into a [`Cron`] in a `const` context, so static
+//! schedules don't have to be parsed at runtime.
+
+use crate::{Cron, DayPolicy, DaysOfMonth, DaysOfMonthKind, DaysOfWeek, DaysOfWeekKind};
+use crate::{Hours, Minutes, Months, Weeks};
+
+use chrono::Weekday;
+
+/// Returns the mask of all bits from `start` to `end` inclusive, wrapping around `max` if the
+/// start is after the end.
+const fn range_bits(start: u8, end: u8, max: u8) -> u64 {
+    const fn bits(start: u8, end: u8) -> u64 {
+        ((2 << end) - 1) & !((1 << start) - 1)
+    }
+
+    if start <= end {
+        bits(start, end)
+    } else {
+        bits(start, max) | bits(0, end)
+    }
+}
+
+macro_rules! value_set {
+    (
+        $(#[$meta:meta])*
+        $name:ident($bits:ty), $min:literal..=$max:literal
+    ) => {
+        $(#[$meta])*
+        ///
+        /// Values outside of the field are clamped to its first or last value, so a set is never
+        /// empty.
+        #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+        pub struct $name($bits);
+
+        impl $name {
+            const MIN: u8 = $min;
+            const MAX: u8 = $max;
+
+            /// A set of every value in the field.
+            pub const ALL: Self = Self::range(Self::MIN, Self::MAX);
+
+            /// The value's bit, clamped to the field
+            #[inline]
+            const fn offset(value: u8) -> u8 {
+                let offset = value.saturating_sub(Self::MIN);
+                if offset > Self::MAX - Self::MIN {
+                    Self::MAX - Self::MIN
+                } else {
+                    offset
+                }
+            }
+
+            /// Returns a set of the one value.
+            #[inline]
+            pub const fn single(value: u8) -> Self {
+                Self(1 << Self::offset(value))
+            }
+
+            /// Returns a set of the values from `start` to `end` inclusive. If the start is after
+            /// the end, the range wraps around the end of the field.
+            #[inline]
+            #[allow(clippy::unnecessary_cast)]
+            pub const fn range(start: u8, end: u8) -> Self {
+                let bits = range_bits(
+                    Self::offset(start),
+                    Self::offset(end),
+                    Self::MAX - Self::MIN,
+                );
+                Self(bits as $bits)
+            }
+
+            /// Returns this set with the value added to it.
+            #[inline]
+            pub const fn with(self, value: u8) -> Self {
+                Self(self.0 | Self::single(value).0)
+            }
+
+            /// Returns a set of the values in either set.
+            #[inline]
+            pub const fn union(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+
+            /// Returns whether the set contains the value.
+            #[inline]
+            pub const fn contains(self, value: u8) -> bool {
+                value >= Self::MIN
+                    && value <= Self::MAX
+                    && self.0 & (1 << (value - Self::MIN)) != 0
+            }
+        }
+    };
+}
+
+value_set! {
+    /// A set of minutes (0-59) to build a [`Cron`] with.
+    MinuteSet(u64), 0..=59
+}
+
+value_set! {
+    /// A set of hours (0-23) to build a [`Cron`] with.
+    HourSet(u32), 0..=23
+}
+
+value_set! {
+    /// A set of days of the month (1-31) to build a [`Cron`] with. A set of every day is
+    /// compiled like a `*` expression.
+    DayOfMonthSet(u32), 1..=31
+}
+
+value_set! {
+    /// A set of months (1-12) to build a [`Cron`] with.
+    MonthSet(u16), 1..=12
+}
+
+/// A set of days of the week to build a [`Cron`] with. A set of every day is compiled like a `*`
+/// expression.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct DayOfWeekSet(u8);
+
+impl DayOfWeekSet {
+    /// A set of every day of the week.
+    pub const ALL: Self = Self(0b0111_1111);
+
+    /// The day's bit, where Sunday is the first bit
+    #[inline]
+    const fn offset(day: Weekday) -> u8 {
+        (day as u8 + 1) % 7
+    }
+
+    /// Returns a set of the one day.
+    ///
+    /// ```
+    /// use saffron::DayOfWeekSet;
+    /// use chrono::Weekday;
+    ///
+    /// const SET: DayOfWeekSet = DayOfWeekSet::single(Weekday::Fri);
+    /// assert!(SET.contains(Weekday::Fri));
+    /// ```
+    #[inline]
+    pub const fn single(day: Weekday) -> Self {
+        Self(1 << Self::offset(day))
+    }
+
+    /// Returns a set of the days from `start` to `end` inclusive. If the start is after the end
+    /// in a week starting on Sunday, the range wraps around the end of the week.
+    #[inline]
+    pub const fn range(start: Weekday, end: Weekday) -> Self {
+        Self(range_bits(Self::offset(start), Self::offset(end), 6) as u8)
+    }
+
+    /// Returns this set with the day added to it.
+    #[inline]
+    pub const fn with(self, day: Weekday) -> Self {
+        Self(self.0 | Self::single(day).0)
+    }
+
+    /// Returns a set of the days in either set.
+    #[inline]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns whether the set contains the day.
+    #[inline]
+    pub const fn contains(self, day: Weekday) -> bool {
+        self.0 & (1 << Self::offset(day)) != 0
+    }
+}
+
+impl Cron {
+    /// Builds a cron value from sets of field values. Unlike parsing, this can be done in a
+    /// `const` context, so a static schedule can be compiled into the binary.
+    ///
+    /// The days of the month and week are combined like they are in a parsed expression: if
+    /// either set contains every day, only the other one is matched.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, DayOfMonthSet, DayOfWeekSet, HourSet, MinuteSet, MonthSet};
+    /// use chrono::Weekday;
+    ///
+    /// // at midnight and noon on weekdays
+    /// static CRON: Cron = Cron::from_parts(
+    ///     MinuteSet::single(0),
+    ///     HourSet::single(0).with(12),
+    ///     DayOfMonthSet::ALL,
+    ///     MonthSet::ALL,
+    ///     DayOfWeekSet::range(Weekday::Mon, Weekday::Fri),
+    /// );
+    ///
+    /// assert_eq!(CRON, "0 0,12 * * MON-FRI".parse().unwrap());
+    /// ```
+    pub const fn from_parts(
+        minutes: MinuteSet,
+        hours: HourSet,
+        days_of_month: DayOfMonthSet,
+        months: MonthSet,
+        days_of_week: DayOfWeekSet,
+    ) -> Cron {
+        let dom = if days_of_month.0 == DayOfMonthSet::ALL.0 {
+            DaysOfMonth(DaysOfMonthKind::Star, 0)
+        } else {
+            DaysOfMonth(DaysOfMonthKind::Pattern, days_of_month.0)
+        };
+        let dow = if days_of_week.0 == DayOfWeekSet::ALL.0 {
+            DaysOfWeek(DaysOfWeekKind::Star, 0)
+        } else {
+            DaysOfWeek(DaysOfWeekKind::Pattern, days_of_week.0)
+        };
+
+        Cron {
+            minutes: Minutes(minutes.0),
+            hours: Hours(hours.0),
+            dom,
+            months: Months(months.0),
+            dow,
+            weeks: Weeks(Weeks::ALL),
+            day_policy: DayPolicy::Or,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets() {
+        assert_eq!(MinuteSet::ALL, MinuteSet::range(0, 59));
+        assert_eq!(MinuteSet::single(60), MinuteSet::single(59));
+        assert!(MinuteSet::range(50, 10).contains(0));
+        assert!(!MinuteSet::range(50, 10).contains(30));
+        assert!(!MinuteSet::ALL.contains(60));
+
+        assert_eq!(DayOfMonthSet::single(0), DayOfMonthSet::single(1));
+        assert!(DayOfMonthSet::ALL.contains(31));
+        assert!(!DayOfMonthSet::ALL.contains(0));
+
+        assert_eq!(MonthSet::single(1).with(2).with(3), MonthSet::range(1, 3));
+        assert_eq!(
+            HourSet::range(0, 5).union(HourSet::range(6, 23)),
+            HourSet::ALL
+        );
+
+        let weekend = DayOfWeekSet::range(Weekday::Sat, Weekday::Sun);
+        assert!(weekend.contains(Weekday::Sat));
+        assert!(weekend.contains(Weekday::Sun));
+        assert!(!weekend.contains(Weekday::Mon));
+        assert_eq!(
+            DayOfWeekSet::range(Weekday::Mon, Weekday::Sun),
+            DayOfWeekSet::ALL
+        );
+    }
+
+    #[test]
+    fn from_parts() {
+        use Weekday::*;
+
+        let cases = [
+            (
+                Cron::from_parts(
+                    MinuteSet::ALL,
+                    HourSet::ALL,
+                    DayOfMonthSet::ALL,
+                    MonthSet::ALL,
+                    DayOfWeekSet::ALL,
+                ),
+                "* * * * *",
+            ),
+            (
+                Cron::from_parts(
+                    MinuteSet::range(0, 15).with(30),
+                    HourSet::range(22, 2),
+                    DayOfMonthSet::single(1).with(15),
+                    MonthSet::range(11, 2),
+                    DayOfWeekSet::ALL,
+                ),
+                "0-15,30 0-2,22-23 1,15 1-2,11-12 *",
+            ),
+            (
+                Cron::from_parts(
+                    MinuteSet::single(0),
+                    HourSet::single(9),
+                    DayOfMonthSet::range(1, 7),
+                    MonthSet::ALL,
+                    DayOfWeekSet::single(Fri).with(Sat),
+                ),
+                "0 9 1-7 * FRI,SAT",
+            ),
+        ];
+
+        for (cron, expr) in cases.iter() {
+            assert_eq!(cron, &expr.parse::<Cron>().unwrap(), "{}", expr);
+        }
+    }
+}
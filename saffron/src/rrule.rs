@@ -0,0 +1,1175 @@
+//! Conversions between cron values and iCalendar recurrence rules, the `RRULE` property defined
+//! in [RFC 5545](https://tools.ietf.org/html/rfc5545#section-3.3.10).
+//!
+//! A recurrence rule only describes how a schedule repeats. Calendar apps pair it with a start
+//! time (`DTSTART`), which should be in UTC, at zero seconds, and itself matched by the cron
+//! value, since the start time is always the first occurrence of a rule.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::parse::{
+    BusinessDay, CronExpr, DayOfMonthExpr, DayOfMonthOffset, DayOfMonthSpecial, DayOfWeekExpr,
+    DayOfWeekSpecial, Exclusions, Expr, ExprValue, Exprs, Hour, Last, Minute, Month, NthDay,
+    OrsExpr, ValueOutOfRangeError,
+};
+use crate::{Cron, DayOfMonthSpec, DayOfWeekSpec, DayPolicy};
+use chrono::Weekday;
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter, Write};
+
+/// An error returned if a cron value can't be written as a recurrence rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NotRepresentable {
+    /// The cron value matches the closest weekday to a day of the month (`15W` or `L-3W`)
+    ClosestWeekday,
+    /// The cron value matches the last weekday of the month (`LW` or `LW-1`) at a time further
+    /// from the end of the month than a rule can select with `BYSETPOS`
+    LastWeekday,
+    /// The cron value matches the nth business day of the month (`3B`) at a time further into
+    /// the month than a rule can select with `BYSETPOS`
+    BusinessDay,
+    /// The cron value restricts both day fields and matches a day matching either field
+    EitherDay,
+    /// The cron value matches days matching both day fields, but the fields can't be combined
+    /// in a rule, like the 13th of the month that's also the second Friday
+    BothDays,
+    /// The cron value restricts the weeks of the year, which a rule can only select with
+    /// `BYWEEKNO` in yearly rules
+    Weeks,
+    /// The cron value matches a list of special days of the month other than last days of the
+    /// month, like `1W,15W` or `L,LW`
+    Specials,
+}
+
+impl Display for NotRepresentable {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NotRepresentable::ClosestWeekday => {
+                "The closest weekday to a day of the month can't be written as a recurrence rule"
+            }
+            NotRepresentable::LastWeekday => {
+                "The last weekday of the month before the 366th to last time in the month can't be written as a recurrence rule"
+            }
+            NotRepresentable::BusinessDay => {
+                "The nth business day of the month past the 366th time in the month can't be written as a recurrence rule"
+            }
+            NotRepresentable::EitherDay => {
+                "Matching either a day of the month or a day of the week can't be written as a recurrence rule"
+            }
+            NotRepresentable::BothDays => {
+                "The combination of the day of the month and day of the week can't be written as a recurrence rule"
+            }
+            NotRepresentable::Weeks => {
+                "Weeks of the year can't be written as a recurrence rule repeating more than once a year"
+            }
+            NotRepresentable::Specials => {
+                "A list of special days of the month other than the last days can't be written as a recurrence rule"
+            }
+        }
+        .fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotRepresentable {}
+
+/// The `FREQ` of a rule, ordered from the shortest to the longest interval
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Frequency {
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    fn name(self) -> &'static str {
+        match self {
+            Frequency::Minutely => "MINUTELY",
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        }
+    }
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// Writes a rule part with a list of values, like `;BYHOUR=9,17`
+fn write_part<T: Display>(out: &mut String, name: &str, values: impl IntoIterator<Item = T>) {
+    let _ = write!(out, ";{}=", name);
+    for (i, value) in values.into_iter().enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        let _ = write!(out, "{}", value);
+    }
+}
+
+impl Cron {
+    /// Writes the cron value as an iCalendar recurrence rule (`RRULE`). Nth and last days of
+    /// the week are written with `BYDAY` (`2MO`, `-1FR`), last days of the month with a negative
+    /// `BYMONTHDAY`, and the last weekday of the month with `BYSETPOS`.
+    ///
+    /// A rule matches days matching both its `BYMONTHDAY` and `BYDAY`, so cron values
+    /// restricting both day fields can only be written with [`DayPolicy::And`].
+    ///
+    /// [`DayPolicy::And`]: enum.DayPolicy.html#variant.And
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use saffron::rrule::NotRepresentable;
+    ///
+    /// let cron: Cron = "30 9 * * MON-FRI".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(
+    ///     cron.to_rrule().unwrap(),
+    ///     "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=30"
+    /// );
+    ///
+    /// let cron: Cron = "0 0 ? * FRI#2".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(
+    ///     cron.to_rrule().unwrap(),
+    ///     "FREQ=MONTHLY;BYDAY=2FR;BYHOUR=0;BYMINUTE=0"
+    /// );
+    ///
+    /// let cron: Cron = "0 0 15W * *".parse().expect("Couldn't parse expression!");
+    /// assert_eq!(cron.to_rrule(), Err(NotRepresentable::ClosestWeekday));
+    /// ```
+    pub fn to_rrule(&self) -> Result<String, NotRepresentable> {
+        if self.weeks().len() < 53 {
+            return Err(NotRepresentable::Weeks);
+        }
+
+        let dom = match self.days_of_month() {
+            DayOfMonthSpec::All | DayOfMonthSpec::Ignored => None,
+            DayOfMonthSpec::ClosestWeekday(_)
+            | DayOfMonthSpec::ClosestWeekdays(_)
+            | DayOfMonthSpec::LastWeekday { offset: 1..=30 } => {
+                return Err(NotRepresentable::ClosestWeekday)
+            }
+            DayOfMonthSpec::Specials(specials)
+                if !{ specials }.all(|spec| matches!(spec, DayOfMonthSpec::Last { .. })) =>
+            {
+                return Err(NotRepresentable::Specials)
+            }
+            spec => Some(spec),
+        };
+        let dow = match self.days_of_week() {
+            DayOfWeekSpec::All | DayOfWeekSpec::Ignored => None,
+            spec => Some(spec),
+        };
+
+        if dom.is_some() && dow.is_some() {
+            if self.day_policy != DayPolicy::And {
+                return Err(NotRepresentable::EitherDay);
+            }
+            // a rule with a BYMONTHDAY only uses its BYDAY to limit the days to days of the week
+            let limits = matches!(
+                dom,
+                Some(DayOfMonthSpec::Days(_))
+                    | Some(DayOfMonthSpec::Last { .. })
+                    | Some(DayOfMonthSpec::Specials(_))
+            );
+            if !limits || !matches!(dow, Some(DayOfWeekSpec::Days(_))) {
+                return Err(NotRepresentable::BothDays);
+            }
+        }
+
+        let frequency = if dom.is_some()
+            || matches!(
+                dow,
+                Some(DayOfWeekSpec::Last(_))
+                    | Some(DayOfWeekSpec::Nth(..))
+                    | Some(DayOfWeekSpec::Specials(_))
+            ) {
+            Frequency::Monthly
+        } else if dow.is_some() {
+            Frequency::Weekly
+        } else if self.hours().len() < 24 {
+            Frequency::Daily
+        } else if self.minutes().len() < 60 {
+            Frequency::Hourly
+        } else {
+            Frequency::Minutely
+        };
+
+        let mut out = String::from("FREQ=");
+        out.push_str(frequency.name());
+        if self.months().len() < 12 {
+            write_part(&mut out, "BYMONTH", self.months());
+        }
+
+        let mut last_weekday = None;
+        let mut business_day = None;
+        match dom {
+            Some(DayOfMonthSpec::Days(days)) => write_part(&mut out, "BYMONTHDAY", days),
+            Some(DayOfMonthSpec::Last { offset }) => {
+                write_part(&mut out, "BYMONTHDAY", Some(-1 - offset as i8))
+            }
+            Some(DayOfMonthSpec::LastWeekday { .. }) => {
+                last_weekday = Some(0);
+                write_part(&mut out, "BYDAY", ["MO", "TU", "WE", "TH", "FR"].iter());
+            }
+            Some(DayOfMonthSpec::LastWeekdayOffset { weekdays }) => {
+                last_weekday = Some(weekdays);
+                write_part(&mut out, "BYDAY", ["MO", "TU", "WE", "TH", "FR"].iter());
+            }
+            Some(DayOfMonthSpec::BusinessDay(n)) => {
+                business_day = Some(n);
+                write_part(&mut out, "BYDAY", ["MO", "TU", "WE", "TH", "FR"].iter());
+            }
+            Some(DayOfMonthSpec::Specials(specials)) => write_part(
+                &mut out,
+                "BYMONTHDAY",
+                specials.filter_map(|spec| match spec {
+                    DayOfMonthSpec::Last { offset } => Some(-1 - offset as i8),
+                    _ => None,
+                }),
+            ),
+            _ => {}
+        }
+        match dow {
+            Some(DayOfWeekSpec::Days(days)) => {
+                write_part(&mut out, "BYDAY", days.map(weekday_code))
+            }
+            Some(DayOfWeekSpec::Last(day)) => {
+                let _ = write!(out, ";BYDAY=-1{}", weekday_code(day));
+            }
+            Some(DayOfWeekSpec::Nth(day, nth)) => {
+                let _ = write!(out, ";BYDAY={}{}", nth, weekday_code(day));
+            }
+            Some(DayOfWeekSpec::Specials(specials)) => {
+                out.push_str(";BYDAY=");
+                for (i, spec) in specials.enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    let _ = match spec {
+                        DayOfWeekSpec::Last(day) => write!(out, "-1{}", weekday_code(day)),
+                        DayOfWeekSpec::Nth(day, nth) => write!(out, "{}{}", nth, weekday_code(day)),
+                        _ => Ok(()),
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        if frequency > Frequency::Hourly {
+            write_part(&mut out, "BYHOUR", self.hours());
+        }
+        if frequency > Frequency::Minutely {
+            write_part(&mut out, "BYMINUTE", self.minutes());
+        }
+
+        if let Some(weekdays) = last_weekday {
+            // BYSETPOS picks from every time in the month, so the last day has to be selected
+            // as the last few times, one for each time of the day, skipping the times of the
+            // weekdays after it
+            let times = self.hours().len() * self.minutes().len();
+            let first = (weekdays as usize + 1) * times;
+            if first > 366 {
+                return Err(NotRepresentable::LastWeekday);
+            }
+            write_part(
+                &mut out,
+                "BYSETPOS",
+                (first - times + 1..=first).rev().map(|i| -(i as i16)),
+            );
+        }
+        if let Some(n) = business_day {
+            // likewise, the nth business day is the nth group of times on weekdays
+            let times = self.hours().len() * self.minutes().len();
+            let last = n as usize * times;
+            if last > 366 {
+                return Err(NotRepresentable::BusinessDay);
+            }
+            write_part(
+                &mut out,
+                "BYSETPOS",
+                (last - times + 1..=last).map(|i| i as i16),
+            );
+        }
+        Ok(out)
+    }
+}
+
+/// A part of a recurrence rule that can't be converted into a cron expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Unsupported {
+    /// A `FREQ` other than `MINUTELY`, `HOURLY`, `DAILY`, `WEEKLY`, or `MONTHLY`
+    Frequency,
+    /// An `INTERVAL` other than 1
+    Interval,
+    /// A `COUNT`, limiting the number of occurrences
+    Count,
+    /// An `UNTIL`, ending the occurrences at a time
+    Until,
+    /// A `BYSECOND` other than 0
+    Seconds,
+    /// A `BYYEARDAY`
+    YearDays,
+    /// A `BYWEEKNO`
+    WeekNumbers,
+    /// A `BYSETPOS` that doesn't select the last weekday, a weekday counted back from it, or the
+    /// nth business day of the month
+    SetPosition,
+    /// A `BYDAY` mixing numbered and unnumbered days, or with days numbered other than 1 to 5
+    /// or -1
+    NthDays,
+    /// A `BYMONTHDAY` mixing days counting from the start and the end of the month
+    LastDays,
+    /// Both a `BYMONTHDAY` and `BYDAY`
+    DaysOfMonthAndWeek,
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Unsupported::Frequency => "rules repeating every second or every year",
+            Unsupported::Interval => "intervals other than 1",
+            Unsupported::Count => "a limited number of occurrences",
+            Unsupported::Until => "an end time",
+            Unsupported::Seconds => "seconds other than 0",
+            Unsupported::YearDays => "days of the year",
+            Unsupported::WeekNumbers => "weeks of the year",
+            Unsupported::SetPosition => {
+                "positions in the set of occurrences other than the last weekdays or nth business day of the month"
+            }
+            Unsupported::NthDays => {
+                "lists mixing numbered and unnumbered days of the week or numbered days other than 1 to 5 or -1"
+            }
+            Unsupported::LastDays => {
+                "lists mixing days counting from the start and the end of the month"
+            }
+            Unsupported::DaysOfMonthAndWeek => "days matching both the day of the month and week",
+        }
+        .fmt(f)
+    }
+}
+
+/// An error indicating that a recurrence rule failed to parse or can't be converted into a cron
+/// expression. Every error carries the byte offset in the rule where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RRuleError {
+    /// A part of the rule is malformed, repeated, or unknown.
+    InvalidSyntax {
+        /// The byte offset of the malformed part or value
+        at: usize,
+    },
+    /// A value is out of the range of valid values for its part.
+    ValueOutOfRange {
+        /// The byte offset of the value
+        at: usize,
+    },
+    /// The rule takes a value from its start time, so it can't be converted without it.
+    MissingPart {
+        /// The name of the part that has to be added to the rule, like `BYHOUR`
+        part: &'static str,
+        /// The byte offset of the end of the rule
+        at: usize,
+    },
+    /// The rule is valid but can't be written as a cron expression.
+    Unsupported {
+        /// The part of the rule that can't be converted
+        feature: Unsupported,
+        /// The byte offset of the part
+        at: usize,
+    },
+}
+
+impl RRuleError {
+    /// Returns the byte offset in the recurrence rule where the error occurred.
+    pub fn position(&self) -> usize {
+        match *self {
+            RRuleError::InvalidSyntax { at }
+            | RRuleError::ValueOutOfRange { at }
+            | RRuleError::MissingPart { at, .. }
+            | RRuleError::Unsupported { at, .. } => at,
+        }
+    }
+}
+
+impl Display for RRuleError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RRuleError::InvalidSyntax { at } => {
+                write!(f, "Invalid recurrence rule at position {}", at)
+            }
+            RRuleError::ValueOutOfRange { at } => {
+                write!(f, "Value at position {} is out of range", at)
+            }
+            RRuleError::MissingPart { part, .. } => write!(
+                f,
+                "Recurrence rule takes its {} from the start time, which a cron expression doesn't have",
+                part
+            ),
+            RRuleError::Unsupported { feature, at } => write!(
+                f,
+                "Recurrence rule matches {} at position {}, which can't be written as a cron expression",
+                feature, at
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RRuleError {}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sun,
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+];
+
+/// A `NAME=VALUE` part of a rule
+#[derive(Clone, Copy)]
+struct Part<'a> {
+    /// The byte offset of the name
+    start: usize,
+    /// The byte offset of the value
+    at: usize,
+    value: &'a str,
+}
+
+impl<'a> Part<'a> {
+    /// Returns the comma separated items of the value and their byte offsets
+    fn items(self) -> impl Iterator<Item = (usize, &'a str)> {
+        let mut offset = self.at;
+        self.value.split(',').map(move |item| {
+            let at = offset;
+            offset += item.len() + 1;
+            (at, item)
+        })
+    }
+
+    fn unsupported(self, feature: Unsupported) -> RRuleError {
+        RRuleError::Unsupported {
+            feature,
+            at: self.start,
+        }
+    }
+}
+
+/// The parts of a rule, each set at most once
+#[derive(Default)]
+struct Rule<'a> {
+    freq: Option<Part<'a>>,
+    interval: Option<Part<'a>>,
+    count: Option<Part<'a>>,
+    until: Option<Part<'a>>,
+    by_second: Option<Part<'a>>,
+    by_minute: Option<Part<'a>>,
+    by_hour: Option<Part<'a>>,
+    by_day: Option<Part<'a>>,
+    by_month_day: Option<Part<'a>>,
+    by_year_day: Option<Part<'a>>,
+    by_week_no: Option<Part<'a>>,
+    by_month: Option<Part<'a>>,
+    by_set_pos: Option<Part<'a>>,
+    week_start: Option<Part<'a>>,
+}
+
+impl<'a> Rule<'a> {
+    fn slot(&mut self, name: &str) -> Option<&mut Option<Part<'a>>> {
+        let is = |part: &str| name.eq_ignore_ascii_case(part);
+        let slot = if is("FREQ") {
+            &mut self.freq
+        } else if is("INTERVAL") {
+            &mut self.interval
+        } else if is("COUNT") {
+            &mut self.count
+        } else if is("UNTIL") {
+            &mut self.until
+        } else if is("BYSECOND") {
+            &mut self.by_second
+        } else if is("BYMINUTE") {
+            &mut self.by_minute
+        } else if is("BYHOUR") {
+            &mut self.by_hour
+        } else if is("BYDAY") {
+            &mut self.by_day
+        } else if is("BYMONTHDAY") {
+            &mut self.by_month_day
+        } else if is("BYYEARDAY") {
+            &mut self.by_year_day
+        } else if is("BYWEEKNO") {
+            &mut self.by_week_no
+        } else if is("BYMONTH") {
+            &mut self.by_month
+        } else if is("BYSETPOS") {
+            &mut self.by_set_pos
+        } else if is("WKST") {
+            &mut self.week_start
+        } else {
+            return None;
+        };
+        Some(slot)
+    }
+}
+
+/// Parses a signed integer value
+fn integer(item: &str, at: usize) -> Result<i32, RRuleError> {
+    let digits = item.strip_prefix(&['+', '-'][..]).unwrap_or(item);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(RRuleError::InvalidSyntax { at });
+    }
+    item.parse().map_err(|_| RRuleError::ValueOutOfRange { at })
+}
+
+fn weekday(code: &str, at: usize) -> Result<Weekday, RRuleError> {
+    WEEKDAYS
+        .iter()
+        .copied()
+        .find(|&day| weekday_code(day).eq_ignore_ascii_case(code))
+        .ok_or(RRuleError::InvalidSyntax { at })
+}
+
+/// Parses a list of values into a bit-mask of the values
+fn values(part: Part, min: u8, max: u8) -> Result<u64, RRuleError> {
+    let mut bits = 0;
+    for (at, item) in part.items() {
+        match integer(item, at)? {
+            value if value < min as i32 || value > max as i32 => {
+                return Err(RRuleError::ValueOutOfRange { at })
+            }
+            value => bits |= 1 << value,
+        }
+    }
+    Ok(bits)
+}
+
+/// Converts a bit-mask of values into a list of the values
+fn field<E: TryFrom<u8, Error = ValueOutOfRangeError>>(bits: u64) -> Exprs<E> {
+    let mut values = (0..64u8)
+        .filter(|&value| bits & (1 << value) != 0)
+        .map(|value| OrsExpr::One(E::try_from(value).expect("Values are in range")));
+    let mut exprs = Exprs::new(values.next().expect("Lists have at least one value"));
+    exprs.tail.extend(values);
+    exprs
+}
+
+/// Parses a list of values of a time field, or returns every value if the field repeats at a
+/// shorter interval than the rule.
+fn time_field<E>(
+    part: Option<Part>,
+    name: &'static str,
+    repeats: bool,
+    end: usize,
+) -> Result<(Expr<E>, u32), RRuleError>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError>,
+{
+    match part {
+        Some(part) => {
+            let bits = values(part, E::MIN, E::MAX)?;
+            Ok((Expr::Many(field(bits)), bits.count_ones()))
+        }
+        None if repeats => Ok((Expr::All, (E::MAX - E::MIN + 1) as u32)),
+        None => Err(RRuleError::MissingPart {
+            part: name,
+            at: end,
+        }),
+    }
+}
+
+/// Parses an iCalendar recurrence rule (`RRULE`) into a cron expression, with or without the
+/// `RRULE:` prefix. Rules repeating every minute, hour, day, week, or month are supported if
+/// they don't take any values from the start time, so `BYHOUR` and `BYMINUTE` have to be set
+/// on rules repeating every day or longer.
+///
+/// The rule is converted as if it's in UTC, and its start time is expected to be at zero
+/// seconds and matched by the rule.
+///
+/// # Example
+/// ```
+/// use saffron::rrule::{self, RRuleError, Unsupported};
+///
+/// let expr = rrule::parse("FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=9;BYMINUTE=30").unwrap();
+/// assert_eq!(expr.to_string(), "30 9 * * 2/2");
+///
+/// let expr = rrule::parse("RRULE:FREQ=MONTHLY;BYDAY=-1FR;BYHOUR=17;BYMINUTE=0").unwrap();
+/// assert_eq!(expr.to_string(), "0 17 ? * 6L");
+///
+/// assert_eq!(
+///     rrule::parse("FREQ=DAILY;COUNT=10;BYHOUR=0;BYMINUTE=0"),
+///     Err(RRuleError::Unsupported { feature: Unsupported::Count, at: 11 })
+/// );
+/// ```
+pub fn parse(rule: &str) -> Result<CronExpr, RRuleError> {
+    let start = match rule.get(..6) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => 6,
+        _ => 0,
+    };
+
+    let mut parts = Rule::default();
+    let mut offset = start;
+    for part in rule[start..].split(';') {
+        let at = offset;
+        offset += part.len() + 1;
+
+        let eq = part.find('=').ok_or(RRuleError::InvalidSyntax { at })?;
+        let slot = parts
+            .slot(&part[..eq])
+            .filter(|slot| slot.is_none())
+            .ok_or(RRuleError::InvalidSyntax { at })?;
+        *slot = Some(Part {
+            start: at,
+            at: at + eq + 1,
+            value: &part[eq + 1..],
+        });
+    }
+
+    let end = rule.len();
+    let freq = parts.freq.ok_or(RRuleError::MissingPart {
+        part: "FREQ",
+        at: end,
+    })?;
+    let frequency = match freq.value {
+        value if value.eq_ignore_ascii_case("MINUTELY") => Frequency::Minutely,
+        value if value.eq_ignore_ascii_case("HOURLY") => Frequency::Hourly,
+        value if value.eq_ignore_ascii_case("DAILY") => Frequency::Daily,
+        value if value.eq_ignore_ascii_case("WEEKLY") => Frequency::Weekly,
+        value if value.eq_ignore_ascii_case("MONTHLY") => Frequency::Monthly,
+        value if value.eq_ignore_ascii_case("SECONDLY") || value.eq_ignore_ascii_case("YEARLY") => {
+            return Err(freq.unsupported(Unsupported::Frequency))
+        }
+        _ => return Err(RRuleError::InvalidSyntax { at: freq.at }),
+    };
+
+    if let Some(interval) = parts.interval {
+        match integer(interval.value, interval.at)? {
+            1 => {}
+            value if value < 1 => return Err(RRuleError::ValueOutOfRange { at: interval.at }),
+            _ => return Err(interval.unsupported(Unsupported::Interval)),
+        }
+    }
+    if let Some(week_start) = parts.week_start {
+        // the start of the week only matters for weekly rules with an interval
+        weekday(week_start.value, week_start.at)?;
+    }
+    for &(part, feature) in &[
+        (parts.count, Unsupported::Count),
+        (parts.until, Unsupported::Until),
+        (parts.by_year_day, Unsupported::YearDays),
+        (parts.by_week_no, Unsupported::WeekNumbers),
+    ] {
+        if let Some(part) = part {
+            return Err(part.unsupported(feature));
+        }
+    }
+    if let Some(by_second) = parts.by_second {
+        if values(by_second, 0, 60)? != 1 {
+            return Err(by_second.unsupported(Unsupported::Seconds));
+        }
+    }
+
+    let (hours, hour_count) =
+        time_field::<Hour>(parts.by_hour, "BYHOUR", frequency <= Frequency::Hourly, end)?;
+    let (minutes, minute_count) = time_field::<Minute>(
+        parts.by_minute,
+        "BYMINUTE",
+        frequency == Frequency::Minutely,
+        end,
+    )?;
+    let months = match parts.by_month {
+        Some(part) => Expr::Many(field(values(part, Month::MIN, Month::MAX)?)),
+        None => Expr::All,
+    };
+
+    let mut doms = match parts.by_month_day {
+        Some(part) => month_days(part)?,
+        None => DayOfMonthExpr::All,
+    };
+    let mut dows = match parts.by_day {
+        Some(part) => weekdays(part, frequency)?,
+        None => DayOfWeekExpr::All,
+    };
+    if let DayOfWeekExpr::Last(_) | DayOfWeekExpr::Nth(..) | DayOfWeekExpr::Specials(_) = dows {
+        doms = DayOfMonthExpr::Ignored;
+    }
+    if let (Some(by_month_day), Some(_)) = (parts.by_month_day, parts.by_day) {
+        return Err(by_month_day.unsupported(Unsupported::DaysOfMonthAndWeek));
+    }
+    match (frequency, parts.by_day, parts.by_month_day) {
+        (Frequency::Weekly, None, _) => {
+            return Err(RRuleError::MissingPart {
+                part: "BYDAY",
+                at: end,
+            })
+        }
+        (Frequency::Monthly, None, None) => {
+            return Err(RRuleError::MissingPart {
+                part: "BYMONTHDAY",
+                at: end,
+            })
+        }
+        _ => {}
+    }
+
+    if let Some(by_set_pos) = parts.by_set_pos {
+        // the last weekday of the month is the last of the times on weekdays in the month, and
+        // the nth business day is the nth group of times on weekdays in the month, counting
+        // from the end for the weekdays before the last
+        let times = (minute_count * hour_count) as i32;
+        let positions = by_set_pos
+            .items()
+            .map(|(at, item)| integer(item, at))
+            .collect::<Result<Vec<_>, _>>()?;
+        let weekdays = Weekday::Mon.num_days_from_sunday()..=Weekday::Fri.num_days_from_sunday();
+        let on_weekdays = frequency == Frequency::Monthly
+            && dows
+                == DayOfWeekExpr::Many(field(weekdays.fold(0, |bits, day| bits | 1 << (day + 1))))
+            && positions.len() as i32 == times;
+        let day = match positions.first() {
+            Some(_) if !on_weekdays => None,
+            Some(&first) if first > 0 && (first - 1) % times == 0 => {
+                let consecutive = positions.iter().zip(first..).all(|(&p, i)| p == i);
+                u8::try_from((first - 1) / times + 1)
+                    .ok()
+                    .and_then(|n| BusinessDay::try_from(n).ok())
+                    .filter(|_| consecutive)
+                    .map(DayOfMonthExpr::BusinessDay)
+            }
+            Some(&first) if first < 0 && -first % times == 0 => {
+                let consecutive = positions.iter().zip(first..).all(|(&p, i)| p == i);
+                match u8::try_from(-first / times - 1) {
+                    _ if !consecutive => None,
+                    Ok(0) => Some(DayOfMonthExpr::Last(Last::Weekday)),
+                    Ok(weekdays) => DayOfMonthOffset::try_from(weekdays)
+                        .ok()
+                        .map(|offset| DayOfMonthExpr::Last(Last::WeekdayOffset(offset))),
+                    Err(_) => None,
+                }
+            }
+            _ => None,
+        };
+        doms = match day {
+            Some(day) => day,
+            None => return Err(by_set_pos.unsupported(Unsupported::SetPosition)),
+        };
+        dows = DayOfWeekExpr::All;
+    }
+
+    Ok(CronExpr {
+        minutes,
+        hours,
+        doms,
+        months,
+        dows,
+        weeks: Expr::All,
+        shortcut: None,
+        exclusions: Exclusions::default(),
+        days_and: false,
+    }
+    .normalize())
+}
+
+/// Parses a `BYMONTHDAY`, a list of days or a list of days counting back from the end of the
+/// month
+fn month_days(part: Part) -> Result<DayOfMonthExpr, RRuleError> {
+    let mut bits = 0;
+    let mut last_days = Vec::new();
+    for (at, item) in part.items() {
+        match integer(item, at)? {
+            day @ 1..=31 => bits |= 1 << day,
+            -1 => last_days.push(Last::Day),
+            day @ -31..=-2 => {
+                let offset = DayOfMonthOffset::try_from((-day - 1) as u8)
+                    .map_err(|_| RRuleError::ValueOutOfRange { at })?;
+                last_days.push(Last::Offset(offset));
+            }
+            _ => return Err(RRuleError::ValueOutOfRange { at }),
+        }
+    }
+
+    match last_days.len() {
+        0 => Ok(DayOfMonthExpr::Many(field(bits))),
+        _ if bits != 0 => Err(part.unsupported(Unsupported::LastDays)),
+        1 => Ok(DayOfMonthExpr::Last(last_days.remove(0))),
+        _ => Ok(DayOfMonthExpr::Specials(
+            last_days.into_iter().map(DayOfMonthSpecial::Last).collect(),
+        )),
+    }
+}
+
+/// Parses a `BYDAY`, a list of days of the week or a list of numbered days of the week
+fn weekdays(part: Part, frequency: Frequency) -> Result<DayOfWeekExpr, RRuleError> {
+    let mut bits = 0;
+    let mut numbered = Vec::new();
+    for (at, item) in part.items() {
+        let code_at = item.len().saturating_sub(2);
+        let (number, code) = match (item.get(..code_at), item.get(code_at..)) {
+            (Some(number), Some(code)) => (number, code),
+            _ => return Err(RRuleError::InvalidSyntax { at }),
+        };
+        let day = weekday(code, at + code_at)?;
+        if code_at == 0 {
+            bits |= 1 << (day.num_days_from_sunday() + 1);
+            continue;
+        }
+
+        // numbered days are only valid in rules repeating every month or year
+        if frequency != Frequency::Monthly {
+            return Err(RRuleError::InvalidSyntax { at });
+        }
+        let special = match integer(number, at)? {
+            -1 => DayOfWeekSpecial::Last(day.into()),
+            nth => match u8::try_from(nth).ok().map(NthDay::try_from) {
+                Some(Ok(nth)) => DayOfWeekSpecial::Nth(day.into(), nth),
+                _ if nth == 0 || !(-53..=53).contains(&nth) => {
+                    return Err(RRuleError::ValueOutOfRange { at })
+                }
+                _ => return Err(part.unsupported(Unsupported::NthDays)),
+            },
+        };
+        numbered.push(special);
+    }
+
+    match numbered.len() {
+        0 => Ok(DayOfWeekExpr::Many(field(bits))),
+        _ if bits != 0 => Err(part.unsupported(Unsupported::NthDays)),
+        1 => Ok(numbered.remove(0).into()),
+        _ => Ok(DayOfWeekExpr::Specials(numbered)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cron, CronOptions, DayPolicy};
+
+    use super::{parse, NotRepresentable, RRuleError, Unsupported};
+    use crate::parse::{CronExpr, Dialect};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{
+        format,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
+    fn rrule(cron: &str) -> Result<String, NotRepresentable> {
+        cron.parse::<Cron>().unwrap().to_rrule()
+    }
+
+    #[test]
+    fn frequencies() {
+        assert_eq!(rrule("* * * * *").unwrap(), "FREQ=MINUTELY");
+        assert_eq!(
+            rrule("*/15 * * * *").unwrap(),
+            "FREQ=HOURLY;BYMINUTE=0,15,30,45"
+        );
+        assert_eq!(
+            rrule("0 22-23 * * *").unwrap(),
+            "FREQ=DAILY;BYHOUR=22,23;BYMINUTE=0"
+        );
+        assert_eq!(
+            rrule("0 0 * * SAT,SUN").unwrap(),
+            "FREQ=WEEKLY;BYDAY=SU,SA;BYHOUR=0;BYMINUTE=0"
+        );
+        assert_eq!(
+            rrule("0 12 1,15 * *").unwrap(),
+            "FREQ=MONTHLY;BYMONTHDAY=1,15;BYHOUR=12;BYMINUTE=0"
+        );
+        assert_eq!(
+            rrule("* 9 * JAN,JUL *").unwrap(),
+            format!(
+                "FREQ=DAILY;BYMONTH=1,7;BYHOUR=9;BYMINUTE={}",
+                (0..60).map(|m| m.to_string()).collect::<Vec<_>>().join(",")
+            )
+        );
+    }
+
+    #[test]
+    fn last_days() {
+        assert_eq!(
+            rrule("0 0 L * *").unwrap(),
+            "FREQ=MONTHLY;BYMONTHDAY=-1;BYHOUR=0;BYMINUTE=0"
+        );
+        assert_eq!(
+            rrule("0 0 L-2 FEB *").unwrap(),
+            "FREQ=MONTHLY;BYMONTH=2;BYMONTHDAY=-3;BYHOUR=0;BYMINUTE=0"
+        );
+        assert_eq!(
+            rrule("0 0 ? * 6L").unwrap(),
+            "FREQ=MONTHLY;BYDAY=-1FR;BYHOUR=0;BYMINUTE=0"
+        );
+        assert_eq!(
+            rrule("0 9 LW * *").unwrap(),
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0;BYSETPOS=-1"
+        );
+        assert_eq!(
+            rrule("0,30 9 LW * *").unwrap(),
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0,30;BYSETPOS=-2,-1"
+        );
+        assert_eq!(
+            rrule("0,30 9 3B * *").unwrap(),
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0,30;BYSETPOS=5,6"
+        );
+        assert_eq!(
+            rrule("0,30 9 LW-1 * *").unwrap(),
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0,30;BYSETPOS=-4,-3"
+        );
+        assert_eq!(
+            rrule("0 0 L,L-2 * *").unwrap(),
+            "FREQ=MONTHLY;BYMONTHDAY=-1,-3;BYHOUR=0;BYMINUTE=0"
+        );
+        assert_eq!(
+            rrule("0 0 ? * MON#1,MON#3,FRIL").unwrap(),
+            "FREQ=MONTHLY;BYDAY=-1FR,1MO,3MO;BYHOUR=0;BYMINUTE=0"
+        );
+    }
+
+    #[test]
+    fn both_days() {
+        let mut options = CronOptions::new();
+        options.day_policy = DayPolicy::And;
+        let and = |cron: &str| Cron::with_options(cron.parse().unwrap(), options.clone()).unwrap();
+
+        assert_eq!(
+            and("0 0 13 * FRI").to_rrule().unwrap(),
+            "FREQ=MONTHLY;BYMONTHDAY=13;BYDAY=FR;BYHOUR=0;BYMINUTE=0"
+        );
+        assert_eq!(
+            and("0 0 13 * FRI#2").to_rrule(),
+            Err(NotRepresentable::BothDays)
+        );
+        assert_eq!(
+            and("0 0 LW * MON").to_rrule(),
+            Err(NotRepresentable::BothDays)
+        );
+        assert_eq!(rrule("0 0 13 * FRI"), Err(NotRepresentable::EitherDay));
+    }
+
+    #[test]
+    fn not_representable() {
+        assert_eq!(rrule("0 0 15W * *"), Err(NotRepresentable::ClosestWeekday));
+        assert_eq!(rrule("0 0 L-3W * *"), Err(NotRepresentable::ClosestWeekday));
+        assert_eq!(rrule("* 0-6 LW * *"), Err(NotRepresentable::LastWeekday));
+        assert_eq!(rrule("*/5 * 2B * *"), Err(NotRepresentable::BusinessDay));
+        assert_eq!(
+            rrule("0 0 1W,15W * *"),
+            Err(NotRepresentable::ClosestWeekday)
+        );
+        assert_eq!(rrule("0 0 1-7W * *"), Err(NotRepresentable::ClosestWeekday));
+        assert_eq!(rrule("0 0 1W,L * *"), Err(NotRepresentable::Specials));
+        assert_eq!(rrule("0 0 L,LW * *"), Err(NotRepresentable::Specials));
+
+        let expr = CronExpr::from_str_with("0 9 * * MON W/2", Dialect::Extended).unwrap();
+        assert_eq!(Cron::new(expr).to_rrule(), Err(NotRepresentable::Weeks));
+    }
+
+    #[track_caller]
+    fn assert_parse(rule: &str, cron: &str) {
+        let expected: CronExpr = cron.parse().expect("Valid cron expression");
+        assert_eq!(parse(rule), Ok(expected.normalize()), "{}", rule);
+    }
+
+    #[test]
+    fn parse_rules() {
+        assert_parse("FREQ=MINUTELY", "* * * * *");
+        assert_parse("RRULE:FREQ=HOURLY;BYMINUTE=0,30", "0,30 * * * *");
+        assert_parse("freq=daily;byhour=9,17;byminute=0", "0 9,17 * * *");
+        assert_parse(
+            "FREQ=DAILY;INTERVAL=1;BYHOUR=12;BYMINUTE=0;BYSECOND=0",
+            "0 12 * * *",
+        );
+        assert_parse(
+            "FREQ=WEEKLY;WKST=SU;BYDAY=MO,TU,WE,TH,FR;BYHOUR=9;BYMINUTE=0",
+            "0 9 * * MON-FRI",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYMONTH=1,4,7,10;BYMONTHDAY=1,15;BYHOUR=0;BYMINUTE=0",
+            "0 0 1,15 JAN,APR,JUL,OCT *",
+        );
+    }
+
+    #[test]
+    fn parse_last_days() {
+        assert_parse(
+            "FREQ=MONTHLY;BYMONTHDAY=-1;BYHOUR=0;BYMINUTE=0",
+            "0 0 L * *",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYMONTHDAY=-3;BYHOUR=0;BYMINUTE=0",
+            "0 0 L-2 * *",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=2MO;BYHOUR=0;BYMINUTE=0",
+            "0 0 ? * MON#2",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=-1FR;BYHOUR=0;BYMINUTE=0",
+            "0 0 ? * FRIL",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=1MO,3MO;BYHOUR=0;BYMINUTE=0",
+            "0 0 ? * MON#1,MON#3",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYMONTHDAY=-1,-3;BYHOUR=0;BYMINUTE=0",
+            "0 0 L,L-2 * *",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;BYHOUR=9;BYMINUTE=0",
+            "0 9 LW * *",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-2,-1;BYHOUR=9;BYMINUTE=0,30",
+            "0,30 9 LW * *",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-4,-3;BYHOUR=9;BYMINUTE=0,30",
+            "0,30 9 LW-1 * *",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=1;BYHOUR=0;BYMINUTE=0",
+            "0 0 1B * *",
+        );
+        assert_parse(
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=5,6;BYHOUR=9;BYMINUTE=0,30",
+            "0,30 9 3B * *",
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for &cron in &[
+            "* * * * *",
+            "*/15 * * * *",
+            "0 9-17 * * MON-FRI",
+            "30 12 1,15 * *",
+            "0 0 L-2 FEB *",
+            "0 0 ? * FRI#2",
+            "0 0 ? * 6L",
+            "0,30 9 LW * *",
+            "0 0 10B * *",
+            "0 0 * JAN-MAR *",
+            "0 0 ? * MON#1,MON#3",
+            "0 0 L,L-2 * *",
+        ] {
+            let cron: Cron = cron.parse().unwrap();
+            let rule = cron.to_rrule().unwrap();
+            assert_eq!(Cron::new(parse(&rule).unwrap()), cron, "{}", rule);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |rule: &str| parse(rule).unwrap_err();
+        let unsupported = |feature, at| RRuleError::Unsupported { feature, at };
+
+        assert_eq!(
+            err("FREQ=DAILY;COUNT=5;BYHOUR=0;BYMINUTE=0"),
+            unsupported(Unsupported::Count, 11)
+        );
+        assert_eq!(
+            err("FREQ=DAILY;UNTIL=20300101T000000Z;BYHOUR=0;BYMINUTE=0"),
+            unsupported(Unsupported::Until, 11)
+        );
+        assert_eq!(
+            err("FREQ=YEARLY;BYMONTH=1"),
+            unsupported(Unsupported::Frequency, 0)
+        );
+        assert_eq!(
+            err("FREQ=HOURLY;INTERVAL=2;BYMINUTE=0"),
+            unsupported(Unsupported::Interval, 12)
+        );
+        assert_eq!(
+            err("FREQ=MINUTELY;BYSECOND=30"),
+            unsupported(Unsupported::Seconds, 14)
+        );
+        assert_eq!(
+            err("FREQ=MONTHLY;BYDAY=1MO,TU;BYHOUR=0;BYMINUTE=0"),
+            unsupported(Unsupported::NthDays, 13)
+        );
+        assert_eq!(
+            err("FREQ=MONTHLY;BYDAY=-2MO;BYHOUR=0;BYMINUTE=0"),
+            unsupported(Unsupported::NthDays, 13)
+        );
+        assert_eq!(
+            err("FREQ=MONTHLY;BYMONTHDAY=-1,2;BYHOUR=0;BYMINUTE=0"),
+            unsupported(Unsupported::LastDays, 13)
+        );
+        assert_eq!(
+            err("FREQ=MONTHLY;BYMONTHDAY=13;BYDAY=FR;BYHOUR=0;BYMINUTE=0"),
+            unsupported(Unsupported::DaysOfMonthAndWeek, 13)
+        );
+        assert_eq!(
+            err("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=2;BYHOUR=0;BYMINUTE=0,30"),
+            unsupported(Unsupported::SetPosition, 34)
+        );
+        assert_eq!(
+            err("FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;BYHOUR=0;BYMINUTE=0,30"),
+            unsupported(Unsupported::SetPosition, 34)
+        );
+
+        assert_eq!(
+            err("FREQ=DAILY;BYMINUTE=0"),
+            RRuleError::MissingPart {
+                part: "BYHOUR",
+                at: 21
+            }
+        );
+        assert_eq!(
+            err("FREQ=WEEKLY;BYHOUR=0;BYMINUTE=0"),
+            RRuleError::MissingPart {
+                part: "BYDAY",
+                at: 31
+            }
+        );
+        assert_eq!(
+            err("BYHOUR=0"),
+            RRuleError::MissingPart {
+                part: "FREQ",
+                at: 8
+            }
+        );
+
+        assert_eq!(
+            err("FREQ=DAILY;BYHOUR=24;BYMINUTE=0"),
+            RRuleError::ValueOutOfRange { at: 18 }
+        );
+        assert_eq!(
+            err("FREQ=MONTHLY;BYDAY=0MO;BYHOUR=0;BYMINUTE=0"),
+            RRuleError::ValueOutOfRange { at: 19 }
+        );
+        assert_eq!(
+            err("FREQ=WEEKLY;BYDAY=2MO;BYHOUR=0;BYMINUTE=0"),
+            RRuleError::InvalidSyntax { at: 18 }
+        );
+        assert_eq!(
+            err("FREQ=DAILY;FREQ=DAILY"),
+            RRuleError::InvalidSyntax { at: 11 }
+        );
+        assert_eq!(
+            err("FREQ=DAILY;BYHOUR=x"),
+            RRuleError::InvalidSyntax { at: 18 }
+        );
+        assert_eq!(
+            err("FREQ=DAILY;FOO=1"),
+            RRuleError::InvalidSyntax { at: 11 }
+        );
+        assert_eq!(err("FREQ"), RRuleError::InvalidSyntax { at: 0 });
+    }
+}
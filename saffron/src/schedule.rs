@@ -0,0 +1,279 @@
+//! Schedules producing firing times, like cron values, intervals, and sets of them.
+
+use crate::interval::{Interval, IntervalTimesIter};
+use crate::{Calendar, CalendarCron, Cron, CronSet, CronTimesIter};
+use chrono::{DateTime, Duration, Utc};
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+
+/// Anything that produces firing times, so schedulers can be generic over the kind of schedule
+/// instead of only taking cron values.
+///
+/// # Example
+/// ```
+/// use saffron::{Cron, Interval, Schedule};
+/// use chrono::{prelude::*, Duration};
+///
+/// fn fires_within<S: Schedule>(schedule: &S, now: DateTime<Utc>, within: Duration) -> bool {
+///     schedule.next_from(now).map_or(false, |next| next - now <= within)
+/// }
+///
+/// let now = Utc.with_ymd_and_hms(2021, 1, 1, 0, 10, 0).unwrap();
+/// let cron: Cron = "0 * * * *".parse().unwrap();
+/// let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+/// let interval = Interval::new(start, Duration::minutes(15));
+/// assert!(!fires_within(&cron, now, Duration::minutes(30)));
+/// assert!(fires_within(&interval, now, Duration::minutes(30)));
+/// ```
+pub trait Schedule {
+    /// Returns whether the schedule matches the given time.
+    fn contains(&self, dt: DateTime<Utc>) -> bool;
+
+    /// Returns the next time the schedule will match including the given date.
+    fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>>;
+
+    /// Returns the next time the schedule will match after the given date.
+    fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>>;
+}
+
+impl<S: Schedule + ?Sized> Schedule for &S {
+    fn contains(&self, dt: DateTime<Utc>) -> bool {
+        (**self).contains(dt)
+    }
+
+    fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (**self).next_from(start)
+    }
+
+    fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (**self).next_after(start)
+    }
+}
+
+impl Schedule for Cron {
+    fn contains(&self, dt: DateTime<Utc>) -> bool {
+        Cron::contains(self, dt)
+    }
+
+    fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Cron::next_from(self, start)
+    }
+
+    fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Cron::next_after(self, start)
+    }
+}
+
+impl Schedule for Interval {
+    fn contains(&self, dt: DateTime<Utc>) -> bool {
+        Interval::contains(self, dt)
+    }
+
+    fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Interval::next_from(self, start)
+    }
+
+    fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Interval::next_after(self, start)
+    }
+}
+
+impl Schedule for CronSet {
+    fn contains(&self, dt: DateTime<Utc>) -> bool {
+        CronSet::contains(self, dt)
+    }
+
+    fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        CronSet::next_from(self, start)
+    }
+
+    fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        CronSet::next_after(self, start)
+    }
+}
+
+impl Schedule for AnySchedule {
+    fn contains(&self, dt: DateTime<Utc>) -> bool {
+        AnySchedule::contains(self, dt)
+    }
+
+    fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        AnySchedule::next_from(self, start)
+    }
+
+    fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        AnySchedule::next_after(self, start)
+    }
+}
+
+impl<C: Calendar> Schedule for CalendarCron<C> {
+    fn contains(&self, dt: DateTime<Utc>) -> bool {
+        CalendarCron::contains(self, dt)
+    }
+
+    fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        CalendarCron::next_from(self, start)
+    }
+
+    fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        CalendarCron::next_after(self, start)
+    }
+}
+
+/// A cron value or an interval. Both match times with the same API, and either can be added to
+/// a [`CronSet`].
+///
+/// [`CronSet`]: struct.CronSet.html
+///
+/// # Example
+/// ```
+/// use saffron::{AnySchedule, Cron, CronSet};
+/// use chrono::{prelude::*, Duration};
+///
+/// let anchor = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+/// let mut set = CronSet::new();
+/// set.push_schedule(AnySchedule::interval(anchor, Duration::minutes(90)));
+/// set.push_schedule(AnySchedule::from("0 12 * * *".parse::<Cron>().unwrap()));
+///
+/// let times = set.iter_after(anchor).take(3).collect::<Vec<_>>();
+/// assert_eq!(
+///     times,
+///     [
+///         Utc.with_ymd_and_hms(2021, 1, 1, 1, 30, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2021, 1, 1, 3, 0, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2021, 1, 1, 4, 30, 0).unwrap(),
+///     ]
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum AnySchedule {
+    /// A cron value
+    Cron(Cron),
+    /// A fixed period from an anchor time
+    Interval(Interval),
+}
+
+impl AnySchedule {
+    /// Creates a schedule matching the anchor and every multiple of the period after it. This
+    /// is short for `AnySchedule::Interval(Interval::new(anchor, period))`.
+    ///
+    /// # Panics
+    /// Panics if the period isn't positive.
+    pub fn interval(anchor: DateTime<Utc>, period: Duration) -> Self {
+        AnySchedule::Interval(Interval::new(anchor, period))
+    }
+
+    /// Returns whether the schedule will ever match any given time.
+    pub fn any(&self) -> bool {
+        match self {
+            AnySchedule::Cron(cron) => cron.any(),
+            AnySchedule::Interval(_) => true,
+        }
+    }
+
+    /// Returns whether the schedule matches the given time.
+    pub fn contains(&self, dt: DateTime<Utc>) -> bool {
+        match self {
+            AnySchedule::Cron(cron) => cron.contains(dt),
+            AnySchedule::Interval(interval) => interval.contains(dt),
+        }
+    }
+
+    /// Returns the next time the schedule will match including the given date.
+    pub fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            AnySchedule::Cron(cron) => cron.next_from(start),
+            AnySchedule::Interval(interval) => interval.next_from(start),
+        }
+    }
+
+    /// Returns the next time the schedule will match after the given date.
+    pub fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            AnySchedule::Cron(cron) => cron.next_after(start),
+            AnySchedule::Interval(interval) => interval.next_after(start),
+        }
+    }
+
+    /// Creates an iterator of date times that match the schedule. This is short for
+    /// `iter((Bound::Included(start), Bound::Unbounded))` or `iter(start..)`.
+    #[inline]
+    pub fn iter_from(self, start: DateTime<Utc>) -> ScheduleTimesIter {
+        self.iter((Bound::Included(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match the schedule after the given date. This is
+    /// short for `iter((Bound::Excluded(start), Bound::Unbounded))`.
+    #[inline]
+    pub fn iter_after(self, start: DateTime<Utc>) -> ScheduleTimesIter {
+        self.iter((Bound::Excluded(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match the schedule using the given start and end
+    /// range bounds, like [`Cron::iter`] and [`Interval::iter`].
+    ///
+    /// [`Cron::iter`]: struct.Cron.html#method.iter
+    /// [`Interval::iter`]: struct.Interval.html#method.iter
+    pub fn iter<R: RangeBounds<DateTime<Utc>>>(self, bounds: R) -> ScheduleTimesIter {
+        ScheduleTimesIter(match self {
+            AnySchedule::Cron(cron) => TimesIter::Cron(cron.iter(bounds)),
+            AnySchedule::Interval(interval) => TimesIter::Interval(interval.iter(bounds)),
+        })
+    }
+}
+
+impl From<Cron> for AnySchedule {
+    fn from(cron: Cron) -> Self {
+        AnySchedule::Cron(cron)
+    }
+}
+
+impl From<Interval> for AnySchedule {
+    fn from(interval: Interval) -> Self {
+        AnySchedule::Interval(interval)
+    }
+}
+
+/// An iterator over the times matching a schedule. Created with [`AnySchedule::iter`],
+/// [`AnySchedule::iter_from`], and [`AnySchedule::iter_after`].
+///
+/// [`AnySchedule::iter`]: enum.Schedule.html#method.iter
+/// [`AnySchedule::iter_from`]: enum.Schedule.html#method.iter_from
+/// [`AnySchedule::iter_after`]: enum.Schedule.html#method.iter_after
+#[derive(Clone)]
+pub struct ScheduleTimesIter(TimesIter);
+
+#[derive(Clone)]
+enum TimesIter {
+    Cron(CronTimesIter),
+    Interval(IntervalTimesIter),
+}
+
+impl Iterator for ScheduleTimesIter {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            TimesIter::Cron(iter) => iter.next(),
+            TimesIter::Interval(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            TimesIter::Cron(iter) => iter.size_hint(),
+            TimesIter::Interval(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl DoubleEndedIterator for ScheduleTimesIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            TimesIter::Cron(iter) => iter.next_back(),
+            TimesIter::Interval(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl FusedIterator for ScheduleTimesIter {}
@@ -0,0 +1,434 @@
+//! A small in-process scheduler that runs callbacks at the times cron values match.
+//!
+//! A [`Scheduler`] keeps its jobs in a queue ordered by each job's next time. It doesn't keep
+//! time itself: [`Scheduler::run_pending`] runs the jobs due at a given time, and
+//! [`Scheduler::next_wakeup`] returns when it should be called next, so it can be driven by any
+//! loop. [`Scheduler::spawn`] runs one on a background thread.
+//!
+//! [`Scheduler`]: struct.Scheduler.html
+//! [`Scheduler::run_pending`]: struct.Scheduler.html#method.run_pending
+//! [`Scheduler::next_wakeup`]: struct.Scheduler.html#method.next_wakeup
+//! [`Scheduler::spawn`]: struct.Scheduler.html#method.spawn
+
+use crate::{minute_floor, next_minute, Cron};
+
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::SystemTime;
+
+/// An identifier for a job added to a scheduler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JobId(u64);
+
+/// A run of a job, passed to its callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Tick {
+    /// The job being run
+    pub job: JobId,
+    /// The time the job was scheduled to run at
+    pub scheduled: DateTime<Utc>,
+    /// The number of times the job matched after the scheduled time that were missed because the
+    /// scheduler ran late. Missed times are collapsed into this run instead of running the job
+    /// for each of them.
+    pub missed: u64,
+}
+
+type Callback = Box<dyn FnMut(Tick) + Send>;
+
+struct Job {
+    cron: Cron,
+    callback: Callback,
+    next: Option<DateTime<Utc>>,
+}
+
+/// A set of cron jobs and a queue of the next time each will run.
+///
+/// # Example
+/// ```
+/// use saffron::{Cron, scheduler::Scheduler};
+/// use chrono::prelude::*;
+///
+/// let mut scheduler = Scheduler::new();
+/// let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+/// let job = scheduler.add("*/15 * * * *".parse().unwrap(), start, |tick| {
+///     println!("Running the job scheduled for {}", tick.scheduled);
+/// });
+///
+/// assert_eq!(scheduler.next_wakeup(), Some(start));
+/// assert_eq!(scheduler.run_pending(start), 1);
+/// assert_eq!(scheduler.next_wakeup(), Some(Utc.with_ymd_and_hms(2021, 1, 1, 0, 15, 0).unwrap()));
+///
+/// assert!(scheduler.remove(job));
+/// assert_eq!(scheduler.next_wakeup(), None);
+/// ```
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: HashMap<JobId, Job>,
+    queue: BinaryHeap<Reverse<(DateTime<Utc>, JobId)>>,
+    next_id: u64,
+}
+
+impl Debug for Scheduler {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("jobs", &self.jobs.len())
+            .field("next_wakeup", &self.next_wakeup())
+            .finish()
+    }
+}
+
+impl Scheduler {
+    /// Creates a scheduler without any jobs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of jobs in the scheduler.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns whether the scheduler has no jobs.
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Adds a job that calls the callback at each time the cron value matches, starting with the
+    /// first time at or after the given time. A cron value that never matches is kept as a job,
+    /// but never runs.
+    pub fn add<F>(&mut self, cron: Cron, start: DateTime<Utc>, callback: F) -> JobId
+    where
+        F: FnMut(Tick) + Send + 'static,
+    {
+        let id = JobId(self.next_id);
+        self.next_id += 1;
+
+        // the next time from is truncated to the minute, so it can be before the start
+        let next = cron
+            .next_from(start)
+            .filter(|&next| next >= start)
+            .or_else(|| cron.next_after(start));
+        if let Some(next) = next {
+            self.queue.push(Reverse((next, id)));
+        }
+
+        self.jobs.insert(
+            id,
+            Job {
+                cron,
+                callback: Box::new(callback),
+                next,
+            },
+        );
+        id
+    }
+
+    /// Removes a job from the scheduler, returning whether it was found.
+    pub fn remove(&mut self, id: JobId) -> bool {
+        match self.jobs.remove(&id) {
+            Some(job) => {
+                if job.next.is_some() {
+                    let queue = std::mem::take(&mut self.queue);
+                    self.queue = queue
+                        .into_iter()
+                        .filter(|Reverse((_, job))| *job != id)
+                        .collect();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the next time a job is scheduled to run, or none if no job will run again.
+    pub fn next_wakeup(&self) -> Option<DateTime<Utc>> {
+        self.queue.peek().map(|Reverse((next, _))| *next)
+    }
+
+    /// Runs each job scheduled at or before the given time, returning the number of jobs run.
+    ///
+    /// A job runs once even if more than one of its times have passed, and the next time it's
+    /// scheduled for is the first time after the given time. The times skipped are reported in
+    /// [`Tick::missed`](struct.Tick.html#structfield.missed).
+    pub fn run_pending(&mut self, now: DateTime<Utc>) -> usize {
+        let now = minute_floor(now);
+
+        let mut ran = 0;
+        while let Some(&Reverse((scheduled, id))) = self.queue.peek() {
+            if scheduled > now {
+                break;
+            }
+            self.queue.pop();
+
+            let job = self
+                .jobs
+                .get_mut(&id)
+                .expect("queued jobs are in the scheduler");
+            let missed = match (next_minute(scheduled), next_minute(now)) {
+                (Some(start), Some(end)) => job.cron.count_between(start, end),
+                _ => 0,
+            };
+            (job.callback)(Tick {
+                job: id,
+                scheduled,
+                missed,
+            });
+            ran += 1;
+
+            job.next = job.cron.next_after(now);
+            if let Some(next) = job.next {
+                self.queue.push(Reverse((next, id)));
+            }
+        }
+        ran
+    }
+
+    /// Moves the scheduler to a background thread that runs jobs as they're scheduled, using the
+    /// system clock. Jobs can be added and removed while it runs through the returned handle.
+    ///
+    /// Callbacks are run on the scheduler thread while the scheduler is locked, so they
+    /// shouldn't block for long or use the handle.
+    pub fn spawn(self) -> SchedulerThread {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                scheduler: self,
+                shutdown: false,
+            }),
+            wake: Condvar::new(),
+        });
+
+        let thread = {
+            let shared = shared.clone();
+            thread::spawn(move || shared.run())
+        };
+
+        SchedulerThread {
+            shared,
+            thread: Some(thread),
+        }
+    }
+}
+
+struct State {
+    scheduler: Scheduler,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+fn system_now() -> DateTime<Utc> {
+    SystemTime::now().into()
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn run(&self) {
+        let mut state = self.lock();
+        loop {
+            if state.shutdown {
+                return;
+            }
+
+            let now = system_now();
+            state.scheduler.run_pending(now);
+            state = match state.scheduler.next_wakeup() {
+                Some(next) => {
+                    let wait = (next - now).to_std().unwrap_or_default();
+                    self.wake
+                        .wait_timeout(state, wait)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .wake
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+/// A handle to a scheduler running on a background thread, created with [`Scheduler::spawn`].
+/// The thread is stopped when the handle is dropped.
+///
+/// [`Scheduler::spawn`]: struct.Scheduler.html#method.spawn
+pub struct SchedulerThread {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Debug for SchedulerThread {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SchedulerThread")
+            .field("scheduler", &self.shared.lock().scheduler)
+            .finish()
+    }
+}
+
+impl SchedulerThread {
+    /// Adds a job that calls the callback at each time the cron value matches from now on.
+    pub fn add<F>(&self, cron: Cron, callback: F) -> JobId
+    where
+        F: FnMut(Tick) + Send + 'static,
+    {
+        let id = self
+            .shared
+            .lock()
+            .scheduler
+            .add(cron, system_now(), callback);
+        self.shared.wake.notify_one();
+        id
+    }
+
+    /// Removes a job from the scheduler, returning whether it was found.
+    pub fn remove(&self, id: JobId) -> bool {
+        let removed = self.shared.lock().scheduler.remove(id);
+        self.shared.wake.notify_one();
+        removed
+    }
+
+    /// Returns the next time a job is scheduled to run.
+    pub fn next_wakeup(&self) -> Option<DateTime<Utc>> {
+        self.shared.lock().scheduler.next_wakeup()
+    }
+
+    /// Stops the scheduler thread, waiting for any running callback to finish, and returns the
+    /// scheduler.
+    pub fn shutdown(mut self) -> Scheduler {
+        self.stop();
+        std::mem::take(&mut self.shared.lock().scheduler)
+    }
+
+    fn stop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.shared.lock().shutdown = true;
+            self.shared.wake.notify_one();
+            // a panic in a callback has already been reported by the thread
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for SchedulerThread {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::mpsc;
+
+    fn recorder() -> (impl FnMut(Tick) + Send, mpsc::Receiver<Tick>) {
+        let (sender, receiver) = mpsc::channel();
+        (move |tick| sender.send(tick).unwrap(), receiver)
+    }
+
+    #[test]
+    fn runs_jobs_in_order() {
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 30).unwrap();
+        let mut scheduler = Scheduler::new();
+        let (quarters, quarter_ticks) = recorder();
+        let (hours, hour_ticks) = recorder();
+        let quarter = scheduler.add("*/15 * * * *".parse().unwrap(), start, quarters);
+        let hour = scheduler.add("0 * * * *".parse().unwrap(), start, hours);
+        scheduler.add("0 0 30 2 *".parse().unwrap(), start, |_| unreachable!());
+        assert_eq!(scheduler.len(), 3);
+
+        // the start is after the first minute matched
+        assert_eq!(
+            scheduler.next_wakeup(),
+            Some(Utc.with_ymd_and_hms(2021, 1, 1, 0, 15, 0).unwrap())
+        );
+        assert_eq!(
+            scheduler.run_pending(Utc.with_ymd_and_hms(2021, 1, 1, 0, 14, 59).unwrap()),
+            0
+        );
+        assert_eq!(
+            scheduler.run_pending(Utc.with_ymd_and_hms(2021, 1, 1, 0, 15, 0).unwrap()),
+            1
+        );
+        assert_eq!(
+            scheduler.run_pending(Utc.with_ymd_and_hms(2021, 1, 1, 1, 0, 0).unwrap()),
+            2
+        );
+
+        let ticks: Vec<_> = quarter_ticks.try_iter().collect();
+        assert_eq!(
+            ticks,
+            [
+                Tick {
+                    job: quarter,
+                    scheduled: Utc.with_ymd_and_hms(2021, 1, 1, 0, 15, 0).unwrap(),
+                    missed: 0,
+                },
+                Tick {
+                    job: quarter,
+                    scheduled: Utc.with_ymd_and_hms(2021, 1, 1, 0, 30, 0).unwrap(),
+                    missed: 2,
+                },
+            ]
+        );
+        assert_eq!(
+            hour_ticks.try_iter().collect::<Vec<_>>(),
+            [Tick {
+                job: hour,
+                scheduled: Utc.with_ymd_and_hms(2021, 1, 1, 1, 0, 0).unwrap(),
+                missed: 0,
+            }]
+        );
+        assert_eq!(
+            scheduler.next_wakeup(),
+            Some(Utc.with_ymd_and_hms(2021, 1, 1, 1, 15, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn removes_jobs() {
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let mut scheduler = Scheduler::new();
+        let minutes = scheduler.add("* * * * *".parse().unwrap(), start, |_| {});
+        let (hours, hour_ticks) = recorder();
+        scheduler.add("0 * * * *".parse().unwrap(), start, hours);
+
+        assert!(scheduler.remove(minutes));
+        assert!(!scheduler.remove(minutes));
+        assert_eq!(scheduler.len(), 1);
+        assert_eq!(
+            scheduler.run_pending(Utc.with_ymd_and_hms(2021, 1, 1, 0, 30, 0).unwrap()),
+            1
+        );
+        assert_eq!(hour_ticks.try_iter().count(), 1);
+        assert_eq!(
+            scheduler.next_wakeup(),
+            Some(Utc.with_ymd_and_hms(2021, 1, 1, 1, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn thread_shutdown() {
+        let scheduler = Scheduler::new().spawn();
+        assert_eq!(scheduler.next_wakeup(), None);
+
+        let job = scheduler.add("0 0 1 1 *".parse().unwrap(), |_| {});
+        assert!(scheduler.next_wakeup().is_some());
+        assert!(scheduler.remove(job));
+        scheduler.add("0 0 1 1 *".parse().unwrap(), |_| {});
+
+        let scheduler = scheduler.shutdown();
+        assert_eq!(scheduler.len(), 1);
+    }
+}
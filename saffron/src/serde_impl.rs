@@ -0,0 +1,109 @@
+//! Serde support for cron values and expressions. Both types are represented as cron strings,
+//! deserializing from any valid expression and serializing into a canonical form. Strings are
+//! parsed in the extended dialect, so values restricting the weeks of the year round trip.
+
+use crate::parse::{CronExpr, CronParseError, Dialect};
+use crate::Cron;
+
+use core::fmt::{self, Formatter};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// A visitor that parses any string into a value using a parse function.
+struct StrVisitor<T>(fn(&str) -> Result<T, CronParseError>);
+
+impl<'de, T> Visitor<'de> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a cron expression string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        (self.0)(v).map_err(E::custom)
+    }
+}
+
+impl Serialize for CronExpr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CronExpr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(|s| {
+            CronExpr::from_str_with(s, Dialect::Extended)
+        }))
+    }
+}
+
+impl Serialize for Cron {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cron {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(|s| Cron::parse_with(s, Dialect::Extended)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[track_caller]
+    fn assert_expr(cron: &str, canonical: &'static str) {
+        let expr = CronExpr::from_str_with(cron, Dialect::Extended).expect("Valid cron expression");
+        assert_tokens(&expr, &[Token::Str(canonical)]);
+    }
+
+    #[track_caller]
+    fn assert_cron(cron: &str, canonical: &'static str) {
+        let compiled = Cron::parse_with(cron, Dialect::Extended).expect("Valid cron expression");
+        assert_tokens(&compiled, &[Token::Str(canonical)]);
+    }
+
+    #[test]
+    fn expr_round_trips() {
+        assert_expr("* * * * *", "* * * * *");
+        assert_expr(
+            "*/5 0-6,12 1/2 JAN-MAR,DEC MON-FRI",
+            "0/5 0-6,12 1/2 1-3,12 2-6",
+        );
+        assert_expr("0 0 L-3W * 6L", "0 0 L-3W * 6L");
+        assert_expr("0 0 15W * MON#2", "0 0 15W * 2#2");
+        assert_expr("0 0 LW * L", "0 0 LW * 7");
+        assert_expr("5-40/5 20-4/2 * * SAT-SUN", "5-40/5 20-4/2 * * 7-1");
+        assert_expr("0 9 * * MON W/2", "0 9 * * 2 W1/2");
+    }
+
+    #[test]
+    fn cron_round_trips() {
+        assert_cron("* * * * *", "* * * * *");
+        assert_cron("0-59 0-23 * 1-12 *", "* * * * *");
+        assert_cron("*/15 9-17 * * MON-FRI", "0,15,30,45 9-17 * * 2-6");
+        assert_cron("0 20-4/2 1,2,3,31 * 1-7", "0 0,2,4,20,22 1-3,31 * 1-7");
+        assert_cron("0 0 L-2 FEB 3L", "0 0 L-2 2 3L");
+        assert_cron("0 0 LW * MON#5", "0 0 LW * 2#5");
+        assert_cron("0 0 L-30W * *", "0 0 L-30W * *");
+        assert_cron("0 0 1W * *", "0 0 1W * *");
+        assert_cron("0 9 * * MON W1-26,53", "0 9 * * 2 W1-26,53");
+        assert_cron("0 0 15 * &FRI", "0 0 15 * &6");
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert_de_tokens_error::<CronExpr>(
+            &[Token::Str("* * * *")],
+            "Missing day of week field at position 7",
+        );
+        assert_de_tokens_error::<Cron>(
+            &[Token::Str("60 * * * *")],
+            "Value 60 in minutes field at position 0 is out of range (0-59)",
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! A union of many cron values and intervals which is matched as one schedule.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BinaryHeap, vec::Vec};
+
+use crate::interval::Interval;
+use crate::schedule::{AnySchedule, ScheduleTimesIter};
+use crate::Cron;
+use chrono::{DateTime, Utc};
+use core::cmp::Reverse;
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::{Bound, RangeBounds};
+
+#[cfg(feature = "std")]
+use std::collections::BinaryHeap;
+
+/// A set of cron values and intervals. A time matches the set if it matches any of the cron
+/// values or intervals in it.
+///
+/// # Example
+/// ```
+/// use saffron::{Cron, CronSet};
+/// use chrono::prelude::*;
+///
+/// let set: CronSet = ["0 9 * * MON-FRI", "0 12 * * SAT,SUN"]
+///     .iter()
+///     .map(|s| s.parse::<Cron>().expect("Couldn't parse expression!"))
+///     .collect();
+///
+/// // 2020-10-17 is a Saturday
+/// assert!(set.contains(Utc.with_ymd_and_hms(2020, 10, 17, 12, 0, 0).unwrap()));
+/// assert!(!set.contains(Utc.with_ymd_and_hms(2020, 10, 17, 9, 0, 0).unwrap()));
+///
+/// let start = Utc.with_ymd_and_hms(2020, 10, 16, 0, 0, 0).unwrap();
+/// let times = set.iter_from(start).take(3).collect::<Vec<_>>();
+/// assert_eq!(
+///     times,
+///     [
+///         Utc.with_ymd_and_hms(2020, 10, 16, 9, 0, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2020, 10, 17, 12, 0, 0).unwrap(),
+///         Utc.with_ymd_and_hms(2020, 10, 18, 12, 0, 0).unwrap(),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct CronSet {
+    crons: Vec<Cron>,
+    intervals: Vec<Interval>,
+}
+
+impl CronSet {
+    /// Creates a new empty set. An empty set never matches any time.
+    pub const fn new() -> Self {
+        Self {
+            crons: Vec::new(),
+            intervals: Vec::new(),
+        }
+    }
+
+    /// Adds a cron value to the set.
+    pub fn push(&mut self, cron: Cron) {
+        self.crons.push(cron);
+    }
+
+    /// Adds an interval to the set.
+    pub fn push_interval(&mut self, interval: Interval) {
+        self.intervals.push(interval);
+    }
+
+    /// Adds a cron value or an interval to the set.
+    pub fn push_schedule(&mut self, schedule: AnySchedule) {
+        match schedule {
+            AnySchedule::Cron(cron) => self.push(cron),
+            AnySchedule::Interval(interval) => self.push_interval(interval),
+        }
+    }
+
+    /// Returns the cron values in the set.
+    pub fn crons(&self) -> &[Cron] {
+        &self.crons
+    }
+
+    /// Returns the intervals in the set.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// Returns the number of cron values and intervals in the set.
+    pub fn len(&self) -> usize {
+        self.crons.len() + self.intervals.len()
+    }
+
+    /// Returns whether the set contains no cron values or intervals.
+    pub fn is_empty(&self) -> bool {
+        self.crons.is_empty() && self.intervals.is_empty()
+    }
+
+    /// Returns whether any cron value or interval in the set will ever match any given time.
+    pub fn any(&self) -> bool {
+        !self.intervals.is_empty() || self.crons.iter().any(Cron::any)
+    }
+
+    /// Returns whether any cron value or interval in the set matches the given time.
+    pub fn contains(&self, dt: DateTime<Utc>) -> bool {
+        self.crons.iter().any(|cron| cron.contains(dt))
+            || self.intervals.iter().any(|interval| interval.contains(dt))
+    }
+
+    /// Returns the next time any cron value or interval in the set will match including the
+    /// given date.
+    pub fn next_from(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let crons = self.crons.iter().filter_map(|cron| cron.next_from(start));
+        let intervals = self
+            .intervals
+            .iter()
+            .filter_map(|interval| interval.next_from(start));
+        crons.chain(intervals).min()
+    }
+
+    /// Returns the next time any cron value or interval in the set will match after the given
+    /// date.
+    pub fn next_after(&self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let crons = self.crons.iter().filter_map(|cron| cron.next_after(start));
+        let intervals = self
+            .intervals
+            .iter()
+            .filter_map(|interval| interval.next_after(start));
+        crons.chain(intervals).min()
+    }
+
+    /// Creates an iterator of date times that match with any cron value or interval in the set.
+    /// This is short for `iter((Bound::Included(start), Bound::Unbounded))` or `iter(start..)`.
+    #[inline]
+    pub fn iter_from(self, start: DateTime<Utc>) -> CronSetTimesIter {
+        self.iter((Bound::Included(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match with any cron value or interval in the set
+    /// after the given date. This is short for `iter((Bound::Excluded(start), Bound::Unbounded))`.
+    #[inline]
+    pub fn iter_after(self, start: DateTime<Utc>) -> CronSetTimesIter {
+        self.iter((Bound::Excluded(start), Bound::Unbounded))
+    }
+
+    /// Creates an iterator of date times that match with any cron value or interval in the set
+    /// using the given start and end range bounds. Times are yielded in order and a time matched
+    /// by more than one cron value or interval is only yielded once.
+    pub fn iter<R: RangeBounds<DateTime<Utc>>>(self, bounds: R) -> CronSetTimesIter {
+        let start = match bounds.start_bound() {
+            Bound::Unbounded => Bound::Unbounded,
+            Bound::Included(&start) => Bound::Included(start),
+            Bound::Excluded(&start) => Bound::Excluded(start),
+        };
+        let end = match bounds.end_bound() {
+            Bound::Unbounded => Bound::Unbounded,
+            Bound::Included(&end) => Bound::Included(end),
+            Bound::Excluded(&end) => Bound::Excluded(end),
+        };
+
+        let crons = self.crons.into_iter().map(AnySchedule::Cron);
+        let intervals = self.intervals.into_iter().map(AnySchedule::Interval);
+        let mut iters = crons
+            .chain(intervals)
+            .map(|schedule| schedule.iter((start, end)))
+            .collect::<Vec<_>>();
+        let heap = iters
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, iter)| iter.next().map(|time| Reverse((time, index))))
+            .collect();
+
+        CronSetTimesIter { iters, heap }
+    }
+}
+
+impl From<Vec<Cron>> for CronSet {
+    fn from(crons: Vec<Cron>) -> Self {
+        Self {
+            crons,
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl FromIterator<Cron> for CronSet {
+    fn from_iter<I: IntoIterator<Item = Cron>>(iter: I) -> Self {
+        Self {
+            crons: iter.into_iter().collect(),
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl Extend<Cron> for CronSet {
+    fn extend<I: IntoIterator<Item = Cron>>(&mut self, iter: I) {
+        self.crons.extend(iter)
+    }
+}
+
+/// An iterator over the times matching any cron value or interval in a set. Created with
+/// [`CronSet::iter`], [`CronSet::iter_from`], and [`CronSet::iter_after`].
+///
+/// Each cron value and interval is iterated separately and their times are merged, so getting
+/// the next time only searches forward in the ones that matched the previous time.
+///
+/// [`CronSet::iter`]: struct.CronSet.html#method.iter
+/// [`CronSet::iter_from`]: struct.CronSet.html#method.iter_from
+/// [`CronSet::iter_after`]: struct.CronSet.html#method.iter_after
+pub struct CronSetTimesIter {
+    iters: Vec<ScheduleTimesIter>,
+    /// The next time of every iterator that hasn't finished, paired with its index
+    heap: BinaryHeap<Reverse<(DateTime<Utc>, usize)>>,
+}
+
+impl CronSetTimesIter {
+    /// Pushes the next time of the iterator at the given index onto the heap.
+    fn advance(&mut self, index: usize) {
+        if let Some(time) = self.iters[index].next() {
+            self.heap.push(Reverse((time, index)));
+        }
+    }
+}
+
+impl Iterator for CronSetTimesIter {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((time, index)) = self.heap.pop()?;
+        self.advance(index);
+
+        // skip the same time in any other cron values or intervals
+        while let Some(&Reverse((next, other))) = self.heap.peek() {
+            if next != time {
+                break;
+            }
+
+            self.heap.pop();
+            self.advance(other);
+        }
+
+        Some(time)
+    }
+}
+
+impl FusedIterator for CronSetTimesIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{prelude::*, Duration};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    fn set(crons: &[&str]) -> CronSet {
+        crons
+            .iter()
+            .map(|cron| cron.parse::<Cron>().expect("Valid cron expression"))
+            .collect()
+    }
+
+    #[test]
+    fn empty_set_never_matches() {
+        let set = CronSet::new();
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        assert!(!set.any());
+        assert!(!set.contains(start));
+        assert_eq!(set.next_from(start), None);
+        assert_eq!(set.iter_from(start).next(), None);
+    }
+
+    #[test]
+    fn next_uses_earliest_cron() {
+        let set = set(&["30 * * * *", "0 */2 * * *"]);
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 1, 0, 0).unwrap();
+        assert_eq!(set.next_from(start), Some(start.with_minute(30).unwrap()));
+        assert_eq!(
+            set.next_after(start.with_minute(30).unwrap()),
+            Some(Utc.with_ymd_and_hms(2020, 1, 1, 2, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn iter_merges_in_order_without_duplicates() {
+        let set = set(&["*/20 0 * * *", "*/30 0 * * *", "* * 31 11 *"]);
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 1, 1, 1, 0, 0).unwrap();
+
+        let times = set.iter(start..end).collect::<Vec<_>>();
+        let expected = vec![0, 20, 30, 40]
+            .into_iter()
+            .map(|minute| Utc.with_ymd_and_hms(2020, 1, 1, 0, minute, 0).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(times, expected);
+    }
+
+    #[test]
+    fn intervals() {
+        let mut set = set(&["0 12 * * *"]);
+        let anchor = Utc.with_ymd_and_hms(2020, 1, 1, 9, 0, 0).unwrap();
+        set.push_interval(Interval::new(anchor, Duration::minutes(90)));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(Utc.with_ymd_and_hms(2020, 1, 1, 10, 30, 0).unwrap()));
+        assert!(set.contains(Utc.with_ymd_and_hms(2020, 1, 1, 12, 0, 0).unwrap()));
+        assert!(!set.contains(Utc.with_ymd_and_hms(2020, 1, 1, 11, 0, 0).unwrap()));
+        assert_eq!(
+            set.next_after(Utc.with_ymd_and_hms(2020, 1, 1, 10, 30, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2020, 1, 1, 12, 0, 0).unwrap())
+        );
+
+        // 12:00 is matched by both, but only yielded once
+        let times = set.iter_from(anchor).take(4).collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            [
+                Utc.with_ymd_and_hms(2020, 1, 1, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 1, 1, 10, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 1, 1, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 1, 1, 13, 30, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn iter_after_excludes_start() {
+        let set = set(&["0 0 * * *", "0 12 * * *"]);
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+
+        let times = set.iter_after(start).take(3).collect::<Vec<_>>();
+        assert_eq!(
+            times,
+            [
+                Utc.with_ymd_and_hms(2020, 1, 1, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 1, 2, 12, 0, 0).unwrap(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Shifting a cron value written for a fixed offset time zone to the same times in UTC, for
+//! platforms that only evaluate schedules in UTC.
+
+use crate::{
+    Cron, DaysOfMonth, DaysOfMonthKind, DaysOfWeek, DaysOfWeekKind, Months, Weeks, MINUTES_IN_DAY,
+};
+use chrono::{Duration, FixedOffset, NaiveDate};
+use core::fmt::{self, Display, Formatter};
+
+/// The days in the 400 year cycle of the Gregorian calendar, after which the days of the week,
+/// leap years, and ISO weeks all repeat
+const DAYS_IN_CYCLE: i64 = 146_097;
+
+/// An error returned when a cron value can't be shifted to UTC with [`Cron::shifted_to`].
+///
+/// [`Cron::shifted_to`]: struct.Cron.html#method.shifted_to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ShiftError {
+    /// The offset isn't a whole number of minutes, so the times can't be written in minutes
+    PartialMinutes,
+    /// The shifted times of the day can't be written as minutes and hours fields, like
+    /// `30,45 9` shifted by 40 minutes matching 8:50 and 9:05 but not 8:05 or 9:50
+    Times,
+    /// Some of the times move to another day and some don't, but the day fields don't match
+    /// every day, so the shifted times would need different days
+    MixedDays,
+    /// The times move to another day, but the days matched can't be shifted, like the first
+    /// day of the month moving to the last day of the month before it, or the last Friday of
+    /// the month
+    Days,
+}
+
+impl Display for ShiftError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ShiftError::PartialMinutes => f.write_str("The offset isn't a whole number of minutes"),
+            ShiftError::Times => {
+                f.write_str("The shifted times can't be written as minutes and hours fields")
+            }
+            ShiftError::MixedDays => {
+                f.write_str("Only some of the times move to another day, and not every day matches")
+            }
+            ShiftError::Days => f.write_str("The days matched can't be moved to another day"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ShiftError {}
+
+impl Cron {
+    /// Shifts a cron value written for times in the given fixed offset time zone to a cron value
+    /// matching the same instants in UTC. The minutes and hours fields are rewritten for the
+    /// offset, and the day fields are moved a day when the times cross midnight.
+    ///
+    /// Shifting fails if the result can't be written as a cron value, like when some times
+    /// cross midnight and others don't on days that don't all match. Time zones observing
+    /// daylight saving time don't have a fixed offset, so they need a cron value for each
+    /// offset they use.
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::{Cron, ShiftError};
+    /// use chrono::FixedOffset;
+    ///
+    /// let cron: Cron = "0 9 * * MON-FRI".parse().unwrap();
+    ///
+    /// let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+    /// assert_eq!(cron.shifted_to(tokyo), Ok("0 0 * * MON-FRI".parse().unwrap()));
+    ///
+    /// let sydney = FixedOffset::east_opt(10 * 3600).unwrap();
+    /// assert_eq!(cron.shifted_to(sydney), Ok("0 23 * * SUN-THU".parse().unwrap()));
+    ///
+    /// // the 1st at 5:00 in Sydney is the last day of the month before it in UTC
+    /// let cron: Cron = "0 5 1 * ?".parse().unwrap();
+    /// assert_eq!(cron.shifted_to(sydney), Err(ShiftError::Days));
+    /// ```
+    pub fn shifted_to(&self, offset: FixedOffset) -> Result<Cron, ShiftError> {
+        let seconds = offset.local_minus_utc();
+        if seconds % 60 != 0 {
+            return Err(ShiftError::PartialMinutes);
+        }
+        let offset = seconds / 60;
+        let contains_local = |local: i32| {
+            let local = local.rem_euclid(MINUTES_IN_DAY as i32) as u32;
+            self.minutes.0 & (1 << (local % 60)) != 0 && self.hours.0 & (1 << (local / 60)) != 0
+        };
+
+        // the days the times move by, where -1 moves a time to the day before
+        let mut day_shifts = [false; 3];
+        let mut shifted = self.clone();
+        shifted.minutes.0 = 0;
+        shifted.hours.0 = 0;
+        for utc in 0..MINUTES_IN_DAY as i32 {
+            let local = utc + offset;
+            if contains_local(local) {
+                shifted.minutes.0 |= 1 << (utc % 60);
+                shifted.hours.0 |= 1 << (utc / 60);
+                let day_shift = if local < 0 {
+                    1
+                } else if local >= MINUTES_IN_DAY as i32 {
+                    -1
+                } else {
+                    0
+                };
+                day_shifts[(day_shift + 1) as usize] = true;
+            }
+        }
+
+        // every time in the fields of the shifted value has to be a shifted time
+        for utc in 0..MINUTES_IN_DAY as i32 {
+            let set = shifted.minutes.0 & (1 << (utc % 60)) != 0
+                && shifted.hours.0 & (1 << (utc / 60)) != 0;
+            if set && !contains_local(utc + offset) {
+                return Err(ShiftError::Times);
+            }
+        }
+
+        let every_day = self.dom.is_star()
+            && self.dow.is_star()
+            && self.months.0 == Months::ALL
+            && self.weeks.0 == Weeks::ALL;
+        let day_shift = match day_shifts {
+            _ if every_day => return Ok(shifted),
+            [false, true, false] => return Ok(shifted),
+            [true, false, false] => -1,
+            [false, false, true] => 1,
+            _ => return Err(ShiftError::MixedDays),
+        };
+
+        if shifted.dom.kind() == DaysOfMonthKind::Pattern {
+            shifted.dom.1 = if day_shift < 0 {
+                (self.dom.1 & DaysOfMonth::DAY_BITS) >> 1
+            } else {
+                (self.dom.1 << 1) & DaysOfMonth::DAY_BITS
+            };
+        }
+        if shifted.dow.kind() == DaysOfWeekKind::Pattern {
+            let days = self.dow.1;
+            shifted.dow.1 = if day_shift < 0 {
+                (days >> 1) | ((days & 1) << 6)
+            } else {
+                ((days << 1) | (days >> 6)) & DaysOfWeek::DAY_BITS
+            };
+        }
+
+        // the fields are shifted by day number, which is only right if no date matched moves
+        // into another month, year, or week that isn't matched the same way
+        let start = NaiveDate::from_ymd_opt(2000, 1, 1).expect("2000-01-01 is a valid date");
+        let same_days = (0..DAYS_IN_CYCLE).all(|day| {
+            let utc = start + Duration::days(day);
+            let local = utc - Duration::days(day_shift);
+            shifted.contains_date(utc) == self.contains_date(local)
+        });
+        if same_days {
+            Ok(shifted)
+        } else {
+            Err(ShiftError::Days)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn east(hours: i32, minutes: i32) -> FixedOffset {
+        FixedOffset::east_opt(hours * 3600 + minutes * 60).unwrap()
+    }
+
+    fn shift(cron: &str, offset: FixedOffset) -> Result<Cron, ShiftError> {
+        cron.parse::<Cron>().unwrap().shifted_to(offset)
+    }
+
+    fn cron(cron: &str) -> Result<Cron, ShiftError> {
+        Ok(cron.parse().unwrap())
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(shift("0 9 * * *", east(0, 0)), cron("0 9 * * *"));
+        assert_eq!(shift("0 9 * * *", east(2, 0)), cron("0 7 * * *"));
+        assert_eq!(shift("0 9 * * *", east(-5, 0)), cron("0 14 * * *"));
+        assert_eq!(shift("0 9 * * *", east(5, 30)), cron("30 3 * * *"));
+        assert_eq!(
+            shift("*/15 9-17 * * *", east(1, 0)),
+            cron("*/15 8-16 * * *")
+        );
+        assert_eq!(shift("0,30 * * * *", east(5, 45)), cron("15,45 * * * *"));
+        assert_eq!(shift("30,45 9 * * *", east(0, 40)), Err(ShiftError::Times));
+        assert_eq!(
+            "0 0 * * *"
+                .parse::<Cron>()
+                .unwrap()
+                .shifted_to(FixedOffset::east_opt(30).unwrap()),
+            Err(ShiftError::PartialMinutes)
+        );
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(shift("0 1 * * MON", east(2, 0)), cron("0 23 * * SUN"));
+        assert_eq!(shift("0 22 * * SAT", east(-3, 0)), cron("0 1 * * SUN"));
+        assert_eq!(shift("0 1 15 * ?", east(2, 0)), cron("0 23 14 * ?"));
+        assert_eq!(shift("0 1 2-10 JUN ?", east(2, 0)), cron("0 23 1-9 JUN ?"));
+        assert_eq!(
+            shift("0 22 10,20 * MON", east(-3, 0)),
+            cron("0 1 11,21 * TUE")
+        );
+        // a time crossing midnight on every day
+        assert_eq!(shift("0 1,23 * * *", east(2, 0)), cron("0 21,23 * * *"));
+
+        assert_eq!(
+            shift("0 1,23 * * MON", east(2, 0)),
+            Err(ShiftError::MixedDays)
+        );
+        assert_eq!(shift("0 1 1 * ?", east(2, 0)), Err(ShiftError::Days));
+        assert_eq!(shift("0 22 31 * ?", east(-3, 0)), Err(ShiftError::Days));
+        // the 28th is the last day of February in most years
+        assert_eq!(shift("0 22 28 2 ?", east(-3, 0)), Err(ShiftError::Days));
+        assert_eq!(shift("0 22 28 1 ?", east(-3, 0)), cron("0 1 29 1 ?"));
+        assert_eq!(shift("0 1 L * ?", east(2, 0)), Err(ShiftError::Days));
+        assert_eq!(shift("0 1 ? * FRI#2", east(2, 0)), Err(ShiftError::Days));
+        // Mondays in January can be on the 1st
+        assert_eq!(shift("0 1 ? JAN MON", east(2, 0)), Err(ShiftError::Days));
+    }
+
+    #[test]
+    fn same_instants() {
+        let crons = [
+            (
+                "0 9 * * MON-FRI",
+                [east(9, 0), east(-7, 0), east(5, 45), east(-3, -30)],
+            ),
+            (
+                "*/20 0-3 * * SAT,SUN",
+                [east(9, 0), east(-7, 0), east(4, 0), east(-12, 0)],
+            ),
+            (
+                "15 22 5-20 * ?",
+                [east(9, 0), east(-7, 0), east(5, 45), east(-3, -30)],
+            ),
+        ];
+        for &(expr, offsets) in &crons {
+            let local: Cron = expr.parse().unwrap();
+            for &offset in &offsets {
+                let utc = local.shifted_to(offset).unwrap();
+                let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+                let times = local.clone().iter_from(start).take(50).map(|time| {
+                    let time = offset.from_local_datetime(&time.naive_utc()).unwrap();
+                    time.with_timezone(&Utc)
+                });
+                let first = times.clone().next().unwrap();
+                let shifted = utc.iter_from(start - Duration::days(1));
+                assert!(
+                    shifted
+                        .skip_while(|&time| time < first)
+                        .zip(times)
+                        .all(|(utc, local)| utc == local),
+                    "{} at {}",
+                    expr,
+                    offset
+                );
+            }
+        }
+    }
+}
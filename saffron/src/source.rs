@@ -0,0 +1,170 @@
+//! Expressions and cron values kept together with the text they were parsed from, so errors and
+//! tools can show exactly what was written instead of the normalized expression.
+
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
+
+use crate::parse::{CronExpr, CronParseError};
+use crate::Cron;
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+/// A value paired with the text it was parsed from. Created with
+/// [`CronExpr::parse_with_source`] and [`Cron::parse_with_source`].
+///
+/// The text is kept exactly as given, including the spaces between fields, and is what
+/// the value is displayed as.
+///
+/// [`CronExpr::parse_with_source`]: parse/struct.CronExpr.html#method.parse_with_source
+/// [`Cron::parse_with_source`]: struct.Cron.html#method.parse_with_source
+///
+/// # Example
+/// ```
+/// use saffron::Cron;
+///
+/// let cron = Cron::parse_with_source("0  12 ? * mon-fri").unwrap();
+/// assert_eq!(cron.source(), "0  12 ? * mon-fri");
+/// assert_eq!(cron.to_string(), "0  12 ? * mon-fri");
+/// assert_eq!(cron.get().to_string(), "0 12 ? * 2-6");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WithSource<'a, T> {
+    value: T,
+    source: Cow<'a, str>,
+}
+
+impl<'a, T> WithSource<'a, T> {
+    /// Pairs a value with the text it was parsed from.
+    pub fn new<S: Into<Cow<'a, str>>>(value: T, source: S) -> Self {
+        Self {
+            value,
+            source: source.into(),
+        }
+    }
+
+    /// Returns the text the value was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the value, dropping the text.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the value and the text it was parsed from.
+    pub fn into_parts(self) -> (T, Cow<'a, str>) {
+        (self.value, self.source)
+    }
+
+    /// Maps the value, keeping the text. Compiling an expression with `map(Cron::new)` keeps the
+    /// text it was parsed from with the cron value.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> WithSource<'a, U> {
+        WithSource {
+            value: f(self.value),
+            source: self.source,
+        }
+    }
+
+    /// Copies the text if it's borrowed, so the value can outlive it.
+    pub fn into_owned(self) -> WithSource<'static, T> {
+        WithSource {
+            value: self.value,
+            source: Cow::Owned(self.source.into_owned()),
+        }
+    }
+}
+
+impl<T> Display for WithSource<'_, T> {
+    /// Formats the text the value was parsed from
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl CronExpr {
+    /// Parses a cron expression like [`FromStr`], keeping the text it was parsed from. The
+    /// text can be borrowed or owned.
+    ///
+    /// [`FromStr`]: #impl-FromStr
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::parse::CronExpr;
+    ///
+    /// let expr = CronExpr::parse_with_source(String::from("0 9 * * mon")).unwrap();
+    /// assert_eq!(expr.source(), "0 9 * * mon");
+    /// assert_eq!(expr.get().to_string(), "0 9 * * 2");
+    /// ```
+    pub fn parse_with_source<'a, S: Into<Cow<'a, str>>>(
+        s: S,
+    ) -> Result<WithSource<'a, CronExpr>, CronParseError> {
+        let source = s.into();
+        let expr = source.parse()?;
+        Ok(WithSource::new(expr, source))
+    }
+}
+
+impl Cron {
+    /// Parses and compiles a cron expression like [`FromStr`], keeping the text it was parsed
+    /// from. The text can be borrowed or owned.
+    ///
+    /// [`FromStr`]: #impl-FromStr
+    pub fn parse_with_source<'a, S: Into<Cow<'a, str>>>(
+        s: S,
+    ) -> Result<WithSource<'a, Cron>, CronParseError> {
+        let source = s.into();
+        let cron = source.parse()?;
+        Ok(WithSource::new(cron, source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::string::{String, ToString};
+
+    #[test]
+    fn keeps_source() {
+        let text = "*/5  * *\t* MON";
+        let expr = CronExpr::parse_with_source(text).unwrap();
+        assert_eq!(expr.source(), text);
+        assert_eq!(expr.to_string(), text);
+        assert_eq!(expr.get(), &text.parse::<CronExpr>().unwrap());
+        assert!(matches!(expr.clone().into_parts().1, Cow::Borrowed(_)));
+
+        let cron = expr.map(Cron::new);
+        assert_eq!(cron.source(), text);
+        assert_eq!(cron.into_inner(), text.parse::<Cron>().unwrap());
+    }
+
+    #[test]
+    fn owned_source() {
+        let cron = {
+            let text = String::from("0 12 * * *");
+            Cron::parse_with_source(text.as_str()).unwrap().into_owned()
+        };
+        assert_eq!(cron.source(), "0 12 * * *");
+
+        let cron = Cron::parse_with_source("@hourly".to_string()).unwrap();
+        assert!(matches!(cron.into_parts().1, Cow::Owned(_)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Cron::parse_with_source("0 12 * *").unwrap_err(),
+            "0 12 * *".parse::<Cron>().unwrap_err()
+        );
+        assert!(CronExpr::parse_with_source("60 * * * *").is_err());
+    }
+}
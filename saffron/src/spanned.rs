@@ -0,0 +1,283 @@
+//! Cron expressions paired with the byte range of each of their parts in the text they were
+//! parsed from, so tools like editors can highlight the characters a part of a schedule comes
+//! from.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::parse::{is_blank, CronExpr, CronParseError, Dialect, FieldKind};
+use core::ops::Range;
+use core::str::FromStr;
+
+/// Splits text into the ranges of its whitespace separated fields.
+pub(crate) fn split_fields(text: &str) -> Vec<Range<usize>> {
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(Some((text.len(), ' '))) {
+        match (start, is_blank(c)) {
+            (None, false) => start = Some(i),
+            (Some(field_start), true) => {
+                fields.push(field_start..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
+/// Splits a field into the ranges of its comma separated items.
+pub(crate) fn split_items(text: &str, field: Range<usize>) -> Vec<Range<usize>> {
+    let mut items = Vec::new();
+    let mut start = field.start;
+    for (i, c) in text[field.clone()].char_indices() {
+        if c == ',' {
+            items.push(start..field.start + i);
+            start = field.start + i + 1;
+        }
+    }
+    items.push(start..field.end);
+    items
+}
+
+/// A parsed cron expression with the byte range of each field, item, and value in the text it
+/// was parsed from.
+///
+/// # Example
+/// ```
+/// use saffron::parse::{FieldKind, SpannedCronExpr};
+///
+/// let text = "*/5 9-17 * * MON,FRI";
+/// let spanned: SpannedCronExpr = text.parse().expect("Couldn't parse expression!");
+/// assert_eq!(spanned.expr(), &text.parse().unwrap());
+///
+/// let dows = spanned.field(FieldKind::DaysOfWeek).unwrap();
+/// assert_eq!(&text[dows.span.clone()], "MON,FRI");
+/// assert_eq!(&text[dows.items[1].span.clone()], "FRI");
+///
+/// let minutes = spanned.field(FieldKind::Minutes).unwrap();
+/// assert_eq!(&text[minutes.items[0].values[0].clone()], "5");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpannedCronExpr {
+    expr: CronExpr,
+    span: Range<usize>,
+    fields: Vec<SpannedField>,
+}
+
+/// A field of a [`SpannedCronExpr`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SpannedField {
+    /// The field this is
+    pub kind: FieldKind,
+    /// The range of the whole field
+    pub span: Range<usize>,
+    /// The comma separated items of the field, in order
+    pub items: Vec<SpannedItem>,
+}
+
+/// A comma separated item of a field, like `1-5/2`, which is one value, range, step, or special
+/// expression of the field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SpannedItem {
+    /// The range of the whole item
+    pub span: Range<usize>,
+    /// The ranges of the numbers and names in the item, in order. These are the start, end,
+    /// and step of a range like `1-5/2`, the day and nth value of a `MON#2`, the offset of an
+    /// `L-3`, and so on. Symbols like `*`, `L`, and `W` aren't values.
+    pub values: Vec<Range<usize>>,
+}
+
+impl FromStr for SpannedCronExpr {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SpannedCronExpr::from_str_with(s, Dialect::Quartz)
+    }
+}
+
+impl SpannedCronExpr {
+    /// Parses a cron expression written in the given dialect, keeping the ranges of its parts.
+    /// Shortcuts like `@daily` have no fields.
+    pub fn from_str_with(s: &str, dialect: Dialect) -> Result<Self, CronParseError> {
+        let expr = CronExpr::from_str_with(s, dialect)?;
+        if s.starts_with('@') {
+            return Ok(Self {
+                expr,
+                span: 0..s.len(),
+                fields: Vec::new(),
+            });
+        }
+
+        let fields: Vec<SpannedField> = split_fields(s)
+            .into_iter()
+            .zip(FieldKind::ALL.iter())
+            .map(|(span, &kind)| {
+                let items = split_items(s, span.clone())
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, mut span)| {
+                        // the weeks field starts with a 'W' before its first item
+                        if kind == FieldKind::Weeks && i == 0 {
+                            span.start += 1;
+                        }
+                        let values = values(s, span.clone(), kind);
+                        SpannedItem { span, values }
+                    })
+                    .collect();
+                SpannedField { kind, span, items }
+            })
+            .collect();
+
+        let span = match (fields.first(), fields.last()) {
+            (Some(first), Some(last)) => first.span.start..last.span.end,
+            _ => 0..s.len(),
+        };
+        Ok(Self { expr, span, fields })
+    }
+
+    /// Returns the parsed expression
+    pub fn expr(&self) -> &CronExpr {
+        &self.expr
+    }
+
+    /// Returns the parsed expression, dropping the ranges of its parts
+    pub fn into_expr(self) -> CronExpr {
+        self.expr
+    }
+
+    /// Returns the range of the whole expression
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the fields of the expression in order. The weeks field is only included if the
+    /// expression has one.
+    pub fn fields(&self) -> &[SpannedField] {
+        &self.fields
+    }
+
+    /// Returns the given field, if the expression has it
+    pub fn field(&self, kind: FieldKind) -> Option<&SpannedField> {
+        self.fields.iter().find(|field| field.kind == kind)
+    }
+}
+
+/// Finds the ranges of the numbers and names in an item. Names are always three letters, and
+/// other letters are symbols like 'L' and 'W'.
+fn values(text: &str, item: Range<usize>, kind: FieldKind) -> Vec<Range<usize>> {
+    let has_names = matches!(kind, FieldKind::Months | FieldKind::DaysOfWeek);
+    let bytes = text.as_bytes();
+    let mut values = Vec::new();
+    let mut i = item.start;
+    while i < item.end {
+        let run = |is: fn(&u8) -> bool| {
+            bytes[i..item.end]
+                .iter()
+                .position(|b| !is(b))
+                .map_or(item.end, |len| i + len)
+        };
+        if bytes[i].is_ascii_digit() {
+            let end = run(u8::is_ascii_digit);
+            values.push(i..end);
+            i = end;
+        } else if bytes[i].is_ascii_alphabetic() {
+            let end = run(u8::is_ascii_alphabetic);
+            if has_names && end - i >= 3 {
+                values.push(i..i + 3);
+            }
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    /// Returns the text of the values of each item in each field
+    fn values(text: &str, dialect: Dialect) -> Vec<Vec<Vec<&str>>> {
+        let spanned = SpannedCronExpr::from_str_with(text, dialect).unwrap();
+        spanned
+            .fields()
+            .iter()
+            .map(|field| {
+                field
+                    .items
+                    .iter()
+                    .map(|item| {
+                        item.values
+                            .iter()
+                            .map(|value| &text[value.clone()])
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn spans() {
+        let text = "0,30  9-17/2\t1W,L-3 JAN-MAR FRIL,MON#2 W1,10";
+        let spanned = SpannedCronExpr::from_str_with(text, Dialect::Extended).unwrap();
+        assert_eq!(spanned.span(), 0..text.len());
+        assert_eq!(
+            spanned
+                .fields()
+                .iter()
+                .map(|field| (field.kind, &text[field.span.clone()]))
+                .collect::<Vec<_>>(),
+            [
+                (FieldKind::Minutes, "0,30"),
+                (FieldKind::Hours, "9-17/2"),
+                (FieldKind::DaysOfMonth, "1W,L-3"),
+                (FieldKind::Months, "JAN-MAR"),
+                (FieldKind::DaysOfWeek, "FRIL,MON#2"),
+                (FieldKind::Weeks, "W1,10"),
+            ]
+        );
+        let weeks = spanned.field(FieldKind::Weeks).unwrap();
+        assert_eq!(&text[weeks.items[0].span.clone()], "1");
+
+        assert_eq!(
+            values(text, Dialect::Extended),
+            [
+                vec![vec!["0"], vec!["30"]],
+                vec![vec!["9", "17", "2"]],
+                vec![vec!["1"], vec!["3"]],
+                vec![vec!["JAN", "MAR"]],
+                vec![vec!["FRI"], vec!["MON", "2"]],
+                vec![vec!["1"], vec!["10"]],
+            ]
+        );
+        assert_eq!(
+            values("*/2 * LW,15B * ?", Dialect::Quartz),
+            [
+                vec![vec!["2"]],
+                vec![vec![]],
+                vec![vec![], vec!["15"]],
+                vec![vec![]],
+                vec![vec![]],
+            ]
+        );
+    }
+
+    #[test]
+    fn shortcuts_and_errors() {
+        let spanned: SpannedCronExpr = "@daily".parse().unwrap();
+        assert_eq!(spanned.expr(), &"@daily".parse().unwrap());
+        assert!(spanned.fields().is_empty());
+        assert_eq!(
+            "0 0 * * * *".parse::<SpannedCronExpr>(),
+            Err("0 0 * * * *".parse::<CronExpr>().unwrap_err())
+        );
+    }
+}
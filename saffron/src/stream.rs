@@ -0,0 +1,292 @@
+//! An asynchronous stream of the times a cron value matches, waiting for each with a clock.
+
+use crate::Cron;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+use chrono::{DateTime, Duration, Utc};
+use core::cmp;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::{FusedStream, Stream};
+
+/// A source of the current time that can wait until a later time. This is implemented for the
+/// timer of an async runtime, or a mock clock in tests.
+///
+/// # Example
+/// A clock for tokio, converting deadlines into tokio instants:
+/// ```ignore
+/// use chrono::{DateTime, Utc};
+/// use saffron::Clock;
+/// use std::time::SystemTime;
+///
+/// struct TokioClock;
+///
+/// impl Clock for TokioClock {
+///     type Sleep = tokio::time::Sleep;
+///
+///     fn now(&self) -> DateTime<Utc> {
+///         SystemTime::now().into()
+///     }
+///
+///     fn sleep_until(&self, deadline: DateTime<Utc>) -> Self::Sleep {
+///         let wait = (deadline - self.now()).to_std().unwrap_or_default();
+///         tokio::time::sleep(wait)
+///     }
+/// }
+/// ```
+pub trait Clock {
+    /// The future returned by [`sleep_until`](#tymethod.sleep_until)
+    type Sleep: Future<Output = ()>;
+
+    /// Returns the current time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Returns a future that completes once the given time is reached
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> Self::Sleep;
+}
+
+impl Cron {
+    /// Creates a stream that yields each time the cron value matches, waiting until that time
+    /// with the given clock. The first time yielded is the first match at or after the current
+    /// time. If the stream isn't polled until after a time has passed, the times missed are
+    /// skipped and the stream continues with the next match after the current time.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use futures::StreamExt;
+    ///
+    /// let cron = "*/5 * * * *".parse::<saffron::Cron>().unwrap();
+    /// let mut ticks = cron.tick_stream(TokioClock);
+    /// while let Some(time) = ticks.next().await {
+    ///     println!("Running the job scheduled for {}", time);
+    /// }
+    /// ```
+    pub fn tick_stream<C: Clock>(self, clock: C) -> TickStream<C> {
+        let done = !self.any();
+        TickStream {
+            cron: self,
+            clock,
+            last: None,
+            sleep: None,
+            done,
+        }
+    }
+}
+
+/// A stream of the times a cron value matches, yielding each once the clock reaches it.
+/// Created with [`Cron::tick_stream`].
+///
+/// [`Cron::tick_stream`]: struct.Cron.html#method.tick_stream
+pub struct TickStream<C: Clock> {
+    cron: Cron,
+    clock: C,
+    /// The last time yielded
+    last: Option<DateTime<Utc>>,
+    /// The next time to yield and the sleep until it
+    sleep: Option<PendingTick<C::Sleep>>,
+    done: bool,
+}
+
+type PendingTick<S> = (DateTime<Utc>, Pin<Box<S>>);
+
+// the sleep is the only pinned value and it's boxed, so the stream can be moved
+impl<C: Clock> Unpin for TickStream<C> {}
+
+impl<C: Clock> TickStream<C> {
+    /// Returns the underlying cron value.
+    pub fn cron(&self) -> &Cron {
+        &self.cron
+    }
+
+    /// Returns the clock used to wait for each time.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Finds the next time to yield, no earlier than the current time or the minute after the
+    /// last time yielded
+    fn next_tick(&self) -> Option<DateTime<Utc>> {
+        let now = self.clock.now();
+        let from = match self.last {
+            Some(last) => cmp::max(last.checked_add_signed(Duration::minutes(1))?, now),
+            None => now,
+        };
+
+        // the next time from is truncated to the minute, so it can be before the start
+        self.cron
+            .next_from(from)
+            .filter(|&next| next >= from)
+            .or_else(|| self.cron.next_after(from))
+    }
+}
+
+impl<C: Clock> Stream for TickStream<C> {
+    type Item = DateTime<Utc>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            match &mut this.sleep {
+                Some((tick, sleep)) => {
+                    if sleep.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+
+                    let tick = *tick;
+                    this.sleep = None;
+                    this.last = Some(tick);
+                    return Poll::Ready(Some(tick));
+                }
+                None => match this.next_tick() {
+                    Some(tick) => {
+                        let sleep = Box::pin(this.clock.sleep_until(tick));
+                        this.sleep = Some((tick, sleep));
+                    }
+                    None => this.done = true,
+                },
+            }
+        }
+    }
+}
+
+impl<C: Clock> FusedStream for TickStream<C> {
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    use chrono::TimeZone;
+    use core::cell::Cell;
+    use core::ptr;
+    use core::task::{RawWaker, RawWakerVTable, Waker};
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        // the waker doesn't use its data pointer, so it's always valid
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+
+    fn poll<C: Clock>(stream: &mut TickStream<C>) -> Poll<Option<DateTime<Utc>>> {
+        let waker = noop_waker();
+        Pin::new(stream).poll_next(&mut Context::from_waker(&waker))
+    }
+
+    /// A clock that only moves when it's told to
+    struct MockClock<'a>(&'a Cell<DateTime<Utc>>);
+
+    struct MockSleep<'a> {
+        now: &'a Cell<DateTime<Utc>>,
+        deadline: DateTime<Utc>,
+    }
+
+    impl Future for MockSleep<'_> {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+            if self.now.get() >= self.deadline {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<'a> Clock for MockClock<'a> {
+        type Sleep = MockSleep<'a>;
+
+        fn now(&self) -> DateTime<Utc> {
+            self.0.get()
+        }
+
+        fn sleep_until(&self, deadline: DateTime<Utc>) -> Self::Sleep {
+            MockSleep {
+                now: self.0,
+                deadline,
+            }
+        }
+    }
+
+    /// A clock that skips ahead to every deadline
+    struct SkippingClock(Cell<DateTime<Utc>>);
+
+    impl Clock for SkippingClock {
+        type Sleep = core::future::Ready<()>;
+
+        fn now(&self) -> DateTime<Utc> {
+            self.0.get()
+        }
+
+        fn sleep_until(&self, deadline: DateTime<Utc>) -> Self::Sleep {
+            self.0.set(cmp::max(deadline, self.0.get()));
+            core::future::ready(())
+        }
+    }
+
+    #[test]
+    fn ticks() {
+        let cron: Cron = "*/15 * * * *".parse().unwrap();
+        let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 7, 30).unwrap();
+        let mut stream = cron.tick_stream(SkippingClock(Cell::new(start)));
+        let ticks: Vec<_> = (0..3).map(|_| poll(&mut stream)).collect();
+        assert_eq!(
+            ticks,
+            [
+                Poll::Ready(Some(Utc.with_ymd_and_hms(2021, 1, 1, 0, 15, 0).unwrap())),
+                Poll::Ready(Some(Utc.with_ymd_and_hms(2021, 1, 1, 0, 30, 0).unwrap())),
+                Poll::Ready(Some(Utc.with_ymd_and_hms(2021, 1, 1, 0, 45, 0).unwrap())),
+            ]
+        );
+    }
+
+    #[test]
+    fn waits_for_ticks() {
+        let cron: Cron = "0 * * * *".parse().unwrap();
+        let now = Cell::new(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap());
+        let mut stream = cron.tick_stream(MockClock(&now));
+
+        // the current time matches
+        assert_eq!(poll(&mut stream), Poll::Ready(Some(now.get())));
+
+        now.set(Utc.with_ymd_and_hms(2021, 1, 1, 0, 59, 59).unwrap());
+        assert_eq!(poll(&mut stream), Poll::Pending);
+        now.set(Utc.with_ymd_and_hms(2021, 1, 1, 1, 0, 0).unwrap());
+        assert_eq!(poll(&mut stream), Poll::Ready(Some(now.get())));
+
+        // missed times are skipped
+        now.set(Utc.with_ymd_and_hms(2021, 1, 1, 3, 30, 0).unwrap());
+        assert_eq!(poll(&mut stream), Poll::Pending);
+        now.set(Utc.with_ymd_and_hms(2021, 1, 1, 4, 0, 0).unwrap());
+        assert_eq!(
+            poll(&mut stream),
+            Poll::Ready(Some(Utc.with_ymd_and_hms(2021, 1, 1, 4, 0, 0).unwrap()))
+        );
+    }
+
+    #[test]
+    fn never_ticks() {
+        let cron: Cron = "0 0 30 2 *".parse().unwrap();
+        let now = Cell::new(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap());
+        let mut stream = cron.tick_stream(MockClock(&now));
+        assert!(stream.is_terminated());
+        assert_eq!(poll(&mut stream), Poll::Ready(None));
+    }
+}
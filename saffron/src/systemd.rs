@@ -0,0 +1,925 @@
+//! Conversions between cron expressions and the calendar events of systemd timers, written in
+//! the `OnCalendar=` setting of a timer unit.
+//!
+//! A calendar event is written as `[weekdays] [year-month-day] [hour:minute[:second]]`, like
+//! `Mon..Fri *-*-* 09:00:00`. Each part left out matches every value, except the time which
+//! defaults to midnight. Calendar events are always converted as if they're in UTC.
+//!
+//! Cron expressions can't represent every calendar event, and the other way around:
+//!  * Events can only match a single year (`*`) and second (`00`)
+//!  * A day of the week and a day of the month must both match in an event, while a cron
+//!    expression with both day fields restricted matches either of them. Both can only be
+//!    converted if the days are the nth (`Mon *-*-08..14`) or the last (`Mon *-*~07/1`) day of
+//!    the week of the month.
+//!  * The last days of the month are written with a `~` in events (`*-*~01` for `L`), but
+//!    there's no way to write the closest weekday to a day (`W`) in an event.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::parse::{
+    CronExpr, DayOfMonth, DayOfMonthExpr, DayOfMonthOffset, DayOfWeek, DayOfWeekExpr, Exclusions,
+    Expr, ExprValue, Exprs, Hour, Last, Minute, Month, NthDay, OrsExpr, Step, ValueOutOfRangeError,
+};
+use chrono::Weekday;
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter, Write};
+
+/// A part of a calendar event that can't be converted into a cron expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Unsupported {
+    /// The event matches specific years
+    Year,
+    /// The event matches seconds other than `00`
+    Seconds,
+    /// The event is in a timezone other than UTC
+    Timezone,
+    /// The event matches last days of the month other than a single day (`~03`) or the last
+    /// week of the month with a day of the week (`Fri *-*~07/1`)
+    LastDays,
+    /// The event matches both a day of the week and a day of the month
+    DaysOfMonthAndWeek,
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Unsupported::Year => "specific years",
+            Unsupported::Seconds => "seconds other than 00",
+            Unsupported::Timezone => "timezones other than UTC",
+            Unsupported::LastDays => "ranges of the last days of the month",
+            Unsupported::DaysOfMonthAndWeek => "both days of the month and days of the week",
+        }
+        .fmt(f)
+    }
+}
+
+/// An error indicating that a calendar event failed to parse or can't be converted into a cron
+/// expression. Every error carries the byte offset in the event where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OnCalendarError {
+    /// A part of the event contains an unexpected character or is otherwise malformed.
+    InvalidSyntax {
+        /// The byte offset of the malformed part
+        at: usize,
+    },
+    /// A value is out of the range of valid values for its position.
+    ValueOutOfRange {
+        /// The byte offset of the value
+        at: usize,
+    },
+    /// The event is valid but can't be written as a cron expression.
+    Unsupported {
+        /// The part of the event that can't be converted
+        feature: Unsupported,
+        /// The byte offset of the part
+        at: usize,
+    },
+}
+
+impl OnCalendarError {
+    /// Returns the byte offset in the calendar event where the error occurred.
+    pub fn position(&self) -> usize {
+        match *self {
+            OnCalendarError::InvalidSyntax { at }
+            | OnCalendarError::ValueOutOfRange { at }
+            | OnCalendarError::Unsupported { at, .. } => at,
+        }
+    }
+}
+
+impl Display for OnCalendarError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OnCalendarError::InvalidSyntax { at } => {
+                write!(f, "Invalid calendar event at position {}", at)
+            }
+            OnCalendarError::ValueOutOfRange { at } => {
+                write!(f, "Value at position {} is out of range", at)
+            }
+            OnCalendarError::Unsupported { feature, at } => write!(
+                f,
+                "Calendar event matches {} at position {}, which can't be written as a cron expression",
+                feature, at
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OnCalendarError {}
+
+/// An error returned if a cron expression can't be written as a calendar event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NotRepresentable {
+    /// The expression matches the closest weekday to a day of the month (`15W`)
+    ClosestWeekday,
+    /// The expression matches the last weekday of the month (`LW`, `L-3W` or `LW-1`)
+    LastWeekday,
+    /// The expression matches the nth business day of the month (`3B`)
+    BusinessDay,
+    /// The expression restricts both day fields, so it matches a day matching either field
+    EitherDay,
+    /// The expression restricts the weeks of the year
+    Weeks,
+    /// The expression has a list of special day expressions (`1W,15W` or `MON#1,MON#3`)
+    Specials,
+}
+
+impl Display for NotRepresentable {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NotRepresentable::ClosestWeekday => {
+                "The closest weekday to a day of the month can't be written as a calendar event"
+            }
+            NotRepresentable::LastWeekday => {
+                "The last weekday of the month can't be written as a calendar event"
+            }
+            NotRepresentable::BusinessDay => {
+                "The nth business day of the month can't be written as a calendar event"
+            }
+            NotRepresentable::EitherDay => {
+                "Matching either a day of the month or a day of the week can't be written as a calendar event"
+            }
+            NotRepresentable::Weeks => "Weeks of the year can't be written as a calendar event",
+            NotRepresentable::Specials => {
+                "A list of special day expressions can't be written as one calendar event"
+            }
+        }
+        .fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NotRepresentable {}
+
+/// The shorthand names of calendar events and the events they expand to
+const SHORTHANDS: [(&str, &str); 9] = [
+    ("minutely", "*-*-* *:*:00"),
+    ("hourly", "*-*-* *:00:00"),
+    ("daily", "*-*-* 00:00:00"),
+    ("weekly", "Mon *-*-* 00:00:00"),
+    ("monthly", "*-*-01 00:00:00"),
+    ("yearly", "*-01-01 00:00:00"),
+    ("annually", "*-01-01 00:00:00"),
+    ("quarterly", "*-01,04,07,10-01 00:00:00"),
+    ("semiannually", "*-01,07-01 00:00:00"),
+];
+
+const WEEKDAYS: [(&str, &str, Weekday); 7] = [
+    ("Mon", "Monday", Weekday::Mon),
+    ("Tue", "Tuesday", Weekday::Tue),
+    ("Wed", "Wednesday", Weekday::Wed),
+    ("Thu", "Thursday", Weekday::Thu),
+    ("Fri", "Friday", Weekday::Fri),
+    ("Sat", "Saturday", Weekday::Sat),
+    ("Sun", "Sunday", Weekday::Sun),
+];
+
+/// Parses a calendar event into a cron expression.
+///
+/// # Example
+/// ```
+/// use saffron::systemd;
+///
+/// let expr = systemd::parse("Mon..Fri *-*-* 09:00:00").expect("Valid calendar event");
+/// assert_eq!(expr.to_string(), "0 9 * * 2-6");
+///
+/// let expr = systemd::parse("*-*~01 12:30").expect("Valid calendar event");
+/// assert_eq!(expr.to_string(), "30 12 L * *");
+/// ```
+pub fn parse(event: &str) -> Result<CronExpr, OnCalendarError> {
+    let trimmed = event.trim();
+    if let Some(&(_, expanded)) = SHORTHANDS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(trimmed))
+    {
+        return parse(expanded);
+    }
+
+    let mut parts = Parts::new(event);
+    let mut dows = None;
+    let mut date = None;
+    let mut time = None;
+
+    if let Some((at, part)) = parts.peek() {
+        if part.starts_with(|c: char| c.is_ascii_alphabetic()) && !is_utc(part) {
+            dows = Some((at, weekdays(part, at)?));
+            parts.next();
+        }
+    }
+    if let Some((at, part)) = parts.peek() {
+        if !part.contains(':') && !is_utc(part) {
+            date = Some(self::date(part, at)?);
+            parts.next();
+        }
+    }
+    if let Some((at, part)) = parts.peek() {
+        if part.contains(':') {
+            time = Some(self::time(part, at)?);
+            parts.next();
+        }
+    }
+    match parts.next() {
+        None => {}
+        Some((_, part)) if is_utc(part) => {}
+        Some((at, part)) if part.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+            return Err(OnCalendarError::Unsupported {
+                feature: Unsupported::Timezone,
+                at,
+            })
+        }
+        Some((at, _)) => return Err(OnCalendarError::InvalidSyntax { at }),
+    }
+    if let Some((at, _)) = parts.next() {
+        return Err(OnCalendarError::InvalidSyntax { at });
+    }
+    if dows.is_none() && date.is_none() && time.is_none() {
+        return Err(OnCalendarError::InvalidSyntax { at: event.len() });
+    }
+
+    let (months, days) = date.unwrap_or((Expr::All, Days::All));
+    let (hours, minutes) = time.unwrap_or_else(|| {
+        (
+            Expr::Many(Exprs::new(OrsExpr::One(<Hour as ExprValue>::min()))),
+            Expr::Many(Exprs::new(OrsExpr::One(<Minute as ExprValue>::min()))),
+        )
+    });
+
+    let (doms, dows) = match (days, dows) {
+        (Days::All, None) => (DayOfMonthExpr::All, DayOfWeekExpr::All),
+        (days, None) => (days.into_expr(), DayOfWeekExpr::All),
+        (Days::All, Some((_, dows))) => (DayOfMonthExpr::All, DayOfWeekExpr::Many(dows)),
+        (days, Some((at, dows))) => {
+            let day = match dows {
+                Exprs {
+                    first: OrsExpr::One(day),
+                    tail,
+                } if tail.is_empty() => Some(day),
+                _ => None,
+            };
+            match (day, days) {
+                (Some(day), Days::LastWeek) => (DayOfMonthExpr::Ignored, DayOfWeekExpr::Last(day)),
+                (Some(day), Days::Many(exprs)) => match week_of_month(&exprs) {
+                    Some(nth) => (DayOfMonthExpr::Ignored, DayOfWeekExpr::Nth(day, nth)),
+                    None => {
+                        return Err(OnCalendarError::Unsupported {
+                            feature: Unsupported::DaysOfMonthAndWeek,
+                            at,
+                        })
+                    }
+                },
+                _ => {
+                    return Err(OnCalendarError::Unsupported {
+                        feature: Unsupported::DaysOfMonthAndWeek,
+                        at,
+                    })
+                }
+            }
+        }
+    };
+
+    Ok(CronExpr {
+        minutes,
+        hours,
+        doms,
+        months,
+        dows,
+        weeks: Expr::All,
+        shortcut: None,
+        exclusions: Exclusions::default(),
+        days_and: false,
+    })
+}
+
+/// Writes a cron expression as a calendar event.
+///
+/// # Example
+/// ```
+/// use saffron::parse::CronExpr;
+/// use saffron::systemd::{self, NotRepresentable};
+///
+/// let expr: CronExpr = "*/15 9-17 * * MON-FRI".parse().expect("Valid cron expression");
+/// assert_eq!(
+///     systemd::to_on_calendar(&expr).unwrap(),
+///     "Mon..Fri *-*-* 09..17:00/15:00"
+/// );
+///
+/// let expr: CronExpr = "0 0 15W * *".parse().expect("Valid cron expression");
+/// assert_eq!(
+///     systemd::to_on_calendar(&expr),
+///     Err(NotRepresentable::ClosestWeekday)
+/// );
+/// ```
+pub fn to_on_calendar(expr: &CronExpr) -> Result<String, NotRepresentable> {
+    // normalizing the expression leaves ascending values and ranges that don't wrap around
+    let expr = expr.normalize();
+
+    let restricted_doms = !matches!(expr.doms, DayOfMonthExpr::All | DayOfMonthExpr::Ignored);
+    let restricted_dows = !matches!(expr.dows, DayOfWeekExpr::All | DayOfWeekExpr::Ignored);
+    if restricted_doms && restricted_dows {
+        return Err(NotRepresentable::EitherDay);
+    }
+    if expr.weeks != Expr::All {
+        return Err(NotRepresentable::Weeks);
+    }
+
+    let mut out = String::new();
+    let mut days = String::from("-*");
+    match &expr.dows {
+        DayOfWeekExpr::All | DayOfWeekExpr::Ignored => {}
+        DayOfWeekExpr::Last(day) => {
+            let _ = write!(out, "{} ", weekday_name(*day));
+            days = String::from("~07/1");
+        }
+        DayOfWeekExpr::Nth(day, nth) => {
+            let first = (u8::from(*nth) - 1) * 7 + 1;
+            let _ = write!(out, "{} ", weekday_name(*day));
+            days = String::new();
+            // the fifth week of the month is cut short by the end of the month
+            let _ = write!(
+                days,
+                "-{:02}..{:02}",
+                first,
+                (first + 6).min(DayOfMonth::MAX)
+            );
+        }
+        DayOfWeekExpr::Many(exprs) => {
+            write_weekdays(&mut out, exprs);
+            out.push(' ');
+        }
+        DayOfWeekExpr::Specials(_) => return Err(NotRepresentable::Specials),
+    }
+
+    match &expr.doms {
+        DayOfMonthExpr::All | DayOfMonthExpr::Ignored => {}
+        DayOfMonthExpr::ClosestWeekday(_) | DayOfMonthExpr::ClosestWeekdays(_) => {
+            return Err(NotRepresentable::ClosestWeekday)
+        }
+        DayOfMonthExpr::BusinessDay(_) => return Err(NotRepresentable::BusinessDay),
+        DayOfMonthExpr::Last(Last::Weekday)
+        | DayOfMonthExpr::Last(Last::OffsetWeekday(_))
+        | DayOfMonthExpr::Last(Last::WeekdayOffset(_)) => {
+            return Err(NotRepresentable::LastWeekday)
+        }
+        DayOfMonthExpr::Last(Last::Day) => days = String::from("~01"),
+        DayOfMonthExpr::Last(Last::Offset(offset)) => {
+            days = String::new();
+            let _ = write!(days, "~{:02}", u8::from(*offset) + 1);
+        }
+        DayOfMonthExpr::Many(exprs) => {
+            days = String::from("-");
+            write_field(&mut days, &Expr::Many(exprs.clone()));
+        }
+        DayOfMonthExpr::Specials(_) => return Err(NotRepresentable::Specials),
+    }
+
+    out.push_str("*-");
+    write_field(&mut out, &expr.months);
+    out.push_str(&days);
+    out.push(' ');
+    write_field(&mut out, &expr.hours);
+    out.push(':');
+    write_field(&mut out, &expr.minutes);
+    out.push_str(":00");
+    Ok(out)
+}
+
+/// Returns the value of a field as written in an expression
+fn written<E: ExprValue + Copy>(value: E) -> u8
+where
+    u8: From<E>,
+{
+    // values of fields starting at 1 are zero based
+    u8::from(value) + E::MIN
+}
+
+/// Writes a normalized field as a list of values, ranges, and repetitions
+fn write_field<E: ExprValue + Copy>(out: &mut String, expr: &Expr<E>)
+where
+    u8: From<E>,
+{
+    let exprs = match expr {
+        Expr::All => return out.push('*'),
+        Expr::Many(exprs) => exprs,
+    };
+
+    for (i, expr) in exprs.iter().enumerate() {
+        if i != 0 {
+            out.push(',');
+        }
+        let _ = match *expr {
+            OrsExpr::One(value) => write!(out, "{:02}", written(value)),
+            OrsExpr::Range(start, end) => {
+                write!(out, "{:02}..{:02}", written(start), written(end))
+            }
+            OrsExpr::Step { start, end, step } if written(end) == E::MAX => {
+                write!(out, "{:02}/{}", written(start), step)
+            }
+            OrsExpr::Step { start, end, step } => {
+                write!(out, "{:02}..{:02}/{}", written(start), written(end), step)
+            }
+        };
+    }
+}
+
+fn weekday_name(day: DayOfWeek) -> &'static str {
+    let day = Weekday::from(day);
+    WEEKDAYS
+        .iter()
+        .find(|&&(_, _, weekday)| weekday == day)
+        .map(|&(name, _, _)| name)
+        .expect("Every weekday has a name")
+}
+
+/// Writes a normalized day of the week field. Events can't repeat days of the week, so steps
+/// are written as lists of days.
+fn write_weekdays(out: &mut String, exprs: &Exprs<DayOfWeek>) {
+    let mut first = true;
+    let mut push = |out: &mut String, item: &dyn Display| {
+        if !first {
+            out.push(',');
+        }
+        first = false;
+        let _ = write!(out, "{}", item);
+    };
+
+    for expr in exprs {
+        match *expr {
+            OrsExpr::One(day) => push(out, &weekday_name(day)),
+            OrsExpr::Range(start, end) => push(
+                out,
+                &format_args!("{}..{}", weekday_name(start), weekday_name(end)),
+            ),
+            OrsExpr::Step { start, end, step } => {
+                let mut value = u8::from(start);
+                while value <= u8::from(end) {
+                    let day = DayOfWeek::try_from(value + 1).expect("Value is a day of the week");
+                    push(out, &weekday_name(day));
+                    value += u8::from(step);
+                }
+            }
+        }
+    }
+}
+
+fn is_utc(part: &str) -> bool {
+    part.eq_ignore_ascii_case("UTC")
+}
+
+/// The whitespace separated parts of an event and their byte offsets
+struct Parts<'a> {
+    event: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parts<'a> {
+    fn new(event: &'a str) -> Self {
+        Self { event, offset: 0 }
+    }
+
+    fn peek(&self) -> Option<(usize, &'a str)> {
+        let rest = &self.event[self.offset..];
+        let start = rest.len() - rest.trim_start().len();
+        let rest = &rest[start..];
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if len == 0 {
+            None
+        } else {
+            Some((self.offset + start, &rest[..len]))
+        }
+    }
+
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let (at, part) = self.peek()?;
+        self.offset = at + part.len();
+        Some((at, part))
+    }
+}
+
+/// An item in a comma separated list of values
+struct Item {
+    at: usize,
+    /// The first value, or none for a `*`
+    start: Option<u8>,
+    end: Option<u8>,
+    step: Option<u8>,
+}
+
+/// Splits a comma separated list into its items, reading the values of each item with the
+/// given function.
+fn items(
+    list: &str,
+    at: usize,
+    value: impl Fn(&str) -> Option<u8>,
+) -> Result<Vec<Item>, OnCalendarError> {
+    let mut items = Vec::new();
+    let mut offset = at;
+    for item in list.split(',') {
+        let at = offset;
+        offset += item.len() + 1;
+
+        let (range, step) = match item.find('/') {
+            Some(slash) => {
+                let step = item[slash + 1..]
+                    .parse::<u8>()
+                    .map_err(|_| OnCalendarError::InvalidSyntax { at: at + slash + 1 })?;
+                (&item[..slash], Some(step))
+            }
+            None => (item, None),
+        };
+        let (start, end) = match range.find("..") {
+            Some(dots) => (&range[..dots], Some(&range[dots + 2..])),
+            None => (range, None),
+        };
+
+        let read = |s: &str, at: usize| value(s).ok_or(OnCalendarError::InvalidSyntax { at });
+        let start = match start {
+            "*" if end.is_none() => None,
+            start => Some(read(start, at)?),
+        };
+        let end = match end {
+            Some(end) => Some(read(end, at + range.len() - end.len())?),
+            None => None,
+        };
+        items.push(Item {
+            at,
+            start,
+            end,
+            step,
+        });
+    }
+    Ok(items)
+}
+
+/// Converts the items of a list into expressions of a field, or none if the list is just `*`
+fn exprs<E>(items: Vec<Item>) -> Result<Option<Exprs<E>>, OnCalendarError>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Copy,
+    Step<E>: TryFrom<u8, Error = ValueOutOfRangeError>,
+{
+    let mut exprs = Vec::with_capacity(items.len());
+    for item in items {
+        let out_of_range = OnCalendarError::ValueOutOfRange { at: item.at };
+        let value = |value: u8| E::try_from(value).map_err(|_| out_of_range.clone());
+        let start = match item.start {
+            Some(start) => value(start)?,
+            None if item.step.is_none() => return Ok(None),
+            None => E::min(),
+        };
+        let end = match item.end {
+            Some(end) if end < item.start.unwrap_or(E::MIN) => return Err(out_of_range),
+            Some(end) => Some(value(end)?),
+            None => None,
+        };
+        exprs.push(match (end, item.step) {
+            (None, None) => OrsExpr::One(start),
+            (Some(end), None) => OrsExpr::Range(start, end),
+            (end, Some(step)) => OrsExpr::Step {
+                start,
+                end: end.unwrap_or_else(E::max),
+                step: Step::try_from(step).map_err(|_| out_of_range.clone())?,
+            },
+        });
+    }
+
+    let mut exprs = exprs.into_iter();
+    let mut many = Exprs::new(exprs.next().expect("Lists have at least one item"));
+    many.tail.extend(exprs);
+    Ok(Some(many))
+}
+
+/// Parses a field of numbers
+fn field<E>(list: &str, at: usize) -> Result<Expr<E>, OnCalendarError>
+where
+    E: ExprValue + TryFrom<u8, Error = ValueOutOfRangeError> + Copy,
+    Step<E>: TryFrom<u8, Error = ValueOutOfRangeError>,
+{
+    let items = items(list, at, |value| value.parse().ok())?;
+    Ok(exprs(items)?.map_or(Expr::All, Expr::Many))
+}
+
+fn weekdays(list: &str, at: usize) -> Result<Exprs<DayOfWeek>, OnCalendarError> {
+    let items = items(list, at, |value| {
+        WEEKDAYS
+            .iter()
+            .find(|(short, long, _)| {
+                short.eq_ignore_ascii_case(value) || long.eq_ignore_ascii_case(value)
+            })
+            .map(|&(_, _, weekday)| weekday.number_from_sunday() as u8)
+    })?;
+
+    // ranges wrapping around the end of the week, like Sat..Mon, are split in two
+    let mut split = Vec::with_capacity(items.len());
+    for item in items {
+        if item.step.is_some() {
+            return Err(OnCalendarError::InvalidSyntax { at: item.at });
+        }
+        match (item.start, item.end) {
+            (None, _) => return Err(OnCalendarError::InvalidSyntax { at: item.at }),
+            (Some(start), Some(end)) if end < start => {
+                split.push(Item {
+                    end: Some(DayOfWeek::MAX),
+                    ..item
+                });
+                split.push(Item {
+                    at: item.at,
+                    start: Some(DayOfWeek::MIN),
+                    end: Some(end).filter(|&end| end != DayOfWeek::MIN),
+                    step: None,
+                });
+            }
+            _ => split.push(item),
+        }
+    }
+    Ok(exprs(split)?.expect("Weekdays can't be `*`"))
+}
+
+/// The day of the month part of a date
+enum Days {
+    All,
+    Many(Exprs<DayOfMonth>),
+    Last(Last),
+    /// The last seven days of the month, matching the last of a day of the week
+    LastWeek,
+}
+
+impl Days {
+    fn into_expr(self) -> DayOfMonthExpr {
+        match self {
+            Days::All => DayOfMonthExpr::All,
+            Days::Many(exprs) => DayOfMonthExpr::Many(exprs),
+            Days::Last(last) => DayOfMonthExpr::Last(last),
+            Days::LastWeek => unreachable!("The last week is only used with a weekday"),
+        }
+    }
+}
+
+/// Returns the nth week of the month if the days are the seven days of the week
+fn week_of_month(days: &Exprs<DayOfMonth>) -> Option<NthDay> {
+    match *days {
+        Exprs {
+            first: OrsExpr::Range(start, end),
+            ref tail,
+        } if tail.is_empty()
+            && u8::from(start) % 7 == 0
+            && written(end) == (written(start) + 6).min(DayOfMonth::MAX) =>
+        {
+            NthDay::try_from(u8::from(start) / 7 + 1).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Parses a date, `[year-]month-day` or `[year-]month~day`
+fn date(part: &str, at: usize) -> Result<(Expr<Month>, Days), OnCalendarError> {
+    let day_at = match part.rfind(&['-', '~'][..]) {
+        Some(separator) => separator,
+        None => return Err(OnCalendarError::InvalidSyntax { at }),
+    };
+    let last = part[day_at..].starts_with('~');
+    let (year_month, day) = (&part[..day_at], &part[day_at + 1..]);
+    let day_at = at + day_at + 1;
+
+    let (month, month_at) = match year_month.find('-') {
+        Some(separator) => {
+            if &year_month[..separator] != "*" {
+                return Err(OnCalendarError::Unsupported {
+                    feature: Unsupported::Year,
+                    at,
+                });
+            }
+            (&year_month[separator + 1..], at + separator + 1)
+        }
+        None => (year_month, at),
+    };
+    if month.contains('~') {
+        return Err(OnCalendarError::InvalidSyntax { at: month_at });
+    }
+    let months = field::<Month>(month, month_at)?;
+
+    if !last {
+        let days = match field::<DayOfMonth>(day, day_at)? {
+            Expr::All => Days::All,
+            Expr::Many(exprs) => Days::Many(exprs),
+        };
+        return Ok((months, days));
+    }
+
+    let unsupported = OnCalendarError::Unsupported {
+        feature: Unsupported::LastDays,
+        at: day_at,
+    };
+    let items = items(day, day_at, |value| value.parse().ok())?;
+    let days = match &*items {
+        [Item {
+            start: Some(7),
+            end: None,
+            step: Some(1),
+            ..
+        }] => Days::LastWeek,
+        [Item {
+            start: Some(1),
+            end: None,
+            step: None,
+            ..
+        }] => Days::Last(Last::Day),
+        &[Item {
+            start: Some(day),
+            end: None,
+            step: None,
+            at,
+        }] => match day
+            .checked_sub(1)
+            .and_then(|offset| DayOfMonthOffset::try_from(offset).ok())
+        {
+            Some(offset) => Days::Last(Last::Offset(offset)),
+            None => return Err(OnCalendarError::ValueOutOfRange { at }),
+        },
+        _ => return Err(unsupported),
+    };
+    Ok((months, days))
+}
+
+/// Parses a time, `hour:minute[:second]`
+fn time(part: &str, at: usize) -> Result<(Expr<Hour>, Expr<Minute>), OnCalendarError> {
+    let mut fields = part.splitn(3, ':');
+    let hour = fields.next().unwrap_or("");
+    let minute = fields.next().unwrap_or("");
+    let minute_at = at + hour.len() + 1;
+    let hours = field::<Hour>(hour, at)?;
+    let minutes = field::<Minute>(minute, minute_at)?;
+
+    if let Some(second) = fields.next() {
+        let second_at = minute_at + minute.len() + 1;
+        match second.parse::<u8>() {
+            Ok(0) => {}
+            Ok(60..=u8::MAX) => return Err(OnCalendarError::ValueOutOfRange { at: second_at }),
+            _ if second.is_empty() => return Err(OnCalendarError::InvalidSyntax { at: second_at }),
+            _ => {
+                return Err(OnCalendarError::Unsupported {
+                    feature: Unsupported::Seconds,
+                    at: second_at,
+                })
+            }
+        }
+    }
+    Ok((hours, minutes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Dialect;
+
+    #[track_caller]
+    fn assert_parse(event: &str, cron: &str) {
+        let expected: CronExpr = cron.parse().expect("Valid cron expression");
+        assert_eq!(parse(event), Ok(expected), "{}", event);
+    }
+
+    #[track_caller]
+    fn assert_event(cron: &str, event: &str) {
+        let expr: CronExpr = cron.parse().expect("Valid cron expression");
+        assert_eq!(to_on_calendar(&expr).as_deref(), Ok(event), "{}", cron);
+    }
+
+    #[test]
+    fn parse_events() {
+        assert_parse("*-*-* 09:00:00", "0 9 * * *");
+        assert_parse("Mon..Fri *-*-* 09:00:00", "0 9 * * MON-FRI");
+        assert_parse("Sat,Sun 10:30", "30 10 * * SAT,SUN");
+        assert_parse("Fri..Mon", "0 0 * * FRI-SAT,SUN-MON");
+        assert_parse("Tuesday", "0 0 * * TUE");
+        assert_parse("Mon..Sun", "0 0 * * MON-SAT,SUN");
+        assert_parse("*-*-01 00:00", "0 0 1 * *");
+        assert_parse("01-01 00:00", "0 0 1 JAN *");
+        assert_parse("*-01,07-01..07 12:00 UTC", "0 12 1-7 JAN,JUL *");
+        assert_parse("*-*-* *:0/15", "*/15 * * * *");
+        assert_parse("*-*-* 08..17:00..30/10", "0-30/10 8-17 * * *");
+        assert_parse("*-*-1/2", "0 0 1/2 * *");
+    }
+
+    #[test]
+    fn parse_last_days() {
+        assert_parse("*-*~01", "0 0 L * *");
+        assert_parse("*-02~03 00:00", "0 0 L-2 FEB *");
+        assert_parse("Fri *-*~07/1", "0 0 ? * FRIL");
+        assert_parse("Mon *-*-08..14 09:00", "0 9 ? * MON#2");
+        assert_parse("Thu *-*-29..31", "0 0 ? * THU#5");
+    }
+
+    #[test]
+    fn parse_shorthands() {
+        assert_parse("minutely", "* * * * *");
+        assert_parse("hourly", "0 * * * *");
+        assert_parse("Daily", "0 0 * * *");
+        assert_parse("weekly", "0 0 * * MON");
+        assert_parse("quarterly", "0 0 1 1,4,7,10 *");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |event: &str| parse(event).unwrap_err();
+        assert_eq!(
+            err("2024-*-* 00:00"),
+            OnCalendarError::Unsupported {
+                feature: Unsupported::Year,
+                at: 0,
+            }
+        );
+        assert_eq!(
+            err("*-*-* 00:00:30"),
+            OnCalendarError::Unsupported {
+                feature: Unsupported::Seconds,
+                at: 12,
+            }
+        );
+        assert_eq!(
+            err("*-*-* 00:00 Europe/Berlin"),
+            OnCalendarError::Unsupported {
+                feature: Unsupported::Timezone,
+                at: 12,
+            }
+        );
+        assert_eq!(
+            err("Mon *-*-13"),
+            OnCalendarError::Unsupported {
+                feature: Unsupported::DaysOfMonthAndWeek,
+                at: 0,
+            }
+        );
+        assert_eq!(
+            err("*-*~02..05"),
+            OnCalendarError::Unsupported {
+                feature: Unsupported::LastDays,
+                at: 4,
+            }
+        );
+        assert_eq!(
+            err("*-*-* 24:00"),
+            OnCalendarError::ValueOutOfRange { at: 6 }
+        );
+        assert_eq!(err("*-13-01"), OnCalendarError::ValueOutOfRange { at: 2 });
+        assert_eq!(err("Mo..Fr"), OnCalendarError::InvalidSyntax { at: 0 });
+        assert_eq!(err("*-*-* 0x:00"), OnCalendarError::InvalidSyntax { at: 6 });
+        assert_eq!(err(""), OnCalendarError::InvalidSyntax { at: 0 });
+    }
+
+    #[test]
+    fn events() {
+        assert_event("* * * * *", "*-*-* *:*:00");
+        assert_event("0 9 * * MON-FRI", "Mon..Fri *-*-* 09:00:00");
+        assert_event("30 10 * * SAT,SUN", "Sun,Sat *-*-* 10:30:00");
+        assert_event("*/15 * * * *", "*-*-* *:00/15:00");
+        assert_event("0 22-2 * * *", "*-*-* 00..02,22..23:00:00");
+        assert_event("0 0 1,15 JAN-MAR *", "*-01..03-01,15 00:00:00");
+        assert_event("0 0 * * */2", "Sun,Tue,Thu,Sat *-*-* 00:00:00");
+        assert_event("0 0 L * *", "*-*~01 00:00:00");
+        assert_event("0 0 L-2 FEB *", "*-02~03 00:00:00");
+        assert_event("0 0 ? * FRIL", "Fri *-*~07/1 00:00:00");
+        assert_event("0 9 ? * MON#2", "Mon *-*-08..14 09:00:00");
+        assert_event("@weekly", "Sun *-*-* 00:00:00");
+    }
+
+    #[test]
+    fn not_representable() {
+        let err = |cron: &str| to_on_calendar(&cron.parse().unwrap()).unwrap_err();
+        assert_eq!(err("0 0 15W * *"), NotRepresentable::ClosestWeekday);
+        assert_eq!(err("0 0 3B * *"), NotRepresentable::BusinessDay);
+        assert_eq!(err("0 0 LW * *"), NotRepresentable::LastWeekday);
+        assert_eq!(err("0 0 L-3W * *"), NotRepresentable::LastWeekday);
+        assert_eq!(err("0 0 LW-1 * *"), NotRepresentable::LastWeekday);
+        assert_eq!(err("0 0 13 * FRI"), NotRepresentable::EitherDay);
+        assert_eq!(err("0 0 L * MON#1"), NotRepresentable::EitherDay);
+
+        let expr = CronExpr::from_str_with("0 0 * * MON W1-26", Dialect::Extended).unwrap();
+        assert_eq!(to_on_calendar(&expr), Err(NotRepresentable::Weeks));
+    }
+
+    #[test]
+    fn round_trips() {
+        for &cron in &[
+            "0 9 * * MON-FRI",
+            "*/15 8-17 1,15 * *",
+            "0 0 L-2 FEB *",
+            "0 0 ? * FRIL",
+            "5 4 ? * SUN#3",
+            "0 0 ? * WED#5",
+            "0 12 1/2 JAN-JUN *",
+        ] {
+            let expr: CronExpr = cron.parse().unwrap();
+            let event = to_on_calendar(&expr).unwrap();
+            assert_eq!(
+                crate::Cron::new(parse(&event).unwrap()),
+                crate::Cron::new(expr),
+                "{} -> {}",
+                cron,
+                event
+            );
+        }
+    }
+}
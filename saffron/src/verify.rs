@@ -0,0 +1,220 @@
+//! A self-check of the ways a cron value matches times against each other, meant to be run by
+//! tests and fuzzers.
+
+use crate::{minute_floor, next_minute, Cron};
+
+use chrono::{DateTime, Utc};
+use core::fmt::{self, Display, Formatter};
+use core::ops::Range;
+
+/// An inconsistency between the ways a cron value matches times, found by
+/// [`Cron::verify_consistency`].
+///
+/// [`Cron::verify_consistency`]: struct.Cron.html#method.verify_consistency
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Inconsistency {
+    /// The time is contained in the cron value, but searching for the next time skipped over it
+    Skipped {
+        time: DateTime<Utc>,
+        next: Option<DateTime<Utc>>,
+    },
+    /// Searching for the next time found a time that isn't a minute contained in the cron value
+    NotContained { time: DateTime<Utc> },
+    /// The time is contained in the cron value, but [`Cron::any`] returned false
+    ///
+    /// [`Cron::any`]: struct.Cron.html#method.any
+    NotAny { time: DateTime<Utc> },
+    /// Iterating over the window yielded a different time than searching for the next time
+    Iterator {
+        expected: Option<DateTime<Utc>>,
+        found: Option<DateTime<Utc>>,
+    },
+    /// Counting the times in the window returned a different count than visiting each time
+    Count { expected: u64, found: u64 },
+}
+
+impl Display for Inconsistency {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Inconsistency::Skipped {
+                time,
+                next: Some(next),
+            } => write!(
+                f,
+                "{} is matched but the next time found was {}",
+                time, next
+            ),
+            Inconsistency::Skipped { time, next: None } => {
+                write!(f, "{} is matched but no next time was found", time)
+            }
+            Inconsistency::NotContained { time } => {
+                write!(f, "{} was found as the next time but isn't matched", time)
+            }
+            Inconsistency::NotAny { time } => {
+                write!(f, "{} is matched but the cron value never matches", time)
+            }
+            Inconsistency::Iterator { expected, found } => write!(
+                f,
+                "Expected the iterator to yield {:?} but found {:?}",
+                expected, found
+            ),
+            Inconsistency::Count { expected, found } => write!(
+                f,
+                "Expected {} times to be counted but found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Inconsistency {}
+
+impl Cron {
+    /// Checks that [`contains`], [`any`], [`next_from`], [`iter`], and [`count_between`] agree on
+    /// the times matched in the window, returning the first inconsistency found. Every minute in
+    /// the window is visited, so the window should be kept to days or months.
+    ///
+    /// [`contains`]: #method.contains
+    /// [`any`]: #method.any
+    /// [`next_from`]: #method.next_from
+    /// [`iter`]: #method.iter
+    /// [`count_between`]: #method.count_between
+    ///
+    /// # Example
+    /// ```
+    /// use saffron::Cron;
+    /// use chrono::prelude::*;
+    ///
+    /// let cron = "0 0 LW * ?".parse::<Cron>().expect("Couldn't parse expression!");
+    /// let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+    /// let end = Utc.with_ymd_and_hms(2021, 3, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(cron.verify_consistency(start..end), Ok(()));
+    /// ```
+    pub fn verify_consistency(&self, window: Range<DateTime<Utc>>) -> Result<(), Inconsistency> {
+        // like the iterator bounds, both dates are truncated to the minute
+        let start = minute_floor(window.start);
+        let end = minute_floor(window.end);
+
+        let any = self.any();
+        let mut iter = self.clone().iter(start..end);
+        let mut next = self.next_from(start).filter(|&next| next < end);
+        let mut count = 0;
+        let mut time = start;
+        while time < end {
+            let contained = self.contains(time);
+            if contained && !any {
+                return Err(Inconsistency::NotAny { time });
+            }
+
+            match next {
+                Some(next_time) if next_time == time => {
+                    if !contained {
+                        return Err(Inconsistency::NotContained { time });
+                    }
+
+                    let found = iter.next();
+                    if found != Some(time) {
+                        return Err(Inconsistency::Iterator {
+                            expected: Some(time),
+                            found,
+                        });
+                    }
+
+                    count += 1;
+                    next = self.next_after(time).filter(|&next| next < end);
+                }
+                _ if contained => return Err(Inconsistency::Skipped { time, next }),
+                _ => {}
+            }
+
+            time = match next_minute(time) {
+                Some(time) => time,
+                None => break,
+            };
+        }
+
+        // the search found a time that the walk over every minute never reached
+        if let Some(time) = next {
+            return Err(Inconsistency::NotContained { time });
+        }
+
+        if let Some(found) = iter.next() {
+            return Err(Inconsistency::Iterator {
+                expected: None,
+                found: Some(found),
+            });
+        }
+
+        let found = self.count_between(start, end);
+        if found != count {
+            return Err(Inconsistency::Count {
+                expected: count,
+                found,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cron, CronOptions, DayPolicy};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn consistent() {
+        let windows = [
+            (
+                Utc.with_ymd_and_hms(2020, 1, 25, 0, 0, 30).unwrap(),
+                Utc.with_ymd_and_hms(2020, 3, 5, 0, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2021, 11, 20, 0, 0, 30).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 10, 0, 0, 0).unwrap(),
+            ),
+        ];
+        for &cron in &[
+            "* * * * *",
+            "*/7 9-17 * * *",
+            "0,30 */5 1,15 * *",
+            "59 23 L * ?",
+            "0 12 L-3 * ?",
+            "0 0 LW * ?",
+            "0 0 L-2W * ?",
+            "0 0 1W * ?",
+            "0 0 31W * ?",
+            "0 0 3B * ?",
+            "0 0 23B * *",
+            "0 0 LW-1 * ?",
+            "0 0 LW-22 * *",
+            "0 0 ? * 6L",
+            "0 0 ? * MON#5",
+            "0 0 1W,15W,L * ?",
+            "0 0 1-7W * ?",
+            "0 0 29-31W * ?",
+            "0 0 ? * MON#1,MON#3,5L",
+            "0 0 13 * FRI",
+            "0 0 29 2 *",
+            "0 0 30 2 *",
+            "50-10 22-2 * * *",
+        ] {
+            let expr = cron.parse().unwrap();
+            let mut options = CronOptions::new();
+            options.day_policy = DayPolicy::And;
+            let and = Cron::with_options(cron.parse().unwrap(), options).unwrap();
+            for cron_value in [Cron::new(expr), and].iter() {
+                for &(start, end) in &windows {
+                    assert_eq!(
+                        cron_value.verify_consistency(start..end),
+                        Ok(()),
+                        "{}",
+                        cron
+                    );
+                }
+            }
+        }
+    }
+}